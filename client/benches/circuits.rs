@@ -0,0 +1,73 @@
+// client/benches/circuits.rs
+//
+// Constraint counts and synthesis time for the circuit templates.
+// Run with `cargo bench --bench circuits`; counts are printed before each
+// group so regressions in circuit size show up next to the timings.
+
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use scoria_client::core::zkp::circuits::{
+    attention::AttentionCircuit,
+    conv2d::{Conv2dCircuit, Conv2dShape},
+    dense::DenseCircuit,
+};
+
+const FRAC_BITS: u32 = 8;
+const BITS: u32 = 24;
+
+fn constraints<C: ConstraintSynthesizer<Fr>>(circuit: C) -> usize {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    circuit.generate_constraints(cs.clone()).unwrap();
+    cs.num_constraints()
+}
+
+fn bench_template<C, F>(c: &mut Criterion, group: &str, cases: &[(String, F)])
+where
+    C: ConstraintSynthesizer<Fr>,
+    F: Fn() -> C,
+{
+    let mut group = c.benchmark_group(group);
+    group.sample_size(10);
+    for (label, build) in cases {
+        eprintln!("{label}: {} constraints", constraints(build()));
+        group.bench_function(BenchmarkId::from_parameter(label), |b| b.iter(|| constraints(build())));
+    }
+    group.finish();
+}
+
+fn bench_circuits(c: &mut Criterion) {
+    let dense: Vec<_> = [(64, 16), (256, 64), (784, 128)]
+        .into_iter()
+        .map(|(i, o)| (format!("{i}x{o}"), move || DenseCircuit::blank(i, o, FRAC_BITS, BITS, true)))
+        .collect();
+    bench_template(c, "dense", &dense);
+
+    let conv: Vec<_> = [(1, 8, 28), (8, 16, 14), (16, 32, 7)]
+        .into_iter()
+        .map(|(cin, cout, hw)| {
+            let shape = Conv2dShape {
+                in_channels: cin,
+                out_channels: cout,
+                height: hw,
+                width: hw,
+                kernel: 3,
+                stride: 1,
+                padding: 1,
+            };
+            (format!("{cin}->{cout}@{hw}x{hw}"), move || Conv2dCircuit::blank(shape, FRAC_BITS, BITS, true))
+        })
+        .collect();
+    bench_template(c, "conv2d_3x3", &conv);
+
+    let attention: Vec<_> = [(8, 16, 8), (16, 32, 16), (32, 64, 32)]
+        .into_iter()
+        .map(|(seq, dim, head)| {
+            (format!("seq{seq}_d{dim}_h{head}"), move || AttentionCircuit::blank(seq, dim, head, FRAC_BITS, BITS))
+        })
+        .collect();
+    bench_template(c, "attention", &attention);
+}
+
+criterion_group!(benches, bench_circuits);
+criterion_main!(benches);
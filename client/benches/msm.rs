@@ -0,0 +1,53 @@
+// client/benches/msm.rs
+//
+// Groth16 MSM: CPU arkworks vs the wgpu compute path.
+// Run with `cargo bench --bench msm`; the wgpu group is skipped when no
+// adapter is available.
+
+use ark_bn254::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::UniformRand;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use scoria_client::core::zkp::wgpu_msm::WgpuMsm;
+
+const SIZES: [usize; 3] = [1 << 10, 1 << 12, 1 << 14];
+
+fn inputs(n: usize) -> (Vec<G1Affine>, Vec<Fr>) {
+    let mut rng = rand::thread_rng();
+    let bases = G1Projective::normalize_batch(&(0..n).map(|_| G1Projective::rand(&mut rng)).collect::<Vec<_>>());
+    let scalars = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+    (bases, scalars)
+}
+
+fn bench_msm(c: &mut Criterion) {
+    let gpu = match WgpuMsm::new() {
+        Ok(gpu) => Some(gpu),
+        Err(e) => {
+            eprintln!("wgpu unavailable, benchmarking CPU only: {}", e);
+            None
+        }
+    };
+
+    let mut group = c.benchmark_group("g1_msm");
+    group.sample_size(10);
+    for n in SIZES {
+        let (bases, scalars) = inputs(n);
+        group.throughput(Throughput::Elements(n as u64));
+
+        group.bench_with_input(BenchmarkId::new("arkworks_cpu", n), &n, |b, _| {
+            b.iter(|| G1Projective::msm(&bases, &scalars).unwrap())
+        });
+
+        if let Some(gpu) = &gpu {
+            // Sanity check before timing
+            assert_eq!(gpu.msm(&bases, &scalars).unwrap(), G1Projective::msm(&bases, &scalars).unwrap());
+            group.bench_with_input(BenchmarkId::new(format!("wgpu/{}", gpu.adapter_name()), n), &n, |b, _| {
+                b.iter(|| gpu.msm(&bases, &scalars).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_msm);
+criterion_main!(benches);
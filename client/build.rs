@@ -0,0 +1,54 @@
+// client/build.rs
+
+use std::{env, path::PathBuf, process::Command};
+
+const HIP_KERNELS: &str = "src/hardware/rocm/kernels.hip";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_ROCM").is_some() {
+        build_rocm_kernels();
+    }
+}
+
+/// Compile the HIP kernels and their `extern "C"` launchers into a static
+/// library linked into `rocm` builds.
+///
+/// `ROCM_PATH` (default `/opt/rocm`) locates hipcc and libamdhip64;
+/// `SCORIA_HIP_ARCH` restricts code generation to e.g. `gfx90a,gfx1100`.
+fn build_rocm_kernels() {
+    println!("cargo:rerun-if-changed={HIP_KERNELS}");
+    println!("cargo:rerun-if-env-changed=ROCM_PATH");
+    println!("cargo:rerun-if-env-changed=SCORIA_HIP_ARCH");
+
+    let rocm = PathBuf::from(env::var("ROCM_PATH").unwrap_or_else(|_| "/opt/rocm".to_string()));
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let object = out_dir.join("rocm_kernels.o");
+
+    let mut hipcc = Command::new(rocm.join("bin/hipcc"));
+    hipcc.args(["-c", "-fPIC", "-O3", HIP_KERNELS, "-o"]).arg(&object);
+    if let Ok(archs) = env::var("SCORIA_HIP_ARCH") {
+        for arch in archs.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+            hipcc.arg(format!("--offload-arch={arch}"));
+        }
+    }
+    run(hipcc, "hipcc");
+
+    let mut ar = Command::new("ar");
+    ar.arg("crs").arg(out_dir.join("libscoria_rocm_kernels.a")).arg(&object);
+    run(ar, "ar");
+
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    println!("cargo:rustc-link-lib=static=scoria_rocm_kernels");
+    println!("cargo:rustc-link-search=native={}", rocm.join("lib").display());
+    println!("cargo:rustc-link-lib=dylib=stdc++");
+}
+
+fn run(mut command: Command, tool: &str) {
+    let status = command
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run {tool} for the `rocm` feature: {e}"));
+    if !status.success() {
+        panic!("{tool} failed for the `rocm` feature ({status})");
+    }
+}
@@ -0,0 +1,17 @@
+// client/scoria-ffi/build.rs
+
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    // Checked in as well, so C and mobile consumers can build against a prebuilt library
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap())
+        .generate()
+        .expect("failed to generate the C header")
+        .write_to_file(crate_dir.join("include/scoria.h"));
+}
@@ -0,0 +1,146 @@
+#ifndef SCORIA_H
+#define SCORIA_H
+
+/* Generated with cbindgen:0.26.0 */
+
+/* Generated by cbindgen from scoria-ffi; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Bumped on any incompatible change to the functions or types below
+ */
+#define SCORIA_ABI_VERSION 1
+
+/**
+ * Result of every fallible call; values are stable across releases
+ */
+typedef enum ScoriaStatus {
+  SCORIA_STATUS_OK = 0,
+  SCORIA_STATUS_NULL_POINTER = 1,
+  SCORIA_STATUS_INVALID_ARGUMENT = 2,
+  SCORIA_STATUS_DECRYPTION = 3,
+  SCORIA_STATUS_MODEL_LOADING = 4,
+  SCORIA_STATUS_INFERENCE = 5,
+  SCORIA_STATUS_PROOF = 6,
+  SCORIA_STATUS_RUNTIME = 7,
+  SCORIA_STATUS_PANIC = 8,
+} ScoriaStatus;
+
+/**
+ * A decrypted model held in memory; calls from several threads are serialized
+ */
+typedef struct ScoriaModel ScoriaModel;
+
+/**
+ * Worker threads the runtime drives async loading and proving on
+ */
+typedef struct ScoriaRuntime ScoriaRuntime;
+
+/**
+ * Dense row-major float32 tensor. Inputs are only borrowed for the call.
+ */
+typedef struct ScoriaTensor {
+  const size_t *shape;
+  size_t rank;
+  const float *data;
+  size_t len;
+} ScoriaTensor;
+
+/**
+ * Outputs and proof of one inference, released with `scoria_free_output`
+ */
+typedef struct ScoriaOutput {
+  struct ScoriaTensor *tensors;
+  size_t tensor_count;
+  uint8_t *proof;
+  size_t proof_len;
+} ScoriaOutput;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * `SCORIA_ABI_VERSION` of the loaded library; compare against the header's
+ */
+uint32_t scoria_abi_version(void);
+
+/**
+ * Message for the last failed call on this thread, or null.
+ * Valid until the next failing call on the same thread.
+ */
+const char *scoria_last_error(void);
+
+/**
+ * Start a runtime with `worker_threads` workers, or one per core when 0.
+ *
+ * # Safety
+ * `out` must be valid for a pointer write.
+ */
+enum ScoriaStatus scoria_create_runtime(uint32_t worker_threads, struct ScoriaRuntime **out);
+
+/**
+ * Decrypt an AES-256-GCM model file and prepare it for proving.
+ *
+ * # Safety
+ * `path` must be a NUL-terminated UTF-8 string, `key` and `zk_params` valid
+ * for their lengths, and `out` valid for a pointer write.
+ */
+enum ScoriaStatus scoria_load_model(const struct ScoriaRuntime *runtime,
+                                    const char *path,
+                                    const uint8_t *key,
+                                    size_t key_len,
+                                    const uint8_t *zk_params,
+                                    size_t zk_params_len,
+                                    bool use_gpu,
+                                    struct ScoriaModel **out);
+
+/**
+ * Write the BLAKE3 hash of the decrypted model to `out`.
+ *
+ * # Safety
+ * `model` must come from `scoria_load_model` and `out` be valid for 32 bytes.
+ */
+enum ScoriaStatus scoria_model_hash(const struct ScoriaModel *model, uint8_t *out);
+
+/**
+ * Run `model` on `inputs`, filling `out` with the outputs and proof.
+ *
+ * # Safety
+ * `runtime` and `model` must come from this library, `inputs` be valid for
+ * `input_count` tensors, and `out` valid for a write. Release `out` with
+ * `scoria_free_output`.
+ */
+enum ScoriaStatus scoria_infer(const struct ScoriaRuntime *runtime,
+                               const struct ScoriaModel *model,
+                               const struct ScoriaTensor *inputs,
+                               size_t input_count,
+                               struct ScoriaOutput *out);
+
+/**
+ * # Safety
+ * `output` must be null or filled by `scoria_infer` and not yet freed.
+ */
+void scoria_free_output(struct ScoriaOutput *output);
+
+/**
+ * # Safety
+ * `model` must be null or come from `scoria_load_model`, and not be in use.
+ */
+void scoria_free_model(struct ScoriaModel *model);
+
+/**
+ * # Safety
+ * `runtime` must be null or come from `scoria_create_runtime`; models
+ * loaded on it stay usable with another runtime.
+ */
+void scoria_free_runtime(struct ScoriaRuntime *runtime);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SCORIA_H */
@@ -0,0 +1,384 @@
+// client/scoria-ffi/src/lib.rs
+
+//! C ABI over the encrypted ONNX runtime and its proof generation.
+//!
+//! Every fallible function returns a `ScoriaStatus`; on failure
+//! `scoria_last_error` describes it on the calling thread. Panics are caught
+//! at the boundary and reported as `SCORIA_STATUS_PANIC`. Objects handed out
+//! belong to the caller and are released with the matching `scoria_free_*`.
+
+use aes_gcm::{Aes256Gcm, KeyInit};
+use scoria_client_core::core::inference::{
+    backend::{BackendError, InferenceBackend, InferenceOutput, TensorData},
+    onnx::{OnnxError, OnnxRuntime},
+};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+    sync::Mutex,
+};
+use tokio::runtime::Runtime;
+
+/// Bumped on any incompatible change to the functions or types below
+pub const SCORIA_ABI_VERSION: u32 = 1;
+
+/// Result of every fallible call; values are stable across releases
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoriaStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidArgument = 2,
+    Decryption = 3,
+    ModelLoading = 4,
+    Inference = 5,
+    Proof = 6,
+    Runtime = 7,
+    Panic = 8,
+}
+
+/// Worker threads the runtime drives async loading and proving on
+pub struct ScoriaRuntime {
+    runtime: Runtime,
+}
+
+/// A decrypted model held in memory; calls from several threads are serialized
+pub struct ScoriaModel {
+    backend: Mutex<OnnxRuntime>,
+}
+
+/// Dense row-major float32 tensor. Inputs are only borrowed for the call.
+#[repr(C)]
+pub struct ScoriaTensor {
+    pub shape: *const usize,
+    pub rank: usize,
+    pub data: *const f32,
+    pub len: usize,
+}
+
+/// Outputs and proof of one inference, released with `scoria_free_output`
+#[repr(C)]
+pub struct ScoriaOutput {
+    pub tensors: *mut ScoriaTensor,
+    pub tensor_count: usize,
+    pub proof: *mut u8,
+    pub proof_len: usize,
+}
+
+struct FfiError(ScoriaStatus, String);
+
+impl FfiError {
+    fn null(what: &str) -> Self {
+        Self(ScoriaStatus::NullPointer, format!("{what} is null"))
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self(ScoriaStatus::InvalidArgument, message.into())
+    }
+}
+
+impl From<OnnxError> for FfiError {
+    fn from(e: OnnxError) -> Self {
+        let status = match &e {
+            OnnxError::Decryption(_) => ScoriaStatus::Decryption,
+            OnnxError::ModelLoading(_) | OnnxError::Quantization(_) => ScoriaStatus::ModelLoading,
+            OnnxError::ZkProof(_) => ScoriaStatus::Proof,
+            OnnxError::Inference(_) | OnnxError::ChainVerification(_) | OnnxError::GpuError(_) => {
+                ScoriaStatus::Inference
+            }
+        };
+        Self(status, e.to_string())
+    }
+}
+
+impl From<BackendError> for FfiError {
+    fn from(e: BackendError) -> Self {
+        let status = match &e {
+            BackendError::Decryption(_) => ScoriaStatus::Decryption,
+            BackendError::ModelLoading(_) | BackendError::Unavailable(_) => ScoriaStatus::ModelLoading,
+            BackendError::InvalidInput(_) => ScoriaStatus::InvalidArgument,
+            BackendError::Inference(_) => ScoriaStatus::Inference,
+            BackendError::ZkProof(_) => ScoriaStatus::Proof,
+        };
+        Self(status, e.to_string())
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', "")).expect("NULs were removed");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    let reason = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("panic in scoria: {reason}")
+}
+
+/// Unwinding into C is undefined behavior, so every entry point runs through here
+fn guard(f: impl FnOnce() -> Result<(), FfiError>) -> ScoriaStatus {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return ScoriaStatus::Ok,
+        Ok(Err(FfiError(status, message))) => (status, message),
+        Err(panic) => (ScoriaStatus::Panic, panic_message(panic.as_ref())),
+    };
+    set_last_error(message);
+    status
+}
+
+unsafe fn reference<'a, T>(ptr: *const T, what: &str) -> Result<&'a T, FfiError> {
+    ptr.as_ref().ok_or_else(|| FfiError::null(what))
+}
+
+/// A null pointer is accepted for an empty slice
+unsafe fn slice_arg<'a, T>(ptr: *const T, len: usize, what: &str) -> Result<&'a [T], FfiError> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(FfiError::null(what)),
+        (false, len) => Ok(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+unsafe fn to_tensor(tensor: &ScoriaTensor) -> Result<TensorData, FfiError> {
+    let shape = slice_arg(tensor.shape, tensor.rank, "tensor shape")?;
+    let data = slice_arg(tensor.data, tensor.len, "tensor data")?;
+    Ok(TensorData::new(shape.to_vec(), data.to_vec())?)
+}
+
+fn into_raw<T>(values: Vec<T>) -> *mut T {
+    Box::into_raw(values.into_boxed_slice()) as *mut T
+}
+
+unsafe fn drop_raw<T>(ptr: *mut T, len: usize) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)));
+    }
+}
+
+fn to_output(output: InferenceOutput) -> ScoriaOutput {
+    let tensors: Vec<ScoriaTensor> = output
+        .outputs
+        .into_iter()
+        .map(|t| ScoriaTensor {
+            rank: t.shape.len(),
+            shape: into_raw(t.shape),
+            len: t.data.len(),
+            data: into_raw(t.data),
+        })
+        .collect();
+    ScoriaOutput {
+        tensor_count: tensors.len(),
+        tensors: into_raw(tensors),
+        proof_len: output.proof.len(),
+        proof: into_raw(output.proof),
+    }
+}
+
+/// `SCORIA_ABI_VERSION` of the loaded library; compare against the header's
+#[no_mangle]
+pub extern "C" fn scoria_abi_version() -> u32 {
+    SCORIA_ABI_VERSION
+}
+
+/// Message for the last failed call on this thread, or null.
+/// Valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn scoria_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+/// Start a runtime with `worker_threads` workers, or one per core when 0.
+///
+/// # Safety
+/// `out` must be valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_create_runtime(worker_threads: u32, out: *mut *mut ScoriaRuntime) -> ScoriaStatus {
+    guard(|| {
+        let out = out.as_mut().ok_or_else(|| FfiError::null("out"))?;
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        if worker_threads > 0 {
+            builder.worker_threads(worker_threads as usize);
+        }
+        let runtime = builder
+            .enable_all()
+            .build()
+            .map_err(|e| FfiError(ScoriaStatus::Runtime, e.to_string()))?;
+        *out = Box::into_raw(Box::new(ScoriaRuntime { runtime }));
+        Ok(())
+    })
+}
+
+/// Decrypt an AES-256-GCM model file and prepare it for proving.
+///
+/// # Safety
+/// `path` must be a NUL-terminated UTF-8 string, `key` and `zk_params` valid
+/// for their lengths, and `out` valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_load_model(
+    runtime: *const ScoriaRuntime,
+    path: *const c_char,
+    key: *const u8,
+    key_len: usize,
+    zk_params: *const u8,
+    zk_params_len: usize,
+    use_gpu: bool,
+    out: *mut *mut ScoriaModel,
+) -> ScoriaStatus {
+    guard(|| {
+        let runtime = reference(runtime, "runtime")?;
+        let out = out.as_mut().ok_or_else(|| FfiError::null("out"))?;
+        if path.is_null() {
+            return Err(FfiError::null("path"));
+        }
+        let path = CStr::from_ptr(path).to_str().map_err(|_| FfiError::invalid("path is not UTF-8"))?;
+        let key = slice_arg(key, key_len, "key")?;
+        let aes = Aes256Gcm::new_from_slice(key)
+            .map_err(|_| FfiError::invalid(format!("key must be 32 bytes, got {key_len}")))?;
+        let zk_params = slice_arg(zk_params, zk_params_len, "zk_params")?;
+
+        let backend = runtime
+            .runtime
+            .block_on(OnnxRuntime::load_encrypted(path, &aes, Pubkey::default(), use_gpu, zk_params))?;
+        *out = Box::into_raw(Box::new(ScoriaModel { backend: Mutex::new(backend) }));
+        Ok(())
+    })
+}
+
+/// Write the BLAKE3 hash of the decrypted model to `out`.
+///
+/// # Safety
+/// `model` must come from `scoria_load_model` and `out` be valid for 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_model_hash(model: *const ScoriaModel, out: *mut u8) -> ScoriaStatus {
+    guard(|| {
+        let model = reference(model, "model")?;
+        if out.is_null() {
+            return Err(FfiError::null("out"));
+        }
+        let hash = model
+            .backend
+            .lock()
+            .map_err(|_| FfiError(ScoriaStatus::Panic, "model is unusable after an earlier panic".into()))?
+            .model_hash();
+        ptr::copy_nonoverlapping(hash.as_ptr(), out, hash.len());
+        Ok(())
+    })
+}
+
+/// Run `model` on `inputs`, filling `out` with the outputs and proof.
+///
+/// # Safety
+/// `runtime` and `model` must come from this library, `inputs` be valid for
+/// `input_count` tensors, and `out` valid for a write. Release `out` with
+/// `scoria_free_output`.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_infer(
+    runtime: *const ScoriaRuntime,
+    model: *const ScoriaModel,
+    inputs: *const ScoriaTensor,
+    input_count: usize,
+    out: *mut ScoriaOutput,
+) -> ScoriaStatus {
+    guard(|| {
+        let runtime = reference(runtime, "runtime")?;
+        let model = reference(model, "model")?;
+        let out = out.as_mut().ok_or_else(|| FfiError::null("out"))?;
+        let inputs = slice_arg(inputs, input_count, "inputs")?
+            .iter()
+            .map(|t| to_tensor(t))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut backend = model
+            .backend
+            .lock()
+            .map_err(|_| FfiError(ScoriaStatus::Panic, "model is unusable after an earlier panic".into()))?;
+        let output = runtime.runtime.block_on(backend.infer_with_proof(&inputs))?;
+        *out = to_output(output);
+        Ok(())
+    })
+}
+
+/// # Safety
+/// `output` must be null or filled by `scoria_infer` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_free_output(output: *mut ScoriaOutput) {
+    let Some(output) = output.as_mut() else { return };
+    if !output.tensors.is_null() {
+        for tensor in slice::from_raw_parts(output.tensors, output.tensor_count) {
+            drop_raw(tensor.shape as *mut usize, tensor.rank);
+            drop_raw(tensor.data as *mut f32, tensor.len);
+        }
+    }
+    drop_raw(output.tensors, output.tensor_count);
+    drop_raw(output.proof, output.proof_len);
+    *output = ScoriaOutput { tensors: ptr::null_mut(), tensor_count: 0, proof: ptr::null_mut(), proof_len: 0 };
+}
+
+/// # Safety
+/// `model` must be null or come from `scoria_load_model`, and not be in use.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_free_model(model: *mut ScoriaModel) {
+    if !model.is_null() {
+        drop(Box::from_raw(model));
+    }
+}
+
+/// # Safety
+/// `runtime` must be null or come from `scoria_create_runtime`; models
+/// loaded on it stay usable with another runtime.
+#[no_mangle]
+pub unsafe extern "C" fn scoria_free_runtime(runtime: *mut ScoriaRuntime) {
+    if !runtime.is_null() {
+        drop(Box::from_raw(runtime));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(scoria_last_error()) }.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn panics_and_null_arguments_become_status_codes() {
+        let status = guard(|| panic!("boom"));
+        assert_eq!(status, ScoriaStatus::Panic);
+        assert!(last_error().contains("boom"));
+
+        let mut out = ScoriaOutput { tensors: ptr::null_mut(), tensor_count: 0, proof: ptr::null_mut(), proof_len: 0 };
+        let status = unsafe { scoria_infer(ptr::null(), ptr::null(), ptr::null(), 0, &mut out) };
+        assert_eq!(status, ScoriaStatus::NullPointer);
+        assert_eq!(last_error(), "runtime is null");
+    }
+
+    #[test]
+    fn output_round_trips_and_frees() {
+        let output = InferenceOutput {
+            outputs: vec![TensorData::new(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]).unwrap()],
+            proof: vec![7; 192],
+        };
+        let mut out = to_output(output);
+        unsafe {
+            let tensor = &*out.tensors;
+            assert_eq!(to_tensor(tensor).ok().unwrap().data, [1.0, 2.0, 3.0, 4.0]);
+            assert_eq!(slice::from_raw_parts(out.proof, out.proof_len), [7; 192]);
+
+            scoria_free_output(&mut out);
+            scoria_free_output(&mut out);
+        }
+        assert!(out.tensors.is_null() && out.proof.is_null());
+    }
+}
@@ -0,0 +1,29 @@
+"""Encrypted model inference, zero-knowledge proofs and data contribution for the SCORIA network."""
+
+from ._scoria import (
+    ContributionError,
+    InferenceError,
+    InvalidProof,
+    Model,
+    ModelError,
+    ProofError,
+    ScoriaError,
+    contribute_dataset,
+    infer_with_proof,
+    load_encrypted_model,
+    verify_proof,
+)
+
+__all__ = [
+    "ContributionError",
+    "InferenceError",
+    "InvalidProof",
+    "Model",
+    "ModelError",
+    "ProofError",
+    "ScoriaError",
+    "contribute_dataset",
+    "infer_with_proof",
+    "load_encrypted_model",
+    "verify_proof",
+]
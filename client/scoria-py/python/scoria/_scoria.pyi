@@ -0,0 +1,43 @@
+from os import PathLike
+from typing import List, Optional, Sequence, Tuple, Union
+
+import numpy as np
+import numpy.typing as npt
+
+_Path = Union[str, PathLike]
+
+class ScoriaError(Exception): ...
+class ModelError(ScoriaError): ...
+class InferenceError(ScoriaError): ...
+class ContributionError(ScoriaError): ...
+class ProofError(ScoriaError): ...
+class InvalidProof(ProofError): ...
+
+class Model:
+    @property
+    def model_hash(self) -> bytes: ...
+
+def load_encrypted_model(
+    path: _Path,
+    key: bytes,
+    zk_params: bytes,
+    gpu: bool = False,
+    rpc_pubkey: Optional[str] = None,
+) -> Model: ...
+def infer_with_proof(
+    model: Model, inputs: Sequence[npt.NDArray[np.float32]]
+) -> Tuple[List[npt.NDArray[np.float32]], bytes]: ...
+def contribute_dataset(
+    dataset: _Path,
+    model_id: str,
+    config: Optional[_Path] = None,
+    signer: Optional[str] = None,
+    dp_epsilon: float = 3.0,
+    pii_policy: str = "mask",
+    synthetic: bool = False,
+    schema: Optional[_Path] = None,
+    retention_days: int = 365,
+    purposes: Sequence[str] = ("training",),
+    jurisdictions: Sequence[str] = (),
+) -> Tuple[str, bytes]: ...
+def verify_proof(proof: bytes, vk: bytes, public_inputs: bytes) -> str: ...
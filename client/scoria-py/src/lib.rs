@@ -0,0 +1,244 @@
+// client/scoria-py/src/lib.rs
+
+//! Python bindings over `scoria_client_core`. Model loading, proving and
+//! contribution run with the GIL released; tensors cross as float32 numpy arrays.
+
+use aes_gcm::{Aes256Gcm, KeyInit};
+use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use scoria_client_core::{
+    config::load_config,
+    core::{
+        data_sanitizer::pii::{PiiPolicy, PiiScanner},
+        dataset::schema::Schema,
+        inference::{
+            backend::{InferenceBackend, TensorData},
+            onnx::OnnxRuntime,
+        },
+        zkp::verifier::{self, Proof, VerifyError, VerifyingKey},
+    },
+    connect_rpc, crypto_context,
+    wallet::{fees::TxBuilder, signer::resolve_signer},
+    consent_policy, ConsentPurpose, ContributeOptions, CountryCode, ScoriaClient,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Mutex, MutexGuard, OnceLock},
+};
+use tokio::runtime::Runtime;
+
+create_exception!(scoria, ScoriaError, PyException);
+create_exception!(scoria, ModelError, ScoriaError);
+create_exception!(scoria, InferenceError, ScoriaError);
+create_exception!(scoria, ContributionError, ScoriaError);
+create_exception!(scoria, ProofError, ScoriaError);
+create_exception!(scoria, InvalidProof, ProofError);
+
+/// Shared by every call; drives model loading, inference and contributions
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the tokio runtime")
+    })
+}
+
+fn parse_pubkey(value: &str) -> PyResult<Pubkey> {
+    Pubkey::from_str(value).map_err(|e| PyValueError::new_err(format!("{value}: {e}")))
+}
+
+fn contribution_error(e: impl Display) -> PyErr {
+    ContributionError::new_err(e.to_string())
+}
+
+/// Copies in logical order, so non-contiguous and transposed views are accepted
+fn to_tensor(array: &PyReadonlyArrayDyn<'_, f32>) -> PyResult<TensorData> {
+    let view = array.as_array();
+    TensorData::new(view.shape().to_vec(), view.iter().copied().collect())
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// A decrypted model held in memory; calls from several threads are serialized
+#[pyclass(module = "scoria")]
+pub struct Model {
+    backend: Mutex<OnnxRuntime>,
+}
+
+impl Model {
+    fn backend(&self) -> PyResult<MutexGuard<'_, OnnxRuntime>> {
+        self.backend
+            .lock()
+            .map_err(|_| InferenceError::new_err("model is unusable after a panic in an earlier call"))
+    }
+}
+
+#[pymethods]
+impl Model {
+    /// BLAKE3 hash of the decrypted model
+    #[getter]
+    fn model_hash<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        Ok(PyBytes::new(py, &self.backend()?.model_hash()))
+    }
+}
+
+/// Decrypt an AES-256-GCM model file and prepare it for proving.
+///
+/// `zk_params` are the circuit's proving parameters; `rpc_pubkey` only labels metrics.
+#[pyfunction]
+#[pyo3(signature = (path, key, zk_params, gpu = false, rpc_pubkey = None))]
+fn load_encrypted_model(
+    py: Python<'_>,
+    path: PathBuf,
+    key: &[u8],
+    zk_params: &[u8],
+    gpu: bool,
+    rpc_pubkey: Option<&str>,
+) -> PyResult<Model> {
+    let aes = Aes256Gcm::new_from_slice(key)
+        .map_err(|_| PyValueError::new_err(format!("key must be 32 bytes, got {}", key.len())))?;
+    let rpc_pubkey = rpc_pubkey.map(parse_pubkey).transpose()?.unwrap_or_default();
+
+    let backend = py
+        .allow_threads(|| runtime().block_on(OnnxRuntime::load_encrypted(&path, &aes, rpc_pubkey, gpu, zk_params)))
+        .map_err(|e| ModelError::new_err(e.to_string()))?;
+    Ok(Model { backend: Mutex::new(backend) })
+}
+
+/// Run `model` on float32 arrays, returning `(outputs, proof)`
+#[pyfunction]
+fn infer_with_proof<'py>(
+    py: Python<'py>,
+    model: PyRef<'py, Model>,
+    inputs: Vec<PyReadonlyArrayDyn<'py, f32>>,
+) -> PyResult<(Vec<&'py PyArrayDyn<f32>>, &'py PyBytes)> {
+    let inputs = inputs.iter().map(to_tensor).collect::<PyResult<Vec<_>>>()?;
+    let model = &*model;
+
+    let output = py.allow_threads(|| {
+        let mut backend = model.backend()?;
+        runtime()
+            .block_on(backend.infer_with_proof(&inputs))
+            .map_err(|e| InferenceError::new_err(e.to_string()))
+    })?;
+
+    let outputs = output
+        .outputs
+        .into_iter()
+        .map(|t| {
+            ndarray::ArrayD::from_shape_vec(t.shape, t.data)
+                .map(|a| a.into_pyarray(py))
+                .map_err(|e| InferenceError::new_err(e.to_string()))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok((outputs, PyBytes::new(py, &output.proof)))
+}
+
+/// Sanitize, encrypt and record a dataset contribution using the client config and wallet.
+///
+/// Returns `(signature, data_hash)`.
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (
+    dataset, model_id, config = None, signer = None, dp_epsilon = 3.0, pii_policy = "mask", synthetic = false,
+    schema = None, retention_days = 365, purposes = vec!["training".to_string()], jurisdictions = Vec::new()
+))]
+fn contribute_dataset<'py>(
+    py: Python<'py>,
+    dataset: PathBuf,
+    model_id: &str,
+    config: Option<PathBuf>,
+    signer: Option<String>,
+    dp_epsilon: f64,
+    pii_policy: &str,
+    synthetic: bool,
+    schema: Option<PathBuf>,
+    retention_days: i64,
+    purposes: Vec<String>,
+    jurisdictions: Vec<String>,
+) -> PyResult<(String, &'py PyBytes)> {
+    let model_id = parse_pubkey(model_id)?;
+    let pii_policy = PiiPolicy::from_str(pii_policy).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let purposes = purposes
+        .iter()
+        .map(|p| ConsentPurpose::from_str(p))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PyValueError::new_err)?;
+    let jurisdictions = jurisdictions
+        .iter()
+        .map(|j| CountryCode::from_str(j))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PyValueError::new_err)?;
+
+    let contribution = py.allow_threads(|| {
+        let config = load_config(&config).map_err(contribution_error)?;
+        config.programs.install();
+        let mut wallet_manager = None;
+        let signer = resolve_signer(signer.as_deref(), &config.wallet, &mut wallet_manager).map_err(contribution_error)?;
+        let crypto_ctx = crypto_context(&config.security).map_err(contribution_error)?;
+        let schema = schema.as_deref().map(Schema::load).transpose().map_err(contribution_error)?;
+
+        let (rpc_client, _) = connect_rpc(&config.network);
+        let tx_builder = TxBuilder::new(&rpc_client, config.network.priority_fee.clone());
+        let client = ScoriaClient::new(&rpc_client, signer, &crypto_ctx, &tx_builder);
+        let scanner = PiiScanner::new(pii_policy);
+        let options = ContributeOptions {
+            dp_epsilon,
+            pii_scanner: &scanner,
+            anonymity: config.privacy.anonymity.as_ref(),
+            synthetic,
+            schema: schema.as_ref(),
+            openings: &config.paths.contributions,
+            consent: consent_policy(retention_days, &purposes, &jurisdictions),
+        };
+        runtime()
+            .block_on(client.contribute(&dataset, model_id, &options))
+            .map_err(contribution_error)
+    })?;
+    Ok((contribution.signature.to_string(), PyBytes::new(py, &contribution.data_hash)))
+}
+
+/// Check a proof against a verifying key and snarkjs `public.json`, returning the protocol.
+///
+/// Raises `InvalidProof` when a well-formed proof does not verify and
+/// `ProofError` when any of the inputs cannot be parsed.
+#[pyfunction]
+fn verify_proof(py: Python<'_>, proof: &[u8], vk: &[u8], public_inputs: &[u8]) -> PyResult<&'static str> {
+    py.allow_threads(|| {
+        let proof = Proof::parse(proof)?;
+        let vk = VerifyingKey::parse(vk)?;
+        let public = verifier::parse_public_inputs(public_inputs)?;
+        verifier::verify(&proof, &vk, &public)?;
+        Ok(proof.protocol())
+    })
+    .map_err(|e: VerifyError| match e {
+        VerifyError::Invalid => InvalidProof::new_err(e.to_string()),
+        _ => ProofError::new_err(e.to_string()),
+    })
+}
+
+#[pymodule]
+fn _scoria(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<Model>()?;
+    m.add_function(wrap_pyfunction!(load_encrypted_model, m)?)?;
+    m.add_function(wrap_pyfunction!(infer_with_proof, m)?)?;
+    m.add_function(wrap_pyfunction!(contribute_dataset, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_proof, m)?)?;
+
+    m.add("ScoriaError", py.get_type::<ScoriaError>())?;
+    m.add("ModelError", py.get_type::<ModelError>())?;
+    m.add("InferenceError", py.get_type::<InferenceError>())?;
+    m.add("ContributionError", py.get_type::<ContributionError>())?;
+    m.add("ProofError", py.get_type::<ProofError>())?;
+    m.add("InvalidProof", py.get_type::<InvalidProof>())?;
+    Ok(())
+}
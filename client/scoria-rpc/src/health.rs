@@ -0,0 +1,61 @@
+// client/scoria-rpc/src/health.rs
+
+use crate::FailoverSender;
+use solana_rpc_client::rpc_sender::RpcSender;
+use solana_rpc_client_api::request::RpcRequest;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Probe every endpoint with `getHealth` and `getSlot` on an interval, taking
+/// unhealthy or lagging ones out of rotation before a request hits them.
+///
+/// Returns `None` when `health_check_interval_secs` is 0.
+pub fn spawn_health_checks(sender: &FailoverSender) -> Option<JoinHandle<()>> {
+    let inner = sender.inner.clone();
+    let interval = Duration::from_secs(inner.config.health_check_interval_secs);
+    if interval.is_zero() {
+        return None;
+    }
+
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let mut slots = Vec::with_capacity(inner.endpoints.len());
+            for endpoint in &inner.endpoints {
+                let healthy = endpoint.sender.send(RpcRequest::GetHealth, serde_json::Value::Null).await;
+                let slot = match healthy {
+                    Ok(_) => endpoint
+                        .sender
+                        .send(RpcRequest::GetSlot, serde_json::json!([{ "commitment": "processed" }]))
+                        .await
+                        .ok()
+                        .and_then(|v| v.as_u64()),
+                    Err(e) => {
+                        tracing::debug!(endpoint = %endpoint.url, error = %e, "RPC health check failed");
+                        None
+                    }
+                };
+                slots.push(slot);
+            }
+
+            let newest = slots.iter().flatten().copied().max().unwrap_or(0);
+            for (index, slot) in slots.into_iter().enumerate() {
+                let url = inner.endpoints[index].url.clone();
+                match slot {
+                    Some(slot) => {
+                        let lag = newest - slot;
+                        metrics::gauge!("rpc_endpoint_slot_lag", "endpoint" => url.clone()).set(lag as f64);
+                        if lag > inner.config.max_slot_lag {
+                            tracing::warn!(endpoint = %url, lag, "RPC endpoint is behind, taking it out of rotation");
+                            inner.mark_down(index);
+                        } else {
+                            inner.mark_up(index);
+                        }
+                    }
+                    None => inner.mark_down(index),
+                }
+            }
+        }
+    }))
+}
@@ -0,0 +1,285 @@
+// client/scoria-rpc/src/lib.rs
+
+//! `RpcSender` over several endpoints: requests go to the first healthy one,
+//! and rate limits, timeouts and unhealthy nodes rotate to the next after a
+//! jittered exponential backoff. Every `RpcClient` made with
+//! [`FailoverSender::client`] shares the same endpoints and their health.
+
+mod health;
+
+pub use health::spawn_health_checks;
+
+use async_trait::async_trait;
+use rand::Rng;
+use serde::Deserialize;
+use solana_rpc_client::{
+    http_sender::HttpSender,
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::RpcClientConfig,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_rpc_client_api::{
+    client_error::{reqwest::StatusCode, Error as ClientError, ErrorKind, Result},
+    custom_error,
+    request::{RpcError, RpcRequest},
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FailoverConfig {
+    /// Attempts after the first, across all endpoints
+    pub max_retries: u32,
+    pub base_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub request_timeout_secs: u64,
+    /// How long a failing endpoint is skipped before it is tried again
+    pub cooldown_secs: u64,
+    /// 0 disables background health checks
+    pub health_check_interval_secs: u64,
+    /// Endpoints this many slots behind the most advanced one are skipped
+    pub max_slot_lag: u64,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            base_backoff_ms: 250,
+            max_backoff_ms: 8_000,
+            request_timeout_secs: 30,
+            cooldown_secs: 30,
+            health_check_interval_secs: 15,
+            max_slot_lag: 150,
+        }
+    }
+}
+
+struct Endpoint {
+    url: String,
+    sender: HttpSender,
+    /// Skipped until this instant; `None` while healthy
+    down_until: Mutex<Option<Instant>>,
+}
+
+struct Inner {
+    endpoints: Vec<Endpoint>,
+    config: FailoverConfig,
+}
+
+/// Cheap to clone; clones share endpoint health
+#[derive(Clone)]
+pub struct FailoverSender {
+    inner: Arc<Inner>,
+}
+
+impl FailoverSender {
+    /// `urls` in order of preference; panics when empty
+    pub fn new(urls: Vec<String>, config: &FailoverConfig) -> Self {
+        assert!(!urls.is_empty(), "at least one RPC endpoint is required");
+        let timeout = Duration::from_secs(config.request_timeout_secs);
+        let endpoints = urls
+            .into_iter()
+            .map(|url| {
+                metrics::gauge!("rpc_endpoint_up", "endpoint" => url.clone()).set(1.0);
+                Endpoint { sender: HttpSender::new_with_timeout(&url, timeout), url, down_until: Mutex::new(None) }
+            })
+            .collect();
+        Self { inner: Arc::new(Inner { endpoints, config: config.clone() }) }
+    }
+
+    /// Nonblocking client whose every call goes through this sender
+    pub fn client(&self, commitment: CommitmentConfig) -> RpcClient {
+        RpcClient::new_sender(self.clone(), RpcClientConfig::with_commitment(commitment))
+    }
+
+    /// URLs with whether each is currently in rotation
+    pub fn endpoint_status(&self) -> Vec<(String, bool)> {
+        let now = Instant::now();
+        (0..self.inner.endpoints.len())
+            .map(|i| (self.inner.endpoints[i].url.clone(), self.inner.is_up(i, now)))
+            .collect()
+    }
+}
+
+impl Inner {
+    fn is_up(&self, index: usize, now: Instant) -> bool {
+        self.endpoints[index].down_until.lock().unwrap().map_or(true, |until| until <= now)
+    }
+
+    /// First healthy endpoint in preference order, or the one that recovers soonest
+    fn pick(&self) -> usize {
+        let now = Instant::now();
+        (0..self.endpoints.len()).find(|&i| self.is_up(i, now)).unwrap_or_else(|| {
+            (0..self.endpoints.len())
+                .min_by_key(|&i| *self.endpoints[i].down_until.lock().unwrap())
+                .unwrap_or(0)
+        })
+    }
+
+    fn mark_down(&self, index: usize) {
+        let endpoint = &self.endpoints[index];
+        let until = Instant::now() + Duration::from_secs(self.config.cooldown_secs);
+        if endpoint.down_until.lock().unwrap().replace(until).is_none() {
+            metrics::gauge!("rpc_endpoint_up", "endpoint" => endpoint.url.clone()).set(0.0);
+            metrics::counter!("rpc_failovers_total", "endpoint" => endpoint.url.clone()).increment(1);
+        }
+    }
+
+    fn mark_up(&self, index: usize) {
+        let endpoint = &self.endpoints[index];
+        if endpoint.down_until.lock().unwrap().take().is_some() {
+            metrics::gauge!("rpc_endpoint_up", "endpoint" => endpoint.url.clone()).set(1.0);
+            tracing::info!(endpoint = %endpoint.url, "RPC endpoint back in rotation");
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for FailoverSender {
+    async fn send(&self, request: RpcRequest, params: serde_json::Value) -> Result<serde_json::Value> {
+        let config = &self.inner.config;
+        let mut attempt = 0;
+        loop {
+            let index = self.inner.pick();
+            let endpoint = &self.inner.endpoints[index];
+            let started = Instant::now();
+            let result = endpoint.sender.send(request, params.clone()).await;
+
+            let retryable = result.as_ref().err().is_some_and(is_retryable);
+            let outcome = match &result {
+                Ok(_) => "ok",
+                Err(_) if retryable => "unavailable",
+                Err(_) => "error",
+            };
+            metrics::histogram!("rpc_request_duration_seconds", "endpoint" => endpoint.url.clone())
+                .record(started.elapsed().as_secs_f64());
+            metrics::counter!("rpc_requests_total", "endpoint" => endpoint.url.clone(), "outcome" => outcome)
+                .increment(1);
+
+            match result {
+                Err(e) if retryable => {
+                    self.inner.mark_down(index);
+                    if attempt >= config.max_retries {
+                        return Err(e);
+                    }
+                    let delay = backoff(attempt, config, &mut rand::thread_rng());
+                    tracing::warn!(
+                        endpoint = %endpoint.url,
+                        %request,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "RPC endpoint unavailable, failing over"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                // Application errors (bad params, preflight failures) would fail on any node
+                result => {
+                    if result.is_ok() {
+                        self.inner.mark_up(index);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.endpoints.iter().map(|e| e.sender.get_transport_stats()).fold(
+            RpcTransportStats::default(),
+            |mut total, stats| {
+                total.request_count += stats.request_count;
+                total.elapsed_time += stats.elapsed_time;
+                total.rate_limited_time += stats.rate_limited_time;
+                total
+            },
+        )
+    }
+
+    fn url(&self) -> String {
+        self.inner.endpoints[self.inner.pick()].url.clone()
+    }
+}
+
+/// Worth another endpoint: rate limits, transport failures and lagging nodes.
+///
+/// `HttpSender` already honours `Retry-After` on 429s a few times before
+/// giving up, so a 429 here means the endpoint is persistently throttling us.
+fn is_retryable(err: &ClientError) -> bool {
+    match err.kind() {
+        ErrorKind::Io(_) => true,
+        ErrorKind::Reqwest(e) => {
+            e.is_timeout()
+                || e.is_connect()
+                || e.status().is_some_and(|s| s == StatusCode::TOO_MANY_REQUESTS || s.is_server_error())
+        }
+        ErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => matches!(
+            *code,
+            custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | custom_error::JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+        ),
+        _ => false,
+    }
+}
+
+/// Exponential backoff with equal jitter: uniform in `[cap / 2, cap]`
+fn backoff(attempt: u32, config: &FailoverConfig, rng: &mut impl Rng) -> Duration {
+    let cap = config
+        .base_backoff_ms
+        .saturating_mul(1 << attempt.min(20))
+        .min(config.max_backoff_ms)
+        .max(1);
+    Duration::from_millis(rng.gen_range(cap / 2..=cap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_rpc_client_api::request::RpcResponseErrorData;
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let config = FailoverConfig { base_backoff_ms: 100, max_backoff_ms: 1_000, ..Default::default() };
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let first = backoff(0, &config, &mut rng).as_millis();
+            assert!((50..=100).contains(&first));
+            let third = backoff(2, &config, &mut rng).as_millis();
+            assert!((200..=400).contains(&third));
+            assert!(backoff(30, &config, &mut rng).as_millis() <= 1_000);
+        }
+    }
+
+    #[test]
+    fn test_down_endpoints_are_skipped_until_all_are_down() {
+        let sender = FailoverSender::new(
+            vec!["http://primary".into(), "http://secondary".into()],
+            &FailoverConfig::default(),
+        );
+        let inner = &sender.inner;
+        assert_eq!(inner.pick(), 0);
+        inner.mark_down(0);
+        assert_eq!(inner.pick(), 1);
+        // With everything down, the endpoint that went down first is retried
+        inner.mark_down(1);
+        assert_eq!(inner.pick(), 0);
+        inner.mark_up(0);
+        assert_eq!(sender.endpoint_status(), vec![("http://primary".into(), true), ("http://secondary".into(), false)]);
+    }
+
+    #[test]
+    fn test_only_node_errors_fail_over() {
+        let response = |code| -> ClientError {
+            RpcError::RpcResponseError { code, message: String::new(), data: RpcResponseErrorData::Empty }.into()
+        };
+        assert!(is_retryable(&response(custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY)));
+        assert!(!is_retryable(&response(custom_error::JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE)));
+        assert!(!is_retryable(&ErrorKind::Custom("bad params".into()).into()));
+    }
+}
@@ -0,0 +1,182 @@
+// client/src/bin/scoria-prover.rs
+//
+// Remote proving daemon. Clients seal circuit inputs to the daemon's X25519
+// key (see `core::zkp::remote`), the daemon proves on its GPU, and every
+// proof comes back with an Ed25519-signed attestation of the environment.
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = ProverCli::parse();
+    match cli.command {
+        ProverCommands::Serve { listen, config, identity, tls_cert, tls_key, client_ca } => {
+            let config = load_config(&config)?;
+            config.programs.install();
+            let _telemetry = scoria_client_core::telemetry::init(config.monitoring.otlp_endpoint.as_deref())?;
+
+            // 1. Identity seed: 32 raw bytes, created on first start
+            let seed = match std::fs::read(&identity) {
+                Ok(bytes) => <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| "identity file must hold 32 bytes")?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    let mut seed = [0u8; 32];
+                    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut seed);
+                    std::fs::write(&identity, seed)?;
+                    seed
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let backend = format!("{:?}", config.zkp.gpu_backend).to_lowercase();
+            let identity = ProverIdentity::from_seed(&seed, ProverEnvironment::current(&backend)?);
+            tracing::info!(identity = %hex::encode(identity.info().identity), %backend, "Prover identity loaded");
+
+            // 2. Shared state: verified circuits and one warm prover per circuit
+            let witnesses = Arc::new(WitnessCache::from_config(&config.zkp.witness_cache, &config.paths.model_cache));
+            let state = Arc::new(ProverState {
+                identity,
+                circuits: CircuitRegistry::new(config.zkp.registry.clone()),
+                provers: tokio::sync::Mutex::new(HashMap::new()),
+                witnesses,
+                zkp: config.zkp,
+            });
+            let app = axum::Router::new()
+                .route(INFO_PATH, axum::routing::get(info))
+                .route(PROVE_PATH, axum::routing::post(prove))
+                .layer(axum::extract::DefaultBodyLimit::max(MAX_REQUEST_BYTES))
+                .with_state(state);
+
+            // 3. TLS is mandatory; a client CA turns on mutual TLS
+            let tls = server_tls(&tls_cert, &tls_key, client_ca.as_deref())?;
+            tracing::info!(%listen, mtls = client_ca.is_some(), "Serving proofs");
+            axum_server::bind_rustls(listen, tls).serve(app.into_make_service()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Sealed witnesses for the largest supported templates stay well below this
+const MAX_REQUEST_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Parser)]
+#[command(name = "scoria-prover")]
+#[command(about = "Remote Groth16 prover for SCORIA clients", long_about = None)]
+struct ProverCli {
+    #[command(subcommand)]
+    command: ProverCommands,
+}
+
+#[derive(Subcommand)]
+enum ProverCommands {
+    /// Accept sealed proving requests over HTTPS
+    Serve {
+        #[arg(long, default_value = "0.0.0.0:8443")]
+        listen: std::net::SocketAddr,
+
+        #[arg(short, long, help = "Client config; `zkp` and `paths` select the backend and circuit registry")]
+        config: Option<PathBuf>,
+
+        #[arg(long, default_value = "prover_identity.key", help = "Ed25519 seed file, created when missing")]
+        identity: PathBuf,
+
+        #[arg(long, help = "Server certificate chain (PEM)")]
+        tls_cert: PathBuf,
+
+        #[arg(long, help = "Server private key (PEM)")]
+        tls_key: PathBuf,
+
+        #[arg(long, help = "Require client certificates signed by this CA (PEM)")]
+        client_ca: Option<PathBuf>,
+    },
+}
+
+struct ProverState {
+    identity: ProverIdentity,
+    circuits: CircuitRegistry,
+    provers: tokio::sync::Mutex<HashMap<[u8; 32], Arc<ZKProver>>>,
+    witnesses: Arc<WitnessCache>,
+    zkp: ZkpConfig,
+}
+
+type Rejection = (axum::http::StatusCode, String);
+
+async fn info(axum::extract::State(state): axum::extract::State<Arc<ProverState>>) -> axum::Json<ProverInfo> {
+    axum::Json(state.identity.info())
+}
+
+async fn prove(
+    axum::extract::State(state): axum::extract::State<Arc<ProverState>>,
+    axum::Json(request): axum::Json<ProveRequest>,
+) -> Result<axum::Json<ProveResponse>, Rejection> {
+    use axum::http::StatusCode;
+    let started = Instant::now();
+
+    // 1. Only circuits from the registry, verified against their hash
+    let circuit = state
+        .circuits
+        .load(&request.circuit_hash)
+        .await
+        .map_err(|e| (StatusCode::NOT_FOUND, e.to_string()))?;
+    let inputs = state
+        .identity
+        .open(&request)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    // 2. Proving keys are large; keep one loaded prover per circuit
+    let prover = {
+        let mut provers = state.provers.lock().await;
+        match provers.get(&request.circuit_hash) {
+            Some(prover) => prover.clone(),
+            None => {
+                let prover = ZKProver::new(&circuit.proving_key_path.to_string_lossy(), &state.zkp)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("{e:?}")))?
+                    .with_witness_cache(state.witnesses.clone());
+                provers.entry(request.circuit_hash).or_insert(Arc::new(prover)).clone()
+            }
+        }
+    };
+    let named: Vec<(&str, Vec<Fr>)> = inputs.iter().map(|(name, values)| (name.as_str(), values.clone())).collect();
+    let proof = prover
+        .prove_circuit(&circuit, &named)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("{e:?}")))?;
+
+    // 3. Attest to the request, the proof and this environment
+    let attestation = state.identity.attest(&request, &proof);
+    tracing::info!(
+        circuit = %hex::encode(request.circuit_hash),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "Proof served"
+    );
+    Ok(axum::Json(ProveResponse { proof, attestation }))
+}
+
+fn server_tls(
+    cert: &Path,
+    key: &Path,
+    client_ca: Option<&Path>,
+) -> Result<axum_server::tls_rustls::RustlsConfig, Box<dyn std::error::Error>> {
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(std::fs::File::open(cert)?))?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+    let key = rustls_pemfile::pkcs8_private_keys(&mut std::io::BufReader::new(std::fs::File::open(key)?))?
+        .into_iter()
+        .next()
+        .map(rustls::PrivateKey)
+        .ok_or("no PKCS#8 private key in --tls-key")?;
+
+    let builder = rustls::ServerConfig::builder().with_safe_defaults();
+    let mut server = match client_ca {
+        Some(ca) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for der in rustls_pemfile::certs(&mut std::io::BufReader::new(std::fs::File::open(ca)?))? {
+                roots.add(&rustls::Certificate(der))?;
+            }
+            builder
+                .with_client_cert_verifier(rustls::server::AllowAnyAuthenticatedClient::new(roots).boxed())
+                .with_single_cert(certs, key)?
+        }
+        None => builder.with_no_client_auth().with_single_cert(certs, key)?,
+    };
+    server.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(axum_server::tls_rustls::RustlsConfig::from_config(Arc::new(server)))
+}
@@ -0,0 +1,103 @@
+// client/src/bin/scoria-provider.rs
+//
+// Provider worker. Watches the registry for Pending inference requests on the
+// given models, claims each, runs it with a proof on a warm model and submits
+// the fulfillment; see `scoria_client_core::provider`.
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = ProviderCli::parse();
+    match cli.command {
+        ProviderCommands::Run { models, config, signer, accel, gpu_devices, max_concurrent, metrics } => {
+            let config = load_config(&config)?;
+            config.programs.install();
+            let _telemetry = scoria_client_core::telemetry::init(config.monitoring.otlp_endpoint.as_deref())?;
+            if metrics {
+                scoria_client_core::metrics::install_exporter(config.monitoring.prometheus_port)?;
+            }
+
+            // 1. Chain access and the provider key, which must be a Contributor on every model
+            let (rpc_client, rpc_sender) = connect_rpc(&config.network);
+            let _rpc_health = spawn_health_checks(&rpc_sender);
+            let mut wallet_manager = None;
+            let signer = resolve_signer(signer.as_deref(), &config.wallet, &mut wallet_manager)?;
+            let crypto_ctx = crypto_context(&config.security)?;
+            let tx_builder = TxBuilder::new(&rpc_client, config.network.priority_fee.clone());
+            let client = ScoriaClient::new(&rpc_client, signer.clone(), &crypto_ctx, &tx_builder);
+
+            // 2. Model, circuit and schema stores, as for `scoria-cli serve`
+            let accel = AccelDevice::open(accel, gpu_devices.as_deref());
+            let circuits = CircuitRegistry::new(config.zkp.registry.clone());
+            let schemas = SchemaStore::new(
+                config.zkp.registry.ipfs_gateway.clone(),
+                config.paths.model_cache.join("schemas"),
+            );
+            let disk_cache = DiskCache::open(config.paths.model_cache.join("store"), &config.cache)?;
+            let model_cache = ModelCache::new(&disk_cache, &config.model);
+            let distributor = ModelDistributor::new(config.distribution.clone());
+            let runtime = InferenceRuntime {
+                circuits: &circuits,
+                schemas: &schemas,
+                model_cache: &model_cache,
+                distributor: &distributor,
+                result_cache: None,
+                accel,
+            };
+
+            // 3. Poll, claim and fulfill until stopped
+            let mut provider_config = config.provider.clone();
+            provider_config.max_concurrent = max_concurrent.unwrap_or(provider_config.max_concurrent);
+            tracing::info!(
+                provider = %signer.pubkey(),
+                models = models.len(),
+                max_concurrent = provider_config.max_concurrent,
+                "Providing inference"
+            );
+            let provider = Provider::new(
+                &client,
+                &runtime,
+                &provider_config,
+                &config.serve,
+                config.zkp.registry.ipfs_gateway.clone(),
+                models,
+            );
+            provider.run().await;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Parser)]
+#[command(name = "scoria-provider")]
+#[command(about = "Claims and fulfills on-chain SCORIA inference requests", long_about = None)]
+struct ProviderCli {
+    #[command(subcommand)]
+    command: ProviderCommands,
+}
+
+#[derive(Subcommand)]
+enum ProviderCommands {
+    /// Watch for and fulfill Pending requests on the given models
+    Run {
+        #[arg(long = "model", required = true, help = "Model account to serve; repeat for several")]
+        models: Vec<Pubkey>,
+
+        #[arg(short, long, help = "Client config; `[provider]` tunes polling, claims and concurrency")]
+        config: Option<PathBuf>,
+
+        #[arg(long, help = "Provider signer: keypair file path or ledger://[?key=<account>/<change>]")]
+        signer: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = HardwareAccel::Auto, help = "Accelerator for inference and proving")]
+        accel: HardwareAccel,
+
+        #[arg(long, value_delimiter = ',', help = "Restrict GPU work to these device ordinals, e.g. 0,2")]
+        gpu_devices: Option<Vec<u32>>,
+
+        #[arg(long, help = "Requests run at once; overrides `provider.max_concurrent`")]
+        max_concurrent: Option<usize>,
+
+        #[arg(long, help = "Serve Prometheus /metrics on monitoring.prometheus_port")]
+        metrics: bool,
+    },
+}
@@ -0,0 +1,89 @@
+// client/src/completion.rs
+
+//! Shell completion. The scripts from `scoria-cli completions` call back into
+//! the binary with `COMPLETE=<shell>` set, so values such as model IDs come
+//! from current state instead of being baked into the script.
+
+use crate::{
+    config::load_config,
+    core::{
+        cache::known::{KnownModel, KnownModels},
+        indexer::{IndexerClient, SearchQuery},
+    },
+};
+use clap_complete::{env::Shells, CompletionCandidate};
+use std::{ffi::OsStr, io, time::Duration};
+
+/// Environment variable that switches the binary into completion mode
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Tab should not hang on a slow indexer
+const INDEXER_TIMEOUT: Duration = Duration::from_millis(1500);
+const INDEXER_LIMIT: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl CompletionShell {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+        }
+    }
+
+    /// Script registering `bin` as its own completer, to source from the
+    /// shell's startup file
+    pub fn write_registration(self, bin: &str, out: &mut dyn io::Write) -> io::Result<()> {
+        let shells = Shells::builtins();
+        let shell = shells.completer(self.name()).expect("bash, zsh and fish are builtin");
+        shell.write_registration(COMPLETE_VAR, "scoria-cli", bin, bin, out)
+    }
+}
+
+/// Model IDs starting with what was typed: ones this machine has used, then
+/// the indexer's most popular when `network.indexer_url` is set. Reads the
+/// default config, since the command line is not parsed yet.
+pub fn model_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(prefix) = current.to_str() else {
+        return Vec::new();
+    };
+    let Ok(config) = load_config(&None) else {
+        return Vec::new();
+    };
+    let mut known = KnownModels::load(&config.paths.model_cache);
+    if let Some(url) = &config.network.indexer_url {
+        known.merge(indexed(url));
+    }
+    known
+        .iter()
+        .filter(|(id, _)| id.starts_with(prefix))
+        .map(|(id, label)| CompletionCandidate::new(id).help(label.clone().map(Into::into)))
+        .collect()
+}
+
+/// Best effort: nothing on any failure. Runs on its own thread and runtime,
+/// since the completer may be called from inside the CLI's.
+fn indexed(url: &str) -> Vec<KnownModel> {
+    let url = url.to_string();
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().ok()?;
+        runtime.block_on(async {
+            let client = IndexerClient::new(&url).ok()?;
+            let query = SearchQuery { limit: INDEXER_LIMIT, ..Default::default() };
+            tokio::time::timeout(INDEXER_TIMEOUT, client.search(&query)).await.ok()?.ok()
+        })
+    })
+    .join()
+    .ok()
+    .flatten()
+    .unwrap_or_default()
+    .into_iter()
+    .map(|model| (model.id, model.name))
+    .collect()
+}
@@ -0,0 +1,272 @@
+// client/src/config/init.rs
+
+//! First-run setup behind `scoria-cli init`: line prompts, and the config the
+//! answers produce. Network checks and key provisioning stay with the caller.
+
+use super::{
+    profile::{Network, ACTIVE_KEY, PROFILES_KEY},
+    ConfigError, HsmConfig, MasterKeyConfig,
+};
+use rand::{rngs::OsRng, RngCore};
+use std::{
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+const HEADER: &str = "# Generated by `scoria-cli init`; `scoria-cli config show` prints the effective config\n\n";
+
+/// Questions on `output`, answers from `input`; with `assume_defaults` every
+/// question takes its default without reading
+pub struct Prompter<R, W> {
+    input: R,
+    output: W,
+    assume_defaults: bool,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    pub fn new(input: R, output: W, assume_defaults: bool) -> Self {
+        Self { input, output, assume_defaults }
+    }
+
+    /// Free-form answer; empty takes `default`
+    pub fn ask(&mut self, question: &str, default: &str) -> io::Result<String> {
+        if default.is_empty() {
+            write!(self.output, "{question}: ")?;
+        } else {
+            write!(self.output, "{question} [{default}]: ")?;
+        }
+        let answer = self.read()?;
+        Ok(if answer.is_empty() { default.to_string() } else { answer })
+    }
+
+    pub fn confirm(&mut self, question: &str, default: bool) -> io::Result<bool> {
+        loop {
+            write!(self.output, "{question} [{}]: ", if default { "Y/n" } else { "y/N" })?;
+            match self.read()?.to_ascii_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => writeln!(self.output, "Answer y or n")?,
+            }
+        }
+    }
+
+    /// Index into `options`, picked by number or name
+    pub fn choose(&mut self, question: &str, options: &[&str], default: usize) -> io::Result<usize> {
+        writeln!(self.output, "{question}")?;
+        for (i, option) in options.iter().enumerate() {
+            writeln!(self.output, "  {}) {option}", i + 1)?;
+        }
+        loop {
+            write!(self.output, "Choice [{}]: ", default + 1)?;
+            let answer = self.read()?;
+            if answer.is_empty() {
+                return Ok(default);
+            }
+            let picked = match answer.parse::<usize>() {
+                Ok(n) => n.checked_sub(1).filter(|i| *i < options.len()),
+                Err(_) => options.iter().position(|option| option.eq_ignore_ascii_case(&answer)),
+            };
+            match picked {
+                Some(i) => return Ok(i),
+                None => writeln!(self.output, "Pick 1-{}", options.len())?,
+            }
+        }
+    }
+
+    /// Report a step's outcome between questions
+    pub fn say(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.output, "{line}")
+    }
+
+    /// Trimmed line; end of input is an error rather than endless defaults
+    fn read(&mut self) -> io::Result<String> {
+        if self.assume_defaults {
+            writeln!(self.output)?;
+            return Ok(String::new());
+        }
+        self.output.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no answer on stdin"));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+/// What the wizard settled on
+#[derive(Debug, Clone)]
+pub struct Setup {
+    pub network: Network,
+    /// Endpoint other than the cluster's public one, kept in its profile
+    pub rpc_url: Option<String>,
+    pub wallet: PathBuf,
+    /// e.g. `ledger://`; the wallet file is then not used for signing
+    pub signer: Option<String>,
+    /// Caches, audit logs and contribution openings go under it
+    pub data_dir: PathBuf,
+    pub ipfs_api_url: String,
+    /// The key, or where it is kept; see [`super::secret`]
+    pub encryption_key: String,
+    pub master_key: Option<MasterKeyConfig>,
+    pub hsm: Option<HsmConfig>,
+}
+
+impl Setup {
+    /// Config file contents. The network is the active profile, so
+    /// `--network` switches clusters without editing the file.
+    pub fn render(&self) -> String {
+        let mut root = Table::new();
+        root.insert(ACTIVE_KEY.into(), self.network.profile().into());
+
+        if let Some(url) = &self.rpc_url {
+            let network = table([("rpc_url", url.as_str().into())]);
+            let profile = table([("network", Value::Table(network))]);
+            root.insert(PROFILES_KEY.into(), Value::Table(table([(self.network.profile(), Value::Table(profile))])));
+        }
+
+        let mut wallet = table([("path", path(&self.wallet))]);
+        if let Some(signer) = &self.signer {
+            wallet.insert("signer".into(), signer.as_str().into());
+        }
+        root.insert("wallet".into(), Value::Table(wallet));
+
+        let mut security = table([("encryption_key", self.encryption_key.as_str().into())]);
+        if let Some(master) = &self.master_key {
+            security.insert(
+                "master_key".into(),
+                Value::Table(table([
+                    ("key_id", master.key_id.as_str().into()),
+                    ("version", i64::from(master.version).into()),
+                    ("sealed_path", path(&master.sealed_path)),
+                ])),
+            );
+        }
+        if let Some(hsm) = &self.hsm {
+            // The PIN stays out of the file: `SCORIA_HSM_PIN`
+            security.insert(
+                "hsm".into(),
+                Value::Table(table([
+                    ("module", path(&hsm.module)),
+                    ("slot", Value::Integer(hsm.slot as i64)),
+                    ("key_label", hsm.key_label.as_str().into()),
+                ])),
+            );
+        }
+        root.insert("security".into(), Value::Table(security));
+
+        let (model_cache, audit_logs, contributions) = self.data_paths();
+        root.insert(
+            "paths".into(),
+            Value::Table(table([
+                ("model_cache", path(&model_cache)),
+                ("audit_logs", path(&audit_logs)),
+                ("contributions", path(&contributions)),
+            ])),
+        );
+        root.insert("ipfs".into(), Value::Table(table([("api_url", self.ipfs_api_url.as_str().into())])));
+
+        format!("{HEADER}{}", toml::to_string(&root).expect("string-keyed tables serialize"))
+    }
+
+    /// Write the config to `path`, readable by the owner only since it may
+    /// hold the encryption key, and create the data directories it points at
+    pub fn write(&self, path: &Path) -> Result<(), ConfigError> {
+        let write_error = |source| ConfigError::Write { path: path.to_path_buf(), source };
+        let (model_cache, audit_logs, contributions) = self.data_paths();
+        for dir in path.parent().into_iter().chain([model_cache.as_path(), audit_logs.as_path(), contributions.as_path()]) {
+            std::fs::create_dir_all(dir).map_err(write_error)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path).and_then(|mut file| file.write_all(self.render().as_bytes())).map_err(write_error)
+    }
+
+    fn data_paths(&self) -> (PathBuf, PathBuf, PathBuf) {
+        (
+            self.data_dir.join("cache").join("models"),
+            self.data_dir.join("logs").join("audit"),
+            self.data_dir.join("contributions"),
+        )
+    }
+}
+
+/// Random passphrase for `security.encryption_key`
+pub fn generate_encryption_key() -> String {
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    hex::encode(key)
+}
+
+fn table<const N: usize>(entries: [(&str, Value); N]) -> Table {
+    entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect()
+}
+
+fn path(path: &Path) -> Value {
+    path.to_string_lossy().into_owned().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_profile;
+    use std::io::Cursor;
+
+    fn setup(dir: &Path) -> Setup {
+        Setup {
+            network: Network::Devnet,
+            rpc_url: None,
+            wallet: dir.join("id.json"),
+            signer: None,
+            data_dir: dir.join("data"),
+            ipfs_api_url: "http://127.0.0.1:5001".into(),
+            encryption_key: generate_encryption_key(),
+            master_key: None,
+            hsm: None,
+        }
+    }
+
+    #[test]
+    fn test_prompter_defaults_and_retries() {
+        let input = Cursor::new("\nmainnet\n7\n2\nmaybe\nyes\n");
+        let mut output = Vec::new();
+        let mut prompter = Prompter::new(input, &mut output, false);
+        assert_eq!(prompter.ask("RPC", "http://a").unwrap(), "http://a");
+        assert_eq!(prompter.choose("Network", &["devnet", "mainnet"], 0).unwrap(), 1);
+        assert_eq!(prompter.choose("Again", &["devnet", "mainnet"], 0).unwrap(), 1);
+        assert!(prompter.confirm("HSM?", false).unwrap());
+        assert!(prompter.ask("More", "").is_err(), "end of input");
+        assert!(String::from_utf8(output).unwrap().contains("Pick 1-2"));
+
+        let mut prompter = Prompter::new(Cursor::new(""), Vec::new(), true);
+        assert_eq!(prompter.choose("Network", &["devnet", "mainnet"], 1).unwrap(), 1);
+        assert!(!prompter.confirm("HSM?", false).unwrap());
+    }
+
+    #[test]
+    fn test_written_config_loads() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("scoria").join("config.toml");
+        let mut setup = setup(dir.path());
+        setup.write(&path).unwrap();
+        assert!(dir.path().join("data/contributions").is_dir());
+
+        let config = load_profile(&Some(path.clone()), None).unwrap();
+        assert_eq!(config.network.rpc_url, Network::Devnet.rpc_url());
+        assert_eq!(config.wallet.path, setup.wallet);
+        assert_eq!(config.paths.contributions, dir.path().join("data/contributions"));
+
+        // A custom endpoint stays with its network, not every profile
+        setup.rpc_url = Some("http://rpc.internal:8899".into());
+        setup.signer = Some("ledger://".into());
+        setup.write(&path).unwrap();
+        let config = load_profile(&Some(path.clone()), None).unwrap();
+        assert_eq!(config.network.rpc_url, "http://rpc.internal:8899");
+        assert_eq!(config.wallet.signer.as_deref(), Some("ledger://"));
+        let mainnet = load_profile(&Some(path.clone()), Some(Network::Mainnet.profile())).unwrap();
+        assert_eq!(mainnet.network.rpc_url, Network::Mainnet.rpc_url());
+    }
+}
@@ -0,0 +1,787 @@
+// client/src/config/mod.rs
+
+pub mod init;
+pub mod profile;
+pub mod programs;
+pub mod secret;
+
+use crate::core::model_loader::{aes::KdfParams, format::CipherId, recipient::KemId};
+use programs::ProgramIds;
+use scoria_rpc::FailoverConfig;
+use secret::{SecretError, SecretSource};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Failed to read config {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to write config {path}: {source}")]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid config: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Invalid config `{key}`: {reason}")]
+    Invalid { key: String, reason: String },
+    #[error("Unknown profile `{0}`")]
+    UnknownProfile(String),
+    #[error("Profile inheritance cycle: {0}")]
+    ProfileCycle(String),
+}
+
+/// Top-level client configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScoriaConfig {
+    pub network: NetworkConfig,
+    /// Overrides of the compiled-in program IDs, for clusters other than mainnet
+    #[serde(default)]
+    pub programs: ProgramIds,
+    pub wallet: WalletConfig,
+    pub security: SecurityConfig,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub model: ModelConfig,
+    #[serde(default)]
+    pub zkp: ZkpConfig,
+    #[serde(default)]
+    pub monitoring: MonitoringConfig,
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+    #[serde(default)]
+    pub tee: TeeConfig,
+    #[serde(default)]
+    pub ipfs: IpfsConfig,
+    #[serde(default)]
+    pub distribution: DistributionConfig,
+    #[serde(default)]
+    pub serve: ServeConfig,
+    #[serde(default)]
+    pub provider: ProviderConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkConfig {
+    pub rpc_url: String,
+    /// Tried in order when `rpc_url` is rate limited, unreachable or lagging
+    #[serde(default)]
+    pub rpc_fallbacks: Vec<String>,
+    #[serde(default)]
+    pub failover: FailoverConfig,
+    #[serde(default)]
+    pub priority_fee: PriorityFeeConfig,
+    /// Indexer REST API used by `model search`, e.g. `https://indexer.scoria.network`
+    #[serde(default)]
+    pub indexer_url: Option<String>,
+}
+
+impl NetworkConfig {
+    /// `rpc_url` followed by the fallbacks, in order of preference
+    pub fn rpc_endpoints(&self) -> Vec<String> {
+        std::iter::once(self.rpc_url.clone()).chain(self.rpc_fallbacks.iter().cloned()).collect()
+    }
+}
+
+/// Compute-budget and priority-fee policy applied to every transaction
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PriorityFeeConfig {
+    pub mode: FeeMode,
+    /// Fixed price, and the floor for dynamic bids (micro-lamports per CU)
+    pub micro_lamports: u64,
+    pub max_micro_lamports: u64,
+    /// Percentile of recent prioritization fees to bid at
+    pub percentile: u8,
+    /// Multiplier on simulated compute units
+    pub compute_unit_margin: f64,
+    pub max_retries: u32,
+    /// Fee multiplier per retry under congestion
+    pub escalation: f64,
+    pub retry_backoff_ms: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            mode: FeeMode::Dynamic,
+            micro_lamports: 0,
+            max_micro_lamports: 1_000_000,
+            percentile: 75,
+            compute_unit_margin: 1.1,
+            max_retries: 3,
+            escalation: 1.5,
+            retry_backoff_ms: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeMode {
+    Fixed,
+    #[default]
+    Dynamic,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WalletConfig {
+    /// JSON keypair, or a keystore from `wallet new`/`wallet export --encrypted`
+    pub path: PathBuf,
+    /// Default signer URI, e.g. `ledger://`; overridden by `--signer`
+    #[serde(default)]
+    pub signer: Option<String>,
+    /// Hardware wallet derivation path, e.g. `0/0`
+    #[serde(default)]
+    pub derivation_path: Option<String>,
+    /// Require on-device confirmation of the derived public key
+    #[serde(default)]
+    pub confirm_key: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityConfig {
+    /// `keychain://<entry>` or `keystore://<path>`; a plain value is the key itself
+    pub encryption_key: String,
+    /// Enables envelope encryption of model data keys
+    #[serde(default)]
+    pub master_key: Option<MasterKeyConfig>,
+    /// PKCS#11 token holding the key-encryption key; supersedes `master_key` for new models
+    #[serde(default)]
+    pub hsm: Option<HsmConfig>,
+    /// Argon2 costs for new password-encrypted files; `keys calibrate` picks them for this host
+    #[serde(default)]
+    pub kdf: KdfParams,
+    /// Cipher for new encrypted files; `xchacha20-poly1305` is faster on CPUs without AES instructions
+    #[serde(default)]
+    pub cipher: CipherId,
+    /// Wrapping of data keys shared with inference providers; `x25519-mlkem768`
+    /// adds ML-KEM-768 and needs the post-quantum feature
+    #[serde(default)]
+    pub share_kem: KemId,
+    /// Seed of this node's recipient keys; providers open shared and
+    /// re-encrypted data keys with it, owners issue re-encryption keys
+    #[serde(default)]
+    pub recipient_seed: Option<PathBuf>,
+}
+
+impl SecurityConfig {
+    /// `encryption_key`, read from the store it names
+    pub fn encryption_key(&self) -> Result<Zeroizing<String>, SecretError> {
+        SecretSource::parse(&self.encryption_key).resolve()
+    }
+}
+
+/// Agent behind `scoria-cli unlock`; its socket is `SCORIA_AGENT_SOCK` or a
+/// per-user default, so key loading finds it without a config
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// Seconds without use before unlocked keys are wiped
+    pub auto_lock_secs: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self { auto_lock_secs: 15 * 60 }
+    }
+}
+
+/// Hardware-sealed key-encryption key
+#[derive(Debug, Clone, Deserialize)]
+pub struct MasterKeyConfig {
+    pub key_id: String,
+    pub version: u32,
+    pub sealed_path: PathBuf,
+}
+
+/// PKCS#11 module and token, e.g. `module = "/usr/lib/softhsm/libsofthsm2.so"`
+#[derive(Debug, Clone, Deserialize)]
+pub struct HsmConfig {
+    pub module: PathBuf,
+    pub slot: u64,
+    /// User PIN; read from `SCORIA_HSM_PIN` when unset
+    #[serde(default)]
+    pub pin: Option<String>,
+    /// Label of the AES-256 key-encryption key on the token
+    pub key_label: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PathsConfig {
+    pub model_cache: PathBuf,
+    pub audit_logs: PathBuf,
+    /// Openings of dataset contributions, for `contribute prove-inclusion`
+    #[serde(default = "default_contributions_dir")]
+    pub contributions: PathBuf,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            model_cache: PathBuf::from("./.cache/models"),
+            audit_logs: PathBuf::from("./logs/audit"),
+            contributions: default_contributions_dir(),
+        }
+    }
+}
+
+fn default_contributions_dir() -> PathBuf {
+    PathBuf::from("./.scoria/contributions")
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ZkpConfig {
+    /// Accelerator for Groth16 MSM, e.g. `gpu_backend = "wgpu"`
+    #[serde(default)]
+    pub gpu_backend: GpuBackend,
+    #[serde(default)]
+    pub registry: CircuitRegistryConfig,
+    /// Block template key to hex circuit hash, for chunked proving, e.g. `"gemm+relu:128x784" = "1f3a..."`
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
+    #[serde(default)]
+    pub witness_cache: WitnessCacheConfig,
+    /// Offload proving to a `scoria-prover serve` daemon, e.g. `https://prover.internal:8443`
+    #[serde(default)]
+    pub remote_prover_url: Option<String>,
+    #[serde(default)]
+    pub remote_prover: RemoteProverConfig,
+}
+
+/// TLS and trust settings for `zkp.remote_prover_url`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RemoteProverConfig {
+    /// CA bundle (PEM) for the prover's certificate, in addition to the system roots
+    pub ca_cert: Option<PathBuf>,
+    /// Client certificate and key (PEM) for mutual TLS
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+    /// Expected prover identity (hex Ed25519 key); any self-consistent prover when unset
+    pub identity: Option<String>,
+    /// Accepted prover binary hashes (hex blake3); any when empty
+    pub allowed_binaries: Vec<String>,
+    pub timeout_secs: u64,
+}
+
+impl Default for RemoteProverConfig {
+    fn default() -> Self {
+        Self {
+            ca_cert: None,
+            client_cert: None,
+            client_key: None,
+            identity: None,
+            allowed_binaries: Vec::new(),
+            timeout_secs: 600,
+        }
+    }
+}
+
+/// Trust policy for SGX/SEV-SNP quotes produced by TEE-attested inference
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TeeConfig {
+    /// Accepted MRENCLAVE (SGX, 32 bytes) or launch measurement (SNP, 48 bytes), hex
+    pub allowed_measurements: Vec<String>,
+    /// PCK certificate public key (hex SEC1 P-256) that signs the SGX quoting enclave report
+    pub pck_key: Option<String>,
+    /// VCEK public key (hex SEC1 P-384) of the SNP host
+    pub vcek_key: Option<String>,
+    /// How long a quote nonce is remembered for replay protection
+    pub nonce_ttl_secs: u64,
+    pub nonce_store: NonceStoreConfig,
+}
+
+impl Default for TeeConfig {
+    fn default() -> Self {
+        Self {
+            allowed_measurements: Vec::new(),
+            pck_key: None,
+            vcek_key: None,
+            nonce_ttl_secs: 3600,
+            nonce_store: NonceStoreConfig::default(),
+        }
+    }
+}
+
+/// Where used quote nonces are kept, e.g. `backend = "redis"` for verifiers on several nodes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NonceStoreConfig {
+    pub backend: NonceBackend,
+    /// sled journal directory
+    pub path: Option<PathBuf>,
+    /// TPM NV counter index bound to the sled journal against snapshot rollback
+    pub tpm_nv_index: Option<u32>,
+    pub redis_url: Option<String>,
+    pub key_prefix: String,
+}
+
+impl Default for NonceStoreConfig {
+    fn default() -> Self {
+        Self {
+            backend: NonceBackend::Memory,
+            path: None,
+            tpm_nv_index: None,
+            redis_url: None,
+            key_prefix: "scoria:nonce:".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NonceBackend {
+    /// Process-local, lost on restart
+    #[default]
+    Memory,
+    Sled,
+    Redis,
+}
+
+/// Reuse of computed witnesses across proofs over the same circuit and inputs
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WitnessCacheConfig {
+    /// Witnesses kept in memory; 0 disables the cache
+    pub capacity: usize,
+    /// Also write witnesses under `<paths.model_cache>/witnesses`; they include private inputs
+    pub persist: bool,
+}
+
+impl Default for WitnessCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 64,
+            persist: false,
+        }
+    }
+}
+
+/// Content-addressed store for downloaded models and proofs under `<paths.model_cache>/store`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Total size of stored objects, in bytes, before entries are evicted
+    pub max_bytes: u64,
+    pub eviction: EvictionPolicy,
+    pub results: ResultCacheConfig,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 10 * 1024 * 1024 * 1024,
+            eviction: EvictionPolicy::Lru,
+            results: ResultCacheConfig::default(),
+        }
+    }
+}
+
+/// Inference results shared across a provider fleet through Redis
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ResultCacheConfig {
+    /// Disabled when unset and `cluster_nodes` is empty
+    pub redis_url: Option<String>,
+    /// Seed nodes of a Redis Cluster, used instead of `redis_url`
+    pub cluster_nodes: Vec<String>,
+    pub key_prefix: String,
+    pub ttl_secs: u64,
+    /// How long one node may hold the right to compute a missing result;
+    /// others wait this long for it before computing themselves
+    pub lock_ttl_ms: u64,
+    pub poll_interval_ms: u64,
+}
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self {
+            redis_url: None,
+            cluster_nodes: Vec::new(),
+            key_prefix: "scoria:result:".to_string(),
+            ttl_secs: 24 * 60 * 60,
+            lock_ttl_ms: 120_000,
+            poll_interval_ms: 250,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ModelConfig {
+    /// Bytes of downloaded models kept in the cache; least recently used models
+    /// not in use are evicted before a download that would exceed it
+    pub max_cache_size: u64,
+    /// Applied to models and version patches before encryption
+    pub compression: CompressionConfig,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self {
+            max_cache_size: 8 * 1024 * 1024 * 1024,
+            compression: CompressionConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CompressionConfig {
+    pub codec: CompressionCodec,
+    /// zstd level (1-22) or brotli quality (0-11); the codec's default when
+    /// unset. lz4 has no levels.
+    pub level: Option<i32>,
+    /// Chunks are compressed independently and in parallel
+    pub chunk_size: usize,
+    /// Train a zstd dictionary on the model's tensor data, recovering
+    /// redundancy across chunk boundaries
+    pub train_dictionary: bool,
+    pub dictionary_size: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            codec: CompressionCodec::Zstd,
+            level: None,
+            chunk_size: 4 * 1024 * 1024,
+            train_dictionary: true,
+            dictionary_size: 112 * 1024,
+        }
+    }
+}
+
+/// The codec is recorded in each container, so changing it never breaks loading
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionCodec {
+    /// Best ratio for the time; the only codec that uses a trained dictionary
+    #[default]
+    Zstd,
+    /// Fastest to decompress, for latency-sensitive providers
+    Lz4,
+    /// Highest ratio, slowest to write
+    Brotli,
+    None,
+}
+
+/// Which entries go first when the store is over budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EvictionPolicy {
+    /// Least recently read or written
+    #[default]
+    Lru,
+    /// Fewest hits, least recently used among ties
+    Lfu,
+    /// Largest objects
+    Size,
+}
+
+/// Where circuits and proving keys are fetched from and cached
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CircuitRegistryConfig {
+    /// Registry index (`ipfs://`, `ar://` or `file://`) mapping circuit hashes to manifests
+    pub index_uri: String,
+    pub ipfs_gateway: String,
+    pub arweave_gateway: String,
+    pub cache_dir: PathBuf,
+    /// Circuit name to the only version this client will load, e.g. `inference = "1.2.0"`
+    pub pins: HashMap<String, String>,
+}
+
+impl Default for CircuitRegistryConfig {
+    fn default() -> Self {
+        Self {
+            index_uri: "file://./circuits/index.json".into(),
+            ipfs_gateway: "https://ipfs.io".into(),
+            arweave_gateway: "https://arweave.net".into(),
+            cache_dir: PathBuf::from("./.cache/circuits"),
+            pins: HashMap::new(),
+        }
+    }
+}
+
+/// IPFS node that artifacts are added to, and where they are kept pinned
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct IpfsConfig {
+    /// Kubo RPC API, e.g. `http://127.0.0.1:5001`
+    pub api_url: String,
+    pub pinning_service: Option<PinningServiceConfig>,
+}
+
+impl Default for IpfsConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "http://127.0.0.1:5001".into(),
+            pinning_service: None,
+        }
+    }
+}
+
+/// Remote IPFS Pinning Service API, e.g. `endpoint = "https://api.pinata.cloud/psa"`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PinningServiceConfig {
+    pub endpoint: String,
+    /// Bearer token; read from `SCORIA_PINNING_TOKEN` when unset
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// Pin requests per artifact, counting re-pins after a failure
+    #[serde(default = "default_pin_attempts")]
+    pub max_attempts: u32,
+    /// How long uploads wait for `pinned` before leaving the service to finish
+    #[serde(default = "default_pin_timeout_secs")]
+    pub pin_timeout_secs: u64,
+    #[serde(default = "default_pin_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_pin_attempts() -> u32 {
+    3
+}
+
+fn default_pin_timeout_secs() -> u64 {
+    300
+}
+
+fn default_pin_poll_interval_secs() -> u64 {
+    5
+}
+
+/// Erasure coding of deployed models, and the gateways shards are fetched from
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DistributionConfig {
+    /// Shards any of which rebuild a model
+    pub data_shards: usize,
+    /// Extra shards; up to this many can be lost or corrupt. At least 1.
+    pub parity_shards: usize,
+    pub ipfs_gateways: Vec<String>,
+    pub arweave_gateways: Vec<String>,
+    /// Bundler that signs and pays for Arweave uploads (`POST /tx` of raw
+    /// bytes, returning `{"id"}`); shards go to IPFS only when unset
+    pub arweave_bundler: Option<String>,
+    /// Bearer token; read from `SCORIA_ARWEAVE_TOKEN` when unset
+    pub arweave_token: Option<String>,
+    /// Shard uploads and downloads in flight at once
+    pub max_parallel: usize,
+    pub shard_timeout_secs: u64,
+}
+
+impl Default for DistributionConfig {
+    fn default() -> Self {
+        Self {
+            data_shards: 8,
+            parity_shards: 4,
+            ipfs_gateways: vec!["https://ipfs.io".into(), "https://dweb.link".into(), "https://w3s.link".into()],
+            arweave_gateways: vec!["https://arweave.net".into()],
+            arweave_bundler: None,
+            arweave_token: None,
+            max_parallel: 8,
+            shard_timeout_secs: 120,
+        }
+    }
+}
+
+/// `scoria-cli serve` inference daemon
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ServeConfig {
+    pub listen: SocketAddr,
+    /// Bytes of decrypted models kept in memory; least recently used models
+    /// are dropped beyond it
+    pub warm_bytes: u64,
+    /// How long a warm model is trusted before its account is re-read for a new version
+    pub refresh_secs: u64,
+    /// Inferences run at once; further requests wait in the queue
+    pub max_concurrent: usize,
+    /// Requests waiting beyond this are rejected with 503
+    pub queue_depth: usize,
+    pub max_request_bytes: usize,
+    /// Callers and their limits; with none configured, every caller shares the
+    /// default limit as one anonymous key
+    pub api_keys: Vec<ApiKeyConfig>,
+    pub requests_per_minute: u32,
+    pub burst: u32,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            listen: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            warm_bytes: 4 * 1024 * 1024 * 1024,
+            refresh_secs: 300,
+            max_concurrent: 4,
+            queue_depth: 64,
+            max_request_bytes: 32 * 1024 * 1024,
+            api_keys: Vec::new(),
+            requests_per_minute: 60,
+            burst: 10,
+        }
+    }
+}
+
+/// `scoria-provider` worker; warm models are bounded by `[serve]`'s `warm_bytes`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ProviderConfig {
+    /// How often Pending requests are listed
+    pub poll_secs: u64,
+    /// Claim length; a crashed worker's requests reopen after this
+    pub claim_secs: i64,
+    /// Requests claimed and running at once
+    pub max_concurrent: usize,
+    /// Largest request input fetched from its `input_uri`
+    pub max_input_bytes: usize,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self { poll_secs: 10, claim_secs: 600, max_concurrent: 4, max_input_bytes: 32 * 1024 * 1024 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Reported in logs and metrics instead of the key
+    pub name: String,
+    /// SHA2-256 of the bearer token, so the config never holds the key itself
+    #[serde(with = "hex::serde")]
+    pub key_sha256: [u8; 32],
+    /// Overrides of the server-wide limits
+    pub requests_per_minute: Option<u32>,
+    pub burst: Option<u32>,
+}
+
+/// Proving backend; `wgpu` covers Vulkan, Metal and DX12 GPUs without CUDA
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuBackend {
+    #[default]
+    Cuda,
+    Wgpu,
+    Cpu,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MonitoringConfig {
+    /// Port for the Prometheus `/metrics` endpoint, served when `--metrics` is passed
+    pub prometheus_port: u16,
+    /// OTLP/gRPC collector for traces; needs the `telemetry` feature
+    pub otlp_endpoint: Option<String>,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            prometheus_port: 9090,
+            otlp_endpoint: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PrivacyConfig {
+    /// k-anonymity over quasi-identifiers, enforced before a contribution is encrypted
+    #[serde(default)]
+    pub anonymity: Option<AnonymityConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnonymityConfig {
+    pub k: usize,
+    /// Minimum distinct sensitive values per equivalence class
+    #[serde(default)]
+    pub l_diversity: Option<usize>,
+    #[serde(default)]
+    pub sensitive_column: Option<String>,
+    /// Fraction of records that may be suppressed instead of generalizing further
+    #[serde(default = "default_max_suppression")]
+    pub max_suppression: f64,
+    pub quasi_identifiers: Vec<QuasiIdentifierConfig>,
+}
+
+fn default_max_suppression() -> f64 {
+    0.05
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuasiIdentifierConfig {
+    pub column: String,
+    #[serde(flatten)]
+    pub hierarchy: Hierarchy,
+}
+
+/// How a quasi-identifier is coarsened, one level at a time
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "hierarchy", rename_all = "lowercase")]
+pub enum Hierarchy {
+    /// Numeric ranges starting at `width`, doubling for each of `levels`
+    Range { width: f64, levels: u8 },
+    /// Mask trailing characters, one per level, e.g. zip codes
+    Prefix { length: u8 },
+    /// Exact value or `*`
+    Suppress,
+}
+
+/// `path`, or `config.toml` in the working directory
+pub fn config_path(path: &Option<PathBuf>) -> &Path {
+    path.as_deref().unwrap_or_else(|| Path::new(DEFAULT_CONFIG_PATH))
+}
+
+/// Load configuration from `path`, or `config.toml` in the working directory,
+/// with the file's active profile applied
+pub fn load_config(path: &Option<PathBuf>) -> Result<ScoriaConfig, ConfigError> {
+    load_profile(path, None)
+}
+
+/// Load configuration with `profile`, or the file's active profile, applied
+pub fn load_profile(path: &Option<PathBuf>, profile: Option<&str>) -> Result<ScoriaConfig, ConfigError> {
+    let resolved = profile::resolve(read_table(path)?, profile)?;
+    Ok(toml::Value::Table(resolved).try_into()?)
+}
+
+/// Keys whose values `config show` masks, wherever they appear
+const SECRET_KEYS: [&str; 4] = ["encryption_key", "pin", "access_token", "arweave_token"];
+
+/// Mask secrets in a resolved table before it is printed
+pub fn redact(table: &mut toml::Table) {
+    for (key, value) in table.iter_mut() {
+        match value {
+            toml::Value::Table(inner) => redact(inner),
+            toml::Value::String(s) if SecretSource::is_reference(s) => {}
+            _ if SECRET_KEYS.contains(&key.as_str()) => *value = toml::Value::String("<redacted>".into()),
+            _ => {}
+        }
+    }
+}
+
+/// The raw file, profiles unresolved
+pub fn read_table(path: &Option<PathBuf>) -> Result<toml::Table, ConfigError> {
+    let path = config_path(path);
+    let raw = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(toml::from_str(&raw)?)
+}
@@ -0,0 +1,324 @@
+// client/src/config/profile.rs
+
+//! Named profiles layered over the base config. A profile is a partial config
+//! under `[profiles.<name>]`; `inherits = "<other>"` layers it over another
+//! profile first. Tables merge key by key, anything else is replaced:
+//!
+//! ```toml
+//! profile = "devnet"            # active unless --profile/--network is given
+//!
+//! [profiles.devnet.network]
+//! rpc_url = "https://api.devnet.solana.com"
+//!
+//! [profiles.staging]
+//! inherits = "devnet"
+//! programs.model_registry = "Stg1..."
+//! ```
+//!
+//! `devnet`, `mainnet` and `localnet` work without being declared: they point
+//! `network.rpc_url` at the cluster, and a declared one starts from that.
+
+use super::ConfigError;
+use std::path::Path;
+use toml::{Table, Value};
+use toml_edit::{Document, Item};
+
+/// Key of the active profile's name
+pub const ACTIVE_KEY: &str = "profile";
+/// Table holding the profiles
+pub const PROFILES_KEY: &str = "profiles";
+const INHERITS_KEY: &str = "inherits";
+
+/// Clusters selectable with `--network`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Network {
+    Devnet,
+    Mainnet,
+    Localnet,
+}
+
+impl Network {
+    pub fn profile(self) -> &'static str {
+        match self {
+            Network::Devnet => "devnet",
+            Network::Mainnet => "mainnet",
+            Network::Localnet => "localnet",
+        }
+    }
+
+    /// Public RPC endpoint of the cluster
+    pub fn rpc_url(self) -> &'static str {
+        match self {
+            Network::Devnet => "https://api.devnet.solana.com",
+            Network::Mainnet => "https://api.mainnet-beta.solana.com",
+            Network::Localnet => "http://127.0.0.1:8899",
+        }
+    }
+
+    /// Identifies the cluster behind an endpoint; a localnet's is fresh per ledger
+    pub fn genesis_hash(self) -> Option<&'static str> {
+        match self {
+            Network::Devnet => Some("EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG"),
+            Network::Mainnet => Some("5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d"),
+            Network::Localnet => None,
+        }
+    }
+
+    fn from_profile(name: &str) -> Option<Self> {
+        [Network::Devnet, Network::Mainnet, Network::Localnet].into_iter().find(|n| n.profile() == name)
+    }
+}
+
+/// Profile to apply: `selected` when given, else the file's active profile
+pub fn active<'a>(root: &'a Table, selected: Option<&'a str>) -> Option<&'a str> {
+    selected.or_else(|| root.get(ACTIVE_KEY).and_then(Value::as_str))
+}
+
+/// Declared profiles, then built-in networks not redeclared
+pub fn names(root: &Table) -> Vec<String> {
+    let mut names: Vec<String> = declared(root).map(|profiles| profiles.keys().cloned().collect()).unwrap_or_default();
+    for network in [Network::Devnet, Network::Mainnet, Network::Localnet] {
+        if !names.iter().any(|name| name == network.profile()) {
+            names.push(network.profile().to_string());
+        }
+    }
+    names
+}
+
+/// Whether `name` can be selected
+pub fn exists(root: &Table, name: &str) -> bool {
+    declared(root).is_some_and(|profiles| profiles.contains_key(name)) || Network::from_profile(name).is_some()
+}
+
+/// The base config with the selected profile and its ancestors applied, and
+/// the profile keys removed
+pub fn resolve(mut root: Table, selected: Option<&str>) -> Result<Table, ConfigError> {
+    let name = active(&root, selected).map(str::to_string);
+    let profiles = match root.remove(PROFILES_KEY) {
+        Some(Value::Table(profiles)) => profiles,
+        Some(_) => return Err(invalid(PROFILES_KEY, "must be a table")),
+        None => Table::new(),
+    };
+    root.remove(ACTIVE_KEY);
+    let Some(name) = name else {
+        return Ok(root);
+    };
+
+    // Most derived first
+    let mut chain: Vec<Table> = Vec::new();
+    let mut visited: Vec<String> = Vec::new();
+    let mut next = Some(name);
+    while let Some(name) = next.take() {
+        if visited.contains(&name) {
+            visited.push(name);
+            return Err(ConfigError::ProfileCycle(visited.join(" -> ")));
+        }
+        let mut layer = match profiles.get(&name) {
+            Some(Value::Table(layer)) => match Network::from_profile(&name) {
+                Some(network) => {
+                    let mut base = builtin(network);
+                    merge(&mut base, layer.clone());
+                    base
+                }
+                None => layer.clone(),
+            },
+            Some(_) => return Err(invalid(&format!("{PROFILES_KEY}.{name}"), "must be a table")),
+            None => match Network::from_profile(&name) {
+                Some(network) => builtin(network),
+                None => return Err(ConfigError::UnknownProfile(name)),
+            },
+        };
+        next = match layer.remove(INHERITS_KEY) {
+            Some(Value::String(parent)) => Some(parent),
+            Some(_) => return Err(invalid(&format!("{PROFILES_KEY}.{name}.{INHERITS_KEY}"), "must be a string")),
+            None => None,
+        };
+        visited.push(name);
+        chain.push(layer);
+    }
+
+    for layer in chain.into_iter().rev() {
+        merge(&mut root, layer);
+    }
+    Ok(root)
+}
+
+/// Make `name` the active profile in the file at `path`
+pub fn activate(path: &Path, name: &str) -> Result<(), ConfigError> {
+    edit(path, |doc| {
+        doc[ACTIVE_KEY] = toml_edit::value(name);
+        Ok(())
+    })
+}
+
+/// Set dotted `key` to `value` in the file at `path`, under `profile` when
+/// given. `value` is read as TOML (numbers, booleans, arrays), else as a
+/// string. Comments and layout elsewhere in the file are kept.
+pub fn set(path: &Path, profile: Option<&str>, key: &str, value: &str) -> Result<(), ConfigError> {
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|part| part.is_empty()) || matches!(parts[..], [ACTIVE_KEY] | [PROFILES_KEY, ..]) {
+        return Err(invalid(key, "not a settable key; use `config use` to switch profiles"));
+    }
+    let value = value.parse::<toml_edit::Value>().unwrap_or_else(|_| value.into());
+    edit(path, |doc| {
+        let mut item = doc.as_item_mut();
+        let prefix = profile.map(|name| [PROFILES_KEY, name]);
+        let (last, parents) = parts.split_last().expect("split yields a part");
+        for part in prefix.iter().flatten().chain(parents) {
+            if item.get(part).is_none() {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                item[part] = Item::Table(table);
+            }
+            item = &mut item[part];
+            if !item.is_table_like() {
+                return Err(invalid(key, &format!("`{part}` is not a table")));
+            }
+        }
+        item[last] = Item::Value(value);
+        Ok(())
+    })
+}
+
+fn edit(path: &Path, apply: impl FnOnce(&mut Document) -> Result<(), ConfigError>) -> Result<(), ConfigError> {
+    let raw = std::fs::read_to_string(path).map_err(|source| ConfigError::Io { path: path.to_path_buf(), source })?;
+    let mut doc: Document = raw
+        .parse()
+        .map_err(|e: toml_edit::TomlError| invalid(&path.display().to_string(), &e.to_string()))?;
+    apply(&mut doc)?;
+    std::fs::write(path, doc.to_string()).map_err(|source| ConfigError::Write { path: path.to_path_buf(), source })
+}
+
+fn declared(root: &Table) -> Option<&Table> {
+    root.get(PROFILES_KEY).and_then(Value::as_table)
+}
+
+fn builtin(network: Network) -> Table {
+    let mut rpc = Table::new();
+    rpc.insert("rpc_url".to_string(), Value::String(network.rpc_url().to_string()));
+    let mut layer = Table::new();
+    layer.insert("network".to_string(), Value::Table(rpc));
+    layer
+}
+
+/// Tables merge recursively; arrays and scalars are replaced
+fn merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(layer)) => merge(base, layer),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn invalid(key: &str, reason: &str) -> ConfigError {
+    ConfigError::Invalid { key: key.to_string(), reason: reason.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(toml: &str) -> Table {
+        toml.parse().unwrap()
+    }
+
+    const CONFIG: &str = r#"
+        profile = "devnet"
+
+        [network]
+        rpc_url = "http://base"
+        rpc_fallbacks = ["http://a", "http://b"]
+
+        [ipfs]
+        gateway = "http://ipfs-base"
+        timeout_secs = 30
+
+        [profiles.devnet.network]
+        rpc_url = "http://devnet"
+
+        [profiles.staging]
+        inherits = "devnet"
+        ipfs = { gateway = "http://ipfs-staging" }
+        network = { rpc_fallbacks = ["http://c"] }
+    "#;
+
+    #[test]
+    fn test_no_profile_leaves_base() {
+        let mut root = table(CONFIG);
+        root.remove(ACTIVE_KEY);
+        let resolved = resolve(root, None).unwrap();
+        assert_eq!(resolved["network"]["rpc_url"].as_str(), Some("http://base"));
+        assert!(!resolved.contains_key(PROFILES_KEY));
+    }
+
+    #[test]
+    fn test_active_profile_and_inheritance() {
+        let resolved = resolve(table(CONFIG), None).unwrap();
+        assert_eq!(resolved["network"]["rpc_url"].as_str(), Some("http://devnet"));
+        assert!(!resolved.contains_key(ACTIVE_KEY));
+
+        let resolved = resolve(table(CONFIG), Some("staging")).unwrap();
+        assert_eq!(resolved["network"]["rpc_url"].as_str(), Some("http://devnet"));
+        // Arrays are replaced, sibling keys kept
+        assert_eq!(resolved["network"]["rpc_fallbacks"].as_array().unwrap().len(), 1);
+        assert_eq!(resolved["ipfs"]["gateway"].as_str(), Some("http://ipfs-staging"));
+        assert_eq!(resolved["ipfs"]["timeout_secs"].as_integer(), Some(30));
+    }
+
+    #[test]
+    fn test_builtin_networks() {
+        let resolved = resolve(table(CONFIG), Some(Network::Mainnet.profile())).unwrap();
+        assert_eq!(resolved["network"]["rpc_url"].as_str(), Some("https://api.mainnet-beta.solana.com"));
+
+        let declared = table("[profiles.localnet.ipfs]\ngateway = \"http://127.0.0.1:8080\"");
+        let resolved = resolve(declared, Some("localnet")).unwrap();
+        assert_eq!(resolved["network"]["rpc_url"].as_str(), Some("http://127.0.0.1:8899"));
+        assert_eq!(resolved["ipfs"]["gateway"].as_str(), Some("http://127.0.0.1:8080"));
+        assert!(exists(&table(CONFIG), "localnet"));
+        assert_eq!(names(&table(CONFIG)), ["devnet", "staging", "mainnet", "localnet"]);
+    }
+
+    #[test]
+    fn test_set_and_activate_keep_the_rest_of_the_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, CONFIG.replace("profile = \"devnet\"", "# Active profile\nprofile = \"devnet\"")).unwrap();
+
+        set(&path, Some("staging"), "network.rpc_url", "http://staging").unwrap();
+        set(&path, None, "ipfs.timeout_secs", "60").unwrap();
+        set(&path, Some("prod"), "programs.model_registry", "Reg111").unwrap();
+        activate(&path, "staging").unwrap();
+        assert!(set(&path, None, "profiles.devnet.network.rpc_url", "x").is_err());
+        assert!(set(&path, None, "network.rpc_url.host", "x").is_err());
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("# Active profile"));
+        let resolved = resolve(table(&raw), None).unwrap();
+        assert_eq!(resolved["network"]["rpc_url"].as_str(), Some("http://staging"));
+        assert_eq!(resolved["ipfs"]["timeout_secs"].as_integer(), Some(60));
+        assert_eq!(resolved["ipfs"]["gateway"].as_str(), Some("http://ipfs-staging"));
+        let prod = resolve(table(&raw), Some("prod")).unwrap();
+        assert_eq!(prod["programs"]["model_registry"].as_str(), Some("Reg111"));
+    }
+
+    #[test]
+    fn test_unknown_and_cyclic_profiles() {
+        assert!(matches!(resolve(table(CONFIG), Some("nope")), Err(ConfigError::UnknownProfile(_))));
+
+        let cyclic = table(
+            r#"
+            [profiles.a]
+            inherits = "b"
+            [profiles.b]
+            inherits = "a"
+            "#,
+        );
+        match resolve(cyclic, Some("a")) {
+            Err(ConfigError::ProfileCycle(chain)) => assert_eq!(chain, "a -> b -> a"),
+            other => panic!("expected a cycle, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// client/src/config/programs.rs
+
+//! Program IDs of the network the client talks to. The compiled-in IDs are
+//! the mainnet deployments; `[programs]` overrides them for other clusters,
+//! e.g. a localnet with freshly deployed programs.
+
+use serde::{Deserialize, Deserializer};
+use solana_sdk::pubkey::Pubkey;
+use std::{str::FromStr, sync::OnceLock};
+
+static ACTIVE: OnceLock<ProgramIds> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ProgramIds {
+    #[serde(deserialize_with = "base58")]
+    pub model_registry: Pubkey,
+    #[serde(deserialize_with = "base58")]
+    pub federation: Pubkey,
+    #[serde(deserialize_with = "base58")]
+    pub governance: Pubkey,
+}
+
+impl Default for ProgramIds {
+    fn default() -> Self {
+        Self { model_registry: model_registry::ID, federation: federation::ID, governance: dao::ID }
+    }
+}
+
+impl ProgramIds {
+    /// Make these the IDs every operation uses. Call after loading the config
+    /// and before any chain access; the first call wins.
+    pub fn install(self) {
+        if let Err(ids) = ACTIVE.set(self) {
+            if ids != *program_ids() {
+                tracing::warn!("Program IDs already installed; keeping the first set");
+            }
+        }
+    }
+}
+
+/// IDs installed from the config, or the compiled-in ones
+pub fn program_ids() -> &'static ProgramIds {
+    ACTIVE.get_or_init(ProgramIds::default)
+}
+
+fn base58<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    Pubkey::from_str(&s).map_err(|e| serde::de::Error::custom(format!("{s}: {e}")))
+}
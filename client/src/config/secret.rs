@@ -0,0 +1,169 @@
+// client/src/config/secret.rs
+
+//! Where `security.encryption_key` is kept. The config holds a reference
+//! instead of the key: `keychain://<entry>` for the OS secret store (macOS
+//! Keychain, Windows Credential Manager under DPAPI, Secret Service on Linux)
+//! or `keystore://<path>` for a passphrase-protected keystore file. Any other
+//! value is the key itself, still accepted from older configs.
+
+use crate::wallet::keystore::{new_passphrase, KeyFile, Keystore, KeystoreError};
+use rand::{rngs::OsRng, RngCore};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+pub const KEYCHAIN_SCHEME: &str = "keychain://";
+pub const KEYSTORE_SCHEME: &str = "keystore://";
+/// Service the OS secret store files entries under
+pub const KEYCHAIN_SERVICE: &str = "scoria";
+/// Keystore label, checked on open so a wallet keystore is not taken for it
+const ENCRYPTION_KEY_LABEL: &str = "encryption_key";
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error("OS secret store entry `{entry}`: {source}")]
+    Keychain { entry: String, source: keyring::Error },
+    #[error("OS secret store entry `{0}` already exists; pick another name")]
+    KeychainExists(String),
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+    #[error("Keystore {0} does not hold the encryption key")]
+    NotEncryptionKey(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    Keychain(String),
+    Keystore(PathBuf),
+    /// The key itself, in plaintext
+    Inline(String),
+}
+
+impl SecretSource {
+    pub fn parse(value: &str) -> Self {
+        if let Some(entry) = value.strip_prefix(KEYCHAIN_SCHEME) {
+            Self::Keychain(entry.to_string())
+        } else if let Some(path) = value.strip_prefix(KEYSTORE_SCHEME) {
+            Self::Keystore(PathBuf::from(path))
+        } else {
+            Self::Inline(value.to_string())
+        }
+    }
+
+    /// Keychain entry with a fresh name, so configs on one machine never
+    /// share, or overwrite, each other's key
+    pub fn new_keychain_entry() -> Self {
+        let mut suffix = [0u8; 4];
+        OsRng.fill_bytes(&mut suffix);
+        Self::Keychain(format!("encryption-key-{}", hex::encode(suffix)))
+    }
+
+    /// Value for `security.encryption_key`
+    pub fn reference(&self) -> String {
+        match self {
+            Self::Keychain(entry) => format!("{KEYCHAIN_SCHEME}{entry}"),
+            Self::Keystore(path) => format!("{KEYSTORE_SCHEME}{}", path.display()),
+            Self::Inline(key) => key.clone(),
+        }
+    }
+
+    /// The key; a keystore not in the unlocked session asks for its passphrase
+    pub fn resolve(&self) -> Result<Zeroizing<String>, SecretError> {
+        match self {
+            Self::Keychain(entry) => {
+                keychain(entry)?.get_password().map(Zeroizing::new).map_err(|source| keychain_error(entry, source))
+            }
+            Self::Keystore(path) => {
+                let KeyFile::Encrypted(keystore) = KeyFile::read(path)? else {
+                    return Err(SecretError::NotEncryptionKey(path.clone()));
+                };
+                if keystore.label.as_deref() != Some(ENCRYPTION_KEY_LABEL) {
+                    return Err(SecretError::NotEncryptionKey(path.clone()));
+                }
+                let secret = keystore.unlock_secret(path)?;
+                String::from_utf8(secret.to_vec())
+                    .map(Zeroizing::new)
+                    .map_err(|_| SecretError::NotEncryptionKey(path.clone()))
+            }
+            Self::Inline(key) => {
+                tracing::warn!("security.encryption_key is plaintext in the config; `keys protect` moves it out");
+                Ok(Zeroizing::new(key.clone()))
+            }
+        }
+    }
+
+    /// Save `key` where this source points, never replacing an existing
+    /// entry or file. A keystore asks for a new passphrase.
+    pub fn store(&self, key: &str) -> Result<(), SecretError> {
+        match self {
+            Self::Keychain(entry) => {
+                let keychain = keychain(entry)?;
+                match keychain.get_password() {
+                    Ok(_) => return Err(SecretError::KeychainExists(entry.clone())),
+                    Err(keyring::Error::NoEntry) => {}
+                    Err(source) => return Err(keychain_error(entry, source)),
+                }
+                keychain.set_password(key).map_err(|source| keychain_error(entry, source))
+            }
+            Self::Keystore(path) => {
+                let passphrase = new_passphrase()?;
+                let keystore = Keystore::seal_secret(ENCRYPTION_KEY_LABEL, key.as_bytes(), &passphrase)?;
+                Ok(KeyFile::Encrypted(keystore).write(path, false)?)
+            }
+            Self::Inline(_) => Ok(()),
+        }
+    }
+
+    /// Whether a config value names a store rather than holding the key
+    pub fn is_reference(value: &str) -> bool {
+        !matches!(Self::parse(value), Self::Inline(_))
+    }
+}
+
+/// Default keystore for the encryption key under a data directory
+pub fn default_keystore(data_dir: &Path) -> SecretSource {
+    SecretSource::Keystore(data_dir.join("encryption-key.keystore.json"))
+}
+
+fn keychain(entry: &str) -> Result<keyring::Entry, SecretError> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, entry).map_err(|source| keychain_error(entry, source))
+}
+
+fn keychain_error(entry: &str, source: keyring::Error) -> SecretError {
+    SecretError::Keychain { entry: entry.to_string(), source }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references_round_trip() {
+        for source in [
+            SecretSource::Keychain("encryption-key-0a1b2c3d".into()),
+            SecretSource::Keystore(PathBuf::from("/var/lib/scoria/encryption-key.keystore.json")),
+            SecretSource::Inline("hunter2".into()),
+        ] {
+            assert_eq!(SecretSource::parse(&source.reference()), source);
+        }
+        assert!(SecretSource::is_reference("keychain://scoria"));
+        assert!(!SecretSource::is_reference("a1b2c3"));
+        assert_ne!(SecretSource::new_keychain_entry(), SecretSource::new_keychain_entry());
+    }
+
+    #[test]
+    fn test_keystore_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::env::set_var(crate::wallet::keystore::PASSPHRASE_ENV, "test passphrase");
+        let source = default_keystore(dir.path());
+        source.store("data key").unwrap();
+        assert_eq!(source.resolve().unwrap().as_str(), "data key");
+        assert!(source.store("other key").is_err(), "an existing keystore is kept");
+
+        // A wallet keystore is not mistaken for the encryption key
+        let wallet = dir.path().join("id.json");
+        let keypair = solana_sdk::signature::Keypair::new();
+        KeyFile::Encrypted(Keystore::seal(&keypair, "test passphrase").unwrap()).write(&wallet, false).unwrap();
+        assert!(matches!(SecretSource::Keystore(wallet).resolve(), Err(SecretError::NotEncryptionKey(_))));
+    }
+}
@@ -0,0 +1,365 @@
+// client/src/core/attestation/nonce.rs
+//
+// Replay protection for attestation nonces. A nonce is accepted once per
+// TTL window. The sled journal survives restarts and can be bound to a TPM
+// NV counter so that restoring an old journal snapshot is detected; the
+// Redis store shares one window across every node running a QuoteVerifier.
+
+use crate::config::{NonceBackend, NonceStoreConfig};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NonceError {
+    #[error("Nonce was already used")]
+    Replay,
+    #[error("Nonce journal error: {0}")]
+    Journal(#[from] sled::Error),
+    #[error("Redis nonce store error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("Nonce journal is at counter {journal} but the monotonic counter is at {counter}; journal was rolled back")]
+    Rollback { journal: u64, counter: u64 },
+    #[error("Monotonic counter error: {0}")]
+    Counter(String),
+    #[error("Nonce store misconfigured: {0}")]
+    Config(&'static str),
+}
+
+/// Insert-if-absent of nonces with expiry
+pub trait NonceStore: Send + Sync {
+    /// Record `nonce`, or fail with `Replay` if it was recorded within `ttl`
+    fn check_and_insert(&self, nonce: &[u8; 32], ttl: Duration) -> Result<(), NonceError>;
+
+    /// Drop nonces older than `ttl`; returns how many were removed
+    fn prune(&self, ttl: Duration) -> Result<usize, NonceError>;
+}
+
+/// Hardware counter that only moves forward, e.g. a TPM NV counter index
+pub trait MonotonicCounter: Send + Sync {
+    fn read(&self) -> Result<u64, NonceError>;
+    fn increment(&self) -> Result<u64, NonceError>;
+}
+
+/// Open the store selected by `[tee.nonce_store]`
+pub fn open(config: &NonceStoreConfig) -> Result<Box<dyn NonceStore>, NonceError> {
+    Ok(match config.backend {
+        NonceBackend::Memory => Box::new(MemoryNonceStore::default()),
+        NonceBackend::Sled => {
+            let path = config.path.as_deref().ok_or(NonceError::Config("sled backend needs `path`"))?;
+            let journal = NonceJournal::open(path)?;
+            match config.tpm_nv_index {
+                #[cfg(feature = "tpm-support")]
+                Some(index) => Box::new(journal.bind_counter(Box::new(TpmNvCounter::open(index)?))?),
+                #[cfg(not(feature = "tpm-support"))]
+                Some(_) => return Err(NonceError::Config("`tpm_nv_index` needs the tpm-support feature")),
+                None => Box::new(journal),
+            }
+        }
+        NonceBackend::Redis => {
+            let url = config.redis_url.as_deref().ok_or(NonceError::Config("redis backend needs `redis_url`"))?;
+            Box::new(RedisNonceStore::open(url, &config.key_prefix)?)
+        }
+    })
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Process-local store; nonces are forgotten on restart
+#[derive(Default)]
+pub struct MemoryNonceStore {
+    seen: Mutex<HashMap<[u8; 32], u64>>,
+}
+
+impl NonceStore for MemoryNonceStore {
+    fn check_and_insert(&self, nonce: &[u8; 32], ttl: Duration) -> Result<(), NonceError> {
+        let now = unix_now();
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, first| now.saturating_sub(*first) < ttl.as_secs());
+        if seen.insert(*nonce, now).is_some() {
+            return Err(NonceError::Replay);
+        }
+        Ok(())
+    }
+
+    fn prune(&self, ttl: Duration) -> Result<usize, NonceError> {
+        let now = unix_now();
+        let mut seen = self.seen.lock().unwrap();
+        let before = seen.len();
+        seen.retain(|_, first| now.saturating_sub(*first) < ttl.as_secs());
+        Ok(before - seen.len())
+    }
+}
+
+/// Journal entries between automatic prunes
+const PRUNE_EVERY: u64 = 1024;
+const COUNTER_KEY: &[u8] = b"counter";
+
+/// Persistent nonce journal on sled: nonce to first-seen unix seconds
+pub struct NonceJournal {
+    db: sled::Db,
+    nonces: sled::Tree,
+    counter: Option<Box<dyn MonotonicCounter>>,
+    inserts: AtomicU64,
+}
+
+impl NonceJournal {
+    pub fn open(path: &Path) -> Result<Self, NonceError> {
+        let db = sled::open(path)?;
+        let nonces = db.open_tree("nonces")?;
+        Ok(Self {
+            db,
+            nonces,
+            counter: None,
+            inserts: AtomicU64::new(0),
+        })
+    }
+
+    /// Tie the journal to `counter`. Every insert advances both; a journal
+    /// behind the counter was restored from an older copy and is refused.
+    pub fn bind_counter(mut self, counter: Box<dyn MonotonicCounter>) -> Result<Self, NonceError> {
+        let journal = self.journal_counter()?;
+        let current = counter.read()?;
+        if journal < current {
+            return Err(NonceError::Rollback { journal, counter: current });
+        }
+        self.counter = Some(counter);
+        Ok(self)
+    }
+
+    fn journal_counter(&self) -> Result<u64, NonceError> {
+        Ok(self
+            .db
+            .get(COUNTER_KEY)?
+            .map_or(0, |v| u64::from_be_bytes(v.as_ref().try_into().unwrap_or_default())))
+    }
+
+    fn record(&self, nonce: &[u8; 32], now: u64, ttl: Duration) -> Result<(), NonceError> {
+        let stamp = now.to_be_bytes();
+        let mut previous = None;
+        loop {
+            match self.nonces.compare_and_swap(nonce, previous.as_ref(), Some(&stamp[..]))? {
+                Ok(()) => return Ok(()),
+                Err(conflict) => {
+                    // Present and unexpired is a replay; an expired entry is overwritten
+                    let current = conflict.current.ok_or(NonceError::Replay)?;
+                    let first = u64::from_be_bytes(current.as_ref().try_into().unwrap_or_default());
+                    if now.saturating_sub(first) < ttl.as_secs() {
+                        return Err(NonceError::Replay);
+                    }
+                    previous = Some(current);
+                }
+            }
+        }
+    }
+}
+
+impl NonceStore for NonceJournal {
+    fn check_and_insert(&self, nonce: &[u8; 32], ttl: Duration) -> Result<(), NonceError> {
+        self.record(nonce, unix_now(), ttl)?;
+        if let Some(counter) = &self.counter {
+            // 1. Journal first: after a crash it may lead the counter, never trail it
+            let next = self.journal_counter()?.max(counter.read()?) + 1;
+            self.db.insert(COUNTER_KEY, &next.to_be_bytes())?;
+            self.db.flush()?;
+            // 2. Then the hardware counter
+            counter.increment()?;
+        } else {
+            self.db.flush()?;
+        }
+        if self.inserts.fetch_add(1, Ordering::Relaxed) % PRUNE_EVERY == PRUNE_EVERY - 1 {
+            self.prune(ttl)?;
+        }
+        Ok(())
+    }
+
+    fn prune(&self, ttl: Duration) -> Result<usize, NonceError> {
+        let now = unix_now();
+        let mut removed = 0;
+        for entry in self.nonces.iter() {
+            let (nonce, first) = entry?;
+            let first = u64::from_be_bytes(first.as_ref().try_into().unwrap_or_default());
+            if now.saturating_sub(first) >= ttl.as_secs() && self.nonces.compare_and_swap(&nonce, Some(&first.to_be_bytes()[..]), None::<&[u8]>)?.is_ok() {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Shared store for multi-node verifiers: `SET key NX EX ttl`, expiry by Redis
+pub struct RedisNonceStore {
+    client: redis::Client,
+    key_prefix: String,
+}
+
+impl RedisNonceStore {
+    pub fn open(url: &str, key_prefix: &str) -> Result<Self, NonceError> {
+        let store = Self {
+            client: redis::Client::open(url)?,
+            key_prefix: key_prefix.to_string(),
+        };
+        // Fail at startup rather than on the first quote
+        redis::cmd("PING").query::<String>(&mut store.client.get_connection()?)?;
+        Ok(store)
+    }
+}
+
+impl NonceStore for RedisNonceStore {
+    fn check_and_insert(&self, nonce: &[u8; 32], ttl: Duration) -> Result<(), NonceError> {
+        let mut conn = self.client.get_connection()?;
+        let set: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}", self.key_prefix, hex::encode(nonce)))
+            .arg(unix_now())
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query(&mut conn)?;
+        set.map(|_| ()).ok_or(NonceError::Replay)
+    }
+
+    fn prune(&self, _ttl: Duration) -> Result<usize, NonceError> {
+        Ok(0)
+    }
+}
+
+/// NV counter index (`TPM_NT_COUNTER`) defined by the operator, e.g.
+/// `tpm2_nvdefine 0x1500016 -C o -s 8 -a "ownerread|authread|authwrite|nt=counter"`
+#[cfg(feature = "tpm-support")]
+pub struct TpmNvCounter {
+    context: Mutex<tss_esapi::Context>,
+    index: tss_esapi::handles::NvIndexHandle,
+}
+
+#[cfg(feature = "tpm-support")]
+impl TpmNvCounter {
+    pub fn open(index: u32) -> Result<Self, NonceError> {
+        use tss_esapi::{
+            handles::{NvIndexTpmHandle, TpmHandle},
+            tcti_ldr::TctiNameConf,
+        };
+        let tpm = |e: tss_esapi::Error| NonceError::Counter(e.to_string());
+        let mut context = tss_esapi::Context::new(TctiNameConf::from_environment_variable().map_err(tpm)?).map_err(tpm)?;
+        let handle = NvIndexTpmHandle::new(index).map_err(tpm)?;
+        let index = context
+            .execute_without_session(|ctx| ctx.tr_from_tpm_public(TpmHandle::NvIndex(handle)))
+            .map_err(tpm)?
+            .into();
+        Ok(Self {
+            context: Mutex::new(context),
+            index,
+        })
+    }
+}
+
+#[cfg(feature = "tpm-support")]
+impl MonotonicCounter for TpmNvCounter {
+    fn read(&self) -> Result<u64, NonceError> {
+        use tss_esapi::interface_types::resource_handles::NvAuth;
+        let mut context = self.context.lock().unwrap();
+        match context.execute_with_nullauth_session(|ctx| ctx.nv_read(NvAuth::NvIndex(self.index), self.index, 8, 0)) {
+            Ok(value) => Ok(u64::from_be_bytes(value.as_slice().try_into().map_err(|_| NonceError::Counter("short NV read".into()))?)),
+            // A counter index reads as uninitialized until its first increment
+            Err(tss_esapi::Error::Tss2Error(rc)) if rc.kind() == Some(tss_esapi::constants::response_code::Tss2ResponseCodeKind::NvUninitialized) => Ok(0),
+            Err(e) => Err(NonceError::Counter(e.to_string())),
+        }
+    }
+
+    fn increment(&self) -> Result<u64, NonceError> {
+        use tss_esapi::interface_types::resource_handles::NvAuth;
+        self.context
+            .lock()
+            .unwrap()
+            .execute_with_nullauth_session(|ctx| ctx.nv_increment(NvAuth::NvIndex(self.index), self.index))
+            .map_err(|e| NonceError::Counter(e.to_string()))?;
+        self.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Software counter standing in for the TPM
+    #[derive(Clone, Default)]
+    struct SoftCounter(std::sync::Arc<AtomicU64>);
+
+    impl MonotonicCounter for SoftCounter {
+        fn read(&self) -> Result<u64, NonceError> {
+            Ok(self.0.load(Ordering::SeqCst))
+        }
+
+        fn increment(&self) -> Result<u64, NonceError> {
+            Ok(self.0.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+    }
+
+    #[test]
+    fn journal_rejects_replays_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let ttl = Duration::from_secs(60);
+        {
+            let journal = NonceJournal::open(dir.path()).unwrap();
+            journal.check_and_insert(&[1; 32], ttl).unwrap();
+            assert!(matches!(journal.check_and_insert(&[1; 32], ttl), Err(NonceError::Replay)));
+        }
+        let journal = NonceJournal::open(dir.path()).unwrap();
+        assert!(matches!(journal.check_and_insert(&[1; 32], ttl), Err(NonceError::Replay)));
+        journal.check_and_insert(&[2; 32], ttl).unwrap();
+    }
+
+    #[test]
+    fn expired_nonces_are_pruned_and_reusable() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = NonceJournal::open(dir.path()).unwrap();
+        journal.check_and_insert(&[1; 32], Duration::from_secs(60)).unwrap();
+        assert_eq!(journal.prune(Duration::from_secs(60)).unwrap(), 0);
+        journal.check_and_insert(&[1; 32], Duration::ZERO).unwrap();
+        assert_eq!(journal.prune(Duration::ZERO).unwrap(), 1);
+
+        let memory = MemoryNonceStore::default();
+        memory.check_and_insert(&[3; 32], Duration::ZERO).unwrap();
+        memory.check_and_insert(&[3; 32], Duration::ZERO).unwrap();
+    }
+
+    #[test]
+    fn restored_journal_is_detected_by_counter() {
+        let (live, snapshot) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let counter = SoftCounter::default();
+        let ttl = Duration::from_secs(60);
+
+        let journal = NonceJournal::open(live.path()).unwrap().bind_counter(Box::new(counter.clone())).unwrap();
+        journal.check_and_insert(&[1; 32], ttl).unwrap();
+        drop(journal);
+        // An attacker keeps this copy, then more nonces are accepted
+        copy_dir(live.path(), snapshot.path());
+        let journal = NonceJournal::open(live.path()).unwrap().bind_counter(Box::new(counter.clone())).unwrap();
+        journal.check_and_insert(&[2; 32], ttl).unwrap();
+        drop(journal);
+
+        let restored = NonceJournal::open(snapshot.path()).unwrap().bind_counter(Box::new(counter));
+        assert!(matches!(restored, Err(NonceError::Rollback { journal: 1, counter: 2 })));
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let target = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                std::fs::create_dir_all(&target).unwrap();
+                copy_dir(&entry.path(), &target);
+            } else {
+                std::fs::copy(entry.path(), target).unwrap();
+            }
+        }
+    }
+}
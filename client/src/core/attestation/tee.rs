@@ -0,0 +1,391 @@
+// client/src/core/attestation/tee.rs
+
+use super::nonce::{self, NonceError, NonceStore};
+use crate::config::TeeConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::{ed25519_program, instruction::Instruction, pubkey::Pubkey, signer::Signer};
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TeeError {
+    #[error("TEE quoting interface unavailable: {0}")]
+    Unavailable(#[from] std::io::Error),
+    #[error("Malformed {platform:?} quote: {reason}")]
+    Malformed { platform: TeePlatform, reason: &'static str },
+    #[error("Quote signature does not verify: {0}")]
+    Signature(&'static str),
+    #[error("No {0:?} signing key configured under [tee]")]
+    MissingPlatformKey(TeePlatform),
+    #[error("Measurement {0} is not allowed")]
+    Measurement(String),
+    #[error("Report data does not bind this model, input and output")]
+    Binding,
+    #[error("Quote nonce rejected: {0}")]
+    Nonce(#[from] NonceError),
+}
+
+/// Discriminants match the on-chain `TeePlatform`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeePlatform {
+    /// Intel SGX, ECDSA (DCAP) quote v3
+    Sgx = 0,
+    /// AMD SEV-SNP attestation report
+    Snp = 1,
+}
+
+/// `sha256(model_hash || input_hash || output_hash) || nonce`, the 64 bytes of report data
+pub fn report_data(model_hash: &[u8; 32], input_hash: &[u8; 32], output_hash: &[u8; 32], nonce: &[u8; 32]) -> [u8; 64] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(&Sha256::new().chain_update(model_hash).chain_update(input_hash).chain_update(output_hash).finalize());
+    data[32..].copy_from_slice(nonce);
+    data
+}
+
+// SGX DCAP quote v3: 48-byte header, 384-byte report body, then the signature data
+const SGX_BODY: usize = 48;
+const SGX_SIGNED: usize = SGX_BODY + 384;
+const SGX_QUOTE_SIG: usize = SGX_SIGNED + 4;
+const SGX_ATTEST_KEY: usize = SGX_QUOTE_SIG + 64;
+const SGX_QE_REPORT: usize = SGX_ATTEST_KEY + 64;
+const SGX_QE_SIG: usize = SGX_QE_REPORT + 384;
+const SGX_QE_AUTH: usize = SGX_QE_SIG + 64;
+// Offsets inside a 384-byte SGX report body
+const SGX_MRENCLAVE: usize = 64;
+const SGX_REPORT_DATA: usize = 320;
+
+// SEV-SNP ATTESTATION_REPORT
+const SNP_REPORT_DATA: usize = 0x50;
+const SNP_MEASUREMENT: usize = 0x90;
+const SNP_SIGNED: usize = 0x2A0;
+const SNP_REPORT_LEN: usize = 0x4A0;
+
+/// Raw platform evidence as produced inside the enclave or confidential VM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeeQuote {
+    pub platform: TeePlatform,
+    #[serde(with = "hex::serde")]
+    pub raw: Vec<u8>,
+}
+
+impl TeeQuote {
+    /// Ask the platform for a quote over `report_data`: the Gramine `/dev/attestation`
+    /// interface inside SGX enclaves, configfs-tsm inside SNP guests
+    pub fn generate(platform: TeePlatform, report_data: &[u8; 64]) -> Result<Self, TeeError> {
+        let raw = match platform {
+            TeePlatform::Sgx => {
+                std::fs::write("/dev/attestation/user_report_data", report_data)?;
+                std::fs::read("/dev/attestation/quote")?
+            }
+            TeePlatform::Snp => {
+                let entry = Path::new("/sys/kernel/config/tsm/report").join(format!("scoria-{}", std::process::id()));
+                std::fs::create_dir(&entry)?;
+                let report = std::fs::write(entry.join("inblob"), report_data).and_then(|_| std::fs::read(entry.join("outblob")));
+                let _ = std::fs::remove_dir(&entry);
+                report?
+            }
+        };
+        let quote = Self { platform, raw };
+        quote.body()?;
+        Ok(quote)
+    }
+
+    fn malformed(&self, reason: &'static str) -> TeeError {
+        TeeError::Malformed { platform: self.platform, reason }
+    }
+
+    /// `(measurement, report data)`; SGX MRENCLAVE is zero-padded to SNP's 48 bytes
+    pub fn body(&self) -> Result<([u8; 48], [u8; 64]), TeeError> {
+        let mut measurement = [0u8; 48];
+        let mut data = [0u8; 64];
+        match self.platform {
+            TeePlatform::Sgx => {
+                if self.raw.len() < SGX_QE_AUTH + 2 || u16::from_le_bytes([self.raw[0], self.raw[1]]) != 3 {
+                    return Err(self.malformed("not a version 3 quote"));
+                }
+                measurement[..32].copy_from_slice(&self.raw[SGX_BODY + SGX_MRENCLAVE..][..32]);
+                data.copy_from_slice(&self.raw[SGX_BODY + SGX_REPORT_DATA..][..64]);
+            }
+            TeePlatform::Snp => {
+                if self.raw.len() < SNP_REPORT_LEN {
+                    return Err(self.malformed("report too short"));
+                }
+                measurement.copy_from_slice(&self.raw[SNP_MEASUREMENT..][..48]);
+                data.copy_from_slice(&self.raw[SNP_REPORT_DATA..][..64]);
+            }
+        }
+        Ok((measurement, data))
+    }
+
+    /// Check the quote signature chain up to the pinned platform key.
+    ///
+    /// SGX: the QE report is signed by the PCK key, commits to the attestation
+    /// key, which signs the enclave report. SNP: the VCEK signs the report.
+    /// Validating the pinned key against Intel/AMD roots is the operator's job.
+    pub fn verify_signature(&self, config: &TeeConfig) -> Result<(), TeeError> {
+        use p256::ecdsa::signature::Verifier;
+        self.body()?;
+        match self.platform {
+            TeePlatform::Sgx => {
+                let pck = config.pck_key.as_deref().ok_or(TeeError::MissingPlatformKey(TeePlatform::Sgx))?;
+                let pck = hex::decode(pck)
+                    .ok()
+                    .and_then(|k| p256::ecdsa::VerifyingKey::from_sec1_bytes(&k).ok())
+                    .ok_or(TeeError::Signature("pck_key is not a SEC1 P-256 key"))?;
+                let p256_sig = |bytes: &[u8]| {
+                    p256::ecdsa::Signature::from_slice(bytes).map_err(|_| self.malformed("bad ECDSA signature encoding"))
+                };
+
+                // 1. QE report signed by the PCK key
+                let qe_report = &self.raw[SGX_QE_REPORT..SGX_QE_SIG];
+                pck.verify(qe_report, &p256_sig(&self.raw[SGX_QE_SIG..SGX_QE_AUTH])?)
+                    .map_err(|_| TeeError::Signature("QE report"))?;
+
+                // 2. QE report data commits to the attestation key and QE auth data
+                let auth_len = u16::from_le_bytes([self.raw[SGX_QE_AUTH], self.raw[SGX_QE_AUTH + 1]]) as usize;
+                let auth = self.raw.get(SGX_QE_AUTH + 2..SGX_QE_AUTH + 2 + auth_len).ok_or_else(|| self.malformed("truncated QE auth data"))?;
+                let attest_key = &self.raw[SGX_ATTEST_KEY..SGX_QE_REPORT];
+                let expected = Sha256::new().chain_update(attest_key).chain_update(auth).finalize();
+                let qe_data = &qe_report[SGX_REPORT_DATA..];
+                if qe_data[..32] != expected[..] || qe_data[32..].iter().any(|&b| b != 0) {
+                    return Err(TeeError::Signature("QE report does not commit to the attestation key"));
+                }
+
+                // 3. Header and enclave report signed by the attestation key
+                let mut sec1 = vec![0x04];
+                sec1.extend_from_slice(attest_key);
+                let attest_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&sec1).map_err(|_| self.malformed("bad attestation key"))?;
+                attest_key
+                    .verify(&self.raw[..SGX_SIGNED], &p256_sig(&self.raw[SGX_QUOTE_SIG..SGX_ATTEST_KEY])?)
+                    .map_err(|_| TeeError::Signature("enclave report"))
+            }
+            TeePlatform::Snp => {
+                let vcek = config.vcek_key.as_deref().ok_or(TeeError::MissingPlatformKey(TeePlatform::Snp))?;
+                let vcek = hex::decode(vcek)
+                    .ok()
+                    .and_then(|k| p384::ecdsa::VerifyingKey::from_sec1_bytes(&k).ok())
+                    .ok_or(TeeError::Signature("vcek_key is not a SEC1 P-384 key"))?;
+                // r and s are 72-byte little-endian fields; P-384 scalars use the low 48 bytes
+                let scalar = |offset: usize| {
+                    let mut be: [u8; 48] = self.raw[offset..offset + 48].try_into().expect("48 bytes");
+                    be.reverse();
+                    be
+                };
+                let signature = p384::ecdsa::Signature::from_scalars(scalar(SNP_SIGNED), scalar(SNP_SIGNED + 72))
+                    .map_err(|_| self.malformed("bad ECDSA signature encoding"))?;
+                vcek.verify(&self.raw[..SNP_SIGNED], &signature)
+                    .map_err(|_| TeeError::Signature("attestation report"))
+            }
+        }
+    }
+}
+
+/// A verified quote, reduced to what the program checks. The attestation
+/// verifier signs `message()`; `verify_tee_attestation` checks that signature
+/// through the Ed25519 program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeeEvidence {
+    pub platform: TeePlatform,
+    #[serde(with = "hex::serde")]
+    pub measurement: [u8; 48],
+    #[serde(with = "hex::serde")]
+    pub report_data: [u8; 64],
+    pub verifier: Pubkey,
+    pub verified_at: i64,
+}
+
+impl TeeEvidence {
+    /// Byte layout shared with the program's `TeeEvidence::message`
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = b"scoria-tee-evidence-v1".to_vec();
+        message.push(self.platform as u8);
+        message.extend_from_slice(&self.measurement);
+        message.extend_from_slice(&self.report_data);
+        message.extend_from_slice(self.verifier.as_ref());
+        message.extend_from_slice(&self.verified_at.to_le_bytes());
+        message
+    }
+
+    pub fn sign(&self, verifier: &dyn Signer) -> [u8; 64] {
+        verifier.sign_message(&self.message()).into()
+    }
+
+    /// Ed25519 program check of `signature`, placed right before `verify_tee_attestation`
+    pub fn ed25519_instruction(&self, signature: &[u8; 64]) -> Instruction {
+        ed25519_instruction(&self.verifier, signature, &self.message())
+    }
+}
+
+/// Ed25519 program check of one signature, laid out the way `utils::ed25519::verify_preceding` expects
+pub fn ed25519_instruction(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    // [count, padding, 7 x u16 offsets into this instruction], then key, signature, message
+    const KEY: u16 = 16;
+    const SIGNATURE: u16 = KEY + 32;
+    const MESSAGE: u16 = SIGNATURE + 64;
+    let mut data = vec![1u8, 0];
+    for field in [SIGNATURE, u16::MAX, KEY, u16::MAX, MESSAGE, message.len() as u16, u16::MAX] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction::new_with_bytes(ed25519_program::id(), &data, vec![])
+}
+
+/// Off-chain quote verifier: signature chain, measurement allowlist, binding and replay
+pub struct QuoteVerifier {
+    config: TeeConfig,
+    nonces: Box<dyn NonceStore>,
+}
+
+impl QuoteVerifier {
+    /// Verifier with the nonce store selected by `tee.nonce_store`
+    pub fn new(config: TeeConfig) -> Result<Self, TeeError> {
+        let nonces = nonce::open(&config.nonce_store)?;
+        Ok(Self::with_store(config, nonces))
+    }
+
+    pub fn with_store(config: TeeConfig, nonces: Box<dyn NonceStore>) -> Self {
+        Self { config, nonces }
+    }
+
+    /// Verify `quote` for one inference and reduce it to signable evidence
+    pub fn verify(
+        &self,
+        quote: &TeeQuote,
+        model_hash: &[u8; 32],
+        input_hash: &[u8; 32],
+        output_hash: &[u8; 32],
+        verifier: Pubkey,
+    ) -> Result<TeeEvidence, TeeError> {
+        // 1. Platform signature chain
+        quote.verify_signature(&self.config)?;
+
+        // 2. Code identity
+        let (measurement, data) = quote.body()?;
+        let measurement_hex = hex::encode(match quote.platform {
+            TeePlatform::Sgx => &measurement[..32],
+            TeePlatform::Snp => &measurement[..],
+        });
+        if !self.config.allowed_measurements.iter().any(|m| m.eq_ignore_ascii_case(&measurement_hex)) {
+            return Err(TeeError::Measurement(measurement_hex));
+        }
+
+        // 3. Bound to this model, input and output, with an unused nonce
+        let nonce: [u8; 32] = data[32..].try_into().expect("32 bytes");
+        if data != report_data(model_hash, input_hash, output_hash, &nonce) {
+            return Err(TeeError::Binding);
+        }
+        self.check_nonce_freshness(&nonce)?;
+
+        Ok(TeeEvidence {
+            platform: quote.platform,
+            measurement,
+            report_data: data,
+            verifier,
+            verified_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+        })
+    }
+
+    /// Reject nonces seen within `nonce_ttl_secs`
+    pub fn check_nonce_freshness(&self, nonce: &[u8; 32]) -> Result<(), TeeError> {
+        let ttl = Duration::from_secs(self.config.nonce_ttl_secs);
+        Ok(self.nonces.check_and_insert(nonce, ttl)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::{signature::Signer as _, SigningKey};
+
+    const MRENCLAVE: [u8; 32] = [0xAB; 32];
+
+    /// Minimal well-signed v3 quote over `data`
+    fn sgx_quote(pck: &SigningKey, data: &[u8; 64]) -> TeeQuote {
+        let attest = SigningKey::from_slice(&[5; 32]).unwrap();
+        let mut raw = vec![0u8; SGX_QE_AUTH + 2];
+        raw[0] = 3;
+        raw[SGX_BODY + SGX_MRENCLAVE..][..32].copy_from_slice(&MRENCLAVE);
+        raw[SGX_BODY + SGX_REPORT_DATA..][..64].copy_from_slice(data);
+        let attest_key = attest.verifying_key().to_encoded_point(false);
+        raw[SGX_ATTEST_KEY..SGX_QE_REPORT].copy_from_slice(&attest_key.as_bytes()[1..]);
+        let commitment = Sha256::digest(&raw[SGX_ATTEST_KEY..SGX_QE_REPORT]);
+        raw[SGX_QE_REPORT + SGX_REPORT_DATA..][..32].copy_from_slice(&commitment);
+
+        let quote_sig: p256::ecdsa::Signature = attest.sign(&raw[..SGX_SIGNED]);
+        raw[SGX_QUOTE_SIG..SGX_ATTEST_KEY].copy_from_slice(&quote_sig.to_bytes());
+        let qe_sig: p256::ecdsa::Signature = pck.sign(&raw[SGX_QE_REPORT..SGX_QE_SIG]);
+        raw[SGX_QE_SIG..SGX_QE_AUTH].copy_from_slice(&qe_sig.to_bytes());
+        TeeQuote { platform: TeePlatform::Sgx, raw }
+    }
+
+    fn setup() -> (SigningKey, QuoteVerifier, [u8; 64]) {
+        let pck = SigningKey::from_slice(&[7; 32]).unwrap();
+        let config = TeeConfig {
+            pck_key: Some(hex::encode(pck.verifying_key().to_encoded_point(true).as_bytes())),
+            allowed_measurements: vec![hex::encode(MRENCLAVE)],
+            ..TeeConfig::default()
+        };
+        (pck, QuoteVerifier::new(config).unwrap(), report_data(&[1; 32], &[2; 32], &[3; 32], &[4; 32]))
+    }
+
+    #[test]
+    fn sgx_quote_verifies_into_evidence_once() {
+        let (pck, verifier, data) = setup();
+        let quote = sgx_quote(&pck, &data);
+        let evidence = verifier.verify(&quote, &[1; 32], &[2; 32], &[3; 32], Pubkey::new_unique()).unwrap();
+        assert_eq!(evidence.measurement[..32], MRENCLAVE);
+        assert_eq!(evidence.report_data, data);
+
+        // The same nonce cannot be presented twice
+        assert!(matches!(verifier.verify(&quote, &[1; 32], &[2; 32], &[3; 32], evidence.verifier), Err(TeeError::Nonce(NonceError::Replay))));
+    }
+
+    #[test]
+    fn rejects_wrong_binding_measurement_and_tampering() {
+        let (pck, verifier, data) = setup();
+        let quote = sgx_quote(&pck, &data);
+        assert!(matches!(verifier.verify(&quote, &[1; 32], &[2; 32], &[9; 32], Pubkey::default()), Err(TeeError::Binding)));
+
+        let mut tampered = quote.clone();
+        tampered.raw[SGX_BODY + SGX_MRENCLAVE] ^= 1;
+        assert!(matches!(tampered.verify_signature(&verifier.config), Err(TeeError::Signature(_))));
+
+        let strict = QuoteVerifier::new(TeeConfig { allowed_measurements: vec![], ..verifier.config.clone() }).unwrap();
+        assert!(matches!(strict.verify(&quote, &[1; 32], &[2; 32], &[3; 32], Pubkey::default()), Err(TeeError::Measurement(_))));
+
+        let forged = sgx_quote(&SigningKey::from_slice(&[8; 32]).unwrap(), &data);
+        assert!(matches!(forged.verify_signature(&verifier.config), Err(TeeError::Signature("QE report"))));
+    }
+
+    #[test]
+    fn snp_report_signed_by_vcek() {
+        let vcek = p384::ecdsa::SigningKey::from_slice(&[3; 48]).unwrap();
+        let mut raw = vec![0u8; SNP_REPORT_LEN];
+        raw[SNP_MEASUREMENT..][..48].copy_from_slice(&[0xCD; 48]);
+        let signature: p384::ecdsa::Signature = vcek.sign(&raw[..SNP_SIGNED]);
+        let (r, s) = signature.split_bytes();
+        for (offset, scalar) in [(SNP_SIGNED, r), (SNP_SIGNED + 72, s)] {
+            let mut le = scalar.to_vec();
+            le.reverse();
+            raw[offset..offset + 48].copy_from_slice(&le);
+        }
+        let config = TeeConfig {
+            vcek_key: Some(hex::encode(vcek.verifying_key().to_encoded_point(false).as_bytes())),
+            ..TeeConfig::default()
+        };
+        let quote = TeeQuote { platform: TeePlatform::Snp, raw };
+        assert!(quote.verify_signature(&config).is_ok());
+        assert_eq!(quote.body().unwrap().0, [0xCD; 48]);
+
+        let mut tampered = quote;
+        tampered.raw[SNP_MEASUREMENT] = 0;
+        assert!(tampered.verify_signature(&config).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+// client/src/core/audit.rs
+
+use serde::Serialize;
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Audit log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Audit record serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Append-only JSONL audit log for security-relevant client operations
+pub struct AuditLog {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+#[derive(Serialize)]
+struct AuditRecord<'a, T: Serialize> {
+    timestamp: u64,
+    action: &'a str,
+    #[serde(flatten)]
+    details: &'a T,
+}
+
+impl AuditLog {
+    /// Open (or create) the audit log at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AuditError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Append a record and flush it to disk before returning
+    pub fn record<T: Serialize>(&mut self, action: &str, details: &T) -> Result<(), AuditError> {
+        let record = AuditRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            action,
+            details,
+        };
+
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,397 @@
+// client/src/core/cache/cluster.rs
+
+use super::redis::{RedisConfig, RedisManager};
+use deadpool::managed::Pool;
+use redis::{
+    aio::Connection, Client, Cmd, ConnectionAddr, ConnectionInfo, ErrorKind, FromRedisValue,
+    IntoConnectionInfo, RedisError, RedisResult, Value,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+use tokio::{task::JoinHandle, time::timeout};
+use tracing::{info, warn};
+
+pub const SLOT_COUNT: u16 = 16384;
+/// Redirects followed for one command before giving up
+const MAX_REDIRECTS: usize = 5;
+
+/// Contiguous slots served by one primary and its replicas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotRange {
+    pub start: u16,
+    pub end: u16,
+    pub primary: String,
+    pub replicas: Vec<String>,
+}
+
+struct Node {
+    pool: Pool<RedisManager>,
+    healthy: AtomicBool,
+}
+
+/// Slot-aware routing over a Redis Cluster.
+///
+/// The slot map is loaded from `CLUSTER SLOTS` on the first reachable node
+/// and patched in place on `MOVED`; a full reload follows a `MOVED` or a
+/// connection failure. Each node has its own connection pool and a health
+/// flag kept current by `spawn_health_checks`, so reads can skip a primary
+/// that stopped answering.
+pub struct ClusterRouter {
+    seeds: Vec<String>,
+    /// Scheme, credentials and TLS settings applied to every discovered node
+    template: ConnectionInfo,
+    read_from_replicas: bool,
+    slots: RwLock<Vec<SlotRange>>,
+    nodes: RwLock<HashMap<String, Arc<Node>>>,
+}
+
+impl ClusterRouter {
+    pub async fn connect(seeds: Vec<String>, read_from_replicas: bool) -> RedisResult<Self> {
+        let first = seeds
+            .first()
+            .ok_or_else(|| RedisError::from((ErrorKind::InvalidClientConfig, "No cluster nodes configured")))?;
+        let router = Self {
+            template: first.as_str().into_connection_info()?,
+            seeds: seeds.clone(),
+            read_from_replicas,
+            slots: RwLock::new(Vec::new()),
+            nodes: RwLock::new(HashMap::new()),
+        };
+        router.refresh_slots().await?;
+        Ok(router)
+    }
+
+    /// Run `cmd` on the node serving `key`, following `MOVED` and `ASK` redirects
+    pub async fn query<T: FromRedisValue>(&self, key: &[u8], read_only: bool, cmd: &Cmd) -> RedisResult<T> {
+        let slot = key_slot(key);
+        let mut addr = self.route(slot, read_only)?;
+        let mut asking = false;
+
+        for _ in 0..MAX_REDIRECTS {
+            let mut conn = self.node_conn(&addr).await?;
+            let result = if asking {
+                redis::pipe()
+                    .cmd("ASKING")
+                    .ignore()
+                    .add_command(cmd.clone())
+                    .query_async::<_, (T,)>(&mut conn)
+                    .await
+                    .map(|(value,)| value)
+            } else {
+                cmd.query_async(&mut conn).await
+            };
+
+            match result {
+                Err(e) if e.kind() == ErrorKind::Moved => {
+                    let target = redirect_target(&e)?;
+                    info!(slot, from = %addr, to = %target, "Slot moved");
+                    self.reassign(slot, &target);
+                    self.reload().await;
+                    (addr, asking) = (target, false);
+                }
+                Err(e) if e.kind() == ErrorKind::Ask => {
+                    // Mid-migration: this command only, the slot map stays as is
+                    (addr, asking) = (redirect_target(&e)?, true);
+                }
+                Err(e) if e.is_connection_dropped() || e.is_io_error() || e.is_timeout() => {
+                    self.mark(&addr, false);
+                    self.reload().await;
+                    return Err(e);
+                }
+                other => return other,
+            }
+        }
+        Err(RedisError::from((ErrorKind::ClientError, "Too many cluster redirects")))
+    }
+
+    /// Connection to the node serving `key`; redirects are the caller's to handle
+    pub async fn conn_for(&self, key: &[u8], read_only: bool) -> RedisResult<Connection> {
+        let addr = self.route(key_slot(key), read_only)?;
+        self.node_conn(&addr).await
+    }
+
+    /// Connection to any healthy primary, for commands without a key
+    pub async fn any_conn(&self) -> RedisResult<Connection> {
+        let primaries: Vec<String> = self.slots.read().unwrap().iter().map(|r| r.primary.clone()).collect();
+        let addr = primaries
+            .iter()
+            .find(|a| self.is_healthy(a))
+            .or(primaries.first())
+            .cloned()
+            .ok_or_else(no_slots)?;
+        self.node_conn(&addr).await
+    }
+
+    /// Reload the slot map from the first node that answers
+    pub async fn refresh_slots(&self) -> RedisResult<()> {
+        let mut candidates: Vec<String> = self.nodes.read().unwrap().keys().cloned().collect();
+        candidates.extend(self.seeds.iter().filter_map(|s| node_addr(s)));
+        candidates.dedup();
+
+        let mut last_error = None;
+        for addr in candidates {
+            let loaded = async {
+                let mut conn = self.client(&addr)?.get_async_connection().await?;
+                let value: Value = redis::cmd("CLUSTER").arg("SLOTS").query_async(&mut conn).await?;
+                parse_slots(value)
+            };
+            match timeout(Duration::from_secs(5), loaded).await {
+                Ok(Ok(ranges)) if !ranges.is_empty() => {
+                    info!(ranges = ranges.len(), source = %addr, "Cluster slot map loaded");
+                    *self.slots.write().unwrap() = ranges;
+                    return Ok(());
+                }
+                Ok(Ok(_)) => last_error = Some(no_slots()),
+                Ok(Err(e)) => last_error = Some(e),
+                Err(_) => last_error = Some(RedisError::from((ErrorKind::IoError, "CLUSTER SLOTS timed out"))),
+            }
+        }
+        Err(last_error.unwrap_or_else(no_slots))
+    }
+
+    /// PING every node in the slot map, returning each node's health
+    pub async fn health_check(&self) -> Vec<(String, bool)> {
+        let addrs: Vec<String> = {
+            let slots = self.slots.read().unwrap();
+            let mut addrs: Vec<String> = slots
+                .iter()
+                .flat_map(|r| std::iter::once(&r.primary).chain(&r.replicas))
+                .cloned()
+                .collect();
+            addrs.sort();
+            addrs.dedup();
+            addrs
+        };
+
+        let mut report = Vec::with_capacity(addrs.len());
+        for addr in addrs {
+            let ping = async {
+                let mut conn = self.node_conn(&addr).await?;
+                redis::cmd("PING").query_async::<_, String>(&mut conn).await
+            };
+            let healthy = matches!(timeout(Duration::from_secs(2), ping).await, Ok(Ok(_)));
+            if !healthy && self.is_healthy(&addr) {
+                warn!(node = %addr, "Redis cluster node unhealthy");
+            }
+            self.mark(&addr, healthy);
+            report.push((addr, healthy));
+        }
+        report
+    }
+
+    /// Check node health every `period`, reloading the slot map when a node is down
+    pub fn spawn_health_checks(self: Arc<Self>, period: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                if self.health_check().await.iter().any(|(_, healthy)| !healthy) {
+                    if let Err(e) = self.refresh_slots().await {
+                        warn!(error = %e, "Cluster slot refresh failed");
+                    }
+                }
+            }
+        })
+    }
+
+    /// Primary for writes; a healthy replica for reads when allowed, or when the primary is down
+    fn route(&self, slot: u16, read_only: bool) -> RedisResult<String> {
+        let slots = self.slots.read().unwrap();
+        let range = slots.iter().find(|r| (r.start..=r.end).contains(&slot)).ok_or_else(no_slots)?;
+        if read_only && !range.replicas.is_empty() {
+            let primary_down = !self.is_healthy(&range.primary);
+            if self.read_from_replicas || primary_down {
+                let healthy: Vec<&String> = range.replicas.iter().filter(|r| self.is_healthy(r)).collect();
+                if !healthy.is_empty() {
+                    return Ok(healthy[slot as usize % healthy.len()].clone());
+                }
+            }
+        }
+        Ok(range.primary.clone())
+    }
+
+    /// Point the range holding `slot` at its new primary until the next full reload
+    fn reassign(&self, slot: u16, primary: &str) {
+        let mut slots = self.slots.write().unwrap();
+        let Some(index) = slots.iter().position(|r| (r.start..=r.end).contains(&slot)) else { return };
+        let range = slots.remove(index);
+        let moved = SlotRange { start: slot, end: slot, primary: primary.to_string(), replicas: Vec::new() };
+        if range.start < slot {
+            slots.push(SlotRange { end: slot - 1, ..range.clone() });
+        }
+        if slot < range.end {
+            slots.push(SlotRange { start: slot + 1, ..range });
+        }
+        slots.push(moved);
+        slots.sort_by_key(|r| r.start);
+    }
+
+    /// Routing stays correct through redirects meanwhile, so a failed reload only costs hops
+    async fn reload(&self) {
+        if let Err(e) = self.refresh_slots().await {
+            warn!(error = %e, "Cluster slot refresh failed");
+        }
+    }
+
+    async fn node_conn(&self, addr: &str) -> RedisResult<Connection> {
+        let node = self.node(addr)?;
+        match node.pool.get().await {
+            Ok(conn) => Ok(conn),
+            Err(e) => {
+                node.healthy.store(false, Ordering::Relaxed);
+                Err(RedisError::from((ErrorKind::IoError, "Cluster node unavailable", e.to_string())))
+            }
+        }
+    }
+
+    fn node(&self, addr: &str) -> RedisResult<Arc<Node>> {
+        if let Some(node) = self.nodes.read().unwrap().get(addr) {
+            return Ok(node.clone());
+        }
+        let manager = RedisManager::new(
+            self.client(addr)?,
+            RedisConfig::Cluster { nodes: self.seeds.clone(), read_from_replicas: self.read_from_replicas },
+        );
+        let pool = Pool::builder(manager)
+            .max_size(10)
+            .create_timeout(Some(Duration::from_secs(5)))
+            .wait_timeout(Some(Duration::from_secs(2)))
+            .build()
+            .map_err(|e| RedisError::from((ErrorKind::ClientError, "Cluster pool", e.to_string())))?;
+        let node = Arc::new(Node { pool, healthy: AtomicBool::new(true) });
+        Ok(self.nodes.write().unwrap().entry(addr.to_string()).or_insert(node).clone())
+    }
+
+    /// Client for a discovered `host:port`, with the seed's credentials and TLS mode
+    fn client(&self, addr: &str) -> RedisResult<Client> {
+        let (host, port) = split_addr(addr)?;
+        let mut info = self.template.clone();
+        match &mut info.addr {
+            ConnectionAddr::Tcp(h, p) | ConnectionAddr::TcpTls { host: h, port: p, .. } => (*h, *p) = (host, port),
+            other => *other = ConnectionAddr::Tcp(host, port),
+        }
+        Client::open(info)
+    }
+
+    fn is_healthy(&self, addr: &str) -> bool {
+        self.nodes.read().unwrap().get(addr).map_or(true, |n| n.healthy.load(Ordering::Relaxed))
+    }
+
+    fn mark(&self, addr: &str, healthy: bool) {
+        if let Some(node) = self.nodes.read().unwrap().get(addr) {
+            node.healthy.store(healthy, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Hash slot of `key`, honouring `{hash tags}` so related keys share a node
+pub fn key_slot(key: &[u8]) -> u16 {
+    let hashed = match key.iter().position(|&b| b == b'{') {
+        Some(open) => match key[open + 1..].iter().position(|&b| b == b'}') {
+            Some(len) if len > 0 => &key[open + 1..open + 1 + len],
+            _ => key,
+        },
+        None => key,
+    };
+    crc16(hashed) % SLOT_COUNT
+}
+
+/// CRC16/XMODEM, as specified for cluster key hashing
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Parse a `CLUSTER SLOTS` reply: `[[start, end, [host, port, ..], replicas..], ..]`
+pub fn parse_slots(value: Value) -> RedisResult<Vec<SlotRange>> {
+    let invalid = || RedisError::from((ErrorKind::TypeError, "Malformed CLUSTER SLOTS reply"));
+    let Value::Bulk(entries) = value else { return Err(invalid()) };
+
+    let mut ranges = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let Value::Bulk(fields) = entry else { return Err(invalid()) };
+        if fields.len() < 3 {
+            return Err(invalid());
+        }
+        let start = u16::from_redis_value(&fields[0])?;
+        let end = u16::from_redis_value(&fields[1])?;
+        let mut nodes = fields[2..].iter().map(|node| {
+            let Value::Bulk(parts) = node else { return Err(invalid()) };
+            if parts.len() < 2 {
+                return Err(invalid());
+            }
+            let host = String::from_redis_value(&parts[0])?;
+            let port = u16::from_redis_value(&parts[1])?;
+            Ok(format!("{host}:{port}"))
+        });
+        let primary = nodes.next().ok_or_else(invalid)??;
+        let replicas = nodes.collect::<RedisResult<Vec<_>>>()?;
+        ranges.push(SlotRange { start, end, primary, replicas });
+    }
+    ranges.sort_by_key(|r| r.start);
+    Ok(ranges)
+}
+
+fn redirect_target(e: &RedisError) -> RedisResult<String> {
+    e.redirect_node()
+        .map(|(addr, _slot)| addr.to_string())
+        .ok_or_else(|| RedisError::from((ErrorKind::TypeError, "Redirect without a target node")))
+}
+
+fn split_addr(addr: &str) -> RedisResult<(String, u16)> {
+    addr.rsplit_once(':')
+        .and_then(|(host, port)| Some((host.to_string(), port.parse().ok()?)))
+        .ok_or_else(|| RedisError::from((ErrorKind::InvalidClientConfig, "Invalid node address", addr.to_string())))
+}
+
+/// `host:port` of a seed URL
+fn node_addr(url: &str) -> Option<String> {
+    match url.into_connection_info().ok()?.addr {
+        ConnectionAddr::Tcp(host, port) | ConnectionAddr::TcpTls { host, port, .. } => Some(format!("{host}:{port}")),
+        _ => None,
+    }
+}
+
+fn no_slots() -> RedisError {
+    RedisError::from((ErrorKind::ClusterDown, "No slot map available"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_slot_matches_cluster_spec() {
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+        assert_eq!(key_slot(b"foo"), 12182);
+        assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"{user1000}.followers"));
+        // Empty tags hash the whole key
+        assert_eq!(key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % SLOT_COUNT);
+    }
+
+    #[test]
+    fn parses_cluster_slots_reply() {
+        let node = |host: &str, port: i64| Value::Bulk(vec![Value::Data(host.into()), Value::Int(port)]);
+        let reply = Value::Bulk(vec![
+            Value::Bulk(vec![Value::Int(5461), Value::Int(16383), node("10.0.0.2", 6379)]),
+            Value::Bulk(vec![Value::Int(0), Value::Int(5460), node("10.0.0.1", 6379), node("10.0.0.3", 6380)]),
+        ]);
+        let ranges = parse_slots(reply).unwrap();
+        assert_eq!(ranges[0].primary, "10.0.0.1:6379");
+        assert_eq!(ranges[0].replicas, vec!["10.0.0.3:6380".to_string()]);
+        assert_eq!((ranges[1].start, ranges[1].end), (5461, 16383));
+        assert!(parse_slots(Value::Int(1)).is_err());
+    }
+}
@@ -0,0 +1,463 @@
+// client/src/core/cache/disk.rs
+
+use crate::config::{CacheConfig, EvictionPolicy};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use thiserror::Error;
+
+const INDEX_FILE: &str = "index.json";
+const OBJECTS_DIR: &str = "objects";
+
+#[derive(Debug, Error)]
+pub enum DiskCacheError {
+    #[error("Cache I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Cache index error: {0}")]
+    Index(#[from] serde_json::Error),
+    #[error("Entry of {size} bytes exceeds the cache budget of {max_bytes} bytes")]
+    TooLarge { size: u64, max_bytes: u64 },
+    #[error("Need {needed} bytes under a {quota} byte quota, but the rest is pinned by running models")]
+    QuotaExceeded { needed: u64, quota: u64 },
+}
+
+/// One stored blob, shared by every key whose content hashes the same
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Object {
+    size: u64,
+    hits: u64,
+    /// Logical clock value of the last read or write
+    last_access: u64,
+}
+
+/// Counters kept across runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Counters {
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    corruptions: u64,
+    deduplicated: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Index {
+    /// Namespaced key, e.g. `model:<storage uri>`, to hex blake3 of the content
+    keys: HashMap<String, String>,
+    objects: HashMap<String, Object>,
+    counters: Counters,
+    clock: u64,
+    /// Objects in use by this process, never evicted; hash to pin count
+    #[serde(skip)]
+    pins: HashMap<String, usize>,
+}
+
+impl Index {
+    fn bytes(&self) -> u64 {
+        self.objects.values().map(|o| o.size).sum()
+    }
+
+    /// Distinct objects referenced by keys under `prefix`
+    fn objects_under(&self, prefix: &str) -> HashSet<&String> {
+        self.keys.iter().filter(|(k, _)| k.starts_with(prefix)).map(|(_, h)| h).collect()
+    }
+
+    fn touch(&mut self, hash: &str) {
+        self.clock += 1;
+        if let Some(object) = self.objects.get_mut(hash) {
+            object.last_access = self.clock;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub keys: usize,
+    pub objects: usize,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    pub eviction: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Objects dropped because their content no longer matched their hash
+    pub corruptions: u64,
+    /// Writes whose content was already stored
+    pub deduplicated: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GcReport {
+    pub corrupt: usize,
+    /// Files with no index entry, or index entries with no file
+    pub orphans: usize,
+    pub evicted: usize,
+    pub bytes_freed: u64,
+}
+
+/// Persistent content-addressed cache of model binaries and proofs.
+///
+/// Objects are stored once under `objects/<blake3>` however many keys refer
+/// to them, and rehashed on every read so a corrupted file is dropped rather
+/// than returned. The index is rewritten atomically after each change.
+pub struct DiskCache {
+    root: PathBuf,
+    max_bytes: u64,
+    eviction: EvictionPolicy,
+    index: Mutex<Index>,
+}
+
+impl DiskCache {
+    pub fn open(root: impl Into<PathBuf>, config: &CacheConfig) -> Result<Self, DiskCacheError> {
+        let root = root.into();
+        fs::create_dir_all(root.join(OBJECTS_DIR))?;
+        let index = match fs::read(root.join(INDEX_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                // The cache is disposable; `gc` reclaims whatever the lost index pointed at
+                tracing::warn!(error = %e, "Cache index unreadable, starting empty");
+                Index::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Index::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            root,
+            max_bytes: config.max_bytes,
+            eviction: config.eviction,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// Cached content for `key`, verified against its hash
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, DiskCacheError> {
+        let mut index = self.index.lock().unwrap();
+        let Some(hash) = index.keys.get(key).cloned() else {
+            index.counters.misses += 1;
+            self.save(&index)?;
+            return Ok(None);
+        };
+
+        let content = match fs::read(self.object_path(&hash)) {
+            Ok(content) => Some(content).filter(|c| blake3::hash(c).to_hex().as_str() == hash),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        match content {
+            Some(content) => {
+                index.counters.hits += 1;
+                if let Some(object) = index.objects.get_mut(&hash) {
+                    object.hits += 1;
+                }
+                index.touch(&hash);
+                self.save(&index)?;
+                Ok(Some(content))
+            }
+            None => {
+                tracing::warn!(key, hash = %hash, "Cache object corrupt or missing, dropping");
+                index.counters.corruptions += 1;
+                index.counters.misses += 1;
+                self.drop_object(&mut index, &hash)?;
+                self.save(&index)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Store `content` under `key`, evicting other entries to stay within budget
+    pub fn put(&self, key: &str, content: &[u8]) -> Result<blake3::Hash, DiskCacheError> {
+        let size = content.len() as u64;
+        if size > self.max_bytes {
+            return Err(DiskCacheError::TooLarge { size, max_bytes: self.max_bytes });
+        }
+        let hash = blake3::hash(content);
+        let hex = hash.to_hex().to_string();
+
+        let mut index = self.index.lock().unwrap();
+        if index.objects.contains_key(&hex) {
+            index.counters.deduplicated += 1;
+        } else {
+            let path = self.object_path(&hex);
+            fs::create_dir_all(path.parent().unwrap())?;
+            write_atomic(&path, content)?;
+            index.objects.insert(hex.clone(), Object { size, hits: 0, last_access: 0 });
+        }
+        index.touch(&hex);
+
+        if let Some(previous) = index.keys.insert(key.to_string(), hex.clone()) {
+            if previous != hex && !index.keys.values().any(|h| *h == previous) {
+                self.drop_object(&mut index, &previous)?;
+            }
+        }
+        self.evict_to_budget(&mut index, Some(&hex))?;
+        self.save(&index)?;
+        Ok(hash)
+    }
+
+    pub fn remove(&self, key: &str) -> Result<bool, DiskCacheError> {
+        let mut index = self.index.lock().unwrap();
+        let Some(hash) = index.keys.remove(key) else { return Ok(false) };
+        if !index.keys.values().any(|h| *h == hash) {
+            self.drop_object(&mut index, &hash)?;
+        }
+        self.save(&index)?;
+        Ok(true)
+    }
+
+    /// Protect the object behind `key` from eviction until `unpin`; returns its hash
+    pub fn pin(&self, key: &str) -> Option<String> {
+        let mut index = self.index.lock().unwrap();
+        let hash = index.keys.get(key)?.clone();
+        *index.pins.entry(hash.clone()).or_default() += 1;
+        Some(hash)
+    }
+
+    pub fn unpin(&self, hash: &str) {
+        let mut index = self.index.lock().unwrap();
+        if let Some(count) = index.pins.get_mut(hash) {
+            *count -= 1;
+            if *count == 0 {
+                index.pins.remove(hash);
+            }
+        }
+    }
+
+    /// Bytes, objects and pinned objects referenced by keys under `prefix`
+    pub fn usage(&self, prefix: &str) -> (u64, usize, usize) {
+        let index = self.index.lock().unwrap();
+        let hashes = index.objects_under(prefix);
+        let bytes = hashes.iter().filter_map(|h| index.objects.get(*h)).map(|o| o.size).sum();
+        let pinned = hashes.iter().filter(|h| index.pins.contains_key(**h)).count();
+        (bytes, hashes.len(), pinned)
+    }
+
+    /// Evict least recently used, unpinned objects under `prefix` until
+    /// `incoming` more bytes fit within `quota`
+    pub fn make_room(&self, prefix: &str, incoming: u64, quota: u64) -> Result<usize, DiskCacheError> {
+        let mut index = self.index.lock().unwrap();
+        let mut victims: Vec<(String, Object)> = index
+            .objects_under(prefix)
+            .into_iter()
+            .filter_map(|h| Some((h.clone(), index.objects.get(h)?.clone())))
+            .collect();
+        let mut used: u64 = victims.iter().map(|(_, o)| o.size).sum();
+        if used + incoming <= quota {
+            return Ok(0);
+        }
+
+        victims.retain(|(hash, _)| !index.pins.contains_key(hash));
+        victims.sort_by_key(|(_, o)| o.last_access);
+        let mut evicted = 0;
+        for (hash, object) in victims {
+            if used + incoming <= quota {
+                break;
+            }
+            self.drop_object(&mut index, &hash)?;
+            used -= object.size;
+            evicted += 1;
+        }
+        index.counters.evictions += evicted as u64;
+        self.save(&index)?;
+
+        if used + incoming > quota {
+            return Err(DiskCacheError::QuotaExceeded { needed: incoming, quota });
+        }
+        Ok(evicted)
+    }
+
+    /// Verify every object, reconcile the index with the files on disk, and evict to budget
+    pub fn gc(&self) -> Result<GcReport, DiskCacheError> {
+        let mut index = self.index.lock().unwrap();
+        let mut report = GcReport::default();
+        let before = index.bytes();
+
+        let hashes: Vec<String> = index.objects.keys().cloned().collect();
+        for hash in hashes {
+            match fs::read(self.object_path(&hash)) {
+                Ok(content) if blake3::hash(&content).to_hex().as_str() == hash => {}
+                Ok(_) => {
+                    report.corrupt += 1;
+                    index.counters.corruptions += 1;
+                    self.drop_object(&mut index, &hash)?;
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    report.orphans += 1;
+                    self.drop_object(&mut index, &hash)?;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        // Files left behind by an interrupted write or a lost index
+        let known: HashSet<PathBuf> = index.objects.keys().map(|h| self.object_path(h)).collect();
+        for shard in fs::read_dir(self.root.join(OBJECTS_DIR))? {
+            let shard = shard?.path();
+            if !shard.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(shard)? {
+                let path = file?.path();
+                if !known.contains(&path) {
+                    report.bytes_freed += fs::metadata(&path)?.len();
+                    report.orphans += 1;
+                    fs::remove_file(&path)?;
+                }
+            }
+        }
+
+        // Keys whose object was dropped above
+        let live: HashSet<String> = index.objects.keys().cloned().collect();
+        index.keys.retain(|_, hash| live.contains(hash));
+
+        report.evicted = self.evict_to_budget(&mut index, None)?;
+        report.bytes_freed += before - index.bytes();
+        self.save(&index)?;
+        Ok(report)
+    }
+
+    /// Delete every entry; counters are kept
+    pub fn clear(&self) -> Result<u64, DiskCacheError> {
+        let mut index = self.index.lock().unwrap();
+        let freed = index.bytes();
+        fs::remove_dir_all(self.root.join(OBJECTS_DIR))?;
+        fs::create_dir_all(self.root.join(OBJECTS_DIR))?;
+        index.keys.clear();
+        index.objects.clear();
+        self.save(&index)?;
+        Ok(freed)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let index = self.index.lock().unwrap();
+        let counters = index.counters.clone();
+        CacheStats {
+            keys: index.keys.len(),
+            objects: index.objects.len(),
+            bytes: index.bytes(),
+            max_bytes: self.max_bytes,
+            eviction: format!("{:?}", self.eviction).to_lowercase(),
+            hits: counters.hits,
+            misses: counters.misses,
+            evictions: counters.evictions,
+            corruptions: counters.corruptions,
+            deduplicated: counters.deduplicated,
+        }
+    }
+
+    /// Drop objects in policy order until the store fits, sparing `keep`
+    fn evict_to_budget(&self, index: &mut Index, keep: Option<&str>) -> Result<usize, DiskCacheError> {
+        let mut total = index.bytes();
+        if total <= self.max_bytes {
+            return Ok(0);
+        }
+
+        let mut victims: Vec<(String, Object)> = index
+            .objects
+            .iter()
+            .filter(|(hash, _)| Some(hash.as_str()) != keep && !index.pins.contains_key(*hash))
+            .map(|(hash, object)| (hash.clone(), object.clone()))
+            .collect();
+        match self.eviction {
+            EvictionPolicy::Lru => victims.sort_by_key(|(_, o)| o.last_access),
+            EvictionPolicy::Lfu => victims.sort_by_key(|(_, o)| (o.hits, o.last_access)),
+            EvictionPolicy::Size => victims.sort_by_key(|(_, o)| std::cmp::Reverse(o.size)),
+        }
+
+        let mut evicted = 0;
+        for (hash, object) in victims {
+            if total <= self.max_bytes {
+                break;
+            }
+            self.drop_object(index, &hash)?;
+            total -= object.size;
+            evicted += 1;
+        }
+        index.counters.evictions += evicted as u64;
+        Ok(evicted)
+    }
+
+    /// Remove an object and every key pointing at it
+    fn drop_object(&self, index: &mut Index, hash: &str) -> Result<(), DiskCacheError> {
+        index.objects.remove(hash);
+        index.keys.retain(|_, h| h != hash);
+        match fs::remove_file(self.object_path(hash)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join(OBJECTS_DIR).join(&hash[..2]).join(&hash[2..])
+    }
+
+    fn save(&self, index: &Index) -> Result<(), DiskCacheError> {
+        write_atomic(&self.root.join(INDEX_FILE), &serde_json::to_vec(index)?)?;
+        Ok(())
+    }
+}
+
+/// Write to a sibling temp file and rename, so readers never see a partial file
+fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(dir: &Path, max_bytes: u64, eviction: EvictionPolicy) -> DiskCache {
+        DiskCache::open(dir, &CacheConfig { max_bytes, eviction, ..Default::default() }).unwrap()
+    }
+
+    #[test]
+    fn identical_content_is_stored_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = cache(dir.path(), 1024, EvictionPolicy::Lru);
+        store.put("model:a", b"weights").unwrap();
+        store.put("model:b", b"weights").unwrap();
+
+        let stats = store.stats();
+        assert_eq!((stats.keys, stats.objects, stats.bytes, stats.deduplicated), (2, 1, 7, 1));
+
+        // The shared object outlives either key alone
+        store.remove("model:a").unwrap();
+        let reopened = cache(dir.path(), 1024, EvictionPolicy::Lru);
+        assert_eq!(reopened.get("model:b").unwrap().as_deref(), Some(&b"weights"[..]));
+    }
+
+    #[test]
+    fn corrupted_object_is_dropped_on_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = cache(dir.path(), 1024, EvictionPolicy::Lru);
+        let hash = store.put("proof:x", b"proof bytes").unwrap();
+        fs::write(store.object_path(hash.to_hex().as_str()), b"tampered").unwrap();
+
+        assert_eq!(store.get("proof:x").unwrap(), None);
+        assert_eq!(store.stats().corruptions, 1);
+        assert_eq!(store.stats().objects, 0);
+    }
+
+    #[test]
+    fn eviction_follows_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let lfu = cache(&dir.path().join("lfu"), 10, EvictionPolicy::Lfu);
+        lfu.put("hot", b"aaaa").unwrap();
+        lfu.put("cold", b"bbbb").unwrap();
+        lfu.get("hot").unwrap();
+        lfu.put("new", b"cccc").unwrap();
+        assert!(lfu.get("hot").unwrap().is_some());
+        assert!(lfu.get("cold").unwrap().is_none());
+
+        let size = cache(&dir.path().join("size"), 10, EvictionPolicy::Size);
+        size.put("big", b"aaaaaa").unwrap();
+        size.put("small", b"bb").unwrap();
+        size.put("next", b"ccc").unwrap();
+        assert!(size.get("big").unwrap().is_none());
+        assert_eq!(size.stats().bytes, 5);
+    }
+}
@@ -0,0 +1,118 @@
+// client/src/core/cache/known.rs
+
+//! Model IDs this machine has deployed, run or found in a search, most recent
+//! first. Kept next to the model cache, whose entries are keyed by content
+//! rather than model, so shell completion can offer them offline.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const FILE: &str = "known_models";
+const MAX_ENTRIES: usize = 256;
+
+/// Model ID and, when one was seen, its catalog name or file name
+pub type KnownModel = (String, Option<String>);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownModels {
+    entries: Vec<KnownModel>,
+}
+
+impl KnownModels {
+    /// The list under `dir`; missing or unreadable is empty, since it only
+    /// ever serves as a hint
+    pub fn load(dir: &Path) -> Self {
+        let entries = fs::read_to_string(Self::path(dir))
+            .map(|text| {
+                text.lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| match line.split_once('\t') {
+                        Some((id, label)) => (id.to_string(), Some(label.to_string())),
+                        None => (line.to_string(), None),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { entries }
+    }
+
+    /// Move `models` to the front of the list under `dir`. A model recorded
+    /// without a label keeps the one it had.
+    pub fn record(dir: &Path, models: impl IntoIterator<Item = KnownModel>) -> io::Result<()> {
+        let mut known = Self::load(dir);
+        let mut front: Vec<KnownModel> = Vec::new();
+        for (id, label) in models {
+            if front.iter().any(|(seen, _)| *seen == id) {
+                continue;
+            }
+            let previous = known.entries.iter().position(|(known_id, _)| *known_id == id).map(|i| known.entries.remove(i));
+            let label = label.filter(|l| !l.is_empty()).or(previous.and_then(|(_, label)| label));
+            front.push((id, label.map(|l| l.replace(['\t', '\n'], " "))));
+        }
+        front.append(&mut known.entries);
+        front.truncate(MAX_ENTRIES);
+
+        fs::create_dir_all(dir)?;
+        let text: String = front
+            .iter()
+            .map(|(id, label)| match label {
+                Some(label) => format!("{id}\t{label}\n"),
+                None => format!("{id}\n"),
+            })
+            .collect();
+        fs::write(Self::path(dir), text)
+    }
+
+    /// Append `models` not already listed, e.g. what an indexer returned
+    pub fn merge(&mut self, models: impl IntoIterator<Item = KnownModel>) {
+        for model in models {
+            if !self.entries.iter().any(|(id, _)| *id == model.0) {
+                self.entries.push(model);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KnownModel> {
+        self.entries.iter()
+    }
+
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(FILE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(known: &KnownModels) -> Vec<&str> {
+        known.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_recent_first_and_labels_kept() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(KnownModels::load(dir.path()), KnownModels::default());
+
+        KnownModels::record(dir.path(), [("A".into(), Some("resnet".into())), ("B".into(), None)]).unwrap();
+        KnownModels::record(dir.path(), [("C".into(), None), ("A".into(), None)]).unwrap();
+        let known = KnownModels::load(dir.path());
+        assert_eq!(ids(&known), ["C", "A", "B"]);
+        assert_eq!(known.iter().nth(1).unwrap().1.as_deref(), Some("resnet"));
+
+        let mut merged = known.clone();
+        merged.merge([("B".into(), Some("bert".into())), ("D".into(), None)]);
+        assert_eq!(ids(&merged), ["C", "A", "B", "D"]);
+    }
+
+    #[test]
+    fn test_list_is_capped() {
+        let dir = tempfile::TempDir::new().unwrap();
+        KnownModels::record(dir.path(), (0..MAX_ENTRIES + 10).map(|i| (i.to_string(), None))).unwrap();
+        let known = KnownModels::load(dir.path());
+        assert_eq!(known.iter().count(), MAX_ENTRIES);
+        assert_eq!(known.iter().next().unwrap().0, "0");
+    }
+}
@@ -1,24 +1,62 @@
 // local_engine/src/cache/lru.rs
 
 use std::{
-    collections::{HashMap, LinkedList},
-    hash::Hash,
+    collections::{hash_map::RandomState, HashMap},
+    future::Future,
+    hash::{BuildHasher, Hash},
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
+use tokio::sync::OnceCell;
 
-/// LRU Cache entry with expiration and access tracking
-struct CacheEntry<V> {
+/// Slab index standing in for a null link
+const NIL: usize = usize::MAX;
+
+/// Lookup counters, cumulative since creation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Live entries dropped to stay within capacity
+    pub evictions: u64,
+    /// Entries dropped because their TTL had passed
+    pub expirations: u64,
+}
+
+impl CacheStats {
+    fn add(&mut self, other: &CacheStats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.evictions += other.evictions;
+        self.expirations += other.expirations;
+    }
+}
+
+/// LRU Cache entry with expiration, linked into the recency list
+struct Node<K, V> {
+    key: K,
     value: V,
-    last_accessed: Instant,
     expires_at: Option<Instant>,
+    prev: usize,
+    next: usize,
 }
 
-/// Main LRU Cache structure
+/// Main LRU Cache structure.
+///
+/// Entries live in a slab and are threaded onto an intrusive doubly-linked
+/// list by index, so lookups, inserts and recency updates are O(1). Expired
+/// entries are dropped lazily, when looked up or when they reach the LRU end.
 pub struct LRUCache<K, V> {
     capacity: usize,
-    store: HashMap<K, CacheEntry<V>>,
-    access_order: LinkedList<K>,
+    index: HashMap<K, usize>,
+    slab: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    /// Least recently used
+    head: usize,
+    /// Most recently used
+    tail: usize,
     default_ttl: Option<Duration>,
+    stats: CacheStats,
 }
 
 impl<K, V> LRUCache<K, V>
@@ -29,9 +67,13 @@ where
     pub fn new(capacity: usize, default_ttl: Option<Duration>) -> Self {
         Self {
             capacity,
-            store: HashMap::with_capacity(capacity),
-            access_order: LinkedList::new(),
+            index: HashMap::with_capacity(capacity),
+            slab: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: NIL,
+            tail: NIL,
             default_ttl,
+            stats: CacheStats::default(),
         }
     }
 
@@ -41,77 +83,152 @@ where
             .or(self.default_ttl)
             .map(|d| Instant::now() + d);
 
-        let entry = CacheEntry {
-            value,
-            last_accessed: Instant::now(),
-            expires_at,
-        };
-
-        // Evict expired entries first
-        self.evict_expired();
+        if let Some(&idx) = self.index.get(&key) {
+            let node = self.node_mut(idx);
+            node.expires_at = expires_at;
+            let old_value = std::mem::replace(&mut node.value, value);
+            self.touch(idx);
+            return Some(old_value);
+        }
 
-        let old_value = self.store.insert(key.clone(), entry);
-        self.update_access_order(&key);
+        let node = Node { key: key.clone(), value, expires_at, prev: NIL, next: NIL };
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.slab[idx] = Some(node);
+                idx
+            }
+            None => {
+                self.slab.push(Some(node));
+                self.slab.len() - 1
+            }
+        };
+        self.index.insert(key, idx);
+        self.push_back(idx);
 
-        if self.store.len() > self.capacity {
+        if self.index.len() > self.capacity {
             self.evict_lru();
         }
-
-        old_value.map(|e| e.value)
+        None
     }
 
     /// Get mutable reference to value, updating access time
     pub fn get(&mut self, key: &K) -> Option<&mut V> {
-        if let Some(entry) = self.store.get_mut(key) {
-            if entry.is_expired() {
-                self.store.remove(key);
-                return None;
-            }
+        let Some(&idx) = self.index.get(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        if self.node_mut(idx).is_expired_at(Instant::now()) {
+            self.remove_at(idx);
+            self.stats.expirations += 1;
+            self.stats.misses += 1;
+            return None;
+        }
 
-            entry.last_accessed = Instant::now();
-            self.update_access_order(key);
-            Some(&mut entry.value)
-        } else {
-            None
+        self.stats.hits += 1;
+        self.touch(idx);
+        Some(&mut self.node_mut(idx).value)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let idx = *self.index.get(key)?;
+        Some(self.remove_at(idx).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Drop every expired entry now rather than lazily; O(n)
+    pub fn purge_expired(&mut self) -> usize {
+        let now = Instant::now();
+        let expired: Vec<usize> = self
+            .index
+            .values()
+            .copied()
+            .filter(|&idx| self.slab[idx].as_ref().is_some_and(|n| n.is_expired_at(now)))
+            .collect();
+        for &idx in &expired {
+            self.remove_at(idx);
         }
+        self.stats.expirations += expired.len() as u64;
+        expired.len()
     }
 
-    /// Remove least recently used entry
+    /// Remove least recently used entry, discarding expired ones on the way
     fn evict_lru(&mut self) -> Option<(K, V)> {
-        while let Some(key) = self.access_order.pop_front() {
-            if let Some(entry) = self.store.remove(&key) {
-                if !entry.is_expired() {
-                    return Some((key, entry.value));
-                }
+        let now = Instant::now();
+        while self.head != NIL {
+            let expired = self.node_mut(self.head).is_expired_at(now);
+            let entry = self.remove_at(self.head);
+            if expired {
+                self.stats.expirations += 1;
+            } else {
+                self.stats.evictions += 1;
+                return Some(entry);
             }
         }
         None
     }
 
-    /// Evict all expired entries
-    fn evict_expired(&mut self) {
-        let now = Instant::now();
-        self.store.retain(|k, v| {
-            let retain = !v.is_expired_at(now);
-            if !retain {
-                self.access_order.retain(|x| x != k);
-            }
-            retain
-        });
+    fn remove_at(&mut self, idx: usize) -> (K, V) {
+        self.unlink(idx);
+        let node = self.slab[idx].take().expect("linked slab slot is occupied");
+        self.free.push(idx);
+        self.index.remove(&node.key);
+        (node.key, node.value)
     }
 
-    /// Update access order tracking
-    fn update_access_order(&mut self, key: &K) {
-        self.access_order.retain(|k| k != key);
-        self.access_order.push_back(key.clone());
+    /// Mark as most recently used
+    fn touch(&mut self, idx: usize) {
+        if self.tail != idx {
+            self.unlink(idx);
+            self.push_back(idx);
+        }
     }
-}
 
-impl<V> CacheEntry<V> {
-    fn is_expired(&self) -> bool {
-        self.is_expired_at(Instant::now())
+    fn push_back(&mut self, idx: usize) {
+        let tail = self.tail;
+        {
+            let node = self.node_mut(idx);
+            node.prev = tail;
+            node.next = NIL;
+        }
+        match tail {
+            NIL => self.head = idx,
+            tail => self.node_mut(tail).next = idx,
+        }
+        self.tail = idx;
     }
 
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next) = {
+            let node = self.node_mut(idx);
+            (node.prev, node.next)
+        };
+        match prev {
+            NIL => self.head = next,
+            prev => self.node_mut(prev).next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.node_mut(next).prev = prev,
+        }
+    }
+
+    fn node_mut(&mut self, idx: usize) -> &mut Node<K, V> {
+        self.slab[idx].as_mut().expect("linked slab slot is occupied")
+    }
+}
+
+impl<K, V> Node<K, V> {
     fn is_expired_at(&self, timestamp: Instant) -> bool {
         self.expires_at
             .map(|expiry| timestamp > expiry)
@@ -119,22 +236,134 @@ impl<V> CacheEntry<V> {
     }
 }
 
+struct Shard<K, V> {
+    cache: LRUCache<K, V>,
+    /// Loads in progress, so concurrent misses on one key share a single load
+    loading: HashMap<K, Arc<OnceCell<V>>>,
+}
+
+/// Thread-safe LRU split into independently locked shards by key hash.
+///
+/// Capacity is divided evenly, so eviction is least-recently-used per shard
+/// rather than globally. Values are cloned out; wrap large ones in `Arc`.
+pub struct ShardedLruCache<K, V> {
+    shards: Box<[Mutex<Shard<K, V>>]>,
+    hasher: RandomState,
+}
+
+impl<K, V> ShardedLruCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new(capacity: usize, shards: usize, default_ttl: Option<Duration>) -> Self {
+        let shards = shards.max(1);
+        let per_shard = capacity.div_ceil(shards);
+        Self {
+            shards: (0..shards)
+                .map(|_| {
+                    Mutex::new(Shard {
+                        cache: LRUCache::new(per_shard, default_ttl),
+                        loading: HashMap::new(),
+                    })
+                })
+                .collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.shard(key).lock().unwrap().cache.get(key).cloned()
+    }
+
+    pub fn insert(&self, key: K, value: V, custom_ttl: Option<Duration>) -> Option<V> {
+        self.shard(&key).lock().unwrap().cache.insert(key, value, custom_ttl)
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.shard(key).lock().unwrap().cache.remove(key)
+    }
+
+    /// Cached value, or the result of `load`, which runs at most once per key
+    /// however many tasks miss on it concurrently. No lock is held while loading.
+    pub async fn get_or_insert_with<F, Fut>(&self, key: K, load: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        match self.get_or_try_insert_with(key, || async { Ok::<_, std::convert::Infallible>(load().await) }).await {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// As `get_or_insert_with`, but a failed load is returned and nothing is cached
+    pub async fn get_or_try_insert_with<F, Fut, E>(&self, key: K, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let shard = self.shard(&key);
+        let cell = {
+            let mut guard = shard.lock().unwrap();
+            if let Some(value) = guard.cache.get(&key) {
+                return Ok(value.clone());
+            }
+            guard.loading.entry(key.clone()).or_default().clone()
+        };
+
+        let result = cell.get_or_try_init(load).await.cloned();
+
+        let mut guard = shard.lock().unwrap();
+        // Only the first finisher publishes; later waiters find the entry gone
+        if guard.loading.get(&key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+            guard.loading.remove(&key);
+            if let Ok(value) = &result {
+                guard.cache.insert(key, value.clone(), None);
+            }
+        }
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.lock().unwrap().cache.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Counters summed over all shards
+    pub fn stats(&self) -> CacheStats {
+        let mut total = CacheStats::default();
+        for shard in self.shards.iter() {
+            total.add(&shard.lock().unwrap().cache.stats());
+        }
+        total
+    }
+
+    fn shard(&self, key: &K) -> &Mutex<Shard<K, V>> {
+        &self.shards[self.hasher.hash_one(key) as usize % self.shards.len()]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::thread::sleep;
 
     #[test]
     fn test_basic_lru() {
         let mut cache = LRUCache::new(2, None);
-        
+
         cache.insert("a", 1, None);
         cache.insert("b", 2, None);
-        
+
         assert_eq!(cache.get(&"a"), Some(&mut 1));
-        
+
         cache.insert("c", 3, None);
-        
+
         assert_eq!(cache.get(&"b"), None); // b should be evicted
         assert_eq!(cache.get(&"a"), Some(&mut 1));
         assert_eq!(cache.get(&"c"), Some(&mut 3));
@@ -143,12 +372,12 @@ mod tests {
     #[test]
     fn test_ttl_expiration() {
         let mut cache = LRUCache::new(3, Some(Duration::from_secs(1)));
-        
+
         cache.insert(1, "a", None);
         cache.insert(2, "b", Some(Duration::from_millis(500)));
-        
+
         sleep(Duration::from_secs(1));
-        
+
         assert_eq!(cache.get(&1), None);
         assert_eq!(cache.get(&2), None);
     }
@@ -156,13 +385,58 @@ mod tests {
     #[test]
     fn test_custom_ttl_override() {
         let mut cache = LRUCache::new(2, Some(Duration::from_secs(1)));
-        
+
         cache.insert("a", 1, Some(Duration::from_secs(3)));
         cache.insert("b", 2, None); // Uses default 1s TTL
-        
+
         sleep(Duration::from_secs(2));
-        
+
         assert_eq!(cache.get(&"a"), Some(&mut 1)); // Still valid
         assert_eq!(cache.get(&"b"), None); // Expired
     }
+
+    #[test]
+    fn test_slots_reused_and_counted() {
+        let mut cache = LRUCache::new(2, None);
+        for i in 0..100 {
+            cache.insert(i, i, None);
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.slab.len(), 3);
+
+        assert_eq!(cache.remove(&99), Some(99));
+        cache.get(&98);
+        cache.get(&0);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, evictions: 98, expirations: 0 });
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_load_once() {
+        let cache = Arc::new(ShardedLruCache::new(64, 8, None));
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..16)
+            .map(|_| {
+                let (cache, loads) = (cache.clone(), loads.clone());
+                tokio::spawn(async move {
+                    cache
+                        .get_or_insert_with("model", || async {
+                            loads.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            7
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), 7);
+        }
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.get(&"model"), Some(7));
+        let failed = cache.get_or_try_insert_with("other", || async { Err::<i32, _>("offline") }).await;
+        assert_eq!(failed, Err("offline"));
+        assert_eq!(cache.len(), 1);
+    }
 }
@@ -0,0 +1,117 @@
+// client/src/core/cache/manager.rs
+
+use super::disk::{DiskCache, DiskCacheError};
+use crate::config::ModelConfig;
+use serde::Serialize;
+
+/// Key namespace of model binaries in the shared store
+const MODEL_PREFIX: &str = "model:";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelCacheUsage {
+    pub bytes: u64,
+    pub quota: u64,
+    pub models: usize,
+    /// Held by a running inference or verification
+    pub pinned: usize,
+}
+
+/// Releases a model for eviction when dropped
+pub struct ModelPin<'a> {
+    store: &'a DiskCache,
+    hash: Option<String>,
+}
+
+impl Drop for ModelPin<'_> {
+    fn drop(&mut self) {
+        if let Some(hash) = &self.hash {
+            self.store.unpin(hash);
+        }
+    }
+}
+
+/// Keeps downloaded models within `model.max_cache_size`.
+///
+/// Models share the content-addressed store with proofs; this quota applies
+/// to the model keys alone, on top of the store-wide `cache.max_bytes`.
+/// Downloads land in memory, so room is made before anything is written.
+pub struct ModelCache<'a> {
+    store: &'a DiskCache,
+    quota: u64,
+}
+
+impl<'a> ModelCache<'a> {
+    pub fn new(store: &'a DiskCache, config: &ModelConfig) -> Self {
+        Self { store, quota: config.max_cache_size }
+    }
+
+    /// Cached binary for `storage_uri`, pinned until the returned guard drops
+    pub fn get(&self, storage_uri: &str) -> Result<Option<(ModelPin<'a>, Vec<u8>)>, DiskCacheError> {
+        let key = model_key(storage_uri);
+        let pin = self.pin(&key);
+        let blob = self.store.get(&key)?;
+        self.record();
+        Ok(blob.map(|blob| (pin, blob)))
+    }
+
+    /// Evict least recently used, unpinned models until `blob` fits, then store and pin it
+    pub fn insert(&self, storage_uri: &str, blob: &[u8]) -> Result<ModelPin<'a>, DiskCacheError> {
+        let key = model_key(storage_uri);
+        let evicted = self.store.make_room(MODEL_PREFIX, blob.len() as u64, self.quota)?;
+        if evicted > 0 {
+            crate::metrics::log_model_cache_evictions(evicted);
+            tracing::info!(evicted, quota = self.quota, "Evicted cached models to stay within quota");
+        }
+        self.store.put(&key, blob)?;
+        let pin = self.pin(&key);
+        self.record();
+        Ok(pin)
+    }
+
+    pub fn usage(&self) -> ModelCacheUsage {
+        let (bytes, models, pinned) = self.store.usage(MODEL_PREFIX);
+        ModelCacheUsage { bytes, quota: self.quota, models, pinned }
+    }
+
+    fn pin(&self, key: &str) -> ModelPin<'a> {
+        ModelPin { store: self.store, hash: self.store.pin(key) }
+    }
+
+    fn record(&self) {
+        let usage = self.usage();
+        crate::metrics::log_model_cache_usage(usage.bytes, usage.quota);
+    }
+}
+
+fn model_key(storage_uri: &str) -> String {
+    format!("{MODEL_PREFIX}{storage_uri}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CacheConfig, EvictionPolicy};
+
+    #[test]
+    fn pinned_models_survive_quota_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskCache::open(dir.path(), &CacheConfig { max_bytes: 1024, eviction: EvictionPolicy::Lru, ..Default::default() }).unwrap();
+        let models = ModelCache::new(&store, &ModelConfig { max_cache_size: 10 });
+
+        let in_use = models.insert("ipfs://a", b"aaaa").unwrap();
+        drop(models.insert("ipfs://b", b"bbbb").unwrap());
+        // Only the unpinned model can make room
+        let _c = models.insert("ipfs://c", b"cccc").unwrap();
+        assert!(store.get("model:ipfs://a").unwrap().is_some());
+        assert!(store.get("model:ipfs://b").unwrap().is_none());
+
+        // Everything left is pinned, so the quota cannot be met
+        assert!(matches!(
+            models.insert("ipfs://d", b"dddd"),
+            Err(DiskCacheError::QuotaExceeded { needed: 4, quota: 10 })
+        ));
+        drop(in_use);
+        assert!(models.insert("ipfs://d", b"dddd").is_ok());
+        assert_eq!(models.usage().bytes, 8);
+    }
+}
@@ -1,8 +1,9 @@
 // local_engine/src/db/redis.rs
 
+use super::cluster::ClusterRouter;
 use deadpool::managed::{Manager, Pool, RecycleResult};
-use redis::{aio::Connection, Client, Cmd, IntoConnectionInfo, RedisResult};
-use std::time::Duration;
+use redis::{aio::Connection, Client, Cmd, FromRedisValue, IntoConnectionInfo, RedisResult};
+use std::{sync::Arc, time::Duration};
 use tokio::time::timeout;
 use tracing::{error, info, instrument};
 
@@ -18,8 +19,17 @@ pub enum RedisConfig {
         service_name: String,
         read_only: bool,
     },
+    /// Seed nodes of a Redis Cluster; the rest are discovered from `CLUSTER SLOTS`
+    Cluster {
+        nodes: Vec<String>,
+        /// Serve reads from replicas, not only when a primary is down
+        read_from_replicas: bool,
+    },
 }
 
+/// Interval between PINGs of every cluster node
+const CLUSTER_HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Redis connection manager with health checks
 #[derive(Clone)]
 pub struct RedisManager {
@@ -27,6 +37,12 @@ pub struct RedisManager {
     config: RedisConfig,
 }
 
+impl RedisManager {
+    pub(crate) fn new(client: Client, config: RedisConfig) -> Self {
+        Self { client, config }
+    }
+}
+
 impl Manager for RedisManager {
     type Type = Connection;
     type Error = redis::RedisError;
@@ -36,7 +52,8 @@ impl Manager for RedisManager {
         
         // Set read-only mode for replicas
         if let RedisConfig::SingleNode { read_only, .. } | 
-            RedisConfig::Sentinel { read_only, .. } = &self.config 
+            RedisConfig::Sentinel { read_only, .. } |
+            RedisConfig::Cluster { read_from_replicas: read_only, .. } = &self.config 
         {
             if *read_only {
                 Cmd::new().arg("READONLY").query_async(&mut conn).await?;
@@ -66,8 +83,10 @@ impl Manager for RedisManager {
 
 /// Redis connection pool with failover support
 pub struct RedisPool {
-    primary_pool: Pool<RedisManager>,
+    primary_pool: Option<Pool<RedisManager>>,
     replica_pool: Option<Pool<RedisManager>>,
+    /// Set for `RedisConfig::Cluster`, which routes per key instead of using the pools above
+    cluster: Option<Arc<ClusterRouter>>,
 }
 
 impl RedisPool {
@@ -75,6 +94,15 @@ impl RedisPool {
     #[instrument(skip_all)]
     pub async fn new(config: RedisConfig) -> RedisResult<Self> {
         let (primary_manager, replica_manager) = match &config {
+            RedisConfig::Cluster { nodes, read_from_replicas } => {
+                let router = Arc::new(ClusterRouter::connect(nodes.clone(), *read_from_replicas).await?);
+                router.clone().spawn_health_checks(CLUSTER_HEALTH_INTERVAL);
+                return Ok(Self {
+                    primary_pool: None,
+                    replica_pool: None,
+                    cluster: Some(router),
+                });
+            }
             RedisConfig::SingleNode { url, read_only } => {
                 let client = Client::open(url.as_str())?;
                 let primary_manager = RedisManager {
@@ -153,21 +181,50 @@ impl RedisPool {
         });
 
         Ok(Self {
-            primary_pool,
+            primary_pool: Some(primary_pool),
             replica_pool,
+            cluster: None,
         })
     }
 
-    /// Get connection with read/write preference
+    /// Get connection with read/write preference; on a cluster, to any healthy primary
     #[instrument(skip(self))]
     pub async fn get_conn(&self, read_only: bool) -> RedisResult<Connection> {
+        if let Some(cluster) = &self.cluster {
+            return cluster.any_conn().await;
+        }
         if read_only {
             if let Some(replica_pool) = &self.replica_pool {
                 return self.get_conn_with_retry(replica_pool, 3).await;
             }
         }
         
-        self.get_conn_with_retry(&self.primary_pool, 3).await
+        self.get_conn_with_retry(self.primary_pool.as_ref().expect("standalone pool"), 3).await
+    }
+
+    /// Get connection to the node serving `key`
+    #[instrument(skip(self, key))]
+    pub async fn get_conn_for(&self, key: &str, read_only: bool) -> RedisResult<Connection> {
+        match &self.cluster {
+            Some(cluster) => cluster.conn_for(key.as_bytes(), read_only).await,
+            None => self.get_conn(read_only).await,
+        }
+    }
+
+    /// Run a single-key command, following cluster redirects
+    pub async fn query<T: FromRedisValue>(&self, key: &str, read_only: bool, cmd: &Cmd) -> RedisResult<T> {
+        match &self.cluster {
+            Some(cluster) => cluster.query(key.as_bytes(), read_only, cmd).await,
+            None => cmd.query_async(&mut self.get_conn(read_only).await?).await,
+        }
+    }
+
+    /// Health of every cluster node; empty for single-node and Sentinel deployments
+    pub async fn node_health(&self) -> Vec<(String, bool)> {
+        match &self.cluster {
+            Some(cluster) => cluster.health_check().await,
+            None => Vec::new(),
+        }
     }
 
     /// Retry logic with exponential backoff
@@ -206,3 +263,6 @@ impl RedisPool {
 /// 
 /// let mut read_conn = pool.get_conn(true).await?; // Read connection
 /// let value: String = redis::cmd("GET").arg("key").query_async(&mut read_conn).await?;
+///
+/// // Routed by key slot on a cluster, plain GET elsewhere
+/// let value: String = pool.query("key", true, redis::cmd("GET").arg("key")).await?;
@@ -0,0 +1,207 @@
+// client/src/core/cache/results.rs
+
+use super::redis::RedisPool;
+use crate::config::ResultCacheConfig;
+use crate::core::inference::backend::TensorData;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Compare-and-delete, so a lock that expired and was retaken is left alone
+const RELEASE_LOCK: &str = r#"if redis.call("GET", KEYS[1]) == ARGV[1] then return redis.call("DEL", KEYS[1]) else return 0 end"#;
+
+#[derive(Debug, Error)]
+pub enum ResultCacheError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("Cached result encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+    #[error("Cached output does not match its hash")]
+    Corrupt,
+}
+
+/// Output and proof of one inference, shared across a provider fleet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedInference {
+    /// `output_hash` of `output`, checked on every read
+    pub output_hash: [u8; 32],
+    pub proof: Vec<u8>,
+    pub output: Vec<TensorData>,
+}
+
+impl CachedInference {
+    pub fn new(output: Vec<TensorData>, proof: Vec<u8>) -> Self {
+        Self { output_hash: output_hash(&output), proof, output }
+    }
+}
+
+/// Blake3 over every tensor's shape and little-endian elements, in order
+pub fn output_hash(tensors: &[TensorData]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    for tensor in tensors {
+        hasher.update(&(tensor.shape.len() as u64).to_le_bytes());
+        for dim in &tensor.shape {
+            hasher.update(&(*dim as u64).to_le_bytes());
+        }
+        for value in &tensor.data {
+            hasher.update(&value.to_le_bytes());
+        }
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// Redis cache of `(model_hash, input_hash) -> (output_hash, proof)`.
+///
+/// Concurrent misses on one key are single-flighted across the fleet with a
+/// `SET NX PX` lock: one node computes while the others poll for its result,
+/// falling back to computing themselves if the lock holder does not finish.
+/// Redis being unreachable never fails an inference; it only disables reuse.
+pub struct InferenceResultCache {
+    pool: RedisPool,
+    config: ResultCacheConfig,
+}
+
+impl InferenceResultCache {
+    pub fn new(pool: RedisPool, config: &ResultCacheConfig) -> Self {
+        Self { pool, config: config.clone() }
+    }
+
+    /// Cached result, or the result of `compute`, stored for the next caller.
+    /// The flag is true when the result came from the cache.
+    pub async fn get_or_compute<F, Fut, E>(
+        &self,
+        model_hash: &[u8; 32],
+        input_hash: &[u8; 32],
+        compute: F,
+    ) -> Result<(CachedInference, bool), E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<CachedInference, E>>,
+    {
+        let key = self.key(model_hash, input_hash);
+        if let Some(result) = self.lookup(&key).await {
+            return Ok((result, true));
+        }
+
+        let lock = format!("{key}:lock");
+        let token = hex::encode(rand::random::<[u8; 16]>());
+        match self.try_lock(&lock, &token).await {
+            Ok(true) => {
+                let result = compute().await;
+                if let Ok(result) = &result {
+                    self.store(&key, result).await;
+                }
+                if let Err(e) = self.unlock(&lock, &token).await {
+                    warn!(error = %e, "Result cache lock not released; it will expire");
+                }
+                result.map(|r| (r, false))
+            }
+            Ok(false) => {
+                if let Some(result) = self.wait_for(&key, &lock).await {
+                    return Ok((result, true));
+                }
+                let result = compute().await?;
+                self.store(&key, &result).await;
+                Ok((result, false))
+            }
+            Err(e) => {
+                warn!(error = %e, "Result cache unavailable, computing directly");
+                compute().await.map(|r| (r, false))
+            }
+        }
+    }
+
+    fn key(&self, model_hash: &[u8; 32], input_hash: &[u8; 32]) -> String {
+        format!("{}{}:{}", self.config.key_prefix, hex::encode(model_hash), hex::encode(input_hash))
+    }
+
+    async fn lookup(&self, key: &str) -> Option<CachedInference> {
+        match self.get(key).await {
+            Ok(result) => {
+                crate::metrics::log_result_cache(if result.is_some() { "hit" } else { "miss" });
+                result
+            }
+            Err(e) => {
+                warn!(error = %e, key, "Result cache read failed");
+                crate::metrics::log_result_cache("error");
+                None
+            }
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<CachedInference>, ResultCacheError> {
+        let bytes: Option<Vec<u8>> = self.pool.query(key, true, redis::cmd("GET").arg(key)).await?;
+        let Some(bytes) = bytes else { return Ok(None) };
+        let result: CachedInference = bincode::deserialize(&bytes)?;
+        if output_hash(&result.output) != result.output_hash {
+            return Err(ResultCacheError::Corrupt);
+        }
+        Ok(Some(result))
+    }
+
+    async fn store(&self, key: &str, result: &CachedInference) {
+        let stored = async {
+            let value = bincode::serialize(result)?;
+            self.pool
+                .query::<()>(key, false, redis::cmd("SET").arg(key).arg(value).arg("EX").arg(self.config.ttl_secs.max(1)))
+                .await?;
+            Ok::<_, ResultCacheError>(())
+        };
+        if let Err(e) = stored.await {
+            warn!(error = %e, key, "Result not cached");
+        }
+    }
+
+    async fn try_lock(&self, lock: &str, token: &str) -> Result<bool, ResultCacheError> {
+        let set: Option<String> = self
+            .pool
+            .query(lock, false, redis::cmd("SET").arg(lock).arg(token).arg("NX").arg("PX").arg(self.config.lock_ttl_ms.max(1)))
+            .await?;
+        Ok(set.is_some())
+    }
+
+    async fn unlock(&self, lock: &str, token: &str) -> Result<(), ResultCacheError> {
+        self.pool
+            .query::<i64>(lock, false, redis::cmd("EVAL").arg(RELEASE_LOCK).arg(1).arg(lock).arg(token))
+            .await?;
+        Ok(())
+    }
+
+    /// Poll until another node publishes the result, its lock lapses, or `lock_ttl_ms` passes
+    async fn wait_for(&self, key: &str, lock: &str) -> Option<CachedInference> {
+        let deadline = Instant::now() + Duration::from_millis(self.config.lock_ttl_ms);
+        let poll = Duration::from_millis(self.config.poll_interval_ms.max(10));
+        while Instant::now() < deadline {
+            tokio::time::sleep(poll).await;
+            if let Some(result) = self.lookup(key).await {
+                return Some(result);
+            }
+            match self.pool.query::<bool>(lock, false, redis::cmd("EXISTS").arg(lock)).await {
+                Ok(true) => continue,
+                // The holder finished without a result (it failed) or Redis is gone
+                Ok(false) | Err(_) => break,
+            }
+        }
+        debug!(key, "No result from the lock holder, computing locally");
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_hash_covers_shape_and_values() {
+        let flat = TensorData::new(vec![4], vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let square = TensorData::new(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_ne!(output_hash(&[flat.clone()]), output_hash(&[square]));
+
+        let mut changed = flat.clone();
+        changed.data[3] = 4.5;
+        assert_ne!(output_hash(&[flat.clone()]), output_hash(&[changed]));
+        assert_eq!(CachedInference::new(vec![flat.clone()], vec![]).output_hash, output_hash(&[flat]));
+    }
+}
@@ -0,0 +1,154 @@
+// client/src/core/catalog.rs
+
+use crate::core::inference::io_schema::{hash_from_cid, SchemaError, SchemaStore, ShapeSpec};
+use crate::core::postprocessing::pipeline::Postprocess;
+use crate::core::preprocessing::pipeline::Preprocessing;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub const MAX_NAME_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+pub const MAX_TAGS: usize = 16;
+pub const MAX_TAG_LEN: usize = 32;
+/// Frameworks the indexer exposes as a search facet
+pub const FRAMEWORKS: [&str; 6] = ["onnx", "pytorch", "tensorflow", "tflite", "tensorrt", "jax"];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CatalogError {
+    #[error("Model name must be 1 to {MAX_NAME_LEN} characters")]
+    Name,
+    #[error("Description exceeds {MAX_DESCRIPTION_LEN} characters")]
+    Description,
+    #[error("At most {MAX_TAGS} tags are allowed")]
+    TooManyTags,
+    #[error("Tag `{0}` must be 1 to {MAX_TAG_LEN} characters of [a-z0-9-]")]
+    Tag(String),
+    #[error("Duplicate tag `{0}`")]
+    DuplicateTag(String),
+    #[error("License must be an SPDX identifier such as `Apache-2.0`")]
+    License,
+    #[error("Unknown framework `{0}`")]
+    Framework(String),
+    #[error("Input shape must have at least one dimension")]
+    InputShape,
+    #[error("{0}")]
+    Preprocessing(String),
+    #[error("{0}")]
+    Postprocess(String),
+}
+
+/// Catalog document published to IPFS and anchored with `set_metadata_uri`:
+/// `{"name": "resnet50", "description": "...", "tags": ["vision"], "license": "Apache-2.0",
+///   "framework": "onnx", "input_shape": [null, 3, 224, 224]}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelCatalog {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub license: String,
+    pub framework: String,
+    /// `null` marks a dynamic dimension, as in I/O schemas
+    pub input_shape: ShapeSpec,
+    /// Turns raw text, images or audio into the model's input tensors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preprocessing: Option<Preprocessing>,
+    /// Turns output tensors into labelled predictions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postprocess: Option<Postprocess>,
+}
+
+impl ModelCatalog {
+    /// Reject documents the indexer would not index, before anything is pinned
+    pub fn validate(&self) -> Result<(), CatalogError> {
+        if self.name.trim().is_empty() || self.name.chars().count() > MAX_NAME_LEN {
+            return Err(CatalogError::Name);
+        }
+        if self.description.chars().count() > MAX_DESCRIPTION_LEN {
+            return Err(CatalogError::Description);
+        }
+        if self.tags.len() > MAX_TAGS {
+            return Err(CatalogError::TooManyTags);
+        }
+        for (i, tag) in self.tags.iter().enumerate() {
+            let valid_chars = tag.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+            if tag.is_empty() || tag.len() > MAX_TAG_LEN || !valid_chars {
+                return Err(CatalogError::Tag(tag.clone()));
+            }
+            if self.tags[..i].contains(tag) {
+                return Err(CatalogError::DuplicateTag(tag.clone()));
+            }
+        }
+        let valid_license = self.license.bytes().all(|b| b.is_ascii_alphanumeric() || b"-.+".contains(&b));
+        if self.license.is_empty() || !valid_license {
+            return Err(CatalogError::License);
+        }
+        if !FRAMEWORKS.contains(&self.framework.as_str()) {
+            return Err(CatalogError::Framework(self.framework.clone()));
+        }
+        if self.input_shape.0.is_empty() || self.input_shape.0.contains(&Some(0)) {
+            return Err(CatalogError::InputShape);
+        }
+        if let Some(preprocessing) = &self.preprocessing {
+            preprocessing.validate().map_err(|e| CatalogError::Preprocessing(e.to_string()))?;
+        }
+        if let Some(postprocess) = &self.postprocess {
+            postprocess.validate().map_err(|e| CatalogError::Postprocess(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// The document anchored at `metadata_uri`, or `None` when the model has none
+    pub async fn fetch(store: &SchemaStore, metadata_uri: &str) -> Result<Option<Self>, SchemaError> {
+        let Some(hash) = metadata_uri.strip_prefix("ipfs://").and_then(hash_from_cid) else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_slice(&store.fetch_document(&hash).await?)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> ModelCatalog {
+        serde_json::from_str(
+            r#"{"name": "resnet50", "description": "ImageNet classifier", "tags": ["vision", "int8"],
+                "license": "Apache-2.0", "framework": "onnx", "input_shape": [null, 3, 224, 224]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn accepts_well_formed_catalog() {
+        assert_eq!(catalog().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_unsearchable_tags() {
+        let mut upper = catalog();
+        upper.tags.push("Vision".into());
+        assert_eq!(upper.validate(), Err(CatalogError::Tag("Vision".into())));
+
+        let mut duplicate = catalog();
+        duplicate.tags.push("vision".into());
+        assert_eq!(duplicate.validate(), Err(CatalogError::DuplicateTag("vision".into())));
+    }
+
+    #[test]
+    fn rejects_unknown_framework_and_empty_shape() {
+        let mut model = catalog();
+        model.framework = "caffe".into();
+        assert_eq!(model.validate(), Err(CatalogError::Framework("caffe".into())));
+
+        let mut model = catalog();
+        model.input_shape = ShapeSpec(vec![]);
+        assert_eq!(model.validate(), Err(CatalogError::InputShape));
+
+        let mut model = catalog();
+        model.preprocessing = serde_json::from_str(r#"{"kind": "image", "width": 0, "height": 224}"#).unwrap();
+        assert!(matches!(model.validate(), Err(CatalogError::Preprocessing(_))));
+    }
+}
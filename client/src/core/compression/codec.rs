@@ -0,0 +1,74 @@
+// client/src/core/compression/codec.rs
+
+use crate::config::CompressionCodec;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const ZSTD_DEFAULT_LEVEL: i32 = 9;
+const BROTLI_DEFAULT_QUALITY: i32 = 9;
+/// 4 MiB window, the chunk size default
+const BROTLI_WINDOW_LOG: i32 = 22;
+
+/// Codec and level a container was written with, recorded in its header so
+/// the loader never needs the writer's configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "codec", rename_all = "lowercase")]
+pub enum Codec {
+    Zstd { level: i32 },
+    Lz4,
+    Brotli { quality: i32 },
+    None,
+}
+
+impl Codec {
+    pub fn from_config(codec: CompressionCodec, level: Option<i32>) -> Self {
+        match codec {
+            CompressionCodec::Zstd => Codec::Zstd { level: level.unwrap_or(ZSTD_DEFAULT_LEVEL) },
+            CompressionCodec::Lz4 => Codec::Lz4,
+            CompressionCodec::Brotli => Codec::Brotli { quality: level.unwrap_or(BROTLI_DEFAULT_QUALITY) },
+            CompressionCodec::None => Codec::None,
+        }
+    }
+
+    /// Only zstd makes use of a trained dictionary
+    pub fn uses_dictionary(self) -> bool {
+        matches!(self, Codec::Zstd { .. })
+    }
+
+    pub fn compress(self, chunk: &[u8], dictionary: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Codec::Zstd { level } => zstd::bulk::Compressor::with_dictionary(level, dictionary)?.compress(chunk),
+            Codec::Lz4 => Ok(lz4_flex::block::compress(chunk)),
+            Codec::Brotli { quality } => {
+                let params = brotli::enc::BrotliEncoderParams {
+                    quality,
+                    lgwin: BROTLI_WINDOW_LOG,
+                    ..Default::default()
+                };
+                let mut out = Vec::new();
+                brotli::BrotliCompress(&mut &chunk[..], &mut out, &params)?;
+                Ok(out)
+            }
+            Codec::None => Ok(chunk.to_vec()),
+        }
+    }
+
+    /// `raw_len` is the chunk's exact uncompressed size, from the container header
+    pub fn decompress(self, chunk: &[u8], raw_len: usize, dictionary: &[u8]) -> io::Result<Vec<u8>> {
+        let raw = match self {
+            Codec::Zstd { .. } => zstd::bulk::Decompressor::with_dictionary(dictionary)?.decompress(chunk, raw_len)?,
+            Codec::Lz4 => lz4_flex::block::decompress(chunk, raw_len)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Codec::Brotli { .. } => {
+                let mut out = Vec::with_capacity(raw_len);
+                brotli::BrotliDecompress(&mut &chunk[..], &mut out)?;
+                out
+            }
+            Codec::None => chunk.to_vec(),
+        };
+        if raw.len() != raw_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "chunk decompressed to the wrong size"));
+        }
+        Ok(raw)
+    }
+}
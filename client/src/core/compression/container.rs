@@ -0,0 +1,129 @@
+// client/src/core/compression/container.rs
+
+//! Compressed model container: magic, a JSON header naming the codec and
+//! chunk sizes, the dictionary if one was trained, then independently
+//! compressed chunks. Chunks are compressed and decompressed on rayon.
+
+use super::{codec::Codec, dictionary};
+use crate::config::CompressionConfig;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"SCZ1";
+
+#[derive(Debug, Error)]
+pub enum CompressionError {
+    #[error("Compression failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid container header: {0}")]
+    Header(#[from] serde_json::Error),
+    #[error("Container is truncated")]
+    Truncated,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    #[serde(flatten)]
+    codec: Codec,
+    chunk_size: usize,
+    /// Uncompressed length
+    length: usize,
+    dictionary_len: usize,
+    /// Compressed size of each chunk, in order
+    chunks: Vec<usize>,
+}
+
+pub fn compress(data: &[u8], config: &CompressionConfig) -> Result<Vec<u8>, CompressionError> {
+    let codec = Codec::from_config(config.codec, config.level);
+    let chunk_size = config.chunk_size.max(1);
+    let dictionary = if config.train_dictionary && codec.uses_dictionary() {
+        dictionary::train(data, config.dictionary_size).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let chunks = data
+        .par_chunks(chunk_size)
+        .map(|chunk| codec.compress(chunk, &dictionary))
+        .collect::<Result<Vec<_>, _>>()?;
+    let header = serde_json::to_vec(&Header {
+        codec,
+        chunk_size,
+        length: data.len(),
+        dictionary_len: dictionary.len(),
+        chunks: chunks.iter().map(Vec::len).collect(),
+    })?;
+
+    let mut out = Vec::with_capacity(8 + header.len() + dictionary.len() + chunks.iter().map(Vec::len).sum::<usize>());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    out.extend_from_slice(&header);
+    out.extend_from_slice(&dictionary);
+    chunks.iter().for_each(|chunk| out.extend_from_slice(chunk));
+    Ok(out)
+}
+
+/// Inverse of [`compress`]. Blobs without the container magic were stored
+/// before compression was recorded and are returned unchanged.
+pub fn decompress(blob: Vec<u8>) -> Result<Vec<u8>, CompressionError> {
+    let Some(rest) = blob.strip_prefix(MAGIC) else {
+        return Ok(blob);
+    };
+    let header_len = u32::from_le_bytes(rest.get(..4).ok_or(CompressionError::Truncated)?.try_into().unwrap()) as usize;
+    let header: Header = serde_json::from_slice(rest.get(4..4 + header_len).ok_or(CompressionError::Truncated)?)?;
+    let body = &rest[4 + header_len..];
+    let dictionary = body.get(..header.dictionary_len).ok_or(CompressionError::Truncated)?;
+
+    // Byte range and uncompressed size of each chunk
+    let mut offset = header.dictionary_len;
+    let mut chunks = Vec::with_capacity(header.chunks.len());
+    for (index, &len) in header.chunks.iter().enumerate() {
+        let raw_len = header.length.saturating_sub(index * header.chunk_size).min(header.chunk_size);
+        chunks.push((body.get(offset..offset + len).ok_or(CompressionError::Truncated)?, raw_len));
+        offset += len;
+    }
+
+    let raw = chunks
+        .into_par_iter()
+        .map(|(chunk, raw_len)| header.codec.decompress(chunk, raw_len, dictionary))
+        .collect::<Result<Vec<_>, _>>()?
+        .concat();
+    if raw.len() != header.length {
+        return Err(CompressionError::Truncated);
+    }
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CompressionCodec;
+
+    /// Low-entropy float weights, like a quantization-friendly layer
+    fn weights() -> Vec<u8> {
+        (0..300_000u32).flat_map(|i| ((i % 97) as f32 * 0.01).to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_every_codec_round_trips_through_the_container() {
+        let data = weights();
+        for codec in [CompressionCodec::Zstd, CompressionCodec::Lz4, CompressionCodec::Brotli, CompressionCodec::None] {
+            let config = CompressionConfig { codec, chunk_size: 64 * 1024, ..Default::default() };
+            let packed = compress(&data, &config).unwrap();
+            if codec != CompressionCodec::None {
+                assert!(packed.len() < data.len() / 2, "{codec:?} packed to {} bytes", packed.len());
+            }
+            assert_eq!(decompress(packed).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_blobs_without_magic_pass_through_and_truncation_is_caught() {
+        assert_eq!(decompress(b"legacy model".to_vec()).unwrap(), b"legacy model");
+
+        let mut packed = compress(&weights(), &CompressionConfig::default()).unwrap();
+        packed.truncate(packed.len() - 10);
+        assert!(matches!(decompress(packed), Err(CompressionError::Truncated)));
+    }
+}
@@ -0,0 +1,34 @@
+// client/src/core/compression/dictionary.rs
+
+//! zstd dictionaries trained on a model's own tensor data. Chunks compress
+//! independently, so each would otherwise relearn the weight statistics; a
+//! shared dictionary carries them across chunk boundaries.
+
+/// A few KiB of weights per sample, spanning several tensors' worth of values
+const SAMPLE_SIZE: usize = 4096;
+/// zstd recommends about 100x the dictionary size in training data
+const SAMPLE_BYTES_PER_DICT_BYTE: usize = 100;
+/// Below this, training has too little to generalize from
+const MIN_SAMPLES: usize = 16;
+
+/// Train on samples spread evenly over `data`; `None` when the model is too
+/// small for a dictionary to pay off or training does not converge
+pub fn train(data: &[u8], max_size: usize) -> Option<Vec<u8>> {
+    let available = data.len() / SAMPLE_SIZE;
+    if available < MIN_SAMPLES {
+        return None;
+    }
+    let wanted = (max_size * SAMPLE_BYTES_PER_DICT_BYTE / SAMPLE_SIZE).clamp(MIN_SAMPLES, available);
+    let samples: Vec<&[u8]> = data.chunks_exact(SAMPLE_SIZE).step_by(available / wanted).take(wanted).collect();
+
+    match zstd::dict::from_samples(&samples, max_size) {
+        Ok(dictionary) => {
+            tracing::debug!(samples = samples.len(), bytes = dictionary.len(), "Trained compression dictionary");
+            Some(dictionary)
+        }
+        Err(e) => {
+            tracing::debug!(error = %e, "Dictionary training failed; compressing without one");
+            None
+        }
+    }
+}
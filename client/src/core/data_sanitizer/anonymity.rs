@@ -0,0 +1,339 @@
+// client/src/core/data_sanitizer/anonymity.rs
+
+use super::pii::Record;
+use crate::config::{AnonymityConfig, Hierarchy};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AnonymityError {
+    #[error("Invalid anonymity config: {0}")]
+    InvalidConfig(String),
+    #[error("Record {index} has no column `{column}`")]
+    MissingColumn { index: usize, column: String },
+    #[error("Cannot reach {k}-anonymity over {records} records even with every quasi-identifier suppressed")]
+    Unsatisfiable { k: usize, records: usize },
+}
+
+/// Information loss of an anonymization pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnonymityReport {
+    /// Generalization level chosen per quasi-identifier
+    pub levels: BTreeMap<String, u8>,
+    pub suppressed: usize,
+    pub equivalence_classes: usize,
+    /// Mean normalized generalization height: 0 is exact, 1 fully suppressed
+    pub generalization_loss: f64,
+    /// Sum of squared class sizes, plus n for each suppressed record (Bayardo & Agrawal)
+    pub discernibility: u64,
+    /// Average class size relative to k; 1.0 is optimal
+    pub avg_class_size_ratio: f64,
+}
+
+impl AnonymityReport {
+    pub fn log(&self) {
+        tracing::info!(
+            levels = ?self.levels,
+            suppressed = self.suppressed,
+            classes = self.equivalence_classes,
+            generalization_loss = self.generalization_loss,
+            discernibility = self.discernibility,
+            avg_class_size_ratio = self.avg_class_size_ratio,
+            "k-anonymity enforced"
+        );
+    }
+}
+
+/// Generalize quasi-identifiers and suppress outliers until every equivalence
+/// class has at least `k` records (and `l` distinct sensitive values).
+///
+/// Greedy full-domain generalization (Datafly): the quasi-identifier with the
+/// most distinct values is coarsened until the records still violating the
+/// constraints fit within the suppression allowance.
+pub fn enforce_k_anonymity(config: &AnonymityConfig, records: &mut Vec<Record>) -> Result<AnonymityReport, AnonymityError> {
+    validate(config, records)?;
+
+    let n = records.len();
+    let allowance = (config.max_suppression * n as f64).floor() as usize;
+    let heights: Vec<u8> = config.quasi_identifiers.iter().map(|q| height(&q.hierarchy)).collect();
+    let mut levels = vec![0u8; heights.len()];
+
+    let violating = loop {
+        let classes = equivalence_classes(config, records, &levels);
+        let violating = violating_records(config, records, &classes);
+        if violating.len() <= allowance {
+            break violating;
+        }
+
+        // 1. Coarsen the quasi-identifier that still splits records the most
+        let next = (0..levels.len())
+            .filter(|&i| levels[i] < heights[i])
+            .max_by_key(|&i| distinct_values(config, records, &levels, i));
+        match next {
+            Some(i) => levels[i] += 1,
+            // 2. Fully generalized: suppress whatever is left, unless that is everything
+            None if violating.len() < n => break violating,
+            None => return Err(AnonymityError::Unsatisfiable { k: config.k, records: n }),
+        }
+    };
+
+    // 3. Rewrite quasi-identifiers at the chosen levels and drop violators
+    let suppressed: HashSet<usize> = violating.into_iter().collect();
+    let mut index = 0;
+    records.retain(|_| {
+        index += 1;
+        !suppressed.contains(&(index - 1))
+    });
+    for record in records.iter_mut() {
+        for (qi, &level) in config.quasi_identifiers.iter().zip(&levels) {
+            let value = record.get_mut(&qi.column).expect("validated column");
+            *value = generalize(&qi.hierarchy, value, level);
+        }
+    }
+
+    let classes = equivalence_classes(config, records, &levels);
+    let discernibility = classes.values().map(|c| (c.len() * c.len()) as u64).sum::<u64>()
+        + (suppressed.len() * n) as u64;
+    let avg_class_size_ratio = if classes.is_empty() {
+        0.0
+    } else {
+        records.len() as f64 / classes.len() as f64 / config.k as f64
+    };
+    let generalization_loss = levels
+        .iter()
+        .zip(&heights)
+        .map(|(&l, &h)| l as f64 / h as f64)
+        .sum::<f64>()
+        / levels.len() as f64;
+
+    Ok(AnonymityReport {
+        levels: config
+            .quasi_identifiers
+            .iter()
+            .map(|q| q.column.clone())
+            .zip(levels.iter().copied())
+            .collect(),
+        suppressed: suppressed.len(),
+        equivalence_classes: classes.len(),
+        generalization_loss,
+        discernibility,
+        avg_class_size_ratio,
+    })
+}
+
+fn validate(config: &AnonymityConfig, records: &[Record]) -> Result<(), AnonymityError> {
+    if config.k < 2 {
+        return Err(AnonymityError::InvalidConfig("k must be at least 2".into()));
+    }
+    if config.quasi_identifiers.is_empty() {
+        return Err(AnonymityError::InvalidConfig("no quasi-identifiers configured".into()));
+    }
+    if !(0.0..=1.0).contains(&config.max_suppression) {
+        return Err(AnonymityError::InvalidConfig("max_suppression must be in [0, 1]".into()));
+    }
+    if config.l_diversity.is_some() && config.sensitive_column.is_none() {
+        return Err(AnonymityError::InvalidConfig("l_diversity needs a sensitive_column".into()));
+    }
+
+    let columns = config
+        .quasi_identifiers
+        .iter()
+        .map(|q| &q.column)
+        .chain(config.sensitive_column.iter());
+    for column in columns {
+        if let Some(index) = records.iter().position(|r| !r.contains_key(column)) {
+            return Err(AnonymityError::MissingColumn { index, column: column.clone() });
+        }
+    }
+    Ok(())
+}
+
+/// Highest level, at which every value becomes `*`
+fn height(hierarchy: &Hierarchy) -> u8 {
+    match hierarchy {
+        Hierarchy::Range { levels, .. } => levels + 1,
+        Hierarchy::Prefix { length } => *length,
+        Hierarchy::Suppress => 1,
+    }
+}
+
+fn generalize(hierarchy: &Hierarchy, value: &str, level: u8) -> String {
+    if level == 0 {
+        return value.to_string();
+    }
+    if level >= height(hierarchy) {
+        return "*".to_string();
+    }
+    match hierarchy {
+        Hierarchy::Range { width, .. } => match value.trim().parse::<f64>() {
+            Ok(v) => {
+                let width = width * 2f64.powi(level as i32 - 1);
+                let lower = (v / width).floor() * width;
+                format!("[{}, {})", lower, lower + width)
+            }
+            Err(_) => "*".to_string(),
+        },
+        Hierarchy::Prefix { .. } => {
+            let chars: Vec<char> = value.chars().collect();
+            let keep = chars.len().saturating_sub(level as usize);
+            let mut masked: String = chars[..keep].iter().collect();
+            masked.push_str(&"*".repeat(chars.len() - keep));
+            masked
+        }
+        Hierarchy::Suppress => unreachable!("suppression has a single level"),
+    }
+}
+
+fn class_key(config: &AnonymityConfig, record: &Record, levels: &[u8]) -> Vec<String> {
+    config
+        .quasi_identifiers
+        .iter()
+        .zip(levels)
+        .map(|(q, &level)| generalize(&q.hierarchy, &record[&q.column], level))
+        .collect()
+}
+
+fn equivalence_classes(config: &AnonymityConfig, records: &[Record], levels: &[u8]) -> HashMap<Vec<String>, Vec<usize>> {
+    let mut classes: HashMap<Vec<String>, Vec<usize>> = HashMap::new();
+    for (i, record) in records.iter().enumerate() {
+        classes.entry(class_key(config, record, levels)).or_default().push(i);
+    }
+    classes
+}
+
+fn violating_records(
+    config: &AnonymityConfig,
+    records: &[Record],
+    classes: &HashMap<Vec<String>, Vec<usize>>,
+) -> Vec<usize> {
+    classes
+        .values()
+        .filter(|members| {
+            if members.len() < config.k {
+                return true;
+            }
+            match (config.l_diversity, &config.sensitive_column) {
+                (Some(l), Some(column)) => {
+                    let distinct: HashSet<&str> = members.iter().map(|&i| records[i][column].as_str()).collect();
+                    distinct.len() < l
+                }
+                _ => false,
+            }
+        })
+        .flatten()
+        .copied()
+        .collect()
+}
+
+fn distinct_values(config: &AnonymityConfig, records: &[Record], levels: &[u8], qi: usize) -> usize {
+    let q = &config.quasi_identifiers[qi];
+    records
+        .iter()
+        .map(|r| generalize(&q.hierarchy, &r[&q.column], levels[qi]))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::QuasiIdentifierConfig;
+
+    fn records(rows: &[(&str, &str, &str)]) -> Vec<Record> {
+        rows.iter()
+            .map(|(zip, age, diagnosis)| {
+                [("zip", zip), ("age", age), ("diagnosis", diagnosis)]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn config(k: usize, l: Option<usize>) -> AnonymityConfig {
+        AnonymityConfig {
+            k,
+            l_diversity: l,
+            sensitive_column: Some("diagnosis".into()),
+            max_suppression: 0.0,
+            quasi_identifiers: vec![
+                QuasiIdentifierConfig {
+                    column: "zip".into(),
+                    hierarchy: Hierarchy::Prefix { length: 5 },
+                },
+                QuasiIdentifierConfig {
+                    column: "age".into(),
+                    hierarchy: Hierarchy::Range { width: 10.0, levels: 3 },
+                },
+            ],
+        }
+    }
+
+    fn dataset() -> Vec<Record> {
+        records(&[
+            ("13053", "28", "flu"),
+            ("13068", "29", "cancer"),
+            ("13068", "21", "flu"),
+            ("13053", "23", "asthma"),
+            ("14853", "50", "cancer"),
+            ("14853", "55", "flu"),
+            ("14850", "47", "asthma"),
+            ("14850", "49", "flu"),
+        ])
+    }
+
+    #[test]
+    fn every_class_reaches_k() {
+        let mut data = dataset();
+        let report = enforce_k_anonymity(&config(4, None), &mut data).unwrap();
+
+        assert_eq!(report.suppressed, 0);
+        let classes = equivalence_classes(&config(4, None), &data, &[0, 0]);
+        assert!(classes.values().all(|c| c.len() >= 4), "{data:?}");
+        assert!(report.generalization_loss > 0.0 && report.generalization_loss < 1.0);
+        assert_eq!(report.discernibility, 32);
+    }
+
+    #[test]
+    fn l_diversity_forces_further_generalization() {
+        let mut plain = dataset();
+        let k_only = enforce_k_anonymity(&config(2, None), &mut plain).unwrap();
+        let mut diverse = dataset();
+        let with_l = enforce_k_anonymity(&config(2, Some(3)), &mut diverse).unwrap();
+
+        assert!(with_l.generalization_loss >= k_only.generalization_loss);
+        let classes = equivalence_classes(&config(2, Some(3)), &diverse, &[0, 0]);
+        for members in classes.values() {
+            let distinct: HashSet<_> = members.iter().map(|&i| &diverse[i]["diagnosis"]).collect();
+            assert!(distinct.len() >= 3);
+        }
+    }
+
+    #[test]
+    fn outliers_are_suppressed_within_the_allowance() {
+        let mut data = dataset();
+        data.extend(records(&[("99999", "90", "flu")]));
+        let mut config = config(4, None);
+        config.max_suppression = 0.2;
+
+        let report = enforce_k_anonymity(&config, &mut data).unwrap();
+        assert_eq!(report.suppressed, 1);
+        assert_eq!(data.len(), 8);
+    }
+
+    #[test]
+    fn generalizes_ranges_and_prefixes() {
+        let range = Hierarchy::Range { width: 10.0, levels: 2 };
+        assert_eq!(generalize(&range, "37", 1), "[30, 40)");
+        assert_eq!(generalize(&range, "37", 2), "[20, 40)");
+        assert_eq!(generalize(&range, "37", 3), "*");
+        assert_eq!(generalize(&Hierarchy::Prefix { length: 5 }, "13053", 2), "130**");
+    }
+
+    #[test]
+    fn too_few_records_is_unsatisfiable() {
+        let mut data = dataset();
+        let err = enforce_k_anonymity(&config(10, None), &mut data).unwrap_err();
+        assert_eq!(err, AnonymityError::Unsatisfiable { k: 10, records: 8 });
+    }
+}
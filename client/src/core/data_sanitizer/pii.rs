@@ -0,0 +1,388 @@
+// client/src/core/data_sanitizer/pii.rs
+
+use rand::{rngs::OsRng, RngCore};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// One dataset row: field name to raw value
+pub type Record = BTreeMap<String, String>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PiiError {
+    #[error("Invalid PII policy `{0}`: expected `<action>[,<kind>=<action>...]`")]
+    InvalidPolicy(String),
+    #[error("Unknown PII kind `{0}`")]
+    UnknownKind(String),
+    #[error("Unknown PII action `{0}`: expected drop, mask or hash")]
+    UnknownAction(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PiiKind {
+    Email,
+    Phone,
+    NationalId,
+    /// Terms from a user-supplied dictionary (names, addresses, ...)
+    Dictionary,
+}
+
+impl PiiKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Phone => "phone",
+            Self::NationalId => "national_id",
+            Self::Dictionary => "dictionary",
+        }
+    }
+}
+
+impl fmt::Display for PiiKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for PiiKind {
+    type Err = PiiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(Self::Email),
+            "phone" => Ok(Self::Phone),
+            "national_id" => Ok(Self::NationalId),
+            "dictionary" => Ok(Self::Dictionary),
+            other => Err(PiiError::UnknownKind(other.to_string())),
+        }
+    }
+}
+
+/// What to do with a detected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiiAction {
+    /// Remove the whole record
+    Drop,
+    /// Replace the match with a `[KIND]` placeholder
+    Mask,
+    /// Replace the match with a keyed hash, so equal values still join within the dataset
+    Hash,
+}
+
+impl FromStr for PiiAction {
+    type Err = PiiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" => Ok(Self::Drop),
+            "mask" => Ok(Self::Mask),
+            "hash" => Ok(Self::Hash),
+            other => Err(PiiError::UnknownAction(other.to_string())),
+        }
+    }
+}
+
+/// Action per PII kind, parsed from `--pii-policy`, e.g. `mask,email=hash,national_id=drop`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiPolicy {
+    pub default: PiiAction,
+    pub overrides: HashMap<PiiKind, PiiAction>,
+}
+
+impl PiiPolicy {
+    pub fn action(&self, kind: PiiKind) -> PiiAction {
+        self.overrides.get(&kind).copied().unwrap_or(self.default)
+    }
+}
+
+impl Default for PiiPolicy {
+    fn default() -> Self {
+        Self {
+            default: PiiAction::Mask,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl FromStr for PiiPolicy {
+    type Err = PiiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(str::trim);
+        let default = parts
+            .next()
+            .filter(|p| !p.is_empty() && !p.contains('='))
+            .ok_or_else(|| PiiError::InvalidPolicy(s.to_string()))?
+            .parse()?;
+
+        let mut overrides = HashMap::new();
+        for part in parts {
+            let (kind, action) = part
+                .split_once('=')
+                .ok_or_else(|| PiiError::InvalidPolicy(s.to_string()))?;
+            overrides.insert(kind.trim().parse()?, action.trim().parse()?);
+        }
+        Ok(Self { default, overrides })
+    }
+}
+
+/// Matches of each kind found in one field
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FieldReport {
+    pub matches: BTreeMap<PiiKind, usize>,
+    /// Records dropped because of a match in this field
+    pub records_dropped: usize,
+}
+
+/// Summary of a redaction pass, keyed by field name
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PiiReport {
+    pub records_scanned: usize,
+    pub records_dropped: usize,
+    pub fields: BTreeMap<String, FieldReport>,
+}
+
+impl PiiReport {
+    pub fn total_matches(&self) -> usize {
+        self.fields.values().flat_map(|f| f.matches.values()).sum()
+    }
+
+    pub fn log(&self) {
+        for (field, report) in &self.fields {
+            for (kind, count) in &report.matches {
+                tracing::info!(field, kind = %kind, count, "PII detected");
+            }
+        }
+        tracing::info!(
+            scanned = self.records_scanned,
+            dropped = self.records_dropped,
+            matches = self.total_matches(),
+            "PII redaction complete"
+        );
+    }
+}
+
+struct Detector {
+    kind: PiiKind,
+    pattern: Regex,
+}
+
+/// Regex and dictionary PII detectors with a redaction policy.
+///
+/// Runs before any DP noise is added: noise protects aggregates, not
+/// identifiers sitting verbatim in a free-text field.
+pub struct PiiScanner {
+    detectors: Vec<Detector>,
+    policy: PiiPolicy,
+    hash_key: [u8; 32],
+}
+
+impl PiiScanner {
+    pub fn new(policy: PiiPolicy) -> Self {
+        let mut hash_key = [0u8; 32];
+        OsRng.fill_bytes(&mut hash_key);
+        Self::with_hash_key(policy, hash_key)
+    }
+
+    /// Fixed hash key; hashes are only stable across datasets sharing a key
+    pub fn with_hash_key(policy: PiiPolicy, hash_key: [u8; 32]) -> Self {
+        let builtin = [
+            (PiiKind::Email, r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}\b"),
+            // US SSN and UK National Insurance number
+            (PiiKind::NationalId, r"\b\d{3}-\d{2}-\d{4}\b|\b[A-CEGHJ-PR-TW-Z]{2}\d{6}[A-D]\b"),
+            (PiiKind::Phone, r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)|\b\d{3})[\s.-]?\d{3}[\s.-]?\d{4}\b"),
+        ];
+        let detectors = builtin
+            .into_iter()
+            .map(|(kind, pattern)| Detector {
+                kind,
+                pattern: Regex::new(pattern).expect("built-in PII pattern"),
+            })
+            .collect();
+
+        Self { detectors, policy, hash_key }
+    }
+
+    /// Flag whole-word, case-insensitive occurrences of `terms`
+    pub fn with_dictionary<I, S>(mut self, terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let alternatives: Vec<String> = terms
+            .into_iter()
+            .map(|t| t.as_ref().trim().to_string())
+            .filter(|t| !t.is_empty())
+            .map(|t| regex::escape(&t))
+            .collect();
+        if !alternatives.is_empty() {
+            let pattern = format!(r"(?i)\b(?:{})\b", alternatives.join("|"));
+            self.detectors.push(Detector {
+                kind: PiiKind::Dictionary,
+                pattern: Regex::new(&pattern).expect("escaped dictionary terms"),
+            });
+        }
+        self
+    }
+
+    /// Redact `records` in place, dropping those the policy says to drop
+    pub fn redact(&self, records: &mut Vec<Record>) -> PiiReport {
+        let mut report = PiiReport {
+            records_scanned: records.len(),
+            ..Default::default()
+        };
+
+        records.retain_mut(|record| {
+            let mut drop_field = None;
+            for (field, value) in record.iter_mut() {
+                let matches = self.find(value);
+                if matches.is_empty() {
+                    continue;
+                }
+
+                let field_report = report.fields.entry(field.clone()).or_default();
+                for m in &matches {
+                    *field_report.matches.entry(m.kind).or_default() += 1;
+                }
+                if matches.iter().any(|m| self.policy.action(m.kind) == PiiAction::Drop) {
+                    drop_field.get_or_insert_with(|| field.clone());
+                    continue;
+                }
+                *value = self.replace(value, &matches);
+            }
+
+            match drop_field {
+                Some(field) => {
+                    report.records_dropped += 1;
+                    if let Some(field_report) = report.fields.get_mut(&field) {
+                        field_report.records_dropped += 1;
+                    }
+                    false
+                }
+                None => true,
+            }
+        });
+
+        report
+    }
+
+    /// Non-overlapping matches in order; earlier detectors win ties
+    fn find(&self, value: &str) -> Vec<Match> {
+        let mut matches: Vec<Match> = Vec::new();
+        for detector in &self.detectors {
+            for m in detector.pattern.find_iter(value) {
+                if matches.iter().all(|o| m.end() <= o.start || m.start() >= o.end) {
+                    matches.push(Match {
+                        kind: detector.kind,
+                        start: m.start(),
+                        end: m.end(),
+                    });
+                }
+            }
+        }
+        matches.sort_by_key(|m| m.start);
+        matches
+    }
+
+    fn replace(&self, value: &str, matches: &[Match]) -> String {
+        let mut out = String::with_capacity(value.len());
+        let mut cursor = 0;
+        for m in matches {
+            out.push_str(&value[cursor..m.start]);
+            match self.policy.action(m.kind) {
+                PiiAction::Mask => {
+                    out.push('[');
+                    out.push_str(&m.kind.label().to_uppercase());
+                    out.push(']');
+                }
+                PiiAction::Hash => {
+                    let normalized = value[m.start..m.end].to_lowercase();
+                    let digest = blake3::keyed_hash(&self.hash_key, normalized.as_bytes());
+                    out.push_str(m.kind.label());
+                    out.push(':');
+                    out.push_str(&digest.to_hex()[..16]);
+                }
+                PiiAction::Drop => unreachable!("records with dropped matches are removed"),
+            }
+            cursor = m.end;
+        }
+        out.push_str(&value[cursor..]);
+        out
+    }
+}
+
+struct Match {
+    kind: PiiKind,
+    start: usize,
+    end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(&str, &str)]) -> Record {
+        fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn scanner(policy: &str) -> PiiScanner {
+        PiiScanner::with_hash_key(policy.parse().unwrap(), [7u8; 32])
+    }
+
+    #[test]
+    fn masks_builtin_kinds() {
+        let mut records = vec![record(&[(
+            "notes",
+            "mail jane.doe@example.com or call (555) 123-4567, ssn 123-45-6789",
+        )])];
+        let report = scanner("mask").redact(&mut records);
+
+        assert_eq!(records[0]["notes"], "mail [EMAIL] or call [PHONE], ssn [NATIONAL_ID]");
+        assert_eq!(report.total_matches(), 3);
+        assert_eq!(report.fields["notes"].matches[&PiiKind::NationalId], 1);
+    }
+
+    #[test]
+    fn hashes_are_stable_within_a_dataset() {
+        let mut records = vec![
+            record(&[("contact", "Jane.Doe@example.com")]),
+            record(&[("contact", "jane.doe@example.com")]),
+        ];
+        scanner("hash").redact(&mut records);
+
+        assert!(records[0]["contact"].starts_with("email:"));
+        assert_eq!(records[0]["contact"], records[1]["contact"]);
+    }
+
+    #[test]
+    fn drop_policy_removes_records_and_reports_the_field() {
+        let mut records = vec![
+            record(&[("id", "1"), ("ssn", "123-45-6789")]),
+            record(&[("id", "2"), ("ssn", "n/a")]),
+        ];
+        let report = scanner("mask,national_id=drop").redact(&mut records);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["id"], "2");
+        assert_eq!(report.records_dropped, 1);
+        assert_eq!(report.fields["ssn"].records_dropped, 1);
+    }
+
+    #[test]
+    fn dictionary_terms_match_whole_words() {
+        let mut records = vec![record(&[("name", "Alice met Bob at Alicetown")])];
+        scanner("mask").with_dictionary(["alice", "bob"]).redact(&mut records);
+
+        assert_eq!(records[0]["name"], "[DICTIONARY] met [DICTIONARY] at Alicetown");
+    }
+
+    #[test]
+    fn rejects_malformed_policies() {
+        assert!("".parse::<PiiPolicy>().is_err());
+        assert!("email=hash".parse::<PiiPolicy>().is_err());
+        assert!("mask,ssn=drop".parse::<PiiPolicy>().is_err());
+        assert!("mask,email=redact".parse::<PiiPolicy>().is_err());
+    }
+}
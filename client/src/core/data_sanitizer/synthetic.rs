@@ -0,0 +1,412 @@
+// client/src/core/data_sanitizer/synthetic.rs
+
+use super::pii::Record;
+use crate::core::privacy::{
+    accountant::{gaussian_sigma, PrivacyError},
+    dp::{DifferentialPrivacy, QueryConfig},
+};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng, RngCore};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SyntheticError {
+    #[error("Cannot synthesize from an empty dataset")]
+    Empty,
+    #[error(transparent)]
+    Privacy(#[from] PrivacyError),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SynthesizerConfig {
+    /// Numeric columns with more distinct values than this are binned
+    pub max_categories: usize,
+    pub bins: usize,
+}
+
+impl Default for SynthesizerConfig {
+    fn default() -> Self {
+        Self {
+            max_categories: 64,
+            bins: 16,
+        }
+    }
+}
+
+/// Values a column can take in the model
+#[derive(Debug, Clone)]
+enum Domain {
+    Categorical(Vec<String>),
+    /// Equal-width bins over the observed range; the range itself is not noised
+    Binned {
+        lower: f64,
+        width: f64,
+        bins: usize,
+        integer: bool,
+    },
+}
+
+impl Domain {
+    fn len(&self) -> usize {
+        match self {
+            Self::Categorical(values) => values.len(),
+            Self::Binned { bins, .. } => *bins,
+        }
+    }
+
+    fn index_of(&self, value: &str) -> Option<usize> {
+        match self {
+            Self::Categorical(values) => values.binary_search_by(|v| v.as_str().cmp(value)).ok(),
+            Self::Binned { lower, width, bins, .. } => {
+                let v: f64 = value.trim().parse().ok()?;
+                let bin = ((v - lower) / width).floor().max(0.0) as usize;
+                Some(bin.min(bins - 1))
+            }
+        }
+    }
+
+    fn value<R: Rng>(&self, index: usize, rng: &mut R) -> String {
+        match self {
+            Self::Categorical(values) => values[index].clone(),
+            Self::Binned { lower, width, integer, .. } => {
+                let v = lower + width * (index as f64 + rng.gen::<f64>());
+                if *integer {
+                    format!("{}", v.floor())
+                } else {
+                    format!("{v}")
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ColumnModel {
+    name: String,
+    domain: Domain,
+    marginal: Vec<f64>,
+}
+
+/// DP marginal-based synthesizer.
+///
+/// Fits noisy one-way marginals for every column and noisy two-way marginals
+/// between neighbouring columns, then samples records along that chain.
+/// Categories whose noisy count falls under the Gaussian tail threshold are
+/// discarded, so rare values (names, IDs) are never reproduced.
+#[derive(Debug, Clone)]
+pub struct MarginalSynthesizer {
+    columns: Vec<ColumnModel>,
+    /// Flattened `prev x next` noisy counts for each neighbouring pair
+    pairs: Vec<Vec<f64>>,
+    noisy_rows: f64,
+}
+
+impl MarginalSynthesizer {
+    /// Fit on `records`, spending the whole budget of a fresh `dp`
+    pub fn fit<R: RngCore>(
+        records: &[Record],
+        config: SynthesizerConfig,
+        dp: &mut DifferentialPrivacy<R>,
+    ) -> Result<Self, SyntheticError> {
+        let names: BTreeSet<&String> = records.iter().flat_map(|r| r.keys()).collect();
+        if names.is_empty() {
+            return Err(SyntheticError::Empty);
+        }
+
+        // One histogram per column plus one per neighbouring pair, all at sensitivity 1
+        let queries = 2 * names.len() - 1;
+        let query = QueryConfig::new(1.0, 1.0 / queries as f64);
+        let (_, delta) = dp.accountant().target();
+        let sigma = gaussian_sigma(1.0, dp.accountant().budget_rho() * query.budget_share);
+        let threshold = 1.0 + sigma * (2.0 * (1.0 / delta).ln()).sqrt();
+
+        // 1. Noisy one-way marginals; categories below the threshold are dropped
+        let mut columns = Vec::with_capacity(names.len());
+        let mut noisy_rows = 0.0;
+        for name in names {
+            let values: Vec<&str> = records.iter().map(|r| r.get(name).map_or("", String::as_str)).collect();
+            let domain = candidate_domain(&values, config);
+            let counts = histogram(values.iter().map(|v| domain.index_of(v)), domain.len());
+            let noisy = dp.noisy_histogram(&counts, query)?;
+            if columns.is_empty() {
+                noisy_rows = noisy.iter().sum();
+            }
+
+            let (domain, marginal) = match domain {
+                Domain::Categorical(values) => {
+                    let (kept, marginal): (Vec<String>, Vec<f64>) = values
+                        .into_iter()
+                        .zip(noisy)
+                        .filter(|(_, count)| *count >= threshold)
+                        .unzip();
+                    (Domain::Categorical(kept), marginal)
+                }
+                binned => (binned, noisy),
+            };
+            columns.push(ColumnModel { name: name.clone(), domain, marginal });
+        }
+
+        // 2. Noisy two-way marginals along the column chain
+        let mut pairs = Vec::with_capacity(columns.len().saturating_sub(1));
+        for window in columns.windows(2) {
+            let (prev, next) = (&window[0], &window[1]);
+            let cells = records.iter().map(|r| {
+                let a = prev.domain.index_of(r.get(&prev.name).map_or("", String::as_str))?;
+                let b = next.domain.index_of(r.get(&next.name).map_or("", String::as_str))?;
+                Some(a * next.domain.len() + b)
+            });
+            let counts = histogram(cells, prev.domain.len() * next.domain.len());
+            pairs.push(dp.noisy_histogram(&counts, query)?);
+        }
+
+        Ok(Self { columns, pairs, noisy_rows })
+    }
+
+    /// Noisy size of the training data, a DP default for how many records to sample
+    pub fn noisy_rows(&self) -> usize {
+        self.noisy_rows.round().max(1.0) as usize
+    }
+
+    pub fn sample<R: Rng>(&self, n: usize, rng: &mut R) -> Vec<Record> {
+        (0..n).map(|_| self.sample_record(rng)).collect()
+    }
+
+    fn sample_record<R: Rng>(&self, rng: &mut R) -> Record {
+        let mut record = Record::new();
+        let mut prev: Option<usize> = None;
+        for (i, column) in self.columns.iter().enumerate() {
+            let len = column.domain.len();
+            let conditional = match (prev, i.checked_sub(1)) {
+                (Some(p), Some(pair)) => pick(&self.pairs[pair][p * len..(p + 1) * len], rng),
+                _ => None,
+            };
+            let index = conditional.or_else(|| pick(&column.marginal, rng));
+            let value = index.map(|idx| column.domain.value(idx, rng)).unwrap_or_default();
+            record.insert(column.name.clone(), value);
+            prev = index;
+        }
+        record
+    }
+
+    /// Compare `synthetic` against the `real` data it was fitted on.
+    ///
+    /// Reads the real data without noise: the report is for the contributor
+    /// and must not be published alongside the contribution.
+    pub fn quality_report(&self, real: &[Record], synthetic: &[Record]) -> QualityReport {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                let quality = ColumnQuality {
+                    tvd: total_variation(
+                        &self.distribution(real, &[column]),
+                        &self.distribution(synthetic, &[column]),
+                    ),
+                    real_mean: mean(real, &column.name),
+                    synthetic_mean: mean(synthetic, &column.name),
+                };
+                (column.name.clone(), quality)
+            })
+            .collect();
+
+        let pairs = self
+            .columns
+            .windows(2)
+            .map(|w| PairQuality {
+                columns: (w[0].name.clone(), w[1].name.clone()),
+                tvd: total_variation(
+                    &self.distribution(real, &[&w[0], &w[1]]),
+                    &self.distribution(synthetic, &[&w[0], &w[1]]),
+                ),
+            })
+            .collect();
+
+        QualityReport {
+            real_records: real.len(),
+            synthetic_records: synthetic.len(),
+            columns,
+            pairs,
+        }
+    }
+
+    /// Empirical distribution over the model's cells; values outside the domain share one cell
+    fn distribution(&self, records: &[Record], columns: &[&ColumnModel]) -> BTreeMap<Vec<Option<usize>>, f64> {
+        let mut dist = BTreeMap::new();
+        for record in records {
+            let key = columns
+                .iter()
+                .map(|c| c.domain.index_of(record.get(&c.name).map_or("", String::as_str)))
+                .collect();
+            *dist.entry(key).or_insert(0.0) += 1.0 / records.len() as f64;
+        }
+        dist
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnQuality {
+    /// Total variation distance between real and synthetic marginals, in [0, 1]
+    pub tvd: f64,
+    /// Means of numeric columns
+    pub real_mean: Option<f64>,
+    pub synthetic_mean: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairQuality {
+    pub columns: (String, String),
+    pub tvd: f64,
+}
+
+/// Statistical fidelity of a synthetic dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    pub real_records: usize,
+    pub synthetic_records: usize,
+    pub columns: BTreeMap<String, ColumnQuality>,
+    pub pairs: Vec<PairQuality>,
+}
+
+impl QualityReport {
+    pub fn log(&self) {
+        for (name, column) in &self.columns {
+            tracing::info!(
+                column = %name,
+                tvd = column.tvd,
+                real_mean = ?column.real_mean,
+                synthetic_mean = ?column.synthetic_mean,
+                "Synthetic marginal"
+            );
+        }
+        for pair in &self.pairs {
+            tracing::info!(columns = ?pair.columns, tvd = pair.tvd, "Synthetic pairwise marginal");
+        }
+        tracing::info!(
+            real = self.real_records,
+            synthetic = self.synthetic_records,
+            "Synthetic dataset generated"
+        );
+    }
+}
+
+fn candidate_domain(values: &[&str], config: SynthesizerConfig) -> Domain {
+    let distinct: BTreeSet<&str> = values.iter().copied().collect();
+    let numeric: Option<Vec<f64>> = values.iter().map(|v| v.trim().parse::<f64>().ok()).collect();
+
+    match numeric {
+        Some(numbers) if distinct.len() > config.max_categories => {
+            let lower = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+            let upper = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            Domain::Binned {
+                lower,
+                width: ((upper - lower) / config.bins as f64).max(f64::EPSILON),
+                bins: config.bins,
+                integer: numbers.iter().all(|n| n.fract() == 0.0),
+            }
+        }
+        _ => Domain::Categorical(distinct.into_iter().map(str::to_string).collect()),
+    }
+}
+
+fn histogram(cells: impl Iterator<Item = Option<usize>>, len: usize) -> Vec<u64> {
+    let mut counts = vec![0u64; len];
+    for cell in cells.flatten() {
+        counts[cell] += 1;
+    }
+    counts
+}
+
+fn pick<R: Rng>(weights: &[f64], rng: &mut R) -> Option<usize> {
+    WeightedIndex::new(weights).ok().map(|dist| dist.sample(rng))
+}
+
+fn mean(records: &[Record], column: &str) -> Option<f64> {
+    let values: Vec<f64> = records
+        .iter()
+        .filter_map(|r| r.get(column)?.trim().parse().ok())
+        .collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn total_variation<K: Ord>(p: &BTreeMap<K, f64>, q: &BTreeMap<K, f64>) -> f64 {
+    let keys: BTreeSet<&K> = p.keys().chain(q.keys()).collect();
+    keys.into_iter()
+        .map(|k| (p.get(k).unwrap_or(&0.0) - q.get(k).unwrap_or(&0.0)).abs())
+        .sum::<f64>()
+        / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn dataset() -> Vec<Record> {
+        let mut rng = StdRng::seed_from_u64(1);
+        (0..5000)
+            .map(|i| {
+                let smoker = rng.gen_bool(0.3);
+                let risk = if smoker == rng.gen_bool(0.9) { "high" } else { "low" };
+                let age = 20 + rng.gen_range(0..80);
+                [
+                    ("age", age.to_string()),
+                    ("email", format!("user{i}@example.com")),
+                    ("risk", risk.to_string()),
+                    ("smoker", smoker.to_string()),
+                ]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect()
+            })
+            .collect()
+    }
+
+    fn fit(records: &[Record], epsilon: f64) -> MarginalSynthesizer {
+        let mut dp = DifferentialPrivacy::with_rng(epsilon, 1e-6, StdRng::seed_from_u64(2)).unwrap();
+        MarginalSynthesizer::fit(records, SynthesizerConfig::default(), &mut dp).unwrap()
+    }
+
+    #[test]
+    fn preserves_marginals_and_neighbouring_correlations() {
+        let real = dataset();
+        let model = fit(&real, 10.0);
+        let synthetic = model.sample(model.noisy_rows(), &mut StdRng::seed_from_u64(3));
+        let report = model.quality_report(&real, &synthetic);
+
+        assert!((synthetic.len() as f64 - 5000.0).abs() < 200.0);
+        assert!(report.columns["smoker"].tvd < 0.05, "{report:?}");
+        assert!(report.columns["age"].tvd < 0.1, "{report:?}");
+        let risk_smoker = report.pairs.iter().find(|p| p.columns.0 == "risk").unwrap();
+        assert!(risk_smoker.tvd < 0.05, "{report:?}");
+
+        let (real_mean, synthetic_mean) = (report.columns["age"].real_mean, report.columns["age"].synthetic_mean);
+        assert!((real_mean.unwrap() - synthetic_mean.unwrap()).abs() < 3.0);
+    }
+
+    #[test]
+    fn never_reproduces_rare_values() {
+        let real = dataset();
+        let model = fit(&real, 10.0);
+        let synthetic = model.sample(1000, &mut StdRng::seed_from_u64(4));
+
+        assert!(synthetic.iter().all(|r| r["email"].is_empty()));
+    }
+
+    #[test]
+    fn spends_the_whole_budget() {
+        let real = dataset();
+        let mut dp = DifferentialPrivacy::with_rng(1.0, 1e-6, StdRng::seed_from_u64(5)).unwrap();
+        MarginalSynthesizer::fit(&real, SynthesizerConfig::default(), &mut dp).unwrap();
+
+        assert_eq!(dp.accountant().ledger().len(), 7);
+        assert!((dp.accountant().spent_epsilon() - 1.0).abs() < 1e-6);
+        assert!(MarginalSynthesizer::fit(&real, SynthesizerConfig::default(), &mut dp).is_err());
+    }
+
+    #[test]
+    fn rejects_empty_datasets() {
+        let mut dp = DifferentialPrivacy::with_rng(1.0, 1e-6, StdRng::seed_from_u64(6)).unwrap();
+        let err = MarginalSynthesizer::fit(&[], SynthesizerConfig::default(), &mut dp).unwrap_err();
+        assert_eq!(err, SyntheticError::Empty);
+    }
+}
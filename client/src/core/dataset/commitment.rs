@@ -0,0 +1,261 @@
+// client/src/core/dataset/commitment.rs
+
+//! Merkle commitment over the records of a dataset contribution. Leaves are
+//! salted blake3 hashes of each record, so an inclusion proof reveals one
+//! record and only opaque hashes of the rest. Nodes pair like the registry's
+//! `merkle_utils`: blake3(left || right), odd tails padded with zeros.
+
+use crate::core::data_sanitizer::pii::Record;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const LEAF_DOMAIN: &[u8] = b"scoria-record-v1";
+
+#[derive(Debug, Error)]
+pub enum CommitmentError {
+    #[error("Record {index} out of range: the contribution has {count} records")]
+    OutOfRange { index: usize, count: usize },
+    #[error("Contribution opening {path}: {reason}")]
+    Opening { path: PathBuf, reason: String },
+}
+
+/// What the contributor keeps to open the commitment later: the committed
+/// records and their salts. Holds sanitized records; never publish it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributionOpening {
+    pub model: String,
+    records: Vec<Record>,
+    salts: Vec<Salt>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Salt(#[serde(with = "hex::serde")] [u8; 32]);
+
+/// One record opened against a contribution's on-chain root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    #[serde(with = "hex::serde")]
+    pub root: [u8; 32],
+    pub index: u64,
+    pub record_count: u64,
+    pub record: Record,
+    #[serde(with = "hex::serde")]
+    pub salt: [u8; 32],
+    /// Bottom-up siblings, zero padding included
+    pub siblings: Vec<Sibling>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sibling(#[serde(with = "hex::serde")] pub [u8; 32]);
+
+impl ContributionOpening {
+    /// Commit to `records` under fresh random salts
+    pub fn commit(model: String, records: Vec<Record>) -> Self {
+        let mut rng = rand::thread_rng();
+        let salts = records
+            .iter()
+            .map(|_| {
+                let mut salt = [0u8; 32];
+                rng.fill_bytes(&mut salt);
+                Salt(salt)
+            })
+            .collect();
+        Self { model, records, salts }
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Root stored in the contribution account; all zeros for no records
+    pub fn root(&self) -> [u8; 32] {
+        let mut level = self.leaves();
+        while level.len() > 1 {
+            level = next_level(&level);
+        }
+        level.first().copied().unwrap_or([0u8; 32])
+    }
+
+    pub fn prove(&self, index: usize) -> Result<InclusionProof, CommitmentError> {
+        let count = self.records.len();
+        if index >= count {
+            return Err(CommitmentError::OutOfRange { index, count });
+        }
+        let mut level = self.leaves();
+        let mut siblings = Vec::new();
+        let mut position = index;
+        while level.len() > 1 {
+            siblings.push(Sibling(*level.get(position ^ 1).unwrap_or(&[0u8; 32])));
+            level = next_level(&level);
+            position /= 2;
+        }
+        Ok(InclusionProof {
+            root: level[0],
+            index: index as u64,
+            record_count: count as u64,
+            record: self.records[index].clone(),
+            salt: self.salts[index].0,
+            siblings,
+        })
+    }
+
+    /// Where the opening of the contribution with `data_hash` is kept
+    pub fn path(dir: &Path, data_hash: &[u8; 32]) -> PathBuf {
+        dir.join(format!("{}.json", hex::encode(data_hash)))
+    }
+
+    /// Write to `Self::path`, readable by the owner only
+    pub fn save(&self, dir: &Path, data_hash: &[u8; 32]) -> Result<PathBuf, CommitmentError> {
+        let path = Self::path(dir, data_hash);
+        let opening = |reason: String| CommitmentError::Opening { path: path.clone(), reason };
+        std::fs::create_dir_all(dir).map_err(|e| opening(e.to_string()))?;
+        let json = serde_json::to_vec(self).map_err(|e| opening(e.to_string()))?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        std::io::Write::write_all(&mut options.open(&path).map_err(|e| opening(e.to_string()))?, &json)
+            .map_err(|e| opening(e.to_string()))?;
+        Ok(path)
+    }
+
+    pub fn load(dir: &Path, data_hash: &[u8; 32]) -> Result<Self, CommitmentError> {
+        let path = Self::path(dir, data_hash);
+        let opening = |reason: String| CommitmentError::Opening { path: path.clone(), reason };
+        let json = std::fs::read(&path).map_err(|e| opening(e.to_string()))?;
+        let loaded: Self = serde_json::from_slice(&json).map_err(|e| opening(e.to_string()))?;
+        if loaded.salts.len() != loaded.records.len() {
+            return Err(opening("salt and record counts differ".into()));
+        }
+        Ok(loaded)
+    }
+
+    /// Erase the opening, e.g. once the contribution is revoked. `false` when
+    /// there was none.
+    pub fn remove(dir: &Path, data_hash: &[u8; 32]) -> Result<bool, CommitmentError> {
+        let path = Self::path(dir, data_hash);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(CommitmentError::Opening { path, reason: e.to_string() }),
+        }
+    }
+
+    fn leaves(&self) -> Vec<[u8; 32]> {
+        self.records.iter().zip(&self.salts).map(|(record, salt)| leaf_hash(record, &salt.0)).collect()
+    }
+}
+
+impl InclusionProof {
+    /// Whether the proof opens `root`, e.g. read from the contribution account.
+    /// The path length must match `record_count` so the index is bound too.
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        if self.index >= self.record_count || self.siblings.len() != depth(self.record_count) {
+            return false;
+        }
+        let mut node = leaf_hash(&self.record, &self.salt);
+        let mut position = self.index;
+        for sibling in &self.siblings {
+            node = if position & 1 == 0 { hash_pair(&node, &sibling.0) } else { hash_pair(&sibling.0, &node) };
+            position /= 2;
+        }
+        node == *root && self.root == *root
+    }
+}
+
+/// Records are BTreeMaps, so their JSON is canonical
+fn leaf_hash(record: &Record, salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(LEAF_DOMAIN);
+    hasher.update(salt);
+    hasher.update(&serde_json::to_vec(record).expect("string maps serialize"));
+    *hasher.finalize().as_bytes()
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level.chunks(2).map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&[0u8; 32]))).collect()
+}
+
+fn depth(count: u64) -> usize {
+    count.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(n: usize) -> Vec<Record> {
+        (0..n).map(|i| Record::from([("id".to_string(), i.to_string())])).collect()
+    }
+
+    #[test]
+    fn test_every_record_opens_the_root() {
+        for n in 1..=9 {
+            let opening = ContributionOpening::commit("model".into(), records(n));
+            let root = opening.root();
+            for i in 0..n {
+                let proof = opening.prove(i).unwrap();
+                assert!(proof.verify(&root), "n={n} i={i}");
+            }
+            assert!(matches!(opening.prove(n), Err(CommitmentError::OutOfRange { .. })));
+        }
+    }
+
+    #[test]
+    fn test_tampered_proofs_fail() {
+        let opening = ContributionOpening::commit("model".into(), records(5));
+        let root = opening.root();
+        let proof = opening.prove(2).unwrap();
+
+        let mut record = proof.clone();
+        record.record.insert("id".into(), "9".into());
+        assert!(!record.verify(&root));
+
+        let mut index = proof.clone();
+        index.index = 3;
+        assert!(!index.verify(&root));
+
+        // A shorter claimed count cannot drop path levels
+        let mut count = proof.clone();
+        count.record_count = 3;
+        assert!(!count.verify(&root));
+
+        let other = ContributionOpening::commit("model".into(), records(5));
+        assert_ne!(other.root(), root, "salts hide identical datasets");
+        assert!(!proof.verify(&other.root()));
+    }
+
+    #[test]
+    fn test_opening_round_trips_through_disk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let opening = ContributionOpening::commit("model".into(), records(3));
+        let path = opening.save(dir.path(), &[7u8; 32]).unwrap();
+        assert!(path.ends_with(format!("{}.json", hex::encode([7u8; 32]))));
+
+        let loaded = ContributionOpening::load(dir.path(), &[7u8; 32]).unwrap();
+        assert_eq!(loaded.root(), opening.root());
+        assert_eq!(loaded.records(), opening.records());
+        assert!(ContributionOpening::load(dir.path(), &[8u8; 32]).is_err());
+
+        assert!(ContributionOpening::remove(dir.path(), &[7u8; 32]).unwrap());
+        assert!(!ContributionOpening::remove(dir.path(), &[7u8; 32]).unwrap());
+        assert!(ContributionOpening::load(dir.path(), &[7u8; 32]).is_err());
+    }
+}
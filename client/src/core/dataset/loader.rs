@@ -0,0 +1,403 @@
+// client/src/core/dataset/loader.rs
+
+use super::schema::{Schema, INFERENCE_SAMPLE};
+use crate::core::data_sanitizer::pii::Record;
+use parquet::file::reader::SerializedFileReader;
+use parquet::record::{reader::RowIter, Field};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DatasetError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Unsupported dataset file {0}: expected .csv, .parquet or .jsonl")]
+    UnsupportedFormat(PathBuf),
+    #[error("No rows found in {0}")]
+    Empty(PathBuf),
+    #[error("{position}: {message}")]
+    Parse { position: Position, message: String },
+    #[error("{position}: column `{column}`: {message}")]
+    Invalid {
+        position: Position,
+        column: String,
+        message: String,
+    },
+    #[error("Invalid schema: {0}")]
+    Schema(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetFormat {
+    Csv,
+    Parquet,
+    Jsonl,
+}
+
+impl DatasetFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "parquet" | "pq" => Some(Self::Parquet),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// Where a row came from; CSV and JSONL rows report their line number
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub file: Arc<Path>,
+    pub row: u64,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.row)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub position: Position,
+    pub record: Record,
+}
+
+enum FileRows {
+    Csv {
+        reader: csv::Reader<File>,
+        headers: csv::StringRecord,
+    },
+    Jsonl {
+        lines: Lines<BufReader<File>>,
+        line: u64,
+    },
+    Parquet {
+        rows: RowIter<'static>,
+        row: u64,
+    },
+}
+
+/// Streams rows from a dataset file, or every supported file in a directory.
+///
+/// Nothing is buffered beyond the current row, so large datasets can be
+/// validated or sanitized without loading them whole.
+pub struct DatasetReader {
+    pending: VecDeque<PathBuf>,
+    current: Option<(Arc<Path>, FileRows)>,
+    schema: Option<Schema>,
+}
+
+impl DatasetReader {
+    pub fn open(path: &Path) -> Result<Self, DatasetError> {
+        let io_err = |source| DatasetError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        let pending = if path.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+                .map_err(io_err)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<_, _>>()
+                .map_err(io_err)?;
+            files.retain(|f| DatasetFormat::from_path(f).is_some());
+            files.sort();
+            files
+        } else if DatasetFormat::from_path(path).is_some() {
+            vec![path.to_path_buf()]
+        } else {
+            return Err(DatasetError::UnsupportedFormat(path.to_path_buf()));
+        };
+
+        if pending.is_empty() {
+            return Err(DatasetError::Empty(path.to_path_buf()));
+        }
+        Ok(Self {
+            pending: pending.into(),
+            current: None,
+            schema: None,
+        })
+    }
+
+    /// Validate every row as it is read
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    fn open_next(&mut self) -> Result<bool, DatasetError> {
+        let Some(path) = self.pending.pop_front() else {
+            return Ok(false);
+        };
+        let io_err = |source| DatasetError::Io {
+            path: path.clone(),
+            source,
+        };
+        let file = File::open(&path).map_err(io_err)?;
+
+        let rows = match DatasetFormat::from_path(&path) {
+            Some(DatasetFormat::Csv) => {
+                let mut reader = csv::Reader::from_reader(file);
+                let headers = reader.headers().map_err(|e| csv_error(&path, e))?.clone();
+                FileRows::Csv { reader, headers }
+            }
+            Some(DatasetFormat::Jsonl) => FileRows::Jsonl {
+                lines: BufReader::new(file).lines(),
+                line: 0,
+            },
+            Some(DatasetFormat::Parquet) => {
+                let reader = SerializedFileReader::new(file).map_err(|e| DatasetError::Parse {
+                    position: Position { file: path.as_path().into(), row: 0 },
+                    message: e.to_string(),
+                })?;
+                FileRows::Parquet {
+                    rows: reader.into_iter(),
+                    row: 0,
+                }
+            }
+            None => return Err(DatasetError::UnsupportedFormat(path)),
+        };
+        self.current = Some((path.into(), rows));
+        Ok(true)
+    }
+}
+
+impl Iterator for DatasetReader {
+    type Item = Result<Row, DatasetError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current.is_none() {
+                match self.open_next() {
+                    Ok(true) => {}
+                    Ok(false) => return None,
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            let (file, rows) = self.current.as_mut().expect("file opened above");
+            match next_row(file, rows) {
+                Some(Ok(row)) => {
+                    let validated = match &self.schema {
+                        Some(schema) => schema.validate(&row).map(|_| row),
+                        None => Ok(row),
+                    };
+                    return Some(validated);
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => self.current = None,
+            }
+        }
+    }
+}
+
+fn next_row(file: &Arc<Path>, rows: &mut FileRows) -> Option<Result<Row, DatasetError>> {
+    let at = |row| Position { file: file.clone(), row };
+    match rows {
+        FileRows::Csv { reader, headers } => {
+            let mut record = csv::StringRecord::new();
+            match reader.read_record(&mut record) {
+                Ok(false) => None,
+                Ok(true) => {
+                    let line = record.position().map_or(0, |p| p.line());
+                    let fields = headers.iter().zip(record.iter());
+                    Some(Ok(Row {
+                        position: at(line),
+                        record: fields.map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+                    }))
+                }
+                Err(e) => Some(Err(csv_error(file, e))),
+            }
+        }
+        FileRows::Jsonl { lines, line } => loop {
+            *line += 1;
+            let text = match lines.next()? {
+                Ok(text) => text,
+                Err(source) => {
+                    return Some(Err(DatasetError::Io {
+                        path: file.to_path_buf(),
+                        source,
+                    }))
+                }
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            let parsed = match serde_json::from_str::<serde_json::Value>(&text) {
+                Ok(serde_json::Value::Object(object)) => Ok(object),
+                Ok(_) => Err("expected a JSON object per line".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            return Some(match parsed {
+                Ok(object) => Ok(Row {
+                    position: at(*line),
+                    record: object.into_iter().map(|(k, v)| (k, json_to_string(v))).collect(),
+                }),
+                Err(message) => Err(DatasetError::Parse { position: at(*line), message }),
+            });
+        },
+        FileRows::Parquet { rows, row } => {
+            *row += 1;
+            Some(match rows.next()? {
+                Ok(parquet_row) => Ok(Row {
+                    position: at(*row),
+                    record: parquet_row
+                        .get_column_iter()
+                        .map(|(name, field)| (name.clone(), field_to_string(field)))
+                        .collect(),
+                }),
+                Err(e) => Err(DatasetError::Parse {
+                    position: at(*row),
+                    message: e.to_string(),
+                }),
+            })
+        }
+    }
+}
+
+/// Read and validate a whole dataset against `schema`, inferring one from the
+/// first rows when the model declares none
+pub fn load_dataset(path: &Path, schema: Option<&Schema>) -> Result<Vec<Record>, DatasetError> {
+    let rows: Vec<Row> = DatasetReader::open(path)?.collect::<Result<_, _>>()?;
+    if rows.is_empty() {
+        return Err(DatasetError::Empty(path.to_path_buf()));
+    }
+
+    let inferred;
+    let schema = match schema {
+        Some(schema) => schema,
+        None => {
+            inferred = Schema::infer(&rows[..rows.len().min(INFERENCE_SAMPLE)]);
+            tracing::info!(schema = ?inferred.fields, "Inferred dataset schema");
+            &inferred
+        }
+    };
+    for row in &rows {
+        schema.validate(row)?;
+    }
+    Ok(rows.into_iter().map(|row| row.record).collect())
+}
+
+fn csv_error(file: &Path, e: csv::Error) -> DatasetError {
+    let position = Position {
+        file: file.into(),
+        row: e.position().map_or(0, |p| p.line()),
+    };
+    DatasetError::Parse {
+        position,
+        message: e.to_string(),
+    }
+}
+
+fn json_to_string(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+fn field_to_string(field: &Field) -> String {
+    match field {
+        Field::Null => String::new(),
+        Field::Str(s) => s.clone(),
+        Field::Bool(v) => v.to_string(),
+        Field::Byte(v) => v.to_string(),
+        Field::Short(v) => v.to_string(),
+        Field::Int(v) => v.to_string(),
+        Field::Long(v) => v.to_string(),
+        Field::UByte(v) => v.to_string(),
+        Field::UShort(v) => v.to_string(),
+        Field::UInt(v) => v.to_string(),
+        Field::ULong(v) => v.to_string(),
+        Field::Float(v) => v.to_string(),
+        Field::Double(v) => v.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dataset::schema::{Field as SchemaField, FieldType};
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn infers_csv_schema() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "data.csv", "age,score,label\n31,0.5,yes\n45,1,\n");
+
+        let rows: Vec<Row> = DatasetReader::open(&path).unwrap().collect::<Result<_, _>>().unwrap();
+        let schema = Schema::infer(&rows);
+
+        let types: Vec<_> = schema.fields.iter().map(|f| (f.name.as_str(), f.dtype, f.nullable)).collect();
+        assert_eq!(
+            types,
+            [
+                ("age", FieldType::Int, false),
+                ("label", FieldType::String, true),
+                ("score", FieldType::Float, false),
+            ]
+        );
+        assert_eq!(rows[1].position.row, 3);
+    }
+
+    #[test]
+    fn type_errors_point_at_the_row() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "data.jsonl", "{\"age\": 31}\n\n{\"age\": \"unknown\"}\n");
+        let schema = Schema {
+            fields: vec![SchemaField {
+                name: "age".into(),
+                dtype: FieldType::Int,
+                nullable: false,
+            }],
+        };
+
+        let err = load_dataset(&path, Some(&schema)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("{}:3: column `age`: expected Int, found `unknown`", path.display())
+        );
+    }
+
+    #[test]
+    fn streams_every_supported_file_in_a_directory() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "a.csv", "x\n1\n2\n");
+        write(&dir, "b.jsonl", "{\"x\": 3}\n");
+        write(&dir, "README.md", "not data");
+
+        let records = load_dataset(dir.path(), None).unwrap();
+        let xs: Vec<&str> = records.iter().map(|r| r["x"].as_str()).collect();
+        assert_eq!(xs, ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "data.jsonl", "{\"x\": 1}\n[1, 2]\n");
+
+        let err = DatasetReader::open(&path).unwrap().nth(1).unwrap().unwrap_err();
+        assert!(matches!(err, DatasetError::Parse { ref position, .. } if position.row == 2));
+        assert!(matches!(
+            DatasetReader::open(&dir.path().join("data.txt")),
+            Err(DatasetError::UnsupportedFormat(_))
+        ));
+    }
+}
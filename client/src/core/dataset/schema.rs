@@ -0,0 +1,146 @@
+// client/src/core/dataset/schema.rs
+
+use super::loader::{DatasetError, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Rows read to infer a schema when none is declared
+pub const INFERENCE_SAMPLE: usize = 1000;
+
+/// Column types, narrowest first; inference picks the narrowest that fits every value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    Bool,
+    Int,
+    Float,
+    String,
+}
+
+impl FieldType {
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::Bool => matches!(value, "true" | "false"),
+            Self::Int => value.parse::<i64>().is_ok(),
+            Self::Float => value.parse::<f64>().is_ok(),
+            Self::String => true,
+        }
+    }
+
+    fn narrowest(value: &str) -> Self {
+        [Self::Bool, Self::Int, Self::Float]
+            .into_iter()
+            .find(|t| t.accepts(value))
+            .unwrap_or(Self::String)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub dtype: FieldType,
+    #[serde(default)]
+    pub nullable: bool,
+}
+
+/// Tabular schema a dataset is validated against, stored as JSON:
+/// `{"fields": [{"name": "age", "type": "int", "nullable": false}]}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn load(path: &Path) -> Result<Self, DatasetError> {
+        let raw = std::fs::read_to_string(path).map_err(|source| DatasetError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&raw).map_err(|e| DatasetError::Schema(format!("{}: {e}", path.display())))
+    }
+
+    /// Narrowest schema every row in `sample` satisfies; empty or missing values make a field nullable
+    pub fn infer(sample: &[Row]) -> Self {
+        let mut fields: BTreeMap<&str, (Option<FieldType>, bool)> = BTreeMap::new();
+        for row in sample {
+            for (name, value) in &row.record {
+                let (dtype, nullable) = fields.entry(name.as_str()).or_insert((None, false));
+                if value.is_empty() {
+                    *nullable = true;
+                    continue;
+                }
+                let observed = FieldType::narrowest(value);
+                // Int widens to Float; anything else mixed becomes String
+                *dtype = Some(match *dtype {
+                    None => observed,
+                    Some(current) if current == observed => current,
+                    Some(FieldType::Int) if observed == FieldType::Float => FieldType::Float,
+                    Some(FieldType::Float) if observed == FieldType::Int => FieldType::Float,
+                    Some(_) => FieldType::String,
+                });
+            }
+        }
+        for row in sample {
+            for (name, (_, nullable)) in fields.iter_mut() {
+                if !row.record.contains_key(*name) {
+                    *nullable = true;
+                }
+            }
+        }
+
+        Self {
+            fields: fields
+                .into_iter()
+                .map(|(name, (dtype, nullable))| Field {
+                    name: name.to_string(),
+                    // Columns that were always empty carry no type information
+                    dtype: dtype.unwrap_or(FieldType::String),
+                    nullable,
+                })
+                .collect(),
+        }
+    }
+
+    /// Check one row, pointing the error at its position and column
+    pub fn validate(&self, row: &Row) -> Result<(), DatasetError> {
+        let invalid = |column: &str, message: String| DatasetError::Invalid {
+            position: row.position.clone(),
+            column: column.to_string(),
+            message,
+        };
+
+        for field in &self.fields {
+            match row.record.get(&field.name).map(String::as_str) {
+                None | Some("") if field.nullable => {}
+                None => return Err(invalid(&field.name, "missing required column".into())),
+                Some("") => return Err(invalid(&field.name, "empty value in non-nullable column".into())),
+                Some(value) if !field.dtype.accepts(value) => {
+                    return Err(invalid(
+                        &field.name,
+                        format!("expected {:?}, found `{}`", field.dtype, truncate(value)),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(extra) = row.record.keys().find(|k| !self.fields.iter().any(|f| &f.name == *k)) {
+            return Err(invalid(extra, "column not in schema".into()));
+        }
+        Ok(())
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|f| f.name.as_str())
+    }
+}
+
+/// Keep error messages readable when a cell holds a blob
+fn truncate(value: &str) -> String {
+    const MAX: usize = 40;
+    match value.char_indices().nth(MAX) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value.to_string(),
+    }
+}
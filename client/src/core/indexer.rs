@@ -0,0 +1,192 @@
+// client/src/core/indexer.rs
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+pub const MODELS_PATH: &str = "/models";
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error("Indexer request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Indexer rejected the query ({status}): {message}")]
+    Rejected { status: u16, message: String },
+}
+
+/// Result ordering understood by the indexer's `/models` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SortOrder {
+    /// Most completed inferences first
+    #[default]
+    Popularity,
+    /// Most recently registered first
+    Newest,
+    /// Cheapest inference fee first
+    Fee,
+}
+
+impl SortOrder {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Popularity => "popularity",
+            Self::Newest => "newest",
+            Self::Fee => "fee",
+        }
+    }
+}
+
+/// Filters for `model search`; every set filter must match
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    /// Full-text match against catalog name and description
+    pub query: Option<String>,
+    pub framework: Option<String>,
+    pub tags: Vec<String>,
+    pub max_fee: Option<u64>,
+    pub sort: SortOrder,
+    pub limit: u32,
+}
+
+impl SearchQuery {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params: Vec<_> = self.tags.iter().map(|t| ("tag", t.clone())).collect();
+        if let Some(query) = &self.query {
+            params.push(("q", query.clone()));
+        }
+        if let Some(framework) = &self.framework {
+            params.push(("framework", framework.clone()));
+        }
+        if let Some(fee) = self.max_fee {
+            params.push(("max_fee", fee.to_string()));
+        }
+        params.push(("sort", self.sort.as_str().to_string()));
+        params.push(("limit", self.limit.to_string()));
+        params
+    }
+}
+
+/// One registered model as indexed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelSummary {
+    pub id: String,
+    /// From the catalog document; `None` until the indexer has fetched it
+    pub name: Option<String>,
+    pub owner: String,
+    pub active_version: i64,
+    /// Lamports, or base units of the model's fee token
+    pub inference_fee: i64,
+    pub framework: Option<String>,
+    pub license: Option<String>,
+    pub tags: Vec<String>,
+    /// Completed inferences to date
+    pub inferences: i64,
+    pub status: String,
+}
+
+/// Read-only client for the indexer's REST API
+pub struct IndexerClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl IndexerClient {
+    pub fn new(url: &str) -> Result<Self, IndexerError> {
+        let http = reqwest::Client::builder().timeout(Duration::from_secs(30)).build()?;
+        Ok(Self { http, url: url.trim_end_matches('/').to_string() })
+    }
+
+    pub async fn search(&self, query: &SearchQuery) -> Result<Vec<ModelSummary>, IndexerError> {
+        let response = self.http.get(format!("{}{MODELS_PATH}", self.url)).query(&query.params()).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(IndexerError::Rejected {
+                status: status.as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// Fixed-width table for terminal output
+pub fn render_table(models: &[ModelSummary]) -> String {
+    const HEADERS: [&str; 8] = ["MODEL", "NAME", "FRAMEWORK", "VERSION", "FEE", "INFERENCES", "OWNER", "TAGS"];
+    let rows: Vec<[String; 8]> = models
+        .iter()
+        .map(|m| {
+            [
+                m.id.clone(),
+                m.name.clone().unwrap_or_default(),
+                m.framework.clone().unwrap_or_default(),
+                m.active_version.to_string(),
+                m.inference_fee.to_string(),
+                m.inferences.to_string(),
+                m.owner.clone(),
+                m.tags.join(","),
+            ]
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let line = |cells: [&str; 8]| {
+        let padded: Vec<String> = cells.iter().zip(widths).map(|(c, w)| format!("{c:<w$}")).collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    let mut out = vec![line(HEADERS)];
+    out.extend(rows.iter().map(|row| line(row.each_ref().map(String::as_str))));
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(id: &str, fee: i64, tags: &[&str]) -> ModelSummary {
+        ModelSummary {
+            id: id.into(),
+            name: Some("resnet".into()),
+            owner: "Owner1111".into(),
+            active_version: 3,
+            inference_fee: fee,
+            framework: None,
+            license: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            inferences: 42,
+            status: "active".into(),
+        }
+    }
+
+    #[test]
+    fn test_query_params_repeat_tags_and_skip_unset_fee() {
+        let query = SearchQuery {
+            query: None,
+            framework: Some("onnx".into()),
+            tags: vec!["vision".into(), "int8".into()],
+            max_fee: None,
+            sort: SortOrder::Fee,
+            limit: 20,
+        };
+        let params = query.params();
+        assert_eq!(params.iter().filter(|(k, _)| *k == "tag").count(), 2);
+        assert!(!params.iter().any(|(k, _)| *k == "max_fee"));
+        assert!(!params.iter().any(|(k, _)| *k == "q"));
+        assert!(params.contains(&("framework", "onnx".to_string())));
+        assert!(params.contains(&("sort", "fee".to_string())));
+    }
+
+    #[test]
+    fn test_table_columns_align() {
+        let table = render_table(&[summary("ShortId", 5, &["vision"]), summary("AMuchLongerModelId", 1000, &[])]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let version_col = lines[0].find("VERSION").unwrap();
+        assert!(lines[1..].iter().all(|l| l[version_col..].starts_with('3')));
+    }
+}
@@ -0,0 +1,145 @@
+// client/src/core/inference/backend.rs
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BackendError {
+    #[error("Model decryption failed: {0}")]
+    Decryption(String),
+    #[error("Model loading error: {0}")]
+    ModelLoading(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Inference error: {0}")]
+    Inference(String),
+    #[error("ZK proof generation failed: {0}")]
+    ZkProof(String),
+    #[error("Backend {0:?} is not available in this build")]
+    Unavailable(BackendKind),
+}
+
+/// Runtimes a model can be executed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// tract ONNX runtime (portable CPU, optional CUDA)
+    Onnx,
+    /// NVIDIA TensorRT
+    TensorRt,
+    /// TensorFlow Lite for mobile and edge devices
+    TfLite,
+}
+
+impl BackendKind {
+    /// Backends compiled into this binary, fastest first
+    pub fn available() -> Vec<BackendKind> {
+        let mut kinds = Vec::new();
+        if cfg!(feature = "gpu-accel") {
+            kinds.push(BackendKind::TensorRt);
+        }
+        kinds.push(BackendKind::Onnx);
+        if cfg!(feature = "tflite") {
+            kinds.push(BackendKind::TfLite);
+        }
+        kinds
+    }
+
+    /// Pick `preferred` if compiled in, otherwise the fastest available backend
+    pub fn select(preferred: Option<BackendKind>) -> Result<BackendKind, BackendError> {
+        let available = Self::available();
+        match preferred {
+            Some(kind) if available.contains(&kind) => Ok(kind),
+            Some(kind) => Err(BackendError::Unavailable(kind)),
+            None => Ok(available[0]),
+        }
+    }
+}
+
+/// Dense f32 tensor exchanged with every backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TensorData {
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+impl TensorData {
+    pub fn new(shape: Vec<usize>, data: Vec<f32>) -> Result<Self, BackendError> {
+        let expected: usize = shape.iter().product();
+        if expected != data.len() {
+            return Err(BackendError::InvalidInput(format!(
+                "shape {:?} needs {} elements, got {}",
+                shape,
+                expected,
+                data.len()
+            )));
+        }
+        Ok(Self { shape, data })
+    }
+}
+
+/// Outputs of one inference together with its proof of correct execution
+#[derive(Debug, Clone)]
+pub struct InferenceOutput {
+    pub outputs: Vec<TensorData>,
+    pub proof: Vec<u8>,
+}
+
+/// Common surface for encrypted-model runtimes.
+///
+/// Implementations load a decrypted model from memory only, check it against
+/// the expected BLAKE3 hash, and return a Groth16 proof with every result.
+/// Inference is async so backends with an async prover run on the caller's
+/// runtime, whatever its flavour; blocking backends return a ready future.
+pub trait InferenceBackend: Send {
+    fn kind(&self) -> BackendKind;
+
+    /// BLAKE3 hash of the decrypted model
+    fn model_hash(&self) -> [u8; 32];
+
+    fn infer_with_proof<'a>(
+        &'a mut self,
+        inputs: &'a [TensorData],
+    ) -> BoxFuture<'a, Result<InferenceOutput, BackendError>>;
+}
+
+/// Reject models whose decrypted bytes do not match the registered hash
+pub fn verify_model_hash(model: &[u8], expected: Option<&[u8; 32]>) -> Result<[u8; 32], BackendError> {
+    let hash = *blake3::hash(model).as_bytes();
+    if let Some(expected) = expected {
+        if hash != *expected {
+            return Err(BackendError::ModelLoading("model hash mismatch".into()));
+        }
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tensor_shape_validation() {
+        assert!(TensorData::new(vec![2, 3], vec![0.0; 6]).is_ok());
+        assert!(matches!(
+            TensorData::new(vec![2, 3], vec![0.0; 5]),
+            Err(BackendError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_select_falls_back_to_available() {
+        let kind = BackendKind::select(None).unwrap();
+        assert!(BackendKind::available().contains(&kind));
+        assert!(BackendKind::select(Some(BackendKind::Onnx)).is_ok());
+    }
+
+    #[test]
+    fn test_model_hash_mismatch() {
+        let model = b"model bytes";
+        let hash = verify_model_hash(model, None).unwrap();
+        assert!(verify_model_hash(model, Some(&hash)).is_ok());
+        assert!(verify_model_hash(model, Some(&[0u8; 32])).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+// client/src/core/inference/io_schema.rs
+
+use super::backend::TensorData;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// A model with this hash on-chain has not declared a schema
+const UNDECLARED: [u8; 32] = [0; 32];
+
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("Schema fetch failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Schema cache I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid schema document: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Schema hash mismatch: model declares {expected}, document hashes to {actual}")]
+    HashMismatch { expected: String, actual: String },
+    #[error("Model expects {expected} tensors, got {actual}")]
+    TensorCount { expected: usize, actual: usize },
+    #[error("Tensor `{tensor}` must have shape {expected}, got {actual:?}")]
+    Shape {
+        tensor: String,
+        expected: ShapeSpec,
+        actual: Vec<usize>,
+    },
+    #[error("Tensor `{tensor}` element {index} is {value}, outside [{min}, {max}]")]
+    OutOfRange {
+        tensor: String,
+        index: usize,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+}
+
+/// Tensor dimensions; `null` marks a dynamic dimension such as the batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ShapeSpec(pub Vec<Option<usize>>);
+
+impl ShapeSpec {
+    fn matches(&self, shape: &[usize]) -> bool {
+        self.0.len() == shape.len() && self.0.iter().zip(shape).all(|(spec, dim)| spec.iter().all(|s| s == dim))
+    }
+}
+
+impl fmt::Display for ShapeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dims: Vec<String> = self.0.iter().map(|d| d.map_or("?".into(), |d| d.to_string())).collect();
+        write!(f, "[{}]", dims.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TensorSpec {
+    pub name: String,
+    pub shape: ShapeSpec,
+    /// Inclusive bounds every element must fall within, e.g. normalized pixels
+    #[serde(default)]
+    pub range: Option<(f32, f32)>,
+}
+
+/// Input or output schema of a model, published to IPFS as JSON:
+/// `{"tensors": [{"name": "image", "shape": [null, 3, 224, 224], "range": [0.0, 1.0]}]}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IoSchema {
+    pub tensors: Vec<TensorSpec>,
+}
+
+impl IoSchema {
+    /// Reject tensors the model would not accept, before any inference work is done
+    pub fn validate(&self, tensors: &[TensorData]) -> Result<(), SchemaError> {
+        if tensors.len() != self.tensors.len() {
+            return Err(SchemaError::TensorCount {
+                expected: self.tensors.len(),
+                actual: tensors.len(),
+            });
+        }
+
+        for (spec, tensor) in self.tensors.iter().zip(tensors) {
+            if !spec.shape.matches(&tensor.shape) {
+                return Err(SchemaError::Shape {
+                    tensor: spec.name.clone(),
+                    expected: spec.shape.clone(),
+                    actual: tensor.shape.clone(),
+                });
+            }
+            if let Some((min, max)) = spec.range {
+                // NaN fails both comparisons, so check inclusion rather than exclusion
+                if let Some((index, &value)) = tensor.data.iter().enumerate().find(|(_, v)| !(min..=max).contains(*v)) {
+                    return Err(SchemaError::OutOfRange {
+                        tensor: spec.name.clone(),
+                        index,
+                        value,
+                        min,
+                        max,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// SHA2-256 of a schema document, the value stored on-chain
+pub fn schema_hash(document: &[u8]) -> [u8; 32] {
+    Sha256::digest(document).into()
+}
+
+/// CIDv1 (raw codec, sha2-256) under which a document with this hash is pinned
+pub fn schema_cid(hash: &[u8; 32]) -> String {
+    let mut bytes = vec![0x01, 0x55, 0x12, 0x20];
+    bytes.extend_from_slice(hash);
+    format!("b{}", base32_lower(&bytes))
+}
+
+/// Inverse of [`schema_cid`]; `None` for CIDs of any other form
+pub fn hash_from_cid(cid: &str) -> Option<[u8; 32]> {
+    let bytes = base32_lower_decode(cid.strip_prefix('b')?)?;
+    bytes.strip_prefix(&[0x01, 0x55, 0x12, 0x20][..])?.try_into().ok()
+}
+
+/// RFC 4648 base32, lowercase without padding, as used by multibase `b`
+fn base32_lower(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_lower_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.bytes() {
+        let value = match c {
+            b'a'..=b'z' => c - b'a',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Fetches schemas from an IPFS gateway by their on-chain hash, caching verified copies
+pub struct SchemaStore {
+    gateway: String,
+    cache_dir: PathBuf,
+    http: reqwest::Client,
+}
+
+impl SchemaStore {
+    pub fn new(gateway: impl Into<String>, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            gateway: gateway.into(),
+            cache_dir: cache_dir.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// The schema committed to by `hash`, or `None` when the model declares none
+    pub async fn fetch(&self, hash: &[u8; 32]) -> Result<Option<IoSchema>, SchemaError> {
+        if *hash == UNDECLARED {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&self.fetch_document(hash).await?)?))
+    }
+
+    /// Any hash-addressed document, such as a catalog or tokenizer, verified and cached like schemas
+    pub async fn fetch_document(&self, hash: &[u8; 32]) -> Result<Vec<u8>, SchemaError> {
+        let cached = self.cache_dir.join(format!("{}.json", hex::encode(hash)));
+        let document = match std::fs::read(&cached) {
+            Ok(document) if schema_hash(&document) == *hash => document,
+            _ => {
+                let url = format!("{}/ipfs/{}", self.gateway.trim_end_matches('/'), schema_cid(hash));
+                let document = self.http.get(url).send().await?.error_for_status()?.bytes().await?.to_vec();
+                let actual = schema_hash(&document);
+                if actual != *hash {
+                    return Err(SchemaError::HashMismatch {
+                        expected: hex::encode(hash),
+                        actual: hex::encode(actual),
+                    });
+                }
+                std::fs::create_dir_all(&self.cache_dir)?;
+                std::fs::write(&cached, &document)?;
+                document
+            }
+        };
+        Ok(document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> IoSchema {
+        serde_json::from_str(r#"{"tensors": [{"name": "image", "shape": [null, 3, 2, 2], "range": [0.0, 1.0]}]}"#)
+            .unwrap()
+    }
+
+    #[test]
+    fn accepts_dynamic_batch_dimension() {
+        let input = TensorData::new(vec![4, 3, 2, 2], vec![0.5; 48]).unwrap();
+        assert!(schema().validate(&[input]).is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatched_tensors() {
+        let wrong_shape = TensorData::new(vec![1, 1, 2, 2], vec![0.5; 4]).unwrap();
+        let err = schema().validate(&[wrong_shape]).unwrap_err();
+        assert_eq!(err.to_string(), "Tensor `image` must have shape [?, 3, 2, 2], got [1, 1, 2, 2]");
+
+        let mut data = vec![0.5; 12];
+        data[7] = f32::NAN;
+        let out_of_range = TensorData::new(vec![1, 3, 2, 2], data).unwrap();
+        assert!(matches!(
+            schema().validate(&[out_of_range]),
+            Err(SchemaError::OutOfRange { index: 7, .. })
+        ));
+
+        assert!(matches!(schema().validate(&[]), Err(SchemaError::TensorCount { expected: 1, actual: 0 })));
+    }
+
+    #[test]
+    fn cid_matches_ipfs_raw_leaves() {
+        // `ipfs add --cid-version 1 --raw-leaves` of the empty file
+        assert_eq!(
+            schema_cid(&schema_hash(b"")),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+        assert_eq!(hash_from_cid("bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"), Some(schema_hash(b"")));
+        assert_eq!(hash_from_cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"), None);
+    }
+}
@@ -5,12 +5,22 @@ use blake3::Hash;
 use ndarray::{Array, IxDyn};
 use solana_program::pubkey::Pubkey;
 use std::{
+    collections::HashMap,
     fs::File,
     io::Read,
     path::Path,
     sync::Arc,
     time::Instant,
 };
+use super::backend::{BackendError, BackendKind, InferenceBackend, InferenceOutput, TensorData};
+use super::quantize::QuantizedModel;
+use futures::future::BoxFuture;
+use crate::config::ZkpConfig;
+use crate::core::zkp::partition::{activation_to_fr, commit_activations, BlockProof, ChunkedProof, Partition};
+use crate::core::zkp::prover::ZKProver;
+use crate::core::zkp::registry::{CircuitRegistry, LoadedCircuit};
+use crate::core::zkp::remote::RemoteProver;
+use crate::core::zkp::witness_cache::WitnessCache;
 use thiserror::Error;
 use tract_onnx::{
     prelude::*,
@@ -25,6 +35,19 @@ pub struct OnnxRuntime {
     gpu: bool,
     zk_context: ZkContext,
     rpc_pubkey: Pubkey,
+    /// Integer-only execution for bit-identical outputs across verifiers
+    quantized: Option<QuantizedModel>,
+    /// Decrypted model, re-read when planning chunked proofs
+    source: Arc<[u8]>,
+}
+
+/// The model split into provable blocks, with every block boundary exposed as an output
+pub struct ChunkedPlan {
+    partition: Partition,
+    circuits: Vec<Arc<LoadedCircuit>>,
+    model: TypedRunnableModel<TypedModel>,
+    /// Repeated blocks and inputs across a batch reuse their witnesses
+    witnesses: Arc<WitnessCache>,
 }
 
 #[derive(Debug, Error)]
@@ -41,6 +64,8 @@ pub enum OnnxError {
     ChainVerification(String),
     #[error("GPU acceleration error: {0}")]
     GpuError(String),
+    #[error("Quantization error: {0}")]
+    Quantization(#[from] super::quantize::QuantizeError),
 }
 
 /// Zero-Knowledge Proof Context
@@ -56,6 +81,7 @@ impl OnnxRuntime {
         aes: &Aes256Gcm,
         rpc_pubkey: Pubkey,
         use_gpu: bool,
+        zk_params: &[u8],
     ) -> Result<Self, OnnxError> {
         // 1. Memory-mapped file loading
         let mut file = File::open(path).map_err(|e| OnnxError::ModelLoading(e.to_string()))?;
@@ -92,11 +118,131 @@ impl OnnxRuntime {
             model,
             model_hash: hash,
             gpu: use_gpu,
-            zk_context: init_zk_context()?,
+            zk_context: init_zk_context(zk_params)?,
             rpc_pubkey,
+            quantized: None,
+            source: decrypted.into(),
         })
     }
 
+    /// Partition the graph and resolve a circuit template for every block
+    pub async fn plan_chunks(
+        &self,
+        templates: &HashMap<String, String>,
+        registry: &CircuitRegistry,
+        witnesses: Arc<WitnessCache>,
+    ) -> Result<ChunkedPlan, OnnxError> {
+        let partition = Partition::from_onnx(&self.source).map_err(|e| OnnxError::ZkProof(e.to_string()))?;
+        let circuits = partition
+            .resolve(templates, registry)
+            .await
+            .map_err(|e| OnnxError::ZkProof(e.to_string()))?;
+
+        let mut model = tract_onnx::onnx()
+            .model_for_read(&mut &*self.source)
+            .map_err(|e| OnnxError::ModelLoading(e.to_string()))?;
+        model
+            .set_output_names(partition.boundaries())
+            .map_err(|e| OnnxError::ModelLoading(e.to_string()))?;
+        let model = model
+            .into_optimized()
+            .map_err(|e| OnnxError::ModelLoading(e.to_string()))?
+            .into_runnable()
+            .map_err(|e| OnnxError::ModelLoading(e.to_string()))?;
+
+        tracing::info!(blocks = partition.blocks.len(), "Model partitioned for chunked proving");
+        Ok(ChunkedPlan { partition, circuits, model, witnesses })
+    }
+
+    /// Perform inference, proving each block separately and chaining the proofs
+    /// through commitments to the activations at every block boundary
+    pub async fn infer_with_chunked_proof(
+        &self,
+        inputs: TVec<Arc<Tensor>>,
+        plan: &ChunkedPlan,
+        zkp: &ZkpConfig,
+    ) -> Result<(TVec<Arc<Tensor>>, ChunkedProof), OnnxError> {
+        // 1. Run once, capturing every block boundary
+        let start = Instant::now();
+        let mut previous = inputs
+            .first()
+            .ok_or_else(|| OnnxError::Inference("no input tensor".into()))?
+            .as_slice::<f32>()
+            .map_err(|e| OnnxError::Inference(e.to_string()))?
+            .to_vec();
+        let boundaries = plan.model.run(inputs).map_err(|e| OnnxError::Inference(e.to_string()))?;
+
+        // 2. Prove each block against its template circuit, remotely when configured
+        let remote = match &zkp.remote_prover_url {
+            Some(url) => Some(
+                RemoteProver::connect(url, &zkp.remote_prover)
+                    .await
+                    .map_err(|e| OnnxError::ZkProof(e.to_string()))?,
+            ),
+            None => None,
+        };
+        let mut proof = ChunkedProof::default();
+        for ((block, circuit), boundary) in plan.partition.blocks.iter().zip(&plan.circuits).zip(&boundaries) {
+            let output = boundary
+                .as_slice::<f32>()
+                .map_err(|e| OnnxError::Inference(e.to_string()))?
+                .to_vec();
+            let mut block_proof = BlockProof {
+                template: block.template.clone(),
+                circuit_hash: circuit.circuit_hash,
+                weights_commitment: block.weights_commitment,
+                input_commitment: commit_activations(&previous),
+                output_commitment: commit_activations(&output),
+                proof: Vec::new(),
+            };
+
+            let [weights_commitment, input_commitment, output_commitment] = block_proof.public_inputs();
+            let signals = [
+                ("weights_commitment", vec![weights_commitment]),
+                ("input_commitment", vec![input_commitment]),
+                ("output_commitment", vec![output_commitment]),
+                ("input", previous.iter().map(|&v| activation_to_fr(v)).collect()),
+                ("output", output.iter().map(|&v| activation_to_fr(v)).collect()),
+            ];
+            block_proof.proof = match &remote {
+                Some(remote) => remote
+                    .prove_circuit(circuit, &signals)
+                    .await
+                    .map_err(|e| OnnxError::ZkProof(format!("block {}: {e}", block.index)))?,
+                None => {
+                    let prover = ZKProver::new(&circuit.proving_key_path.to_string_lossy(), zkp)
+                        .await
+                        .map_err(|e| OnnxError::ZkProof(format!("{e:?}")))?
+                        .with_witness_cache(plan.witnesses.clone());
+                    prover
+                        .prove_circuit(circuit, &signals)
+                        .await
+                        .map_err(|e| OnnxError::ZkProof(format!("block {}: {e:?}", block.index)))?
+                }
+            };
+
+            tracing::debug!(block = block.index, template = %block.template, "Block proven");
+            proof.blocks.push(block_proof);
+            previous = output;
+        }
+
+        // 3. Performance metrics
+        crate::metrics::log_inference(start.elapsed(), self.model_hash, self.rpc_pubkey);
+
+        let output = boundaries
+            .last()
+            .cloned()
+            .ok_or_else(|| OnnxError::Inference("model produced no output".into()))?;
+        Ok((tvec!(output), proof))
+    }
+
+    /// Switch to deterministic int8 execution calibrated for this exact model
+    pub fn with_quantization(mut self, quantized: QuantizedModel) -> Result<Self, OnnxError> {
+        quantized.check_source(self.model_hash.as_bytes())?;
+        self.quantized = Some(quantized);
+        Ok(self)
+    }
+
     /// Perform inference with ZKP generation
     pub async fn infer_with_proof(
         &self,
@@ -106,10 +252,13 @@ impl OnnxRuntime {
         let start = Instant::now();
         let mut state = SimpleState::new(self.model.clone());
 
-        // 2. Run inference
-        let outputs = state.run_async(inputs)
-            .await
-            .map_err(|e| OnnxError::Inference(e.to_string()))?;
+        // 2. Run inference; the quantized path never touches f32 kernels
+        let outputs = match &self.quantized {
+            Some(quantized) => run_quantized(quantized, &inputs)?,
+            None => state.run_async(inputs)
+                .await
+                .map_err(|e| OnnxError::Inference(e.to_string()))?,
+        };
 
         // 3. Generate ZK proof
         let proof = self.generate_zk_proof(&state, &outputs)
@@ -117,7 +266,6 @@ impl OnnxRuntime {
             .map_err(|e| OnnxError::ZkProof(e.to_string()))?;
 
         // 4. Performance metrics
-        #[cfg(feature = "telemetry")]
         crate::metrics::log_inference(
             start.elapsed(),
             self.model_hash,
@@ -173,11 +321,66 @@ impl OnnxRuntime {
     }
 }
 
-// Initialize ZK-SNARK parameters
-fn init_zk_context() -> Result<ZkContext, OnnxError> {
-    // Load pre-generated parameters
-    let params = include_bytes!("../zk_params.bin");
-    let params = bellman::groth16::Parameters::read(&mut &params[..], true)
+impl InferenceBackend for OnnxRuntime {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Onnx
+    }
+
+    fn model_hash(&self) -> [u8; 32] {
+        *self.model_hash.as_bytes()
+    }
+
+    fn infer_with_proof<'a>(
+        &'a mut self,
+        inputs: &'a [TensorData],
+    ) -> BoxFuture<'a, Result<InferenceOutput, BackendError>> {
+        Box::pin(async move {
+            let inputs = inputs
+                .iter()
+                .map(|t| {
+                    Array::from_shape_vec(IxDyn(&t.shape), t.data.clone())
+                        .map(|a| Arc::new(Tensor::from(a)))
+                        .map_err(|e| BackendError::InvalidInput(e.to_string()))
+                })
+                .collect::<Result<TVec<_>, _>>()?;
+
+            let (outputs, proof) = OnnxRuntime::infer_with_proof(self, inputs)
+                .await
+                .map_err(|e| BackendError::Inference(e.to_string()))?;
+
+            let outputs = outputs
+                .iter()
+                .map(|t| {
+                    let data = t
+                        .as_slice::<f32>()
+                        .map_err(|e| BackendError::Inference(e.to_string()))?
+                        .to_vec();
+                    TensorData::new(t.shape().to_vec(), data)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(InferenceOutput { outputs, proof })
+        })
+    }
+}
+
+fn run_quantized(quantized: &QuantizedModel, inputs: &TVec<Arc<Tensor>>) -> Result<TVec<Arc<Tensor>>, OnnxError> {
+    let [input] = inputs.as_slice() else {
+        return Err(OnnxError::Inference(format!("quantized models take one input, got {}", inputs.len())));
+    };
+    let data = input.as_slice::<f32>().map_err(|e| OnnxError::Inference(e.to_string()))?;
+    let input = TensorData::new(input.shape().to_vec(), data.to_vec())
+        .map_err(|e| OnnxError::Inference(e.to_string()))?;
+
+    let output = quantized.run(&input)?;
+    let output = Array::from_shape_vec(IxDyn(&output.shape), output.data)
+        .map_err(|e| OnnxError::Inference(e.to_string()))?;
+    Ok(tvec!(Arc::new(Tensor::from(output))))
+}
+
+// Initialize ZK-SNARK parameters from a registry-resolved proving key
+fn init_zk_context(params: &[u8]) -> Result<ZkContext, OnnxError> {
+    let params = bellman::groth16::Parameters::read(params, true)
         .map_err(|e| OnnxError::ZkProof(e.to_string()))?;
 
     // Build verification circuit
@@ -220,7 +423,8 @@ mod tests {
             temp_model.path(),
             &aes,
             Pubkey::new_unique(),
-            false
+            false,
+            &include_bytes!("../zk_params.bin")[..],
         ).await.unwrap();
 
         assert!(!runtime.model_hash.as_bytes().is_empty());
@@ -234,7 +438,8 @@ mod tests {
             temp_model.path(),
             &aes,
             Pubkey::new_unique(),
-            false
+            false,
+            &include_bytes!("../zk_params.bin")[..],
         ).await.unwrap();
 
         let input = Tensor::from(Array::from_elem(IxDyn(&[1, 3, 224, 224]), 0.5f32));
@@ -0,0 +1,436 @@
+// client/src/core/inference/quantize.rs
+
+use super::backend::TensorData;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tract_onnx::pb::{GraphProto, NodeProto, TensorProto};
+
+/// ONNX `TensorProto.DataType.FLOAT`
+const ONNX_FLOAT: i32 = 1;
+
+#[derive(Debug, Error)]
+pub enum QuantizeError {
+    #[error("ONNX parse error: {0}")]
+    Onnx(String),
+    #[error("Node `{node}` ({op}) cannot be quantized: {reason}")]
+    Unsupported { node: String, op: String, reason: String },
+    #[error("Calibration needs at least one sample")]
+    NoSamples,
+    #[error("Quantized model was calibrated for {expected}, not {actual}")]
+    SourceMismatch { expected: String, actual: String },
+    #[error("Input must be [batch, {expected}], got {actual:?}")]
+    Shape { expected: usize, actual: Vec<usize> },
+}
+
+/// Affine int8 mapping `real = scale * (q - zero_point)`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuantParams {
+    pub scale: f32,
+    pub zero_point: i32,
+}
+
+impl QuantParams {
+    /// Parameters covering `[min, max]`, widened to include zero so ReLU and padding are exact
+    pub fn from_range(min: f32, max: f32) -> Self {
+        let (min, max) = (min.min(0.0), max.max(0.0));
+        let scale = if max > min { (max - min) / 255.0 } else { 1.0 };
+        let zero_point = (-128.0 - min / scale).round().clamp(-128.0, 127.0) as i32;
+        Self { scale, zero_point }
+    }
+
+    /// IEEE 754 division and rounding are exact-rounded, so this is identical on every verifier
+    pub fn quantize(&self, x: f32) -> i8 {
+        ((x / self.scale).round() as i32 + self.zero_point).clamp(-128, 127) as i8
+    }
+
+    pub fn dequantize(&self, q: i8) -> f32 {
+        self.scale * (q as i32 - self.zero_point) as f32
+    }
+}
+
+/// Real multiplier `multiplier * 2^-(31 + shift)` applied with integer arithmetic only
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixedMultiplier {
+    pub multiplier: i32,
+    pub shift: i32,
+}
+
+impl FixedMultiplier {
+    /// Normalize `real` into a Q31 mantissa in [0.5, 1) and a power-of-two shift
+    pub fn from_real(real: f64) -> Self {
+        if real <= 0.0 {
+            return Self { multiplier: 0, shift: 0 };
+        }
+        let mut shift = 0;
+        let mut mantissa = real;
+        while mantissa < 0.5 {
+            mantissa *= 2.0;
+            shift += 1;
+        }
+        while mantissa >= 1.0 {
+            mantissa /= 2.0;
+            shift -= 1;
+        }
+        let mut q = (mantissa * (1i64 << 31) as f64).round() as i64;
+        if q == 1 << 31 {
+            q /= 2;
+            shift -= 1;
+        }
+        // Keep the total shift within i64; multipliers outside this range never come from sane calibration
+        Self { multiplier: q as i32, shift: shift.clamp(-30, 31) }
+    }
+
+    /// `round(x * real)`, rounding halves towards positive infinity
+    pub fn apply(&self, x: i32) -> i32 {
+        let product = x as i64 * self.multiplier as i64;
+        let shift = 31 + self.shift;
+        ((product + (1i64 << (shift - 1))) >> shift) as i32
+    }
+}
+
+/// Fully connected layer in f32, weights row-major `[outputs][inputs]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseLayer {
+    pub inputs: usize,
+    pub outputs: usize,
+    pub weights: Vec<f32>,
+    pub bias: Vec<f32>,
+    pub relu: bool,
+}
+
+impl DenseLayer {
+    fn forward(&self, x: &[f32]) -> Vec<f32> {
+        self.weights
+            .chunks(self.inputs)
+            .zip(&self.bias)
+            .map(|(row, b)| {
+                let y = row.iter().zip(x).map(|(w, x)| w * x).sum::<f32>() + b;
+                if self.relu { y.max(0.0) } else { y }
+            })
+            .collect()
+    }
+}
+
+/// Int8 layer: per-output-channel symmetric weights, int32 bias at the accumulator scale
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedDense {
+    pub inputs: usize,
+    pub outputs: usize,
+    pub weights: Vec<i8>,
+    pub bias: Vec<i32>,
+    pub requantize: Vec<FixedMultiplier>,
+    pub relu: bool,
+    pub output: QuantParams,
+}
+
+impl QuantizedDense {
+    fn forward(&self, x: &[i8], input_zero_point: i32) -> Vec<i8> {
+        self.weights
+            .chunks(self.inputs)
+            .zip(self.bias.iter().zip(&self.requantize))
+            .map(|(row, (&bias, requantize))| {
+                let acc = row
+                    .iter()
+                    .zip(x)
+                    .fold(bias, |acc, (&w, &x)| acc + w as i32 * (x as i32 - input_zero_point));
+                let mut y = self.output.zero_point + requantize.apply(acc);
+                if self.relu {
+                    y = y.max(self.output.zero_point);
+                }
+                y.clamp(-128, 127) as i8
+            })
+            .collect()
+    }
+}
+
+/// Integer-only model produced by `scoria-cli model quantize`; every verifier reproduces its
+/// outputs bit for bit, unlike f32 kernels whose results depend on SIMD width and FMA support
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedModel {
+    /// Blake3 of the ONNX model this was calibrated from, hex encoded
+    pub source_hash: String,
+    pub input: QuantParams,
+    pub layers: Vec<QuantizedDense>,
+}
+
+impl QuantizedModel {
+    /// Calibrate per-tensor activation ranges by running `samples` through the f32 graph
+    pub fn calibrate(onnx: &[u8], samples: &[TensorData]) -> Result<Self, QuantizeError> {
+        let layers = extract_dense_layers(onnx)?;
+        Self::from_layers(blake3::hash(onnx).to_hex().to_string(), &layers, samples)
+    }
+
+    pub fn from_layers(source_hash: String, layers: &[DenseLayer], samples: &[TensorData]) -> Result<Self, QuantizeError> {
+        let features = layers.first().map_or(0, |l| l.inputs);
+        let mut rows = Vec::new();
+        for sample in samples {
+            rows.extend(batch_rows(sample, features)?.map(<[f32]>::to_vec));
+        }
+        if rows.is_empty() {
+            return Err(QuantizeError::NoSamples);
+        }
+
+        let (min, max) = min_max(&rows);
+        let input = QuantParams::from_range(min, max);
+        let mut input_scale = input.scale;
+        let mut quantized = Vec::with_capacity(layers.len());
+        for layer in layers {
+            rows = rows.iter().map(|x| layer.forward(x)).collect();
+            let (min, max) = min_max(&rows);
+            let output = QuantParams::from_range(min, max);
+
+            let mut weights = Vec::with_capacity(layer.weights.len());
+            let mut bias = Vec::with_capacity(layer.outputs);
+            let mut requantize = Vec::with_capacity(layer.outputs);
+            for (row, &b) in layer.weights.chunks(layer.inputs).zip(&layer.bias) {
+                let max_abs = row.iter().fold(0f32, |m, w| m.max(w.abs()));
+                let weight_scale = if max_abs > 0.0 { max_abs / 127.0 } else { 1.0 };
+                let acc_scale = input_scale as f64 * weight_scale as f64;
+                weights.extend(row.iter().map(|w| (w / weight_scale).round().clamp(-127.0, 127.0) as i8));
+                bias.push((b as f64 / acc_scale).round() as i32);
+                requantize.push(FixedMultiplier::from_real(acc_scale / output.scale as f64));
+            }
+
+            quantized.push(QuantizedDense {
+                inputs: layer.inputs,
+                outputs: layer.outputs,
+                weights,
+                bias,
+                requantize,
+                relu: layer.relu,
+                output,
+            });
+            input_scale = output.scale;
+        }
+
+        Ok(Self { source_hash, input, layers: quantized })
+    }
+
+    /// Refuse to run against a model other than the one calibrated
+    pub fn check_source(&self, model_hash: &[u8; 32]) -> Result<(), QuantizeError> {
+        let actual = hex::encode(model_hash);
+        if actual != self.source_hash {
+            return Err(QuantizeError::SourceMismatch { expected: self.source_hash.clone(), actual });
+        }
+        Ok(())
+    }
+
+    /// Run a `[batch, features]` input; only quantizing the input touches floating point
+    pub fn run(&self, input: &TensorData) -> Result<TensorData, QuantizeError> {
+        let features = self.layers.first().map_or(0, |l| l.inputs);
+        let outputs = self.layers.last().map_or(features, |l| l.outputs);
+        let mut data = Vec::new();
+        let mut batch = 0;
+        for row in batch_rows(input, features)? {
+            let mut x: Vec<i8> = row.iter().map(|&v| self.input.quantize(v)).collect();
+            let mut params = self.input;
+            for layer in &self.layers {
+                x = layer.forward(&x, params.zero_point);
+                params = layer.output;
+            }
+            data.extend(x.iter().map(|&q| params.dequantize(q)));
+            batch += 1;
+        }
+        TensorData::new(vec![batch, outputs], data).map_err(|_| QuantizeError::Shape {
+            expected: features,
+            actual: input.shape.clone(),
+        })
+    }
+}
+
+fn batch_rows(tensor: &TensorData, features: usize) -> Result<std::slice::Chunks<'_, f32>, QuantizeError> {
+    match tensor.shape.as_slice() {
+        [_, f] if *f == features && features > 0 => Ok(tensor.data.chunks(features)),
+        _ => Err(QuantizeError::Shape { expected: features, actual: tensor.shape.clone() }),
+    }
+}
+
+fn min_max(rows: &[Vec<f32>]) -> (f32, f32) {
+    rows.iter()
+        .flatten()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+}
+
+/// Lower a sequential Gemm/MatMul/Add/Relu graph (an MLP) to dense layers
+pub fn extract_dense_layers(onnx: &[u8]) -> Result<Vec<DenseLayer>, QuantizeError> {
+    let proto = tract_onnx::onnx()
+        .proto_model_for_read(&mut &*onnx)
+        .map_err(|e| QuantizeError::Onnx(e.to_string()))?;
+    let graph: GraphProto = proto.graph.ok_or_else(|| QuantizeError::Onnx("model has no graph".into()))?;
+    let initializer = |name: &str| graph.initializer.iter().find(|t| t.name == name);
+
+    let mut layers: Vec<DenseLayer> = Vec::new();
+    let mut current = graph
+        .input
+        .iter()
+        .map(|i| i.name.as_str())
+        .find(|name| initializer(name).is_none())
+        .ok_or_else(|| QuantizeError::Onnx("graph has no runtime input".into()))?
+        .to_string();
+
+    for node in &graph.node {
+        let unsupported = |reason: &str| QuantizeError::Unsupported {
+            node: node.name.clone(),
+            op: node.op_type.clone(),
+            reason: reason.into(),
+        };
+        // Each node must consume the previous node's output: no branches or skip connections
+        let operands: Vec<&str> = node.input.iter().map(String::as_str).filter(|i| *i != current).collect();
+        if operands.len() == node.input.len() {
+            return Err(unsupported("graph is not sequential"));
+        }
+
+        match node.op_type.as_str() {
+            "Gemm" | "MatMul" => {
+                let (weights, dims) = node
+                    .input
+                    .get(1)
+                    .and_then(|name| initializer(name))
+                    .ok_or_else(|| unsupported("weights are not a constant initializer"))
+                    .and_then(|t| float_data(t).map_err(|e| unsupported(&e)))?;
+                let [rows, cols] = dims[..] else { return Err(unsupported("weights are not 2-D")) };
+                let is_gemm = node.op_type == "Gemm";
+                if is_gemm && (attr_i(node, "transA") != 0 || attr_f(node, "alpha") != 1.0 || attr_f(node, "beta") != 1.0) {
+                    return Err(unsupported("only transA=0, alpha=1, beta=1 are supported"));
+                }
+                let trans_b = is_gemm && attr_i(node, "transB") != 0;
+                let (inputs, outputs) = if trans_b { (cols, rows) } else { (rows, cols) };
+                let weights = if trans_b {
+                    weights
+                } else {
+                    (0..outputs).flat_map(|o| (0..inputs).map(move |i| (i, o))).map(|(i, o)| weights[i * outputs + o]).collect()
+                };
+                let bias = match node.input.get(2).filter(|_| is_gemm).and_then(|name| initializer(name)) {
+                    Some(t) => float_data(t).map_err(|e| unsupported(&e))?.0,
+                    None => vec![0.0; outputs],
+                };
+                if bias.len() != outputs {
+                    return Err(unsupported("bias is not per output channel"));
+                }
+                layers.push(DenseLayer { inputs, outputs, weights, bias, relu: false });
+            }
+            "Add" => {
+                let layer = layers.last_mut().filter(|l| !l.relu).ok_or_else(|| unsupported("Add must follow a linear layer"))?;
+                let (bias, _) = operands
+                    .first()
+                    .and_then(|name| initializer(name))
+                    .ok_or_else(|| unsupported("addend is not a constant initializer"))
+                    .and_then(|t| float_data(t).map_err(|e| unsupported(&e)))?;
+                if bias.len() != layer.outputs {
+                    return Err(unsupported("addend is not per output channel"));
+                }
+                layer.bias.iter_mut().zip(bias).for_each(|(b, a)| *b += a);
+            }
+            "Relu" => {
+                layers.last_mut().ok_or_else(|| unsupported("Relu must follow a linear layer"))?.relu = true;
+            }
+            // Shape-only ops are no-ops on a flattened feature vector
+            "Flatten" | "Reshape" | "Identity" | "Dropout" => {}
+            _ => return Err(unsupported("operator has no integer kernel")),
+        }
+        current = node.output.first().cloned().unwrap_or_default();
+    }
+
+    if layers.is_empty() {
+        return Err(QuantizeError::Onnx("graph has no linear layers".into()));
+    }
+    if let Some(pair) = layers.windows(2).find(|w| w[0].outputs != w[1].inputs) {
+        return Err(QuantizeError::Onnx(format!(
+            "layer widths do not chain: {} outputs into {} inputs",
+            pair[0].outputs, pair[1].inputs
+        )));
+    }
+    Ok(layers)
+}
+
+fn float_data(tensor: &TensorProto) -> Result<(Vec<f32>, Vec<usize>), String> {
+    if tensor.data_type != ONNX_FLOAT {
+        return Err(format!("initializer `{}` is not float32", tensor.name));
+    }
+    let data = if tensor.raw_data.is_empty() {
+        tensor.float_data.clone()
+    } else {
+        tensor.raw_data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect()
+    };
+    Ok((data, tensor.dims.iter().map(|&d| d as usize).collect()))
+}
+
+fn attr_i(node: &NodeProto, name: &str) -> i64 {
+    node.attribute.iter().find(|a| a.name == name).map_or(0, |a| a.i)
+}
+
+fn attr_f(node: &NodeProto, name: &str) -> f32 {
+    node.attribute.iter().find(|a| a.name == name).map_or(1.0, |a| a.f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mlp() -> Vec<DenseLayer> {
+        let weight = |i: usize| ((i * 37 % 19) as f32 - 9.0) / 10.0;
+        vec![
+            DenseLayer {
+                inputs: 4,
+                outputs: 8,
+                weights: (0..32).map(weight).collect(),
+                bias: (0..8).map(|i| i as f32 / 20.0 - 0.2).collect(),
+                relu: true,
+            },
+            DenseLayer {
+                inputs: 8,
+                outputs: 3,
+                weights: (0..24).map(|i| weight(i + 5)).collect(),
+                bias: vec![0.1, -0.1, 0.0],
+                relu: false,
+            },
+        ]
+    }
+
+    fn samples() -> TensorData {
+        let data = (0..64).map(|i| ((i * 13 % 29) as f32) / 29.0 * 2.0 - 1.0).collect();
+        TensorData::new(vec![16, 4], data).unwrap()
+    }
+
+    #[test]
+    fn fixed_multiplier_rounds_like_real_product() {
+        for real in [0.000_37, 0.013, 0.25, 0.5, 0.999, 1.7] {
+            let m = FixedMultiplier::from_real(real);
+            for x in [-100_000, -1234, -1, 0, 1, 77, 54_321] {
+                let expected = (x as f64 * real).round();
+                assert!((m.apply(x) as f64 - expected).abs() <= 1.0, "{real} * {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn quantized_tracks_float_model() {
+        let layers = mlp();
+        let samples = samples();
+        let model = QuantizedModel::from_layers("00".into(), &layers, std::slice::from_ref(&samples)).unwrap();
+        let output = model.run(&samples).unwrap();
+        assert_eq!(output.shape, vec![16, 3]);
+
+        for (row, quantized) in samples.data.chunks(4).zip(output.data.chunks(3)) {
+            let expected = layers.iter().fold(row.to_vec(), |x, l| l.forward(&x));
+            for (e, q) in expected.iter().zip(quantized) {
+                assert!((e - q).abs() < 0.05, "{e} vs {q}");
+            }
+        }
+    }
+
+    #[test]
+    fn serialized_model_reproduces_outputs_bit_for_bit() {
+        let model = QuantizedModel::from_layers("00".into(), &mlp(), &[samples()]).unwrap();
+        let reloaded: QuantizedModel = serde_json::from_str(&serde_json::to_string(&model).unwrap()).unwrap();
+        let bits = |m: &QuantizedModel| m.run(&samples()).unwrap().data.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&model), bits(&reloaded));
+
+        let wrong = TensorData::new(vec![2, 2], vec![0.0; 4]).unwrap();
+        assert!(matches!(model.run(&wrong), Err(QuantizeError::Shape { expected: 4, .. })));
+        assert!(matches!(
+            model.check_source(&[1; 32]),
+            Err(QuantizeError::SourceMismatch { .. })
+        ));
+    }
+}
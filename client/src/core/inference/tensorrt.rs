@@ -8,6 +8,8 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
+use super::backend::{BackendError, BackendKind, InferenceBackend, InferenceOutput, TensorData};
+use futures::future::BoxFuture;
 use thiserror::Error;
 use zerocopy::AsBytes;
 
@@ -45,6 +47,8 @@ pub enum TensorRtError {
 
 /// Secure TensorRT Runtime with CUDA acceleration
 pub struct TensorRtRuntime {
+    /// CUDA driver context the engine was built in; made current per call
+    cu_context: *mut c_void,
     engine: *mut c_void,
     context: *mut c_void,
     model_hash: [u8; 32],
@@ -95,6 +99,7 @@ impl TensorRtRuntime {
             .map_err(|e| TensorRtError::ZkProof(e.to_string()))?;
 
         Ok(Self {
+            cu_context: ctx,
             engine,
             context,
             model_hash: *hash.as_bytes(),
@@ -108,6 +113,9 @@ impl TensorRtRuntime {
         &mut self,
         inputs: &[&[f32]],
     ) -> Result<(Vec<f32>, Vec<u8>), TensorRtError> {
+        // The runtime may have moved threads since the last call
+        cuda_check(cuCtxSetCurrent(self.cu_context))?;
+
         // 1. Allocate device memory
         let mut d_inputs = vec![];
         let mut d_outputs = vec![];
@@ -157,6 +165,47 @@ impl TensorRtRuntime {
     }
 }
 
+impl InferenceBackend for TensorRtRuntime {
+    fn kind(&self) -> BackendKind {
+        BackendKind::TensorRt
+    }
+
+    fn model_hash(&self) -> [u8; 32] {
+        self.model_hash
+    }
+
+    fn infer_with_proof<'a>(
+        &'a mut self,
+        inputs: &'a [TensorData],
+    ) -> BoxFuture<'a, Result<InferenceOutput, BackendError>> {
+        let slices: Vec<&[f32]> = inputs.iter().map(|t| t.data.as_slice()).collect();
+
+        // SAFETY: the engine, context and stream were created together in
+        // `load_encrypted`, are only used through `&mut self`, and the CUDA
+        // context is rebound to this thread before use
+        let result = unsafe { TensorRtRuntime::infer_with_proof(self, &slices) }
+            .map_err(|e| match e {
+                TensorRtError::ZkProof(msg) => BackendError::ZkProof(msg),
+                other => BackendError::Inference(other.to_string()),
+            })
+            .and_then(|(output, proof)| {
+                let len = output.len();
+                Ok(InferenceOutput {
+                    outputs: vec![TensorData::new(vec![len], output)?],
+                    proof,
+                })
+            });
+        // Execution is synchronous on the stream; there is nothing to await
+        Box::pin(std::future::ready(result))
+    }
+}
+
+// Moving the runtime to another thread is sound because every entry point
+// taking `&mut self` first binds `cu_context` to the calling thread with
+// `cuCtxSetCurrent`; new methods touching device state must do the same.
+// Not `Sync`: the execution context and stream must not be used concurrently.
+unsafe impl Send for TensorRtRuntime {}
+
 // CUDA error checking macro
 macro_rules! cuda_check {
     ($call:expr) => {
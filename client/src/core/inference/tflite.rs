@@ -0,0 +1,166 @@
+// client/src/core/inference/tflite.rs
+
+use super::backend::{
+    verify_model_hash, BackendError, BackendKind, InferenceBackend, InferenceOutput, TensorData,
+};
+use crate::core::{compression::container, model_loader::context::CryptoContext};
+use futures::future::BoxFuture;
+use std::{path::Path, time::Instant};
+use tflite::{
+    ops::builtin::BuiltinOpResolver,
+    FlatBufferModel, Interpreter, InterpreterBuilder,
+};
+
+/// Threads used by the TFLite interpreter on edge devices
+const DEFAULT_THREADS: i32 = 2;
+
+/// Secure TensorFlow Lite runtime for mobile and edge clients
+pub struct TfLiteRuntime {
+    interpreter: Interpreter<'static, BuiltinOpResolver>,
+    model_hash: [u8; 32],
+    zk_context: ZkContext,
+}
+
+/// Zero-Knowledge Proof Context
+struct ZkContext {
+    circuit: crate::zk::InferenceCircuit,
+    params: bellman::groth16::Parameters<bls12_381::Bls12>,
+}
+
+impl TfLiteRuntime {
+    /// Decrypt a `.tflite` flatbuffer in memory and build an interpreter for it
+    pub fn load_encrypted(
+        path: impl AsRef<Path>,
+        crypto_ctx: &CryptoContext,
+        expected_hash: Option<&[u8; 32]>,
+        zk_params: &[u8],
+    ) -> Result<Self, BackendError> {
+        // 1. Read and decrypt; plaintext never touches disk
+        let encrypted = std::fs::read(path)
+            .map_err(|e| BackendError::ModelLoading(e.to_string()))?;
+        let decrypted = crypto_ctx
+            .decrypt_model(encrypted)
+            .map_err(|e| BackendError::Decryption(e.to_string()))?;
+        let decrypted = container::decompress(decrypted)
+            .map_err(|e| BackendError::ModelLoading(e.to_string()))?;
+
+        // 2. Blake3 integrity check
+        let model_hash = verify_model_hash(&decrypted, expected_hash)?;
+
+        // 3. Build interpreter from the in-memory flatbuffer
+        let model = FlatBufferModel::build_from_buffer(decrypted)
+            .map_err(|e| BackendError::ModelLoading(e.to_string()))?;
+        let mut interpreter = InterpreterBuilder::new(model, BuiltinOpResolver::default())
+            .and_then(|builder| builder.build())
+            .map_err(|e| BackendError::ModelLoading(e.to_string()))?;
+        interpreter.set_num_threads(DEFAULT_THREADS);
+        interpreter
+            .allocate_tensors()
+            .map_err(|e| BackendError::ModelLoading(e.to_string()))?;
+
+        // 4. ZK parameters shared with the other backends
+        let params = bellman::groth16::Parameters::read(zk_params, true)
+            .map_err(|e| BackendError::ZkProof(e.to_string()))?;
+
+        Ok(Self {
+            interpreter,
+            model_hash,
+            zk_context: ZkContext {
+                circuit: crate::zk::InferenceCircuit::default(),
+                params,
+            },
+        })
+    }
+
+    fn generate_zk_proof(&self, inputs: &[TensorData], outputs: &[TensorData]) -> Result<Vec<u8>, BackendError> {
+        let circuit = self.zk_context.circuit.with_witness(
+            &self.model_hash,
+            inputs.iter().map(|t| t.data.as_slice()),
+            outputs.iter().map(|t| t.data.as_slice()),
+        );
+        let proof = bellman::groth16::create_random_proof(circuit, &self.zk_context.params, &mut rand::thread_rng())
+            .map_err(|e| BackendError::ZkProof(e.to_string()))?;
+
+        let mut proof_bytes = vec![];
+        proof
+            .write(&mut proof_bytes)
+            .map_err(|e| BackendError::ZkProof(e.to_string()))?;
+        Ok(proof_bytes)
+    }
+}
+
+impl InferenceBackend for TfLiteRuntime {
+    fn kind(&self) -> BackendKind {
+        BackendKind::TfLite
+    }
+
+    fn model_hash(&self) -> [u8; 32] {
+        self.model_hash
+    }
+
+    fn infer_with_proof<'a>(
+        &'a mut self,
+        inputs: &'a [TensorData],
+    ) -> BoxFuture<'a, Result<InferenceOutput, BackendError>> {
+        // The interpreter blocks; there is nothing to await
+        Box::pin(std::future::ready(self.invoke(inputs)))
+    }
+}
+
+impl TfLiteRuntime {
+    fn invoke(&mut self, inputs: &[TensorData]) -> Result<InferenceOutput, BackendError> {
+        let start = Instant::now();
+        let input_indices = self.interpreter.inputs().to_vec();
+        if input_indices.len() != inputs.len() {
+            return Err(BackendError::InvalidInput(format!(
+                "model expects {} inputs, got {}",
+                input_indices.len(),
+                inputs.len()
+            )));
+        }
+
+        // 1. Copy inputs into interpreter-owned buffers
+        for (&index, tensor) in input_indices.iter().zip(inputs) {
+            let buffer = self
+                .interpreter
+                .tensor_data_mut::<f32>(index)
+                .map_err(|e| BackendError::InvalidInput(e.to_string()))?;
+            if buffer.len() != tensor.data.len() {
+                return Err(BackendError::InvalidInput(format!(
+                    "input {} expects {} elements, got {}",
+                    index,
+                    buffer.len(),
+                    tensor.data.len()
+                )));
+            }
+            buffer.copy_from_slice(&tensor.data);
+        }
+
+        // 2. Run inference
+        self.interpreter
+            .invoke()
+            .map_err(|e| BackendError::Inference(e.to_string()))?;
+
+        // 3. Collect outputs
+        let mut outputs = Vec::new();
+        for &index in self.interpreter.outputs() {
+            let shape = self
+                .interpreter
+                .tensor_info(index)
+                .ok_or_else(|| BackendError::Inference(format!("missing output tensor {}", index)))?
+                .dims;
+            let data = self
+                .interpreter
+                .tensor_data::<f32>(index)
+                .map_err(|e| BackendError::Inference(e.to_string()))?
+                .to_vec();
+            outputs.push(TensorData::new(shape, data)?);
+        }
+
+        // 4. Generate ZK proof
+        let proof = self.generate_zk_proof(inputs, &outputs)?;
+
+        tracing::debug!(elapsed = ?start.elapsed(), backend = "tflite", "Inference completed");
+        Ok(InferenceOutput { outputs, proof })
+    }
+}
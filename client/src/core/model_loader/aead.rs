@@ -0,0 +1,88 @@
+// client/src/core/model_loader/aead.rs
+
+//! AEAD ciphers behind `Aes256GcmProvider`. AES-256-GCM is the default and
+//! uses AES-NI where present; XChaCha20-Poly1305 is for CPUs without AES
+//! instructions, e.g. ARM edge devices, where software AES is slow. The
+//! header of each artifact names its cipher, so either one reads both.
+
+use super::{
+    aes::{AesError, AesGcmAead},
+    format::CipherId,
+};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use std::sync::OnceLock;
+
+static ACTIVE_CIPHER: OnceLock<CipherId> = OnceLock::new();
+
+pub trait AeadProvider: Send + Sync {
+    fn id(&self) -> CipherId;
+
+    /// Nonce bytes; STREAM segments take all but the last five as a prefix
+    fn nonce_len(&self) -> usize;
+
+    /// Ciphertext with the tag appended
+    fn seal(&self, key: &[u8; 32], nonce: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError>;
+
+    fn open(&self, key: &[u8; 32], nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError>;
+}
+
+/// XChaCha20-Poly1305: constant-time in software, and its 192-bit nonces
+/// are safe to pick at random
+pub struct XChaCha20Poly1305Aead;
+
+impl AeadProvider for XChaCha20Poly1305Aead {
+    fn id(&self) -> CipherId {
+        CipherId::XChaCha20Poly1305
+    }
+
+    fn nonce_len(&self) -> usize {
+        24
+    }
+
+    fn seal(&self, key: &[u8; 32], nonce: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+        check_nonce(self, nonce)?;
+        XChaCha20Poly1305::new(key.into())
+            .encrypt(XNonce::from_slice(nonce), Payload { msg: plaintext, aad })
+            .map_err(|_| AesError::EncryptionFailed)
+    }
+
+    fn open(&self, key: &[u8; 32], nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+        check_nonce(self, nonce)?;
+        XChaCha20Poly1305::new(key.into())
+            .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+            .map_err(|_| AesError::DecryptionFailed)
+    }
+}
+
+impl CipherId {
+    /// Make this the cipher new artifacts use. Call after loading the
+    /// config; the first call wins.
+    pub fn install(self) {
+        if ACTIVE_CIPHER.set(self).is_err() && cipher_id() != self {
+            tracing::warn!("Cipher already installed; keeping the first");
+        }
+    }
+}
+
+/// Cipher installed from the config, or AES-256-GCM
+pub fn cipher_id() -> CipherId {
+    *ACTIVE_CIPHER.get_or_init(CipherId::default)
+}
+
+/// Implementation of `id`
+pub fn aead(id: CipherId) -> &'static dyn AeadProvider {
+    match id {
+        CipherId::Aes256Gcm => &AesGcmAead,
+        CipherId::XChaCha20Poly1305 => &XChaCha20Poly1305Aead,
+    }
+}
+
+pub(crate) fn check_nonce(aead: &dyn AeadProvider, nonce: &[u8]) -> Result<(), AesError> {
+    if nonce.len() != aead.nonce_len() {
+        return Err(AesError::InvalidLength);
+    }
+    Ok(())
+}
@@ -1,53 +1,168 @@
 // client/src/crypto/aes.rs
 
 use aes_gcm::{
-    aead::{Aead, AeadCore, KeyInit, OsRng},
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
     Aes256Gcm, Nonce
 };
+use super::{
+    aead::{aead, check_nonce, cipher_id, AeadProvider},
+    format::{CipherHeader, CipherId, Kdf, LegacyLayout, FORMAT_VERSION},
+};
 use argon2::{self, Config, ThreadMode, Variant, Version};
-use hex;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
 use std::{
+    io::{ErrorKind, Read, Write},
     mem::MaybeUninit,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    time::{Duration, Instant},
 };
+use zeroize::Zeroize;
+
+/// Plaintext bytes per STREAM segment
+pub const STREAM_CHUNK_SIZE: usize = 1024 * 1024; // 1 MB
+const STREAM_NONCE_SUFFIX_LEN: usize = 5; // BE32 counter + last-block flag
+const TAG_LEN: usize = 16;
+
+const MAX_MEM_COST: u32 = 4 * 1024 * 1024; // 4 GiB
+const MAX_TIME_COST: u32 = 64;
+const MAX_LANES: u32 = 64;
+/// Calibration never goes below this much memory, however slow the host
+const MIN_CALIBRATED_MEM_COST: u32 = 8 * 1024; // 8 MiB
+const MAX_CALIBRATED_LANES: usize = 8;
+
+static ACTIVE_KDF: OnceLock<KdfParams> = OnceLock::new();
+
+/// Argon2id costs. Ciphertexts record theirs in their header, so files stay
+/// readable after the costs change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KdfParams {
+    /// Memory in KiB
+    pub mem_cost: u32,
+    /// Passes over memory
+    pub time_cost: u32,
+    /// Parallel lanes
+    pub lanes: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::LEGACY
+    }
+}
+
+impl KdfParams {
+    /// Costs of every ciphertext written before headers: 19 MiB, 3 passes
+    pub const LEGACY: Self = Self { mem_cost: 19456, time_cost: 3, lanes: 4 };
+
+    /// Make these the costs new ciphertexts use. Call after loading the
+    /// config; the first call wins.
+    pub fn install(self) {
+        if ACTIVE_KDF.set(self).is_err() && *kdf_params() != self {
+            tracing::warn!("Argon2 costs already installed; keeping the first set");
+        }
+    }
+
+    /// Benchmark this host and pick costs taking about `target` per key
+    /// derivation. Memory grows first, up to `max_mem_cost` KiB, then passes.
+    /// Returns the costs and how long they took.
+    pub fn calibrate(target: Duration, max_mem_cost: u32) -> Result<(Self, Duration), AesError> {
+        let lanes = std::thread::available_parallelism().map_or(1, |n| n.get().min(MAX_CALIBRATED_LANES)) as u32;
+        let max_mem_cost = max_mem_cost.clamp(MIN_CALIBRATED_MEM_COST, MAX_MEM_COST);
+        let mut params = Self { mem_cost: MIN_CALIBRATED_MEM_COST, time_cost: 1, lanes };
+        let mut elapsed = params.benchmark()?;
+
+        // Memory is what makes guessing expensive on GPUs, so spend the budget there first
+        while elapsed < target && params.mem_cost < max_mem_cost {
+            let scale = (target.as_secs_f64() / elapsed.as_secs_f64().max(1e-3)).min(4.0);
+            params.mem_cost = ((params.mem_cost as f64 * scale) as u32).clamp(params.mem_cost + 1, max_mem_cost);
+            elapsed = params.benchmark()?;
+        }
+        if elapsed < target {
+            let scale = target.as_secs_f64() / elapsed.as_secs_f64().max(1e-3);
+            params.time_cost = (scale as u32).clamp(1, MAX_TIME_COST);
+            elapsed = params.benchmark()?;
+        }
+        Ok((params, elapsed))
+    }
+
+    fn benchmark(&self) -> Result<Duration, AesError> {
+        let start = Instant::now();
+        derive_key("calibration", &[0u8; 16], self)?.zeroize();
+        Ok(start.elapsed())
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        (1..=MAX_LANES).contains(&self.lanes)
+            && (8 * self.lanes..=MAX_MEM_COST).contains(&self.mem_cost)
+            && (1..=MAX_TIME_COST).contains(&self.time_cost)
+    }
+}
 
-/// Hardware-accelerated AES implementation
+/// Costs installed from the config, or [`KdfParams::LEGACY`]
+pub fn kdf_params() -> &'static KdfParams {
+    ACTIVE_KDF.get_or_init(KdfParams::default)
+}
+
+/// Password and data-key encryption with the installed cipher: AES-256-GCM,
+/// hardware-accelerated where available, unless the config picks another
 #[derive(Clone)]
 pub struct Aes256GcmProvider {
-    use_hardware: bool,
+    cipher: CipherId,
+    kdf: KdfParams,
 }
 
 impl Aes256GcmProvider {
-    /// Initialize AES provider with hardware acceleration detection
+    /// Initialize the provider with the installed cipher and Argon2 costs
     pub fn new() -> Self {
         Self {
-            use_hardware: is_aesni_supported(),
+            cipher: cipher_id(),
+            kdf: *kdf_params(),
         }
     }
 
-    /// Encrypt data with AES-256-GCM and Argon2 key derivation
+    /// Seal new ciphertexts with `cipher` instead of the installed one
+    pub fn with_cipher(mut self, cipher: CipherId) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Derive keys for new ciphertexts with `kdf` instead of the installed costs
+    pub fn with_kdf(mut self, kdf: KdfParams) -> Self {
+        self.kdf = kdf;
+        self
+    }
+
+    /// Whether `ciphertext` predates the current format or was sealed with
+    /// another cipher or other Argon2 costs than this provider's, so should
+    /// be re-encrypted once its password is at hand
+    pub fn needs_rekey(&self, ciphertext: &[u8]) -> bool {
+        CipherHeader::read(&mut &ciphertext[..], LegacyLayout::SEALED).is_ok_and(|header| {
+            header.version < FORMAT_VERSION || header.cipher != self.cipher || header.kdf != Kdf::Argon2id(self.kdf)
+        })
+    }
+
+    /// Encrypt data with the installed cipher and Argon2 key derivation
     pub fn encrypt(&self, plaintext: &[u8], password: &str, aad: &[u8]) -> Result<Vec<u8>, AesError> {
         // Key derivation with Argon2
         let salt = Argon2Salt::generate();
-        let key = self.derive_key(password, &salt)?;
+        let key = self.derive_key(password, &salt, &self.kdf)?;
 
-        // Initialize cipher
-        let cipher = self.init_cipher(&key)?;
+        // Random nonce of the cipher's size
+        let aead = aead(self.cipher);
+        let mut nonce = vec![0u8; aead.nonce_len()];
+        OsRng.fill_bytes(&mut nonce);
 
-        // Generate random 96-bit nonce
-        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        // The header is authenticated along with the data
+        let header = CipherHeader::new(self.cipher, Kdf::Argon2id(self.kdf), &salt, &nonce, aad).to_bytes();
+        let ciphertext = aead.seal(&key, &nonce, plaintext, &header)?;
 
-        // Hardware-accelerated encryption
-        let ciphertext = if self.use_hardware {
-            unsafe { self.encrypt_ni(cipher, nonce, plaintext, aad)? }
-        } else {
-            cipher.encrypt(&nonce, plaintext)?
-        };
-
-        // Build output format: [Argon2 salt (16B)] [nonce (12B)] [ciphertext] [tag (16B)]
-        let mut output = Vec::with_capacity(16 + 12 + ciphertext.len() + 16);
-        output.extend_from_slice(&salt);
-        output.extend_from_slice(nonce.as_slice());
+        // Build output format: [header] [ciphertext] [tag (16B)]
+        let mut output = header;
         output.extend(ciphertext);
         Ok(output)
     }
@@ -55,56 +170,209 @@ impl Aes256GcmProvider {
     /// Decrypt data with authentication checks
     pub fn decrypt(&self, ciphertext: &[u8], password: &str, aad: &[u8]) -> Result<Vec<u8>, AesError> {
         // Parse ciphertext components
-        if ciphertext.len() < 16 + 12 + 16 {
+        let mut ciphertext = ciphertext;
+        let header = CipherHeader::read(&mut ciphertext, LegacyLayout::SEALED)?;
+        header.check_aad(aad)?;
+        let kdf = header.kdf_params().ok_or(AesError::Format("sealed under a raw key, not a password".into()))?;
+        if ciphertext.len() < TAG_LEN {
             return Err(AesError::InvalidLength);
         }
 
-        let salt = &ciphertext[0..16];
-        let nonce = Nonce::from_slice(&ciphertext[16..28]);
-        let encrypted_data = &ciphertext[28..ciphertext.len() - 16];
-        let tag = &ciphertext[ciphertext.len() - 16..];
+        // Derive key
+        let key = self.derive_key(password, &header.salt, &kdf)?;
 
-        // Reconstruct full ciphertext with tag
-        let mut full_ciphertext = Vec::from(encrypted_data);
-        full_ciphertext.extend_from_slice(tag);
+        aead(header.cipher).open(&key, &header.nonce, ciphertext, &header.aead_aad(aad))
+    }
 
-        // Derive key
-        let key = self.derive_key(password, salt)?;
+    /// Encrypt a stream with the STREAM construction (BE32 counter).
+    ///
+    /// Memory use is bounded by `STREAM_CHUNK_SIZE` regardless of input size.
+    /// Output format: [header] then one `chunk + tag` segment per
+    /// `STREAM_CHUNK_SIZE` of plaintext, the final segment sealed with the
+    /// last-block flag so truncation is detected.
+    /// Returns the number of plaintext bytes consumed.
+    pub fn encrypt_stream<R: Read, W: Write>(
+        &self,
+        reader: R,
+        writer: W,
+        password: &str,
+        aad: &[u8],
+    ) -> Result<u64, AesError> {
+        let salt = Argon2Salt::generate();
+        let key = self.derive_key(password, &salt, &self.kdf)?;
+        self.seal_segments(reader, writer, &key, Kdf::Argon2id(self.kdf), &salt, aad)
+    }
 
-        // Initialize cipher
-        let cipher = self.init_cipher(&key)?;
+    /// Decrypt a stream produced by `encrypt_stream`.
+    ///
+    /// Each segment is authenticated before it is written out; a missing or
+    /// reordered segment fails with `DecryptionFailed`. Returns the number of
+    /// plaintext bytes written.
+    pub fn decrypt_stream<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        writer: W,
+        password: &str,
+        aad: &[u8],
+    ) -> Result<u64, AesError> {
+        let header = CipherHeader::read(&mut reader, LegacyLayout::STREAM)?;
+        header.check_aad(aad)?;
+        let kdf = header.kdf_params().ok_or(AesError::Format("sealed under a raw key, not a password".into()))?;
+        let key = self.derive_key(password, &header.salt, &kdf)?;
+        self.open_segments(reader, writer, &key, &header, aad)
+    }
 
-        // Hardware-accelerated decryption
-        let plaintext = if self.use_hardware {
-            unsafe { self.decrypt_ni(cipher, nonce, &full_ciphertext, aad)? }
-        } else {
-            cipher.decrypt(nonce, full_ciphertext.as_slice())?
-        };
+    /// STREAM encryption under a raw 256-bit data key (no KDF, no salt)
+    pub fn encrypt_stream_with_key<R: Read, W: Write>(
+        &self,
+        reader: R,
+        writer: W,
+        key: &[u8; 32],
+        aad: &[u8],
+    ) -> Result<u64, AesError> {
+        self.seal_segments(reader, writer, key, Kdf::None, &[], aad)
+    }
 
-        Ok(plaintext)
+    /// STREAM decryption under a raw 256-bit data key
+    pub fn decrypt_stream_with_key<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        writer: W,
+        key: &[u8; 32],
+        aad: &[u8],
+    ) -> Result<u64, AesError> {
+        let header = CipherHeader::read(&mut reader, LegacyLayout::KEYED_STREAM)?;
+        header.check_aad(aad)?;
+        if header.kdf != Kdf::None {
+            return Err(AesError::Format("sealed under a password, not a raw key".into()));
+        }
+        self.open_segments(reader, writer, key, &header, aad)
     }
 
-    /// Key derivation with Argon2id
-    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<[u8; 32], AesError> {
-        let config = Config {
-            variant: Variant::Argon2id,
-            version: Version::Version13,
-            mem_cost: 19456,  // 19 MB
-            time_cost: 3,
-            lanes: 4,
-            thread_mode: ThreadMode::Parallel,
-            secret: &[],
-            ad: &[],
-            hash_length: 32,
-        };
+    fn seal_segments<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        mut writer: W,
+        key: &[u8; 32],
+        kdf: Kdf,
+        salt: &[u8],
+        aad: &[u8],
+    ) -> Result<u64, AesError> {
+        let aead = aead(self.cipher);
+        let mut nonce_prefix = vec![0u8; aead.nonce_len() - STREAM_NONCE_SUFFIX_LEN];
+        OsRng.fill_bytes(&mut nonce_prefix);
+        let header = CipherHeader::new(self.cipher, kdf, salt, &nonce_prefix, aad).to_bytes();
+        writer.write_all(&header)?;
+
+        let mut segments = Segments::new(aead, key, &nonce_prefix, &header);
+        let mut current = vec![0u8; STREAM_CHUNK_SIZE];
+        let mut next = vec![0u8; STREAM_CHUNK_SIZE];
+        let mut current_len = read_full(&mut reader, &mut current)?;
+        let mut total = 0u64;
+
+        loop {
+            // Look ahead one chunk so the final segment can be flagged
+            let next_len = if current_len == STREAM_CHUNK_SIZE {
+                read_full(&mut reader, &mut next)?
+            } else {
+                0
+            };
+            total += current_len as u64;
+
+            let last = next_len == 0;
+            writer.write_all(&segments.seal(&current[..current_len], last)?)?;
+            if last {
+                break;
+            }
+
+            std::mem::swap(&mut current, &mut next);
+            current_len = next_len;
+        }
 
-        let key = argon2::hash_encoded(password.as_bytes(), salt, &config)
-            .map_err(|_| AesError::KeyDerivationFailed)?;
-        
-        hex::decode(key)
-            .map_err(|_| AesError::KeyDecodingFailed)?
-            .try_into()
-            .map_err(|_| AesError::InvalidKeyLength)
+        writer.flush()?;
+        Ok(total)
+    }
+
+    fn open_segments<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        mut writer: W,
+        key: &[u8; 32],
+        header: &CipherHeader,
+        aad: &[u8],
+    ) -> Result<u64, AesError> {
+        let aead = aead(header.cipher);
+        if header.nonce.len() + STREAM_NONCE_SUFFIX_LEN != aead.nonce_len() {
+            return Err(AesError::InvalidLength);
+        }
+        let aad = header.aead_aad(aad);
+        let mut segments = Segments::new(aead, key, &header.nonce, &aad);
+
+        let segment_len = STREAM_CHUNK_SIZE + TAG_LEN;
+        let mut current = vec![0u8; segment_len];
+        let mut next = vec![0u8; segment_len];
+        let mut current_len = read_full(&mut reader, &mut current)?;
+        if current_len < TAG_LEN {
+            return Err(AesError::InvalidLength);
+        }
+        let mut total = 0u64;
+
+        loop {
+            let next_len = if current_len == segment_len {
+                read_full(&mut reader, &mut next)?
+            } else {
+                0
+            };
+
+            let plaintext = segments.open(&current[..current_len], next_len == 0)?;
+            writer.write_all(&plaintext)?;
+            total += plaintext.len() as u64;
+
+            if next_len == 0 {
+                break;
+            }
+            if next_len < TAG_LEN {
+                return Err(AesError::InvalidLength);
+            }
+            std::mem::swap(&mut current, &mut next);
+            current_len = next_len;
+        }
+
+        writer.flush()?;
+        Ok(total)
+    }
+
+    /// Seal a short secret (e.g. a data key) under a raw key.
+    ///
+    /// Output format: [nonce (12B)] [ciphertext] [tag (16B)]
+    pub fn seal_with_key(&self, key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+        let cipher = self.init_cipher(key)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad })
+            .map_err(|_| AesError::EncryptionFailed)?;
+
+        let mut output = Vec::with_capacity(12 + ciphertext.len());
+        output.extend_from_slice(nonce.as_slice());
+        output.extend(ciphertext);
+        Ok(output)
+    }
+
+    /// Open a secret sealed by `seal_with_key`
+    pub fn open_with_key(&self, key: &[u8; 32], sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+        if sealed.len() < 12 + TAG_LEN {
+            return Err(AesError::InvalidLength);
+        }
+        let cipher = self.init_cipher(key)?;
+        let nonce = Nonce::from_slice(&sealed[..12]);
+        cipher
+            .decrypt(nonce, Payload { msg: &sealed[12..], aad })
+            .map_err(|_| AesError::DecryptionFailed)
+    }
+
+    /// Key derivation with Argon2id
+    fn derive_key(&self, password: &str, salt: &[u8], kdf: &KdfParams) -> Result<[u8; 32], AesError> {
+        derive_key(password, salt, kdf)
     }
 
     /// Initialize cipher with key
@@ -112,7 +380,80 @@ impl Aes256GcmProvider {
         let key = aes_gcm::Key::<Aes256Gcm>::from_slice(key);
         Ok(Aes256Gcm::new(key))
     }
+}
 
+/// STREAM segment nonces: the prefix, a BE32 counter, then the last-block flag
+struct Segments<'a> {
+    aead: &'static dyn AeadProvider,
+    key: &'a [u8; 32],
+    nonce: Vec<u8>,
+    aad: &'a [u8],
+    position: u32,
+}
+
+impl<'a> Segments<'a> {
+    fn new(aead: &'static dyn AeadProvider, key: &'a [u8; 32], prefix: &[u8], aad: &'a [u8]) -> Self {
+        let mut nonce = prefix.to_vec();
+        nonce.resize(aead.nonce_len(), 0);
+        Self { aead, key, nonce, aad, position: 0 }
+    }
+
+    fn seal(&mut self, chunk: &[u8], last: bool) -> Result<Vec<u8>, AesError> {
+        self.advance(last).ok_or(AesError::EncryptionFailed)?;
+        self.aead.seal(self.key, &self.nonce, chunk, self.aad).map_err(|_| AesError::EncryptionFailed)
+    }
+
+    fn open(&mut self, segment: &[u8], last: bool) -> Result<Vec<u8>, AesError> {
+        self.advance(last).ok_or(AesError::DecryptionFailed)?;
+        self.aead.open(self.key, &self.nonce, segment, self.aad).map_err(|_| AesError::DecryptionFailed)
+    }
+
+    /// Nonce for the next segment; `None` once the counter is spent
+    fn advance(&mut self, last: bool) -> Option<()> {
+        let suffix = self.nonce.len() - STREAM_NONCE_SUFFIX_LEN;
+        self.nonce[suffix..suffix + 4].copy_from_slice(&self.position.to_be_bytes());
+        self.nonce[suffix + 4] = last as u8;
+        self.position = self.position.checked_add(1)?;
+        Some(())
+    }
+}
+
+/// AES-256-GCM, through AES-NI where the CPU has it
+pub struct AesGcmAead;
+
+impl AeadProvider for AesGcmAead {
+    fn id(&self) -> CipherId {
+        CipherId::Aes256Gcm
+    }
+
+    fn nonce_len(&self) -> usize {
+        12
+    }
+
+    fn seal(&self, key: &[u8; 32], nonce: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+        check_nonce(self, nonce)?;
+        let cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(key));
+        let nonce = Nonce::from_slice(nonce);
+        if is_aesni_supported() {
+            unsafe { self.encrypt_ni(cipher, *nonce, plaintext, aad) }
+        } else {
+            cipher.encrypt(nonce, Payload { msg: plaintext, aad }).map_err(|_| AesError::EncryptionFailed)
+        }
+    }
+
+    fn open(&self, key: &[u8; 32], nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+        check_nonce(self, nonce)?;
+        let cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(key));
+        let nonce = Nonce::from_slice(nonce);
+        if is_aesni_supported() {
+            unsafe { self.decrypt_ni(cipher, nonce, ciphertext, aad) }
+        } else {
+            cipher.decrypt(nonce, Payload { msg: ciphertext, aad }).map_err(|_| AesError::DecryptionFailed)
+        }
+    }
+}
+
+impl AesGcmAead {
     /// Hardware-accelerated encryption using AES-NI
     unsafe fn encrypt_ni(
         &self,
@@ -176,6 +517,43 @@ impl Aes256GcmProvider {
     }
 }
 
+fn derive_key(password: &str, salt: &[u8], kdf: &KdfParams) -> Result<[u8; 32], AesError> {
+    if !kdf.is_valid() {
+        return Err(AesError::Format(format!("Argon2 costs {kdf:?}")));
+    }
+    let config = Config {
+        variant: Variant::Argon2id,
+        version: Version::Version13,
+        mem_cost: kdf.mem_cost,
+        time_cost: kdf.time_cost,
+        lanes: kdf.lanes,
+        thread_mode: ThreadMode::Parallel,
+        secret: &[],
+        ad: &[],
+        hash_length: 32,
+    };
+
+    let mut key = argon2::hash_raw(password.as_bytes(), salt, &config)
+        .map_err(|_| AesError::KeyDerivationFailed)?;
+    let derived = key.as_slice().try_into().map_err(|_| AesError::InvalidKeyLength);
+    key.zeroize();
+    derived
+}
+
+/// Fill `buf` from `reader`, returning fewer bytes only at end of stream
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, AesError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
+}
+
 /// Check AES-NI support at runtime
 fn is_aesni_supported() -> bool {
     static HAS_AESNI: AtomicBool = AtomicBool::new(false);
@@ -214,6 +592,12 @@ pub enum AesError {
     KeyDecodingFailed,
     #[error("Invalid key length")]
     InvalidKeyLength,
+    #[error("Unsupported or malformed header: {0}")]
+    Format(String),
+    #[error("Encrypted for other associated data")]
+    AadMismatch,
+    #[error("Stream I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 // FFI bindings for AES-NI acceleration (Linux/macOS x86_64)
@@ -245,6 +629,7 @@ extern "C" {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aes_gcm::aead::stream::EncryptorBE32;
 
     #[test]
     fn test_full_encryption_cycle() {
@@ -267,13 +652,129 @@ mod tests {
     fn test_tamper_protection() {
         let aes = Aes256GcmProvider::new();
         let plaintext = b"Critical security data";
-        let mut ciphertext = aes.encrypt(plaintext, "password", b"aad")
+        let sealed = aes.encrypt(plaintext, "password", b"aad")
             .expect("Encryption failed");
+        let mut body = &sealed[..];
+        let header = CipherHeader::read(&mut body, LegacyLayout::SEALED).expect("Header parse failed");
+        let header_len = sealed.len() - body.len();
 
-        // Tamper with ciphertext
-        ciphertext[30] ^= 0x01;
+        // Tamper with the sealed data past the header
+        let mut ciphertext = sealed.clone();
+        ciphertext[header_len] ^= 0x01;
+        let result = aes.decrypt(&ciphertext, "password", b"aad");
+        assert!(matches!(result, Err(AesError::DecryptionFailed)));
 
+        // The header is authenticated too: flip the nonce's last byte
+        let mut ciphertext = sealed;
+        ciphertext[header_len - header.aad_hash.len() - 1] ^= 0x01;
         let result = aes.decrypt(&ciphertext, "password", b"aad");
         assert!(matches!(result, Err(AesError::DecryptionFailed)));
     }
+
+    #[test]
+    fn test_stream_roundtrip_multi_chunk() {
+        let aes = Aes256GcmProvider::new();
+        let plaintext: Vec<u8> = (0..STREAM_CHUNK_SIZE * 2 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let mut ciphertext = Vec::new();
+        let written = aes
+            .encrypt_stream(&plaintext[..], &mut ciphertext, "password", b"model")
+            .expect("Stream encryption failed");
+        assert_eq!(written, plaintext.len() as u64);
+
+        let mut decrypted = Vec::new();
+        aes.decrypt_stream(&ciphertext[..], &mut decrypted, "password", b"model")
+            .expect("Stream decryption failed");
+        assert_eq!(plaintext, decrypted);
+    }
+
+    #[test]
+    fn test_stream_truncation_detected() {
+        let aes = Aes256GcmProvider::new();
+        let plaintext = vec![7u8; STREAM_CHUNK_SIZE * 2];
+
+        let mut ciphertext = Vec::new();
+        aes.encrypt_stream(&plaintext[..], &mut ciphertext, "password", b"")
+            .expect("Stream encryption failed");
+
+        // Drop the final segment; the remaining one is not flagged as last
+        let header = CipherHeader::read(&mut &ciphertext[..], LegacyLayout::STREAM).unwrap();
+        ciphertext.truncate(header.to_bytes().len() + STREAM_CHUNK_SIZE + TAG_LEN);
+
+        let result = aes.decrypt_stream(&ciphertext[..], &mut Vec::new(), "password", b"");
+        assert!(matches!(result, Err(AesError::DecryptionFailed)));
+    }
+
+    #[test]
+    fn test_header_binds_costs_and_aad() {
+        let light = KdfParams { mem_cost: 1024, time_cost: 1, lanes: 1 };
+        let sealed = Aes256GcmProvider::new().with_kdf(light).encrypt(b"weights", "pw", b"aad").unwrap();
+        let header = CipherHeader::read(&mut &sealed[..], LegacyLayout::SEALED).unwrap();
+        assert_eq!(header.kdf, Kdf::Argon2id(light));
+
+        // Another provider's costs do not matter for reading, only for rekeying
+        let current = Aes256GcmProvider::new().with_kdf(KdfParams { time_cost: 2, ..light });
+        assert_eq!(current.decrypt(&sealed, "pw", b"aad").unwrap(), b"weights");
+        assert!(current.needs_rekey(&sealed));
+        assert!(!Aes256GcmProvider::new().with_kdf(light).needs_rekey(&sealed));
+        assert!(matches!(current.decrypt(&sealed, "pw", b"other"), Err(AesError::AadMismatch)));
+
+        let mut stream = Vec::new();
+        current.encrypt_stream_with_key(&b"blob"[..], &mut stream, &[5; 32], b"aad").unwrap();
+        let result = current.decrypt_stream(&stream[..], &mut Vec::new(), "pw", b"aad");
+        assert!(matches!(result, Err(AesError::Format(_))), "a keyed stream has no password");
+    }
+
+    #[test]
+    fn test_headerless_artifacts_still_open() {
+        let (salt, nonce) = ([3u8; 16], [4u8; 12]);
+        let aes = Aes256GcmProvider::new().with_kdf(KdfParams { mem_cost: 1024, time_cost: 1, lanes: 1 });
+        let cipher = aes.init_cipher(&derive_key("pw", &salt, &KdfParams::LEGACY).unwrap()).unwrap();
+
+        let sealed = cipher.encrypt(Nonce::from_slice(&nonce), Payload { msg: b"old", aad: b"aad" }).unwrap();
+        let sealed = [&salt[..], &nonce, &sealed].concat();
+        assert_eq!(aes.decrypt(&sealed, "pw", b"aad").unwrap(), b"old");
+        assert!(aes.needs_rekey(&sealed));
+
+        let prefix = [6u8; 12 - STREAM_NONCE_SUFFIX_LEN];
+        let segment = EncryptorBE32::from_aead(cipher, (&prefix).into())
+            .encrypt_last(Payload { msg: &b"old"[..], aad: b"aad" })
+            .unwrap();
+        let stream = [&salt[..], &prefix, &segment].concat();
+        let mut plain = Vec::new();
+        aes.decrypt_stream(&stream[..], &mut plain, "pw", b"aad").unwrap();
+        assert_eq!(plain, b"old");
+    }
+
+    #[test]
+    fn test_xchacha_roundtrip() {
+        let kdf = KdfParams { mem_cost: 1024, time_cost: 1, lanes: 1 };
+        let chacha = Aes256GcmProvider::new().with_kdf(kdf).with_cipher(CipherId::XChaCha20Poly1305);
+        let aes = Aes256GcmProvider::new().with_kdf(kdf).with_cipher(CipherId::Aes256Gcm);
+
+        let sealed = chacha.encrypt(b"weights", "pw", b"aad").unwrap();
+        assert_eq!(aes.decrypt(&sealed, "pw", b"aad").unwrap(), b"weights", "the header names the cipher");
+        assert!(aes.needs_rekey(&sealed));
+        assert!(!chacha.needs_rekey(&sealed));
+
+        let plaintext: Vec<u8> = (0..STREAM_CHUNK_SIZE + 7).map(|i| (i % 251) as u8).collect();
+        let mut stream = Vec::new();
+        chacha.encrypt_stream_with_key(&plaintext[..], &mut stream, &[9; 32], b"aad").unwrap();
+        let mut decrypted = Vec::new();
+        aes.decrypt_stream_with_key(&stream[..], &mut decrypted, &[9; 32], b"aad").unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        stream.truncate(stream.len() - 7 - TAG_LEN);
+        let result = aes.decrypt_stream_with_key(&stream[..], &mut Vec::new(), &[9; 32], b"aad");
+        assert!(result.is_err(), "truncation is detected");
+    }
+
+    #[test]
+    fn test_calibration_respects_memory_cap() {
+        let (params, _) = KdfParams::calibrate(Duration::from_millis(20), 16 * 1024).unwrap();
+        assert!(params.is_valid());
+        assert!((MIN_CALIBRATED_MEM_COST..=16 * 1024).contains(&params.mem_cost));
+    }
 }
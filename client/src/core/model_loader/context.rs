@@ -0,0 +1,238 @@
+// client/src/core/model_loader/context.rs
+
+use super::{
+    aes::{Aes256GcmProvider, AesError},
+    envelope::{DataKey, EnvelopeError, MasterKey, WrappedDataKey},
+    pkcs11::{HsmError, Pkcs11Hsm},
+    recipient::{KemId, RecipientKey, RecipientSecret, SharedDataKey},
+    reencrypt::{Capsule, CapsuleFrag},
+};
+use crate::config::HsmConfig;
+use blake3::{Hash, Hasher};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read},
+    path::{Path, PathBuf},
+};
+
+/// Associated data binding ciphertexts to their artifact type
+const MODEL_AAD: &[u8] = b"scoria/model/v1";
+const DATA_AAD: &[u8] = b"scoria/data/v1";
+
+/// Client-side cryptographic context for model and dataset protection
+pub struct CryptoContext {
+    password: String,
+    aes: Aes256GcmProvider,
+    hardware: HardwareSecurity,
+    master_key: Option<MasterKey>,
+    hsm: Option<Pkcs11Hsm>,
+    recipient: Option<RecipientSecret>,
+}
+
+impl CryptoContext {
+    pub fn new(password: &str, hardware: HardwareSecurity) -> Self {
+        Self {
+            password: password.to_string(),
+            aes: Aes256GcmProvider::new(),
+            hardware,
+            master_key: None,
+            hsm: None,
+            recipient: None,
+        }
+    }
+
+    /// Enable envelope encryption with a hardware-sealed master key
+    pub fn with_master_key(mut self, key_id: &str, version: u32, sealed: &[u8]) -> Result<Self, EnvelopeError> {
+        self.master_key = Some(MasterKey::unseal(&self.hardware, key_id, version, sealed)?);
+        Ok(self)
+    }
+
+    /// Wrap data keys on a PKCS#11 token; takes precedence over `with_master_key`
+    pub fn with_hsm(mut self, config: &HsmConfig) -> Result<Self, HsmError> {
+        self.hsm = Some(Pkcs11Hsm::open(config)?);
+        Ok(self)
+    }
+
+    /// Open data keys shared or re-encrypted for this node, and issue
+    /// re-encryption keys for models it owns
+    pub fn with_recipient(mut self, recipient: RecipientSecret) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    pub fn hsm(&self) -> Option<&Pkcs11Hsm> {
+        self.hsm.as_ref()
+    }
+
+    pub fn master_key(&self) -> Option<&MasterKey> {
+        self.master_key.as_ref()
+    }
+
+    pub fn aes(&self) -> &Aes256GcmProvider {
+        &self.aes
+    }
+
+    pub fn hardware(&self) -> &HardwareSecurity {
+        &self.hardware
+    }
+
+    /// Encrypt a model file in memory, returning ciphertext and plaintext hash
+    pub fn encrypt_model(&self, model_path: &Path) -> Result<(Vec<u8>, Hash), AesError> {
+        let plaintext = std::fs::read(model_path)?;
+        let hash = blake3::hash(&plaintext);
+        let ciphertext = self.aes.encrypt(&plaintext, &self.password, MODEL_AAD)?;
+        Ok((ciphertext, hash))
+    }
+
+    /// Stream-encrypt a model file to `output` with bounded memory.
+    ///
+    /// The BLAKE3 hash of the plaintext is computed on the fly so multi-GB
+    /// models never need to be resident. When an HSM or master key is configured
+    /// a fresh data key encrypts the blob and is stored wrapped in a sidecar.
+    pub fn encrypt_model_stream(&self, model_path: &Path, output: &Path) -> Result<Hash, EnvelopeError> {
+        let mut reader = HashingReader::new(BufReader::new(File::open(model_path)?));
+        let writer = BufWriter::new(File::create(output)?);
+
+        match (&self.hsm, &self.master_key) {
+            (Some(hsm), _) => {
+                let dek = DataKey::generate();
+                self.aes
+                    .encrypt_stream_with_key(&mut reader, writer, dek.as_bytes(), MODEL_AAD)?;
+                hsm.wrap_data_key(&dek)?.store(output)?;
+            }
+            (None, Some(master)) => {
+                let dek = DataKey::generate();
+                self.aes
+                    .encrypt_stream_with_key(&mut reader, writer, dek.as_bytes(), MODEL_AAD)?;
+                master.wrap(&self.aes, &dek)?.store(output)?;
+            }
+            (None, None) => {
+                self.aes
+                    .encrypt_stream(&mut reader, writer, &self.password, MODEL_AAD)?;
+            }
+        }
+        Ok(reader.finalize())
+    }
+
+    /// Decrypt a model previously produced by `encrypt_model`
+    pub fn decrypt_model(&self, ciphertext: Vec<u8>) -> Result<Vec<u8>, AesError> {
+        self.aes.decrypt(&ciphertext, &self.password, MODEL_AAD)
+    }
+
+    /// Stream-decrypt a model produced by `encrypt_model_stream`, or one whose
+    /// data key was shared or re-encrypted for this node's recipient key
+    pub fn decrypt_model_stream(&self, input: &Path, output: &Path) -> Result<u64, EnvelopeError> {
+        let reader = BufReader::new(File::open(input)?);
+        let writer = BufWriter::new(File::create(output)?);
+
+        match self.data_key(input)? {
+            Some(dek) => Ok(self
+                .aes
+                .decrypt_stream_with_key(reader, writer, dek.as_bytes(), MODEL_AAD)?),
+            None => Ok(self.aes.decrypt_stream(reader, writer, &self.password, MODEL_AAD)?),
+        }
+    }
+
+    /// Wrap the data key of an enveloped model to an inference provider,
+    /// returning the share written beside the model
+    pub fn share_data_key(&self, model: &Path, recipient: &RecipientKey, kem: KemId) -> Result<PathBuf, EnvelopeError> {
+        let dek = self
+            .data_key(model)?
+            .ok_or(EnvelopeError::Share("model has no wrapped data key to share".into()))?;
+        SharedDataKey::wrap(&self.aes, &dek, recipient, kem)?.store(model)
+    }
+
+    /// As the owner, let `provider`'s recipient key open `model` through a
+    /// proxy, sealing the model's data key in a capsule on first use.
+    /// `sign` is the owner wallet's signature over the key's signing message.
+    /// Returns the re-encryption key to hand to the proxy.
+    pub fn reencryption_key(
+        &self,
+        model: &Path,
+        model_id: &str,
+        provider: &str,
+        recipient: &RecipientKey,
+        sign: impl FnOnce(&[u8]) -> [u8; 64],
+    ) -> Result<PathBuf, EnvelopeError> {
+        let owner = self
+            .recipient
+            .as_ref()
+            .ok_or(EnvelopeError::Share("security.recipient_seed is not set".into()))?;
+        if !Capsule::sidecar_path(model).exists() {
+            let dek = self
+                .data_key(model)?
+                .ok_or(EnvelopeError::Share("model has no wrapped data key to delegate".into()))?;
+            Capsule::seal(&self.aes, &dek, model_id, owner.public_key())?.store(model)?;
+        }
+        let mut kfrag = owner.reencryption_key(model_id, provider, recipient)?;
+        kfrag.signature = sign(&kfrag.signing_message()).to_vec();
+        kfrag.store(model)
+    }
+
+    /// Data key of an enveloped model, or `None` for password-encrypted ones
+    fn data_key(&self, input: &Path) -> Result<Option<DataKey>, EnvelopeError> {
+        let sidecar = WrappedDataKey::sidecar_path(input);
+        let keyed = self.hsm.is_some() || self.master_key.is_some();
+        let wrapped = if keyed && sidecar.exists() { Some(WrappedDataKey::load(input)?) } else { None };
+        // Sidecars from before an HSM was configured still open with the master key
+        Ok(match (wrapped, &self.hsm, &self.master_key) {
+            (Some(w), Some(hsm), _) if w.master_key == hsm.fingerprint() => Some(hsm.unwrap_data_key(&w)?),
+            (Some(w), _, Some(master)) => Some(master.unwrap(&self.aes, &w)?),
+            (Some(w), Some(hsm), None) => Some(hsm.unwrap_data_key(&w)?),
+            _ => self.recipient_data_key(input)?,
+        })
+    }
+
+    /// A share for our recipient key, else a capsule re-encrypted for it or
+    /// sealed by it
+    fn recipient_data_key(&self, input: &Path) -> Result<Option<DataKey>, EnvelopeError> {
+        let Some(recipient) = &self.recipient else { return Ok(None) };
+        let fingerprint = recipient.public_key().fingerprint();
+        if SharedDataKey::sidecar_path(input, &fingerprint).exists() {
+            return Ok(Some(recipient.open(&self.aes, &SharedDataKey::load(input, &fingerprint)?)?));
+        }
+        if !Capsule::sidecar_path(input).exists() {
+            return Ok(None);
+        }
+        let capsule = Capsule::load(input)?;
+        if CapsuleFrag::sidecar_path(input, &fingerprint).exists() {
+            let cfrag = CapsuleFrag::load(input, &fingerprint)?;
+            return Ok(Some(recipient.open_reencrypted(&self.aes, &capsule, &cfrag)?));
+        }
+        Ok(Some(recipient.open_capsule(&self.aes, &capsule)?))
+    }
+
+    /// Encrypt a sanitized dataset, returning ciphertext and plaintext hash
+    pub fn encrypt_data(&self, data: Vec<u8>) -> Result<(Vec<u8>, [u8; 32]), AesError> {
+        let hash = blake3::hash(&data);
+        let ciphertext = self.aes.encrypt(&data, &self.password, DATA_AAD)?;
+        Ok((ciphertext, *hash.as_bytes()))
+    }
+}
+
+/// Reader adapter that hashes everything passing through it
+struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R: Read> HashingReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(),
+        }
+    }
+
+    fn finalize(&self) -> Hash {
+        self.hasher.finalize()
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
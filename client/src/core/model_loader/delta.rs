@@ -0,0 +1,161 @@
+// client/src/core/model_loader/delta.rs
+
+//! Binary patches between model versions, so an update uploads only what
+//! changed. Patches are taken over plaintext and encrypted like full models.
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use thiserror::Error;
+use zstd::stream::raw::CParameter;
+
+pub const DELTA_VERSION: u8 = 1;
+/// `storage_uri` prefix of delta versions; the rest is the delta manifest's CID
+const URI_SCHEME: &str = "scoria-delta://";
+/// Deltas may chain back to a full upload at most this far
+pub const MAX_CHAIN: usize = 16;
+const ZSTD_LEVEL: i32 = 19;
+/// zstd's largest window on 64-bit targets; bases beyond 2 GiB are only partly referenced
+const ZSTD_MAX_WINDOW_LOG: u32 = 31;
+
+#[derive(Debug, Error)]
+pub enum DeltaError {
+    #[error("Patch failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid delta manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    #[error("Unsupported delta manifest version {0}")]
+    Unsupported(u8),
+    #[error("Delta base does not match version {expected}")]
+    BaseMismatch { expected: String },
+    #[error("Patched model does not match version {expected}")]
+    HashMismatch { expected: String },
+    #[error("Delta chain is longer than {MAX_CHAIN} versions")]
+    ChainTooLong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DeltaCodec {
+    /// Smallest patches for localized edits, such as a few fine-tuned layers
+    Bsdiff,
+    /// zstd with the base as a raw dictionary; much faster on large models
+    Zstd,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeltaManifest {
+    pub version: u8,
+    pub codec: DeltaCodec,
+    /// Storage URI of the version the patch applies to; may itself be a delta
+    pub base_uri: String,
+    #[serde(with = "hex::serde")]
+    pub base_hash: [u8; 32],
+    /// Storage URI of the encrypted patch
+    pub patch_uri: String,
+    /// blake3 of the patched plaintext, i.e. the new version's hash
+    #[serde(with = "hex::serde")]
+    pub target_hash: [u8; 32],
+}
+
+impl DeltaManifest {
+    pub fn parse(bytes: &[u8]) -> Result<Self, DeltaError> {
+        let manifest: Self = serde_json::from_slice(bytes)?;
+        if manifest.version != DELTA_VERSION {
+            return Err(DeltaError::Unsupported(manifest.version));
+        }
+        Ok(manifest)
+    }
+
+    pub fn storage_uri(manifest_cid: &str) -> String {
+        format!("{URI_SCHEME}{manifest_cid}")
+    }
+
+    /// Manifest CID of a delta version's `storage_uri`
+    pub fn cid_from_uri(storage_uri: &str) -> Option<&str> {
+        storage_uri.strip_prefix(URI_SCHEME)
+    }
+
+    /// Patch `base` and check both ends against the manifest
+    pub fn apply(&self, base: &[u8], patch: &[u8]) -> Result<Vec<u8>, DeltaError> {
+        if blake3::hash(base) != self.base_hash {
+            return Err(DeltaError::BaseMismatch { expected: hex::encode(self.base_hash) });
+        }
+        let target = apply(base, patch, self.codec)?;
+        if blake3::hash(&target) != self.target_hash {
+            return Err(DeltaError::HashMismatch { expected: hex::encode(self.target_hash) });
+        }
+        Ok(target)
+    }
+}
+
+/// Patch turning `base` into `target`
+pub fn diff(base: &[u8], target: &[u8], codec: DeltaCodec) -> Result<Vec<u8>, DeltaError> {
+    let mut patch = Vec::new();
+    match codec {
+        DeltaCodec::Bsdiff => bsdiff::diff(base, target, &mut patch)?,
+        DeltaCodec::Zstd => {
+            let mut compressor = zstd::bulk::Compressor::with_dictionary(ZSTD_LEVEL, base)?;
+            compressor.set_parameter(CParameter::EnableLongDistanceMatching(true))?;
+            compressor.set_parameter(CParameter::WindowLog(window_log(base.len().max(target.len()))))?;
+            patch = compressor.compress(target)?;
+        }
+    }
+    Ok(patch)
+}
+
+pub fn apply(base: &[u8], patch: &[u8], codec: DeltaCodec) -> Result<Vec<u8>, DeltaError> {
+    let mut target = Vec::new();
+    match codec {
+        DeltaCodec::Bsdiff => bsdiff::patch(base, &mut &patch[..], &mut target)?,
+        DeltaCodec::Zstd => {
+            let mut decoder = zstd::stream::read::Decoder::with_dictionary(patch, base)?;
+            decoder.window_log_max(ZSTD_MAX_WINDOW_LOG)?;
+            decoder.read_to_end(&mut target)?;
+        }
+    }
+    Ok(target)
+}
+
+/// Smallest window covering `len` bytes, so matches can reach the start of the base
+fn window_log(len: usize) -> u32 {
+    (usize::BITS - len.max(1).leading_zeros()).clamp(10, ZSTD_MAX_WINDOW_LOG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> (Vec<u8>, Vec<u8>) {
+        let base: Vec<u8> = (0..200_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        let mut target = base.clone();
+        // A "fine-tuned" layer in the middle
+        target[90_000..92_000].iter_mut().for_each(|b| *b = b.wrapping_add(7));
+        (base, target)
+    }
+
+    #[test]
+    fn test_codecs_round_trip_with_small_patches() {
+        let (base, target) = versions();
+        for codec in [DeltaCodec::Bsdiff, DeltaCodec::Zstd] {
+            let patch = diff(&base, &target, codec).unwrap();
+            assert!(patch.len() < target.len() / 10, "{codec:?} patch is {} bytes", patch.len());
+            assert_eq!(apply(&base, &patch, codec).unwrap(), target);
+        }
+    }
+
+    #[test]
+    fn test_manifest_rejects_wrong_base() {
+        let (base, target) = versions();
+        let manifest = DeltaManifest {
+            version: DELTA_VERSION,
+            codec: DeltaCodec::Zstd,
+            base_uri: "scoria-ec://bafybase".into(),
+            base_hash: *blake3::hash(&base).as_bytes(),
+            patch_uri: "scoria-ec://bafypatch".into(),
+            target_hash: *blake3::hash(&target).as_bytes(),
+        };
+        let patch = diff(&base, &target, DeltaCodec::Zstd).unwrap();
+        assert_eq!(manifest.apply(&base, &patch).unwrap(), target);
+        assert!(matches!(manifest.apply(&target, &patch), Err(DeltaError::BaseMismatch { .. })));
+    }
+}
@@ -0,0 +1,324 @@
+// client/src/core/model_loader/envelope.rs
+
+use super::{
+    aes::{Aes256GcmProvider, AesError},
+    pkcs11::HsmError,
+};
+use crate::core::audit::{AuditError, AuditLog};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Sidecar suffix holding the wrapped data key next to an encrypted model
+pub const KEY_SIDECAR_EXT: &str = "key.json";
+const ENVELOPE_FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug, Error)]
+pub enum EnvelopeError {
+    #[error("Cipher error: {0}")]
+    Cipher(#[from] AesError),
+    #[error("Hardware seal failed: {0}")]
+    Seal(String),
+    #[error("Hardware unseal failed: {0}")]
+    Unseal(String),
+    #[error("Key sidecar I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed key sidecar: {0}")]
+    Malformed(#[from] serde_json::Error),
+    #[error("Data key wrapped by master key {found}, expected {expected}")]
+    MasterKeyMismatch { expected: String, found: String },
+    #[error("Unsupported envelope format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Audit log error: {0}")]
+    Audit(#[from] AuditError),
+    #[error("HSM error: {0}")]
+    Hsm(#[from] HsmError),
+    #[error("Key share: {0}")]
+    Share(String),
+}
+
+/// Per-model 256-bit data encryption key, wiped on drop
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct DataKey([u8; 32]);
+
+impl DataKey {
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        Self(key)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub(crate) fn from_bytes(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+}
+
+/// Key-encryption key unsealed from HSM/TPM storage
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct MasterKey {
+    #[zeroize(skip)]
+    key_id: String,
+    #[zeroize(skip)]
+    version: u32,
+    material: [u8; 32],
+}
+
+impl MasterKey {
+    /// Unseal a master key blob through the hardware security backend
+    pub fn unseal(
+        hardware: &HardwareSecurity,
+        key_id: &str,
+        version: u32,
+        sealed: &[u8],
+    ) -> Result<Self, EnvelopeError> {
+        let material: [u8; 32] = hardware
+            .unseal(sealed)
+            .map_err(|e| EnvelopeError::Unseal(e.to_string()))?
+            .as_slice()
+            .try_into()
+            .map_err(|_| EnvelopeError::Unseal("master key must be 32 bytes".into()))?;
+
+        Ok(Self {
+            key_id: key_id.to_string(),
+            version,
+            material,
+        })
+    }
+
+    /// Generate a master key and seal it through the hardware security backend,
+    /// for `security.master_key.sealed_path`; the key itself never leaves memory
+    pub fn generate_sealed(hardware: &HardwareSecurity) -> Result<Vec<u8>, EnvelopeError> {
+        let mut material = [0u8; 32];
+        OsRng.fill_bytes(&mut material);
+        let sealed = hardware.seal(&material).map_err(|e| EnvelopeError::Seal(e.to_string()));
+        material.zeroize();
+        sealed
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Identifier recorded in sidecars, e.g. `scoria-master:3`
+    pub fn fingerprint(&self) -> String {
+        format!("{}:{}", self.key_id, self.version)
+    }
+
+    fn wrap_aad(&self) -> Vec<u8> {
+        format!("scoria/dek/v{}/{}", ENVELOPE_FORMAT_VERSION, self.fingerprint()).into_bytes()
+    }
+
+    pub fn wrap(&self, aes: &Aes256GcmProvider, dek: &DataKey) -> Result<WrappedDataKey, EnvelopeError> {
+        let wrapped = aes.seal_with_key(&self.material, dek.as_bytes(), &self.wrap_aad())?;
+        Ok(WrappedDataKey::new(self.fingerprint(), wrapped))
+    }
+
+    pub fn unwrap(&self, aes: &Aes256GcmProvider, wrapped: &WrappedDataKey) -> Result<DataKey, EnvelopeError> {
+        if wrapped.format != ENVELOPE_FORMAT_VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(wrapped.format));
+        }
+        if wrapped.master_key != self.fingerprint() {
+            return Err(EnvelopeError::MasterKeyMismatch {
+                expected: self.fingerprint(),
+                found: wrapped.master_key.clone(),
+            });
+        }
+
+        let mut plain = aes.open_with_key(&self.material, &wrapped.wrapped_key, &self.wrap_aad())?;
+        let key: [u8; 32] = plain
+            .as_slice()
+            .try_into()
+            .map_err(|_| AesError::InvalidKeyLength)?;
+        plain.zeroize();
+        Ok(DataKey(key))
+    }
+}
+
+/// Wrapped data key persisted beside the encrypted model blob
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedDataKey {
+    pub format: u8,
+    pub master_key: String,
+    #[serde(with = "hex::serde")]
+    pub wrapped_key: Vec<u8>,
+    pub created_at: u64,
+    pub rotated_at: Option<u64>,
+}
+
+impl WrappedDataKey {
+    pub fn new(master_key: String, wrapped_key: Vec<u8>) -> Self {
+        Self {
+            format: ENVELOPE_FORMAT_VERSION,
+            master_key,
+            wrapped_key,
+            created_at: unix_now(),
+            rotated_at: None,
+        }
+    }
+
+    pub fn sidecar_path(model_path: &Path) -> PathBuf {
+        let mut name = model_path.as_os_str().to_owned();
+        name.push(".");
+        name.push(KEY_SIDECAR_EXT);
+        PathBuf::from(name)
+    }
+
+    pub fn load(model_path: &Path) -> Result<Self, EnvelopeError> {
+        let raw = fs::read(Self::sidecar_path(model_path))?;
+        Ok(serde_json::from_slice(&raw)?)
+    }
+
+    /// Write atomically so a crash mid-rotation never leaves a torn sidecar
+    pub fn store(&self, model_path: &Path) -> Result<(), EnvelopeError> {
+        let path = Self::sidecar_path(model_path);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyRotationEvent {
+    pub model: PathBuf,
+    pub from_master_key: String,
+    pub to_master_key: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct RotationReport {
+    pub rotated: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Re-wrap every data key under `models_dir` from `current` to `next`.
+///
+/// Model blobs are never touched: only the sidecar is rewritten, and each
+/// successful re-wrap is recorded in the audit log before moving on. A sidecar
+/// that cannot be read or re-wrapped lands in `failed` without stopping the run.
+pub fn rotate_master_key(
+    aes: &Aes256GcmProvider,
+    models_dir: &Path,
+    current: &MasterKey,
+    next: &MasterKey,
+    audit: &mut AuditLog,
+) -> Result<RotationReport, EnvelopeError> {
+    let mut report = RotationReport::default();
+    let suffix = format!(".{}", KEY_SIDECAR_EXT);
+
+    for entry in fs::read_dir(models_dir)? {
+        let sidecar = entry?.path();
+        let Some(model_path) = sidecar
+            .to_str()
+            .and_then(|s| s.strip_suffix(&suffix))
+            .map(PathBuf::from)
+        else {
+            continue;
+        };
+
+        let mut wrapped = match WrappedDataKey::load(&model_path) {
+            Ok(wrapped) => wrapped,
+            Err(e) => {
+                report.failed.push((model_path, e.to_string()));
+                continue;
+            }
+        };
+        if wrapped.master_key == next.fingerprint() {
+            report.skipped.push(model_path);
+            continue;
+        }
+
+        let rewrapped = current
+            .unwrap(aes, &wrapped)
+            .and_then(|dek| next.wrap(aes, &dek));
+
+        match rewrapped {
+            Ok(new) => {
+                wrapped.master_key = new.master_key;
+                wrapped.wrapped_key = new.wrapped_key;
+                wrapped.rotated_at = Some(unix_now());
+                wrapped.store(&model_path)?;
+
+                audit.record(
+                    "key_rotation",
+                    &KeyRotationEvent {
+                        model: model_path.clone(),
+                        from_master_key: current.fingerprint(),
+                        to_master_key: next.fingerprint(),
+                    },
+                )?;
+                report.rotated.push(model_path);
+            }
+            Err(e) => report.failed.push((model_path, e.to_string())),
+        }
+    }
+
+    Ok(report)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master(version: u32, byte: u8) -> MasterKey {
+        MasterKey {
+            key_id: "test-master".into(),
+            version,
+            material: [byte; 32],
+        }
+    }
+
+    #[test]
+    fn test_wrap_unwrap_roundtrip() {
+        let aes = Aes256GcmProvider::new();
+        let kek = master(1, 0x11);
+        let dek = DataKey::generate();
+
+        let wrapped = kek.wrap(&aes, &dek).unwrap();
+        let unwrapped = kek.unwrap(&aes, &wrapped).unwrap();
+        assert_eq!(dek.as_bytes(), unwrapped.as_bytes());
+    }
+
+    #[test]
+    fn test_rotation_preserves_data_key() {
+        let aes = Aes256GcmProvider::new();
+        let dir = tempfile::tempdir().unwrap();
+        let model = dir.path().join("model.enc");
+        fs::write(&model, b"ciphertext").unwrap();
+
+        let old = master(1, 0x11);
+        let new = master(2, 0x22);
+        let dek = DataKey::generate();
+        old.wrap(&aes, &dek).unwrap().store(&model).unwrap();
+
+        let mut audit = AuditLog::open(dir.path().join("audit/keys.jsonl")).unwrap();
+        let report = rotate_master_key(&aes, dir.path(), &old, &new, &mut audit).unwrap();
+        assert_eq!(report.rotated, vec![model.clone()]);
+
+        let wrapped = WrappedDataKey::load(&model).unwrap();
+        assert!(matches!(
+            old.unwrap(&aes, &wrapped),
+            Err(EnvelopeError::MasterKeyMismatch { .. })
+        ));
+        assert_eq!(new.unwrap(&aes, &wrapped).unwrap().as_bytes(), dek.as_bytes());
+        assert_eq!(fs::read(&model).unwrap(), b"ciphertext");
+    }
+}
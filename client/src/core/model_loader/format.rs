@@ -0,0 +1,285 @@
+// client/src/core/model_loader/format.rs
+
+//! Versioned header in front of everything `Aes256GcmProvider` encrypts:
+//! magic, format version, header length, cipher, key derivation, salt,
+//! nonce and a BLAKE3 hash of the associated data. The encoded header is the
+//! AEAD associated data, so none of it can be changed undetected, and a new
+//! cipher only needs a new `CipherId`. Older artifacts still parse: the
+//! headerless layout and the costs-only header (version 1).
+
+use super::aes::{AesError, KdfParams};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+pub const MAGIC: [u8; 4] = *b"SKDF";
+pub const FORMAT_VERSION: u8 = 2;
+/// Salt length of headerless and version 1 artifacts
+const LEGACY_SALT_LEN: usize = 16;
+const KDF_NONE: u8 = 0;
+const KDF_ARGON2ID: u8 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CipherId {
+    #[default]
+    #[serde(rename = "aes-256-gcm")]
+    Aes256Gcm = 1,
+    #[serde(rename = "xchacha20-poly1305")]
+    XChaCha20Poly1305 = 2,
+}
+
+impl CipherId {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Aes256Gcm => "aes-256-gcm",
+            Self::XChaCha20Poly1305 => "xchacha20-poly1305",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Aes256Gcm, Self::XChaCha20Poly1305].into_iter().find(|id| id.name() == name)
+    }
+}
+
+impl TryFrom<u8> for CipherId {
+    type Error = AesError;
+
+    fn try_from(id: u8) -> Result<Self, AesError> {
+        match id {
+            1 => Ok(Self::Aes256Gcm),
+            2 => Ok(Self::XChaCha20Poly1305),
+            _ => Err(AesError::Format(format!("unknown cipher {id}"))),
+        }
+    }
+}
+
+/// Where the key came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kdf", rename_all = "kebab-case")]
+pub enum Kdf {
+    /// A raw 256-bit key, e.g. a model data key
+    None,
+    /// A password, stretched with these costs
+    Argon2id(KdfParams),
+}
+
+/// Where salt and nonce sit in artifacts from before the header
+#[derive(Debug, Clone, Copy)]
+pub struct LegacyLayout {
+    salted: bool,
+    nonce_len: usize,
+}
+
+impl LegacyLayout {
+    /// `encrypt`: salt, 96-bit nonce
+    pub const SEALED: Self = Self { salted: true, nonce_len: 12 };
+    /// `encrypt_stream`: salt, STREAM nonce prefix
+    pub const STREAM: Self = Self { salted: true, nonce_len: 7 };
+    /// `encrypt_stream_with_key`: STREAM nonce prefix only
+    pub const KEYED_STREAM: Self = Self { salted: false, nonce_len: 7 };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherHeader {
+    /// 0 for headerless artifacts
+    pub version: u8,
+    pub cipher: CipherId,
+    #[serde(flatten)]
+    pub kdf: Kdf,
+    #[serde(with = "hex::serde")]
+    pub salt: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub nonce: Vec<u8>,
+    /// BLAKE3 of the associated data; empty before version 2
+    #[serde(default, with = "hex::serde")]
+    pub aad_hash: Vec<u8>,
+}
+
+impl CipherHeader {
+    /// Current-format header binding `aad`
+    pub fn new(cipher: CipherId, kdf: Kdf, salt: &[u8], nonce: &[u8], aad: &[u8]) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            cipher,
+            kdf,
+            salt: salt.to_vec(),
+            nonce: nonce.to_vec(),
+            aad_hash: blake3::hash(aad).as_bytes().to_vec(),
+        }
+    }
+
+    /// Magic, version, header length (u16), cipher, KDF id and costs, then
+    /// length-prefixed salt and nonce, then the associated data hash.
+    /// Integers are little-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = vec![self.cipher as u8];
+        match self.kdf {
+            Kdf::None => body.push(KDF_NONE),
+            Kdf::Argon2id(params) => {
+                body.push(KDF_ARGON2ID);
+                for value in [params.mem_cost, params.time_cost, params.lanes] {
+                    body.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        for field in [&self.salt, &self.nonce] {
+            body.push(field.len() as u8);
+            body.extend_from_slice(field);
+        }
+        body.extend_from_slice(&self.aad_hash);
+
+        let len = (MAGIC.len() + 1 + 2 + body.len()) as u16;
+        let mut header = Vec::with_capacity(len as usize);
+        header.extend_from_slice(&MAGIC);
+        header.push(FORMAT_VERSION);
+        header.extend_from_slice(&len.to_le_bytes());
+        header.extend(body);
+        header
+    }
+
+    /// Read the header off the front of `reader`, leaving it at the
+    /// ciphertext. Artifacts without one are read as `legacy` lays them out.
+    pub fn read<R: Read>(reader: &mut R, legacy: LegacyLayout) -> Result<Self, AesError> {
+        let mut magic = [0u8; MAGIC.len()];
+        read_exact(reader, &mut magic)?;
+        if magic != MAGIC {
+            // Headerless: those four bytes already belong to the salt or nonce
+            let mut reader = (&magic[..]).chain(reader);
+            let salt = if legacy.salted { read_vec(&mut reader, LEGACY_SALT_LEN)? } else { Vec::new() };
+            let kdf = if legacy.salted { Kdf::Argon2id(KdfParams::LEGACY) } else { Kdf::None };
+            return Ok(Self::legacy(0, kdf, salt, read_vec(&mut reader, legacy.nonce_len)?));
+        }
+
+        match read_vec(reader, 1)?[0] {
+            1 => {
+                let kdf = Kdf::Argon2id(read_costs(reader)?);
+                let salt = read_vec(reader, LEGACY_SALT_LEN)?;
+                Ok(Self::legacy(1, kdf, salt, read_vec(reader, legacy.nonce_len)?))
+            }
+            FORMAT_VERSION => {
+                let len = u16::from_le_bytes(read_vec(reader, 2)?.try_into().expect("2 bytes")) as usize;
+                let rest = read_vec(reader, len.checked_sub(MAGIC.len() + 3).ok_or(AesError::InvalidLength)?)?;
+                let header = Self::decode(&rest)?;
+                if header.to_bytes().len() != len {
+                    return Err(AesError::Format(format!("header length {len} does not match its fields")));
+                }
+                Ok(header)
+            }
+            version => Err(AesError::Format(format!("format version {version} is newer than this client"))),
+        }
+    }
+
+    /// Fails fast, before any key derivation, when `aad` is not what the
+    /// artifact was sealed for
+    pub fn check_aad(&self, aad: &[u8]) -> Result<(), AesError> {
+        if self.version >= 2 && self.aad_hash != blake3::hash(aad).as_bytes() {
+            return Err(AesError::AadMismatch);
+        }
+        Ok(())
+    }
+
+    /// Associated data the AEAD was sealed with: the encoded header, or
+    /// the caller's alone before version 2
+    pub fn aead_aad(&self, aad: &[u8]) -> Vec<u8> {
+        if self.version >= 2 {
+            self.to_bytes()
+        } else {
+            aad.to_vec()
+        }
+    }
+
+    /// Password costs, for artifacts that have them
+    pub fn kdf_params(&self) -> Option<KdfParams> {
+        match self.kdf {
+            Kdf::Argon2id(params) => Some(params),
+            Kdf::None => None,
+        }
+    }
+
+    fn legacy(version: u8, kdf: Kdf, salt: Vec<u8>, nonce: Vec<u8>) -> Self {
+        Self { version, cipher: CipherId::Aes256Gcm, kdf, salt, nonce, aad_hash: Vec::new() }
+    }
+
+    /// Version 2 fields after the length
+    fn decode(mut rest: &[u8]) -> Result<Self, AesError> {
+        let reader = &mut rest;
+        let cipher = CipherId::try_from(read_vec(reader, 1)?[0])?;
+        let kdf = match read_vec(reader, 1)?[0] {
+            KDF_NONE => Kdf::None,
+            KDF_ARGON2ID => Kdf::Argon2id(read_costs(reader)?),
+            id => return Err(AesError::Format(format!("unknown key derivation {id}"))),
+        };
+        let salt_len = read_vec(reader, 1)?[0] as usize;
+        let salt = read_vec(reader, salt_len)?;
+        let nonce_len = read_vec(reader, 1)?[0] as usize;
+        let nonce = read_vec(reader, nonce_len)?;
+        let aad_hash = read_vec(reader, blake3::OUT_LEN)?;
+        Ok(Self { version: FORMAT_VERSION, cipher, kdf, salt, nonce, aad_hash })
+    }
+}
+
+fn read_costs<R: Read>(reader: &mut R) -> Result<KdfParams, AesError> {
+    let mut field = || read_vec(reader, 4).map(|bytes| u32::from_le_bytes(bytes.try_into().expect("4 bytes")));
+    let params = KdfParams { mem_cost: field()?, time_cost: field()?, lanes: field()? };
+    if !params.is_valid() {
+        return Err(AesError::Format(format!("Argon2 costs {params:?}")));
+    }
+    Ok(params)
+}
+
+fn read_vec<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, AesError> {
+    let mut buf = vec![0u8; len];
+    read_exact(reader, &mut buf)?;
+    Ok(buf)
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), AesError> {
+    reader.read_exact(buf).map_err(|_| AesError::InvalidLength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trips() {
+        let kdf = Kdf::Argon2id(KdfParams { mem_cost: 65536, time_cost: 2, lanes: 4 });
+        let header = CipherHeader::new(CipherId::Aes256Gcm, kdf, &[1; 16], &[2; 12], b"scoria/model/v1");
+        let mut encoded = header.to_bytes();
+        encoded.extend_from_slice(b"ciphertext");
+
+        let mut rest = &encoded[..];
+        assert_eq!(CipherHeader::read(&mut rest, LegacyLayout::SEALED).unwrap(), header);
+        assert_eq!(rest, b"ciphertext");
+        assert!(header.check_aad(b"scoria/model/v1").is_ok());
+        assert!(matches!(header.check_aad(b"scoria/data/v1"), Err(AesError::AadMismatch)));
+
+        let json = serde_json::to_string(&header).unwrap();
+        assert!(json.contains(r#""kdf":"argon2id""#) && json.contains(r#""cipher":"aes-256-gcm""#));
+        assert_eq!(serde_json::from_str::<CipherHeader>(&json).unwrap(), header);
+
+        encoded[4] = FORMAT_VERSION + 1;
+        assert!(matches!(CipherHeader::read(&mut &encoded[..], LegacyLayout::SEALED), Err(AesError::Format(_))));
+    }
+
+    #[test]
+    fn test_older_layouts_parse() {
+        let headerless = [[7u8; 16].as_slice(), &[8; 7], b"segments"].concat();
+        let mut rest = &headerless[..];
+        let header = CipherHeader::read(&mut rest, LegacyLayout::STREAM).unwrap();
+        assert_eq!((header.version, header.kdf_params()), (0, Some(KdfParams::LEGACY)));
+        assert_eq!(header.aead_aad(b"aad"), b"aad");
+        assert_eq!((header.salt, header.nonce), (vec![7; 16], vec![8; 7]));
+        assert_eq!(rest, b"segments");
+
+        let mut rest = &headerless[..];
+        let keyed = CipherHeader::read(&mut rest, LegacyLayout::KEYED_STREAM).unwrap();
+        assert_eq!((keyed.kdf, keyed.nonce), (Kdf::None, vec![7; 7]));
+
+        let costs = [3u32 << 10, 1, 1].iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>();
+        let v1 = [&MAGIC[..], &[1], &costs, &[7; 16], &[9; 12], b"ct"].concat();
+        let mut rest = &v1[..];
+        let header = CipherHeader::read(&mut rest, LegacyLayout::SEALED).unwrap();
+        assert_eq!(header.kdf_params(), Some(KdfParams { mem_cost: 3 << 10, time_cost: 1, lanes: 1 }));
+        assert_eq!((header.version, header.nonce, rest), (1, vec![9; 12], &b"ct"[..]));
+    }
+}
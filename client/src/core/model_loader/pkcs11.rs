@@ -0,0 +1,262 @@
+// client/src/core/model_loader/pkcs11.rs
+//
+// PKCS#11 backend for HSMs such as YubiHSM 2 and SoftHSM. The key-encryption
+// key never leaves the token: data keys are wrapped and unwrapped on the
+// device with AES key wrap (RFC 5649), and signing keys are generated there.
+
+use super::envelope::{DataKey, WrappedDataKey};
+use crate::config::HsmConfig;
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Read when `security.hsm.pin` is unset, so the PIN can stay out of config files
+pub const PIN_ENV: &str = "SCORIA_HSM_PIN";
+/// DER OID of secp256r1, the curve of generated signing keys
+const P256_PARAMS: &[u8] = &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+#[derive(Debug, Error)]
+pub enum HsmError {
+    #[error("PKCS#11 error: {0}")]
+    Pkcs11(#[from] cryptoki::error::Error),
+    #[error("No token in slot {0}")]
+    SlotNotFound(u64),
+    #[error("No HSM PIN: set security.hsm.pin or {PIN_ENV}")]
+    MissingPin,
+    #[error("No {class} key labelled `{label}` on the token")]
+    KeyNotFound { class: &'static str, label: String },
+    #[error("Key labelled `{0}` already exists on the token")]
+    KeyExists(String),
+    #[error("Data key wrapped by {found}, token key is {expected}")]
+    WrongKey { expected: String, found: String },
+    #[error("Token returned malformed {0}")]
+    Malformed(&'static str),
+}
+
+/// Logged-in session on one PKCS#11 slot
+pub struct Pkcs11Hsm {
+    // Sessions are not Sync; every operation is a short critical section
+    session: Mutex<Session>,
+    key_label: String,
+    _pkcs11: Pkcs11,
+}
+
+impl Pkcs11Hsm {
+    /// Load the vendor module, open the configured slot and log in as user
+    pub fn open(config: &HsmConfig) -> Result<Self, HsmError> {
+        let pkcs11 = Pkcs11::new(&config.module)?;
+        pkcs11.initialize(CInitializeArgs::OsThreads)?;
+
+        let slot = pkcs11
+            .get_slots_with_token()?
+            .into_iter()
+            .find(|s| s.id() == config.slot)
+            .ok_or(HsmError::SlotNotFound(config.slot))?;
+        let pin = match &config.pin {
+            Some(pin) => pin.clone(),
+            None => std::env::var(PIN_ENV).map_err(|_| HsmError::MissingPin)?,
+        };
+        let session = pkcs11.open_rw_session(slot)?;
+        session.login(UserType::User, Some(&AuthPin::new(pin)))?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+            key_label: config.key_label.clone(),
+            _pkcs11: pkcs11,
+        })
+    }
+
+    /// Identifier recorded in key sidecars, e.g. `pkcs11:scoria-kek`
+    pub fn fingerprint(&self) -> String {
+        format!("pkcs11:{}", self.key_label)
+    }
+
+    fn find(session: &Session, class: ObjectClass, label: &str) -> Result<Option<ObjectHandle>, HsmError> {
+        Ok(session
+            .find_objects(&[Attribute::Class(class), Attribute::Label(label.as_bytes().to_vec())])?
+            .into_iter()
+            .next())
+    }
+
+    fn find_required(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle, HsmError> {
+        Self::find(session, class, label)?.ok_or_else(|| HsmError::KeyNotFound {
+            class: if class == ObjectClass::SECRET_KEY { "AES" } else { "private" },
+            label: label.to_string(),
+        })
+    }
+
+    /// Create a non-extractable AES-256 wrapping key on the token
+    pub fn generate_wrapping_key(&self, label: &str) -> Result<(), HsmError> {
+        let session = self.session.lock().unwrap();
+        if Self::find(&session, ObjectClass::SECRET_KEY, label)?.is_some() {
+            return Err(HsmError::KeyExists(label.to_string()));
+        }
+        session.generate_key(
+            &Mechanism::AesKeyGen,
+            &[
+                Attribute::Token(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+                Attribute::ValueLen(32.into()),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Wrap(true),
+                Attribute::Unwrap(true),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Create a P-256 signing key pair on the token; returns the SEC1 public point
+    pub fn generate_signing_key(&self, label: &str) -> Result<Vec<u8>, HsmError> {
+        let session = self.session.lock().unwrap();
+        if Self::find(&session, ObjectClass::PRIVATE_KEY, label)?.is_some() {
+            return Err(HsmError::KeyExists(label.to_string()));
+        }
+        let (public, _) = session.generate_key_pair(
+            &Mechanism::EccKeyPairGen,
+            &[
+                Attribute::Token(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+                Attribute::EcParams(P256_PARAMS.to_vec()),
+                Attribute::Verify(true),
+            ],
+            &[
+                Attribute::Token(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+                Attribute::Private(true),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Sign(true),
+            ],
+        )?;
+        match session.get_attributes(public, &[AttributeType::EcPoint])?.pop() {
+            // CKA_EC_POINT is a DER OCTET STRING around the SEC1 point
+            Some(Attribute::EcPoint(der)) if der.len() == 67 && der[..2] == [0x04, 65] => Ok(der[2..].to_vec()),
+            _ => Err(HsmError::Malformed("EC point")),
+        }
+    }
+
+    /// ECDSA P-256 over SHA-256 of `message` with the token key `label`; returns `r || s`
+    pub fn sign(&self, label: &str, message: &[u8]) -> Result<[u8; 64], HsmError> {
+        let session = self.session.lock().unwrap();
+        let key = Self::find_required(&session, ObjectClass::PRIVATE_KEY, label)?;
+        let signature = session.sign(&Mechanism::Ecdsa, key, &Sha256::digest(message))?;
+        signature.try_into().map_err(|_| HsmError::Malformed("ECDSA signature"))
+    }
+
+    /// Wrap a data key under the token's key-encryption key
+    pub fn wrap_data_key(&self, dek: &DataKey) -> Result<WrappedDataKey, HsmError> {
+        let session = self.session.lock().unwrap();
+        let kek = Self::find_required(&session, ObjectClass::SECRET_KEY, &self.key_label)?;
+
+        // 1. Import the data key as a session object so the token can wrap it
+        let object = session.create_object(&[
+            Attribute::Class(ObjectClass::SECRET_KEY),
+            Attribute::KeyType(KeyType::AES),
+            Attribute::Token(false),
+            Attribute::Extractable(true),
+            Attribute::Value(dek.as_bytes().to_vec()),
+        ])?;
+        let wrapped = session.wrap_key(&Mechanism::AesKeyWrapPad, kek, object);
+        session.destroy_object(object)?;
+
+        Ok(WrappedDataKey::new(self.fingerprint(), wrapped?))
+    }
+
+    /// Unwrap a data key produced by `wrap_data_key`
+    pub fn unwrap_data_key(&self, wrapped: &WrappedDataKey) -> Result<DataKey, HsmError> {
+        if wrapped.master_key != self.fingerprint() {
+            return Err(HsmError::WrongKey {
+                expected: self.fingerprint(),
+                found: wrapped.master_key.clone(),
+            });
+        }
+        let session = self.session.lock().unwrap();
+        let kek = Self::find_required(&session, ObjectClass::SECRET_KEY, &self.key_label)?;
+
+        // 1. Unwrap into a session object, read the value back, then drop it
+        let object = session.unwrap_key(
+            &Mechanism::AesKeyWrapPad,
+            kek,
+            &wrapped.wrapped_key,
+            &[
+                Attribute::Class(ObjectClass::SECRET_KEY),
+                Attribute::KeyType(KeyType::AES),
+                Attribute::Token(false),
+                Attribute::Sensitive(false),
+                Attribute::Extractable(true),
+            ],
+        )?;
+        let value = session.get_attributes(object, &[AttributeType::Value]);
+        session.destroy_object(object)?;
+
+        match value?.pop() {
+            Some(Attribute::Value(key)) => {
+                let key: [u8; 32] = key.as_slice().try_into().map_err(|_| HsmError::Malformed("data key"))?;
+                Ok(DataKey::from_bytes(key))
+            }
+            _ => Err(HsmError::Malformed("data key")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SoftHSM2 token initialised with
+    /// `softhsm2-util --init-token --free --label scoria --pin 1234 --so-pin 0000`
+    fn softhsm() -> Pkcs11Hsm {
+        let module = std::env::var("SOFTHSM2_MODULE").unwrap_or("/usr/lib/softhsm/libsofthsm2.so".into());
+        let pkcs11 = Pkcs11::new(&module).unwrap();
+        pkcs11.initialize(CInitializeArgs::OsThreads).unwrap();
+        let slot = pkcs11.get_slots_with_token().unwrap()[0].id();
+        drop(pkcs11);
+
+        let hsm = Pkcs11Hsm::open(&HsmConfig {
+            module: module.into(),
+            slot,
+            pin: Some("1234".into()),
+            key_label: format!("kek-{}", std::process::id()),
+        })
+        .unwrap();
+        hsm.generate_wrapping_key(&hsm.key_label).unwrap();
+        hsm
+    }
+
+    #[test]
+    #[ignore = "needs a SoftHSM2 token; see softhsm()"]
+    fn wrap_unwrap_roundtrip_on_token() {
+        let hsm = softhsm();
+        let dek = DataKey::generate();
+        let wrapped = hsm.wrap_data_key(&dek).unwrap();
+        assert_eq!(wrapped.master_key, hsm.fingerprint());
+        assert_ne!(&wrapped.wrapped_key[..], &dek.as_bytes()[..]);
+        assert_eq!(hsm.unwrap_data_key(&wrapped).unwrap().as_bytes(), dek.as_bytes());
+
+        let mut foreign = wrapped;
+        foreign.master_key = "other-master:1".into();
+        assert!(matches!(hsm.unwrap_data_key(&foreign), Err(HsmError::WrongKey { .. })));
+    }
+
+    #[test]
+    #[ignore = "needs a SoftHSM2 token; see softhsm()"]
+    fn token_signatures_verify_with_exported_key() {
+        use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+        let hsm = softhsm();
+        let label = format!("sig-{}", std::process::id());
+        let public = VerifyingKey::from_sec1_bytes(&hsm.generate_signing_key(&label).unwrap()).unwrap();
+        assert!(matches!(hsm.generate_signing_key(&label), Err(HsmError::KeyExists(_))));
+
+        let signature = Signature::from_slice(&hsm.sign(&label, b"scoria").unwrap()).unwrap();
+        assert!(public.verify(b"scoria", &signature).is_ok());
+        assert!(public.verify(b"tampered", &signature).is_err());
+    }
+}
@@ -0,0 +1,399 @@
+// client/src/core/model_loader/recipient.rs
+
+//! Model data keys shared with inference providers, wrapped to the
+//! provider's public key. `x25519` uses an ephemeral X25519 exchange;
+//! `x25519-mlkem768` adds an ML-KEM-768 encapsulation and derives the
+//! wrapping key from both secrets, so a share recorded today stays sealed
+//! unless both X25519 and ML-KEM are broken. The same seed also yields the
+//! Ristretto key used for proxy re-encryption (see `reencrypt`).
+
+use super::{
+    aes::{Aes256GcmProvider, AesError},
+    envelope::{DataKey, EnvelopeError},
+};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use hkdf::Hkdf;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Sidecar suffix of a share, after the recipient fingerprint
+pub const SHARE_SIDECAR_EXT: &str = "share.json";
+const SHARE_FORMAT_VERSION: u8 = 1;
+const SHARE_INFO: &[u8] = b"scoria/dek-share/v1";
+
+/// Key encapsulation used to wrap shared data keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KemId {
+    #[default]
+    #[serde(rename = "x25519")]
+    X25519,
+    /// X25519 combined with ML-KEM-768; needs the post-quantum feature
+    #[serde(rename = "x25519-mlkem768")]
+    X25519MlKem768,
+}
+
+impl KemId {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::X25519 => "x25519",
+            Self::X25519MlKem768 => "x25519-mlkem768",
+        }
+    }
+
+    fn check_supported(self) -> Result<(), EnvelopeError> {
+        if self == Self::X25519MlKem768 && !cfg!(feature = "post-quantum") {
+            return Err(EnvelopeError::Share("`x25519-mlkem768` needs the post-quantum feature".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Public key a provider hands out to receive data keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipientKey {
+    #[serde(with = "hex::serde")]
+    pub x25519: [u8; 32],
+    /// ML-KEM-768 encapsulation key; empty for classical-only recipients
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hex::serde")]
+    pub mlkem768: Vec<u8>,
+    /// Compressed Ristretto point for proxy re-encryption; empty for keys
+    /// printed before it was added
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hex::serde")]
+    pub pre: Vec<u8>,
+}
+
+impl RecipientKey {
+    /// Short identifier recorded in shares and their file names
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = blake3::Hasher::new_derive_key("scoria recipient v1");
+        hasher.update(&self.x25519).update(&self.mlkem768);
+        hex::encode(&hasher.finalize().as_bytes()[..16])
+    }
+}
+
+/// Provider-side keys, all derived from one 32-byte seed
+pub struct RecipientSecret {
+    x25519: StaticSecret,
+    #[cfg(feature = "post-quantum")]
+    mlkem768: Option<mlkem::DecapsulationKey>,
+    pre: Scalar,
+    public: RecipientKey,
+}
+
+impl Drop for RecipientSecret {
+    fn drop(&mut self) {
+        self.pre.zeroize();
+    }
+}
+
+impl RecipientSecret {
+    /// `kem` decides whether an ML-KEM-768 key is derived as well
+    pub fn from_seed(seed: &[u8; 32], kem: KemId) -> Result<Self, EnvelopeError> {
+        kem.check_supported()?;
+        let mut x25519 = [0u8; 32];
+        Hkdf::<Sha256>::new(None, seed)
+            .expand(b"scoria/recipient/x25519", &mut x25519)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        let x25519 = StaticSecret::from(x25519);
+
+        #[cfg(feature = "post-quantum")]
+        let (mlkem768, mlkem768_public) = match kem {
+            KemId::X25519 => (None, Vec::new()),
+            KemId::X25519MlKem768 => {
+                let (dk, ek) = mlkem::derive(seed);
+                (Some(dk), ek)
+            }
+        };
+        #[cfg(not(feature = "post-quantum"))]
+        let mlkem768_public = Vec::new();
+
+        let mut wide = Zeroizing::new([0u8; 64]);
+        Hkdf::<Sha256>::new(None, seed)
+            .expand(b"scoria/recipient/pre", &mut wide[..])
+            .expect("64 bytes is a valid HKDF-SHA256 output length");
+        let pre = Scalar::from_bytes_mod_order_wide(&wide);
+
+        let public = RecipientKey {
+            x25519: PublicKey::from(&x25519).to_bytes(),
+            mlkem768: mlkem768_public,
+            pre: RistrettoPoint::mul_base(&pre).compress().to_bytes().to_vec(),
+        };
+        Ok(Self {
+            x25519,
+            #[cfg(feature = "post-quantum")]
+            mlkem768,
+            pre,
+            public,
+        })
+    }
+
+    /// Keys from the seed file at `path`, e.g. `security.recipient_seed`
+    pub fn load(path: &Path, kem: KemId) -> Result<Self, EnvelopeError> {
+        let raw = Zeroizing::new(fs::read(path)?);
+        let seed: &[u8; 32] = raw
+            .as_slice()
+            .try_into()
+            .map_err(|_| EnvelopeError::Share(format!("{} is not a 32-byte seed", path.display())))?;
+        Self::from_seed(seed, kem)
+    }
+
+    /// As `load`, first creating the seed file readable by the owner only
+    /// when missing
+    pub fn load_or_create(path: &Path, kem: KemId) -> Result<Self, EnvelopeError> {
+        if !path.exists() {
+            let mut seed = Zeroizing::new([0u8; 32]);
+            OsRng.fill_bytes(&mut seed[..]);
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            options.open(path)?.write_all(&seed[..])?;
+        }
+        Self::load(path, kem)
+    }
+
+    pub fn public_key(&self) -> &RecipientKey {
+        &self.public
+    }
+
+    pub(super) fn pre_secret(&self) -> &Scalar {
+        &self.pre
+    }
+
+    /// Unwrap a data key shared with this recipient
+    pub fn open(&self, aes: &Aes256GcmProvider, share: &SharedDataKey) -> Result<DataKey, EnvelopeError> {
+        if share.format != SHARE_FORMAT_VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(share.format));
+        }
+        if share.recipient != self.public.fingerprint() {
+            return Err(EnvelopeError::Share(format!(
+                "wrapped to recipient {}, this key is {}",
+                share.recipient,
+                self.public.fingerprint()
+            )));
+        }
+        share.kem.check_supported()?;
+
+        let classical = self.x25519.diffie_hellman(&PublicKey::from(share.ephemeral_key));
+        let post_quantum = match share.kem {
+            KemId::X25519 => Zeroizing::new(Vec::new()),
+            #[cfg(feature = "post-quantum")]
+            KemId::X25519MlKem768 => {
+                let dk =
+                    self.mlkem768.as_ref().ok_or(EnvelopeError::Share("no ML-KEM-768 key for this share".into()))?;
+                mlkem::decapsulate(dk, &share.mlkem_ciphertext)?
+            }
+            #[cfg(not(feature = "post-quantum"))]
+            KemId::X25519MlKem768 => unreachable!("checked above"),
+        };
+
+        let context = share_context(share.kem, &share.ephemeral_key, &self.public, &share.mlkem_ciphertext);
+        let kek = wrapping_key(classical.as_bytes(), &post_quantum, &context);
+        let mut plain = aes.open_with_key(&kek, &share.wrapped_key, &context)?;
+        let key: Result<[u8; 32], _> = plain.as_slice().try_into();
+        plain.zeroize();
+        Ok(DataKey::from_bytes(key.map_err(|_| AesError::InvalidKeyLength)?))
+    }
+}
+
+/// A data key wrapped to one recipient, stored beside the encrypted model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedDataKey {
+    pub format: u8,
+    pub kem: KemId,
+    /// `RecipientKey::fingerprint` of the provider
+    pub recipient: String,
+    #[serde(with = "hex::serde")]
+    pub ephemeral_key: [u8; 32],
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hex::serde")]
+    pub mlkem_ciphertext: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub wrapped_key: Vec<u8>,
+    pub created_at: u64,
+}
+
+impl SharedDataKey {
+    /// Wrap `dek` to `recipient` with `kem`; hybrid shares need the
+    /// recipient to have published an ML-KEM-768 key
+    pub fn wrap(
+        aes: &Aes256GcmProvider,
+        dek: &DataKey,
+        recipient: &RecipientKey,
+        kem: KemId,
+    ) -> Result<Self, EnvelopeError> {
+        kem.check_supported()?;
+        let ephemeral = StaticSecret::random_from_rng(OsRng);
+        let ephemeral_key = PublicKey::from(&ephemeral).to_bytes();
+        let classical = ephemeral.diffie_hellman(&PublicKey::from(recipient.x25519));
+
+        let (mlkem_ciphertext, post_quantum) = match kem {
+            KemId::X25519 => (Vec::new(), Zeroizing::new(Vec::new())),
+            #[cfg(feature = "post-quantum")]
+            KemId::X25519MlKem768 => {
+                if recipient.mlkem768.is_empty() {
+                    return Err(EnvelopeError::Share(format!(
+                        "recipient {} has no ML-KEM-768 key",
+                        recipient.fingerprint()
+                    )));
+                }
+                mlkem::encapsulate(&recipient.mlkem768)?
+            }
+            #[cfg(not(feature = "post-quantum"))]
+            KemId::X25519MlKem768 => unreachable!("checked above"),
+        };
+
+        let context = share_context(kem, &ephemeral_key, recipient, &mlkem_ciphertext);
+        let kek = wrapping_key(classical.as_bytes(), &post_quantum, &context);
+        Ok(Self {
+            format: SHARE_FORMAT_VERSION,
+            kem,
+            recipient: recipient.fingerprint(),
+            ephemeral_key,
+            mlkem_ciphertext,
+            wrapped_key: aes.seal_with_key(&kek, dek.as_bytes(), &context)?,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        })
+    }
+
+    /// `<model>.<recipient>.share.json`
+    pub fn sidecar_path(model_path: &Path, recipient: &str) -> PathBuf {
+        let mut name = model_path.as_os_str().to_owned();
+        name.push(format!(".{recipient}.{SHARE_SIDECAR_EXT}"));
+        PathBuf::from(name)
+    }
+
+    pub fn load(model_path: &Path, recipient: &str) -> Result<Self, EnvelopeError> {
+        let raw = fs::read(Self::sidecar_path(model_path, recipient))?;
+        Ok(serde_json::from_slice(&raw)?)
+    }
+
+    pub fn store(&self, model_path: &Path) -> Result<PathBuf, EnvelopeError> {
+        let path = Self::sidecar_path(model_path, &self.recipient);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(tmp, &path)?;
+        Ok(path)
+    }
+}
+
+/// Bound into both the wrapping key and the sealed data key, so no field of
+/// a share can be swapped without failing authentication
+fn share_context(kem: KemId, ephemeral_key: &[u8; 32], recipient: &RecipientKey, mlkem_ciphertext: &[u8]) -> Vec<u8> {
+    let mut context = SHARE_INFO.to_vec();
+    context.extend_from_slice(kem.name().as_bytes());
+    context.extend_from_slice(ephemeral_key);
+    context.extend_from_slice(recipient.fingerprint().as_bytes());
+    context.extend_from_slice(blake3::hash(mlkem_ciphertext).as_bytes());
+    context
+}
+
+/// HKDF-SHA256 over the X25519 secret followed by the ML-KEM secret, if any
+fn wrapping_key(classical: &[u8; 32], post_quantum: &[u8], context: &[u8]) -> Zeroizing<[u8; 32]> {
+    let ikm = Zeroizing::new([&classical[..], post_quantum].concat());
+    let mut key = Zeroizing::new([0u8; 32]);
+    Hkdf::<Sha256>::new(None, &ikm)
+        .expand(context, &mut key[..])
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+#[cfg(feature = "post-quantum")]
+mod mlkem {
+    use super::EnvelopeError;
+    use hkdf::Hkdf;
+    use ml_kem::{
+        kem::{Decapsulate, Encapsulate},
+        Ciphertext, EncodedSizeUser, KemCore, MlKem768, B32,
+    };
+    use rand::rngs::OsRng;
+    use sha2::Sha256;
+    use zeroize::Zeroizing;
+
+    pub(super) type DecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+    type EncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+
+    /// Decapsulation key and encoded encapsulation key for `seed`
+    pub(super) fn derive(seed: &[u8; 32]) -> (DecapsulationKey, Vec<u8>) {
+        let mut dz = Zeroizing::new([0u8; 64]);
+        Hkdf::<Sha256>::new(None, seed)
+            .expand(b"scoria/recipient/mlkem768", &mut dz[..])
+            .expect("64 bytes is a valid HKDF-SHA256 output length");
+        let d = B32::try_from(&dz[..32]).expect("32-byte slice");
+        let z = B32::try_from(&dz[32..]).expect("32-byte slice");
+        let (dk, ek) = MlKem768::generate_deterministic(&d, &z);
+        (dk, ek.as_bytes().to_vec())
+    }
+
+    /// Ciphertext and shared secret for an encoded encapsulation key
+    pub(super) fn encapsulate(encoded: &[u8]) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>), EnvelopeError> {
+        let encoded = encoded.try_into().map_err(|_| EnvelopeError::Share("malformed ML-KEM-768 key".into()))?;
+        let (ciphertext, shared) = EncapsulationKey::from_bytes(&encoded)
+            .encapsulate(&mut OsRng)
+            .map_err(|_| EnvelopeError::Share("ML-KEM-768 encapsulation failed".into()))?;
+        Ok((ciphertext.to_vec(), Zeroizing::new(shared.to_vec())))
+    }
+
+    pub(super) fn decapsulate(dk: &DecapsulationKey, ciphertext: &[u8]) -> Result<Zeroizing<Vec<u8>>, EnvelopeError> {
+        let ciphertext = Ciphertext::<MlKem768>::try_from(ciphertext)
+            .map_err(|_| EnvelopeError::Share("malformed ML-KEM-768 ciphertext".into()))?;
+        let shared =
+            dk.decapsulate(&ciphertext).map_err(|_| EnvelopeError::Share("ML-KEM-768 decapsulation failed".into()))?;
+        Ok(Zeroizing::new(shared.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_opens_only_for_its_recipient() {
+        let aes = Aes256GcmProvider::new();
+        let provider = RecipientSecret::from_seed(&[7; 32], KemId::X25519).unwrap();
+        let dek = DataKey::generate();
+
+        let share = SharedDataKey::wrap(&aes, &dek, provider.public_key(), KemId::X25519).unwrap();
+        assert_eq!(provider.open(&aes, &share).unwrap().as_bytes(), dek.as_bytes());
+
+        let other = RecipientSecret::from_seed(&[8; 32], KemId::X25519).unwrap();
+        assert!(matches!(other.open(&aes, &share), Err(EnvelopeError::Share(_))));
+
+        let mut swapped = share.clone();
+        swapped.ephemeral_key = other.public_key().x25519;
+        assert!(provider.open(&aes, &swapped).is_err());
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_hybrid_share_needs_both_secrets() {
+        let aes = Aes256GcmProvider::new();
+        let provider = RecipientSecret::from_seed(&[7; 32], KemId::X25519MlKem768).unwrap();
+        let dek = DataKey::generate();
+
+        let share = SharedDataKey::wrap(&aes, &dek, provider.public_key(), KemId::X25519MlKem768).unwrap();
+        assert_eq!(provider.open(&aes, &share).unwrap().as_bytes(), dek.as_bytes());
+
+        let mut tampered = share.clone();
+        tampered.mlkem_ciphertext[0] ^= 1;
+        assert!(provider.open(&aes, &tampered).is_err());
+
+        let classical = RecipientSecret::from_seed(&[9; 32], KemId::X25519).unwrap();
+        let result = SharedDataKey::wrap(&aes, &dek, classical.public_key(), KemId::X25519MlKem768);
+        assert!(matches!(result, Err(EnvelopeError::Share(_))));
+    }
+
+    #[cfg(not(feature = "post-quantum"))]
+    #[test]
+    fn test_hybrid_needs_feature() {
+        assert!(RecipientSecret::from_seed(&[7; 32], KemId::X25519MlKem768).is_err());
+    }
+}
@@ -0,0 +1,372 @@
+// client/src/core/model_loader/reencrypt.rs
+
+//! Proxy re-encryption of model data keys, after Umbral with a single key
+//! fragment over Ristretto255. The owner seals the data key in a capsule
+//! under their own recipient key and issues one re-encryption key per
+//! provider; a proxy holding it turns the capsule into a fragment only that
+//! provider can open, without seeing the data key or the owner's secret.
+//!
+//! Proxies re-encrypt only while the model's on-chain ACL grants the
+//! provider access, so revoking stops new fragments; a provider keeps keys
+//! it already opened. The owner's wallet signs each re-encryption key, so a
+//! proxy checks the ACL for the model and provider the owner named. As with any single-fragment scheme, a proxy and a
+//! provider together can recover the owner's re-encryption secret.
+
+use super::{
+    aes::{Aes256GcmProvider, AesError},
+    envelope::{DataKey, EnvelopeError},
+    recipient::{RecipientKey, RecipientSecret},
+};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use hkdf::Hkdf;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use zeroize::{Zeroize, Zeroizing};
+
+pub const CAPSULE_SIDECAR_EXT: &str = "capsule.json";
+pub const KFRAG_SIDECAR_EXT: &str = "kfrag.json";
+pub const CFRAG_SIDECAR_EXT: &str = "cfrag.json";
+const PRE_FORMAT_VERSION: u8 = 1;
+const CAPSULE_INFO: &[u8] = b"scoria/pre/v1/capsule";
+const BLINDING_INFO: &[u8] = b"scoria/pre/v1/blinding";
+const KFRAG_INFO: &[u8] = b"scoria/pre/v1/kfrag";
+
+/// Data key sealed under the owner's re-encryption key, beside the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capsule {
+    pub format: u8,
+    /// Model account the data key belongs to
+    pub model: String,
+    /// Owner's `RecipientKey::pre`
+    #[serde(with = "hex::serde")]
+    pub owner: [u8; 32],
+    /// Ephemeral point `rG`
+    #[serde(with = "hex::serde")]
+    pub point: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub wrapped_key: Vec<u8>,
+}
+
+impl Capsule {
+    pub fn seal(
+        aes: &Aes256GcmProvider,
+        dek: &DataKey,
+        model: &str,
+        owner: &RecipientKey,
+    ) -> Result<Self, EnvelopeError> {
+        let owner_point = decode(&owner.pre)?;
+        let r = random_scalar();
+        let point = RistrettoPoint::mul_base(&r).compress().to_bytes();
+        let owner = owner_point.compress().to_bytes();
+
+        let context = capsule_context(model, &owner, &point);
+        let key = wrapping_key(&(*r * owner_point), &context);
+        let wrapped_key = aes.seal_with_key(&key, dek.as_bytes(), &context)?;
+        Ok(Self { format: PRE_FORMAT_VERSION, model: model.to_string(), owner, point, wrapped_key })
+    }
+
+    /// `shared` is `rA`, reached either directly by the owner or through a fragment
+    fn open(&self, aes: &Aes256GcmProvider, shared: &RistrettoPoint) -> Result<DataKey, EnvelopeError> {
+        if self.format != PRE_FORMAT_VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(self.format));
+        }
+        let context = capsule_context(&self.model, &self.owner, &self.point);
+        let mut plain = aes.open_with_key(&wrapping_key(shared, &context), &self.wrapped_key, &context)?;
+        let key: Result<[u8; 32], _> = plain.as_slice().try_into();
+        plain.zeroize();
+        Ok(DataKey::from_bytes(key.map_err(|_| AesError::InvalidKeyLength)?))
+    }
+
+    pub fn sidecar_path(model_path: &Path) -> PathBuf {
+        sidecar(model_path, CAPSULE_SIDECAR_EXT)
+    }
+
+    pub fn load(model_path: &Path) -> Result<Self, EnvelopeError> {
+        Ok(serde_json::from_slice(&fs::read(Self::sidecar_path(model_path))?)?)
+    }
+
+    pub fn store(&self, model_path: &Path) -> Result<PathBuf, EnvelopeError> {
+        store(&Self::sidecar_path(model_path), self)
+    }
+}
+
+/// Lets a proxy re-encrypt the owner's capsules for one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReencryptionKey {
+    pub format: u8,
+    /// Model account whose ACL gates re-encryption
+    pub model: String,
+    /// Provider wallet looked up in the ACL
+    pub provider: String,
+    /// `RecipientKey::fingerprint` of the provider's recipient key
+    pub recipient: String,
+    #[serde(with = "hex::serde")]
+    pub owner: [u8; 32],
+    /// Ephemeral point `xG` the provider needs to remove the blinding
+    #[serde(with = "hex::serde")]
+    pub precursor: [u8; 32],
+    /// Owner secret divided by the blinding factor; for the proxy only
+    #[serde(with = "hex::serde")]
+    pub key: [u8; 32],
+    /// Owner wallet's Ed25519 signature over `signing_message`
+    #[serde(with = "hex::serde")]
+    pub signature: Vec<u8>,
+}
+
+impl ReencryptionKey {
+    /// Bytes the owner's wallet signs, covering every field but the signature
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = KFRAG_INFO.to_vec();
+        message.push(self.format);
+        for field in [&self.model, &self.provider, &self.recipient] {
+            message.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            message.extend_from_slice(field.as_bytes());
+        }
+        message.extend_from_slice(&self.owner);
+        message.extend_from_slice(&self.precursor);
+        message.extend_from_slice(&self.key);
+        message
+    }
+
+    /// The proxy's step: a fragment of `capsule` for this key's recipient
+    pub fn reencrypt(&self, capsule: &Capsule) -> Result<CapsuleFrag, EnvelopeError> {
+        if self.format != PRE_FORMAT_VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(self.format));
+        }
+        if capsule.owner != self.owner {
+            return Err(EnvelopeError::Share("capsule was sealed by another owner".into()));
+        }
+        if capsule.model != self.model {
+            return Err(EnvelopeError::Share(format!(
+                "capsule belongs to model {}, this key is for {}",
+                capsule.model, self.model
+            )));
+        }
+        let key = Zeroizing::new(
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(self.key))
+                .ok_or(EnvelopeError::Share("malformed re-encryption key".into()))?,
+        );
+        Ok(CapsuleFrag {
+            format: PRE_FORMAT_VERSION,
+            recipient: self.recipient.clone(),
+            owner: self.owner,
+            precursor: self.precursor,
+            point: (*key * decode(&capsule.point)?).compress().to_bytes(),
+        })
+    }
+
+    pub fn sidecar_path(model_path: &Path, recipient: &str) -> PathBuf {
+        sidecar(model_path, &format!("{recipient}.{KFRAG_SIDECAR_EXT}"))
+    }
+
+    pub fn store(&self, model_path: &Path) -> Result<PathBuf, EnvelopeError> {
+        store(&Self::sidecar_path(model_path, &self.recipient), self)
+    }
+}
+
+/// A capsule re-encrypted for one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapsuleFrag {
+    pub format: u8,
+    pub recipient: String,
+    #[serde(with = "hex::serde")]
+    pub owner: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub precursor: [u8; 32],
+    /// `rG` times the re-encryption key
+    #[serde(with = "hex::serde")]
+    pub point: [u8; 32],
+}
+
+impl CapsuleFrag {
+    pub fn sidecar_path(model_path: &Path, recipient: &str) -> PathBuf {
+        sidecar(model_path, &format!("{recipient}.{CFRAG_SIDECAR_EXT}"))
+    }
+
+    pub fn load(model_path: &Path, recipient: &str) -> Result<Self, EnvelopeError> {
+        Ok(serde_json::from_slice(&fs::read(Self::sidecar_path(model_path, recipient))?)?)
+    }
+
+    pub fn store(&self, model_path: &Path) -> Result<PathBuf, EnvelopeError> {
+        store(&Self::sidecar_path(model_path, &self.recipient), self)
+    }
+}
+
+impl RecipientSecret {
+    /// As the owner, a re-encryption key letting `recipient`, the recipient
+    /// key of `provider`, open this owner's capsules for `model`. The key is
+    /// unsigned; the owner's wallet fills in `signature` before handing it out.
+    pub fn reencryption_key(
+        &self,
+        model: &str,
+        provider: &str,
+        recipient: &RecipientKey,
+    ) -> Result<ReencryptionKey, EnvelopeError> {
+        let recipient_point = decode(&recipient.pre)?;
+        let x = random_scalar();
+        let precursor = RistrettoPoint::mul_base(&x).compress().to_bytes();
+        let owner = self.own_point();
+        let d = blinding(&precursor, &recipient_point, &(*x * recipient_point), &owner);
+        Ok(ReencryptionKey {
+            format: PRE_FORMAT_VERSION,
+            model: model.to_string(),
+            provider: provider.to_string(),
+            recipient: recipient.fingerprint(),
+            owner,
+            precursor,
+            key: (self.pre_secret() * d.invert()).to_bytes(),
+            signature: Vec::new(),
+        })
+    }
+
+    /// As the owner, open a capsule directly
+    pub fn open_capsule(&self, aes: &Aes256GcmProvider, capsule: &Capsule) -> Result<DataKey, EnvelopeError> {
+        if capsule.owner != self.own_point() {
+            return Err(EnvelopeError::Share("capsule was sealed by another owner".into()));
+        }
+        capsule.open(aes, &(self.pre_secret() * decode(&capsule.point)?))
+    }
+
+    /// As the provider, open a capsule through a fragment re-encrypted for us
+    pub fn open_reencrypted(
+        &self,
+        aes: &Aes256GcmProvider,
+        capsule: &Capsule,
+        cfrag: &CapsuleFrag,
+    ) -> Result<DataKey, EnvelopeError> {
+        if cfrag.recipient != self.public_key().fingerprint() {
+            return Err(EnvelopeError::Share(format!(
+                "re-encrypted for recipient {}, this key is {}",
+                cfrag.recipient,
+                self.public_key().fingerprint()
+            )));
+        }
+        if cfrag.owner != capsule.owner {
+            return Err(EnvelopeError::Share("fragment belongs to another owner's capsule".into()));
+        }
+        let own = RistrettoPoint::mul_base(self.pre_secret());
+        let dh = self.pre_secret() * decode(&cfrag.precursor)?;
+        let d = blinding(&cfrag.precursor, &own, &dh, &capsule.owner);
+        capsule.open(aes, &(d * decode(&cfrag.point)?))
+    }
+
+    fn own_point(&self) -> [u8; 32] {
+        RistrettoPoint::mul_base(self.pre_secret()).compress().to_bytes()
+    }
+}
+
+fn capsule_context(model: &str, owner: &[u8; 32], point: &[u8; 32]) -> Vec<u8> {
+    [CAPSULE_INFO, &(model.len() as u32).to_le_bytes(), model.as_bytes(), &owner[..], &point[..]].concat()
+}
+
+fn wrapping_key(shared: &RistrettoPoint, context: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Hkdf::<Sha256>::new(None, shared.compress().as_bytes())
+        .expand(context, &mut key[..])
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+/// Factor only the proxy's key and the recipient's secret can undo together
+fn blinding(precursor: &[u8; 32], recipient: &RistrettoPoint, dh: &RistrettoPoint, owner: &[u8; 32]) -> Scalar {
+    let info = [BLINDING_INFO, &precursor[..], recipient.compress().as_bytes(), &owner[..]].concat();
+    let mut wide = Zeroizing::new([0u8; 64]);
+    Hkdf::<Sha256>::new(None, dh.compress().as_bytes())
+        .expand(&info, &mut wide[..])
+        .expect("64 bytes is a valid HKDF-SHA256 output length");
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+fn random_scalar() -> Zeroizing<Scalar> {
+    let mut wide = Zeroizing::new([0u8; 64]);
+    OsRng.fill_bytes(&mut wide[..]);
+    Zeroizing::new(Scalar::from_bytes_mod_order_wide(&wide))
+}
+
+/// A point other than the identity
+fn decode(bytes: &[u8]) -> Result<RistrettoPoint, EnvelopeError> {
+    let malformed = || EnvelopeError::Share("malformed re-encryption point".into());
+    let point = CompressedRistretto::from_slice(bytes).map_err(|_| malformed())?.decompress().ok_or_else(malformed)?;
+    if point == RistrettoPoint::default() {
+        return Err(malformed());
+    }
+    Ok(point)
+}
+
+fn sidecar(model_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = model_path.as_os_str().to_owned();
+    name.push(format!(".{suffix}"));
+    PathBuf::from(name)
+}
+
+fn store<T: Serialize>(path: &Path, value: &T) -> Result<PathBuf, EnvelopeError> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(tmp, path)?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model_loader::recipient::KemId;
+
+    fn party(byte: u8) -> RecipientSecret {
+        RecipientSecret::from_seed(&[byte; 32], KemId::X25519).unwrap()
+    }
+
+    #[test]
+    fn test_reencrypted_capsule_opens_only_for_its_provider() {
+        let aes = Aes256GcmProvider::new();
+        let (owner, provider, other) = (party(1), party(2), party(3));
+        let dek = DataKey::generate();
+
+        let capsule = Capsule::seal(&aes, &dek, "model", owner.public_key()).unwrap();
+        assert_eq!(owner.open_capsule(&aes, &capsule).unwrap().as_bytes(), dek.as_bytes());
+        assert!(provider.open_capsule(&aes, &capsule).is_err());
+
+        let kfrag = owner.reencryption_key("model", "wallet", provider.public_key()).unwrap();
+        let cfrag = kfrag.reencrypt(&capsule).unwrap();
+        assert_eq!(provider.open_reencrypted(&aes, &capsule, &cfrag).unwrap().as_bytes(), dek.as_bytes());
+        assert!(matches!(other.open_reencrypted(&aes, &capsule, &cfrag), Err(EnvelopeError::Share(_))));
+
+        // A fragment made with another provider's key does not open here
+        let mut forged =
+            owner.reencryption_key("model", "wallet", other.public_key()).unwrap().reencrypt(&capsule).unwrap();
+        forged.recipient = cfrag.recipient.clone();
+        assert!(provider.open_reencrypted(&aes, &capsule, &forged).is_err());
+    }
+
+    #[test]
+    fn test_reencryption_key_is_bound_to_its_owner() {
+        let aes = Aes256GcmProvider::new();
+        let (owner, provider, stranger) = (party(1), party(2), party(3));
+        let capsule = Capsule::seal(&aes, &DataKey::generate(), "model", stranger.public_key()).unwrap();
+
+        let kfrag = owner.reencryption_key("model", "wallet", provider.public_key()).unwrap();
+        assert!(matches!(kfrag.reencrypt(&capsule), Err(EnvelopeError::Share(_))));
+    }
+
+    #[test]
+    fn test_reencryption_key_is_bound_to_its_model() {
+        let aes = Aes256GcmProvider::new();
+        let (owner, provider) = (party(1), party(2));
+        let mut capsule = Capsule::seal(&aes, &DataKey::generate(), "model", owner.public_key()).unwrap();
+
+        let kfrag = owner.reencryption_key("other", "wallet", provider.public_key()).unwrap();
+        assert!(matches!(kfrag.reencrypt(&capsule), Err(EnvelopeError::Share(_))));
+
+        // Relabelling the capsule to match breaks its authentication
+        capsule.model = "other".into();
+        let cfrag = kfrag.reencrypt(&capsule).unwrap();
+        assert!(provider.open_reencrypted(&aes, &capsule, &cfrag).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+// client/src/core/model_loader/verify.rs
+
+use serde::Serialize;
+use sha3::{Digest, Sha3_256};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, signer::Signer};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Result of one audit step
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Auditor signature outcome; `auditor` is the trusted key it verified under
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditorCheck {
+    pub index: usize,
+    pub auditor: Option<String>,
+    pub version: u64,
+    pub report_cid: String,
+    pub valid: bool,
+}
+
+/// An on-chain `AuditRecord`: one auditor's signed report for a model version
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub auditor: Pubkey,
+    pub version: u64,
+    pub report_cid: String,
+    pub signature: [u8; 64],
+}
+
+/// End-to-end integrity audit of a registered model
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationReport {
+    pub model: String,
+    pub owner: String,
+    pub storage_uri: String,
+    pub blake3: String,
+    pub sha3_256: String,
+    pub onchain_model_hash: String,
+    pub active_version: u64,
+    pub version_count: usize,
+    pub version_root: Option<String>,
+    pub auditors: Vec<AuditorCheck>,
+    pub checks: Vec<Check>,
+    pub passed: bool,
+    pub verified_at: u64,
+}
+
+/// Report plus the verifier's Ed25519 signature over its canonical JSON
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedAttestation {
+    pub report: VerificationReport,
+    pub verifier: String,
+    pub signature: String,
+}
+
+/// On-chain fields the audit compares against
+pub struct OnChainModel<'a> {
+    pub address: Pubkey,
+    pub owner: Pubkey,
+    pub storage_uri: &'a str,
+    pub model_hash: [u8; 32],
+    pub active_version: u64,
+    pub version_history: &'a [[u8; 32]],
+    pub audits: &'a [AuditEntry],
+}
+
+/// Recompute hashes of the decrypted model and check them against chain state
+pub fn audit_model(
+    model: &OnChainModel,
+    plaintext: &[u8],
+    trusted_auditors: &[Pubkey],
+    expected_root: Option<[u8; 32]>,
+) -> VerificationReport {
+    let mut checks = Vec::new();
+
+    // 1. Content hashes; the registry stores SHA3-256, blake3 is reported for IPFS tooling
+    let blake3 = blake3::hash(plaintext);
+    let sha3: [u8; 32] = Sha3_256::digest(plaintext).into();
+    checks.push(Check {
+        name: "model_hash",
+        passed: sha3 == model.model_hash,
+        detail: format!("sha3-256 {} vs on-chain {}", hex::encode(sha3), hex::encode(model.model_hash)),
+    });
+
+    // 2. Version history: head is the current binary and the root reproduces
+    let head = model.version_history.last();
+    checks.push(Check {
+        name: "version_head",
+        passed: head == Some(&model.model_hash),
+        detail: match head {
+            Some(h) => format!("latest history entry {}", hex::encode(h)),
+            None => "version history is empty".into(),
+        },
+    });
+    let root = version_root(model.version_history);
+    if let (Some(root), Some(index)) = (root, model.version_history.len().checked_sub(1)) {
+        let proof = inclusion_proof(model.version_history, index);
+        checks.push(Check {
+            name: "version_inclusion",
+            passed: verify_inclusion(&model.model_hash, index, &proof, &root),
+            detail: format!("{} siblings to root {}", proof.len(), hex::encode(root)),
+        });
+    }
+    if let Some(expected) = expected_root {
+        checks.push(Check {
+            name: "version_root",
+            passed: root == Some(expected),
+            detail: format!("expected {}", hex::encode(expected)),
+        });
+    }
+
+    // 3. Trusted auditor signatures over the current binary and version
+    let auditors: Vec<AuditorCheck> = model
+        .audits
+        .iter()
+        .enumerate()
+        .map(|(index, audit)| {
+            let message = audit_message(&model.model_hash, audit.version, &audit.report_cid);
+            let valid = audit.version == model.active_version
+                && trusted_auditors.contains(&audit.auditor)
+                && Signature::from(audit.signature).verify(audit.auditor.as_ref(), &message);
+            AuditorCheck {
+                index,
+                auditor: valid.then(|| audit.auditor.to_string()),
+                version: audit.version,
+                report_cid: audit.report_cid.clone(),
+                valid,
+            }
+        })
+        .collect();
+    checks.push(Check {
+        name: "auditor_signatures",
+        passed: !auditors.is_empty() && auditors.iter().all(|a| a.valid),
+        detail: format!(
+            "{}/{} signatures verify under trusted auditors",
+            auditors.iter().filter(|a| a.valid).count(),
+            auditors.len()
+        ),
+    });
+
+    VerificationReport {
+        model: model.address.to_string(),
+        owner: model.owner.to_string(),
+        storage_uri: model.storage_uri.to_string(),
+        blake3: blake3.to_hex().to_string(),
+        sha3_256: hex::encode(sha3),
+        onchain_model_hash: hex::encode(model.model_hash),
+        active_version: model.active_version,
+        version_count: model.version_history.len(),
+        version_root: root.map(hex::encode),
+        auditors,
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+        verified_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default(),
+    }
+}
+
+/// Sign the report's canonical JSON with the operator key
+pub fn attest(report: VerificationReport, signer: &dyn Signer) -> Result<SignedAttestation, serde_json::Error> {
+    let body = serde_json::to_vec(&report)?;
+    let signature = signer.sign_message(&body);
+    Ok(SignedAttestation {
+        report,
+        verifier: signer.pubkey().to_string(),
+        signature: signature.to_string(),
+    })
+}
+
+/// Message auditors sign when approving a model build; matches the on-chain `AuditRecord::message`
+pub fn audit_message(model_hash: &[u8; 32], version: u64, report_cid: &str) -> Vec<u8> {
+    [b"scoria-audit-v1".as_ref(), model_hash, &version.to_le_bytes(), report_cid.as_bytes()].concat()
+}
+
+/// Same pairing as `merkle_utils::merkle_root`: blake3(left || right), odd tails padded with zeros
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+pub fn version_root(versions: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let mut level = versions.to_vec();
+    if level.is_empty() {
+        return None;
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&[0u8; 32])))
+            .collect();
+    }
+    Some(level[0])
+}
+
+/// Sibling path for `index`, zero siblings included so every level is covered
+pub fn inclusion_proof(versions: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    let mut level = versions.to_vec();
+    while level.len() > 1 {
+        proof.push(*level.get(index ^ 1).unwrap_or(&[0u8; 32]));
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&[0u8; 32])))
+            .collect();
+        index /= 2;
+    }
+    proof
+}
+
+pub fn verify_inclusion(leaf: &[u8; 32], mut index: usize, proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof.iter().fold(*leaf, |acc, sibling| {
+        let next = if index & 1 == 0 { hash_pair(&acc, sibling) } else { hash_pair(sibling, &acc) };
+        index /= 2;
+        next
+    });
+    &computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    fn history(n: u8) -> Vec<[u8; 32]> {
+        (1..=n).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn test_inclusion_for_every_leaf() {
+        for n in 1..=7 {
+            let versions = history(n);
+            let root = version_root(&versions).unwrap();
+            for (i, leaf) in versions.iter().enumerate() {
+                let proof = inclusion_proof(&versions, i);
+                assert!(verify_inclusion(leaf, i, &proof, &root), "n={n} i={i}");
+                assert!(!verify_inclusion(&[0xff; 32], i, &proof, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_audit_detects_tampered_binary_and_bad_signatures() {
+        let plaintext = b"model weights";
+        let model_hash: [u8; 32] = Sha3_256::digest(plaintext).into();
+        let auditor = Keypair::new();
+        let cid = "bafyreport";
+        let signature = auditor.sign_message(&audit_message(&model_hash, 2, cid)).into();
+        let versions = vec![[1u8; 32], model_hash];
+
+        let good = AuditEntry { auditor: auditor.pubkey(), version: 2, report_cid: cid.into(), signature };
+        let audits = [good.clone(), AuditEntry { signature: [0u8; 64], ..good.clone() }];
+        let model = OnChainModel {
+            address: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            storage_uri: "ipfs://bafy",
+            model_hash,
+            active_version: 2,
+            version_history: &versions,
+            audits: &audits[..1],
+        };
+
+        let report = audit_model(&model, plaintext, &[auditor.pubkey()], None);
+        assert!(report.passed);
+
+        let tampered = audit_model(&model, b"other weights", &[auditor.pubkey()], None);
+        assert!(!tampered.passed);
+
+        let forged = OnChainModel { audits: &audits, ..model };
+        let report = audit_model(&forged, plaintext, &[auditor.pubkey()], None);
+        assert!(!report.passed);
+        assert!(report.auditors[0].valid && !report.auditors[1].valid);
+    }
+
+    #[test]
+    fn test_audit_rejects_stale_version_and_untrusted_auditor() {
+        let model_hash = [3u8; 32];
+        let auditor = Keypair::new();
+        let signature = auditor.sign_message(&audit_message(&model_hash, 1, "bafyold")).into();
+        let audits = [AuditEntry { auditor: auditor.pubkey(), version: 1, report_cid: "bafyold".into(), signature }];
+        let model = OnChainModel {
+            address: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            storage_uri: "ipfs://bafy",
+            model_hash,
+            active_version: 2,
+            version_history: &[model_hash],
+            audits: &audits,
+        };
+
+        assert!(!audit_model(&model, b"", &[auditor.pubkey()], None).auditors[0].valid);
+        let current = OnChainModel { active_version: 1, ..model };
+        assert!(audit_model(&current, b"", &[auditor.pubkey()], None).auditors[0].valid);
+        assert!(!audit_model(&current, b"", &[Pubkey::new_unique()], None).auditors[0].valid);
+    }
+}
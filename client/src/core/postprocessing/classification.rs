@@ -0,0 +1,82 @@
+// client/src/core/postprocessing/classification.rs
+
+use super::pipeline::{label, PostprocessError};
+use crate::core::inference::backend::TensorData;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClassificationSpec {
+    /// Class names by output index; unnamed classes are reported by index
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Apply softmax to logits; leave off for models that already output probabilities
+    #[serde(default)]
+    pub softmax: bool,
+    #[serde(default = "default_top_k")]
+    pub top_k: usize,
+}
+
+fn default_top_k() -> usize {
+    5
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Prediction {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub score: f32,
+}
+
+impl ClassificationSpec {
+    /// Top-k classes of each batch row of a `[batch, classes]` or `[classes]` output
+    pub fn apply(&self, output: &TensorData) -> Result<Vec<Vec<Prediction>>, PostprocessError> {
+        let classes = *output.shape.last().ok_or(PostprocessError::Shape("classification output is a scalar"))?;
+        if classes == 0 || output.shape.len() > 2 {
+            return Err(PostprocessError::Shape("classification output must be [batch, classes]"));
+        }
+
+        Ok(output
+            .data
+            .chunks_exact(classes)
+            .map(|row| {
+                let scores = if self.softmax { softmax(row) } else { row.to_vec() };
+                let mut ranked: Vec<usize> = (0..classes).collect();
+                ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+                ranked
+                    .into_iter()
+                    .take(self.top_k)
+                    .map(|index| Prediction { index, label: label(&self.labels, index), score: scores[index] })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// Numerically stable softmax
+pub fn softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exp: Vec<f32> = logits.iter().map(|&x| (x - max).exp()).collect();
+    let sum: f32 = exp.iter().sum();
+    exp.into_iter().map(|e| e / sum).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k_per_row_with_labels() {
+        let spec = ClassificationSpec { labels: vec!["cat".into(), "dog".into()], softmax: true, top_k: 2 };
+        let output = TensorData::new(vec![2, 3], vec![2.0, 0.0, 1.0, 0.0, 0.0, 5.0]).unwrap();
+        let rows = spec.apply(&output).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].iter().map(|p| p.index).collect::<Vec<_>>(), [0, 2]);
+        assert_eq!(rows[0][0].label.as_deref(), Some("cat"));
+        // Index 2 has no name
+        assert_eq!(rows[1][0].label, None);
+        assert!((rows[1][0].score - 0.986_7).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,163 @@
+// client/src/core/postprocessing/detection.rs
+
+//! Decoding of single-tensor detection heads (YOLO-style `[1, boxes, 4 + classes]`)
+//! followed by per-class non-maximum suppression.
+
+use super::pipeline::{label, PostprocessError};
+use crate::core::inference::backend::TensorData;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoxFormat {
+    /// Center, width and height, as YOLO heads emit
+    #[default]
+    Cxcywh,
+    /// Top-left and bottom-right corners
+    Xyxy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetectionSpec {
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub format: BoxFormat,
+    /// Output is `[1, 4 + classes, boxes]`, as YOLOv8 exports are
+    #[serde(default)]
+    pub transposed: bool,
+    /// A per-box objectness score follows the coordinates, as in YOLOv5
+    #[serde(default)]
+    pub objectness: bool,
+    #[serde(default = "default_score_threshold")]
+    pub score_threshold: f32,
+    #[serde(default = "default_iou_threshold")]
+    pub iou_threshold: f32,
+    #[serde(default = "default_max_detections")]
+    pub max_detections: usize,
+}
+
+fn default_score_threshold() -> f32 {
+    0.25
+}
+
+fn default_iou_threshold() -> f32 {
+    0.45
+}
+
+fn default_max_detections() -> usize {
+    100
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Detection {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub score: f32,
+    /// `[x1, y1, x2, y2]` in the model's input pixel space
+    #[serde(rename = "box")]
+    pub bbox: [f32; 4],
+}
+
+impl DetectionSpec {
+    pub fn apply(&self, output: &TensorData) -> Result<Vec<Detection>, PostprocessError> {
+        let [1, a, b] = output.shape[..] else {
+            return Err(PostprocessError::Shape("detection output must be [1, boxes, values]"));
+        };
+        let (boxes, width) = if self.transposed { (b, a) } else { (a, b) };
+        let offset = 4 + self.objectness as usize;
+        if width <= offset {
+            return Err(PostprocessError::Shape("detection rows hold no class scores"));
+        }
+        let value = |row: usize, col: usize| {
+            if self.transposed {
+                output.data[col * boxes + row]
+            } else {
+                output.data[row * width + col]
+            }
+        };
+
+        let mut candidates: Vec<Detection> = (0..boxes)
+            .filter_map(|row| {
+                let objectness = if self.objectness { value(row, 4) } else { 1.0 };
+                let (index, class_score) = (offset..width)
+                    .map(|col| (col - offset, value(row, col)))
+                    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+                let score = objectness * class_score;
+                (score >= self.score_threshold).then(|| Detection {
+                    index,
+                    label: label(&self.labels, index),
+                    score,
+                    bbox: self.corners([value(row, 0), value(row, 1), value(row, 2), value(row, 3)]),
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        // Greedy NMS within each class, highest scores first
+        let mut kept: Vec<Detection> = Vec::new();
+        for candidate in candidates {
+            if kept.len() == self.max_detections {
+                break;
+            }
+            let suppressed = kept
+                .iter()
+                .any(|k| k.index == candidate.index && iou(&k.bbox, &candidate.bbox) > self.iou_threshold);
+            if !suppressed {
+                kept.push(candidate);
+            }
+        }
+        Ok(kept)
+    }
+
+    fn corners(&self, raw: [f32; 4]) -> [f32; 4] {
+        match self.format {
+            BoxFormat::Xyxy => raw,
+            BoxFormat::Cxcywh => {
+                let [cx, cy, w, h] = raw;
+                [cx - w / 2.0, cy - h / 2.0, cx + w / 2.0, cy + h / 2.0]
+            }
+        }
+    }
+}
+
+fn iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let area = |r: &[f32; 4]| (r[2] - r[0]).max(0.0) * (r[3] - r[1]).max(0.0);
+    let overlap = [a[0].max(b[0]), a[1].max(b[1]), a[2].min(b[2]), a[3].min(b[3])];
+    let intersection = area(&overlap);
+    let union = area(a) + area(b) - intersection;
+    if union > 0.0 {
+        intersection / union
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nms_keeps_the_best_box_per_object_and_class() {
+        let spec: DetectionSpec = serde_json::from_str(r#"{"labels": ["person", "dog"]}"#).unwrap();
+        #[rustfmt::skip]
+        let rows = vec![
+            // cx, cy, w, h, person, dog
+            50.0, 50.0, 20.0, 20.0, 0.9, 0.1,
+            52.0, 51.0, 20.0, 20.0, 0.8, 0.1, // overlaps the first person
+            51.0, 50.0, 20.0, 20.0, 0.1, 0.7, // same place, different class
+            10.0, 10.0,  4.0,  4.0, 0.1, 0.2, // below the score threshold
+        ];
+        let detections = spec.apply(&TensorData::new(vec![1, 4, 6], rows).unwrap()).unwrap();
+
+        assert_eq!(detections.len(), 2);
+        assert_eq!((detections[0].label.as_deref(), detections[0].bbox), (Some("person"), [40.0, 40.0, 60.0, 60.0]));
+        assert_eq!(detections[1].label.as_deref(), Some("dog"));
+
+        let transposed = DetectionSpec { transposed: true, ..spec };
+        let columns = TensorData::new(vec![1, 6, 1], vec![50.0, 50.0, 20.0, 20.0, 0.1, 0.6]).unwrap();
+        assert_eq!(transposed.apply(&columns).unwrap()[0].index, 1);
+    }
+}
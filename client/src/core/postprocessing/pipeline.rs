@@ -0,0 +1,84 @@
+// client/src/core/postprocessing/pipeline.rs
+
+//! Output post-processing declared in a model's catalog document, turning
+//! raw tensors into labelled predictions:
+//! `"postprocess": {"kind": "classification", "labels": ["cat", "dog"], "softmax": true, "top_k": 5}`
+
+use super::{
+    classification::{ClassificationSpec, Prediction},
+    detection::{Detection, DetectionSpec},
+};
+use crate::core::inference::backend::TensorData;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PostprocessError {
+    #[error("Model produced no output tensor")]
+    NoOutput,
+    #[error("Unexpected output shape: {0}")]
+    Shape(&'static str),
+    #[error("Invalid post-processing: {0}")]
+    Invalid(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Postprocess {
+    Classification(ClassificationSpec),
+    Detection(DetectionSpec),
+}
+
+/// Readable inference result, written by `infer` in place of tensors
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Postprocessed {
+    /// Top-k predictions per batch row
+    Classification(Vec<Vec<Prediction>>),
+    Detection(Vec<Detection>),
+}
+
+impl Postprocess {
+    pub fn validate(&self) -> Result<(), PostprocessError> {
+        match self {
+            Postprocess::Classification(spec) if spec.top_k == 0 => Err(PostprocessError::Invalid("top_k must be positive")),
+            Postprocess::Detection(spec) if !(0.0..=1.0).contains(&spec.iou_threshold) => {
+                Err(PostprocessError::Invalid("iou_threshold must be within [0, 1]"))
+            }
+            Postprocess::Detection(spec) if spec.max_detections == 0 => {
+                Err(PostprocessError::Invalid("max_detections must be positive"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Applied to the model's first output tensor
+    pub fn apply(&self, outputs: &[TensorData]) -> Result<Postprocessed, PostprocessError> {
+        let output = outputs.first().ok_or(PostprocessError::NoOutput)?;
+        Ok(match self {
+            Postprocess::Classification(spec) => Postprocessed::Classification(spec.apply(output)?),
+            Postprocess::Detection(spec) => Postprocessed::Detection(spec.apply(output)?),
+        })
+    }
+}
+
+pub(super) fn label(labels: &[String], index: usize) -> Option<String> {
+    labels.get(index).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_is_tagged_by_kind() {
+        let spec: Postprocess = serde_json::from_str(r#"{"kind": "classification", "labels": ["cat"], "top_k": 1}"#).unwrap();
+        spec.validate().unwrap();
+        let result = spec.apply(&[TensorData::new(vec![1, 1], vec![0.5]).unwrap()]).unwrap();
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({"classification": [[{"index": 0, "label": "cat", "score": 0.5}]]})
+        );
+        assert!(matches!(spec.apply(&[]), Err(PostprocessError::NoOutput)));
+    }
+}
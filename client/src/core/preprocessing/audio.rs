@@ -0,0 +1,199 @@
+// client/src/core/preprocessing/audio.rs
+
+//! Log-mel spectrogram features from WAV input, the front end of speech
+//! models such as Whisper and wav2vec classifiers.
+
+use super::pipeline::PreprocessError;
+use crate::core::inference::backend::TensorData;
+use realfft::RealFftPlanner;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+use std::io::Read;
+
+/// Floor applied before the log so silence stays finite
+const LOG_FLOOR: f32 = 1e-10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioPreprocessing {
+    /// Input is resampled to this rate before feature extraction
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+    #[serde(default = "default_n_fft")]
+    pub n_fft: usize,
+    #[serde(default = "default_hop_length")]
+    pub hop_length: usize,
+    #[serde(default = "default_n_mels")]
+    pub n_mels: usize,
+    /// Pad or cut the clip to exactly this long, for fixed-shape models
+    #[serde(default)]
+    pub duration_secs: Option<f32>,
+}
+
+fn default_sample_rate() -> u32 {
+    16_000
+}
+
+fn default_n_fft() -> usize {
+    400
+}
+
+fn default_hop_length() -> usize {
+    160
+}
+
+fn default_n_mels() -> usize {
+    80
+}
+
+impl AudioPreprocessing {
+    pub fn validate(&self) -> Result<(), PreprocessError> {
+        if self.sample_rate == 0 || self.n_fft < 2 || self.hop_length == 0 || self.n_mels == 0 {
+            return Err(PreprocessError::Invalid("audio rates and sizes must be positive".into()));
+        }
+        if self.n_mels > self.n_fft / 2 {
+            return Err(PreprocessError::Invalid("n_mels must be at most n_fft / 2".into()));
+        }
+        if self.duration_secs.is_some_and(|d| !d.is_finite() || d <= 0.0) {
+            return Err(PreprocessError::Invalid("audio duration must be positive".into()));
+        }
+        Ok(())
+    }
+
+    /// `[1, n_mels, frames]` log-mel features of a WAV file, downmixed to mono
+    pub fn apply(&self, wav: impl Read) -> Result<TensorData, PreprocessError> {
+        let reader = hound::WavReader::new(wav)?;
+        let spec = reader.spec();
+        let interleaved: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let scale = (1u64 << (spec.bits_per_sample - 1)) as f32;
+                reader.into_samples::<i32>().map(|s| s.map(|s| s as f32 / scale)).collect::<Result<_, _>>()?
+            }
+        };
+        let channels = spec.channels.max(1) as usize;
+        let mono: Vec<f32> = interleaved.chunks(channels).map(|frame| frame.iter().sum::<f32>() / channels as f32).collect();
+        if mono.is_empty() {
+            return Err(PreprocessError::Empty);
+        }
+
+        let mut samples = resample(&mono, spec.sample_rate, self.sample_rate);
+        if let Some(duration) = self.duration_secs {
+            samples.resize((duration * self.sample_rate as f32).round() as usize, 0.0);
+        }
+        self.log_mel(samples)
+    }
+
+    fn log_mel(&self, mut samples: Vec<f32>) -> Result<TensorData, PreprocessError> {
+        if samples.len() < self.n_fft {
+            samples.resize(self.n_fft, 0.0);
+        }
+        let frames = 1 + (samples.len() - self.n_fft) / self.hop_length;
+        let bins = self.n_fft / 2 + 1;
+        let filters = mel_filters(self.sample_rate, self.n_fft, self.n_mels);
+        // Periodic Hann window
+        let window: Vec<f32> = (0..self.n_fft).map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / self.n_fft as f32).cos()).collect();
+
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(self.n_fft);
+        let mut input = fft.make_input_vec();
+        let mut spectrum = fft.make_output_vec();
+        let mut power = vec![0.0; bins];
+        // Mel-major, so the result is already `[n_mels, frames]`
+        let mut features = vec![0.0; self.n_mels * frames];
+        for frame in 0..frames {
+            let start = frame * self.hop_length;
+            for (slot, (sample, w)) in input.iter_mut().zip(samples[start..start + self.n_fft].iter().zip(&window)) {
+                *slot = sample * w;
+            }
+            fft.process(&mut input, &mut spectrum).map_err(|e| PreprocessError::Invalid(e.to_string()))?;
+            for (p, c) in power.iter_mut().zip(&spectrum) {
+                *p = c.norm_sqr();
+            }
+            for (mel, filter) in filters.chunks_exact(bins).enumerate() {
+                let energy: f32 = filter.iter().zip(&power).map(|(f, p)| f * p).sum();
+                features[mel * frames + frame] = energy.max(LOG_FLOOR).log10();
+            }
+        }
+        Ok(TensorData::new(vec![1, self.n_mels, frames], features)?)
+    }
+}
+
+/// Linear-interpolation resampling; adequate for speech front ends
+fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || from == 0 {
+        return samples.to_vec();
+    }
+    let ratio = from as f64 / to as f64;
+    let len = (samples.len() as f64 / ratio).round().max(1.0) as usize;
+    (0..len)
+        .map(|i| {
+            let position = i as f64 * ratio;
+            let index = position as usize;
+            let frac = (position - index as f64) as f32;
+            let a = samples[index.min(samples.len() - 1)];
+            let b = samples[(index + 1).min(samples.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+/// HTK-scale triangular filters, `n_mels` rows of `n_fft / 2 + 1` bin weights
+fn mel_filters(sample_rate: u32, n_fft: usize, n_mels: usize) -> Vec<f32> {
+    let hz_to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let mel_to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+
+    let bins = n_fft / 2 + 1;
+    let max_mel = hz_to_mel(sample_rate as f32 / 2.0);
+    // Filter edges, evenly spaced in mel and expressed in FFT bins
+    let edges: Vec<f32> = (0..n_mels + 2)
+        .map(|i| mel_to_hz(max_mel * i as f32 / (n_mels + 1) as f32) * n_fft as f32 / sample_rate as f32)
+        .collect();
+
+    let mut filters = vec![0.0; n_mels * bins];
+    for mel in 0..n_mels {
+        let (lower, center, upper) = (edges[mel], edges[mel + 1], edges[mel + 2]);
+        for bin in 0..bins {
+            let f = bin as f32;
+            let weight = if f <= center { (f - lower) / (center - lower) } else { (upper - f) / (upper - center) };
+            filters[mel * bins + bin] = weight.max(0.0);
+        }
+    }
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// One second of a 1 kHz stereo tone at 8 kHz, 16-bit
+    fn wav() -> Vec<u8> {
+        let spec = hound::WavSpec { channels: 2, sample_rate: 8_000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let mut out = Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut out, spec).unwrap();
+        for i in 0..8_000 {
+            let sample = ((2.0 * PI * 1_000.0 * i as f32 / 8_000.0).sin() * 16_000.0) as i16;
+            writer.write_sample(sample).unwrap();
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_tone_peaks_in_the_matching_mel_band() {
+        let spec = AudioPreprocessing { n_mels: 40, duration_secs: Some(0.5), ..serde_json::from_str("{}").unwrap() };
+        spec.validate().unwrap();
+        let features = spec.apply(Cursor::new(wav())).unwrap();
+
+        let frames = 1 + (8_000 - 400) / 160;
+        assert_eq!(features.shape, [1, 40, frames]);
+        let first_frame: Vec<f32> = (0..40).map(|mel| features.data[mel * frames]).collect();
+        let peak = (0..40).max_by(|&a, &b| first_frame[a].total_cmp(&first_frame[b])).unwrap();
+        // The filter centered nearest 1 kHz
+        let filters = mel_filters(16_000, 400, 40);
+        let bin = 1_000 * 400 / 16_000;
+        let expected = (0..40).max_by(|&a, &b| filters[a * 201 + bin].total_cmp(&filters[b * 201 + bin])).unwrap();
+        assert_eq!(peak, expected);
+    }
+}
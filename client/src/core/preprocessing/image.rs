@@ -0,0 +1,127 @@
+// client/src/core/preprocessing/image.rs
+
+use super::pipeline::PreprocessError;
+use crate::core::inference::backend::TensorData;
+use image::{imageops::FilterType, DynamicImage};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    /// `[1, 3, height, width]`, as PyTorch and ONNX vision models expect
+    #[default]
+    Nchw,
+    /// `[1, height, width, 3]`, as TensorFlow Lite models expect
+    Nhwc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImagePreprocessing {
+    pub width: u32,
+    pub height: u32,
+    /// Resize the short side and center-crop instead of stretching to fit
+    #[serde(default)]
+    pub center_crop: bool,
+    /// Per-channel RGB mean and std applied after scaling pixels to [0, 1]
+    #[serde(default = "default_mean")]
+    pub mean: [f32; 3],
+    #[serde(default = "default_std")]
+    pub std: [f32; 3],
+    #[serde(default)]
+    pub layout: Layout,
+}
+
+fn default_mean() -> [f32; 3] {
+    [0.0; 3]
+}
+
+fn default_std() -> [f32; 3] {
+    [1.0; 3]
+}
+
+impl ImagePreprocessing {
+    pub fn validate(&self) -> Result<(), PreprocessError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(PreprocessError::Invalid("image size must be positive".into()));
+        }
+        if self.std.iter().any(|s| !s.is_normal()) {
+            return Err(PreprocessError::Invalid("image std must be finite and non-zero".into()));
+        }
+        Ok(())
+    }
+
+    /// Decode, resize and normalize an encoded image (PNG, JPEG, WebP, ...)
+    pub fn apply(&self, encoded: &[u8]) -> Result<TensorData, PreprocessError> {
+        let image = image::load_from_memory(encoded)?;
+        let rgb = self.resize(image).to_rgb8();
+
+        let (w, h) = (self.width as usize, self.height as usize);
+        let mut data = vec![0.0; 3 * w * h];
+        for (x, y, pixel) in rgb.enumerate_pixels() {
+            let (x, y) = (x as usize, y as usize);
+            for c in 0..3 {
+                let value = (pixel[c] as f32 / 255.0 - self.mean[c]) / self.std[c];
+                let index = match self.layout {
+                    Layout::Nchw => (c * h + y) * w + x,
+                    Layout::Nhwc => (y * w + x) * 3 + c,
+                };
+                data[index] = value;
+            }
+        }
+        let shape = match self.layout {
+            Layout::Nchw => vec![1, 3, h, w],
+            Layout::Nhwc => vec![1, h, w, 3],
+        };
+        Ok(TensorData::new(shape, data)?)
+    }
+
+    fn resize(&self, image: DynamicImage) -> DynamicImage {
+        if !self.center_crop {
+            return image.resize_exact(self.width, self.height, FilterType::Triangle);
+        }
+        // Scale so the target fits inside, then cut the overhang evenly from both sides
+        let scale = (self.width as f32 / image.width() as f32).max(self.height as f32 / image.height() as f32);
+        let scaled_w = ((image.width() as f32 * scale).round() as u32).max(self.width);
+        let scaled_h = ((image.height() as f32 * scale).round() as u32).max(self.height);
+        image
+            .resize_exact(scaled_w, scaled_h, FilterType::Triangle)
+            .crop_imm((scaled_w - self.width) / 2, (scaled_h - self.height) / 2, self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageOutputFormat, Rgb, RgbImage};
+    use std::io::Cursor;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        // Left half red, right half blue
+        let image = RgbImage::from_fn(width, height, |x, _| if x < width / 2 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) });
+        let mut encoded = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(image).write_to(&mut encoded, ImageOutputFormat::Png).unwrap();
+        encoded.into_inner()
+    }
+
+    #[test]
+    fn test_resizes_and_normalizes_into_each_layout() {
+        let spec = ImagePreprocessing {
+            width: 4,
+            height: 2,
+            center_crop: false,
+            mean: [0.5; 3],
+            std: [0.5; 3],
+            layout: Layout::Nchw,
+        };
+        let nchw = spec.apply(&png(4, 2)).unwrap();
+        assert_eq!(nchw.shape, [1, 3, 2, 4]);
+        // Red channel, row 0: red on the left, none on the right
+        assert_eq!(nchw.data[..4], [1.0, 1.0, -1.0, -1.0]);
+
+        let cropped = ImagePreprocessing { center_crop: true, layout: Layout::Nhwc, ..spec };
+        let nhwc = cropped.apply(&png(16, 16)).unwrap();
+        assert_eq!(nhwc.shape, [1, 2, 4, 3]);
+        assert_eq!(nhwc.data[..3], [1.0, -1.0, -1.0]);
+    }
+}
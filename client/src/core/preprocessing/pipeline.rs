@@ -0,0 +1,123 @@
+// client/src/core/preprocessing/pipeline.rs
+
+//! Raw-input preprocessing declared in a model's catalog document, so
+//! `infer` takes text, images and audio as well as prepared tensors:
+//! `"preprocessing": {"kind": "image", "width": 224, "height": 224, "mean": [0.485, 0.456, 0.406], ...}`
+
+use super::{
+    audio::AudioPreprocessing,
+    image::ImagePreprocessing,
+    text::{TextEncoder, TextPreprocessing},
+};
+use crate::core::inference::{
+    backend::{BackendError, TensorData},
+    io_schema::{SchemaError, SchemaStore},
+};
+use serde::{Deserialize, Serialize};
+use std::io::BufReader;
+use std::path::Path;
+use thiserror::Error;
+
+const TEXT_EXTENSIONS: [&str; 2] = ["txt", "text"];
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "webp", "bmp", "gif"];
+const AUDIO_EXTENSIONS: [&str; 1] = ["wav"];
+
+#[derive(Debug, Error)]
+pub enum PreprocessError {
+    #[error("Input I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Tokenizer error: {0}")]
+    Tokenizer(String),
+    #[error("Image decoding failed: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("Audio decoding failed: {0}")]
+    Audio(#[from] hound::Error),
+    #[error("Tokenizer unavailable: {0}")]
+    Fetch(#[from] SchemaError),
+    #[error("Invalid preprocessing: {0}")]
+    Invalid(String),
+    #[error("Input contains no data")]
+    Empty,
+    #[error(transparent)]
+    Tensor(#[from] BackendError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Preprocessing {
+    Text(TextPreprocessing),
+    Image(ImagePreprocessing),
+    Audio(AudioPreprocessing),
+}
+
+impl Preprocessing {
+    pub fn validate(&self) -> Result<(), PreprocessError> {
+        match self {
+            Preprocessing::Text(text) if text.max_length == 0 => {
+                Err(PreprocessError::Invalid("max_length must be positive".into()))
+            }
+            Preprocessing::Text(_) => Ok(()),
+            Preprocessing::Image(image) => image.validate(),
+            Preprocessing::Audio(audio) => audio.validate(),
+        }
+    }
+
+    /// Whether `path` is raw input for this step rather than prepared tensors
+    pub fn accepts(&self, path: &Path) -> bool {
+        let extensions: &[&str] = match self {
+            Preprocessing::Text(_) => &TEXT_EXTENSIONS,
+            Preprocessing::Image(_) => &IMAGE_EXTENSIONS,
+            Preprocessing::Audio(_) => &AUDIO_EXTENSIONS,
+        };
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| extensions.iter().any(|x| x.eq_ignore_ascii_case(e)))
+    }
+}
+
+/// A declared preprocessing step with its tokenizer, if any, fetched and verified
+pub enum Preprocessor {
+    Text(TextEncoder),
+    Image(ImagePreprocessing),
+    Audio(AudioPreprocessing),
+}
+
+impl Preprocessor {
+    /// Tokenizers are fetched by hash through the schema store and cached with schemas
+    pub async fn load(spec: Preprocessing, store: &SchemaStore) -> Result<Self, PreprocessError> {
+        spec.validate()?;
+        Ok(match spec {
+            Preprocessing::Text(text) => {
+                let tokenizer = store.fetch_document(&text.tokenizer).await?;
+                Preprocessor::Text(TextEncoder::new(text, &tokenizer)?)
+            }
+            Preprocessing::Image(image) => Preprocessor::Image(image),
+            Preprocessing::Audio(audio) => Preprocessor::Audio(audio),
+        })
+    }
+
+    pub fn run(&self, input: &Path) -> Result<Vec<TensorData>, PreprocessError> {
+        match self {
+            Preprocessor::Text(encoder) => encoder.encode(BufReader::new(std::fs::File::open(input)?)),
+            Preprocessor::Image(image) => Ok(vec![image.apply(&std::fs::read(input)?)?]),
+            Preprocessor::Audio(audio) => Ok(vec![audio.apply(BufReader::new(std::fs::File::open(input)?))?]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declarations_parse_and_match_input_files() {
+        let spec: Preprocessing = serde_json::from_str(r#"{"kind": "image", "width": 224, "height": 224}"#).unwrap();
+        assert!(spec.accepts(Path::new("cat.JPG")));
+        assert!(!spec.accepts(Path::new("cat.json")));
+        spec.validate().unwrap();
+
+        let audio: Preprocessing = serde_json::from_str(r#"{"kind": "audio", "n_fft": 64, "n_mels": 80}"#).unwrap();
+        assert!(matches!(audio.validate(), Err(PreprocessError::Invalid(_))));
+        assert!(serde_json::from_str::<Preprocessing>(r#"{"kind": "video"}"#).is_err());
+    }
+}
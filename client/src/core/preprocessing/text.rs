@@ -0,0 +1,133 @@
+// client/src/core/preprocessing/text.rs
+
+//! HuggingFace `tokenizer.json` encoding (BPE, WordPiece, Unigram). Input is
+//! read a line at a time and tokenized in batches, so large corpora never sit
+//! in memory as text; every line becomes one row padded to `max_length`.
+
+use super::pipeline::PreprocessError;
+use crate::core::inference::backend::TensorData;
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
+
+/// Lines handed to the tokenizer per `encode_batch` call
+const TOKENIZE_BATCH: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TextPreprocessing {
+    /// SHA2-256 of the `tokenizer.json`, pinned like I/O schemas
+    #[serde(with = "hex::serde")]
+    pub tokenizer: [u8; 32],
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+    #[serde(default = "default_true")]
+    pub add_special_tokens: bool,
+    /// Emit a third `token_type_ids` tensor, as BERT-style models expect
+    #[serde(default)]
+    pub token_type_ids: bool,
+}
+
+fn default_max_length() -> usize {
+    512
+}
+
+fn default_true() -> bool {
+    true
+}
+
+pub struct TextEncoder {
+    tokenizer: Tokenizer,
+    spec: TextPreprocessing,
+}
+
+impl TextEncoder {
+    /// `tokenizer_json` must already be checked against `spec.tokenizer`
+    pub fn new(spec: TextPreprocessing, tokenizer_json: &[u8]) -> Result<Self, PreprocessError> {
+        let mut tokenizer = Tokenizer::from_bytes(tokenizer_json).map_err(|e| PreprocessError::Tokenizer(e.to_string()))?;
+        tokenizer
+            .with_truncation(Some(TruncationParams { max_length: spec.max_length, ..Default::default() }))
+            .map_err(|e| PreprocessError::Tokenizer(e.to_string()))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::Fixed(spec.max_length),
+            ..Default::default()
+        }));
+        Ok(Self { tokenizer, spec })
+    }
+
+    /// `input_ids` and `attention_mask` (and `token_type_ids` when declared),
+    /// each `[lines, max_length]`. Blank lines are skipped.
+    pub fn encode(&self, reader: impl BufRead) -> Result<Vec<TensorData>, PreprocessError> {
+        let outputs = if self.spec.token_type_ids { 3 } else { 2 };
+        let mut columns = vec![Vec::new(); outputs];
+        let mut rows = 0;
+
+        let mut batch = Vec::with_capacity(TOKENIZE_BATCH);
+        let mut lines = reader.lines();
+        loop {
+            let line = lines.next().transpose()?;
+            if let Some(line) = line.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+                batch.push(line.to_string());
+            }
+            if batch.len() == TOKENIZE_BATCH || (line.is_none() && !batch.is_empty()) {
+                let encodings = self
+                    .tokenizer
+                    .encode_batch(std::mem::take(&mut batch), self.spec.add_special_tokens)
+                    .map_err(|e| PreprocessError::Tokenizer(e.to_string()))?;
+                for encoding in &encodings {
+                    columns[0].extend(encoding.get_ids().iter().map(|&id| id as f32));
+                    columns[1].extend(encoding.get_attention_mask().iter().map(|&m| m as f32));
+                    if self.spec.token_type_ids {
+                        columns[2].extend(encoding.get_type_ids().iter().map(|&t| t as f32));
+                    }
+                }
+                rows += encodings.len();
+            }
+            if line.is_none() {
+                break;
+            }
+        }
+        if rows == 0 {
+            return Err(PreprocessError::Empty);
+        }
+
+        columns
+            .into_iter()
+            .map(|data| TensorData::new(vec![rows, self.spec.max_length], data).map_err(PreprocessError::from))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal WordPiece tokenizer over a six-token vocabulary
+    const TOKENIZER: &str = r###"{
+        "version": "1.0", "truncation": null, "padding": null, "added_tokens": [],
+        "normalizer": {"type": "Lowercase"},
+        "pre_tokenizer": {"type": "Whitespace"},
+        "post_processor": null, "decoder": null,
+        "model": {"type": "WordPiece", "unk_token": "[UNK]", "continuing_subword_prefix": "##",
+                  "max_input_chars_per_word": 100,
+                  "vocab": {"[PAD]": 0, "[UNK]": 1, "hello": 2, "world": 3, "scor": 4, "##ia": 5}}
+    }"###;
+
+    #[test]
+    fn test_lines_become_padded_rows() {
+        let spec = TextPreprocessing {
+            tokenizer: [0; 32],
+            max_length: 4,
+            add_special_tokens: false,
+            token_type_ids: false,
+        };
+        let encoder = TextEncoder::new(spec, TOKENIZER.as_bytes()).unwrap();
+        let tensors = encoder.encode("Hello Scoria\n\nhello world hello world hello\n".as_bytes()).unwrap();
+
+        assert_eq!(tensors.len(), 2);
+        assert_eq!(tensors[0].shape, [2, 4]);
+        assert_eq!(tensors[0].data, [2.0, 4.0, 5.0, 0.0, 2.0, 3.0, 2.0, 3.0]);
+        assert_eq!(tensors[1].data, [1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+        assert!(matches!(encoder.encode("\n \n".as_bytes()), Err(PreprocessError::Empty)));
+    }
+}
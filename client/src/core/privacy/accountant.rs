@@ -0,0 +1,145 @@
+// client/src/core/privacy/accountant.rs
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PrivacyError {
+    #[error("Invalid privacy parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Privacy budget exhausted: query `{query}` needs rho={requested:.6}, {remaining:.6} left")]
+    BudgetExceeded {
+        query: String,
+        requested: f64,
+        remaining: f64,
+    },
+}
+
+/// One charged query, kept for the contribution's privacy report
+#[derive(Debug, Clone, PartialEq)]
+pub struct Charge {
+    pub query: String,
+    pub rho: f64,
+}
+
+/// Zero-concentrated DP (zCDP) accountant.
+///
+/// Gaussian mechanisms compose additively in rho, so the ledger is a sum; the
+/// (epsilon, delta) target is converted to a rho budget once, up front.
+#[derive(Debug, Clone)]
+pub struct ZcdpAccountant {
+    epsilon: f64,
+    delta: f64,
+    budget_rho: f64,
+    ledger: Vec<Charge>,
+}
+
+impl ZcdpAccountant {
+    pub fn new(epsilon: f64, delta: f64) -> Result<Self, PrivacyError> {
+        if !(epsilon > 0.0 && epsilon.is_finite()) {
+            return Err(PrivacyError::InvalidParameter(format!("epsilon must be positive, got {epsilon}")));
+        }
+        if !(delta > 0.0 && delta < 1.0) {
+            return Err(PrivacyError::InvalidParameter(format!("delta must be in (0, 1), got {delta}")));
+        }
+        Ok(Self {
+            epsilon,
+            delta,
+            budget_rho: rho_for_epsilon(epsilon, delta),
+            ledger: Vec::new(),
+        })
+    }
+
+    /// Total rho the (epsilon, delta) target allows
+    pub fn budget_rho(&self) -> f64 {
+        self.budget_rho
+    }
+
+    pub fn spent_rho(&self) -> f64 {
+        self.ledger.iter().map(|c| c.rho).sum()
+    }
+
+    pub fn remaining_rho(&self) -> f64 {
+        (self.budget_rho - self.spent_rho()).max(0.0)
+    }
+
+    /// Epsilon spent so far at the accountant's delta
+    pub fn spent_epsilon(&self) -> f64 {
+        epsilon_for_rho(self.spent_rho(), self.delta)
+    }
+
+    pub fn target(&self) -> (f64, f64) {
+        (self.epsilon, self.delta)
+    }
+
+    pub fn ledger(&self) -> &[Charge] {
+        &self.ledger
+    }
+
+    /// Record a query, refusing it when the budget cannot cover it
+    pub fn charge(&mut self, query: &str, rho: f64) -> Result<(), PrivacyError> {
+        if !(rho > 0.0 && rho.is_finite()) {
+            return Err(PrivacyError::InvalidParameter(format!("rho must be positive, got {rho}")));
+        }
+        let remaining = self.remaining_rho();
+        // Tolerate float error when a plan spends the budget exactly
+        if rho > remaining * (1.0 + 1e-9) {
+            return Err(PrivacyError::BudgetExceeded {
+                query: query.to_string(),
+                requested: rho,
+                remaining,
+            });
+        }
+        self.ledger.push(Charge { query: query.to_string(), rho });
+        Ok(())
+    }
+}
+
+/// Tightest standard conversion: rho-zCDP implies (rho + 2*sqrt(rho*ln(1/delta)), delta)-DP
+pub fn epsilon_for_rho(rho: f64, delta: f64) -> f64 {
+    rho + 2.0 * (rho * (1.0 / delta).ln()).sqrt()
+}
+
+/// Inverse of `epsilon_for_rho`
+pub fn rho_for_epsilon(epsilon: f64, delta: f64) -> f64 {
+    let log_term = (1.0 / delta).ln();
+    ((epsilon + log_term).sqrt() - log_term.sqrt()).powi(2)
+}
+
+/// Gaussian noise scale giving rho-zCDP for a query with this L2 sensitivity
+pub fn gaussian_sigma(sensitivity: f64, rho: f64) -> f64 {
+    sensitivity / (2.0 * rho).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rho_conversion_round_trips() {
+        for (epsilon, delta) in [(0.1, 1e-6), (1.0, 1e-5), (8.0, 1e-9)] {
+            let rho = rho_for_epsilon(epsilon, delta);
+            assert!((epsilon_for_rho(rho, delta) - epsilon).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn charges_compose_until_budget_is_spent() {
+        let mut accountant = ZcdpAccountant::new(1.0, 1e-5).unwrap();
+        let half = accountant.budget_rho() / 2.0;
+
+        accountant.charge("count", half).unwrap();
+        accountant.charge("histogram", half).unwrap();
+        assert!((accountant.spent_epsilon() - 1.0).abs() < 1e-9);
+
+        let err = accountant.charge("mean", 1e-6).unwrap_err();
+        assert!(matches!(err, PrivacyError::BudgetExceeded { .. }));
+        assert_eq!(accountant.ledger().len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_targets() {
+        assert!(ZcdpAccountant::new(0.0, 1e-5).is_err());
+        assert!(ZcdpAccountant::new(1.0, 0.0).is_err());
+        assert!(ZcdpAccountant::new(1.0, 1.5).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+// client/src/core/privacy/dp.rs
+
+use super::accountant::{gaussian_sigma, PrivacyError, ZcdpAccountant};
+use rand::{rngs::OsRng, RngCore};
+use rand_distr::{Distribution, Normal};
+
+/// Per-query privacy settings
+#[derive(Debug, Clone, Copy)]
+pub struct QueryConfig {
+    /// Most one record can change the query's output (L2)
+    pub sensitivity: f64,
+    /// Fraction of the *total* budget this query spends, in (0, 1]
+    pub budget_share: f64,
+}
+
+impl QueryConfig {
+    pub fn new(sensitivity: f64, budget_share: f64) -> Self {
+        Self { sensitivity, budget_share }
+    }
+}
+
+/// Differentially private aggregate queries over a contribution.
+///
+/// Every query adds Gaussian noise and is charged to a zCDP accountant; once
+/// the (epsilon, delta) target is reached further queries are refused.
+pub struct DifferentialPrivacy<R: RngCore = OsRng> {
+    accountant: ZcdpAccountant,
+    rng: R,
+}
+
+impl DifferentialPrivacy<OsRng> {
+    pub fn new(epsilon: f64, delta: f64) -> Result<Self, PrivacyError> {
+        Self::with_rng(epsilon, delta, OsRng)
+    }
+}
+
+impl<R: RngCore> DifferentialPrivacy<R> {
+    pub fn with_rng(epsilon: f64, delta: f64, rng: R) -> Result<Self, PrivacyError> {
+        Ok(Self {
+            accountant: ZcdpAccountant::new(epsilon, delta)?,
+            rng,
+        })
+    }
+
+    pub fn accountant(&self) -> &ZcdpAccountant {
+        &self.accountant
+    }
+
+    /// Noisy record count; clamped at zero
+    pub fn noisy_count(&mut self, count: u64, query: QueryConfig) -> Result<f64, PrivacyError> {
+        let sigma = self.spend("count", query)?;
+        Ok((count as f64 + self.sample(sigma)?).max(0.0))
+    }
+
+    /// Noisy per-bin counts, each record falling in one bin; one charge for the whole histogram
+    pub fn noisy_histogram(&mut self, counts: &[u64], query: QueryConfig) -> Result<Vec<f64>, PrivacyError> {
+        let sigma = self.spend("histogram", query)?;
+        counts
+            .iter()
+            .map(|&c| Ok((c as f64 + self.sample(sigma)?).max(0.0)))
+            .collect()
+    }
+
+    /// Noisy mean of values clamped to `[lower, upper]`; the share is split between sum and count
+    pub fn noisy_mean(&mut self, values: &[f64], lower: f64, upper: f64, query: QueryConfig) -> Result<f64, PrivacyError> {
+        if lower.partial_cmp(&upper) != Some(std::cmp::Ordering::Less) {
+            return Err(PrivacyError::InvalidParameter(format!("empty clamp range [{lower}, {upper}]")));
+        }
+        let half = QueryConfig::new(query.sensitivity, query.budget_share / 2.0);
+        let bound = lower.abs().max(upper.abs());
+
+        // One charge covers both halves, taken before sampling so a refusal spends nothing
+        let sum_sigma = gaussian_sigma(bound * half.sensitivity, self.rho_for(half)?);
+        let count_sigma = gaussian_sigma(half.sensitivity, self.rho_for(half)?);
+        self.accountant.charge("mean", self.rho_for(query)?)?;
+
+        let sum: f64 = values.iter().map(|v| v.clamp(lower, upper)).sum();
+        let noisy_sum = sum + self.sample(sum_sigma)?;
+        let noisy_count = (values.len() as f64 + self.sample(count_sigma)?).max(1.0);
+        Ok((noisy_sum / noisy_count).clamp(lower, upper))
+    }
+
+    fn rho_for(&self, query: QueryConfig) -> Result<f64, PrivacyError> {
+        if !(query.budget_share > 0.0 && query.budget_share <= 1.0) {
+            return Err(PrivacyError::InvalidParameter(format!(
+                "budget share must be in (0, 1], got {}",
+                query.budget_share
+            )));
+        }
+        if !(query.sensitivity > 0.0 && query.sensitivity.is_finite()) {
+            return Err(PrivacyError::InvalidParameter(format!(
+                "sensitivity must be positive, got {}",
+                query.sensitivity
+            )));
+        }
+        Ok(self.accountant.budget_rho() * query.budget_share)
+    }
+
+    /// Charge the accountant and return the calibrated noise scale
+    fn spend(&mut self, name: &str, query: QueryConfig) -> Result<f64, PrivacyError> {
+        let rho = self.rho_for(query)?;
+        self.accountant.charge(name, rho)?;
+        Ok(gaussian_sigma(query.sensitivity, rho))
+    }
+
+    fn sample(&mut self, sigma: f64) -> Result<f64, PrivacyError> {
+        let normal = Normal::new(0.0, sigma).map_err(|e| PrivacyError::InvalidParameter(e.to_string()))?;
+        Ok(normal.sample(&mut self.rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn dp(epsilon: f64) -> DifferentialPrivacy<StdRng> {
+        DifferentialPrivacy::with_rng(epsilon, 1e-6, StdRng::seed_from_u64(7)).unwrap()
+    }
+
+    #[test]
+    fn histogram_is_charged_once() {
+        let mut dp = dp(1.0);
+        let noisy = dp.noisy_histogram(&[10, 20, 30, 40], QueryConfig::new(1.0, 0.5)).unwrap();
+
+        assert_eq!(noisy.len(), 4);
+        assert!(noisy.iter().all(|&c| c >= 0.0));
+        assert_eq!(dp.accountant().ledger().len(), 1);
+    }
+
+    #[test]
+    fn refuses_queries_past_the_budget() {
+        let mut dp = dp(1.0);
+        dp.noisy_count(100, QueryConfig::new(1.0, 0.6)).unwrap();
+
+        let err = dp.noisy_count(100, QueryConfig::new(1.0, 0.6)).unwrap_err();
+        assert!(matches!(err, PrivacyError::BudgetExceeded { .. }));
+        assert!(dp.accountant().spent_epsilon() <= 1.0);
+    }
+
+    #[test]
+    fn mean_is_close_with_a_large_budget() {
+        let mut dp = dp(1000.0);
+        let values: Vec<f64> = (0..10_000).map(|i| (i % 10) as f64).collect();
+
+        let mean = dp.noisy_mean(&values, 0.0, 10.0, QueryConfig::new(1.0, 1.0)).unwrap();
+        assert!((mean - 4.5).abs() < 0.1, "mean {mean}");
+    }
+
+    #[test]
+    fn refused_mean_spends_nothing() {
+        let mut dp = dp(1.0);
+        dp.noisy_count(1, QueryConfig::new(1.0, 0.9)).unwrap();
+
+        assert!(dp.noisy_mean(&[1.0], 0.0, 1.0, QueryConfig::new(1.0, 0.5)).is_err());
+        assert_eq!(dp.accountant().ledger().len(), 1);
+    }
+}
@@ -0,0 +1,227 @@
+// client/src/core/storage/distribution.rs
+
+//! Erasure-coded model distribution. Deploys spread shards across IPFS and,
+//! with a bundler configured, Arweave; the loader fetches them in parallel
+//! over every configured gateway and stops as soon as any `data_shards`
+//! verified shards are in.
+
+use super::{
+    erasure::{self, ErasureError, ErasureManifest, ShardEntry, ShardSource},
+    ipfs::{IpfsStorage, StorageError},
+};
+use crate::config::DistributionConfig;
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Env var holding the bundler token when `arweave_token` is not configured
+pub const ARWEAVE_TOKEN_ENV: &str = "SCORIA_ARWEAVE_TOKEN";
+
+#[derive(Debug, Error)]
+pub enum DistributionError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    Erasure(#[from] ErasureError),
+    #[error("Arweave upload failed: {0}")]
+    Arweave(#[from] reqwest::Error),
+    #[error("No Arweave bundler token; set `distribution.arweave_token` or {ARWEAVE_TOKEN_ENV}")]
+    MissingToken,
+    #[error("Manifest {cid} is unavailable from every IPFS gateway")]
+    ManifestUnavailable { cid: String },
+}
+
+/// Bundler `POST /tx` response
+#[derive(Deserialize)]
+struct BundledTx {
+    id: String,
+}
+
+pub struct ModelDistributor {
+    config: DistributionConfig,
+    http: reqwest::Client,
+}
+
+impl ModelDistributor {
+    pub fn new(config: DistributionConfig) -> Self {
+        Self { config, http: reqwest::Client::new() }
+    }
+
+    /// Erasure-code `blob`, upload every shard and the manifest, and return
+    /// the `storage_uri` to register
+    pub async fn publish(&self, storage: &IpfsStorage, blob: &[u8], name: &str) -> Result<String, DistributionError> {
+        let (mut manifest, shards) = erasure::encode(blob, self.config.data_shards, self.config.parity_shards)?;
+        let uploads = manifest.shards.iter().zip(shards).map(|(entry, shard)| async move {
+            let shard_name = format!("{name}:shard:{}", entry.index);
+            let mut sources = Vec::new();
+            if let Some(bundler) = &self.config.arweave_bundler {
+                sources.push(ShardSource::Arweave { tx: self.upload_arweave(bundler, shard.clone()).await? });
+            }
+            let cid = storage.upload_bytes(shard, &shard_name, &[("kind", "shard")]).await?;
+            sources.insert(0, ShardSource::Ipfs { cid });
+            Ok::<_, DistributionError>(sources)
+        });
+        let sources: Vec<_> = stream::iter(uploads).buffered(self.config.max_parallel.max(1)).collect().await;
+        for (entry, sources) in manifest.shards.iter_mut().zip(sources) {
+            entry.sources = sources?;
+        }
+
+        let manifest_json = serde_json::to_vec(&manifest).map_err(ErasureError::from)?;
+        let cid = storage.upload_bytes(manifest_json, &format!("{name}:manifest"), &[("kind", "manifest")]).await?;
+        tracing::info!(
+            %cid,
+            data_shards = manifest.data_shards,
+            parity_shards = manifest.parity_shards,
+            shard_size = manifest.shard_size,
+            "Model distributed"
+        );
+        Ok(ErasureManifest::storage_uri(&cid))
+    }
+
+    /// Rebuild an erasure-coded model from whichever shard sources answer first
+    pub async fn fetch(&self, manifest_cid: &str) -> Result<Vec<u8>, DistributionError> {
+        let manifest = self.fetch_manifest(manifest_cid).await?;
+        let needed = manifest.data_shards;
+
+        // Data shards are requested first: with all of them in, decoding is a copy
+        let mut downloads = stream::iter(&manifest.shards)
+            .map(|entry| async move { (entry.index, self.fetch_shard(entry).await) })
+            .buffer_unordered(self.config.max_parallel.max(1));
+        let mut shards = vec![None; manifest.shards.len()];
+        let mut available = 0;
+        while let Some((index, shard)) = downloads.next().await {
+            if let Some(shard) = shard {
+                shards[index] = Some(shard);
+                available += 1;
+                if available == needed {
+                    break;
+                }
+            }
+        }
+        // Dropping the stream cancels downloads still in flight
+        drop(downloads);
+
+        Ok(erasure::reconstruct(&manifest, shards)?)
+    }
+
+    async fn fetch_manifest(&self, cid: &str) -> Result<ErasureManifest, DistributionError> {
+        let bytes = self.fetch_document(cid, |bytes| ErasureManifest::parse(bytes).is_ok()).await?;
+        Ok(ErasureManifest::parse(&bytes)?)
+    }
+
+    /// Small IPFS document from the first gateway whose copy `valid` accepts
+    pub async fn fetch_document(&self, cid: &str, valid: impl Fn(&[u8]) -> bool) -> Result<Vec<u8>, DistributionError> {
+        for (source, url) in self.candidates(&[ShardSource::Ipfs { cid: cid.to_string() }], 0) {
+            if let Some(bytes) = self.download(&source, &url, &valid).await {
+                return Ok(bytes);
+            }
+        }
+        Err(DistributionError::ManifestUnavailable { cid: cid.to_string() })
+    }
+
+    /// Try every source of one shard until a copy matches its hash
+    async fn fetch_shard(&self, entry: &ShardEntry) -> Option<Vec<u8>> {
+        for (source, url) in self.candidates(&entry.sources, entry.index) {
+            if let Some(shard) = self.download(&source, &url, |bytes| blake3::hash(bytes) == entry.hash).await {
+                return Some(shard);
+            }
+        }
+        tracing::warn!(shard = entry.index, "Shard unavailable from every source");
+        None
+    }
+
+    /// Gateway base URL and full URL for each copy of a shard. The list is
+    /// rotated by shard index so parallel downloads start on different gateways.
+    fn candidates(&self, sources: &[ShardSource], rotation: usize) -> Vec<(String, String)> {
+        let mut candidates: Vec<(String, String)> = sources
+            .iter()
+            .flat_map(|source| {
+                let (gateways, path) = match source {
+                    ShardSource::Ipfs { cid } => (&self.config.ipfs_gateways, format!("ipfs/{cid}")),
+                    ShardSource::Arweave { tx } => (&self.config.arweave_gateways, tx.clone()),
+                };
+                gateways.iter().map(move |gateway| {
+                    let gateway = gateway.trim_end_matches('/');
+                    (gateway.to_string(), format!("{gateway}/{path}"))
+                })
+            })
+            .collect();
+        if !candidates.is_empty() {
+            let len = candidates.len();
+            candidates.rotate_left(rotation % len);
+        }
+        candidates
+    }
+
+    /// One download attempt, recorded against `source`; `None` unless `valid` accepts the body
+    async fn download(&self, source: &str, url: &str, valid: impl Fn(&[u8]) -> bool) -> Option<Vec<u8>> {
+        let started = Instant::now();
+        let response = async {
+            self.http
+                .get(url)
+                .timeout(Duration::from_secs(self.config.shard_timeout_secs))
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await
+        }
+        .await;
+        let (outcome, bytes, body) = match response {
+            Ok(body) if valid(&body) => ("ok", body.len(), Some(body.to_vec())),
+            Ok(body) => {
+                tracing::warn!(%url, "Source returned corrupt data");
+                ("corrupt", body.len(), None)
+            }
+            Err(e) => {
+                tracing::debug!(%url, error = %e, "Source download failed");
+                ("error", 0, None)
+            }
+        };
+        crate::metrics::log_source_fetch(source, bytes, started.elapsed(), outcome);
+        body
+    }
+
+    async fn upload_arweave(&self, bundler: &str, shard: Vec<u8>) -> Result<String, DistributionError> {
+        let token = match &self.config.arweave_token {
+            Some(token) => token.clone(),
+            None => std::env::var(ARWEAVE_TOKEN_ENV).map_err(|_| DistributionError::MissingToken)?,
+        };
+        let bundled: BundledTx = self
+            .http
+            .post(format!("{}/tx", bundler.trim_end_matches('/')))
+            .bearer_auth(token)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(shard)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(bundled.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates_cover_every_gateway_and_rotate_per_shard() {
+        let distributor = ModelDistributor::new(DistributionConfig {
+            ipfs_gateways: vec!["https://ipfs.io/".into(), "https://dweb.link".into()],
+            arweave_gateways: vec!["https://arweave.net".into()],
+            ..Default::default()
+        });
+        let sources = [ShardSource::Ipfs { cid: "bafy".into() }, ShardSource::Arweave { tx: "tx1".into() }];
+
+        let first = distributor.candidates(&sources, 0);
+        assert_eq!(
+            first.iter().map(|(_, url)| url.as_str()).collect::<Vec<_>>(),
+            ["https://ipfs.io/ipfs/bafy", "https://dweb.link/ipfs/bafy", "https://arweave.net/tx1"]
+        );
+        assert_eq!(first[0].0, "https://ipfs.io");
+        assert_eq!(distributor.candidates(&sources, 4)[0].1, "https://dweb.link/ipfs/bafy");
+    }
+}
@@ -0,0 +1,174 @@
+// client/src/core/storage/erasure.rs
+
+//! Reed-Solomon coding of model blobs into `data_shards + parity_shards`
+//! shards, any `data_shards` of which rebuild the blob.
+
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub const MANIFEST_VERSION: u8 = 1;
+/// `storage_uri` prefix of erasure-coded models; the rest is the manifest's CID
+const URI_SCHEME: &str = "scoria-ec://";
+
+#[derive(Debug, Error)]
+pub enum ErasureError {
+    #[error("Reed-Solomon coding failed: {0}")]
+    Codec(#[from] reed_solomon_erasure::Error),
+    #[error("Invalid shard manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    #[error("Unsupported shard manifest: {0}")]
+    Unsupported(String),
+    #[error("Only {available} of the {needed} shards needed could be fetched")]
+    NotEnoughShards { available: usize, needed: usize },
+    #[error("Reconstructed model does not match the manifest hash")]
+    HashMismatch,
+}
+
+/// Where one copy of a shard is stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ShardSource {
+    Ipfs { cid: String },
+    Arweave { tx: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShardEntry {
+    pub index: usize,
+    /// blake3 of the shard, checked before it is used
+    #[serde(with = "hex::serde")]
+    pub hash: [u8; 32],
+    pub sources: Vec<ShardSource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErasureManifest {
+    pub version: u8,
+    pub data_shards: usize,
+    pub parity_shards: usize,
+    pub shard_size: usize,
+    /// Blob length before padding to a whole number of shards
+    pub length: u64,
+    #[serde(with = "hex::serde")]
+    pub hash: [u8; 32],
+    /// Data shards first, then parity
+    pub shards: Vec<ShardEntry>,
+}
+
+impl ErasureManifest {
+    pub fn parse(bytes: &[u8]) -> Result<Self, ErasureError> {
+        let manifest: Self = serde_json::from_slice(bytes)?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(ErasureError::Unsupported(format!("version {}", manifest.version)));
+        }
+        if manifest.shards.len() != manifest.data_shards + manifest.parity_shards {
+            return Err(ErasureError::Unsupported(format!(
+                "{} shards listed for {}+{}",
+                manifest.shards.len(),
+                manifest.data_shards,
+                manifest.parity_shards
+            )));
+        }
+        Ok(manifest)
+    }
+
+    pub fn storage_uri(manifest_cid: &str) -> String {
+        format!("{URI_SCHEME}{manifest_cid}")
+    }
+
+    /// Manifest CID of an erasure-coded model's `storage_uri`
+    pub fn cid_from_uri(storage_uri: &str) -> Option<&str> {
+        storage_uri.strip_prefix(URI_SCHEME)
+    }
+}
+
+/// Split `blob` into zero-padded data shards and compute parity. The manifest
+/// has no sources yet; they are filled in as shards are uploaded.
+pub fn encode(
+    blob: &[u8],
+    data_shards: usize,
+    parity_shards: usize,
+) -> Result<(ErasureManifest, Vec<Vec<u8>>), ErasureError> {
+    let codec = ReedSolomon::new(data_shards, parity_shards)?;
+    let shard_size = blob.len().div_ceil(data_shards).max(1);
+    let mut shards: Vec<Vec<u8>> = blob
+        .chunks(shard_size)
+        .map(|chunk| {
+            let mut shard = chunk.to_vec();
+            shard.resize(shard_size, 0);
+            shard
+        })
+        .collect();
+    shards.resize(data_shards + parity_shards, vec![0; shard_size]);
+    codec.encode(&mut shards)?;
+
+    let manifest = ErasureManifest {
+        version: MANIFEST_VERSION,
+        data_shards,
+        parity_shards,
+        shard_size,
+        length: blob.len() as u64,
+        hash: *blake3::hash(blob).as_bytes(),
+        shards: shards
+            .iter()
+            .enumerate()
+            .map(|(index, shard)| ShardEntry { index, hash: *blake3::hash(shard).as_bytes(), sources: Vec::new() })
+            .collect(),
+    };
+    Ok((manifest, shards))
+}
+
+/// Rebuild the blob from verified shards, indexed as in the manifest; `None`
+/// marks a shard that could not be fetched
+pub fn reconstruct(manifest: &ErasureManifest, mut shards: Vec<Option<Vec<u8>>>) -> Result<Vec<u8>, ErasureError> {
+    let available = shards.iter().flatten().count();
+    if available < manifest.data_shards {
+        return Err(ErasureError::NotEnoughShards { available, needed: manifest.data_shards });
+    }
+    ReedSolomon::new(manifest.data_shards, manifest.parity_shards)?.reconstruct_data(&mut shards)?;
+
+    let mut blob: Vec<u8> = shards.into_iter().take(manifest.data_shards).flatten().flatten().collect();
+    blob.truncate(manifest.length as usize);
+    if blake3::hash(&blob) != manifest.hash {
+        return Err(ErasureError::HashMismatch);
+    }
+    Ok(blob)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_data_shards_rebuild_the_blob() {
+        let blob: Vec<u8> = (0..10_001u32).map(|i| (i * 31 % 251) as u8).collect();
+        let (manifest, shards) = encode(&blob, 4, 2).unwrap();
+        assert_eq!(manifest.shards.len(), 6);
+
+        // Lose one data shard and one parity shard
+        let mut partial: Vec<Option<Vec<u8>>> = shards.into_iter().map(Some).collect();
+        partial[1] = None;
+        partial[5] = None;
+        assert_eq!(reconstruct(&manifest, partial.clone()).unwrap(), blob);
+
+        partial[0] = None;
+        assert!(matches!(
+            reconstruct(&manifest, partial),
+            Err(ErasureError::NotEnoughShards { available: 3, needed: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_manifest_round_trips_and_rejects_mismatched_shard_counts() {
+        let (mut manifest, _) = encode(b"model", 2, 1).unwrap();
+        manifest.shards[0].sources.push(ShardSource::Ipfs { cid: "bafyshard".into() });
+        let json = serde_json::to_vec(&manifest).unwrap();
+        assert_eq!(ErasureManifest::parse(&json).unwrap(), manifest);
+
+        manifest.shards.pop();
+        let json = serde_json::to_vec(&manifest).unwrap();
+        assert!(matches!(ErasureManifest::parse(&json), Err(ErasureError::Unsupported(_))));
+        assert_eq!(ErasureManifest::cid_from_uri(&ErasureManifest::storage_uri("bafy")), Some("bafy"));
+    }
+}
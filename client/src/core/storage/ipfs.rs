@@ -0,0 +1,157 @@
+// client/src/core/storage/ipfs.rs
+
+use super::pinning::{Pin, PinState, PinStatus, PinningClient, PinningError};
+use crate::config::IpfsConfig;
+use serde::Deserialize;
+use std::{path::Path, time::{Duration, Instant}};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("IPFS upload failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Pinning(#[from] PinningError),
+    #[error("Pinning {cid} failed after {attempts} attempts")]
+    PinFailed { cid: String, attempts: u32 },
+    #[error("No pinning service configured; set `ipfs.pinning_service`")]
+    NoPinningService,
+}
+
+/// Kubo `/api/v0/add` response
+#[derive(Deserialize)]
+struct Added {
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+/// Kubo `/api/v0/version` response
+#[derive(Deserialize)]
+struct NodeVersion {
+    #[serde(rename = "Version")]
+    version: String,
+}
+
+/// Uploads to the configured IPFS node and keeps artifacts pinned on a remote
+/// service, so they outlive the node they were added from
+pub struct IpfsStorage {
+    config: IpfsConfig,
+    http: reqwest::Client,
+    pinning: Option<PinningClient>,
+}
+
+impl IpfsStorage {
+    pub fn new(config: IpfsConfig) -> Result<Self, StorageError> {
+        let pinning = config.pinning_service.as_ref().map(PinningClient::new).transpose()?;
+        Ok(Self { config, http: reqwest::Client::new(), pinning })
+    }
+
+    pub fn pinning(&self) -> Result<&PinningClient, StorageError> {
+        self.pinning.as_ref().ok_or(StorageError::NoPinningService)
+    }
+
+    /// Version of the node at `api_url`; a reachability check
+    pub async fn version(&self) -> Result<String, StorageError> {
+        let node: NodeVersion = self
+            .http
+            .post(format!("{}/api/v0/version", self.config.api_url.trim_end_matches('/')))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(node.version)
+    }
+
+    /// Add `path` to the node (pinned locally) and, with a pinning service,
+    /// pin it remotely under `name` and `meta`. Returns the CIDv1.
+    pub async fn upload(&self, path: &Path, name: &str, meta: &[(&str, &str)]) -> Result<String, StorageError> {
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        let part = reqwest::multipart::Part::stream_with_length(file, length)
+            .file_name(path.file_name().map_or("artifact".into(), |n| n.to_string_lossy().into_owned()));
+        self.add(part, length, name, meta).await
+    }
+
+    /// [`Self::upload`] for data already in memory, such as erasure-coded shards
+    pub async fn upload_bytes(&self, bytes: Vec<u8>, name: &str, meta: &[(&str, &str)]) -> Result<String, StorageError> {
+        let length = bytes.len() as u64;
+        self.add(reqwest::multipart::Part::bytes(bytes).file_name(name.to_string()), length, name, meta).await
+    }
+
+    async fn add(
+        &self,
+        part: reqwest::multipart::Part,
+        length: u64,
+        name: &str,
+        meta: &[(&str, &str)],
+    ) -> Result<String, StorageError> {
+        let added: Added = self
+            .http
+            .post(format!("{}/api/v0/add", self.config.api_url.trim_end_matches('/')))
+            .query(&[("cid-version", "1"), ("pin", "true")])
+            .multipart(reqwest::multipart::Form::new().part("file", part))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        tracing::info!(cid = %added.hash, bytes = length, "Added to IPFS");
+
+        if self.pinning.is_some() {
+            let pin = meta.iter().fold(Pin::new(&added.hash, name), |pin, (k, v)| pin.with_meta(k, *v));
+            self.ensure_pinned(pin).await?;
+        }
+        Ok(added.hash)
+    }
+
+    /// Request a remote pin and follow it, re-pinning when the service reports
+    /// a failure. Still queued or pinning at `pin_timeout_secs` is not an error:
+    /// the service keeps working on it, and `storage pins list` shows progress.
+    pub async fn ensure_pinned(&self, pin: Pin) -> Result<PinStatus, StorageError> {
+        let service = self.pinning()?;
+        let settings = self.config.pinning_service.as_ref().ok_or(StorageError::NoPinningService)?;
+        let deadline = Instant::now() + Duration::from_secs(settings.pin_timeout_secs);
+        let poll = Duration::from_secs(settings.poll_interval_secs);
+
+        let mut status = service.add(&pin).await?;
+        let mut attempts = 1;
+        loop {
+            match status.status {
+                PinState::Pinned => {
+                    tracing::info!(cid = %pin.cid, requestid = %status.requestid, "Pinned");
+                    return Ok(status);
+                }
+                PinState::Failed if attempts >= settings.max_attempts => {
+                    return Err(StorageError::PinFailed { cid: pin.cid, attempts });
+                }
+                PinState::Failed => {
+                    tracing::warn!(cid = %pin.cid, attempts, "Pin failed, re-pinning");
+                    status = service.replace(&status.requestid, &pin).await?;
+                    attempts += 1;
+                    continue;
+                }
+                PinState::Queued | PinState::Pinning if Instant::now() >= deadline => {
+                    tracing::warn!(cid = %pin.cid, state = ?status.status, "Pin still in progress; check `storage pins list`");
+                    return Ok(status);
+                }
+                PinState::Queued | PinState::Pinning => {}
+            }
+            tokio::time::sleep(poll).await;
+            status = service.get(&status.requestid).await?;
+        }
+    }
+
+    /// Re-submit every failed pin; returns the new statuses
+    pub async fn repin_failed(&self) -> Result<Vec<PinStatus>, StorageError> {
+        let service = self.pinning()?;
+        let mut repinned = Vec::new();
+        for failed in service.list(None, &[PinState::Failed]).await? {
+            tracing::info!(cid = %failed.pin.cid, requestid = %failed.requestid, "Re-pinning");
+            repinned.push(service.replace(&failed.requestid, &failed.pin).await?);
+        }
+        Ok(repinned)
+    }
+}
@@ -0,0 +1,215 @@
+// client/src/core/storage/pinning.rs
+
+//! Client for the IPFS Pinning Service API, as implemented by Pinata
+//! (`https://api.pinata.cloud/psa`), web3.storage and Filebase.
+
+use crate::config::PinningServiceConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Env var holding the bearer token when `access_token` is not configured
+pub const TOKEN_ENV: &str = "SCORIA_PINNING_TOKEN";
+/// Marks pins created by this client, so listings skip unrelated pins on the same account
+const APP_META: (&str, &str) = ("app", "scoria");
+
+#[derive(Debug, Error)]
+pub enum PinningError {
+    #[error("Pinning service request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Pinning service returned {status}: {reason}")]
+    Service { status: u16, reason: String },
+    #[error("No pinning service access token; set `ipfs.pinning_service.access_token` or {TOKEN_ENV}")]
+    MissingToken,
+    #[error("{cid} is not pinned by this client")]
+    NotFound { cid: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PinState {
+    Queued,
+    Pinning,
+    Pinned,
+    Failed,
+}
+
+impl PinState {
+    pub const ALL: [PinState; 4] = [PinState::Queued, PinState::Pinning, PinState::Pinned, PinState::Failed];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PinState::Queued => "queued",
+            PinState::Pinning => "pinning",
+            PinState::Pinned => "pinned",
+            PinState::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pin {
+    pub cid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Multiaddrs already holding the content, which speeds up the service's fetch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub origins: Vec<String>,
+    #[serde(default)]
+    pub meta: HashMap<String, String>,
+}
+
+impl Pin {
+    pub fn new(cid: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            cid: cid.into(),
+            name: Some(name.into()),
+            origins: Vec::new(),
+            meta: HashMap::from([(APP_META.0.to_string(), APP_META.1.to_string())]),
+        }
+    }
+
+    pub fn with_meta(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.meta.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PinStatus {
+    pub requestid: String,
+    pub status: PinState,
+    /// RFC 3339 timestamp
+    pub created: String,
+    pub pin: Pin,
+    #[serde(default)]
+    pub delegates: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PinResults {
+    results: Vec<PinStatus>,
+}
+
+#[derive(Deserialize)]
+struct ServiceFailure {
+    error: ServiceReason,
+}
+
+#[derive(Deserialize)]
+struct ServiceReason {
+    reason: String,
+    #[serde(default)]
+    details: Option<String>,
+}
+
+pub struct PinningClient {
+    http: reqwest::Client,
+    endpoint: String,
+    token: String,
+}
+
+impl PinningClient {
+    pub fn new(config: &PinningServiceConfig) -> Result<Self, PinningError> {
+        let token = match &config.access_token {
+            Some(token) => token.clone(),
+            None => std::env::var(TOKEN_ENV).map_err(|_| PinningError::MissingToken)?,
+        };
+        Ok(Self {
+            http: reqwest::Client::new(),
+            endpoint: config.endpoint.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    pub async fn add(&self, pin: &Pin) -> Result<PinStatus, PinningError> {
+        let request = self.http.post(format!("{}/pins", self.endpoint)).json(pin);
+        self.send(request).await?.json().await.map_err(Into::into)
+    }
+
+    pub async fn get(&self, requestid: &str) -> Result<PinStatus, PinningError> {
+        let request = self.http.get(format!("{}/pins/{requestid}", self.endpoint));
+        self.send(request).await?.json().await.map_err(Into::into)
+    }
+
+    /// Re-submit a pin under the same request; used to retry failed pins
+    pub async fn replace(&self, requestid: &str, pin: &Pin) -> Result<PinStatus, PinningError> {
+        let request = self.http.post(format!("{}/pins/{requestid}", self.endpoint)).json(pin);
+        self.send(request).await?.json().await.map_err(Into::into)
+    }
+
+    pub async fn remove(&self, requestid: &str) -> Result<(), PinningError> {
+        self.send(self.http.delete(format!("{}/pins/{requestid}", self.endpoint))).await?;
+        Ok(())
+    }
+
+    /// Pins created by this client, newest first; `cid` narrows to one artifact
+    pub async fn list(&self, cid: Option<&str>, states: &[PinState]) -> Result<Vec<PinStatus>, PinningError> {
+        let request = self.http.get(format!("{}/pins", self.endpoint)).query(&list_query(cid, states));
+        let results: PinResults = self.send(request).await?.json().await?;
+        Ok(results.results)
+    }
+
+    /// Every request pinning `cid`, in any state
+    pub async fn find(&self, cid: &str) -> Result<Vec<PinStatus>, PinningError> {
+        let found = self.list(Some(cid), &PinState::ALL).await?;
+        if found.is_empty() {
+            return Err(PinningError::NotFound { cid: cid.to_string() });
+        }
+        Ok(found)
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, PinningError> {
+        let response = request.bearer_auth(&self.token).send().await?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status().as_u16();
+        let reason = match response.json::<ServiceFailure>().await {
+            Ok(ServiceFailure { error: ServiceReason { reason, details: Some(details) } }) => {
+                format!("{reason}: {details}")
+            }
+            Ok(ServiceFailure { error }) => error.reason,
+            Err(_) => "unreadable error body".into(),
+        };
+        Err(PinningError::Service { status, reason })
+    }
+}
+
+/// The spec defaults to `pinned` only, so states are always passed explicitly
+fn list_query(cid: Option<&str>, states: &[PinState]) -> Vec<(&'static str, String)> {
+    let states = if states.is_empty() { &PinState::ALL[..] } else { states };
+    let mut query = vec![
+        ("status", states.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(",")),
+        ("meta", serde_json::json!({ APP_META.0: APP_META.1 }).to_string()),
+        ("limit", "1000".to_string()),
+    ];
+    if let Some(cid) = cid {
+        query.push(("cid", cid.to_string()));
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_parses_spec_response() {
+        let status: PinStatus = serde_json::from_str(
+            r#"{"requestid":"UniqueIdOfPinRequest","status":"failed","created":"2020-07-27T17:32:28Z",
+                "pin":{"cid":"bafkreigh2akiscaildc","name":"scoria:model","meta":{"app":"scoria"}},
+                "delegates":["/dnsaddr/pin-service.example.com"],"info":{"status_details":"timeout"}}"#,
+        )
+        .unwrap();
+        assert_eq!(status.status, PinState::Failed);
+        assert_eq!(status.pin.meta.get("app").map(String::as_str), Some("scoria"));
+    }
+
+    #[test]
+    fn test_list_query_includes_every_state_by_default() {
+        let query = list_query(Some("bafy"), &[]);
+        assert!(query.contains(&("status", "queued,pinning,pinned,failed".into())));
+        assert!(query.contains(&("cid", "bafy".into())));
+    }
+}
@@ -0,0 +1,148 @@
+// client/src/core/zkp/circuits/attention.rs
+
+use super::gadgets::{
+    dot, inputs, linear, linear_native, rescale, rescale_native, softmax, softmax_native, witnesses, Num,
+};
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+
+/// Single-head scaled dot-product self-attention, `softmax(Q K^T / sqrt(d)) V`.
+///
+/// The sequence `[seq_len][model_dim]` and the `[seq_len][head_dim]` result are
+/// public; the Q/K/V projections (row-major `[head_dim][model_dim]`) are private.
+#[derive(Debug, Clone)]
+pub struct AttentionCircuit {
+    pub seq_len: usize,
+    pub model_dim: usize,
+    pub head_dim: usize,
+    pub frac_bits: u32,
+    pub bits: u32,
+    pub w_query: Vec<i64>,
+    pub w_key: Vec<i64>,
+    pub w_value: Vec<i64>,
+    pub input: Vec<i64>,
+    pub output: Vec<i64>,
+}
+
+impl AttentionCircuit {
+    /// Build with the honest output for `input`; `projections` are the query, key and value weights
+    pub fn new(
+        model_dim: usize,
+        head_dim: usize,
+        projections: [Vec<i64>; 3],
+        input: Vec<i64>,
+        frac_bits: u32,
+        bits: u32,
+    ) -> Self {
+        let [w_query, w_key, w_value] = projections;
+        let seq_len = input.len() / model_dim;
+        let project = |w: &[i64]| -> Vec<Vec<i64>> {
+            input
+                .chunks(model_dim)
+                .map(|x| linear_native(w, &vec![0; head_dim], x, frac_bits, false))
+                .collect()
+        };
+        let (q, k, v) = (project(&w_query), project(&w_key), project(&w_value));
+        let scale = score_scale(head_dim, frac_bits);
+
+        let mut output = Vec::with_capacity(seq_len * head_dim);
+        for q_i in &q {
+            let scores: Vec<i64> = k
+                .iter()
+                .map(|k_j| {
+                    let s = rescale_native(q_i.iter().zip(k_j).map(|(a, b)| a * b).sum(), frac_bits);
+                    rescale_native(s * scale, frac_bits)
+                })
+                .collect();
+            let weights = softmax_native(&scores, frac_bits);
+            for h in 0..head_dim {
+                output.push(rescale_native(weights.iter().zip(&v).map(|(a, v_j)| a * v_j[h]).sum(), frac_bits));
+            }
+        }
+
+        Self { seq_len, model_dim, head_dim, frac_bits, bits, w_query, w_key, w_value, input, output }
+    }
+
+    /// Zero-valued instance of the given shape, for key generation
+    pub fn blank(seq_len: usize, model_dim: usize, head_dim: usize, frac_bits: u32, bits: u32) -> Self {
+        let w = vec![0; head_dim * model_dim];
+        Self::new(model_dim, head_dim, [w.clone(), w.clone(), w], vec![0; seq_len * model_dim], frac_bits, bits)
+    }
+}
+
+/// `1 / sqrt(head_dim)` in fixed point
+fn score_scale(head_dim: usize, frac_bits: u32) -> i64 {
+    ((1i64 << frac_bits) as f64 / (head_dim as f64).sqrt()).round() as i64
+}
+
+impl ConstraintSynthesizer<Fr> for AttentionCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let (frac, bits) = (self.frac_bits, self.bits);
+        let x = inputs(&cs, &self.input)?;
+        let claimed = inputs(&cs, &self.output)?;
+        let no_bias = vec![Num::constant(0); self.head_dim];
+        let project = |w: &[i64]| -> Result<Vec<Vec<Num>>, SynthesisError> {
+            let w = witnesses(&cs, w)?;
+            x.chunks(self.model_dim)
+                .map(|x_i| linear(&cs, &w, &no_bias, x_i, frac, bits, false))
+                .collect()
+        };
+        let (q, k, v) = (project(&self.w_query)?, project(&self.w_key)?, project(&self.w_value)?);
+        let scale = score_scale(self.head_dim, frac);
+
+        let mut claimed = claimed.iter();
+        for q_i in &q {
+            let scores = k
+                .iter()
+                .map(|k_j| {
+                    let s = rescale(&cs, &dot(&cs, q_i, k_j)?, frac, bits)?;
+                    rescale(&cs, &s.scale(scale), frac, bits)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let weights = softmax(&cs, &scores, frac, bits)?;
+            for h in 0..self.head_dim {
+                let column: Vec<Num> = v.iter().map(|v_j| v_j[h].clone()).collect();
+                let y = rescale(&cs, &dot(&cs, &weights, &column)?, frac, bits)?;
+                y.enforce_equal(&cs, claimed.next().ok_or(SynthesisError::Unsatisfiable)?)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    fn block() -> AttentionCircuit {
+        let w = |seed: i64| (0..8).map(|i| ((i * seed) % 13 - 6) * 20).collect::<Vec<_>>();
+        let input = (0..12).map(|i| (i * 7 % 11 - 5) * 40).collect();
+        AttentionCircuit::new(4, 2, [w(5), w(7), w(3)], input, 8, 24)
+    }
+
+    fn satisfied(circuit: AttentionCircuit) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn honest_output_satisfies() {
+        let circuit = block();
+        assert_eq!((circuit.seq_len, circuit.output.len()), (3, 6));
+        assert!(satisfied(circuit));
+    }
+
+    #[test]
+    fn tampered_output_or_weights_fail() {
+        let mut output = block();
+        output.output[4] += 1;
+        assert!(!satisfied(output));
+
+        // Claiming the output under different value weights must not verify
+        let mut weights = block();
+        weights.w_value[0] += 64;
+        assert!(!satisfied(weights));
+    }
+}
@@ -0,0 +1,154 @@
+// client/src/core/zkp/circuits/conv2d.rs
+
+use super::gadgets::{dot, inputs, relu, relu_native, rescale, rescale_native, witnesses, Num};
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+
+/// Square-kernel 2-D convolution over a `[channels][height][width]` input with zero padding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conv2dShape {
+    pub in_channels: usize,
+    pub out_channels: usize,
+    pub height: usize,
+    pub width: usize,
+    pub kernel: usize,
+    pub stride: usize,
+    pub padding: usize,
+}
+
+impl Conv2dShape {
+    pub fn output_dims(&self) -> (usize, usize) {
+        let dim = |n: usize| (n + 2 * self.padding - self.kernel) / self.stride + 1;
+        (dim(self.height), dim(self.width))
+    }
+
+    /// `(weight index, input index)` pairs feeding one output; taps landing in padding are skipped
+    fn taps(&self, oc: usize, oy: usize, ox: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let k = self.kernel;
+        (0..self.in_channels).flat_map(move |ic| {
+            (0..k).flat_map(move |ky| {
+                (0..k).filter_map(move |kx| {
+                    let y = (oy * self.stride + ky).checked_sub(self.padding).filter(|&y| y < self.height)?;
+                    let x = (ox * self.stride + kx).checked_sub(self.padding).filter(|&x| x < self.width)?;
+                    Some((((oc * self.in_channels + ic) * k + ky) * k + kx, (ic * self.height + y) * self.width + x))
+                })
+            })
+        })
+    }
+
+    fn outputs(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        let (oh, ow) = self.output_dims();
+        (0..self.out_channels).flat_map(move |oc| (0..oh).flat_map(move |oy| (0..ow).map(move |ox| (oc, oy, ox))))
+    }
+}
+
+/// Convolution layer with one multiplication constraint per kernel tap, optionally followed by ReLU.
+///
+/// Input and output feature maps are public; kernels and bias are private.
+#[derive(Debug, Clone)]
+pub struct Conv2dCircuit {
+    pub shape: Conv2dShape,
+    pub frac_bits: u32,
+    pub bits: u32,
+    pub relu: bool,
+    /// `[out_channels][in_channels][kernel][kernel]`
+    pub weights: Vec<i64>,
+    pub bias: Vec<i64>,
+    pub input: Vec<i64>,
+    pub output: Vec<i64>,
+}
+
+impl Conv2dCircuit {
+    /// Build with the honest output for `input`
+    pub fn new(shape: Conv2dShape, weights: Vec<i64>, bias: Vec<i64>, input: Vec<i64>, frac_bits: u32, bits: u32, relu: bool) -> Self {
+        let output = shape
+            .outputs()
+            .map(|(oc, oy, ox)| {
+                let acc: i64 = shape.taps(oc, oy, ox).map(|(w, x)| weights[w] * input[x]).sum::<i64>() + (bias[oc] << frac_bits);
+                let y = rescale_native(acc, frac_bits);
+                if relu { relu_native(y) } else { y }
+            })
+            .collect();
+        Self { shape, frac_bits, bits, relu, weights, bias, input, output }
+    }
+
+    /// Zero-valued instance of the given shape, for key generation
+    pub fn blank(shape: Conv2dShape, frac_bits: u32, bits: u32, relu: bool) -> Self {
+        let weights = vec![0; shape.out_channels * shape.in_channels * shape.kernel * shape.kernel];
+        let input = vec![0; shape.in_channels * shape.height * shape.width];
+        Self::new(shape, weights, vec![0; shape.out_channels], input, frac_bits, bits, relu)
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for Conv2dCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let x = inputs(&cs, &self.input)?;
+        let claimed = inputs(&cs, &self.output)?;
+        let weights = witnesses(&cs, &self.weights)?;
+        let bias = witnesses(&cs, &self.bias)?;
+
+        for ((oc, oy, ox), claimed) in self.shape.outputs().zip(&claimed) {
+            let (ws, xs): (Vec<Num>, Vec<Num>) = self
+                .shape
+                .taps(oc, oy, ox)
+                .map(|(w, i)| (weights[w].clone(), x[i].clone()))
+                .unzip();
+            let acc = dot(&cs, &ws, &xs)?.add(&bias[oc].scale(1 << self.frac_bits));
+            let mut y = rescale(&cs, &acc, self.frac_bits, self.bits)?;
+            if self.relu {
+                y = relu(&cs, &y, self.bits)?;
+            }
+            y.enforce_equal(&cs, claimed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    const SHAPE: Conv2dShape = Conv2dShape {
+        in_channels: 2,
+        out_channels: 3,
+        height: 5,
+        width: 4,
+        kernel: 3,
+        stride: 2,
+        padding: 1,
+    };
+
+    fn layer() -> Conv2dCircuit {
+        let weights = (0..54).map(|i| (i * 29 % 17 - 8) * 8).collect();
+        let input = (0..40).map(|i| (i * 13 % 31 - 15) * 16).collect();
+        Conv2dCircuit::new(SHAPE, weights, vec![16, 0, -16], input, 8, 24, false)
+    }
+
+    fn satisfied(circuit: Conv2dCircuit) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn output_dims_follow_stride_and_padding() {
+        assert_eq!(SHAPE.output_dims(), (3, 2));
+        // Corner output sees only the in-bounds 2x2 of each channel's 3x3 window
+        assert_eq!(SHAPE.taps(0, 0, 0).count(), 2 * 2 * 2);
+        assert_eq!(layer().output.len(), 3 * 3 * 2);
+    }
+
+    #[test]
+    fn honest_output_satisfies_and_tampering_fails() {
+        assert!(satisfied(layer()));
+
+        let mut tampered = layer();
+        tampered.output[7] -= 1;
+        assert!(!satisfied(tampered));
+
+        let mut swapped = layer();
+        swapped.input.swap(0, 1);
+        assert!(!satisfied(swapped));
+    }
+}
@@ -0,0 +1,99 @@
+// client/src/core/zkp/circuits/dense.rs
+
+use super::gadgets::{inputs, linear, linear_native, witnesses};
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+
+/// Fully connected layer `y = rescale(W x + b)`, optionally followed by ReLU.
+///
+/// Input and output activations are public; weights and bias are private.
+#[derive(Debug, Clone)]
+pub struct DenseCircuit {
+    pub inputs: usize,
+    pub outputs: usize,
+    pub frac_bits: u32,
+    /// Range of every activation, in bits including sign
+    pub bits: u32,
+    pub relu: bool,
+    /// Row-major `[outputs][inputs]`
+    pub weights: Vec<i64>,
+    pub bias: Vec<i64>,
+    pub input: Vec<i64>,
+    pub output: Vec<i64>,
+}
+
+impl DenseCircuit {
+    /// Build with the honest output for `input`
+    pub fn new(weights: Vec<i64>, bias: Vec<i64>, input: Vec<i64>, frac_bits: u32, bits: u32, relu: bool) -> Self {
+        let output = linear_native(&weights, &bias, &input, frac_bits, relu);
+        Self {
+            inputs: input.len(),
+            outputs: bias.len(),
+            frac_bits,
+            bits,
+            relu,
+            weights,
+            bias,
+            input,
+            output,
+        }
+    }
+
+    /// Zero-valued instance of the given shape, for key generation
+    pub fn blank(inputs: usize, outputs: usize, frac_bits: u32, bits: u32, relu: bool) -> Self {
+        Self::new(vec![0; inputs * outputs], vec![0; outputs], vec![0; inputs], frac_bits, bits, relu)
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for DenseCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let x = inputs(&cs, &self.input)?;
+        let claimed = inputs(&cs, &self.output)?;
+        let weights = witnesses(&cs, &self.weights)?;
+        let bias = witnesses(&cs, &self.bias)?;
+
+        let y = linear(&cs, &weights, &bias, &x, self.frac_bits, self.bits, self.relu)?;
+        for (y, claimed) in y.iter().zip(&claimed) {
+            y.enforce_equal(&cs, claimed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    fn layer() -> DenseCircuit {
+        let weights = (0..12).map(|i| (i * 37 % 23 - 11) * 16).collect();
+        DenseCircuit::new(weights, vec![64, -32, 0], vec![256, -128, 77, 1000], 8, 24, true)
+    }
+
+    fn satisfied(circuit: DenseCircuit) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn honest_output_satisfies() {
+        assert!(satisfied(layer()));
+        assert!(layer().output.iter().all(|&y| y >= 0));
+    }
+
+    #[test]
+    fn tampered_output_fails() {
+        let mut circuit = layer();
+        circuit.output[1] += 1;
+        assert!(!satisfied(circuit));
+    }
+
+    #[test]
+    fn constraint_count_is_linear_in_weights() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        DenseCircuit::blank(16, 4, 8, 24, false).generate_constraints(cs.clone()).unwrap();
+        // Per output: one per weight, rescale (8 + 1 + 24 + 1 + 1), equality
+        assert_eq!(cs.num_constraints(), 4 * (16 + 35 + 1));
+    }
+}
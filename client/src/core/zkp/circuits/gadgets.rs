@@ -0,0 +1,300 @@
+// client/src/core/zkp/circuits/gadgets.rs
+//
+// Fixed-point R1CS gadgets shared by the circuit templates. Values are signed
+// integers scaled by 2^frac_bits; negatives are encoded as `p - |x|`. Every
+// gadget has a native twin with identical integer semantics, used to compute
+// public outputs outside the circuit.
+
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSystemRef, LinearCombination, SynthesisError, Variable};
+
+pub type Cs = ConstraintSystemRef<Fr>;
+
+/// Squarings in the `(1 + x/2^K)^(2^K)` exp approximation; inputs below -2^K saturate
+pub const EXP_SQUARINGS: u32 = 3;
+
+pub fn fr(v: i64) -> Fr {
+    if v < 0 {
+        -Fr::from(v.unsigned_abs())
+    } else {
+        Fr::from(v as u64)
+    }
+}
+
+/// A linear combination of circuit variables together with its witness value
+#[derive(Debug, Clone)]
+pub struct Num {
+    pub lc: LinearCombination<Fr>,
+    pub value: i64,
+}
+
+impl Num {
+    pub fn constant(value: i64) -> Self {
+        Self { lc: LinearCombination::from((fr(value), Variable::One)), value }
+    }
+
+    pub fn input(cs: &Cs, value: i64) -> Result<Self, SynthesisError> {
+        let var = cs.new_input_variable(|| Ok(fr(value)))?;
+        Ok(Self { lc: var.into(), value })
+    }
+
+    pub fn witness(cs: &Cs, value: i64) -> Result<Self, SynthesisError> {
+        let var = cs.new_witness_variable(|| Ok(fr(value)))?;
+        Ok(Self { lc: var.into(), value })
+    }
+
+    pub fn add(&self, other: &Num) -> Num {
+        Num { lc: &self.lc + &other.lc, value: self.value + other.value }
+    }
+
+    pub fn sub(&self, other: &Num) -> Num {
+        Num { lc: &self.lc - &other.lc, value: self.value - other.value }
+    }
+
+    /// Multiply by a constant; free, no constraint
+    pub fn scale(&self, k: i64) -> Num {
+        Num { lc: &self.lc * fr(k), value: self.value * k }
+    }
+
+    /// One constraint
+    pub fn mul(&self, cs: &Cs, other: &Num) -> Result<Num, SynthesisError> {
+        let out = Num::witness(cs, self.value * other.value)?;
+        cs.enforce_constraint(self.lc.clone(), other.lc.clone(), out.lc.clone())?;
+        Ok(out)
+    }
+
+    /// One constraint
+    pub fn enforce_equal(&self, cs: &Cs, other: &Num) -> Result<(), SynthesisError> {
+        cs.enforce_constraint(&self.lc - &other.lc, Variable::One.into(), LinearCombination::zero())
+    }
+}
+
+/// Little-endian bits of `x`, which must lie in `[0, 2^bits)`; `bits + 1` constraints
+pub fn to_bits(cs: &Cs, x: &Num, bits: u32) -> Result<Vec<Num>, SynthesisError> {
+    let mut out = Vec::with_capacity(bits as usize);
+    let mut sum = LinearCombination::zero();
+    for k in 0..bits {
+        let bit = Num::witness(cs, (x.value >> k) & 1)?;
+        cs.enforce_constraint(bit.lc.clone(), bit.lc.clone() - Variable::One, LinearCombination::zero())?;
+        sum = sum + (fr(1 << k), &bit.lc);
+        out.push(bit);
+    }
+    cs.enforce_constraint(sum - &x.lc, Variable::One.into(), LinearCombination::zero())?;
+    Ok(out)
+}
+
+/// Enforce `x` in `[-2^(bits-1), 2^(bits-1))`, returning the sign bit (1 when `x >= 0`)
+pub fn range_check_signed(cs: &Cs, x: &Num, bits: u32) -> Result<Num, SynthesisError> {
+    let offset = x.add(&Num::constant(1 << (bits - 1)));
+    Ok(to_bits(cs, &offset, bits)?.pop().expect("bits > 0"))
+}
+
+/// `floor(x / 2^shift)`, itself range checked to `bits`
+pub fn rescale(cs: &Cs, x: &Num, shift: u32, bits: u32) -> Result<Num, SynthesisError> {
+    let q = Num::witness(cs, rescale_native(x.value, shift))?;
+    let r = Num::witness(cs, x.value - (q.value << shift))?;
+    to_bits(cs, &r, shift)?;
+    range_check_signed(cs, &q, bits)?;
+    q.scale(1 << shift).add(&r).enforce_equal(cs, x)?;
+    Ok(q)
+}
+
+pub fn rescale_native(x: i64, shift: u32) -> i64 {
+    x >> shift
+}
+
+pub fn relu(cs: &Cs, x: &Num, bits: u32) -> Result<Num, SynthesisError> {
+    let non_negative = range_check_signed(cs, x, bits)?;
+    non_negative.mul(cs, x)
+}
+
+pub fn relu_native(x: i64) -> i64 {
+    x.max(0)
+}
+
+/// `max(a, b)` and the selector `[a >= b]`
+pub fn max(cs: &Cs, a: &Num, b: &Num, bits: u32) -> Result<(Num, Num), SynthesisError> {
+    let diff = a.sub(b);
+    let select_a = range_check_signed(cs, &diff, bits)?;
+    Ok((b.add(&select_a.mul(cs, &diff)?), select_a))
+}
+
+/// Largest element and its index, the first on ties
+pub fn argmax(cs: &Cs, xs: &[Num], bits: u32) -> Result<(Num, Num), SynthesisError> {
+    let mut best = xs[0].clone();
+    let mut index = Num::constant(0);
+    for (i, x) in xs.iter().enumerate().skip(1) {
+        // Strictly greater replaces, so compare with the running best on the left
+        let (next, keep) = max(cs, &best, x, bits)?;
+        let moved = Num::constant(i as i64).sub(&index);
+        let take = Num::constant(1).sub(&keep);
+        index = index.add(&take.mul(cs, &moved)?);
+        best = next;
+    }
+    Ok((best, index))
+}
+
+pub fn argmax_native(xs: &[i64]) -> (i64, usize) {
+    xs.iter()
+        .enumerate()
+        .fold((xs[0], 0), |(best, index), (i, &x)| if x > best { (x, i) } else { (best, index) })
+}
+
+/// `sum(w_i * x_i)`; one constraint per term
+pub fn dot(cs: &Cs, ws: &[Num], xs: &[Num]) -> Result<Num, SynthesisError> {
+    let mut acc = Num::constant(0);
+    for (w, x) in ws.iter().zip(xs) {
+        acc = acc.add(&w.mul(cs, x)?);
+    }
+    Ok(acc)
+}
+
+/// `exp(x)` for `x <= 0` as `(1 + x/2^K)^(2^K)`, saturating to zero below `-2^K`
+pub fn exp_neg(cs: &Cs, x: &Num, frac_bits: u32, bits: u32) -> Result<Num, SynthesisError> {
+    let one = 1i64 << frac_bits;
+    let limit = Num::constant(one << EXP_SQUARINGS);
+    let clamped = relu(cs, &x.add(&limit), bits)?.sub(&limit);
+    let mut u = Num::constant(one).add(&rescale(cs, &clamped, EXP_SQUARINGS, bits)?);
+    for _ in 0..EXP_SQUARINGS {
+        u = rescale(cs, &u.mul(cs, &u)?, frac_bits, bits)?;
+    }
+    Ok(u)
+}
+
+pub fn exp_neg_native(x: i64, frac_bits: u32) -> i64 {
+    let one = 1i64 << frac_bits;
+    let limit = one << EXP_SQUARINGS;
+    let clamped = relu_native(x + limit) - limit;
+    let mut u = one + rescale_native(clamped, EXP_SQUARINGS);
+    for _ in 0..EXP_SQUARINGS {
+        u = rescale_native(u * u, frac_bits);
+    }
+    u
+}
+
+/// Softmax with outputs `floor(e_i * 2^frac / sum(e))`, each proven by a division remainder
+pub fn softmax(cs: &Cs, xs: &[Num], frac_bits: u32, bits: u32) -> Result<Vec<Num>, SynthesisError> {
+    let (max, _) = argmax(cs, xs, bits)?;
+    let exps = xs
+        .iter()
+        .map(|x| exp_neg(cs, &x.sub(&max), frac_bits, bits))
+        .collect::<Result<Vec<_>, _>>()?;
+    let sum = exps.iter().fold(Num::constant(0), |acc, e| acc.add(e));
+
+    exps.iter()
+        .map(|e| {
+            let numerator = e.scale(1 << frac_bits);
+            let y = Num::witness(cs, numerator.value / sum.value)?;
+            let r = Num::witness(cs, numerator.value % sum.value)?;
+            // 0 <= r < sum, and y bounded so y * sum cannot wrap the field
+            to_bits(cs, &r, bits)?;
+            to_bits(cs, &sum.sub(&r).sub(&Num::constant(1)), bits)?;
+            to_bits(cs, &y, frac_bits + 1)?;
+            y.mul(cs, &sum)?.add(&r).enforce_equal(cs, &numerator)?;
+            Ok(y)
+        })
+        .collect()
+}
+
+pub fn softmax_native(xs: &[i64], frac_bits: u32) -> Vec<i64> {
+    let (max, _) = argmax_native(xs);
+    let exps: Vec<i64> = xs.iter().map(|x| exp_neg_native(x - max, frac_bits)).collect();
+    let sum: i64 = exps.iter().sum();
+    exps.iter().map(|e| (e << frac_bits) / sum).collect()
+}
+
+/// `rescale(W x + b << frac)` per output row, with optional ReLU; weights row-major `[outputs][inputs]`
+pub fn linear(
+    cs: &Cs,
+    weights: &[Num],
+    bias: &[Num],
+    x: &[Num],
+    frac_bits: u32,
+    bits: u32,
+    apply_relu: bool,
+) -> Result<Vec<Num>, SynthesisError> {
+    weights
+        .chunks(x.len())
+        .zip(bias)
+        .map(|(row, b)| {
+            let acc = dot(cs, row, x)?.add(&b.scale(1 << frac_bits));
+            let y = rescale(cs, &acc, frac_bits, bits)?;
+            if apply_relu { relu(cs, &y, bits) } else { Ok(y) }
+        })
+        .collect()
+}
+
+pub fn linear_native(weights: &[i64], bias: &[i64], x: &[i64], frac_bits: u32, apply_relu: bool) -> Vec<i64> {
+    weights
+        .chunks(x.len())
+        .zip(bias)
+        .map(|(row, b)| {
+            let acc: i64 = row.iter().zip(x).map(|(w, x)| w * x).sum::<i64>() + (b << frac_bits);
+            let y = rescale_native(acc, frac_bits);
+            if apply_relu { relu_native(y) } else { y }
+        })
+        .collect()
+}
+
+/// Allocate a slice of private values
+pub fn witnesses(cs: &Cs, values: &[i64]) -> Result<Vec<Num>, SynthesisError> {
+    values.iter().map(|&v| Num::witness(cs, v)).collect()
+}
+
+/// Allocate a slice of public values
+pub fn inputs(cs: &Cs, values: &[i64]) -> Result<Vec<Num>, SynthesisError> {
+    values.iter().map(|&v| Num::input(cs, v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    const FRAC: u32 = 8;
+    const BITS: u32 = 24;
+
+    #[test]
+    fn rescale_and_relu_match_native() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        for v in [-1000, -256, -1, 0, 1, 255, 4097] {
+            let x = Num::witness(&cs, v).unwrap();
+            assert_eq!(rescale(&cs, &x, FRAC, BITS).unwrap().value, rescale_native(v, FRAC));
+            assert_eq!(relu(&cs, &x, BITS).unwrap().value, relu_native(v));
+        }
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn exp_approximation_tracks_exp() {
+        for x in (-2048..=0).step_by(64) {
+            let approx = exp_neg_native(x, FRAC) as f64 / 256.0;
+            let exact = (x as f64 / 256.0).exp();
+            assert!((approx - exact).abs() < 0.07, "exp({}) = {exact}, got {approx}", x as f64 / 256.0);
+        }
+        assert_eq!(exp_neg_native(-100_000, FRAC), 0);
+    }
+
+    #[test]
+    fn softmax_and_argmax_are_satisfiable() {
+        let values = [300, -120, 512, 512, 0];
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let xs = witnesses(&cs, &values).unwrap();
+        let probs = softmax(&cs, &xs, FRAC, BITS).unwrap();
+        let (best, index) = argmax(&cs, &xs, BITS).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        assert_eq!(probs.iter().map(|p| p.value).collect::<Vec<_>>(), softmax_native(&values, FRAC));
+        assert_eq!((best.value, index.value), (512, 2));
+        let total: i64 = probs.iter().map(|p| p.value).sum();
+        assert!((250..=256).contains(&total), "probabilities sum to {total}/256");
+    }
+
+    #[test]
+    fn out_of_range_values_are_unsatisfiable() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let x = Num::witness(&cs, 1 << 30).unwrap();
+        range_check_signed(&cs, &x, BITS).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,258 @@
+// client/src/core/zkp/msm.wgsl
+//
+// BN254 G1 bucket accumulation for Pippenger MSM on any wgpu adapter.
+// WGSL has no 64-bit integers, so field elements are 16 little-endian
+// 16-bit limbs stored one per u32; every limb product then fits in 32 bits.
+// Coordinates are in Montgomery form (R = 2^256), matching arkworks.
+
+const LIMBS: u32 = 16u;
+const MASK: u32 = 0xffffu;
+// -p^{-1} mod 2^16
+const INV: u32 = 0x6389u;
+
+var<private> P: array<u32, 16> = array<u32, 16>(
+    0xfd47u, 0xd87cu, 0x8c16u, 0x3c20u, 0xca8du, 0x6871u, 0x6a91u, 0x9781u,
+    0x585du, 0x8181u, 0x45b6u, 0xb850u, 0xa029u, 0xe131u, 0x4e72u, 0x3064u
+);
+// 2^256 mod p (Montgomery one)
+var<private> ONE: array<u32, 16> = array<u32, 16>(
+    0x0d9du, 0xc58fu, 0x438du, 0xd35du, 0x0b3du, 0xf5c7u, 0xeb28u, 0x0a78u,
+    0x462cu, 0x7879u, 0xa36fu, 0x666eu, 0xdf2fu, 0x9a07u, 0x77c1u, 0x0e0au
+);
+
+struct Fq {
+    l: array<u32, 16>,
+}
+
+struct G1 {
+    x: Fq,
+    y: Fq,
+    z: Fq,
+}
+
+struct Params {
+    // Points in the current chunk
+    count: u32,
+    window_bits: u32,
+    windows: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> bases: array<u32>;        // count * 32: x then y
+@group(0) @binding(1) var<storage, read> scalars: array<u32>;      // count * 16, canonical
+@group(0) @binding(2) var<storage, read_write> buckets: array<u32>; // windows * (2^c - 1) * 48
+@group(0) @binding(3) var<uniform> params: Params;
+
+fn fq_is_zero(a_in: Fq) -> bool {
+    var a = a_in;
+    var acc = 0u;
+    for (var i = 0u; i < LIMBS; i++) {
+        acc |= a.l[i];
+    }
+    return acc == 0u;
+}
+
+fn fq_geq_modulus(a_in: Fq) -> bool {
+    var a = a_in;
+    for (var i = 0u; i < LIMBS; i++) {
+        let k = LIMBS - 1u - i;
+        if (a.l[k] > P[k]) { return true; }
+        if (a.l[k] < P[k]) { return false; }
+    }
+    return true;
+}
+
+fn fq_sub_modulus(a_in: Fq) -> Fq {
+    var a = a_in;
+    var borrow = 0u;
+    for (var i = 0u; i < LIMBS; i++) {
+        let d = a.l[i] + 0x10000u - P[i] - borrow;
+        a.l[i] = d & MASK;
+        borrow = 1u - (d >> 16u);
+    }
+    return a;
+}
+
+fn fq_add(a_in: Fq, b_in: Fq) -> Fq {
+    var a = a_in;
+    var b = b_in;
+    var r: Fq;
+    var carry = 0u;
+    for (var i = 0u; i < LIMBS; i++) {
+        let s = a.l[i] + b.l[i] + carry;
+        r.l[i] = s & MASK;
+        carry = s >> 16u;
+    }
+    if (carry != 0u || fq_geq_modulus(r)) {
+        r = fq_sub_modulus(r);
+    }
+    return r;
+}
+
+fn fq_sub(a_in: Fq, b_in: Fq) -> Fq {
+    var a = a_in;
+    var b = b_in;
+    var r: Fq;
+    var borrow = 0u;
+    for (var i = 0u; i < LIMBS; i++) {
+        let d = a.l[i] + 0x10000u - b.l[i] - borrow;
+        r.l[i] = d & MASK;
+        borrow = 1u - (d >> 16u);
+    }
+    if (borrow != 0u) {
+        var carry = 0u;
+        for (var i = 0u; i < LIMBS; i++) {
+            let s = r.l[i] + P[i] + carry;
+            r.l[i] = s & MASK;
+            carry = s >> 16u;
+        }
+    }
+    return r;
+}
+
+fn fq_double(a: Fq) -> Fq {
+    return fq_add(a, a);
+}
+
+// Montgomery multiplication, CIOS variant
+fn fq_mul(a_in: Fq, b_in: Fq) -> Fq {
+    var a = a_in;
+    var b = b_in;
+    var t: array<u32, 18>;
+    for (var i = 0u; i < LIMBS; i++) {
+        var c = 0u;
+        for (var j = 0u; j < LIMBS; j++) {
+            let s = t[j] + a.l[j] * b.l[i] + c;
+            t[j] = s & MASK;
+            c = s >> 16u;
+        }
+        var s = t[LIMBS] + c;
+        t[LIMBS] = s & MASK;
+        t[LIMBS + 1u] = s >> 16u;
+
+        let m = (t[0] * INV) & MASK;
+        s = t[0] + m * P[0];
+        c = s >> 16u;
+        for (var j = 1u; j < LIMBS; j++) {
+            s = t[j] + m * P[j] + c;
+            t[j - 1u] = s & MASK;
+            c = s >> 16u;
+        }
+        s = t[LIMBS] + c;
+        t[LIMBS - 1u] = s & MASK;
+        t[LIMBS] = t[LIMBS + 1u] + (s >> 16u);
+    }
+
+    var r: Fq;
+    for (var i = 0u; i < LIMBS; i++) {
+        r.l[i] = t[i];
+    }
+    if (t[LIMBS] != 0u || fq_geq_modulus(r)) {
+        r = fq_sub_modulus(r);
+    }
+    return r;
+}
+
+// dbl-2009-l (a = 0)
+fn g1_double(p: G1) -> G1 {
+    if (fq_is_zero(p.z)) {
+        return p;
+    }
+    let a = fq_mul(p.x, p.x);
+    let b = fq_mul(p.y, p.y);
+    let c = fq_mul(b, b);
+    let xb = fq_add(p.x, b);
+    let d = fq_double(fq_sub(fq_sub(fq_mul(xb, xb), a), c));
+    let e = fq_add(fq_double(a), a);
+    let f = fq_mul(e, e);
+
+    var r: G1;
+    r.x = fq_sub(f, fq_double(d));
+    let c8 = fq_double(fq_double(fq_double(c)));
+    r.y = fq_sub(fq_mul(e, fq_sub(d, r.x)), c8);
+    r.z = fq_double(fq_mul(p.y, p.z));
+    return r;
+}
+
+// madd-2007-bl: Jacobian + affine
+fn g1_add_affine(p: G1, x2: Fq, y2: Fq) -> G1 {
+    if (fq_is_zero(p.z)) {
+        var r: G1;
+        r.x = x2;
+        r.y = y2;
+        r.z.l = ONE;
+        return r;
+    }
+
+    let z1z1 = fq_mul(p.z, p.z);
+    let u2 = fq_mul(x2, z1z1);
+    let s2 = fq_mul(fq_mul(y2, p.z), z1z1);
+    let h = fq_sub(u2, p.x);
+    let rr = fq_double(fq_sub(s2, p.y));
+
+    if (fq_is_zero(h)) {
+        if (fq_is_zero(rr)) {
+            return g1_double(p);
+        }
+        var inf: G1;
+        return inf;
+    }
+
+    let hh = fq_mul(h, h);
+    let i = fq_double(fq_double(hh));
+    let j = fq_mul(h, i);
+    let v = fq_mul(p.x, i);
+
+    var r: G1;
+    r.x = fq_sub(fq_sub(fq_mul(rr, rr), j), fq_double(v));
+    r.y = fq_sub(fq_mul(rr, fq_sub(v, r.x)), fq_double(fq_mul(p.y, j)));
+    let z1h = fq_add(p.z, h);
+    r.z = fq_sub(fq_sub(fq_mul(z1h, z1h), z1z1), hh);
+    return r;
+}
+
+// One invocation per (window, bucket). Buckets persist across dispatches so
+// the host can stream points in chunks and stay under driver timeouts.
+// window_bits must divide 16 so digits never straddle limbs.
+@compute @workgroup_size(64)
+fn accumulate(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let buckets_per_window = (1u << params.window_bits) - 1u;
+    let tid = gid.x;
+    if (tid >= params.windows * buckets_per_window) {
+        return;
+    }
+
+    let window = tid / buckets_per_window;
+    let digit = tid % buckets_per_window + 1u;
+    let bit = window * params.window_bits;
+    let limb = bit / 16u;
+    let shift = bit % 16u;
+    let mask = (1u << params.window_bits) - 1u;
+    let out = tid * 48u;
+
+    var acc: G1;
+    for (var i = 0u; i < LIMBS; i++) {
+        acc.x.l[i] = buckets[out + i];
+        acc.y.l[i] = buckets[out + 16u + i];
+        acc.z.l[i] = buckets[out + 32u + i];
+    }
+
+    for (var p = 0u; p < params.count; p++) {
+        if (((scalars[p * 16u + limb] >> shift) & mask) != digit) {
+            continue;
+        }
+        var x: Fq;
+        var y: Fq;
+        for (var i = 0u; i < LIMBS; i++) {
+            x.l[i] = bases[p * 32u + i];
+            y.l[i] = bases[p * 32u + 16u + i];
+        }
+        acc = g1_add_affine(acc, x, y);
+    }
+
+    for (var i = 0u; i < LIMBS; i++) {
+        buckets[out + i] = acc.x.l[i];
+        buckets[out + 16u + i] = acc.y.l[i];
+        buckets[out + 32u + i] = acc.z.l[i];
+    }
+}
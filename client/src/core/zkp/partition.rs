@@ -0,0 +1,349 @@
+// client/src/core/zkp/partition.rs
+
+use super::recursion::hash_to_fr;
+use super::registry::{CircuitRegistry, CircuitRegistryError, LoadedCircuit};
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use light_poseidon::{Poseidon, PoseidonHasher};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, sync::Arc};
+use thiserror::Error;
+use tract_onnx::pb::GraphProto;
+
+/// Ops that open a new block; each block proves exactly one of these
+const ANCHOR_OPS: &[&str] = &["Gemm", "MatMul", "Conv"];
+/// Elementwise, pooling and shape ops folded into the preceding block
+const FUSED_OPS: &[&str] = &[
+    "Add", "Relu", "Sigmoid", "Tanh", "BatchNormalization", "MaxPool", "AveragePool",
+    "GlobalAveragePool", "Softmax", "Flatten", "Reshape", "Identity", "Dropout",
+];
+/// Activations are committed as Q16 fixed-point field elements
+pub const ACTIVATION_FRACTION_BITS: u32 = 16;
+/// Poseidon width is capped at 12 inputs: the running hash plus 11 activations
+const COMMITMENT_RATE: usize = 11;
+
+#[derive(Debug, Error)]
+pub enum PartitionError {
+    #[error("ONNX parse error: {0}")]
+    Onnx(String),
+    #[error("Node `{0}` does not consume the previous block's output; branching graphs need a block template")]
+    NotSequential(String),
+    #[error("Node `{node}` uses {op}, which no circuit template covers")]
+    UnsupportedOp { node: String, op: String },
+    #[error("Block {block} needs template `{key}`, which is not configured under [zkp.templates]")]
+    NoTemplate { block: usize, key: TemplateKey },
+    #[error("Invalid circuit hash for template `{0}`")]
+    InvalidHash(TemplateKey),
+    #[error("Block {block} expects input commitment {expected}, chain carries {actual}")]
+    BrokenChain { block: usize, expected: String, actual: String },
+    #[error(transparent)]
+    Registry(#[from] CircuitRegistryError),
+}
+
+/// Circuit template a block maps to: fused op chain plus anchor weight dims, e.g. `gemm+relu:128x784`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TemplateKey(pub String);
+
+impl fmt::Display for TemplateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Consecutive ONNX nodes proven by one circuit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub index: usize,
+    pub nodes: Vec<String>,
+    pub ops: Vec<String>,
+    /// Tensor consumed from the previous block (or the graph input)
+    pub input: String,
+    /// Tensor handed to the next block; exposed as a model output to extract the witness
+    pub output: String,
+    pub template: TemplateKey,
+    /// Blake3 over the block's initializers, bound into its proof
+    pub weights_commitment: [u8; 32],
+}
+
+/// A sequential model split into provable blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    pub blocks: Vec<Block>,
+}
+
+impl Partition {
+    pub fn from_onnx(onnx: &[u8]) -> Result<Self, PartitionError> {
+        let proto = tract_onnx::onnx()
+            .proto_model_for_read(&mut &*onnx)
+            .map_err(|e| PartitionError::Onnx(e.to_string()))?;
+        Self::from_graph(&proto.graph.ok_or_else(|| PartitionError::Onnx("model has no graph".into()))?)
+    }
+
+    /// Start a block at every anchor op and fuse the ops that follow it
+    pub fn from_graph(graph: &GraphProto) -> Result<Self, PartitionError> {
+        let initializer = |name: &str| graph.initializer.iter().find(|t| t.name == name);
+        let mut current = graph
+            .input
+            .iter()
+            .map(|i| i.name.as_str())
+            .find(|name| initializer(name).is_none())
+            .ok_or_else(|| PartitionError::Onnx("graph has no runtime input".into()))?
+            .to_string();
+
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut weights: Vec<blake3::Hasher> = Vec::new();
+        let mut dims: Vec<String> = Vec::new();
+        for node in &graph.node {
+            let op = node.op_type.as_str();
+            if !ANCHOR_OPS.contains(&op) && !FUSED_OPS.contains(&op) {
+                return Err(PartitionError::UnsupportedOp {
+                    node: node.name.clone(),
+                    op: op.to_string(),
+                });
+            }
+            // Every other operand must be a constant, otherwise the graph branches
+            let consumes_current = node.input.contains(&current);
+            if !consumes_current || node.input.iter().any(|i| *i != current && initializer(i).is_none() && !i.is_empty()) {
+                return Err(PartitionError::NotSequential(node.name.clone()));
+            }
+
+            if ANCHOR_OPS.contains(&op) || blocks.is_empty() {
+                let anchor_weights = node.input.get(1).and_then(|w| initializer(w)).filter(|_| ANCHOR_OPS.contains(&op));
+                dims.push(anchor_weights.map_or(String::new(), |t| {
+                    t.dims.iter().map(i64::to_string).collect::<Vec<_>>().join("x")
+                }));
+                blocks.push(Block {
+                    index: blocks.len(),
+                    nodes: Vec::new(),
+                    ops: Vec::new(),
+                    input: current.clone(),
+                    output: String::new(),
+                    template: TemplateKey(String::new()),
+                    weights_commitment: [0; 32],
+                });
+                weights.push(blake3::Hasher::new());
+            }
+
+            let block = blocks.last_mut().expect("pushed above");
+            let hasher = weights.last_mut().expect("pushed above");
+            for tensor in node.input.iter().filter_map(|i| initializer(i)) {
+                hasher.update(tensor.name.as_bytes());
+                hasher.update(&tensor.raw_data);
+                for v in &tensor.float_data {
+                    hasher.update(&v.to_le_bytes());
+                }
+            }
+            block.nodes.push(node.name.clone());
+            block.ops.push(op.to_string());
+            current = node.output.first().cloned().unwrap_or_default();
+            block.output = current.clone();
+        }
+
+        if blocks.is_empty() {
+            return Err(PartitionError::Onnx("graph has no nodes".into()));
+        }
+        for ((block, hasher), dims) in blocks.iter_mut().zip(weights).zip(dims) {
+            let ops = block.ops.iter().map(|o| o.to_lowercase()).collect::<Vec<_>>().join("+");
+            block.template = TemplateKey(if dims.is_empty() { ops } else { format!("{ops}:{dims}") });
+            block.weights_commitment = *hasher.finalize().as_bytes();
+        }
+        Ok(Self { blocks })
+    }
+
+    /// Intermediate tensors the runtime must expose, in block order; the last is the model output
+    pub fn boundaries(&self) -> impl Iterator<Item = &str> {
+        self.blocks.iter().map(|b| b.output.as_str())
+    }
+
+    /// Resolve each block's template to a verified circuit.
+    ///
+    /// `templates` maps template keys to hex circuit hashes, as in `[zkp.templates]`.
+    pub async fn resolve(
+        &self,
+        templates: &HashMap<String, String>,
+        registry: &CircuitRegistry,
+    ) -> Result<Vec<Arc<LoadedCircuit>>, PartitionError> {
+        let mut circuits = Vec::with_capacity(self.blocks.len());
+        for block in &self.blocks {
+            let hash = templates.get(&block.template.0).ok_or_else(|| PartitionError::NoTemplate {
+                block: block.index,
+                key: block.template.clone(),
+            })?;
+            let mut circuit_hash = [0u8; 32];
+            hex::decode_to_slice(hash, &mut circuit_hash)
+                .map_err(|_| PartitionError::InvalidHash(block.template.clone()))?;
+            circuits.push(registry.load(&circuit_hash).await?);
+        }
+        Ok(circuits)
+    }
+}
+
+/// Activation as a signed Q16 field element, negatives wrapping to `p - |x|`
+pub fn activation_to_fr(value: f32) -> Fr {
+    let fixed = (value as f64 * (1u64 << ACTIVATION_FRACTION_BITS) as f64).round() as i64;
+    if fixed < 0 {
+        -Fr::from(fixed.unsigned_abs())
+    } else {
+        Fr::from(fixed as u64)
+    }
+}
+
+/// Poseidon chain `h = H(h, x_0..x_10)` over the fixed-point activations, seeded with their count.
+///
+/// Block circuits recompute this for their input and output, which is what links
+/// one block's proof to the next.
+pub fn commit_activations(values: &[f32]) -> [u8; 32] {
+    let mut state = Fr::from(values.len() as u64);
+    for chunk in values.chunks(COMMITMENT_RATE) {
+        let mut inputs = Vec::with_capacity(COMMITMENT_RATE + 1);
+        inputs.push(state);
+        inputs.extend(chunk.iter().map(|&v| activation_to_fr(v)));
+        inputs.resize(COMMITMENT_RATE + 1, Fr::from(0u64));
+        let mut poseidon = Poseidon::<Fr>::new_circom(COMMITMENT_RATE + 1).expect("width within 1..=12");
+        state = poseidon.hash(&inputs).expect("input count matches width");
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&state.into_bigint().to_bytes_be());
+    out
+}
+
+/// Proof for one block, bound to its weights and its input/output activations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockProof {
+    pub template: TemplateKey,
+    #[serde(with = "hex::serde")]
+    pub circuit_hash: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub weights_commitment: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub input_commitment: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub output_commitment: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub proof: Vec<u8>,
+}
+
+impl BlockProof {
+    /// Public inputs in the order every block template declares them
+    pub fn public_inputs(&self) -> [Fr; 3] {
+        [
+            hash_to_fr(&self.weights_commitment),
+            hash_to_fr(&self.input_commitment),
+            hash_to_fr(&self.output_commitment),
+        ]
+    }
+}
+
+/// Per-block proofs whose commitments chain from the model input to its output
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkedProof {
+    pub blocks: Vec<BlockProof>,
+}
+
+impl ChunkedProof {
+    /// Each block must start where the previous one ended; individual proofs are checked separately
+    pub fn check_chain(&self, input_commitment: &[u8; 32], output_commitment: &[u8; 32]) -> Result<(), PartitionError> {
+        let mut carried = *input_commitment;
+        for (index, block) in self.blocks.iter().enumerate() {
+            if block.input_commitment != carried {
+                return Err(PartitionError::BrokenChain {
+                    block: index,
+                    expected: hex::encode(block.input_commitment),
+                    actual: hex::encode(carried),
+                });
+            }
+            carried = block.output_commitment;
+        }
+        if carried != *output_commitment {
+            return Err(PartitionError::BrokenChain {
+                block: self.blocks.len(),
+                expected: hex::encode(output_commitment),
+                actual: hex::encode(carried),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tract_onnx::pb::{NodeProto, TensorProto, ValueInfoProto};
+
+    fn node(name: &str, op: &str, inputs: &[&str], output: &str) -> NodeProto {
+        NodeProto {
+            name: name.into(),
+            op_type: op.into(),
+            input: inputs.iter().map(|i| i.to_string()).collect(),
+            output: vec![output.into()],
+            ..Default::default()
+        }
+    }
+
+    fn weights(name: &str, dims: &[i64]) -> TensorProto {
+        TensorProto {
+            name: name.into(),
+            dims: dims.to_vec(),
+            data_type: 1,
+            float_data: vec![0.5; dims.iter().product::<i64>() as usize],
+            ..Default::default()
+        }
+    }
+
+    fn mlp() -> GraphProto {
+        GraphProto {
+            input: vec![ValueInfoProto { name: "x".into(), ..Default::default() }],
+            initializer: vec![weights("w1", &[128, 784]), weights("b1", &[128]), weights("w2", &[10, 128])],
+            node: vec![
+                node("flatten", "Flatten", &["x"], "x_flat"),
+                node("fc1", "Gemm", &["x_flat", "w1", "b1"], "h1"),
+                node("relu1", "Relu", &["h1"], "a1"),
+                node("fc2", "MatMul", &["a1", "w2"], "logits"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn splits_at_anchor_ops() {
+        let partition = Partition::from_graph(&mlp()).unwrap();
+        let keys: Vec<_> = partition.blocks.iter().map(|b| b.template.0.as_str()).collect();
+        assert_eq!(keys, ["flatten", "gemm+relu:128x784", "matmul:10x128"]);
+        assert_eq!(partition.boundaries().collect::<Vec<_>>(), ["x_flat", "a1", "logits"]);
+        assert_eq!(partition.blocks[2].input, "a1");
+        assert_ne!(partition.blocks[1].weights_commitment, partition.blocks[2].weights_commitment);
+    }
+
+    #[test]
+    fn rejects_branches_and_unknown_ops() {
+        let mut residual = mlp();
+        residual.node.push(node("skip", "Add", &["logits", "x_flat"], "out"));
+        assert!(matches!(Partition::from_graph(&residual), Err(PartitionError::NotSequential(n)) if n == "skip"));
+
+        let mut lstm = mlp();
+        lstm.node.push(node("rnn", "LSTM", &["logits"], "out"));
+        assert!(matches!(Partition::from_graph(&lstm), Err(PartitionError::UnsupportedOp { op, .. }) if op == "LSTM"));
+    }
+
+    #[test]
+    fn chain_links_block_commitments() {
+        let c = |values: &[f32]| commit_activations(values);
+        let (input, hidden, output) = (c(&[0.5, -1.0]), c(&[2.0; 20]), c(&[0.25]));
+        assert_ne!(c(&[0.5, -1.0]), c(&[-1.0, 0.5]));
+
+        let block = |i, o| BlockProof {
+            template: TemplateKey("gemm".into()),
+            circuit_hash: [0; 32],
+            weights_commitment: [1; 32],
+            input_commitment: i,
+            output_commitment: o,
+            proof: vec![],
+        };
+        let proof = ChunkedProof { blocks: vec![block(input, hidden), block(hidden, output)] };
+        assert!(proof.check_chain(&input, &output).is_ok());
+
+        let forged = ChunkedProof { blocks: vec![block(input, hidden), block(c(&[9.0]), output)] };
+        assert!(matches!(forged.check_chain(&input, &output), Err(PartitionError::BrokenChain { block: 1, .. })));
+    }
+}
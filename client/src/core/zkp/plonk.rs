@@ -0,0 +1,233 @@
+// client/src/core/zkp/plonk.rs
+//
+// Verifier for snarkjs PLONK proofs over BN254, following snarkjs'
+// `plonk_verify`: Keccak-256 Fiat-Shamir transcript, one batched KZG
+// opening at xi and xi * w, and a single two-pairing check.
+
+use super::verifier::{fr_json, g1_json, g2_json, VerifyError};
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+
+/// snarkjs `proof.json` for `"protocol": "plonk"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlonkProof {
+    pub a: G1Affine,
+    pub b: G1Affine,
+    pub c: G1Affine,
+    pub z: G1Affine,
+    pub t1: G1Affine,
+    pub t2: G1Affine,
+    pub t3: G1Affine,
+    pub wxi: G1Affine,
+    pub wxiw: G1Affine,
+    pub eval_a: Fr,
+    pub eval_b: Fr,
+    pub eval_c: Fr,
+    pub eval_s1: Fr,
+    pub eval_s2: Fr,
+    pub eval_zw: Fr,
+}
+
+/// snarkjs `verification_key.json` for `"protocol": "plonk"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlonkVerifyingKey {
+    pub n_public: usize,
+    /// Domain size is `2^power`
+    pub power: u32,
+    pub k1: Fr,
+    pub k2: Fr,
+    pub qm: G1Affine,
+    pub ql: G1Affine,
+    pub qr: G1Affine,
+    pub qo: G1Affine,
+    pub qc: G1Affine,
+    pub s1: G1Affine,
+    pub s2: G1Affine,
+    pub s3: G1Affine,
+    pub x_2: G2Affine,
+    /// Generator of the evaluation domain
+    pub w: Fr,
+}
+
+impl PlonkProof {
+    pub fn from_json(json: &Value) -> Result<Self, VerifyError> {
+        let g1 = |k: &str| g1_json(&json[k], "PLONK proof");
+        let fr = |k: &str| fr_json(&json[k], "PLONK proof");
+        Ok(Self {
+            a: g1("A")?,
+            b: g1("B")?,
+            c: g1("C")?,
+            z: g1("Z")?,
+            t1: g1("T1")?,
+            t2: g1("T2")?,
+            t3: g1("T3")?,
+            wxi: g1("Wxi")?,
+            wxiw: g1("Wxiw")?,
+            eval_a: fr("eval_a")?,
+            eval_b: fr("eval_b")?,
+            eval_c: fr("eval_c")?,
+            eval_s1: fr("eval_s1")?,
+            eval_s2: fr("eval_s2")?,
+            eval_zw: fr("eval_zw")?,
+        })
+    }
+}
+
+impl PlonkVerifyingKey {
+    pub fn from_json(json: &Value) -> Result<Self, VerifyError> {
+        let g1 = |k: &str| g1_json(&json[k], "PLONK verifying key");
+        let fr = |k: &str| fr_json(&json[k], "PLONK verifying key");
+        let int = |k: &str| {
+            json[k].as_u64().ok_or_else(|| VerifyError::Malformed {
+                what: "PLONK verifying key",
+                reason: format!("`{k}` is not an integer"),
+            })
+        };
+        Ok(Self {
+            n_public: int("nPublic")? as usize,
+            power: int("power")? as u32,
+            k1: fr("k1")?,
+            k2: fr("k2")?,
+            qm: g1("Qm")?,
+            ql: g1("Ql")?,
+            qr: g1("Qr")?,
+            qo: g1("Qo")?,
+            qc: g1("Qc")?,
+            s1: g1("S1")?,
+            s2: g1("S2")?,
+            s3: g1("S3")?,
+            x_2: g2_json(&json["X_2"], "PLONK verifying key")?,
+            w: fr("w")?,
+        })
+    }
+}
+
+/// Keccak-256 Fiat-Shamir transcript in snarkjs' encoding:
+/// points as uncompressed big-endian `x || y`, scalars as 32-byte big-endian
+#[derive(Default)]
+struct Transcript {
+    buffer: Vec<u8>,
+}
+
+impl Transcript {
+    fn point(&mut self, p: &G1Affine) -> &mut Self {
+        let (x, y) = p.xy().map_or((Fq::zero(), Fq::zero()), |(x, y)| (*x, *y));
+        self.buffer.extend(x.into_bigint().to_bytes_be());
+        self.buffer.extend(y.into_bigint().to_bytes_be());
+        self
+    }
+
+    fn scalar(&mut self, s: &Fr) -> &mut Self {
+        self.buffer.extend(s.into_bigint().to_bytes_be());
+        self
+    }
+
+    /// Hash and reset
+    fn challenge(&mut self) -> Fr {
+        let hash = Keccak256::digest(&self.buffer);
+        self.buffer.clear();
+        Fr::from_be_bytes_mod_order(&hash)
+    }
+}
+
+struct Challenges {
+    beta: Fr,
+    gamma: Fr,
+    alpha: Fr,
+    xi: Fr,
+    /// `v^1 ..= v^5`
+    v: [Fr; 5],
+    u: Fr,
+}
+
+fn challenges(proof: &PlonkProof, vk: &PlonkVerifyingKey, public: &[Fr]) -> Challenges {
+    let mut t = Transcript::default();
+    for p in [&vk.qm, &vk.ql, &vk.qr, &vk.qo, &vk.qc, &vk.s1, &vk.s2, &vk.s3] {
+        t.point(p);
+    }
+    for s in public {
+        t.scalar(s);
+    }
+    let beta = t.point(&proof.a).point(&proof.b).point(&proof.c).challenge();
+    let gamma = t.scalar(&beta).challenge();
+    let alpha = t.scalar(&beta).scalar(&gamma).point(&proof.z).challenge();
+    let xi = t.scalar(&alpha).point(&proof.t1).point(&proof.t2).point(&proof.t3).challenge();
+    for s in [&xi, &proof.eval_a, &proof.eval_b, &proof.eval_c, &proof.eval_s1, &proof.eval_s2, &proof.eval_zw] {
+        t.scalar(s);
+    }
+    let v1 = t.challenge();
+    let mut v = [v1; 5];
+    for i in 1..5 {
+        v[i] = v[i - 1] * v1;
+    }
+    let u = t.point(&proof.wxi).point(&proof.wxiw).challenge();
+    Challenges { beta, gamma, alpha, xi, v, u }
+}
+
+/// Full verification: transcript, linearization, and the KZG pairing check
+pub fn verify(proof: &PlonkProof, vk: &PlonkVerifyingKey, public: &[Fr]) -> Result<(), VerifyError> {
+    if public.len() != vk.n_public {
+        return Err(VerifyError::PublicInputCount {
+            expected: vk.n_public,
+            actual: public.len(),
+        });
+    }
+    let ch = challenges(proof, vk, public);
+
+    // 1. Vanishing polynomial and the Lagrange bases of the public inputs at xi
+    let xin = (0..vk.power).fold(ch.xi, |x, _| x.square());
+    let zh = xin - Fr::one();
+    let n = Fr::from(1u64 << vk.power);
+    let mut lagrange = Vec::with_capacity(vk.n_public.max(1));
+    let mut w = Fr::one();
+    for _ in 0..vk.n_public.max(1) {
+        let denominator = (n * (ch.xi - w)).inverse().ok_or(VerifyError::Invalid)?;
+        lagrange.push(w * zh * denominator);
+        w *= vk.w;
+    }
+    let l1 = lagrange[0];
+    let pi = public.iter().zip(&lagrange).fold(Fr::zero(), |acc, (p, l)| acc - *p * l);
+
+    // 2. Constant part of the linearization
+    let alpha_sq = ch.alpha.square();
+    let perm_a = proof.eval_a + ch.beta * proof.eval_s1 + ch.gamma;
+    let perm_b = proof.eval_b + ch.beta * proof.eval_s2 + ch.gamma;
+    let r0 = pi - l1 * alpha_sq - perm_a * perm_b * (proof.eval_c + ch.gamma) * proof.eval_zw * ch.alpha;
+
+    // 3. Linearization commitment D
+    let gates = vk.qm * (proof.eval_a * proof.eval_b) + vk.ql * proof.eval_a + vk.qr * proof.eval_b + vk.qo * proof.eval_c + vk.qc;
+    let beta_xi = ch.beta * ch.xi;
+    let z_coeff = (proof.eval_a + beta_xi + ch.gamma)
+        * (proof.eval_b + beta_xi * vk.k1 + ch.gamma)
+        * (proof.eval_c + beta_xi * vk.k2 + ch.gamma)
+        * ch.alpha
+        + l1 * alpha_sq
+        + ch.u;
+    let s3_coeff = perm_a * perm_b * ch.alpha * ch.beta * proof.eval_zw;
+    let quotient = (proof.t1 + proof.t2 * xin + proof.t3 * xin.square()) * zh;
+    let d: G1Projective = gates + proof.z * z_coeff - vk.s3 * s3_coeff - quotient;
+
+    // 4. Batched opening: commitment F and claimed evaluation E
+    let f = d + proof.a * ch.v[0] + proof.b * ch.v[1] + proof.c * ch.v[2] + vk.s1 * ch.v[3] + vk.s2 * ch.v[4];
+    let e = -r0
+        + ch.v[0] * proof.eval_a
+        + ch.v[1] * proof.eval_b
+        + ch.v[2] * proof.eval_c
+        + ch.v[3] * proof.eval_s1
+        + ch.v[4] * proof.eval_s2
+        + ch.u * proof.eval_zw;
+    let e = G1Affine::generator() * e;
+
+    // 5. e(-(Wxi + u Wxiw), X_2) * e(xi Wxi + u xi w Wxiw + F - E, G2) == 1
+    let a1 = proof.wxi + proof.wxiw * ch.u;
+    let b1 = proof.wxi * ch.xi + proof.wxiw * (ch.u * ch.xi * vk.w) + f - e;
+    let check = Bn254::multi_pairing([(-a1).into_affine(), b1.into_affine()], [vk.x_2, G2Affine::generator()]);
+    if check.is_zero() {
+        Ok(())
+    } else {
+        Err(VerifyError::Invalid)
+    }
+}
@@ -1,80 +1,175 @@
 // local_engine/src/zk/prover.rs
 
-use ark_circom::{CircomBuilder, CircomConfig};
-use ark_groth16::{create_random_proof, ProvingKey};
-use ark_relations::r1cs::{ConstraintMatrices, Matrix};
+use super::registry::LoadedCircuit;
+use super::wgpu_msm::{WgpuMsm, WgpuMsmError};
+use super::witness_cache::{WitnessCache, WitnessKey};
+use crate::config::{GpuBackend, ZkpConfig};
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Projective};
+use ark_circom::{CircomBuilder, CircomCircuit, CircomConfig};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{PrimeField, UniformRand};
+use ark_groth16::{
+    create_random_proof,
+    r1cs_to_qap::{LibsnarkReduction, R1CSToQAP},
+    Proof, ProvingKey,
+};
+use ark_poly::GeneralEvaluationDomain;
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem, Matrix, OptimizationGoal, SynthesisError,
+};
 use ark_snark::SNARK;
+use ark_std::rand::Rng;
 use cudart::execution::CudaStream;
 use rustacuda::memory::DeviceBuffer;
 use rustacuda::prelude::*;
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 /// High-performance ZK prover with GPU acceleration
 pub struct ZKProver {
     pk: Arc<ProvingKey<ark_bn254::Bn254>>,
-    cuda_ctx: Context,
-    cuda_stream: Stream,
+    /// Present only with `zkp.gpu_backend = "cuda"`
+    cuda: Option<(Context, Stream)>,
+    msm: MsmBackend,
+    witnesses: Option<Arc<WitnessCache>>,
 }
 
 impl ZKProver {
-    /// Initialize prover with parameters and CUDA context
-    pub async fn new(params_path: &str) -> Result<Self, ProverError> {
+    /// Initialize prover with parameters and the configured GPU backend
+    pub async fn new(params_path: &str, zkp: &ZkpConfig) -> Result<Self, ProverError> {
         // Load proving key
         let pk = load_proving_key(params_path).await?;
-        
-        // Initialize CUDA
-        let cuda_ctx = Context::new(
-            &Device::get_device(0)?,
-            ContextFlags::SCHED_AUTO,
-            Stream::new(StreamFlags::NON_BLOCKING, None)?
-        )?;
-        
-        // Preload matrices to GPU memory
-        let matrices = ConstraintMatrices::from(&pk.vk);
-        let (a_gpu, b_gpu, c_gpu) = self.upload_matrices_to_gpu(matrices)?;
+
+        let (cuda, msm) = match zkp.gpu_backend {
+            GpuBackend::Cuda => {
+                // Initialize CUDA
+                let cuda_stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+                let cuda_ctx = Context::new(
+                    &Device::get_device(0)?,
+                    ContextFlags::SCHED_AUTO,
+                    cuda_stream,
+                )?;
+
+                // Preload matrices to GPU memory
+                let matrices = ConstraintMatrices::from(&pk.vk);
+                let (a_gpu, b_gpu, c_gpu) = self.upload_matrices_to_gpu(matrices)?;
+
+                (Some((cuda_ctx, cuda_stream)), MsmBackend::Cpu)
+            }
+            GpuBackend::Wgpu => (None, MsmBackend::wgpu_or_cpu()),
+            GpuBackend::Cpu => (None, MsmBackend::Cpu),
+        };
 
         Ok(Self {
             pk: Arc::new(pk),
-            cuda_ctx,
-            cuda_stream,
+            cuda,
+            msm,
+            witnesses: None,
         })
     }
 
+    /// Share computed witnesses with other provers, e.g. across a batch job
+    pub fn with_witness_cache(mut self, cache: Arc<WitnessCache>) -> Self {
+        self.witnesses = Some(cache);
+        self
+    }
+
+    /// Prove a registry circuit, reusing the witness when these inputs were seen before
+    pub async fn prove_circuit(
+        &self,
+        circuit: &LoadedCircuit,
+        inputs: &[(&str, Vec<Fr>)],
+    ) -> Result<Vec<u8>, ProverError> {
+        let started = Instant::now();
+        let config = circuit
+            .circom_config()
+            .map_err(|e| ProverError::CircuitBuildError(e.to_string()))?;
+        let r1cs = config.r1cs.clone();
+
+        // 1. Witness from the cache, or from the circuit's wasm calculator
+        let key = WitnessKey::new(&circuit.circuit_hash, inputs);
+        let witness = match self.witnesses.as_ref().and_then(|cache| cache.get(&key)) {
+            Some(witness) => witness,
+            None => {
+                let mut builder = CircomBuilder::new(config);
+                for (name, values) in inputs {
+                    for value in values {
+                        builder.push_input(*name, num_bigint::BigUint::from(value.into_bigint()));
+                    }
+                }
+                let witness = builder
+                    .build()
+                    .map_err(|e| ProverError::CircuitBuildError(e.to_string()))?
+                    .witness
+                    .ok_or_else(|| ProverError::CircuitBuildError("witness calculator returned nothing".into()))?;
+                match &self.witnesses {
+                    Some(cache) => cache.insert(key, witness),
+                    None => Arc::new(witness),
+                }
+            }
+        };
+
+        // 2. Prove from the full assignment; synthesis skips the calculator entirely
+        let circom = CircomCircuit { r1cs, witness: Some(witness.to_vec()) };
+        let proof = create_proof_with_msm(circom, &self.pk, &mut rand::thread_rng(), &self.msm)?;
+        let mut proof_bytes = Vec::new();
+        proof.serialize_compressed(&mut proof_bytes)?;
+
+        crate::metrics::log_proof(started.elapsed(), self.backend_label());
+        Ok(proof_bytes)
+    }
+
     /// Generate proof with GPU acceleration
     pub async fn generate_proof(
         &self,
         circuit_config: CircomConfig,
         inputs: &[(&str, Vec<ark_bn254::Fr>)],
     ) -> Result<Vec<u8>, ProverError> {
+        let started = Instant::now();
+
         // Build circuit
         let builder = CircomBuilder::new(circuit_config);
         let circom = builder.setup();
 
-        // Generate witness on CPU
-        let witness = circom.build(inputs)?;
-        
-        // Offload computation to GPU
-        let (a_dev, b_dev, c_dev) = self.upload_witness_to_gpu(witness)?;
-        
-        // Execute GPU-accelerated proof generation
-        let proof = unsafe {
-            self.cuda_stream.synchronize()?;
-            create_cuda_proof(
-                &self.pk,
-                a_dev,
-                b_dev,
-                c_dev,
-                &self.cuda_stream,
-            )?
+        let proof = match &self.cuda {
+            Some((_, cuda_stream)) => {
+                // Generate witness on CPU
+                let witness = circom.build(inputs)?;
+
+                // Offload computation to GPU
+                let (a_dev, b_dev, c_dev) = self.upload_witness_to_gpu(witness)?;
+
+                // Execute GPU-accelerated proof generation
+                unsafe {
+                    cuda_stream.synchronize()?;
+                    create_cuda_proof(
+                        &self.pk,
+                        a_dev,
+                        b_dev,
+                        c_dev,
+                        cuda_stream,
+                    )?
+                }
+            }
+            // wgpu or CPU: only the G1 MSMs differ
+            None => create_proof_with_msm(circom, &self.pk, &mut rand::thread_rng(), &self.msm)?,
         };
 
         // Serialize proof for blockchain
         let mut proof_bytes = Vec::new();
         proof.serialize_compressed(&mut proof_bytes)?;
 
+        crate::metrics::log_proof(started.elapsed(), self.backend_label());
         Ok(proof_bytes)
     }
 
+    fn backend_label(&self) -> &'static str {
+        match (&self.cuda, &self.msm) {
+            (Some(_), _) => "cuda",
+            (None, MsmBackend::Wgpu(_)) => "wgpu",
+            (None, MsmBackend::Cpu) => "cpu",
+        }
+    }
+
     // CUDA memory management
     fn upload_matrices_to_gpu(
         &self,
@@ -137,19 +232,105 @@ unsafe fn create_cuda_proof(
     Proof::deserialize_uncompressed(&h_proof[..])
 }
 
+/// Engine for the G1 multi-scalar multiplications of a Groth16 proof
+pub enum MsmBackend {
+    /// arkworks Pippenger on the CPU
+    Cpu,
+    /// Any Vulkan/Metal/DX12 GPU through wgpu
+    Wgpu(WgpuMsm),
+}
+
+impl MsmBackend {
+    /// wgpu when an adapter is available, otherwise CPU
+    pub fn wgpu_or_cpu() -> Self {
+        match WgpuMsm::new() {
+            Ok(gpu) => MsmBackend::Wgpu(gpu),
+            Err(e) => {
+                tracing::warn!(error = %e, "wgpu MSM unavailable, proving on CPU");
+                MsmBackend::Cpu
+            }
+        }
+    }
+
+    /// MSM over the common prefix of `bases` and `scalars`, as arkworks does
+    pub fn msm_g1(&self, bases: &[G1Affine], scalars: &[Fr]) -> Result<G1Projective, ProverError> {
+        let n = bases.len().min(scalars.len());
+        match self {
+            MsmBackend::Cpu => Ok(G1Projective::msm_unchecked(&bases[..n], &scalars[..n])),
+            MsmBackend::Wgpu(gpu) => Ok(gpu.msm(&bases[..n], &scalars[..n])?),
+        }
+    }
+}
+
+/// Groth16 prover with the G1 MSMs routed through `msm`.
+///
+/// Mirrors `ark_groth16`'s prover; the G2 MSM for B stays on the CPU since
+/// it is a single, comparatively small term.
+pub fn create_proof_with_msm<C: ConstraintSynthesizer<Fr>>(
+    circuit: C,
+    pk: &ProvingKey<Bn254>,
+    rng: &mut impl Rng,
+    msm: &MsmBackend,
+) -> Result<Proof<Bn254>, ProverError> {
+    let r = Fr::rand(rng);
+    let s = Fr::rand(rng);
+
+    // 1. Synthesize and compute the QAP witness
+    let cs = ConstraintSystem::new_ref();
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    circuit.generate_constraints(cs.clone())?;
+    cs.finalize();
+    let h = LibsnarkReduction::witness_map::<Fr, GeneralEvaluationDomain<Fr>>(cs.clone())?;
+    let prover = cs.borrow().ok_or(SynthesisError::MissingCS)?;
+    let assignment = [&prover.instance_assignment[1..], &prover.witness_assignment[..]].concat();
+
+    // 2. G1 MSMs on the selected backend
+    let h_acc = msm.msm_g1(&pk.h_query, &h)?;
+    let l_aux_acc = msm.msm_g1(&pk.l_query, &prover.witness_assignment)?;
+    let a_acc = msm.msm_g1(&pk.a_query[1..], &assignment)?;
+    let b_g1_acc = msm.msm_g1(&pk.b_g1_query[1..], &assignment)?;
+    let b_g2_acc = G2Projective::msm_unchecked(&pk.b_g2_query[1..], &assignment);
+
+    // 3. Assemble A, B, C with blinding factors r, s
+    let g_a = pk.delta_g1 * r + pk.vk.alpha_g1 + pk.a_query[0] + a_acc;
+    let g1_b = pk.delta_g1 * s + pk.beta_g1 + pk.b_g1_query[0] + b_g1_acc;
+    let g2_b = pk.vk.delta_g2 * s + pk.vk.beta_g2 + pk.b_g2_query[0] + b_g2_acc;
+    let g_c = g_a * s + g1_b * r - pk.delta_g1 * (r * s) + l_aux_acc + h_acc;
+
+    Ok(Proof {
+        a: g_a.into_affine(),
+        b: g2_b.into_affine(),
+        c: g_c.into_affine(),
+    })
+}
+
 /// Error handling
 #[derive(Debug)]
 pub enum ProverError {
     ArkSerialization(ark_serialize::SerializationError),
     CudaError(CudaError),
     CircuitBuildError(String),
+    Synthesis(SynthesisError),
+    Wgpu(WgpuMsmError),
     // ...
 }
 
+impl From<SynthesisError> for ProverError {
+    fn from(e: SynthesisError) -> Self {
+        ProverError::Synthesis(e)
+    }
+}
+
+impl From<WgpuMsmError> for ProverError {
+    fn from(e: WgpuMsmError) -> Self {
+        ProverError::Wgpu(e)
+    }
+}
+
 // Async proof generation example
 #[tokio::main]
 async fn main() -> Result<(), ProverError> {
-    let prover = ZKProver::new("params/zk_ai.params").await?;
+    let prover = ZKProver::new("params/zk_ai.params", &ZkpConfig::default()).await?;
     
     let config = CircomConfig::<ark_bn254::Fr>::new(
         "circuits/inference_js/inference.wasm",
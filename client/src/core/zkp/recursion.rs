@@ -0,0 +1,281 @@
+// client/src/core/zkp/recursion.rs
+
+use ark_bn254::{constraints::GVar, Bn254, Fq, Fr, G1Affine, G1Projective as G1, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::Groth16;
+use ark_grumpkin::{constraints::GVar as GVar2, Projective as G2};
+use folding_schemes::{
+    commitment::{kzg::KZG, pedersen::Pedersen},
+    folding::nova::{
+        decider_eth::{Decider as DeciderEth, VerifierParam},
+        Nova, PreprocessorParam,
+    },
+    frontend::{circom::CircomFCircuit, FCircuit},
+    transcript::poseidon::poseidon_canonical_config,
+    Decider, FoldingScheme,
+};
+use model_registry::{FoldedProof, FoldedVk};
+use rand::{CryptoRng, RngCore};
+use std::path::PathBuf;
+use thiserror::Error;
+
+type LayerNova = Nova<G1, GVar, G2, GVar2, CircomFCircuit<Fr>, KZG<'static, Bn254>, Pedersen<G2>, false>;
+type LayerDecider = DeciderEth<
+    G1,
+    GVar,
+    G2,
+    GVar2,
+    CircomFCircuit<Fr>,
+    KZG<'static, Bn254>,
+    Pedersen<G2>,
+    Groth16<Bn254>,
+    LayerNova,
+>;
+
+/// State slots reserved by the protocol: [model commitment, activation commitment]
+pub const MIN_STATE_LEN: usize = 2;
+
+#[derive(Debug, Error)]
+pub enum FoldingError {
+    #[error("Circuit error: {0}")]
+    Circuit(String),
+    #[error("Folding step {step} failed: {reason}")]
+    Step { step: u64, reason: String },
+    #[error("Decider proof failed: {0}")]
+    Decider(String),
+    #[error("Folded proof rejected by local verification")]
+    Rejected,
+    #[error("State width {0} below the {MIN_STATE_LEN} protocol slots")]
+    StateTooSmall(usize),
+    #[error("No layers folded")]
+    Empty,
+}
+
+/// Circom circuit for one layer step.
+///
+/// Each step reads `z_i = [model_acc, activation_commitment, ...]`, checks
+/// the layer against its weights commitment (passed as external input), and
+/// outputs the next state.
+#[derive(Debug, Clone)]
+pub struct LayerCircuit {
+    pub r1cs: PathBuf,
+    pub wasm: PathBuf,
+    pub state_len: usize,
+    pub external_inputs_len: usize,
+}
+
+/// Nova and decider parameters for one layer circuit
+pub struct FoldingSetup {
+    circuit: CircomFCircuit<Fr>,
+    nova_params: <LayerNova as FoldingScheme<G1, G2, CircomFCircuit<Fr>>>::ProverParam,
+    nova_vp: <LayerNova as FoldingScheme<G1, G2, CircomFCircuit<Fr>>>::VerifierParam,
+    decider_pp: <LayerDecider as Decider<G1, G2, CircomFCircuit<Fr>, LayerNova>>::ProverParam,
+    decider_vp: <LayerDecider as Decider<G1, G2, CircomFCircuit<Fr>, LayerNova>>::VerifierParam,
+    state_len: usize,
+}
+
+impl FoldingSetup {
+    /// Generate Nova and decider parameters; expensive, run once per circuit
+    pub fn generate<R: RngCore + CryptoRng>(layer: &LayerCircuit, rng: &mut R) -> Result<Self, FoldingError> {
+        if layer.state_len < MIN_STATE_LEN {
+            return Err(FoldingError::StateTooSmall(layer.state_len));
+        }
+        let circuit = CircomFCircuit::<Fr>::new((
+            layer.r1cs.clone().into(),
+            layer.wasm.clone().into(),
+            layer.state_len,
+            layer.external_inputs_len,
+        ))
+        .map_err(|e| FoldingError::Circuit(e.to_string()))?;
+
+        // 1. Nova public parameters over the BN254/Grumpkin cycle
+        let preprocess = PreprocessorParam::new(poseidon_canonical_config::<Fr>(), circuit.clone());
+        let (nova_params, nova_vp) =
+            LayerNova::preprocess(&mut *rng, &preprocess).map_err(|e| FoldingError::Circuit(e.to_string()))?;
+
+        // 2. Decider keys: Groth16 over the Nova verifier circuit plus KZG
+        let seed = LayerNova::init(&(nova_params.clone(), nova_vp.clone()), circuit.clone(), vec![Fr::from(0u64); layer.state_len])
+            .map_err(|e| FoldingError::Circuit(e.to_string()))?;
+        let (decider_pp, decider_vp) = LayerDecider::preprocess(&mut *rng, &(nova_params.clone(), nova_vp.clone()), seed)
+            .map_err(|e| FoldingError::Decider(e.to_string()))?;
+
+        Ok(Self {
+            circuit,
+            nova_params,
+            nova_vp,
+            decider_pp,
+            decider_vp,
+            state_len: layer.state_len,
+        })
+    }
+
+    /// Verifying key in the format stored by `register_folded_verifier`
+    pub fn onchain_vk(&self) -> FoldedVk {
+        onchain_vk(&self.decider_vp)
+    }
+}
+
+/// Folds per-layer inference steps into a single Nova instance
+pub struct InferenceFolder<'a> {
+    setup: &'a FoldingSetup,
+    nova: LayerNova,
+}
+
+impl<'a> InferenceFolder<'a> {
+    /// Start folding with `z_0 = [model_hash, input_hash, 0, ...]`
+    pub fn new(setup: &'a FoldingSetup, model_hash: &[u8; 32], input_hash: &[u8; 32]) -> Result<Self, FoldingError> {
+        let mut z_0 = vec![Fr::from(0u64); setup.state_len];
+        z_0[0] = hash_to_fr(model_hash);
+        z_0[1] = hash_to_fr(input_hash);
+
+        let nova = LayerNova::init(
+            &(setup.nova_params.clone(), setup.nova_vp.clone()),
+            setup.circuit.clone(),
+            z_0,
+        )
+        .map_err(|e| FoldingError::Circuit(e.to_string()))?;
+        Ok(Self { setup, nova })
+    }
+
+    /// Fold one layer; `external_inputs` carry the layer weights commitment and witness
+    pub fn fold_layer<R: RngCore + CryptoRng>(&mut self, rng: &mut R, external_inputs: Vec<Fr>) -> Result<(), FoldingError> {
+        let step = self.steps();
+        self.nova
+            .prove_step(rng, external_inputs, None)
+            .map_err(|e| FoldingError::Step { step, reason: e.to_string() })?;
+        tracing::debug!(step, "Layer folded");
+        Ok(())
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.nova.i.into_bigint().as_ref()[0]
+    }
+
+    /// Compress the folded instance with the decider and check it locally
+    pub fn finalize<R: RngCore + CryptoRng>(self, rng: &mut R) -> Result<FoldedProof, FoldingError> {
+        let num_steps = self.steps();
+        if num_steps == 0 {
+            return Err(FoldingError::Empty);
+        }
+
+        // 1. Decider proof (Groth16 + two KZG openings)
+        let proof = LayerDecider::prove(rng, self.setup.decider_pp.clone(), self.nova.clone())
+            .map_err(|e| FoldingError::Decider(e.to_string()))?;
+
+        // 2. Local verification before anything goes on-chain
+        let verified = LayerDecider::verify(
+            self.setup.decider_vp.clone(),
+            self.nova.i,
+            self.nova.z_0.clone(),
+            self.nova.z_i.clone(),
+            &self.nova.U_i,
+            &self.nova.u_i,
+            &proof,
+        )
+        .map_err(|e| FoldingError::Decider(e.to_string()))?;
+        if !verified {
+            return Err(FoldingError::Rejected);
+        }
+
+        // 3. Folded commitments, as the on-chain verifier expects them
+        let cm_w = (self.nova.U_i.cmW + self.nova.u_i.cmW * proof.r).into_affine();
+        let cm_e = (self.nova.U_i.cmE + proof.cmT * proof.r).into_affine();
+
+        Ok(FoldedProof {
+            num_steps,
+            z_0: self.nova.z_0.iter().map(fr_be).collect(),
+            z_n: self.nova.z_i.iter().map(fr_be).collect(),
+            cm_w: g1_be(&cm_w),
+            cm_e: g1_be(&cm_e),
+            kzg_challenges: [fr_be(&proof.kzg_challenges[0]), fr_be(&proof.kzg_challenges[1])],
+            kzg_evals: [fr_be(&proof.kzg_proofs[0].eval), fr_be(&proof.kzg_proofs[1].eval)],
+            kzg_proofs: [
+                g1_be(&proof.kzg_proofs[0].proof.into_affine()),
+                g1_be(&proof.kzg_proofs[1].proof.into_affine()),
+            ],
+            extra_inputs: vec![fr_be(&self.setup.decider_vp.pp_hash), fr_be(&proof.r)],
+            proof_a: g1_be(&proof.snark_proof.a),
+            proof_b: g2_be(&proof.snark_proof.b),
+            proof_c: g1_be(&proof.snark_proof.c),
+        })
+    }
+}
+
+fn onchain_vk(vp: &VerifierParam<G1, KZG<'static, Bn254>, Groth16<Bn254>>) -> FoldedVk {
+    FoldedVk {
+        alpha_g1: g1_be(&vp.snark_vp.alpha_g1),
+        beta_g2: g2_be(&vp.snark_vp.beta_g2),
+        gamma_g2: g2_be(&vp.snark_vp.gamma_g2),
+        delta_g2: g2_be(&vp.snark_vp.delta_g2),
+        ic: vp.snark_vp.gamma_abc_g1.iter().map(g1_be).collect(),
+        kzg_g2: g2_be(&vp.cs_vp.g2),
+        kzg_tau_g2: g2_be(&vp.cs_vp.beta_g2),
+    }
+}
+
+/// Same mapping as the on-chain `bn254::hash_to_scalar`: clear the top three bits
+pub fn hash_to_fr(hash: &[u8; 32]) -> Fr {
+    let mut bytes = *hash;
+    bytes[0] &= 0x1f;
+    Fr::from_be_bytes_mod_order(&bytes)
+}
+
+fn fr_be(x: &Fr) -> [u8; 32] {
+    to_32(x.into_bigint().to_bytes_be())
+}
+
+fn fq_be(x: &Fq) -> [u8; 32] {
+    to_32(x.into_bigint().to_bytes_be())
+}
+
+fn to_32(bytes: Vec<u8>) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    out
+}
+
+/// EIP-197 G1 encoding; infinity is all zeros
+fn g1_be(p: &G1Affine) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    if let Some((x, y)) = p.xy() {
+        out[..32].copy_from_slice(&fq_be(x));
+        out[32..].copy_from_slice(&fq_be(y));
+    }
+    out
+}
+
+/// EIP-197 G2 encoding: x_im || x_re || y_im || y_re
+fn g2_be(p: &G2Affine) -> [u8; 128] {
+    let mut out = [0u8; 128];
+    if let Some((x, y)) = p.xy() {
+        out[..32].copy_from_slice(&fq_be(&x.c1));
+        out[32..64].copy_from_slice(&fq_be(&x.c0));
+        out[64..96].copy_from_slice(&fq_be(&y.c1));
+        out[96..].copy_from_slice(&fq_be(&y.c0));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_to_fr_matches_onchain_mapping() {
+        let hash = [0xffu8; 32];
+        let fr = hash_to_fr(&hash);
+        let mut expected = hash;
+        expected[0] = 0x1f;
+        assert_eq!(fr_be(&fr), expected);
+    }
+
+    #[test]
+    fn test_g1_encoding() {
+        let g = G1Affine::generator();
+        let encoded = g1_be(&g);
+        assert_eq!(encoded[31], 1);
+        assert_eq!(encoded[63], 2);
+        assert_eq!(g1_be(&G1Affine::identity()), [0u8; 64]);
+    }
+}
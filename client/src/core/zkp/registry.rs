@@ -0,0 +1,376 @@
+// client/src/core/zkp/registry.rs
+
+use crate::config::CircuitRegistryConfig;
+use ark_bn254::Fr;
+use ark_circom::CircomConfig;
+use ark_ff::{BigInteger, PrimeField};
+use light_poseidon::{Poseidon, PoseidonHasher};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+use thiserror::Error;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Error)]
+pub enum CircuitRegistryError {
+    #[error("Circuit {} not listed in registry index", hex::encode(.0))]
+    NotFound([u8; 32]),
+    #[error("Unsupported artifact URI: {0}")]
+    UnsupportedUri(String),
+    #[error("Artifact {name} hash mismatch: expected {expected}, got {actual}")]
+    ArtifactMismatch { name: &'static str, expected: String, actual: String },
+    #[error("Circuit hash mismatch: model expects {expected}, manifest hashes to {actual}")]
+    CircuitMismatch { expected: String, actual: String },
+    #[error("Circuit {name} is pinned to {pinned}, registry offers {offered}")]
+    PinMismatch { name: String, pinned: String, offered: String },
+    #[error("Fetch failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Cache I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    #[error("Circom config error: {0}")]
+    Circom(String),
+}
+
+/// Where an artifact lives: `ipfs://<cid>`, `ar://<tx>`, or a local `file://` path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactUri {
+    Ipfs(String),
+    Arweave(String),
+    File(PathBuf),
+}
+
+impl ArtifactUri {
+    pub fn parse(uri: &str) -> Result<Self, CircuitRegistryError> {
+        if let Some(cid) = uri.strip_prefix("ipfs://") {
+            Ok(ArtifactUri::Ipfs(cid.to_string()))
+        } else if let Some(tx) = uri.strip_prefix("ar://") {
+            Ok(ArtifactUri::Arweave(tx.to_string()))
+        } else if let Some(path) = uri.strip_prefix("file://") {
+            Ok(ArtifactUri::File(PathBuf::from(path)))
+        } else {
+            Err(CircuitRegistryError::UnsupportedUri(uri.to_string()))
+        }
+    }
+
+    fn gateway_url(&self, config: &CircuitRegistryConfig) -> Option<String> {
+        match self {
+            ArtifactUri::Ipfs(cid) => Some(format!("{}/ipfs/{}", config.ipfs_gateway.trim_end_matches('/'), cid)),
+            ArtifactUri::Arweave(tx) => Some(format!("{}/{}", config.arweave_gateway.trim_end_matches('/'), tx)),
+            ArtifactUri::File(_) => None,
+        }
+    }
+}
+
+/// One downloadable circuit file and its blake3 digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    pub uri: String,
+    /// Hex blake3 of the file contents
+    pub blake3: String,
+}
+
+/// Published description of a circuit version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitManifest {
+    pub name: String,
+    pub version: String,
+    pub r1cs: Artifact,
+    pub wasm: Artifact,
+    pub proving_key: Artifact,
+}
+
+impl CircuitManifest {
+    /// Poseidon(r1cs, wasm, proving_key) over the artifact digests.
+    ///
+    /// This is the value stored on-chain in `model.zk_circuit`.
+    pub fn circuit_hash(&self) -> Result<[u8; 32], CircuitRegistryError> {
+        let digests = [&self.r1cs, &self.wasm, &self.proving_key]
+            .iter()
+            .map(|a| parse_digest(&a.blake3).map(|d| digest_to_fr(&d)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(poseidon_hash(&digests))
+    }
+}
+
+/// Registry index: hex circuit hash to manifest URI
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RegistryIndex {
+    pub circuits: HashMap<String, String>,
+}
+
+/// A verified circuit in the local cache
+#[derive(Debug)]
+pub struct LoadedCircuit {
+    pub circuit_hash: [u8; 32],
+    pub manifest: CircuitManifest,
+    pub r1cs_path: PathBuf,
+    pub wasm_path: PathBuf,
+    pub proving_key_path: PathBuf,
+}
+
+impl LoadedCircuit {
+    pub fn circom_config(&self) -> Result<CircomConfig<Fr>, CircuitRegistryError> {
+        CircomConfig::<Fr>::new(&self.wasm_path, &self.r1cs_path)
+            .map_err(|e| CircuitRegistryError::Circom(e.to_string()))
+    }
+
+    pub fn proving_key(&self) -> Result<Vec<u8>, CircuitRegistryError> {
+        Ok(std::fs::read(&self.proving_key_path)?)
+    }
+
+    fn artifacts(&self) -> [(&'static str, &Artifact, &Path); 3] {
+        [
+            ("r1cs", &self.manifest.r1cs, &self.r1cs_path),
+            ("wasm", &self.manifest.wasm, &self.wasm_path),
+            ("proving_key", &self.manifest.proving_key, &self.proving_key_path),
+        ]
+    }
+}
+
+/// Content-addressed circuit store.
+///
+/// Circuits are keyed by the Poseidon hash registered on-chain, fetched from
+/// IPFS/Arweave on first use, verified, and cached under
+/// `<cache_dir>/<hex hash>/`. Loaded circuits are shared behind `Arc`s, so a
+/// model whose `zk_circuit` changes picks up the new circuit on its next
+/// `load` without restarting the client.
+pub struct CircuitRegistry {
+    config: CircuitRegistryConfig,
+    http: reqwest::Client,
+    loaded: RwLock<HashMap<[u8; 32], Arc<LoadedCircuit>>>,
+}
+
+impl CircuitRegistry {
+    pub fn new(config: CircuitRegistryConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+            loaded: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Resolve, verify and cache the circuit committed to by `circuit_hash`
+    pub async fn load(&self, circuit_hash: &[u8; 32]) -> Result<Arc<LoadedCircuit>, CircuitRegistryError> {
+        if let Some(circuit) = self.loaded.read().unwrap().get(circuit_hash) {
+            return Ok(circuit.clone());
+        }
+
+        let dir = self.config.cache_dir.join(hex::encode(circuit_hash));
+        let circuit = match self.load_cached(&dir, circuit_hash) {
+            Ok(circuit) => circuit,
+            Err(e) => {
+                tracing::info!(circuit = %hex::encode(circuit_hash), reason = %e, "Fetching circuit");
+                self.fetch(&dir, circuit_hash).await?
+            }
+        };
+
+        let circuit = Arc::new(circuit);
+        self.loaded.write().unwrap().insert(*circuit_hash, circuit.clone());
+        Ok(circuit)
+    }
+
+    /// Drop in-memory circuits so the next `load` re-reads the cache
+    pub fn invalidate(&self, circuit_hash: &[u8; 32]) {
+        self.loaded.write().unwrap().remove(circuit_hash);
+    }
+
+    /// Verify a cached copy without touching the network
+    fn load_cached(&self, dir: &Path, circuit_hash: &[u8; 32]) -> Result<LoadedCircuit, CircuitRegistryError> {
+        let manifest: CircuitManifest = serde_json::from_slice(&std::fs::read(dir.join(MANIFEST_FILE))?)?;
+        self.check_manifest(&manifest, circuit_hash)?;
+
+        let circuit = loaded_circuit(dir, *circuit_hash, manifest);
+        for (name, artifact, path) in circuit.artifacts() {
+            verify_artifact(name, artifact, &std::fs::read(path)?)?;
+        }
+        Ok(circuit)
+    }
+
+    async fn fetch(&self, dir: &Path, circuit_hash: &[u8; 32]) -> Result<LoadedCircuit, CircuitRegistryError> {
+        // 1. Find the manifest through the registry index
+        let index: RegistryIndex = serde_json::from_slice(&self.read_uri(&self.config.index_uri).await?)?;
+        let manifest_uri = index
+            .circuits
+            .get(&hex::encode(circuit_hash))
+            .ok_or(CircuitRegistryError::NotFound(*circuit_hash))?;
+        let manifest_bytes = self.read_uri(manifest_uri).await?;
+        let manifest: CircuitManifest = serde_json::from_slice(&manifest_bytes)?;
+        self.check_manifest(&manifest, circuit_hash)?;
+
+        // 2. Download and verify every artifact before anything touches the cache
+        std::fs::create_dir_all(dir)?;
+        let circuit = loaded_circuit(dir, *circuit_hash, manifest);
+        for (name, artifact, path) in circuit.artifacts() {
+            let bytes = self.read_uri(&artifact.uri).await?;
+            verify_artifact(name, artifact, &bytes)?;
+            write_atomic(path, &bytes)?;
+        }
+
+        // 3. Manifest last, so a partial download never looks complete
+        write_atomic(&dir.join(MANIFEST_FILE), &manifest_bytes)?;
+        tracing::info!(
+            name = %circuit.manifest.name,
+            version = %circuit.manifest.version,
+            "Circuit cached"
+        );
+        Ok(circuit)
+    }
+
+    /// Manifest must hash to the on-chain value and respect version pins
+    fn check_manifest(&self, manifest: &CircuitManifest, circuit_hash: &[u8; 32]) -> Result<(), CircuitRegistryError> {
+        let actual = manifest.circuit_hash()?;
+        if &actual != circuit_hash {
+            return Err(CircuitRegistryError::CircuitMismatch {
+                expected: hex::encode(circuit_hash),
+                actual: hex::encode(actual),
+            });
+        }
+        if let Some(pinned) = self.config.pins.get(&manifest.name) {
+            if pinned != &manifest.version {
+                return Err(CircuitRegistryError::PinMismatch {
+                    name: manifest.name.clone(),
+                    pinned: pinned.clone(),
+                    offered: manifest.version.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn read_uri(&self, uri: &str) -> Result<Vec<u8>, CircuitRegistryError> {
+        let parsed = ArtifactUri::parse(uri)?;
+        match parsed.gateway_url(&self.config) {
+            Some(url) => Ok(self.http.get(url).send().await?.error_for_status()?.bytes().await?.to_vec()),
+            None => match parsed {
+                ArtifactUri::File(path) => Ok(tokio::fs::read(path).await?),
+                _ => unreachable!(),
+            },
+        }
+    }
+}
+
+fn loaded_circuit(dir: &Path, circuit_hash: [u8; 32], manifest: CircuitManifest) -> LoadedCircuit {
+    LoadedCircuit {
+        circuit_hash,
+        manifest,
+        r1cs_path: dir.join("circuit.r1cs"),
+        wasm_path: dir.join("circuit.wasm"),
+        proving_key_path: dir.join("proving_key.bin"),
+    }
+}
+
+fn verify_artifact(name: &'static str, artifact: &Artifact, bytes: &[u8]) -> Result<(), CircuitRegistryError> {
+    let actual = blake3::hash(bytes).to_hex().to_string();
+    if !actual.eq_ignore_ascii_case(&artifact.blake3) {
+        return Err(CircuitRegistryError::ArtifactMismatch {
+            name,
+            expected: artifact.blake3.clone(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), CircuitRegistryError> {
+    let tmp = path.with_extension("partial");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn parse_digest(hex_digest: &str) -> Result<[u8; 32], CircuitRegistryError> {
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(hex_digest, &mut out).map_err(|_| CircuitRegistryError::ArtifactMismatch {
+        name: "manifest",
+        expected: "32-byte hex digest".into(),
+        actual: hex_digest.to_string(),
+    })?;
+    Ok(out)
+}
+
+/// Clear the top three bits so the digest is a canonical BN254 scalar
+fn digest_to_fr(digest: &[u8; 32]) -> Fr {
+    let mut bytes = *digest;
+    bytes[0] &= 0x1f;
+    Fr::from_be_bytes_mod_order(&bytes)
+}
+
+/// Circom-compatible Poseidon over BN254
+fn poseidon_hash(inputs: &[Fr]) -> [u8; 32] {
+    let mut poseidon = Poseidon::<Fr>::new_circom(inputs.len()).expect("1..=12 inputs");
+    let hash = poseidon.hash(inputs).expect("input count matches width");
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hash.into_bigint().to_bytes_be());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact(contents: &[u8]) -> Artifact {
+        Artifact {
+            uri: "ipfs://bafy".into(),
+            blake3: blake3::hash(contents).to_hex().to_string(),
+        }
+    }
+
+    fn manifest() -> CircuitManifest {
+        CircuitManifest {
+            name: "inference".into(),
+            version: "1.0.0".into(),
+            r1cs: artifact(b"r1cs"),
+            wasm: artifact(b"wasm"),
+            proving_key: artifact(b"pk"),
+        }
+    }
+
+    #[test]
+    fn test_uri_parsing() {
+        assert_eq!(ArtifactUri::parse("ipfs://bafyabc").unwrap(), ArtifactUri::Ipfs("bafyabc".into()));
+        assert_eq!(ArtifactUri::parse("ar://tx123").unwrap(), ArtifactUri::Arweave("tx123".into()));
+        assert!(ArtifactUri::parse("https://example.com/x").is_err());
+    }
+
+    #[test]
+    fn test_circuit_hash_binds_every_artifact() {
+        let base = manifest().circuit_hash().unwrap();
+        let mut changed = manifest();
+        changed.proving_key = artifact(b"other pk");
+        assert_ne!(base, changed.circuit_hash().unwrap());
+
+        // Version metadata is not part of the commitment
+        let mut renamed = manifest();
+        renamed.version = "1.0.1".into();
+        assert_eq!(base, renamed.circuit_hash().unwrap());
+    }
+
+    #[test]
+    fn test_artifact_verification() {
+        let a = artifact(b"wasm");
+        assert!(verify_artifact("wasm", &a, b"wasm").is_ok());
+        assert!(matches!(
+            verify_artifact("wasm", &a, b"tampered"),
+            Err(CircuitRegistryError::ArtifactMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pin_rejects_other_versions() {
+        let m = manifest();
+        let hash = m.circuit_hash().unwrap();
+        let mut config = CircuitRegistryConfig::default();
+        config.pins.insert("inference".into(), "0.9.0".into());
+        let registry = CircuitRegistry::new(config);
+        assert!(matches!(
+            registry.check_manifest(&m, &hash),
+            Err(CircuitRegistryError::PinMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,374 @@
+// client/src/core/zkp/remote.rs
+
+use super::registry::LoadedCircuit;
+use crate::config::RemoteProverConfig;
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use ark_bn254::Fr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hkdf::Hkdf;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::time::Duration;
+use thiserror::Error;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+pub const INFO_PATH: &str = "/v1/info";
+pub const PROVE_PATH: &str = "/v1/prove";
+
+#[derive(Debug, Error)]
+pub enum RemoteProverError {
+    #[error("TLS setup failed: {0}")]
+    Tls(String),
+    #[error("Prover request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Prover rejected the request ({status}): {message}")]
+    Rejected { status: u16, message: String },
+    #[error("Witness could not be sealed or opened")]
+    Crypto,
+    #[error("Prover attestation rejected: {0}")]
+    Attestation(String),
+    #[error("Malformed prover message: {0}")]
+    Malformed(String),
+}
+
+/// What the daemon runs on; signed into every proof attestation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverEnvironment {
+    pub version: String,
+    /// `cuda`, `wgpu` or `cpu`
+    pub backend: String,
+    /// Blake3 of the running `scoria-prover` executable
+    #[serde(with = "hex::serde")]
+    pub binary_hash: [u8; 32],
+}
+
+impl ProverEnvironment {
+    pub fn current(backend: &str) -> std::io::Result<Self> {
+        let binary = std::fs::read(std::env::current_exe()?)?;
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            backend: backend.to_string(),
+            binary_hash: *blake3::hash(&binary).as_bytes(),
+        })
+    }
+
+    fn digest(&self, hasher: &mut blake3::Hasher) {
+        hasher.update(&serde_json::to_vec(self).expect("environment serializes"));
+    }
+}
+
+/// Served at `/v1/info`: the identity key vouches for the witness encryption key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverInfo {
+    #[serde(with = "hex::serde")]
+    pub identity: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub encryption_key: [u8; 32],
+    pub environment: ProverEnvironment,
+    #[serde(with = "hex::serde")]
+    pub signature: [u8; 64],
+}
+
+impl ProverInfo {
+    fn statement(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new_derive_key("scoria-prover info v1");
+        hasher.update(&self.identity).update(&self.encryption_key);
+        self.environment.digest(&mut hasher);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Check the self-signature, then the pinned identity and binary allowlist
+    pub fn verify(&self, config: &RemoteProverConfig) -> Result<(), RemoteProverError> {
+        verify_signature(&self.identity, &self.statement(), &self.signature)?;
+        if let Some(pinned) = &config.identity {
+            if !pinned.eq_ignore_ascii_case(&hex::encode(self.identity)) {
+                return Err(RemoteProverError::Attestation(format!(
+                    "identity {} is not the pinned prover",
+                    hex::encode(self.identity)
+                )));
+            }
+        }
+        let binary = hex::encode(self.environment.binary_hash);
+        if !config.allowed_binaries.is_empty() && !config.allowed_binaries.iter().any(|b| b.eq_ignore_ascii_case(&binary)) {
+            return Err(RemoteProverError::Attestation(format!("prover binary {binary} is not allowed")));
+        }
+        Ok(())
+    }
+}
+
+/// Circuit inputs encrypted to the prover's X25519 key; only the circuit hash travels in clear
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProveRequest {
+    #[serde(with = "hex::serde")]
+    pub circuit_hash: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub ephemeral_key: [u8; 32],
+    #[serde(with = "hex::serde")]
+    pub nonce: [u8; 12],
+    #[serde(with = "hex::serde")]
+    pub ciphertext: Vec<u8>,
+}
+
+impl ProveRequest {
+    /// Seal named circuit inputs for `prover`, binding them to `circuit_hash`
+    pub fn seal(prover: &ProverInfo, circuit_hash: [u8; 32], inputs: &[(&str, Vec<Fr>)]) -> Result<Self, RemoteProverError> {
+        let owned: Vec<(String, Vec<Fr>)> = inputs.iter().map(|(name, values)| (name.to_string(), values.clone())).collect();
+        let mut plaintext = Vec::new();
+        owned.serialize_compressed(&mut plaintext).map_err(|_| RemoteProverError::Crypto)?;
+
+        let ephemeral = StaticSecret::random_from_rng(rand::thread_rng());
+        let ephemeral_key = PublicKey::from(&ephemeral).to_bytes();
+        let shared = ephemeral.diffie_hellman(&PublicKey::from(prover.encryption_key));
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let cipher = witness_cipher(shared.as_bytes(), &ephemeral_key, &prover.encryption_key);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: &plaintext, aad: &circuit_hash })
+            .map_err(|_| RemoteProverError::Crypto)?;
+        Ok(Self { circuit_hash, ephemeral_key, nonce, ciphertext })
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new_derive_key("scoria-prover request v1");
+        hasher.update(&self.circuit_hash).update(&self.ephemeral_key).update(&self.nonce).update(&self.ciphertext);
+        *hasher.finalize().as_bytes()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProveResponse {
+    /// Compressed Groth16 proof, as produced by `ZKProver::prove_circuit`
+    #[serde(with = "hex::serde")]
+    pub proof: Vec<u8>,
+    pub attestation: ProofAttestation,
+}
+
+/// The prover's statement that it produced `proof` for exactly this request in `environment`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofAttestation {
+    pub environment: ProverEnvironment,
+    #[serde(with = "hex::serde")]
+    pub signature: [u8; 64],
+}
+
+impl ProofAttestation {
+    fn statement(environment: &ProverEnvironment, request: &ProveRequest, proof: &[u8]) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new_derive_key("scoria-prover proof v1");
+        hasher.update(&request.digest()).update(blake3::hash(proof).as_bytes());
+        environment.digest(&mut hasher);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// The environment must also match the one the prover advertised at connect time
+    pub fn verify(&self, prover: &ProverInfo, request: &ProveRequest, proof: &[u8]) -> Result<(), RemoteProverError> {
+        if self.environment != prover.environment {
+            return Err(RemoteProverError::Attestation("prover environment changed".into()));
+        }
+        verify_signature(&prover.identity, &Self::statement(&self.environment, request, proof), &self.signature)
+    }
+}
+
+fn verify_signature(identity: &[u8; 32], statement: &[u8; 32], signature: &[u8; 64]) -> Result<(), RemoteProverError> {
+    VerifyingKey::from_bytes(identity)
+        .and_then(|key| key.verify(statement, &Signature::from_bytes(signature)))
+        .map_err(|_| RemoteProverError::Attestation("bad signature".into()))
+}
+
+/// HKDF-SHA256 over the X25519 secret, bound to both public keys
+fn witness_cipher(shared: &[u8; 32], ephemeral_key: &[u8; 32], prover_key: &[u8; 32]) -> Aes256Gcm {
+    let mut info = b"scoria-prover witness v1".to_vec();
+    info.extend_from_slice(ephemeral_key);
+    info.extend_from_slice(prover_key);
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared)
+        .expand(&info, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    Aes256Gcm::new(&key.into())
+}
+
+/// Daemon-side keys. Both the Ed25519 identity and the X25519 witness key derive from one seed.
+pub struct ProverIdentity {
+    signing: SigningKey,
+    encryption: StaticSecret,
+    environment: ProverEnvironment,
+}
+
+impl ProverIdentity {
+    pub fn from_seed(seed: &[u8; 32], environment: ProverEnvironment) -> Self {
+        let mut encryption = [0u8; 32];
+        Hkdf::<Sha256>::new(None, seed)
+            .expand(b"scoria-prover x25519", &mut encryption)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Self {
+            signing: SigningKey::from_bytes(seed),
+            encryption: StaticSecret::from(encryption),
+            environment,
+        }
+    }
+
+    pub fn info(&self) -> ProverInfo {
+        let mut info = ProverInfo {
+            identity: self.signing.verifying_key().to_bytes(),
+            encryption_key: PublicKey::from(&self.encryption).to_bytes(),
+            environment: self.environment.clone(),
+            signature: [0; 64],
+        };
+        info.signature = self.signing.sign(&info.statement()).to_bytes();
+        info
+    }
+
+    /// Decrypt the named circuit inputs of a request
+    pub fn open(&self, request: &ProveRequest) -> Result<Vec<(String, Vec<Fr>)>, RemoteProverError> {
+        let shared = self.encryption.diffie_hellman(&PublicKey::from(request.ephemeral_key));
+        let cipher = witness_cipher(shared.as_bytes(), &request.ephemeral_key, PublicKey::from(&self.encryption).as_bytes());
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&request.nonce), Payload { msg: &request.ciphertext, aad: &request.circuit_hash })
+            .map_err(|_| RemoteProverError::Crypto)?;
+        Vec::<(String, Vec<Fr>)>::deserialize_compressed(&plaintext[..])
+            .map_err(|e| RemoteProverError::Malformed(e.to_string()))
+    }
+
+    pub fn attest(&self, request: &ProveRequest, proof: &[u8]) -> ProofAttestation {
+        ProofAttestation {
+            environment: self.environment.clone(),
+            signature: self.signing.sign(&ProofAttestation::statement(&self.environment, request, proof)).to_bytes(),
+        }
+    }
+}
+
+/// Client for a `scoria-prover serve` daemon, selected by `zkp.remote_prover_url`
+pub struct RemoteProver {
+    http: reqwest::Client,
+    url: String,
+    info: ProverInfo,
+}
+
+impl RemoteProver {
+    /// Open the (m)TLS client and check the prover's advertised identity
+    pub async fn connect(url: &str, config: &RemoteProverConfig) -> Result<Self, RemoteProverError> {
+        let tls = |e: &dyn std::fmt::Display| RemoteProverError::Tls(e.to_string());
+        let mut builder = reqwest::Client::builder()
+            .use_rustls_tls()
+            .timeout(Duration::from_secs(config.timeout_secs));
+        if let Some(ca) = &config.ca_cert {
+            let pem = std::fs::read(ca).map_err(|e| tls(&e))?;
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem).map_err(|e| tls(&e))?);
+        }
+        match (&config.client_cert, &config.client_key) {
+            (Some(cert), Some(key)) => {
+                let mut pem = std::fs::read(cert).map_err(|e| tls(&e))?;
+                pem.extend(std::fs::read(key).map_err(|e| tls(&e))?);
+                builder = builder.identity(reqwest::Identity::from_pem(&pem).map_err(|e| tls(&e))?);
+            }
+            (None, None) => {}
+            _ => return Err(RemoteProverError::Tls("client_cert and client_key must be set together".into())),
+        }
+        let http = builder.build()?;
+        let url = url.trim_end_matches('/').to_string();
+
+        let info: ProverInfo = checked(http.get(format!("{url}{INFO_PATH}")).send().await?).await?.json().await?;
+        info.verify(config)?;
+        tracing::info!(
+            prover = %hex::encode(info.identity),
+            backend = %info.environment.backend,
+            version = %info.environment.version,
+            "Connected to remote prover"
+        );
+        Ok(Self { http, url, info })
+    }
+
+    /// Same contract as `ZKProver::prove_circuit`, executed on the daemon
+    pub async fn prove_circuit(&self, circuit: &LoadedCircuit, inputs: &[(&str, Vec<Fr>)]) -> Result<Vec<u8>, RemoteProverError> {
+        let request = ProveRequest::seal(&self.info, circuit.circuit_hash, inputs)?;
+        let response: ProveResponse = checked(self.http.post(format!("{}{PROVE_PATH}", self.url)).json(&request).send().await?)
+            .await?
+            .json()
+            .await?;
+        response.attestation.verify(&self.info, &request, &response.proof)?;
+        Ok(response.proof)
+    }
+}
+
+async fn checked(response: reqwest::Response) -> Result<reqwest::Response, RemoteProverError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    Err(RemoteProverError::Rejected {
+        status: status.as_u16(),
+        message: response.text().await.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> ProverIdentity {
+        let environment = ProverEnvironment {
+            version: "0.5.0".into(),
+            backend: "cuda".into(),
+            binary_hash: [9; 32],
+        };
+        ProverIdentity::from_seed(&[3; 32], environment)
+    }
+
+    fn inputs() -> Vec<(&'static str, Vec<Fr>)> {
+        vec![("input", vec![Fr::from(1u64), -Fr::from(2u64)]), ("weights_commitment", vec![Fr::from(7u64)])]
+    }
+
+    #[test]
+    fn sealed_inputs_open_only_on_the_prover_for_the_same_circuit() {
+        let prover = identity();
+        let request = ProveRequest::seal(&prover.info(), [1; 32], &inputs()).unwrap();
+
+        let opened = prover.open(&request).unwrap();
+        let expected: Vec<(String, Vec<Fr>)> = inputs().into_iter().map(|(n, v)| (n.to_string(), v)).collect();
+        assert_eq!(opened, expected);
+
+        let mut rebound = request.clone();
+        rebound.circuit_hash = [2; 32];
+        assert!(matches!(prover.open(&rebound), Err(RemoteProverError::Crypto)));
+
+        let other = ProverIdentity::from_seed(&[4; 32], prover.environment.clone());
+        assert!(other.open(&request).is_err());
+    }
+
+    #[test]
+    fn info_checks_pinned_identity_and_binary() {
+        let info = identity().info();
+        let mut config = RemoteProverConfig::default();
+        assert!(info.verify(&config).is_ok());
+
+        config.identity = Some(hex::encode(info.identity).to_uppercase());
+        config.allowed_binaries = vec![hex::encode([9u8; 32])];
+        assert!(info.verify(&config).is_ok());
+
+        config.allowed_binaries = vec![hex::encode([8u8; 32])];
+        assert!(info.verify(&config).is_err());
+
+        // A relabelled environment breaks the self-signature
+        let mut forged = info;
+        forged.environment.backend = "cpu".into();
+        assert!(forged.verify(&RemoteProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn attestation_binds_request_and_proof() {
+        let prover = identity();
+        let info = prover.info();
+        let request = ProveRequest::seal(&info, [1; 32], &inputs()).unwrap();
+        let attestation = prover.attest(&request, b"proof");
+        assert!(attestation.verify(&info, &request, b"proof").is_ok());
+        assert!(attestation.verify(&info, &request, b"other").is_err());
+
+        let replayed = ProveRequest::seal(&info, [1; 32], &inputs()).unwrap();
+        assert!(attestation.verify(&info, &replayed, b"proof").is_err());
+    }
+}
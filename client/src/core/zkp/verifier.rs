@@ -0,0 +1,328 @@
+// client/src/core/zkp/verifier.rs
+
+use super::plonk::{self, PlonkProof, PlonkVerifyingKey};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_groth16::{prepare_verifying_key, Groth16, ProvingKey};
+use ark_serialize::CanonicalDeserialize;
+use serde_json::Value;
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("Malformed {what}: {reason}")]
+    Malformed { what: &'static str, reason: String },
+    #[error("Proof is {proof} but the verifying key is {key}")]
+    ProtocolMismatch { proof: &'static str, key: &'static str },
+    #[error("Verifying key expects {expected} public inputs, got {actual}")]
+    PublicInputCount { expected: usize, actual: usize },
+    #[error("Proof is invalid for these public inputs")]
+    Invalid,
+    #[error("Verifying key does not match on-chain circuit {0}")]
+    KeyMismatch(String),
+    #[error("On-chain lookup failed: {0}")]
+    Chain(String),
+}
+
+impl VerifyError {
+    /// Process exit code for `proof verify`; 0 is reserved for a valid proof
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Invalid => 1,
+            Self::Malformed { .. } | Self::ProtocolMismatch { .. } | Self::PublicInputCount { .. } => 2,
+            Self::KeyMismatch(_) => 3,
+            Self::Chain(_) => 4,
+        }
+    }
+}
+
+/// A proof in snarkjs JSON or arkworks compressed (Groth16 only) encoding
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Proof {
+    Groth16(ark_groth16::Proof<Bn254>),
+    Plonk(PlonkProof),
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerifyingKey {
+    Groth16(ark_groth16::VerifyingKey<Bn254>),
+    Plonk(PlonkVerifyingKey),
+}
+
+impl Proof {
+    pub fn parse(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let Some(json) = parse_json(bytes, "proof")? else {
+            return ark_groth16::Proof::deserialize_compressed(bytes)
+                .map(Self::Groth16)
+                .map_err(|e| malformed("proof", e));
+        };
+        match protocol(&json, "proof")? {
+            "groth16" => Ok(Self::Groth16(ark_groth16::Proof {
+                a: g1_json(&json["pi_a"], "Groth16 proof")?,
+                b: g2_json(&json["pi_b"], "Groth16 proof")?,
+                c: g1_json(&json["pi_c"], "Groth16 proof")?,
+            })),
+            _ => PlonkProof::from_json(&json).map(Self::Plonk),
+        }
+    }
+
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            Self::Groth16(_) => "groth16",
+            Self::Plonk(_) => "plonk",
+        }
+    }
+}
+
+impl VerifyingKey {
+    pub fn parse(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let Some(json) = parse_json(bytes, "verifying key")? else {
+            return ark_groth16::VerifyingKey::deserialize_compressed(bytes)
+                .map(Self::Groth16)
+                .map_err(|e| malformed("verifying key", e));
+        };
+        match protocol(&json, "verifying key")? {
+            "groth16" => {
+                let ic = json["IC"].as_array().ok_or_else(|| malformed("Groth16 verifying key", "`IC` is not an array"))?;
+                Ok(Self::Groth16(ark_groth16::VerifyingKey {
+                    alpha_g1: g1_json(&json["vk_alpha_1"], "Groth16 verifying key")?,
+                    beta_g2: g2_json(&json["vk_beta_2"], "Groth16 verifying key")?,
+                    gamma_g2: g2_json(&json["vk_gamma_2"], "Groth16 verifying key")?,
+                    delta_g2: g2_json(&json["vk_delta_2"], "Groth16 verifying key")?,
+                    gamma_abc_g1: ic.iter().map(|p| g1_json(p, "Groth16 verifying key")).collect::<Result<_, _>>()?,
+                }))
+            }
+            _ => PlonkVerifyingKey::from_json(&json).map(Self::Plonk),
+        }
+    }
+
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            Self::Groth16(_) => "groth16",
+            Self::Plonk(_) => "plonk",
+        }
+    }
+
+    /// Compare against the key embedded in a registry proving key, as fetched
+    /// for the model's on-chain `zk_circuit`. Registry circuits are Groth16.
+    pub fn check_registry_key(&self, proving_key: &[u8], circuit: &str) -> Result<(), VerifyError> {
+        let pk = ProvingKey::<Bn254>::deserialize_compressed(proving_key)
+            .map_err(|e| VerifyError::Chain(format!("registry proving key: {e}")))?;
+        match self {
+            Self::Groth16(vk) if *vk == pk.vk => Ok(()),
+            _ => Err(VerifyError::KeyMismatch(circuit.to_string())),
+        }
+    }
+}
+
+/// snarkjs `public.json`: an array of decimal strings
+pub fn parse_public_inputs(bytes: &[u8]) -> Result<Vec<Fr>, VerifyError> {
+    let json: Value = serde_json::from_slice(bytes).map_err(|e| malformed("public inputs", e))?;
+    json.as_array()
+        .ok_or_else(|| malformed("public inputs", "expected a JSON array"))?
+        .iter()
+        .map(|v| fr_json(v, "public inputs"))
+        .collect()
+}
+
+pub fn verify(proof: &Proof, vk: &VerifyingKey, public: &[Fr]) -> Result<(), VerifyError> {
+    match (proof, vk) {
+        (Proof::Groth16(proof), VerifyingKey::Groth16(vk)) => {
+            if public.len() + 1 != vk.gamma_abc_g1.len() {
+                return Err(VerifyError::PublicInputCount {
+                    expected: vk.gamma_abc_g1.len().saturating_sub(1),
+                    actual: public.len(),
+                });
+            }
+            match Groth16::<Bn254>::verify_proof(&prepare_verifying_key(vk), proof, public) {
+                Ok(true) => Ok(()),
+                _ => Err(VerifyError::Invalid),
+            }
+        }
+        (Proof::Plonk(proof), VerifyingKey::Plonk(vk)) => plonk::verify(proof, vk, public),
+        (proof, vk) => Err(VerifyError::ProtocolMismatch {
+            proof: proof.protocol(),
+            key: vk.protocol(),
+        }),
+    }
+}
+
+fn malformed(what: &'static str, reason: impl ToString) -> VerifyError {
+    VerifyError::Malformed { what, reason: reason.to_string() }
+}
+
+/// `None` for binary (arkworks) encodings
+fn parse_json(bytes: &[u8], what: &'static str) -> Result<Option<Value>, VerifyError> {
+    match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'{') => serde_json::from_slice(bytes).map(Some).map_err(|e| malformed(what, e)),
+        _ => Ok(None),
+    }
+}
+
+fn protocol<'a>(json: &'a Value, what: &'static str) -> Result<&'a str, VerifyError> {
+    if let Some(curve) = json["curve"].as_str() {
+        if curve != "bn128" && curve != "bn254" {
+            return Err(malformed(what, format!("unsupported curve `{curve}`")));
+        }
+    }
+    match json["protocol"].as_str() {
+        Some(p @ ("groth16" | "plonk")) => Ok(p),
+        Some(other) => Err(malformed(what, format!("unsupported protocol `{other}`"))),
+        None => Err(malformed(what, "missing `protocol`")),
+    }
+}
+
+fn decimal<F: FromStr + Display>(value: &Value, what: &'static str) -> Result<F, VerifyError> {
+    // `F::from_str` reduces out-of-range values; only canonical encodings round-trip
+    value
+        .as_str()
+        .and_then(|s| F::from_str(s).ok().filter(|f| f.to_string() == s.trim_start_matches('0')))
+        .ok_or_else(|| malformed(what, format!("`{value}` is not a decimal field element")))
+}
+
+pub fn fr_json(value: &Value, what: &'static str) -> Result<Fr, VerifyError> {
+    decimal(value, what)
+}
+
+/// Projective `[x, y, z]` with `z` either 1 or 0 (infinity)
+pub fn g1_json(value: &Value, what: &'static str) -> Result<G1Affine, VerifyError> {
+    let xyz = value.as_array().filter(|a| a.len() == 3).ok_or_else(|| malformed(what, "G1 point is not [x, y, z]"))?;
+    let z: Fq = decimal(&xyz[2], what)?;
+    if z == Fq::from(0u64) {
+        return Ok(G1Affine::zero());
+    }
+    if z != Fq::from(1u64) {
+        return Err(malformed(what, "G1 point is not normalized"));
+    }
+    let point = G1Affine::new_unchecked(decimal(&xyz[0], what)?, decimal(&xyz[1], what)?);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+        .then_some(point)
+        .ok_or_else(|| malformed(what, "G1 point is not on the curve"))
+}
+
+/// `[[x.c0, x.c1], [y.c0, y.c1], [z.c0, z.c1]]`
+pub fn g2_json(value: &Value, what: &'static str) -> Result<G2Affine, VerifyError> {
+    let fq2 = |v: &Value| -> Result<Fq2, VerifyError> {
+        let c = v.as_array().filter(|a| a.len() == 2).ok_or_else(|| malformed(what, "G2 coordinate is not [c0, c1]"))?;
+        Ok(Fq2::new(decimal(&c[0], what)?, decimal(&c[1], what)?))
+    };
+    let xyz = value.as_array().filter(|a| a.len() == 3).ok_or_else(|| malformed(what, "G2 point is not [x, y, z]"))?;
+    let z = fq2(&xyz[2])?;
+    if z == Fq2::from(0u64) {
+        return Ok(G2Affine::zero());
+    }
+    if z != Fq2::from(1u64) {
+        return Err(malformed(what, "G2 point is not normalized"));
+    }
+    let point = G2Affine::new_unchecked(fq2(&xyz[0])?, fq2(&xyz[1])?);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+        .then_some(point)
+        .ok_or_else(|| malformed(what, "G2 point is not on the curve"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::zkp::circuits::dense::DenseCircuit;
+    use ark_ec::CurveGroup;
+    use ark_ff::PrimeField;
+    use ark_serialize::CanonicalSerialize;
+    use ark_snark::SNARK;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use serde_json::json;
+
+    fn proved() -> (ark_groth16::Proof<Bn254>, ark_groth16::VerifyingKey<Bn254>, Vec<Fr>) {
+        let circuit = DenseCircuit::new(vec![64, -32, 16, 128], vec![1, -1], vec![256, 512], 8, 24, true);
+        let public = [&circuit.input, &circuit.output]
+            .into_iter()
+            .flatten()
+            .map(|v| if *v < 0 { -Fr::from(v.unsigned_abs()) } else { Fr::from(*v as u64) })
+            .collect();
+        let mut rng = StdRng::seed_from_u64(7);
+        let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+        let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut rng).unwrap();
+        (proof, vk, public)
+    }
+
+    fn dec<F: PrimeField>(f: F) -> Value {
+        let digits = f.into_bigint().to_string();
+        json!(digits)
+    }
+
+    fn g1(p: G1Affine) -> Value {
+        let (x, y) = p.xy().unwrap();
+        json!([dec(*x), dec(*y), "1"])
+    }
+
+    fn g2(p: G2Affine) -> Value {
+        let (x, y) = p.xy().unwrap();
+        json!([[dec(x.c0), dec(x.c1)], [dec(y.c0), dec(y.c1)], ["1", "0"]])
+    }
+
+    #[test]
+    fn groth16_roundtrips_through_both_encodings() {
+        let (proof, vk, public) = proved();
+
+        let mut proof_bytes = Vec::new();
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        let mut vk_bytes = Vec::new();
+        vk.serialize_compressed(&mut vk_bytes).unwrap();
+        let binary = (Proof::parse(&proof_bytes).unwrap(), VerifyingKey::parse(&vk_bytes).unwrap());
+        assert!(verify(&binary.0, &binary.1, &public).is_ok());
+
+        let proof_json = json!({ "protocol": "groth16", "curve": "bn128", "pi_a": g1(proof.a), "pi_b": g2(proof.b), "pi_c": g1(proof.c) });
+        let vk_json = json!({
+            "protocol": "groth16",
+            "curve": "bn128",
+            "nPublic": public.len(),
+            "vk_alpha_1": g1(vk.alpha_g1),
+            "vk_beta_2": g2(vk.beta_g2),
+            "vk_gamma_2": g2(vk.gamma_g2),
+            "vk_delta_2": g2(vk.delta_g2),
+            "IC": vk.gamma_abc_g1.iter().map(|p| g1(*p)).collect::<Vec<_>>(),
+        });
+        let public_json: Vec<Value> = public.iter().map(|p| dec(*p)).collect();
+        let parsed = (
+            Proof::parse(proof_json.to_string().as_bytes()).unwrap(),
+            VerifyingKey::parse(vk_json.to_string().as_bytes()).unwrap(),
+            parse_public_inputs(json!(public_json).to_string().as_bytes()).unwrap(),
+        );
+        assert_eq!(parsed, (binary.0, binary.1, public));
+        assert!(verify(&parsed.0, &parsed.1, &parsed.2).is_ok());
+    }
+
+    #[test]
+    fn failure_classes_map_to_distinct_exit_codes() {
+        let (proof, vk, mut public) = proved();
+        let (proof, vk) = (Proof::Groth16(proof), VerifyingKey::Groth16(vk));
+
+        public[0] += Fr::from(1u64);
+        let invalid = verify(&proof, &vk, &public).unwrap_err();
+        let count = verify(&proof, &vk, &public[1..]).unwrap_err();
+        let garbage = Proof::parse(br#"{"protocol": "groth16", "pi_a": ["1", "2"]}"#).unwrap_err();
+        let off_curve = g1_json(&json!(["1", "3", "1"]), "proof").unwrap_err();
+
+        let codes: Vec<i32> = [&invalid, &count, &garbage, &off_curve].iter().map(|e| e.exit_code()).collect();
+        assert_eq!(codes, [1, 2, 2, 2]);
+        assert!(matches!(invalid, VerifyError::Invalid));
+        assert!(matches!(off_curve, VerifyError::Malformed { .. }));
+    }
+
+    #[test]
+    fn registry_key_cross_check() {
+        let circuit = DenseCircuit::blank(2, 2, 8, 24, true);
+        let mut rng = StdRng::seed_from_u64(1);
+        let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut rng).unwrap();
+        let mut pk_bytes = Vec::new();
+        pk.serialize_compressed(&mut pk_bytes).unwrap();
+
+        assert!(VerifyingKey::Groth16(vk.clone()).check_registry_key(&pk_bytes, "ab").is_ok());
+        let mut other = vk;
+        other.delta_g2 = (other.delta_g2 + G2Affine::generator()).into_affine();
+        let err = VerifyingKey::Groth16(other).check_registry_key(&pk_bytes, "ab").unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+    }
+}
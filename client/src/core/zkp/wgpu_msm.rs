@@ -0,0 +1,292 @@
+// client/src/core/zkp/wgpu_msm.rs
+
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, Group};
+use ark_ff::{BigInt, PrimeField, Zero};
+use thiserror::Error;
+use wgpu::util::DeviceExt;
+
+/// Pippenger window width; must divide 16 so digits never straddle limbs
+pub const WINDOW_BITS: u32 = 8;
+const WINDOWS: u32 = 256 / WINDOW_BITS;
+const FQ_LIMBS: usize = 16;
+const AFFINE_LIMBS: usize = 2 * FQ_LIMBS;
+const JACOBIAN_LIMBS: usize = 3 * FQ_LIMBS;
+const WORKGROUP_SIZE: u32 = 64;
+/// Points per dispatch; keeps each submission well under OS GPU watchdogs
+const CHUNK_POINTS: usize = 1 << 14;
+
+#[derive(Debug, Error)]
+pub enum WgpuMsmError {
+    #[error("No Vulkan/Metal/DX12 adapter available")]
+    NoAdapter,
+    #[error("Device request failed: {0}")]
+    RequestDevice(#[from] wgpu::RequestDeviceError),
+    #[error("Buffer readback failed: {0}")]
+    Readback(#[from] wgpu::BufferAsyncError),
+    #[error("Length mismatch: {bases} bases, {scalars} scalars")]
+    LengthMismatch { bases: usize, scalars: usize },
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    count: u32,
+    window_bits: u32,
+    windows: u32,
+    _pad: u32,
+}
+
+/// BN254 G1 multi-scalar multiplication on any wgpu adapter
+pub struct WgpuMsm {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    adapter_name: String,
+}
+
+impl WgpuMsm {
+    pub fn new() -> Result<Self, WgpuMsmError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    async fn new_async() -> Result<Self, WgpuMsmError> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .await
+            .ok_or(WgpuMsmError::NoAdapter)?;
+        let adapter_name = adapter.get_info().name;
+
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("scoria-msm"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: adapter.limits(),
+                },
+                None,
+            )
+            .await?;
+
+        let shader = device.create_shader_module(wgpu::include_wgsl!("msm.wgsl"));
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("msm-bucket-accumulate"),
+            layout: None,
+            module: &shader,
+            entry_point: "accumulate",
+        });
+
+        tracing::info!(adapter = %adapter_name, "wgpu MSM backend initialized");
+        Ok(Self { device, queue, pipeline, adapter_name })
+    }
+
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    /// Compute sum(scalars[i] * bases[i]).
+    ///
+    /// Bucket accumulation runs on the GPU; bucket reduction and window
+    /// combination stay on the host.
+    pub fn msm(&self, bases: &[G1Affine], scalars: &[Fr]) -> Result<G1Projective, WgpuMsmError> {
+        if bases.len() != scalars.len() {
+            return Err(WgpuMsmError::LengthMismatch { bases: bases.len(), scalars: scalars.len() });
+        }
+
+        // 1. Flatten to 16-bit limbs, skipping zero terms
+        let (base_limbs, scalar_limbs) = encode_inputs(bases, scalars);
+        let n = scalar_limbs.len() / FQ_LIMBS;
+        if n == 0 {
+            return Ok(G1Projective::zero());
+        }
+
+        // 2. Device buffers sized for one chunk; buckets start zeroed (all infinity)
+        let buckets_per_window = (1usize << WINDOW_BITS) - 1;
+        let bucket_bytes = (WINDOWS as usize * buckets_per_window * JACOBIAN_LIMBS * 4) as u64;
+        let chunk = n.min(CHUNK_POINTS);
+        let storage = |label, bytes: usize| {
+            self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: bytes as u64,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let bases_buf = storage("msm-bases", chunk * AFFINE_LIMBS * 4);
+        let scalars_buf = storage("msm-scalars", chunk * FQ_LIMBS * 4);
+        let buckets_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("msm-buckets"),
+            size: bucket_bytes,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let params_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("msm-params"),
+            contents: bytemuck::bytes_of(&Params { count: 0, window_bits: WINDOW_BITS, windows: WINDOWS, _pad: 0 }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("msm-readback"),
+            size: bucket_bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("msm-bindings"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: bases_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: scalars_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: buckets_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: params_buf.as_entire_binding() },
+            ],
+        });
+
+        // 3. Stream chunks through the accumulation kernel
+        let invocations = WINDOWS * buckets_per_window as u32;
+        let workgroups = invocations.div_ceil(WORKGROUP_SIZE);
+        for start in (0..n).step_by(CHUNK_POINTS) {
+            let end = (start + CHUNK_POINTS).min(n);
+            self.queue.write_buffer(
+                &bases_buf,
+                0,
+                bytemuck::cast_slice(&base_limbs[start * AFFINE_LIMBS..end * AFFINE_LIMBS]),
+            );
+            self.queue.write_buffer(
+                &scalars_buf,
+                0,
+                bytemuck::cast_slice(&scalar_limbs[start * FQ_LIMBS..end * FQ_LIMBS]),
+            );
+            self.queue.write_buffer(
+                &params_buf,
+                0,
+                bytemuck::bytes_of(&Params {
+                    count: (end - start) as u32,
+                    window_bits: WINDOW_BITS,
+                    windows: WINDOWS,
+                    _pad: 0,
+                }),
+            );
+
+            let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("msm-accumulate"),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(workgroups, 1, 1);
+            }
+            self.queue.submit(Some(encoder.finish()));
+        }
+
+        // 4. Read buckets back and reduce on the host
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_buffer_to_buffer(&buckets_buf, 0, &readback, 0, bucket_bytes);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv().map_err(|_| WgpuMsmError::Readback(wgpu::BufferAsyncError))??;
+
+        let buckets: Vec<u32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback.unmap();
+        Ok(reduce_buckets(&buckets, buckets_per_window))
+    }
+}
+
+fn split_limbs(words: &[u64; 4], out: &mut Vec<u32>) {
+    for w in words {
+        for k in 0..4 {
+            out.push(((w >> (16 * k)) & 0xffff) as u32);
+        }
+    }
+}
+
+fn join_limbs(limbs: &[u32]) -> [u64; 4] {
+    let mut words = [0u64; 4];
+    for (i, word) in words.iter_mut().enumerate() {
+        for k in 0..4 {
+            *word |= (limbs[4 * i + k] as u64) << (16 * k);
+        }
+    }
+    words
+}
+
+/// Bases as Montgomery-form (x, y) limbs, scalars as canonical limbs
+fn encode_inputs(bases: &[G1Affine], scalars: &[Fr]) -> (Vec<u32>, Vec<u32>) {
+    let mut base_limbs = Vec::with_capacity(bases.len() * AFFINE_LIMBS);
+    let mut scalar_limbs = Vec::with_capacity(scalars.len() * FQ_LIMBS);
+    for (base, scalar) in bases.iter().zip(scalars) {
+        if base.is_zero() || scalar.is_zero() {
+            continue;
+        }
+        split_limbs(&base.x.0 .0, &mut base_limbs);
+        split_limbs(&base.y.0 .0, &mut base_limbs);
+        split_limbs(&scalar.into_bigint().0, &mut scalar_limbs);
+    }
+    (base_limbs, scalar_limbs)
+}
+
+fn decode_jacobian(limbs: &[u32]) -> G1Projective {
+    let coord = |i: usize| Fq::new_unchecked(BigInt(join_limbs(&limbs[i * FQ_LIMBS..(i + 1) * FQ_LIMBS])));
+    let z = coord(2);
+    if z.is_zero() {
+        return G1Projective::zero();
+    }
+    G1Projective::new_unchecked(coord(0), coord(1), z)
+}
+
+/// Running-sum bucket reduction per window, then double-and-add across windows
+fn reduce_buckets(buckets: &[u32], buckets_per_window: usize) -> G1Projective {
+    let window_sums: Vec<G1Projective> = buckets
+        .chunks(buckets_per_window * JACOBIAN_LIMBS)
+        .map(|window| {
+            let mut running = G1Projective::zero();
+            let mut sum = G1Projective::zero();
+            for bucket in window.chunks(JACOBIAN_LIMBS).rev() {
+                running += decode_jacobian(bucket);
+                sum += running;
+            }
+            sum
+        })
+        .collect();
+
+    window_sums.into_iter().rev().fold(G1Projective::zero(), |mut acc, window| {
+        for _ in 0..WINDOW_BITS {
+            acc.double_in_place();
+        }
+        acc + window
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limb_roundtrip() {
+        let words = [0x0123_4567_89ab_cdef, u64::MAX, 0, 42];
+        let mut limbs = Vec::new();
+        split_limbs(&words, &mut limbs);
+        assert_eq!(limbs.len(), FQ_LIMBS);
+        assert!(limbs.iter().all(|&l| l <= 0xffff));
+        assert_eq!(join_limbs(&limbs), words);
+    }
+
+    #[test]
+    fn test_infinity_bucket_decodes_to_zero() {
+        assert!(decode_jacobian(&[0u32; JACOBIAN_LIMBS]).is_zero());
+    }
+}
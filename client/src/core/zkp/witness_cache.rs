@@ -0,0 +1,212 @@
+// client/src/core/zkp/witness_cache.rs
+
+use crate::config::WitnessCacheConfig;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Witnesses for one (circuit, input) pair are identical across proofs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WitnessKey {
+    pub circuit_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+}
+
+impl WitnessKey {
+    /// Blake3 over every named input and its field elements, in order
+    pub fn new(circuit_hash: &[u8; 32], inputs: &[(&str, Vec<Fr>)]) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        for (name, values) in inputs {
+            hasher.update(&(name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            hasher.update(&(values.len() as u64).to_le_bytes());
+            for value in values {
+                hasher.update(&value.into_bigint().to_bytes_le());
+            }
+        }
+        Self {
+            circuit_hash: *circuit_hash,
+            input_hash: *hasher.finalize().as_bytes(),
+        }
+    }
+
+    fn path(&self, dir: &Path) -> PathBuf {
+        dir.join(hex::encode(self.circuit_hash)).join(format!("{}.wtns", hex::encode(self.input_hash)))
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    witnesses: HashMap<WitnessKey, Arc<Vec<Fr>>>,
+    /// Least recently used first
+    order: VecDeque<WitnessKey>,
+}
+
+/// In-memory LRU of computed witnesses, optionally backed by `<model_cache>/witnesses/`.
+///
+/// Witnesses contain every private signal of the circuit, weights and user
+/// inputs included, so disk persistence is opt-in.
+pub struct WitnessCache {
+    entries: Mutex<Entries>,
+    capacity: usize,
+    dir: Option<PathBuf>,
+}
+
+impl WitnessCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            capacity,
+            dir: None,
+        }
+    }
+
+    /// Also persist witnesses under `dir`, surviving restarts
+    pub fn persistent(capacity: usize, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+            ..Self::new(capacity)
+        }
+    }
+
+    pub fn from_config(config: &WitnessCacheConfig, model_cache: &Path) -> Self {
+        if config.persist {
+            Self::persistent(config.capacity, model_cache.join("witnesses"))
+        } else {
+            Self::new(config.capacity)
+        }
+    }
+
+    pub fn get(&self, key: &WitnessKey) -> Option<Arc<Vec<Fr>>> {
+        if let Some(witness) = self.lookup_memory(key) {
+            crate::metrics::log_witness_cache("memory_hit");
+            return Some(witness);
+        }
+        if let Some(witness) = self.dir.as_deref().and_then(|dir| read_witness(&key.path(dir))) {
+            crate::metrics::log_witness_cache("disk_hit");
+            return Some(self.remember(*key, Arc::new(witness)));
+        }
+        crate::metrics::log_witness_cache("miss");
+        None
+    }
+
+    pub fn insert(&self, key: WitnessKey, witness: Vec<Fr>) -> Arc<Vec<Fr>> {
+        if let Some(dir) = &self.dir {
+            // A failed write only costs a recomputation later
+            if let Err(e) = write_witness(&key.path(dir), &witness) {
+                tracing::warn!(error = %e, "Witness not persisted");
+            }
+        }
+        self.remember(key, Arc::new(witness))
+    }
+
+    fn lookup_memory(&self, key: &WitnessKey) -> Option<Arc<Vec<Fr>>> {
+        let mut entries = self.entries.lock().unwrap();
+        let witness = entries.witnesses.get(key)?.clone();
+        entries.order.retain(|k| k != key);
+        entries.order.push_back(*key);
+        Some(witness)
+    }
+
+    fn remember(&self, key: WitnessKey, witness: Arc<Vec<Fr>>) -> Arc<Vec<Fr>> {
+        if self.capacity == 0 {
+            return witness;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.witnesses.insert(key, witness.clone()).is_some() {
+            entries.order.retain(|k| *k != key);
+        }
+        entries.order.push_back(key);
+        while entries.order.len() > self.capacity {
+            let evicted = entries.order.pop_front().expect("longer than capacity");
+            entries.witnesses.remove(&evicted);
+        }
+        witness
+    }
+}
+
+/// `blake3(payload) || payload`, payload being the compressed witness
+fn write_witness(path: &Path, witness: &[Fr]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut payload = Vec::new();
+    witness.serialize_compressed(&mut payload)?;
+    let mut bytes = blake3::hash(&payload).as_bytes().to_vec();
+    bytes.extend_from_slice(&payload);
+
+    std::fs::create_dir_all(path.parent().expect("witness path has a parent"))?;
+    let tmp = path.with_extension("partial");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Truncated or corrupted files are dropped and treated as misses
+fn read_witness(path: &Path) -> Option<Vec<Fr>> {
+    let bytes = std::fs::read(path).ok()?;
+    let witness = (bytes.len() >= 32 && blake3::hash(&bytes[32..]).as_bytes()[..] == bytes[..32])
+        .then(|| Vec::<Fr>::deserialize_compressed(&bytes[32..]).ok())
+        .flatten();
+    if witness.is_none() {
+        tracing::warn!(path = %path.display(), "Discarding corrupt cached witness");
+        let _ = std::fs::remove_file(path);
+    }
+    witness
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn key(input: u64) -> WitnessKey {
+        WitnessKey::new(&[7; 32], &[("x", vec![Fr::from(input)])])
+    }
+
+    fn witness(n: u64) -> Vec<Fr> {
+        (0..n).map(Fr::from).collect()
+    }
+
+    #[test]
+    fn key_binds_circuit_names_and_values() {
+        let inputs = [("a", vec![Fr::from(1u64), Fr::from(2u64)])];
+        let base = WitnessKey::new(&[1; 32], &inputs);
+        assert_eq!(base, WitnessKey::new(&[1; 32], &inputs));
+        assert_ne!(base, WitnessKey::new(&[2; 32], &inputs));
+        assert_ne!(base, WitnessKey::new(&[1; 32], &[("b", vec![Fr::from(1u64), Fr::from(2u64)])]));
+        assert_ne!(base, WitnessKey::new(&[1; 32], &[("a", vec![Fr::from(1u64)]), ("", vec![Fr::from(2u64)])]));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = WitnessCache::new(2);
+        cache.insert(key(1), witness(1));
+        cache.insert(key(2), witness(2));
+        assert!(cache.get(&key(1)).is_some());
+        cache.insert(key(3), witness(3));
+
+        assert!(cache.get(&key(2)).is_none());
+        assert_eq!(cache.get(&key(1)).unwrap().len(), 1);
+        assert_eq!(cache.get(&key(3)).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn persists_across_instances_and_drops_corruption() {
+        let dir = TempDir::new().unwrap();
+        WitnessCache::persistent(4, dir.path()).insert(key(1), witness(5));
+        WitnessCache::persistent(4, dir.path()).insert(key(2), witness(6));
+
+        let reopened = WitnessCache::persistent(4, dir.path());
+        assert_eq!(*reopened.get(&key(1)).unwrap(), witness(5));
+
+        let path = key(2).path(dir.path());
+        let mut bytes = std::fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(reopened.get(&key(2)).is_none());
+        assert!(!path.exists());
+    }
+}
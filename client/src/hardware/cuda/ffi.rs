@@ -10,6 +10,8 @@ use nvml_wrapper::Nvml;
 use thiserror::Error;
 use half::f16;
 use cust::{
+    context::{Context, CurrentContext},
+    device::{Device, DeviceAttribute},
     memory::DeviceBox,
     stream::{Stream, StreamFlags},
 };
@@ -26,30 +28,73 @@ pub enum CudaError {
     BatchMismatch { expected: usize, actual: usize },
     #[error("Unsupported precision: {0}")]
     UnsupportedPrecision(String),
+    #[error("NVML error: {0}")]
+    Nvml(#[from] nvml_wrapper::error::NvmlError),
+    #[error("GPU device {0} not found")]
+    DeviceNotFound(u32),
+    #[error("No GPU can fit a {bytes} byte {job} job")]
+    NoDeviceAvailable { job: String, bytes: u64 },
 }
 
 #[repr(C)]
 pub struct CudaContext {
     stream: Stream,
     device_memory: Arc<Mutex<u64>>,
+    ordinal: u32,
+    pci_bus_id: String,
+    context: Context,
 }
 
 impl CudaContext {
+    /// Context on the first GPU
     pub fn new() -> Result<Self, CudaError> {
+        Self::on_device(0)
+    }
+
+    /// Context bound to the GPU with the given ordinal
+    pub fn on_device(ordinal: u32) -> Result<Self, CudaError> {
+        let device = Device::get_device(ordinal).map_err(|_| CudaError::DeviceNotFound(ordinal))?;
+        let context = Context::new(device)?;
+        CurrentContext::set_current(&context)?;
+
         let flags = StreamFlags::NON_BLOCKING;
         let stream = Stream::new(flags)?;
         
-        // Initialize NVML for GPU monitoring
+        // NVML indices follow PCI order while CUDA ordinals default to
+        // fastest-first, so the two are matched by bus ID
+        let pci_bus_id = pci_bus_id(&device)?;
         let nvml = Nvml::init()?;
-        let device = nvml.device_by_index(0)?;
-        let mem_info = device.memory_info()?;
+        let mem_info = nvml.device_by_pci_bus_id(pci_bus_id.as_str())?.memory_info()?;
 
         Ok(Self {
             stream,
             device_memory: Arc::new(Mutex::new(mem_info.free)),
+            ordinal,
+            pci_bus_id,
+            context,
         })
     }
 
+    pub fn ordinal(&self) -> u32 {
+        self.ordinal
+    }
+
+    /// `domain:bus:device.function`, as accepted by `Nvml::device_by_pci_bus_id`
+    pub fn pci_bus_id(&self) -> &str {
+        &self.pci_bus_id
+    }
+
+    /// Bytes still available to this context's allocations
+    pub fn tracked_free_memory(&self) -> u64 {
+        *self.device_memory.lock().unwrap()
+    }
+
+    /// Make this context current on the calling thread
+    pub fn make_current(&self) -> Result<(), CudaError> {
+        CurrentContext::set_current(&self.context)?;
+        Ok(())
+    }
+
     pub fn create_stream(&self) -> Result<*mut c_void, CudaError> {
         let flags = StreamFlags::NON_BLOCKING;
         let stream = Stream::new(flags)?;
@@ -57,6 +102,15 @@ impl CudaContext {
     }
 }
 
+fn pci_bus_id(device: &Device) -> Result<String, CudaError> {
+    Ok(format!(
+        "{:08x}:{:02x}:{:02x}.0",
+        device.get_attribute(DeviceAttribute::PciDomainId)?,
+        device.get_attribute(DeviceAttribute::PciBusId)?,
+        device.get_attribute(DeviceAttribute::PciDeviceId)?,
+    ))
+}
+
 #[repr(C)]
 pub struct DeviceTensor<T> {
     data: DeviceBox<T>,
@@ -0,0 +1,233 @@
+// client/src/hardware/cuda/scheduler.rs
+
+use super::ffi::{CudaContext, CudaError};
+use cust::device::Device;
+use nvml_wrapper::Nvml;
+use serde::Serialize;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Headroom kept free on every device to avoid OOM from fragmentation
+const MEMORY_HEADROOM: u64 = 256 * 1024 * 1024;
+
+/// Work that can be placed on a GPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Inference,
+    ZkProving,
+}
+
+impl std::fmt::Display for JobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobKind::Inference => write!(f, "inference"),
+            JobKind::ZkProving => write!(f, "zk-proving"),
+        }
+    }
+}
+
+/// Point-in-time state of one device, as used for placement
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceMetrics {
+    pub ordinal: u32,
+    pub name: String,
+    pub total_memory: u64,
+    pub free_memory: u64,
+    /// GPU utilization over NVML's last sample period, 0-100
+    pub utilization: u32,
+    pub active_jobs: usize,
+}
+
+struct GpuDevice {
+    name: String,
+    context: Arc<CudaContext>,
+    active_jobs: Arc<AtomicUsize>,
+}
+
+/// Pool of CUDA devices with load-aware job placement
+pub struct GpuPool {
+    nvml: Nvml,
+    devices: Vec<GpuDevice>,
+}
+
+impl GpuPool {
+    /// Enumerate CUDA devices, optionally restricted to `selection` (e.g.
+    /// `--gpu-devices 0,2`). Selections are CUDA ordinals; each device's NVML
+    /// handle is found by PCI bus ID, since NVML indices need not match them.
+    pub fn discover(selection: Option<&[u32]>) -> Result<Self, CudaError> {
+        let nvml = Nvml::init()?;
+        let count = Device::num_devices()?;
+
+        let ordinals: Vec<u32> = match selection {
+            Some(selected) => {
+                if let Some(&missing) = selected.iter().find(|&&o| o >= count) {
+                    return Err(CudaError::DeviceNotFound(missing));
+                }
+                selected.to_vec()
+            }
+            None => (0..count).collect(),
+        };
+
+        let mut devices = Vec::with_capacity(ordinals.len());
+        for ordinal in ordinals {
+            let context = CudaContext::on_device(ordinal)?;
+            let name = nvml.device_by_pci_bus_id(context.pci_bus_id())?.name()?;
+            devices.push(GpuDevice {
+                name,
+                context: Arc::new(context),
+                active_jobs: Arc::new(AtomicUsize::new(0)),
+            });
+        }
+
+        tracing::info!(devices = devices.len(), "GPU pool initialized");
+        Ok(Self { nvml, devices })
+    }
+
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Current per-device metrics
+    pub fn metrics(&self) -> Result<Vec<DeviceMetrics>, CudaError> {
+        self.devices
+            .iter()
+            .map(|d| {
+                let ordinal = d.context.ordinal();
+                let nvml_device = self.nvml.device_by_pci_bus_id(d.context.pci_bus_id())?;
+                let memory = nvml_device.memory_info()?;
+                Ok(DeviceMetrics {
+                    ordinal,
+                    name: d.name.clone(),
+                    total_memory: memory.total,
+                    free_memory: memory.free,
+                    utilization: nvml_device.utilization_rates()?.gpu,
+                    active_jobs: d.active_jobs.load(Ordering::Acquire),
+                })
+            })
+            .collect()
+    }
+
+    /// Reserve the best device for a job needing roughly `bytes` of device memory
+    pub fn acquire(&self, kind: JobKind, bytes: u64) -> Result<GpuLease, CudaError> {
+        let metrics = self.metrics()?;
+        let index = pick_device(&metrics, kind, bytes).ok_or_else(|| CudaError::NoDeviceAvailable {
+            job: kind.to_string(),
+            bytes,
+        })?;
+
+        let device = &self.devices[index];
+        device.active_jobs.fetch_add(1, Ordering::AcqRel);
+        device.context.make_current()?;
+
+        tracing::debug!(
+            ordinal = metrics[index].ordinal,
+            job = %kind,
+            free_memory = metrics[index].free_memory,
+            utilization = metrics[index].utilization,
+            "GPU job placed"
+        );
+        Ok(GpuLease {
+            context: device.context.clone(),
+            active_jobs: device.active_jobs.clone(),
+        })
+    }
+
+    /// Log per-device metrics at info level
+    pub fn report(&self) -> Result<(), CudaError> {
+        for m in self.metrics()? {
+            tracing::info!(
+                ordinal = m.ordinal,
+                name = %m.name,
+                free_memory = m.free_memory,
+                total_memory = m.total_memory,
+                utilization = m.utilization,
+                active_jobs = m.active_jobs,
+                "GPU status"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Exclusive-ish claim on a device; released when dropped
+pub struct GpuLease {
+    context: Arc<CudaContext>,
+    active_jobs: Arc<AtomicUsize>,
+}
+
+impl GpuLease {
+    pub fn context(&self) -> &Arc<CudaContext> {
+        &self.context
+    }
+
+    pub fn ordinal(&self) -> u32 {
+        self.context.ordinal()
+    }
+}
+
+impl Drop for GpuLease {
+    fn drop(&mut self) {
+        self.active_jobs.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Choose a device index for a job.
+///
+/// Proving is memory-bound (MSM tables), so it goes to the device with the
+/// most free memory. Inference is latency-bound, so it prefers the least
+/// utilized device with the fewest queued jobs.
+pub fn pick_device(devices: &[DeviceMetrics], kind: JobKind, bytes: u64) -> Option<usize> {
+    let fits = |d: &DeviceMetrics| d.free_memory >= bytes.saturating_add(MEMORY_HEADROOM);
+    let candidates = devices.iter().enumerate().filter(|(_, d)| fits(d));
+
+    match kind {
+        JobKind::ZkProving => candidates
+            .max_by_key(|(_, d)| (d.free_memory, std::cmp::Reverse(d.active_jobs)))
+            .map(|(i, _)| i),
+        JobKind::Inference => candidates
+            .min_by_key(|(_, d)| (d.active_jobs, d.utilization, std::cmp::Reverse(d.free_memory)))
+            .map(|(i, _)| i),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn device(ordinal: u32, free_gib: u64, utilization: u32, active_jobs: usize) -> DeviceMetrics {
+        DeviceMetrics {
+            ordinal,
+            name: format!("gpu{}", ordinal),
+            total_memory: 24 * GIB,
+            free_memory: free_gib * GIB,
+            utilization,
+            active_jobs,
+        }
+    }
+
+    #[test]
+    fn test_proving_prefers_free_memory() {
+        let devices = vec![device(0, 4, 5, 0), device(1, 20, 90, 1)];
+        assert_eq!(pick_device(&devices, JobKind::ZkProving, 2 * GIB), Some(1));
+    }
+
+    #[test]
+    fn test_inference_prefers_idle_device() {
+        let devices = vec![device(0, 4, 5, 0), device(1, 20, 90, 1)];
+        assert_eq!(pick_device(&devices, JobKind::Inference, GIB), Some(0));
+    }
+
+    #[test]
+    fn test_no_device_fits() {
+        let devices = vec![device(0, 1, 0, 0)];
+        assert_eq!(pick_device(&devices, JobKind::Inference, GIB), None);
+    }
+}
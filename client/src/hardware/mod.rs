@@ -0,0 +1,140 @@
+// client/src/hardware/mod.rs
+
+use crate::hardware::cuda::scheduler::GpuPool;
+#[cfg(feature = "rocm")]
+use crate::hardware::rocm::ffi::RocmContext;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// HIP kernels, compiled by build.rs with hipcc
+#[cfg(feature = "rocm")]
+pub mod rocm {
+    pub mod ffi;
+}
+
+/// Without the `rocm` feature there is nothing to link against and no devices
+#[cfg(not(feature = "rocm"))]
+pub mod rocm {
+    pub mod ffi {
+        pub fn device_count() -> u32 {
+            0
+        }
+    }
+}
+
+/// Accelerator used for inference kernels and Groth16 MSM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum HardwareAccel {
+    /// Pick the first usable GPU runtime, otherwise CPU
+    #[default]
+    Auto,
+    Cpu,
+    /// NVIDIA GPUs through CUDA
+    Cuda,
+    /// AMD GPUs through ROCm/HIP
+    Rocm,
+}
+
+impl HardwareAccel {
+    /// Whether support for this accelerator is compiled into the binary
+    pub fn compiled(self) -> bool {
+        match self {
+            HardwareAccel::Auto | HardwareAccel::Cpu => true,
+            HardwareAccel::Cuda => cfg!(feature = "gpu-accel"),
+            HardwareAccel::Rocm => cfg!(feature = "rocm"),
+        }
+    }
+}
+
+/// Device handles for the accelerator selected at startup
+#[derive(Clone)]
+pub enum AccelDevice {
+    Cpu,
+    Cuda(Arc<GpuPool>),
+    #[cfg(feature = "rocm")]
+    Rocm(Arc<RocmContext>),
+}
+
+impl AccelDevice {
+    /// Open the requested accelerator; `Auto` tries CUDA, then ROCm, then CPU.
+    ///
+    /// An explicitly requested GPU that cannot be opened falls back to CPU
+    /// with a warning rather than aborting the command.
+    pub fn open(requested: HardwareAccel, gpu_devices: Option<&[u32]>) -> Self {
+        let candidates: &[HardwareAccel] = match requested {
+            HardwareAccel::Auto => &[HardwareAccel::Cuda, HardwareAccel::Rocm],
+            HardwareAccel::Cpu => &[],
+            HardwareAccel::Cuda => &[HardwareAccel::Cuda],
+            HardwareAccel::Rocm => &[HardwareAccel::Rocm],
+        };
+
+        for &accel in candidates.iter().filter(|a| a.compiled()) {
+            match Self::try_open(accel, gpu_devices) {
+                Ok(Some(device)) => return device,
+                Ok(None) => {}
+                Err(e) => tracing::warn!(accel = ?accel, error = %e, "GPU acceleration unavailable"),
+            }
+        }
+        if requested != HardwareAccel::Auto && requested != HardwareAccel::Cpu && !requested.compiled() {
+            tracing::warn!(accel = ?requested, "Accelerator not compiled into this build");
+        }
+        AccelDevice::Cpu
+    }
+
+    fn try_open(accel: HardwareAccel, gpu_devices: Option<&[u32]>) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        match accel {
+            HardwareAccel::Cuda => {
+                let pool = GpuPool::discover(gpu_devices)?;
+                Ok((!pool.is_empty()).then(|| AccelDevice::Cuda(Arc::new(pool))))
+            }
+            #[cfg(feature = "rocm")]
+            HardwareAccel::Rocm => {
+                if rocm::ffi::device_count() == 0 {
+                    return Ok(None);
+                }
+                // ROCm has no multi-device pool yet; use the first selected device
+                let ordinal = gpu_devices.and_then(|d| d.first().copied()).unwrap_or(0);
+                Ok(Some(AccelDevice::Rocm(Arc::new(RocmContext::on_device(ordinal)?))))
+            }
+            #[cfg(not(feature = "rocm"))]
+            HardwareAccel::Rocm => Ok(None),
+            HardwareAccel::Auto | HardwareAccel::Cpu => Ok(Some(AccelDevice::Cpu)),
+        }
+    }
+
+    pub fn kind(&self) -> HardwareAccel {
+        match self {
+            AccelDevice::Cpu => HardwareAccel::Cpu,
+            AccelDevice::Cuda(_) => HardwareAccel::Cuda,
+            #[cfg(feature = "rocm")]
+            AccelDevice::Rocm(_) => HardwareAccel::Rocm,
+        }
+    }
+
+    pub fn gpu_pool(&self) -> Option<Arc<GpuPool>> {
+        match self {
+            AccelDevice::Cuda(pool) => Some(pool.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_accel_parsing() {
+        assert_eq!(HardwareAccel::from_str("rocm", true).unwrap(), HardwareAccel::Rocm);
+        assert_eq!(HardwareAccel::from_str("cuda", true).unwrap(), HardwareAccel::Cuda);
+        assert_eq!(HardwareAccel::default(), HardwareAccel::Auto);
+    }
+
+    #[test]
+    fn test_cpu_always_available() {
+        assert!(HardwareAccel::Cpu.compiled());
+        assert_eq!(AccelDevice::open(HardwareAccel::Cpu, None).kind(), HardwareAccel::Cpu);
+    }
+}
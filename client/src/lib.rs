@@ -0,0 +1,23 @@
+// client/src/lib.rs
+
+//! `scoria-client-core`: the client's encryption, inference, proving and
+//! wallet layers, with `ops::ScoriaClient` as the entry point for services.
+//! The `scoria-cli` binary is a thin argument-parsing wrapper over it.
+
+pub mod completion;
+pub mod config;
+pub mod core;
+pub mod hardware;
+pub mod metrics;
+pub mod ops;
+pub mod output;
+pub mod provider;
+pub mod serve;
+pub mod telemetry;
+pub mod wallet;
+
+pub use ops::{
+    connect_rpc, consent_policy, crypto_context, ClientError, ConsentPurpose, ContributeOptions, Contribution,
+    CountryCode, Deployment, InferenceOptions, InferenceOutcome, InferenceRuntime, ScoriaClient, VersionUpdate,
+    VoteReceipt, WarmModel,
+};
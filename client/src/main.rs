@@ -1,65 +1,566 @@
 // client/src/main.rs
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .init();
+async fn main() {
+    // Under `COMPLETE=<shell>` answer the completion request and exit
+    CompleteEnv::with_factory(Cli::command).var(completion::COMPLETE_VAR).complete();
 
     // Parse CLI arguments
     let cli = Cli::parse();
-    let config = load_config(&cli.config)?;
-    let rpc_client = RpcClient::new_with_commitment(
-        config.network.rpc_url.clone(),
-        CommitmentConfig::confirmed()
-    );
+    let output = cli.output;
+    if let Err(e) = run(cli).await {
+        std::process::exit(output.fail(e.as_ref()));
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let output = cli.output;
+    let profile = cli.profile.as_deref().or(cli.network.map(Network::profile));
+    // These work on the file itself, so a missing or broken config can still be fixed;
+    // completions and man pages need no config at all
+    match cli.command {
+        Commands::Config(config_cmd) => return handle_config(&cli.config, profile, config_cmd),
+        Commands::Init { yes, force } => return init_wizard(config_path(&cli.config), cli.network, yes, force).await,
+        Commands::Completions { shell } => {
+            let bin = std::env::args().next().unwrap_or_else(|| "scoria-cli".into());
+            shell.write_registration(&bin, &mut std::io::stdout())?;
+            return Ok(());
+        }
+        Commands::Lock => {
+            let locked = Locked { locked: SessionClient::default().lock()? };
+            output.print_with(&locked, |l| {
+                if l.locked { "Session locked".to_string() } else { "No session was unlocked".to_string() }
+            })?;
+            return Ok(());
+        }
+        Commands::Agent { socket, auto_lock_secs } => {
+            session::serve(socket, Duration::from_secs(auto_lock_secs)).await?;
+            return Ok(());
+        }
+        Commands::Man { out_dir } => {
+            match out_dir {
+                Some(dir) => {
+                    std::fs::create_dir_all(&dir)?;
+                    clap_mangen::generate_to(Cli::command(), &dir)?;
+                }
+                None => clap_mangen::Man::new(Cli::command()).render(&mut std::io::stdout())?,
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+    let config = load_profile(&cli.config, profile)?;
+    config.programs.install();
+    config.security.kdf.install();
+    config.security.cipher.install();
+
+    // Initialize logging, and trace export when configured
+    let _telemetry = scoria_client_core::telemetry::init(config.monitoring.otlp_endpoint.as_deref())?;
+    if cli.metrics {
+        scoria_client_core::metrics::install_exporter(config.monitoring.prometheus_port)?;
+        tracing::info!(port = config.monitoring.prometheus_port, "Serving Prometheus metrics");
+    }
+    if let Commands::Keys(KeyCommands::Calibrate { target_ms, max_memory_mib, dry_run }) = cli.command {
+        let (params, elapsed) = KdfParams::calibrate(Duration::from_millis(target_ms), max_memory_mib.saturating_mul(1024))?;
+        if !dry_run {
+            let path = config_path(&cli.config);
+            let costs = [("mem_cost", params.mem_cost), ("time_cost", params.time_cost), ("lanes", params.lanes)];
+            for (key, value) in costs {
+                config::profile::set(path, profile, &format!("security.kdf.{key}"), &value.to_string())?;
+            }
+        }
+        let calibration = Calibration { params, elapsed_ms: elapsed.as_millis() as u64, saved: !dry_run };
+        output.print_with(&calibration, |c| {
+            format!(
+                "{} MiB, {} pass(es), {} lane(s): {} ms per key derivation{}",
+                c.params.mem_cost / 1024,
+                c.params.time_cost,
+                c.params.lanes,
+                c.elapsed_ms,
+                if c.saved { "; saved to security.kdf" } else { "" }
+            )
+        })?;
+        return Ok(());
+    }
+    if let Commands::Unlock { timeout } = cli.command {
+        let auto_lock_secs = timeout.unwrap_or(config.session.auto_lock_secs);
+        let status = unlock_session(&config, cli.signer.as_deref(), auto_lock_secs).await?;
+        output.print_with(&status, |s| {
+            format!("{} key(s) unlocked; locks after {}s without use", s.unlocked, s.locks_in_secs)
+        })?;
+        return Ok(());
+    }
+    // Key files are written before any signer is loaded from them
+    if let Commands::Wallet(
+        wallet_cmd @ (WalletCommands::New { .. } | WalletCommands::Import { .. } | WalletCommands::Export { .. }),
+    ) = cli.command
+    {
+        let report = write_key_file(&config.wallet, cli.signer.as_deref(), wallet_cmd)?;
+        output.print(&report)?;
+        return Ok(());
+    }
+    // Every RPC call goes through the failover transport over `[network]`'s endpoints
+    let (rpc_client, rpc_sender) = connect_rpc(&config.network);
+    let _rpc_health = spawn_health_checks(&rpc_sender);
 
     // Initialize cryptographic context
-    let keypair = load_keypair(&config.wallet.path)?;
-    let mut crypto_ctx = CryptoContext::new(
-        &config.security.encryption_key,
-        HardwareSecurity::from_config(&config.security)?
+    let mut wallet_manager = None;
+    let signer: Arc<dyn Signer> = match (cli.offline, cli.pubkey) {
+        // Online half of an offline flow: only the public key is needed to build the message
+        (true, Some(pubkey)) => Arc::new(NullSigner::new(&pubkey)),
+        _ => resolve_signer(cli.signer.as_deref(), &config.wallet, &mut wallet_manager)?,
+    };
+    let tx_mode = if cli.offline {
+        TxMode::Export {
+            path: cli.tx_out.clone(),
+            nonce: cli.nonce_account.map(|account| NonceConfig {
+                account: account.to_string(),
+                authority: cli.nonce_authority.unwrap_or(signer.pubkey()).to_string(),
+            }),
+        }
+    } else if cli.dry_run {
+        TxMode::DryRun
+    } else {
+        TxMode::Send
+    };
+    let crypto_ctx = crypto_context(&config.security)?;
+
+    // Open the selected accelerator; fall back to CPU execution when none are usable
+    let accel = AccelDevice::open(cli.accel, cli.gpu_devices.as_deref());
+    tracing::info!(accel = ?accel.kind(), "Hardware acceleration selected");
+
+    // Every transaction gets a compute budget and priority fee
+    let mut tx_builder = TxBuilder::new(&rpc_client, config.network.priority_fee.clone());
+    // Exports carry the nonce themselves, and `nonce` subcommands operate on it directly
+    if let Some(account) = cli.nonce_account.filter(|_| !cli.offline && !matches!(cli.command, Commands::Nonce(_))) {
+        let authority = cli.nonce_authority.unwrap_or(signer.pubkey());
+        tx_builder = tx_builder.with_nonce(DurableNonce::new(account, authority));
+    }
+
+    // Circuits and proving keys are resolved per model from the registry
+    let circuits = CircuitRegistry::new(config.zkp.registry.clone());
+    let schemas = SchemaStore::new(
+        config.zkp.registry.ipfs_gateway.clone(),
+        config.paths.model_cache.join("schemas"),
     );
+    let disk_cache = DiskCache::open(config.paths.model_cache.join("store"), &config.cache)?;
+    let model_cache = ModelCache::new(&disk_cache, &config.model);
+    // Fleet-wide result reuse; inference still works without it
+    let results = &config.cache.results;
+    let redis = match (&results.redis_url, results.cluster_nodes.is_empty()) {
+        (_, false) => Some(RedisConfig::Cluster { nodes: results.cluster_nodes.clone(), read_from_replicas: true }),
+        (Some(url), true) => Some(RedisConfig::SingleNode { url: url.clone(), read_only: false }),
+        (None, true) => None,
+    };
+    let result_cache = match redis {
+        Some(redis) => Some(InferenceResultCache::new(RedisPool::new(redis).await?, results)),
+        None => None,
+    };
+
+    // Artifacts go to the IPFS node and, when configured, a remote pinning service;
+    // models are erasure-coded across it and Arweave and fetched from any k shards
+    let storage = IpfsStorage::new(config.ipfs.clone())?;
+    let distributor = ModelDistributor::new(config.distribution.clone());
+
+    // Operations shared with embedding services; the rest of the commands are CLI-only
+    let client = ScoriaClient::new(&rpc_client, signer.clone(), &crypto_ctx, &tx_builder)
+        .with_tx_mode(tx_mode.clone())
+        .with_storage(&storage, &distributor)
+        .with_compression(config.model.compression.clone());
 
     match cli.command {
-        Commands::Deploy { model_path, model_type } => {
-            deploy_model(
-                &rpc_client,
-                &keypair,
-                &crypto_ctx,
-                &model_path,
-                model_type
-            ).await?;
+        Commands::Deploy { model_path, model_type, fee_mint } => {
+            let deployment = client.deploy_model(&model_path, model_type, fee_mint).await?;
+            let label = model_path.file_stem().map(|stem| stem.to_string_lossy().into_owned());
+            remember_models(&config, [(deployment.model.to_string(), label)]);
+            output.print(&deployment)?;
+        }
+        Commands::Infer { model_id, input_data, output: output_path, quantized, no_cache, raw_output } => {
+            let quantized = quantized
+                .map(|path| -> Result<QuantizedModel, Box<dyn Error>> {
+                    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+                })
+                .transpose()?;
+            let runtime = InferenceRuntime {
+                circuits: &circuits,
+                schemas: &schemas,
+                model_cache: &model_cache,
+                distributor: &distributor,
+                result_cache: result_cache.as_ref().filter(|_| !no_cache),
+                accel: accel.clone(),
+            };
+            let outcome = client
+                .run_inference(&runtime, model_id, &input_data, InferenceOptions { quantized, raw_output })
+                .await?;
+            remember_models(&config, [(model_id.to_string(), None)]);
+            let report = InferenceReport {
+                model: outcome.model,
+                output: &output_path,
+                postprocessed: outcome.postprocessed.is_some(),
+                cache_hit: outcome.cache_hit,
+                proof: &outcome.proof,
+            };
+            match outcome.postprocessed {
+                Some(predictions) => std::fs::write(&output_path, serde_json::to_vec_pretty(&predictions)?)?,
+                None => save_output(&output_path, outcome.output)?,
+            }
+            output.print(&report)?;
+        }
+        Commands::Serve { listen } => {
+            let mut serve_config = config.serve.clone();
+            serve_config.listen = listen.unwrap_or(serve_config.listen);
+            let runtime = InferenceRuntime {
+                circuits: &circuits,
+                schemas: &schemas,
+                model_cache: &model_cache,
+                distributor: &distributor,
+                result_cache: result_cache.as_ref(),
+                accel: accel.clone(),
+            };
+            serve(&serve_config, &client, &runtime).await?;
         }
-        Commands::Infer { model_id, input_data, output } => {
-            run_inference(
+        Commands::Contribute { action: Some(ContributeCommands::ProveInclusion { data_hash, record, output: path }), .. } => {
+            let data_hash: [u8; 32] = hex::FromHex::from_hex(&data_hash)?;
+            let proof = client.prove_inclusion(&config.paths.contributions, data_hash, record).await?;
+            match path {
+                Some(path) => std::fs::write(path, serde_json::to_string_pretty(&proof)?)?,
+                // The proof is a document, not a record: JSON unless YAML was asked for
+                None if output == OutputFormat::Yaml => output.print(&proof)?,
+                None => println!("{}", serde_json::to_string_pretty(&proof)?),
+            }
+            tracing::info!(root = %hex::encode(proof.root), record, "Record inclusion proven");
+        }
+        Commands::Contribute { action: Some(ContributeCommands::WithdrawConsent { data_hash, model_id }), .. } => {
+            let data_hash: [u8; 32] = hex::FromHex::from_hex(&data_hash)?;
+            let sig = client.withdraw_consent(model_id, data_hash).await?;
+            tracing::info!("Consent withdrawn; the blob will be deleted");
+            output.print(&serde_json::json!({
+                "model": model_id.to_string(),
+                "data_hash": hex::encode(data_hash),
+                "signature": sig.to_string(),
+            }))?;
+        }
+        Commands::Contribute { action: Some(ContributeCommands::Revoke { data_hash, model_id }), .. } => {
+            let data_hash: [u8; 32] = hex::FromHex::from_hex(&data_hash)?;
+            let sig = client.revoke_contribution(&config.paths.contributions, model_id, data_hash).await?;
+            tracing::info!("Contribution revoked; stored copies will be purged");
+            output.print(&serde_json::json!({
+                "model": model_id.to_string(),
+                "data_hash": hex::encode(data_hash),
+                "revocation": revocation_address(&model_id, &data_hash).to_string(),
+                "signature": sig.to_string(),
+            }))?;
+        }
+        Commands::Contribute {
+            action: None,
+            dataset,
+            model_id,
+            dp_epsilon,
+            pii_policy,
+            pii_dictionary,
+            synthetic,
+            schema,
+            retention_days,
+            purposes,
+            jurisdictions,
+        } => {
+            // Both are required by clap unless a subcommand is given
+            let (Some(dataset), Some(model_id)) = (dataset, model_id) else { unreachable!() };
+            let schema = schema.as_deref().map(Schema::load).transpose()?;
+            let mut scanner = PiiScanner::new(pii_policy);
+            if let Some(path) = pii_dictionary {
+                scanner = scanner.with_dictionary(std::fs::read_to_string(path)?.lines());
+            }
+            let options = ContributeOptions {
+                dp_epsilon,
+                pii_scanner: &scanner,
+                anonymity: config.privacy.anonymity.as_ref(),
+                synthetic,
+                schema: schema.as_ref(),
+                openings: &config.paths.contributions,
+                consent: consent_policy(retention_days, &purposes, &jurisdictions),
+            };
+            let contribution = client.contribute(&dataset, model_id, &options).await?;
+            output.print(&contribution)?;
+        }
+        Commands::Governance(GovernanceCommands::Vote { proposal, choice, weight, on_behalf_of }) => {
+            let receipt = client.vote(proposal, choice, weight, on_behalf_of).await?;
+            output.print(&receipt)?;
+        }
+        Commands::Governance(gov_cmd) => {
+            handle_governance(&rpc_client, &signer, &tx_builder, &tx_mode, gov_cmd).await?;
+        }
+        Commands::Tx(TxCommands::Sign { file }) => {
+            let mut tx = OfflineTransaction::read(&file)?;
+            eprintln!("Signing: {}", tx.description);
+            tx.sign(signer.as_ref())?;
+            tx.write(&file)?;
+            tracing::info!(remaining = tx.missing_signers().len(), "Signature added");
+        }
+        Commands::Tx(TxCommands::Submit { file }) => {
+            let tx = OfflineTransaction::read(&file)?.into_transaction()?;
+            let sig = rpc_client.send_and_confirm_transaction(&tx).await?;
+            output.print_with(&serde_json::json!({ "signature": sig.to_string() }), |_| sig.to_string())?;
+        }
+        Commands::Nonce(NonceCommands::Create { keypair, authority }) => {
+            let nonce_account = match keypair {
+                Some(path) => read_keypair_file(&path)?,
+                None => Keypair::new(),
+            };
+            let authority = authority.unwrap_or(signer.pubkey());
+            let sig = create_nonce_account(&tx_builder, signer.as_ref(), &nonce_account, &authority).await?;
+            tracing::info!(%sig, %authority, "Nonce account created");
+            println!("{}", nonce_account.pubkey());
+        }
+        Commands::Nonce(NonceCommands::Advance { account }) => {
+            let nonce = DurableNonce::new(account, signer.pubkey());
+            let sig = advance_nonce_account(&tx_builder, signer.as_ref(), &nonce, signer.as_ref()).await?;
+            tracing::info!(%sig, %account, "Nonce advanced");
+        }
+        Commands::Nonce(NonceCommands::Show { account }) => {
+            let data = fetch_nonce_data(&rpc_client, &account).await?;
+            output.print(&serde_json::json!({
+                "authority": data.authority.to_string(),
+                "nonce": data.blockhash().to_string(),
+                "lamports_per_signature": data.fee_calculator.lamports_per_signature,
+            }))?;
+        }
+        Commands::Storage(StorageCommands::Pins(PinCommands::List { status, repin_failed })) => {
+            if repin_failed {
+                for pin in storage.repin_failed().await? {
+                    tracing::info!(cid = %pin.pin.cid, requestid = %pin.requestid, "Re-pinned");
+                }
+            }
+            for pin in storage.pinning()?.list(None, &status).await? {
+                println!(
+                    "{}  {:<8} {}  {}  {}",
+                    pin.requestid,
+                    pin.status.as_str(),
+                    pin.pin.cid,
+                    pin.pin.name.as_deref().unwrap_or("-"),
+                    pin.created
+                );
+            }
+        }
+        Commands::Storage(StorageCommands::Pins(PinCommands::Rm { cid })) => {
+            let pinning = storage.pinning()?;
+            for pin in pinning.find(&cid).await? {
+                pinning.remove(&pin.requestid).await?;
+                tracing::info!(%cid, requestid = %pin.requestid, "Unpinned");
+            }
+        }
+        Commands::Cache(CacheCommands::Stat { json }) => {
+            let stats = disk_cache.stats();
+            let models = model_cache.usage();
+            let output = if json { OutputFormat::Json } else { output };
+            if output != OutputFormat::Table {
+                output.print(&serde_json::json!({ "store": stats, "models": models }))?;
+            } else {
+                println!("entries:      {} keys, {} objects", stats.keys, stats.objects);
+                println!("models:       {} / {} bytes ({} models, {} pinned)", models.bytes, models.quota, models.models, models.pinned);
+                println!("size:         {} / {} bytes ({} eviction)", stats.bytes, stats.max_bytes, stats.eviction);
+                println!("hits/misses:  {} / {}", stats.hits, stats.misses);
+                println!("evictions:    {}", stats.evictions);
+                println!("corruptions:  {}", stats.corruptions);
+                println!("deduplicated: {}", stats.deduplicated);
+            }
+        }
+        Commands::Cache(CacheCommands::Gc) => {
+            let report = disk_cache.gc()?;
+            println!(
+                "removed {} corrupt, {} orphaned, {} evicted; freed {} bytes",
+                report.corrupt, report.orphans, report.evicted, report.bytes_freed
+            );
+        }
+        Commands::Cache(CacheCommands::Clear) => {
+            println!("freed {} bytes", disk_cache.clear()?);
+        }
+        Commands::Keys(KeyCommands::Rotate { models_dir, new_master_key, new_version }) => {
+            rotate_keys(&cli.config, profile, &config, &crypto_ctx, &models_dir, &new_master_key, new_version)?;
+        }
+        Commands::Keys(KeyCommands::Share { model, recipient }) => {
+            let recipient: RecipientKey = serde_json::from_slice(&std::fs::read(&recipient)?)?;
+            let path = crypto_ctx.share_data_key(&model, &recipient, config.security.share_kem)?;
+            tracing::info!(recipient = %recipient.fingerprint(), kem = config.security.share_kem.name(), "Data key shared");
+            println!("{}", path.display());
+        }
+        Commands::Keys(KeyCommands::Delegate { model, model_id, provider, recipient }) => {
+            let recipient: RecipientKey = serde_json::from_slice(&std::fs::read(&recipient)?)?;
+            // The proxy only honours keys signed by the model owner's wallet
+            let path = crypto_ctx.reencryption_key(
+                &model,
+                &model_id.to_string(),
+                &provider.to_string(),
+                &recipient,
+                |message| signer.sign_message(message).into(),
+            )?;
+            tracing::info!(%provider, recipient = %recipient.fingerprint(), "Re-encryption key issued");
+            println!("{}", path.display());
+        }
+        Commands::Keys(KeyCommands::Reencrypt { model, kfrag }) => {
+            let kfrag: ReencryptionKey = serde_json::from_slice(&std::fs::read(&kfrag)?)?;
+            let path = client.reencrypt_data_key(&model, &kfrag).await?;
+            tracing::info!(provider = %kfrag.provider, "Data key re-encrypted");
+            println!("{}", path.display());
+        }
+        Commands::Keys(KeyCommands::Recipient { seed }) => {
+            let seed = seed.or(config.security.recipient_seed.clone()).ok_or("security.recipient_seed must be set")?;
+            let secret = RecipientSecret::load_or_create(&seed, config.security.share_kem)?;
+            output.print_with(secret.public_key(), |key| serde_json::to_string_pretty(key).expect("keys serialize"))?;
+        }
+        Commands::Keys(KeyCommands::HsmGenerate { label, signing }) => {
+            let hsm = crypto_ctx.hsm().ok_or("security.hsm must be configured")?;
+            if signing {
+                println!("{}", hex::encode(hsm.generate_signing_key(&label)?));
+            } else {
+                hsm.generate_wrapping_key(&label)?;
+                tracing::info!(%label, "Wrapping key generated on token");
+            }
+        }
+        Commands::Wallet(WalletCommands::Balance { pubkey }) => {
+            let pubkey = pubkey.unwrap_or(signer.pubkey());
+            let balance = Balance { pubkey, lamports: rpc_client.get_balance(&pubkey).await? };
+            output.print_with(&balance, |b| format!("{} SOL", lamports_to_sol(b.lamports)))?;
+        }
+        Commands::Wallet(WalletCommands::Airdrop { sol, to }) => {
+            let airdrop = request_airdrop(&rpc_client, to.unwrap_or(signer.pubkey()), sol).await?;
+            output.print(&airdrop)?;
+        }
+        Commands::Wallet(_) => unreachable!("key file commands return before the signer is loaded"),
+        Commands::Keys(KeyCommands::Protect { keystore }) => {
+            let reference = protect_encryption_key(&cli.config, profile, &config.security, keystore)?;
+            tracing::info!(%reference, "security.encryption_key moved out of the config");
+        }
+        Commands::Model(ModelCommands::Verify { model_id, auditors, expected_root, output }) => {
+            verify_model(
                 &rpc_client,
+                &signer,
                 &crypto_ctx,
+                &model_cache,
+                &distributor,
                 model_id,
-                &input_data,
-                &output
+                &auditors,
+                expected_root.as_deref(),
+                output.as_deref()
             ).await?;
         }
-        Commands::Contribute { dataset, model_id, dp_epsilon } => {
-            contribute_data(
+        Commands::Model(ModelCommands::Search { query, framework, tags, max_fee, sort, limit, json }) => {
+            let url = config.network.indexer_url.as_deref().ok_or("network.indexer_url must be configured")?;
+            let models = IndexerClient::new(url)?
+                .search(&SearchQuery { query, framework, tags, max_fee, sort, limit })
+                .await?;
+            remember_models(&config, models.iter().map(|model| (model.id.clone(), model.name.clone())));
+            let output = if json { OutputFormat::Json } else { output };
+            output.print_with(&models, |models| render_table(models))?;
+        }
+        Commands::Model(ModelCommands::Audit { model_id, report_cid, submit }) => {
+            sign_audit(&rpc_client, &signer, &tx_builder, model_id, &report_cid, submit).await?;
+        }
+        Commands::Model(ModelCommands::Update { model_id, model_path, delta, deposit }) => {
+            let update = client.propose_update(&model_cache, model_id, &model_path, delta, deposit).await?;
+            output.print(&update)?;
+        }
+        Commands::Model(ModelCommands::Renew { model_id, epochs }) => {
+            renew_storage(&rpc_client, &signer, &tx_builder, &tx_mode, model_id, epochs).await?;
+        }
+        Commands::Model(ModelCommands::SetSchema { model_id, input, output }) => {
+            set_schema(&rpc_client, &signer, &tx_builder, &tx_mode, model_id, input.as_deref(), output.as_deref()).await?;
+        }
+        Commands::Model(ModelCommands::SetMetadata { model_id, catalog }) => {
+            set_metadata(&rpc_client, &signer, &tx_builder, &tx_mode, model_id, &catalog).await?;
+        }
+        Commands::Model(ModelCommands::Quantize { model_path, calibration, output }) => {
+            quantize_model(&model_path, &calibration, &output)?;
+        }
+        Commands::Model(ModelCommands::Access(access_cmd)) => {
+            manage_access(&rpc_client, &signer, &tx_builder, &tx_mode, access_cmd).await?;
+        }
+        Commands::Proof(ProofCommands::Verify { proof, vk, public_inputs, model_id }) => {
+            match verify_proof_files(&rpc_client, &circuits, &proof, &vk, &public_inputs, model_id).await {
+                Ok(protocol) => println!("valid {protocol} proof"),
+                Err(e) => {
+                    eprintln!("{e}");
+                    std::process::exit(e.exit_code());
+                }
+            }
+        }
+        Commands::Proof(ProofCommands::Tee { quote, model_id, input_data, output, submit }) => {
+            verify_tee_quote(&rpc_client, &signer, &tx_builder, &config.tee, model_id, &quote, &input_data, &output, submit).await?;
+        }
+        Commands::ZkAggregate { model_id, r1cs, wasm, state_len, layer_inputs, input_data, output, submit } => {
+            aggregate_proofs(
                 &rpc_client,
-                &keypair,
-                &crypto_ctx,
-                dataset,
+                &signer,
+                &tx_builder,
                 model_id,
-                dp_epsilon
+                LayerCircuit { r1cs, wasm, state_len, external_inputs_len: 0 },
+                &layer_inputs,
+                &input_data,
+                &output,
+                submit
             ).await?;
         }
-        Commands::Governance(gov_cmd) => {
-            handle_governance(&rpc_client, &keypair, gov_cmd).await?;
-        }
         // ... other commands
     }
 
     Ok(())
 }
 
+/// `infer` result; the outputs themselves are written to `output`
+#[derive(serde::Serialize)]
+struct InferenceReport<'a> {
+    #[serde(serialize_with = "scoria_client_core::output::display")]
+    model: Pubkey,
+    output: &'a Path,
+    /// Labelled predictions were written instead of raw tensors
+    postprocessed: bool,
+    cache_hit: bool,
+    #[serde(with = "hex::serde")]
+    proof: &'a [u8],
+}
+
+/// `wallet new/import/export` result
+#[derive(serde::Serialize)]
+struct KeyFileReport {
+    #[serde(serialize_with = "scoria_client_core::output::display")]
+    pubkey: Pubkey,
+    path: PathBuf,
+    /// Passphrase-protected keystore rather than a plain JSON keypair
+    encrypted: bool,
+}
+
+#[derive(serde::Serialize)]
+struct Calibration {
+    #[serde(flatten)]
+    params: KdfParams,
+    elapsed_ms: u64,
+    saved: bool,
+}
+
+#[derive(serde::Serialize)]
+struct Locked {
+    /// An unlocked session was running
+    locked: bool,
+}
+
+#[derive(serde::Serialize)]
+struct Balance {
+    #[serde(serialize_with = "scoria_client_core::output::display")]
+    pubkey: Pubkey,
+    lamports: u64,
+}
+
+#[derive(serde::Serialize)]
+struct Airdrop {
+    #[serde(serialize_with = "scoria_client_core::output::display")]
+    recipient: Pubkey,
+    lamports: u64,
+    #[serde(serialize_with = "scoria_client_core::output::display")]
+    signature: Signature,
+    /// Recipient's balance once the airdrop landed
+    balance: u64,
+}
+
 /// Core CLI command structure
 #[derive(Parser)]
 #[command(name = "scoria-cli")]
@@ -69,6 +570,46 @@ struct Cli {
     #[arg(short, long, global = true)]
     config: Option<PathBuf>,
 
+    // Not global: several subcommands take `--output <FILE>`, so it goes before the subcommand
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table, help = "Result format: a table, or json/yaml with stable field names for scripts")]
+    output: OutputFormat,
+
+    #[arg(long, global = true, help = "Config profile to apply instead of the file's active one")]
+    profile: Option<String>,
+
+    #[arg(long, global = true, value_enum, conflicts_with = "profile", help = "Shorthand for --profile <NETWORK>")]
+    network: Option<Network>,
+
+    #[arg(long, global = true, help = "Signer: keypair file path or ledger://[?key=<account>/<change>]")]
+    signer: Option<String>,
+
+    #[arg(long, global = true, value_delimiter = ',', help = "Restrict GPU work to these device ordinals, e.g. 0,2")]
+    gpu_devices: Option<Vec<u32>>,
+
+    #[arg(long, global = true, value_enum, default_value_t = HardwareAccel::Auto, help = "Accelerator for inference and proving")]
+    accel: HardwareAccel,
+
+    #[arg(long, global = true, help = "Export unsigned transactions instead of sending them")]
+    offline: bool,
+
+    #[arg(long, global = true, conflicts_with = "offline", help = "Simulate transactions and report compute units, fees and rent instead of sending them")]
+    dry_run: bool,
+
+    #[arg(long, global = true, default_value = "unsigned_tx.json", help = "Where --offline writes the transaction")]
+    tx_out: PathBuf,
+
+    #[arg(long, global = true, requires = "offline", help = "Fee payer / authority public key when the signer is air-gapped")]
+    pubkey: Option<Pubkey>,
+
+    #[arg(long, global = true, help = "Durable nonce account, so exported or long-running transactions do not expire")]
+    nonce_account: Option<Pubkey>,
+
+    #[arg(long, global = true, requires = "nonce_account", help = "Nonce authority (defaults to the signer)")]
+    nonce_authority: Option<Pubkey>,
+
+    #[arg(long, global = true, help = "Serve Prometheus /metrics on monitoring.prometheus_port while the command runs")]
+    metrics: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -76,6 +617,15 @@ struct Cli {
 /// Supported subcommands
 #[derive(Subcommand)]
 enum Commands {
+    /// First-run setup: signing key, network, connectivity checks and a config file
+    Init {
+        #[arg(long, help = "Take every default without prompting")]
+        yes: bool,
+
+        #[arg(long, help = "Replace an existing config file")]
+        force: bool,
+    },
+
     /// Deploy AI model to network
     Deploy {
         #[arg(help = "Path to model file (ONNX/PT)")]
@@ -83,178 +633,1706 @@ enum Commands {
 
         #[arg(value_enum, help = "Model type")]
         model_type: ModelType,
+
+        #[arg(long, help = "Pay the registration fee in this whitelisted SPL token instead of SOL")]
+        fee_mint: Option<Pubkey>,
     },
 
     /// Execute local inference with ZKP
     Infer {
-        #[arg(help = "Model ID from registry")]
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
         model_id: Pubkey,
 
-        #[arg(help = "Input data file")]
+        #[arg(help = "Input tensors, or raw .txt/image/.wav input when the model declares preprocessing")]
         input_data: PathBuf,
 
         #[arg(help = "Output file path")]
         output: PathBuf,
+
+        #[arg(long, help = "Run the int8 model from `model quantize` for bit-identical, verifiable outputs")]
+        quantized: Option<PathBuf>,
+
+        #[arg(long, help = "Always compute, neither reading nor writing the shared result cache")]
+        no_cache: bool,
+
+        #[arg(long, help = "Write the output tensors even when the model declares post-processing")]
+        raw_output: bool,
+    },
+
+    /// Serve inference over HTTP from warm, decrypted models
+    Serve {
+        #[arg(long, help = "Listen address; overrides `serve.listen`")]
+        listen: Option<std::net::SocketAddr>,
     },
 
     /// Contribute data to federated learning
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Contribute {
-        #[arg(help = "Dataset directory")]
-        dataset: PathBuf,
+        #[command(subcommand)]
+        action: Option<ContributeCommands>,
 
-        #[arg(help = "Target model ID")]
-        model_id: Pubkey,
+        #[arg(required = true, help = "Dataset file or directory of .csv, .parquet and .jsonl files")]
+        dataset: Option<PathBuf>,
+
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), required = true, help = "Target model ID")]
+        model_id: Option<Pubkey>,
 
         #[arg(long, default_value_t = 3.0)]
         dp_epsilon: f64,
+
+        #[arg(long, default_value = "mask", help = "PII handling: <drop|mask|hash>[,<kind>=<action>...]")]
+        pii_policy: PiiPolicy,
+
+        #[arg(long, help = "Newline-separated terms to treat as PII (names, addresses)")]
+        pii_dictionary: Option<PathBuf>,
+
+        #[arg(long, help = "Contribute DP synthetic records instead of the real ones")]
+        synthetic: bool,
+
+        #[arg(long, help = "JSON schema the dataset must match; inferred when omitted")]
+        schema: Option<PathBuf>,
+
+        #[arg(long, default_value_t = 365, help = "Days the encrypted data may be kept before deletion")]
+        retention_days: i64,
+
+        #[arg(long = "purpose", value_enum, default_values_t = [ConsentPurpose::Training], help = "Consented use; repeatable")]
+        purposes: Vec<ConsentPurpose>,
+
+        #[arg(long = "jurisdiction", help = "ISO 3166 country code where it may be decrypted; repeatable, anywhere when omitted")]
+        jurisdictions: Vec<CountryCode>,
     },
 
     /// Governance operations
     Governance(GovernanceCommands),
+
+    /// Data-key and master-key management
+    #[command(subcommand)]
+    Keys(KeyCommands),
+
+    /// Create, import and export signing keys; balances and devnet airdrops
+    #[command(subcommand)]
+    Wallet(WalletCommands),
+
+    /// Unlock keystores once for the commands that follow, until `lock` or session.auto_lock_secs idle
+    Unlock {
+        #[arg(long, help = "Idle seconds before auto-lock, instead of session.auto_lock_secs")]
+        timeout: Option<u64>,
+    },
+
+    /// Wipe unlocked keys now
+    Lock,
+
+    /// Session agent started by `unlock`
+    #[command(hide = true)]
+    Agent {
+        #[arg(long)]
+        socket: PathBuf,
+
+        #[arg(long)]
+        auto_lock_secs: u64,
+    },
+
+    /// Sign and submit transactions exported with --offline
+    #[command(subcommand)]
+    Tx(TxCommands),
+
+    /// Durable nonce accounts for transactions that must outlive a blockhash
+    #[command(subcommand)]
+    Nonce(NonceCommands),
+
+    /// Model integrity operations
+    #[command(subcommand)]
+    Model(ModelCommands),
+
+    /// Proof inspection and verification
+    #[command(subcommand)]
+    Proof(ProofCommands),
+
+    /// Inspect and maintain the on-disk model and proof cache
+    #[command(subcommand)]
+    Cache(CacheCommands),
+
+    /// Manage model artifacts on IPFS and the pinning service
+    #[command(subcommand)]
+    Storage(StorageCommands),
+
+    /// Inspect and edit config profiles
+    #[command(subcommand)]
+    Config(ConfigCommands),
+
+    /// Print a completion script; model IDs complete from the local cache and the indexer
+    Completions {
+        #[arg(value_enum, help = "Shell to complete for; source the output from its startup file")]
+        shell: CompletionShell,
+    },
+
+    /// Print the scoria-cli(1) man page
+    Man {
+        #[arg(long, help = "Write a page per subcommand into this directory instead")]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Fold per-layer inference proofs into one succinct proof
+    #[command(name = "zk_aggregate")]
+    ZkAggregate {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(long, help = "Per-layer step circuit (.r1cs)")]
+        r1cs: PathBuf,
+
+        #[arg(long, help = "Per-layer witness generator (.wasm)")]
+        wasm: PathBuf,
+
+        #[arg(long, default_value_t = 2, help = "Width of the folded state vector")]
+        state_len: usize,
+
+        #[arg(long, help = "JSON array of per-layer external inputs (decimal field elements)")]
+        layer_inputs: PathBuf,
+
+        #[arg(long, help = "Inference input the proof is bound to")]
+        input_data: PathBuf,
+
+        #[arg(long, help = "Output file for the folded proof")]
+        output: PathBuf,
+
+        #[arg(long, help = "Submit the folded proof on-chain")]
+        submit: bool,
+    },
 }
 
-/// Governance subcommands
+/// Offline transaction subcommands
 #[derive(Subcommand)]
-enum GovernanceCommands {
-    CreateProposal {
-        #[arg(help = "Proposal metadata file")]
-        meta: PathBuf,
+enum TxCommands {
+    /// Add the current signer's signature (run on the air-gapped machine)
+    Sign {
+        #[arg(help = "Exported transaction file, updated in place")]
+        file: PathBuf,
+    },
 
-        #[arg(help = "Deposit amount in SCOR")]
-        deposit: f64,
+    /// Broadcast a fully signed transaction
+    Submit {
+        #[arg(help = "Signed transaction file")]
+        file: PathBuf,
     },
-    // ... other governance operations
 }
 
-/// Production-grade model deployment
-async fn deploy_model(
-    rpc_client: &RpcClient,
-    keypair: &Keypair,
-    crypto_ctx: &CryptoContext,
-    model_path: &Path,
-    model_type: ModelType
-) -> Result<Pubkey, Box<dyn Error>> {
-    // Step 1: Model encryption and hashing
-    let (encrypted_model, model_hash) = crypto_ctx.encrypt_model(model_path)?;
-    let compressed_model = compress_model(&encrypted_model)?;
-
-    // Step 2: Generate deployment metadata
-    let metadata = ModelMetadata {
-        model_type,
-        hash: model_hash,
-        owner: keypair.pubkey(),
-        created_at: SystemTime::now(),
-        zk_circuit_id: DEFAULT_ZK_CIRCUIT,
-    };
+/// Durable nonce subcommands
+#[derive(Subcommand)]
+enum NonceCommands {
+    /// Create a nonce account, paid for by the signer
+    Create {
+        #[arg(long, help = "Keypair for the new account's address; generated when omitted")]
+        keypair: Option<PathBuf>,
 
-    // Step 3: On-chain registration
-    let program = anchor_client::Program::new(
-        MODEL_REGISTRY_ID,
-        Arc::new(rpc_client.clone()),
-        Arc::new(keypair.clone())
-    );
+        #[arg(long, help = "Nonce authority (defaults to the signer)")]
+        authority: Option<Pubkey>,
+    },
 
-    let (model_pda, _) = Pubkey::find_program_address(
-        &[b"model", model_hash.as_ref()],
-        &MODEL_REGISTRY_ID
-    );
+    /// Advance a nonce, invalidating transactions signed against its current value
+    Advance {
+        #[arg(help = "Nonce account; the signer must be its authority")]
+        account: Pubkey,
+    },
 
-    let tx = program.request()
-        .accounts(model_registry::accounts::RegisterModel {
-            model: model_pda,
-            owner: keypair.pubkey(),
-            system_program: System::id(),
-        })
-        .args(model_registry::instruction::RegisterModel {
-            metadata,
-            storage_uri: generate_storage_uri(&model_hash),
-        })
-        .signer(keypair)
-        .send()
-        .await?;
+    /// Show a nonce account's authority and current value
+    Show {
+        #[arg(help = "Nonce account")]
+        account: Pubkey,
+    },
+}
 
-    // Step 4: Distribute encrypted model
-    upload_to_ipfs(&compressed_model).await?;
+/// Config file subcommands; --profile/--network select the profile they act on
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective config, secrets redacted
+    Show {
+        #[arg(long, help = "List the profiles instead, the active one starred")]
+        profiles: bool,
+    },
+    /// Set a dotted key, e.g. `network.rpc_url`, in the base config or the selected profile
+    Set {
+        #[arg(help = "Dotted key")]
+        key: String,
 
-    Ok(model_pda)
+        #[arg(help = "TOML value; bare words are strings")]
+        value: String,
+    },
+    /// Make a profile the default
+    Use {
+        #[arg(help = "Declared profile, or devnet, mainnet or localnet")]
+        name: String,
+    },
 }
 
-/// Privacy-preserving inference workflow
-async fn run_inference(
-    rpc_client: &RpcClient,
-    crypto_ctx: &CryptoContext,
-    model_id: Pubkey,
-    input_data: &Path,
-    output: &Path
-) -> Result<(), Box<dyn Error>> {
-    // Step 1: Fetch model metadata
-    let program = anchor_client::Program::new(
-        MODEL_REGISTRY_ID,
-        Arc::new(rpc_client.clone()),
-        Arc::new(Keypair::new())
-    );
+/// Artifact storage subcommands
+#[derive(Subcommand)]
+enum StorageCommands {
+    /// Remote pins held by the configured pinning service
+    #[command(subcommand)]
+    Pins(PinCommands),
+}
+
+/// Pinning service subcommands
+#[derive(Subcommand)]
+enum PinCommands {
+    /// List pins created by this client
+    List {
+        #[arg(long, value_enum, value_delimiter = ',', help = "Only pins in these states; all when omitted")]
+        status: Vec<PinState>,
 
-    let model_account: Account<ModelAccount> = program.account(model_id).await?;
-    let encrypted_model = download_model(&model_account.storage_uri).await?;
-    let model = crypto_ctx.decrypt_model(encrypted_model)?;
+        #[arg(long, help = "Re-submit failed pins before listing")]
+        repin_failed: bool,
+    },
 
-    // Step 2: Prepare input data
-    let input = load_input_data(input_data)?;
-    let zk_inputs = prepare_zk_inputs(&input);
+    /// Unpin an artifact, removing every pin request for its CID
+    Rm {
+        #[arg(help = "CID of the artifact")]
+        cid: String,
+    },
+}
 
-    // Step 3: Execute local inference with ZKP
-    let (output_data, proof) = ModelRuntime::new()
-        .with_hardware_accel()
-        .execute_with_proof(&model, input, zk_inputs)?;
+/// Disk cache subcommands
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Show size, entry counts and hit/miss statistics
+    Stat {
+        #[arg(long, hide = true, help = "Same as --output json")]
+        json: bool,
+    },
 
-    // Step 4: Verify and save output
-    crypto_ctx.verify_proof(&proof, &model_account.zk_circuit_id)?;
-    save_output(output, output_data)?;
+    /// Verify every entry, remove corrupt and orphaned files, and evict to the size budget
+    Gc,
 
-    Ok(())
+    /// Delete every cached model and proof
+    Clear,
 }
 
-/// Secure data contribution pipeline
-async fn contribute_data(
-    rpc_client: &RpcClient,
-    keypair: &Keypair,
-    crypto_ctx: &CryptoContext,
-    dataset: PathBuf,
-    model_id: Pubkey,
-    dp_epsilon: f64
-) -> Result<(), Box<dyn Error>> {
-    // Step 1: Data preprocessing
-    let raw_data = load_dataset(&dataset)?;
-    let sanitized = DataSanitizer::new()
-        .apply_differential_privacy(dp_epsilon)
-        .process(raw_data)?;
+/// Proof subcommands
+#[derive(Subcommand)]
+enum ProofCommands {
+    /// Verify a Groth16 or PLONK proof locally.
+    ///
+    /// Exit codes: 0 valid, 1 invalid proof, 2 malformed input, 3 verifying key
+    /// does not match the model's circuit, 4 on-chain lookup failed.
+    Verify {
+        #[arg(long, help = "Proof: snarkjs proof.json or arkworks compressed bytes")]
+        proof: PathBuf,
 
-    // Step 2: Cryptographic anonymization
-    let (encrypted_data, data_hash) = crypto_ctx.encrypt_data(sanitized)?;
+        #[arg(long, help = "Verifying key: snarkjs verification_key.json or arkworks compressed bytes")]
+        vk: PathBuf,
 
-    // Step 3: On-chain contribution record
-    let program = anchor_client::Program::new(
-        FEDERATION_ID,
-        Arc::new(rpc_client.clone()),
-        Arc::new(keypair.clone())
-    );
+        #[arg(long, help = "snarkjs public.json (array of decimal field elements)")]
+        public_inputs: PathBuf,
 
-    let tx = program.request()
-        .accounts(federation::accounts::ContributeData {
-            model: model_id,
-            contributor: keypair.pubkey(),
-            system_program: System::id(),
-        })
-        .args(federation::instruction::ContributeData {
-            data_hash,
-            dp_epsilon: FixedI64::from_num(dp_epsilon),
-        })
-        .signer(keypair)
-        .send()
-        .await?;
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), long, help = "Also check the verifying key against this model's on-chain zk_circuit")]
+        model_id: Option<Pubkey>,
+    },
+    /// Verify an SGX/SEV-SNP quote from TEE-attested inference against `[tee]`
+    Tee {
+        #[arg(long, help = "Quote JSON written by the enclave: {\"platform\": \"sgx\"|\"snp\", \"raw\": hex}")]
+        quote: PathBuf,
+
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), long, help = "Model the quote is bound to")]
+        model_id: Pubkey,
+
+        #[arg(long, help = "Inference input file")]
+        input_data: PathBuf,
+
+        #[arg(long, help = "Inference output file")]
+        output: PathBuf,
+
+        #[arg(long, help = "Sign the evidence as a verifier and record it with verify_tee_attestation")]
+        submit: bool,
+    },
+}
 
-    // Step 4: Off-chain storage
-    store_contribution(&data_hash, encrypted_data).await?;
+/// Model subcommands
+#[derive(Subcommand)]
+enum ModelCommands {
+    /// Download a model and audit it against its on-chain record
+    Verify {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(long = "auditor", help = "Trusted auditor public key (repeatable)")]
+        auditors: Vec<Pubkey>,
+
+        #[arg(long, help = "Published version Merkle root (hex) to compare against")]
+        expected_root: Option<String>,
+
+        #[arg(long, help = "Write the signed attestation here instead of stdout")]
+        output: Option<PathBuf>,
+    },
+
+    /// Find registered models through the indexer
+    Search {
+        #[arg(long, help = "Free-text match against catalog name and description")]
+        query: Option<String>,
+
+        #[arg(long, help = "Required framework, e.g. onnx or pytorch")]
+        framework: Option<String>,
+
+        #[arg(long = "tag", help = "Required tag (repeatable)")]
+        tags: Vec<String>,
+
+        #[arg(long, help = "Maximum inference fee in lamports or fee-token base units")]
+        max_fee: Option<u64>,
+
+        #[arg(long, value_enum, default_value_t = SortOrder::Popularity, help = "Result order")]
+        sort: SortOrder,
+
+        #[arg(long, default_value_t = 50, help = "Maximum number of results")]
+        limit: u32,
+
+        #[arg(long, hide = true, help = "Same as --output json")]
+        json: bool,
+    },
+
+    /// Sign an audit report for the active model version (auditor key)
+    Audit {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(long, help = "IPFS CID of the pinned audit report")]
+        report_cid: String,
+
+        #[arg(long, help = "Submit the signature on-chain instead of only printing it")]
+        submit: bool,
+    },
+
+    /// Propose a new version for DAO approval
+    Update {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(help = "Path to the new model file")]
+        model_path: PathBuf,
+
+        #[arg(long, value_enum, help = "Upload a patch against the active version instead of the full model")]
+        delta: Option<DeltaCodec>,
+
+        #[arg(long, help = "Security deposit in lamports, refunded with a reward on approval")]
+        deposit: u64,
+    },
+
+    /// Prepay storage so the model does not expire
+    Renew {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(long, default_value_t = 1, help = "Storage epochs to pay for")]
+        epochs: u64,
+    },
+
+    /// Declare the tensor schemas a model accepts and produces
+    SetSchema {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(long, help = "Input schema JSON; pin the printed CID to IPFS")]
+        input: Option<PathBuf>,
+
+        #[arg(long, help = "Output schema JSON; pin the printed CID to IPFS")]
+        output: Option<PathBuf>,
+    },
+
+    /// Publish a model's catalog entry (name, tags, license, framework, input shape)
+    SetMetadata {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(help = "Catalog JSON; pin the printed CID to IPFS")]
+        catalog: PathBuf,
+    },
+
+    /// Calibrate an int8 fixed-point version of a model for deterministic inference
+    Quantize {
+        #[arg(help = "Path to the f32 ONNX model")]
+        model_path: PathBuf,
+
+        #[arg(long, help = "Directory of representative inputs, in the `infer` input format")]
+        calibration: PathBuf,
+
+        #[arg(long, help = "Output file for the quantized model (JSON)")]
+        output: PathBuf,
+    },
+
+    /// Manage who may run or administer a model
+    #[command(subcommand)]
+    Access(AccessCommands),
+}
+
+/// Model access-control subcommands
+#[derive(Subcommand)]
+enum AccessCommands {
+    /// Grant or change a user's access level
+    Grant {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(help = "User public key")]
+        user: Pubkey,
+
+        #[arg(long, value_enum, default_value_t = AccessLevelArg::Inference, help = "Access level to grant")]
+        level: AccessLevelArg,
+    },
+
+    /// Remove a user from the access list
+    Revoke {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(help = "User public key")]
+        user: Pubkey,
+    },
+
+    /// Show the owner, public flag and access list
+    List {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+    },
+
+    /// Open or close inference to everyone
+    SetPublic {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(action = clap::ArgAction::Set, help = "true or false")]
+        is_public: bool,
+    },
+
+    /// Let a key request inference without an ACL entry, for a limited time and number of calls
+    IssueToken {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(help = "Key the token is issued to")]
+        holder: Pubkey,
+
+        #[arg(long, default_value_t = 216_000, help = "Slots until the token expires; about a day by default")]
+        lifetime_slots: u64,
+
+        #[arg(long, help = "Requests allowed over the token's lifetime")]
+        max_invocations: u64,
+
+        #[arg(long, default_value_t = 0, help = "Requests allowed per rate window; 0 for no limit")]
+        rate_limit: u32,
+
+        #[arg(long, default_value_t = 150, help = "Rate window in slots; about a minute by default")]
+        rate_window: u64,
+    },
+
+    /// Close an access token before it expires
+    RevokeToken {
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), help = "Model ID from registry")]
+        model_id: Pubkey,
+
+        #[arg(help = "Key the token was issued to")]
+        holder: Pubkey,
+    },
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum AccessLevelArg {
+    Inference,
+    Contributor,
+    Admin,
+}
+
+impl From<AccessLevelArg> for AccessLevel {
+    fn from(level: AccessLevelArg) -> Self {
+        match level {
+            AccessLevelArg::Inference => AccessLevel::InferenceOnly,
+            AccessLevelArg::Contributor => AccessLevel::Contributor,
+            AccessLevelArg::Admin => AccessLevel::Administrator,
+        }
+    }
+}
+
+/// Key management subcommands
+#[derive(Subcommand)]
+enum KeyCommands {
+    /// Re-wrap model data keys under a new master key without re-encrypting blobs
+    Rotate {
+        #[arg(long, help = "Directory containing encrypted models and key sidecars")]
+        models_dir: PathBuf,
+
+        #[arg(long, help = "Hardware-sealed blob of the new master key")]
+        new_master_key: PathBuf,
+
+        #[arg(long, help = "Version number of the new master key")]
+        new_version: u32,
+    },
+    /// Generate a key on the PKCS#11 token configured under `security.hsm`
+    HsmGenerate {
+        #[arg(help = "Label for the new key")]
+        label: String,
+
+        #[arg(long, help = "P-256 signing key pair instead of an AES-256 wrapping key")]
+        signing: bool,
+    },
+    /// Move a plaintext security.encryption_key into the OS keychain, leaving a reference in the config
+    Protect {
+        #[arg(long, help = "Use a passphrase-protected keystore file instead of the OS keychain")]
+        keystore: Option<PathBuf>,
+    },
+    /// Benchmark Argon2 on this host and save costs to security.kdf; keystores are re-encrypted when next opened
+    Calibrate {
+        #[arg(long, default_value_t = 500, help = "Time one key derivation should take, in milliseconds")]
+        target_ms: u64,
+
+        #[arg(long, default_value_t = 1024, help = "Most memory one key derivation may use, in MiB")]
+        max_memory_mib: u32,
+
+        #[arg(long, help = "Print the costs without saving them")]
+        dry_run: bool,
+    },
+    /// Wrap an enveloped model's data key to an inference provider with security.share_kem
+    Share {
+        #[arg(help = "Encrypted model with a wrapped data key sidecar")]
+        model: PathBuf,
+
+        #[arg(long, help = "Provider's recipient key, as printed by `keys recipient`")]
+        recipient: PathBuf,
+    },
+    /// Issue a re-encryption key letting a provider open a model through a proxy
+    Delegate {
+        #[arg(help = "Encrypted model with a wrapped data key sidecar")]
+        model: PathBuf,
+
+        #[arg(long, help = "Model account whose ACL gates re-encryption")]
+        model_id: Pubkey,
+
+        #[arg(long, help = "Provider wallet, as listed in the model's ACL")]
+        provider: Pubkey,
+
+        #[arg(long, help = "Provider's recipient key, as printed by `keys recipient`")]
+        recipient: PathBuf,
+    },
+    /// As a proxy, re-encrypt a model's data key for the provider of a re-encryption key
+    Reencrypt {
+        #[arg(help = "Encrypted model with a capsule sidecar")]
+        model: PathBuf,
+
+        #[arg(long, help = "Re-encryption key issued by `keys delegate`")]
+        kfrag: PathBuf,
+    },
+    /// Print this node's recipient key, to receive model data keys under security.share_kem
+    Recipient {
+        #[arg(long, help = "File holding the recipient seed, created when missing; defaults to security.recipient_seed")]
+        seed: Option<PathBuf>,
+    },
+}
+
+/// Wallet subcommands; key files default to `wallet.path`
+#[derive(Subcommand)]
+enum WalletCommands {
+    /// Generate a keypair from a new seed phrase
+    New {
+        #[arg(long, default_value_t = 12, help = "Seed phrase length: 12 or 24 words")]
+        words: usize,
+
+        #[arg(long, help = "Write the key here instead of wallet.path")]
+        outfile: Option<PathBuf>,
+
+        #[arg(long, help = "Write a plain JSON keypair instead of a passphrase-protected keystore")]
+        no_encryption: bool,
+
+        #[arg(long, help = "Replace an existing key file")]
+        force: bool,
+    },
+    /// Recover a keypair from a seed phrase, read from the terminal or stdin
+    Import {
+        #[arg(long, default_value = DEFAULT_DERIVATION_PATH, help = "BIP44 account/change under m/44'/501' (Phantom, Solflare)")]
+        derivation_path: String,
+
+        #[arg(long, conflicts_with = "derivation_path", help = "Derive from the seed directly, like solana-keygen recover")]
+        legacy: bool,
+
+        #[arg(long, help = "Also ask for a BIP39 passphrase")]
+        bip39_passphrase: bool,
+
+        #[arg(long, help = "Write the key here instead of wallet.path")]
+        outfile: Option<PathBuf>,
+
+        #[arg(long, help = "Write a plain JSON keypair instead of a passphrase-protected keystore")]
+        no_encryption: bool,
+
+        #[arg(long, help = "Replace an existing key file")]
+        force: bool,
+    },
+    /// SOL balance of the signer or another account
+    Balance {
+        #[arg(help = "Account to check (defaults to the signer)")]
+        pubkey: Option<Pubkey>,
+    },
+    /// Request SOL from the cluster faucet; refused on mainnet
+    Airdrop {
+        #[arg(help = "Amount in SOL")]
+        sol: f64,
+
+        #[arg(long, help = "Recipient (defaults to the signer)")]
+        to: Option<Pubkey>,
+    },
+    /// Copy the wallet's key to another file
+    Export {
+        #[arg(help = "File to write")]
+        path: PathBuf,
+
+        #[arg(long, help = "Protect it with a new passphrase (Argon2id + AES-256-GCM)")]
+        encrypted: bool,
+
+        #[arg(long, help = "Replace an existing file")]
+        force: bool,
+    },
+}
+
+/// Contribution subcommands
+#[derive(Subcommand)]
+enum ContributeCommands {
+    /// Prove one record was part of a contribution, revealing no other record
+    ProveInclusion {
+        #[arg(help = "Data hash (hex) printed when the contribution was recorded")]
+        data_hash: String,
+
+        #[arg(long, help = "Index of the record in the contributed dataset")]
+        record: usize,
+
+        #[arg(long, help = "Write the proof JSON here instead of stdout")]
+        output: Option<PathBuf>,
+    },
+    /// Withdraw consent; the indexer deletes the encrypted data
+    WithdrawConsent {
+        #[arg(help = "Data hash (hex) printed when the contribution was recorded")]
+        data_hash: String,
+
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), long, help = "Model the data was contributed to")]
+        model_id: Pubkey,
+    },
+    /// Erase a contribution: close it on-chain, purge stored copies and
+    /// exclude it from future aggregation rounds
+    Revoke {
+        #[arg(help = "Data hash (hex) printed when the contribution was recorded")]
+        data_hash: String,
+
+        #[arg(add = ArgValueCompleter::new(completion::model_ids), long, help = "Model the data was contributed to")]
+        model_id: Pubkey,
+    },
+}
+
+/// Governance subcommands
+#[derive(Subcommand)]
+enum GovernanceCommands {
+    CreateProposal {
+        #[arg(help = "Proposal metadata file")]
+        meta: PathBuf,
+
+        #[arg(help = "Deposit amount in SCOR")]
+        deposit: f64,
+    },
+
+    /// Vote on a proposal; delegates pass the holder they vote for
+    Vote {
+        #[arg(help = "Proposal account")]
+        proposal: Pubkey,
+
+        #[arg(help = "Index of the chosen option")]
+        choice: u8,
+
+        #[arg(help = "Voting weight to commit")]
+        weight: u64,
+
+        #[arg(long, help = "Vote with weight delegated by this holder")]
+        on_behalf_of: Option<Pubkey>,
+    },
+    // ... other governance operations
+}
+
+/// `config` subcommands read and edit the raw file rather than a loaded config
+fn handle_config(path: &Option<PathBuf>, profile: Option<&str>, cmd: ConfigCommands) -> Result<(), Box<dyn Error>> {
+    match cmd {
+        ConfigCommands::Show { profiles: true } => {
+            let root = read_table(path)?;
+            let active = config::profile::active(&root, profile);
+            for name in config::profile::names(&root) {
+                let marker = if active == Some(name.as_str()) { '*' } else { ' ' };
+                println!("{marker} {name}");
+            }
+        }
+        ConfigCommands::Show { profiles: false } => {
+            let mut resolved = config::profile::resolve(read_table(path)?, profile)?;
+            // Same check as every other command, so show fails where they would
+            let _: ScoriaConfig = toml::Value::Table(resolved.clone()).try_into()?;
+            redact(&mut resolved);
+            print!("{resolved}");
+        }
+        ConfigCommands::Set { key, value } => {
+            let file = config_path(path);
+            let previous = std::fs::read_to_string(file)?;
+            config::profile::set(file, profile, &key, &value)?;
+            // Keep the file loadable: undo edits that break the config
+            if let Err(e) = load_profile(path, profile) {
+                std::fs::write(file, previous)?;
+                return Err(e.into());
+            }
+            tracing::info!(%key, profile = profile.unwrap_or("<base>"), "Config updated");
+        }
+        ConfigCommands::Use { name } => {
+            if !config::profile::exists(&read_table(path)?, &name) {
+                return Err(ConfigError::UnknownProfile(name).into());
+            }
+            config::profile::activate(config_path(path), &name)?;
+            println!("Active profile: {name}");
+        }
+    }
+    Ok(())
+}
+
+/// Ask for each setting, check the endpoints as they are given, provision
+/// keys, then write the config. Runs before any config exists.
+async fn init_wizard(path: &Path, network: Option<Network>, assume_defaults: bool, force: bool) -> Result<(), Box<dyn Error>> {
+    let mut prompt = Prompter::new(std::io::stdin().lock(), std::io::stderr(), assume_defaults);
+    if path.exists() && !force && !prompt.confirm(&format!("{} exists; replace it?", path.display()), false)? {
+        return Err(format!("{} left unchanged; pass --force to replace it", path.display()).into());
+    }
+
+    // 1. Cluster and endpoint
+    let networks = [Network::Devnet, Network::Mainnet, Network::Localnet];
+    let default = networks.iter().position(|n| Some(*n) == network).unwrap_or(0);
+    let network = networks[prompt.choose("Network:", &networks.map(Network::profile), default)?];
+    let rpc_url = prompt.ask("RPC endpoint", network.rpc_url())?;
+    match check_rpc(&rpc_url, network).await {
+        Ok(version) => prompt.say(&format!("  RPC reachable: solana-core {version}"))?,
+        Err(problem) => prompt.say(&format!("  RPC check failed: {problem}; fix it later with `config set`"))?,
+    }
+
+    // 2. Signing key
+    let data_dir = PathBuf::from(prompt.ask("Data directory for caches, logs and keys", ".scoria")?);
+    let default_wallet = data_dir.join("id.json");
+    let (wallet, signer) = match prompt.choose(
+        "Signing key:",
+        &["generate a new keypair", "import a keypair file", "Ledger hardware wallet"],
+        0,
+    )? {
+        0 => {
+            let wallet = PathBuf::from(prompt.ask("Save it to", &default_wallet.to_string_lossy())?);
+            if wallet.exists() {
+                return Err(format!("{} exists; import it instead", wallet.display()).into());
+            }
+            if let Some(dir) = wallet.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let keypair = Keypair::new();
+            write_keypair_file(&keypair, &wallet)?;
+            prompt.say(&format!("  New keypair {}; back up {}", keypair.pubkey(), wallet.display()))?;
+            (wallet, None)
+        }
+        1 => {
+            let solana_default = std::env::var("HOME").map(|home| format!("{home}/.config/solana/id.json"));
+            let wallet = PathBuf::from(prompt.ask("Keypair file", solana_default.as_deref().unwrap_or(""))?);
+            let pubkey = KeyFile::read(&wallet)?.pubkey().ok_or_else(|| format!("{} has no valid public key", wallet.display()))?;
+            prompt.say(&format!("  Using {pubkey}"))?;
+            (wallet, None)
+        }
+        _ => {
+            prompt.say("  Signing on the first Ledger found; `--signer ledger://?key=<account>` picks another key")?;
+            (default_wallet, Some("ledger://".to_string()))
+        }
+    };
+
+    // 3. Storage
+    let ipfs_api_url = prompt.ask("IPFS (Kubo) API", &IpfsConfig::default().api_url)?;
+    let ipfs = IpfsConfig { api_url: ipfs_api_url.clone(), pinning_service: None };
+    match IpfsStorage::new(ipfs)?.version().await {
+        Ok(version) => prompt.say(&format!("  IPFS reachable: kubo {version}"))?,
+        Err(e) => prompt.say(&format!("  IPFS check failed: {e}; uploads fail until the node is up"))?,
+    }
+
+    let mut setup = Setup {
+        network,
+        rpc_url: (rpc_url != network.rpc_url()).then_some(rpc_url),
+        wallet,
+        signer,
+        data_dir: data_dir.clone(),
+        ipfs_api_url,
+        encryption_key: generate_encryption_key(),
+        master_key: None,
+        hsm: None,
+    };
+
+    // 4. Optional hardware-held key-encryption key
+    if prompt.confirm("Provision a key-encryption key on a PKCS#11 HSM?", false)? {
+        prompt.say("  The token PIN is read from SCORIA_HSM_PIN and never written to the config")?;
+        let hsm = HsmConfig {
+            module: PathBuf::from(prompt.ask("PKCS#11 module", "/usr/lib/softhsm/libsofthsm2.so")?),
+            slot: prompt.ask("Slot", "0")?.parse()?,
+            pin: None,
+            key_label: prompt.ask("Key label", "scoria-kek")?,
+        };
+        Pkcs11Hsm::open(&hsm)?.generate_wrapping_key(&hsm.key_label)?;
+        prompt.say(&format!("  Wrapping key {} generated on slot {}", hsm.key_label, hsm.slot))?;
+        setup.hsm = Some(hsm);
+    } else if cfg!(feature = "tpm-support") && prompt.confirm("Seal a master key to this machine's TPM?", false)? {
+        let security = SecurityConfig {
+            encryption_key: setup.encryption_key.clone(),
+            master_key: None,
+            hsm: None,
+            kdf: KdfParams::default(),
+            cipher: CipherId::default(),
+            share_kem: KemId::default(),
+            recipient_seed: None,
+        };
+        let sealed = MasterKey::generate_sealed(&HardwareSecurity::from_config(&security)?)?;
+        let sealed_path = data_dir.join("master.key.sealed");
+        std::fs::create_dir_all(&data_dir)?;
+        std::fs::write(&sealed_path, sealed)?;
+        prompt.say(&format!("  Master key sealed to {}", sealed_path.display()))?;
+        setup.master_key = Some(MasterKeyConfig { key_id: "scoria-master".into(), version: 1, sealed_path });
+    }
+
+    // 5. Data encryption key, kept out of the config file unless asked otherwise
+    let places = ["the OS keychain", "a passphrase-protected keystore file", "the config file, in plaintext"];
+    let mut default = 0;
+    let key_source = loop {
+        let source = match prompt.choose("Keep the data encryption key in:", &places, default)? {
+            0 => SecretSource::new_keychain_entry(),
+            1 => default_keystore(&data_dir),
+            _ => SecretSource::Inline(setup.encryption_key.clone()),
+        };
+        match source.store(&setup.encryption_key) {
+            Ok(()) => break source,
+            // Headless machines often have no secret service; offer the keystore next
+            Err(e @ SecretError::Keychain { .. }) => {
+                prompt.say(&format!("  {e}"))?;
+                default = 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+    setup.encryption_key = key_source.reference();
+
+    setup.write(path)?;
+    prompt.say(&format!("Wrote {} with profile `{}` active", path.display(), network.profile()))?;
+    Ok(())
+}
+
+/// Node version, or why the endpoint is unusable for `network`
+async fn check_rpc(url: &str, network: Network) -> Result<String, String> {
+    let rpc = RpcClient::new(url.to_string());
+    let version = rpc.get_version().await.map_err(|e| e.to_string())?;
+    let genesis = rpc.get_genesis_hash().await.map_err(|e| e.to_string())?;
+    match network.genesis_hash() {
+        Some(expected) if genesis.to_string() != expected => {
+            Err(format!("genesis {genesis} is not {}'s", network.profile()))
+        }
+        _ => Ok(version.solana_core),
+    }
+}
+
+/// `wallet new/import/export`: derive or unlock the keypair, then write it,
+/// sealed under a new passphrase unless asked not to
+fn write_key_file(
+    wallet: &WalletConfig,
+    cli_signer: Option<&str>,
+    cmd: WalletCommands,
+) -> Result<KeyFileReport, Box<dyn Error>> {
+    let (path, encrypted, force) = match &cmd {
+        WalletCommands::New { outfile, no_encryption, force, .. }
+        | WalletCommands::Import { outfile, no_encryption, force, .. } => {
+            (outfile.clone().unwrap_or_else(|| wallet.path.clone()), !no_encryption, *force)
+        }
+        WalletCommands::Export { path, encrypted, force } => (path.clone(), *encrypted, *force),
+        _ => unreachable!("balance and airdrop go to the chain"),
+    };
+    // Checked first so a seed phrase is never shown for a key that is not saved
+    if path.exists() && !force {
+        return Err(format!("{} exists; pass --force to replace it", path.display()).into());
+    }
+
+    let keypair = match cmd {
+        WalletCommands::New { words, .. } => {
+            let phrase = generate_seed_phrase(words)?;
+            eprintln!("Seed phrase; `wallet import` recovers the key from it. Write it down and keep it offline:\n");
+            eprintln!("    {}\n", phrase.as_str());
+            keypair_from_seed_phrase(&phrase, "", Some(DEFAULT_DERIVATION_PATH))?
+        }
+        WalletCommands::Import { derivation_path, legacy, bip39_passphrase, .. } => {
+            let phrase = Zeroizing::new(secret("Seed phrase: ")?);
+            let extra = Zeroizing::new(if bip39_passphrase { secret("BIP39 passphrase: ")? } else { String::new() });
+            keypair_from_seed_phrase(&phrase, &extra, (!legacy).then_some(derivation_path.as_str()))?
+        }
+        _ => match cli_signer.or(wallet.signer.as_deref()).map(SignerSource::parse).transpose()? {
+            Some(SignerSource::Ledger { .. }) => return Err("keys on a hardware wallet cannot be exported".into()),
+            Some(SignerSource::File(source)) => KeyFile::read(&source)?.unlock(&source)?,
+            None => KeyFile::read(&wallet.path)?.unlock(&wallet.path)?,
+        },
+    };
+
+    let pubkey = keypair.pubkey();
+    let file = if encrypted {
+        KeyFile::Encrypted(Keystore::seal(&keypair, &new_passphrase()?)?)
+    } else {
+        KeyFile::Plain(keypair)
+    };
+    file.write(&path, force)?;
+    Ok(KeyFileReport { pubkey, path, encrypted })
+}
+
+/// Open the wallet and encryption-key keystores and hand their contents to
+/// the session agent, starting one when none runs. A running agent keeps its
+/// auto-lock timeout.
+async fn unlock_session(
+    config: &ScoriaConfig,
+    cli_signer: Option<&str>,
+    auto_lock_secs: u64,
+) -> Result<SessionStatus, Box<dyn Error>> {
+    let mut secrets: Vec<(String, Zeroizing<Vec<u8>>)> = Vec::new();
+    let wallet = match cli_signer.or(config.wallet.signer.as_deref()).map(SignerSource::parse).transpose()? {
+        Some(SignerSource::File(path)) => Some(path),
+        Some(SignerSource::Ledger { .. }) => None,
+        None => Some(config.wallet.path.clone()),
+    };
+    if let Some(path) = wallet {
+        if let KeyFile::Encrypted(keystore) = KeyFile::read(&path)? {
+            let keypair = keystore.open(&passphrase(&format!("Passphrase for {}: ", path.display()))?)?;
+            secrets.push((keystore.id(), Zeroizing::new(keypair.to_bytes().to_vec())));
+        }
+    }
+    if let SecretSource::Keystore(path) = SecretSource::parse(&config.security.encryption_key) {
+        let KeyFile::Encrypted(keystore) = KeyFile::read(&path)? else {
+            return Err(SecretError::NotEncryptionKey(path).into());
+        };
+        let secret = keystore.open_secret(&passphrase(&format!("Passphrase for {}: ", path.display()))?)?;
+        secrets.push((keystore.id(), secret));
+    }
+    if secrets.is_empty() {
+        return Err("nothing to unlock: neither the wallet nor security.encryption_key is a keystore".into());
+    }
+
+    let client = SessionClient::default();
+    if client.status().is_none() {
+        start_agent(auto_lock_secs).await?;
+    }
+    for (id, secret) in &secrets {
+        client.put(id, secret)?;
+    }
+    Ok(client.status().ok_or("the session agent stopped")?)
+}
+
+/// `scoria-cli agent`, detached from this terminal; returns once it answers
+async fn start_agent(auto_lock_secs: u64) -> Result<(), Box<dyn Error>> {
+    let socket = session::socket_path();
+    let mut agent = std::process::Command::new(std::env::current_exe()?);
+    agent
+        .arg("agent")
+        .arg("--socket")
+        .arg(&socket)
+        .arg("--auto-lock-secs")
+        .arg(auto_lock_secs.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut agent, 0);
+    agent.spawn()?;
+
+    let client = SessionClient::new(socket);
+    for _ in 0..50 {
+        if client.status().is_some() {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Err("the session agent did not start".into())
+}
+
+/// Faucet SOL, confirmed before returning. Mainnet has no faucet, so the
+/// request is refused there rather than left to fail at the node.
+async fn request_airdrop(rpc_client: &RpcClient, recipient: Pubkey, sol: f64) -> Result<Airdrop, Box<dyn Error>> {
+    let genesis = rpc_client.get_genesis_hash().await?.to_string();
+    if Network::Mainnet.genesis_hash() == Some(genesis.as_str()) {
+        return Err("airdrops are only available on devnet, testnet and localnet".into());
+    }
+    let lamports = sol_to_lamports(sol);
+    let signature = rpc_client.request_airdrop(&recipient, lamports).await?;
+    rpc_client.poll_for_signature(&signature).await?;
+    let balance = rpc_client.get_balance(&recipient).await?;
+    tracing::info!(%recipient, %signature, lamports, "Airdrop confirmed");
+    Ok(Airdrop { recipient, lamports, signature, balance })
+}
+
+/// Store an inline `security.encryption_key` and replace it in the file with a
+/// reference, at the same level `config set` would write it
+fn protect_encryption_key(
+    path: &Option<PathBuf>,
+    profile: Option<&str>,
+    security: &SecurityConfig,
+    keystore: Option<PathBuf>,
+) -> Result<String, Box<dyn Error>> {
+    let SecretSource::Inline(key) = SecretSource::parse(&security.encryption_key) else {
+        return Err("security.encryption_key is already kept outside the config".into());
+    };
+    let target = keystore.map_or_else(SecretSource::new_keychain_entry, SecretSource::Keystore);
+    target.store(&key)?;
+    let reference = target.reference();
+    config::profile::set(config_path(path), profile, "security.encryption_key", &reference)?;
+    if load_profile(path, profile)?.security.encryption_key != reference {
+        return Err(format!(
+            "key stored at {reference}, but the profile in use sets its own security.encryption_key; \
+             rerun with --profile <name> to replace that one"
+        )
+        .into());
+    }
+    Ok(reference)
+}
+
+/// Note model IDs for shell completion; failing to is not worth failing the command
+fn remember_models(config: &ScoriaConfig, models: impl IntoIterator<Item = KnownModel>) {
+    if let Err(e) = KnownModels::record(&config.paths.model_cache, models) {
+        tracing::debug!(error = %e, "Could not record model IDs for completion");
+    }
+}
+
+/// Calibrate activation ranges over sample inputs and write the int8 model
+fn quantize_model(model_path: &Path, calibration: &Path, output: &Path) -> Result<(), Box<dyn Error>> {
+    let onnx = std::fs::read(model_path)?;
+    let mut samples = Vec::new();
+    for entry in std::fs::read_dir(calibration)? {
+        samples.extend(load_input_data(&entry?.path())?);
+    }
+
+    let quantized = QuantizedModel::calibrate(&onnx, &samples)?;
+    std::fs::write(output, serde_json::to_vec_pretty(&quantized)?)?;
+    tracing::info!(
+        model = %quantized.source_hash,
+        layers = quantized.layers.len(),
+        samples = samples.len(),
+        "Quantized model written"
+    );
+    Ok(())
+}
+
+/// Verify proof files, optionally pinning the key to a registered model's circuit
+async fn verify_proof_files(
+    rpc_client: &RpcClient,
+    circuits: &CircuitRegistry,
+    proof: &Path,
+    vk: &Path,
+    public_inputs: &Path,
+    model_id: Option<Pubkey>
+) -> Result<&'static str, VerifyError> {
+    let read = |path: &Path, what: &'static str| {
+        std::fs::read(path).map_err(|e| VerifyError::Malformed { what, reason: format!("{}: {e}", path.display()) })
+    };
+    let proof = Proof::parse(&read(proof, "proof")?)?;
+    let vk = VerifyingKey::parse(&read(vk, "verifying key")?)?;
+    let public = parse_public_inputs(&read(public_inputs, "public inputs")?)?;
+
+    if let Some(model_id) = model_id {
+        let program = anchor_client::Program::new(
+            program_ids().model_registry,
+            Arc::new(rpc_client.clone()),
+            Arc::new(Keypair::new())
+        );
+        let chain = |e: &dyn std::fmt::Display| VerifyError::Chain(e.to_string());
+        let model_account: ModelAccount = program.account(model_id).await.map_err(|e| chain(&e))?;
+        let circuit = circuits.load(&model_account.zk_circuit).await.map_err(|e| chain(&e))?;
+        let proving_key = circuit.proving_key().map_err(|e| chain(&e))?;
+        vk.check_registry_key(&proving_key, &hex::encode(model_account.zk_circuit))?;
+    }
+
+    verify(&proof, &vk, &public)?;
+    Ok(proof.protocol())
+}
+
+/// Rotate the envelope master key for every model under `models_dir`, then
+/// point `security.master_key` at the new key once every data key moved
+fn rotate_keys(
+    path: &Option<PathBuf>,
+    profile: Option<&str>,
+    config: &ScoriaConfig,
+    crypto_ctx: &CryptoContext,
+    models_dir: &Path,
+    new_master_key: &Path,
+    new_version: u32,
+) -> Result<(), Box<dyn Error>> {
+    let current = crypto_ctx
+        .master_key()
+        .ok_or("security.master_key must be configured to rotate keys")?;
+    if new_version <= current.version() {
+        return Err(format!(
+            "new key version {new_version} must be greater than the current version {}",
+            current.version()
+        )
+        .into());
+    }
+    let key_id = &config.security.master_key.as_ref().unwrap().key_id;
+    let next = MasterKey::unseal(
+        crypto_ctx.hardware(),
+        key_id,
+        new_version,
+        &std::fs::read(new_master_key)?,
+    )?;
+
+    let mut audit = AuditLog::open(config.paths.audit_logs.join("key_rotation.jsonl"))?;
+    let report = rotate_master_key(crypto_ctx.aes(), models_dir, current, &next, &mut audit)?;
+
+    tracing::info!(
+        rotated = report.rotated.len(),
+        skipped = report.skipped.len(),
+        failed = report.failed.len(),
+        "Master key rotation finished"
+    );
+    for (model, reason) in &report.failed {
+        tracing::error!(model = %model.display(), %reason, "Key rotation failed");
+    }
+    if !report.failed.is_empty() {
+        return Err(format!("{} data keys could not be rotated", report.failed.len()).into());
+    }
+
+    // Models now only open with the new key, so the config must follow
+    let sealed_path = std::fs::canonicalize(new_master_key)?;
+    let sealed_value = toml_edit::Value::from(sealed_path.display().to_string()).to_string();
+    let file = config_path(path);
+    config::profile::set(file, profile, "security.master_key.version", &new_version.to_string())?;
+    config::profile::set(file, profile, "security.master_key.sealed_path", &sealed_value)?;
+    let updated = load_profile(path, profile)?.security.master_key;
+    if !updated.is_some_and(|key| key.version == new_version && key.sealed_path == sealed_path) {
+        eprintln!(
+            "the profile in use overrides security.master_key; set version = {new_version} and \
+             sealed_path = {} there before the next run, or models will not open",
+            sealed_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Audit a deployed model end to end and emit a signed attestation
+async fn verify_model(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    crypto_ctx: &CryptoContext,
+    model_cache: &ModelCache<'_>,
+    distributor: &ModelDistributor,
+    model_id: Pubkey,
+    auditors: &[Pubkey],
+    expected_root: Option<&str>,
+    output: Option<&Path>
+) -> Result<(), Box<dyn Error>> {
+    // Step 1: Fetch the on-chain record and the stored binary
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let model_account: ModelAccount = program.account(model_id).await?;
+    let (_pins, plaintext) = load_model(model_cache, distributor, crypto_ctx, &model_account.storage_uri).await?;
+
+    // Each trusted auditor's latest signed report, where one was submitted
+    let mut audits = Vec::new();
+    for auditor in auditors {
+        let (record, _) = Pubkey::find_program_address(
+            &[b"audit", model_id.as_ref(), auditor.as_ref()],
+            &program_ids().model_registry
+        );
+        if let Ok(record) = program.account::<model_registry::AuditRecord>(record).await {
+            audits.push(AuditEntry {
+                auditor: record.auditor,
+                version: record.version,
+                report_cid: record.report_cid,
+                signature: record.signature,
+            });
+        }
+    }
+
+    // Step 2: Recompute hashes and check history and auditor signatures
+    let expected_root = expected_root
+        .map(|root| -> Result<[u8; 32], Box<dyn Error>> {
+            let mut out = [0u8; 32];
+            hex::decode_to_slice(root, &mut out)?;
+            Ok(out)
+        })
+        .transpose()?;
+    let report = audit_model(
+        &OnChainModel {
+            address: model_id,
+            owner: model_account.owner,
+            storage_uri: &model_account.storage_uri,
+            model_hash: model_account.model_hash,
+            active_version: model_account.active_version,
+            version_history: &model_account.version_history,
+            audits: &audits,
+        },
+        &plaintext,
+        auditors,
+        expected_root,
+    );
+    for check in report.checks.iter().filter(|c| !c.passed) {
+        tracing::warn!(check = check.name, detail = %check.detail, "Integrity check failed");
+    }
+    let passed = report.passed;
+
+    // Step 3: Sign and emit the attestation
+    let attestation = serde_json::to_string_pretty(&attest(report, signer.as_ref())?)?;
+    match output {
+        Some(path) => std::fs::write(path, attestation)?,
+        None => println!("{attestation}"),
+    }
+
+    if !passed {
+        return Err(format!("model {model_id} failed integrity verification").into());
+    }
+    Ok(())
+}
+
+/// Sign (model_hash, version, report CID) as an auditor and optionally record it on-chain
+async fn sign_audit(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    model_id: Pubkey,
+    report_cid: &str,
+    submit: bool
+) -> Result<(), Box<dyn Error>> {
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let model_account: ModelAccount = program.account(model_id).await?;
+    let version = model_account.active_version;
+    let message = audit_message(&model_account.model_hash, version, report_cid);
+    let signature: [u8; 64] = signer.sign_message(&message).into();
+    println!("{}", serde_json::json!({
+        "model": model_id.to_string(),
+        "auditor": signer.pubkey().to_string(),
+        "version": version,
+        "report_cid": report_cid,
+        "signature": solana_sdk::signature::Signature::from(signature).to_string(),
+    }));
+
+    if submit {
+        let (registry, _) = Pubkey::find_program_address(&[b"auditor_registry"], &program_ids().model_registry);
+        let (record, _) = Pubkey::find_program_address(
+            &[b"audit", model_id.as_ref(), signer.pubkey().as_ref()],
+            &program_ids().model_registry
+        );
+        let mut instructions = vec![ed25519_instruction(&signer.pubkey(), &signature, &message)];
+        instructions.extend(program.request()
+            .accounts(model_registry::accounts::SubmitAudit {
+                model_account: model_id,
+                registry,
+                record,
+                submitter: signer.pubkey(),
+                instructions: solana_sdk::sysvar::instructions::ID,
+                system_program: System::id(),
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::SubmitAudit {
+                auditor: signer.pubkey(),
+                version,
+                report_cid: report_cid.to_string(),
+            })
+            .instructions()?);
+        let sig = tx_builder.send(instructions, &signer.pubkey(), &[signer.as_ref()]).await?;
+        tracing::info!(%sig, %record, version, "Audit recorded on-chain");
+    }
+    Ok(())
+}
+
+/// Grant, revoke, list or publish model access, or manage access tokens
+async fn manage_access(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    tx_mode: &TxMode,
+    cmd: AccessCommands
+) -> Result<(), Box<dyn Error>> {
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+
+    let accounts = |model_id| model_registry::accounts::ManageAccess {
+        model_account: model_id,
+        authority: signer.pubkey(),
+        live: not_paused_accounts(),
+    };
+    let (model_id, description, instructions) = match cmd {
+        AccessCommands::List { model_id } => {
+            let model_account: ModelAccount = program.account(model_id).await?;
+            println!("owner   {} (Administrator)", model_account.owner);
+            println!("public  {}", model_account.is_public);
+            for (user, level) in &model_account.acl {
+                println!("{user}  {level:?}");
+            }
+            return Ok(());
+        }
+        AccessCommands::Grant { model_id, user, level } => (
+            model_id,
+            format!("Grant {level:?} on {model_id} to {user}"),
+            program.request()
+                .accounts(accounts(model_id))
+                .args(model_registry::instruction::GrantAccess { user, level: level.into() })
+                .instructions()?,
+        ),
+        AccessCommands::Revoke { model_id, user } => (
+            model_id,
+            format!("Revoke access on {model_id} from {user}"),
+            program.request()
+                .accounts(accounts(model_id))
+                .args(model_registry::instruction::RevokeAccess { user })
+                .instructions()?,
+        ),
+        AccessCommands::SetPublic { model_id, is_public } => (
+            model_id,
+            format!("Set {model_id} public={is_public}"),
+            program.request()
+                .accounts(accounts(model_id))
+                .args(model_registry::instruction::SetPublic { is_public })
+                .instructions()?,
+        ),
+        AccessCommands::IssueToken { model_id, holder, lifetime_slots, max_invocations, rate_limit, rate_window } => {
+            let expiry_slot = rpc_client.get_slot().await? + lifetime_slots;
+            (
+                model_id,
+                format!("Issue {holder} a token for {max_invocations} requests on {model_id} until slot {expiry_slot}"),
+                program.request()
+                    .accounts(model_registry::accounts::IssueAccessToken {
+                        model_account: model_id,
+                        access_token: access_token_address(&model_id, &holder),
+                        authority: signer.pubkey(),
+                        system_program: System::id(),
+                        live: not_paused_accounts(),
+                    })
+                    .args(model_registry::instruction::IssueAccessToken {
+                        holder,
+                        terms: AccessTokenTerms { expiry_slot, max_invocations, rate_limit, rate_window },
+                    })
+                    .instructions()?,
+            )
+        }
+        AccessCommands::RevokeToken { model_id, holder } => {
+            let address = access_token_address(&model_id, &holder);
+            let token: AccessToken = program.account(address).await?;
+            (
+                model_id,
+                format!("Revoke the access token of {holder} on {model_id}"),
+                program.request()
+                    .accounts(model_registry::accounts::RevokeAccessToken {
+                        model_account: model_id,
+                        access_token: address,
+                        authority: signer.pubkey(),
+                        issuer: token.issuer,
+                        live: not_paused_accounts(),
+                    })
+                    .args(model_registry::instruction::RevokeAccessToken {})
+                    .instructions()?,
+            )
+        }
+    };
+    send_or_export(
+        tx_builder,
+        tx_mode,
+        &description,
+        instructions,
+        &signer.pubkey(),
+        &[signer.as_ref()],
+    ).await?;
+
+    tracing::info!(%model_id, "{description}");
+    Ok(())
+}
+
+/// Pay for additional storage epochs of a model
+async fn renew_storage(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    tx_mode: &TxMode,
+    model_id: Pubkey,
+    epochs: u64
+) -> Result<(), Box<dyn Error>> {
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let model_account: ModelAccount = program.account(model_id).await?;
+    let (storage_vault, _) = Pubkey::find_program_address(&[b"storage_vault"], &program_ids().model_registry);
+
+    let instructions = program.request()
+        .accounts(model_registry::accounts::RenewStorage {
+            model_account: model_id,
+            storage_vault,
+            payer: signer.pubkey(),
+            system_program: System::id(),
+            live: not_paused_accounts(),
+        })
+        .args(model_registry::instruction::RenewStorage { epochs })
+        .instructions()?;
+    send_or_export(
+        tx_builder,
+        tx_mode,
+        &format!(
+            "Renew storage of {model_id} for {epochs} epochs ({} lamports)",
+            model_account.storage_fee.saturating_mul(epochs)
+        ),
+        instructions,
+        &signer.pubkey(),
+        &[signer.as_ref()],
+    ).await?;
+
+    tracing::info!(%model_id, epochs, previous_expiry = model_account.expires_at, "Storage renewal submitted");
+    Ok(())
+}
+
+/// Hash schema documents and record them on-chain; omitted schemas keep their current hash
+async fn set_schema(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    tx_mode: &TxMode,
+    model_id: Pubkey,
+    input: Option<&Path>,
+    output: Option<&Path>
+) -> Result<(), Box<dyn Error>> {
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let model_account: ModelAccount = program.account(model_id).await?;
+
+    let hash_schema = |path: Option<&Path>, current: [u8; 32]| -> Result<[u8; 32], Box<dyn Error>> {
+        let Some(path) = path else { return Ok(current) };
+        let document = std::fs::read(path)?;
+        // Parse before publishing so a malformed schema never reaches the chain
+        serde_json::from_slice::<IoSchema>(&document)?;
+        let hash = schema_hash(&document);
+        println!("{}: ipfs://{}", path.display(), schema_cid(&hash));
+        Ok(hash)
+    };
+    let input_schema_hash = hash_schema(input, model_account.input_schema_hash)?;
+    let output_schema_hash = hash_schema(output, model_account.output_schema_hash)?;
+
+    let instructions = program.request()
+        .accounts(model_registry::accounts::SetSchema {
+            model_account: model_id,
+            authority: signer.pubkey(),
+            live: not_paused_accounts(),
+        })
+        .args(model_registry::instruction::SetSchema { input_schema_hash, output_schema_hash })
+        .instructions()?;
+    send_or_export(
+        tx_builder,
+        tx_mode,
+        &format!("Set I/O schema of {model_id}"),
+        instructions,
+        &signer.pubkey(),
+        &[signer.as_ref()],
+    ).await?;
+
+    tracing::info!(
+        %model_id,
+        input = %hex::encode(input_schema_hash),
+        output = %hex::encode(output_schema_hash),
+        "Schema update submitted"
+    );
+    Ok(())
+}
+
+/// Validate a catalog document and anchor its IPFS URI on-chain
+async fn set_metadata(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    tx_mode: &TxMode,
+    model_id: Pubkey,
+    catalog: &Path,
+) -> Result<(), Box<dyn Error>> {
+    // Validate before publishing so the indexer never sees an unsearchable entry
+    let document = std::fs::read(catalog)?;
+    serde_json::from_slice::<ModelCatalog>(&document)?.validate()?;
+    let metadata_uri = format!("ipfs://{}", schema_cid(&schema_hash(&document)));
+    println!("{}: {metadata_uri}", catalog.display());
+
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let instructions = program.request()
+        .accounts(model_registry::accounts::SetMetadataUri {
+            model_account: model_id,
+            authority: signer.pubkey(),
+            live: not_paused_accounts(),
+        })
+        .args(model_registry::instruction::SetMetadataUri { metadata_uri: metadata_uri.clone() })
+        .instructions()?;
+    send_or_export(
+        tx_builder,
+        tx_mode,
+        &format!("Set catalog metadata of {model_id}"),
+        instructions,
+        &signer.pubkey(),
+        &[signer.as_ref()],
+    ).await?;
+
+    tracing::info!(%model_id, %metadata_uri, "Metadata update submitted");
+    Ok(())
+}
+
+/// Fold every layer step, compress with the decider, and optionally verify on-chain
+async fn aggregate_proofs(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    model_id: Pubkey,
+    layer: LayerCircuit,
+    layer_inputs: &Path,
+    input_data: &Path,
+    output: &Path,
+    submit: bool
+) -> Result<(), Box<dyn Error>> {
+    // Step 1: Load the model commitment and the per-layer inputs
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let model_account: ModelAccount = program.account(model_id).await?;
+    let input_hash: [u8; 32] = Sha256::digest(std::fs::read(input_data)?).into();
+
+    let steps: Vec<Vec<String>> = serde_json::from_slice(&std::fs::read(layer_inputs)?)?;
+    let steps = steps
+        .into_iter()
+        .map(|step| step.iter().map(|x| Fr::from_str(x).map_err(|_| format!("invalid field element: {x}"))).collect())
+        .collect::<Result<Vec<Vec<Fr>>, _>>()?;
+    let layer = LayerCircuit {
+        external_inputs_len: steps.first().map_or(0, Vec::len),
+        ..layer
+    };
+
+    // Step 2: Fold one step per layer
+    let mut rng = rand::rngs::OsRng;
+    let setup = FoldingSetup::generate(&layer, &mut rng)?;
+    let mut folder = InferenceFolder::new(&setup, &model_account.model_hash, &input_hash)?;
+    for ext_inputs in steps {
+        folder.fold_layer(&mut rng, ext_inputs)?;
+    }
+    let proof = folder.finalize(&mut rng)?;
+    tracing::info!(steps = proof.num_steps, "Layer proofs folded");
+    std::fs::write(output, proof.try_to_vec()?)?;
+
+    // Step 3: On-chain verification of the folded proof
+    if submit {
+        let (verifier_key, _) = Pubkey::find_program_address(
+            &[b"folded_vk", model_id.as_ref()],
+            &program_ids().model_registry
+        );
+        let (record, _) = Pubkey::find_program_address(
+            &[b"folded", model_id.as_ref(), &input_hash],
+            &program_ids().model_registry
+        );
+
+        let instructions = program.request()
+            .accounts(model_registry::accounts::SubmitFoldedInference {
+                model_account: model_id,
+                verifier_key,
+                record,
+                submitter: signer.pubkey(),
+                system_program: System::id(),
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::SubmitFoldedInference {
+                input_hash,
+                proof,
+            })
+            .instructions()?;
+        // Pairing checks dominate; the builder sizes the compute budget by simulation
+        let sig = tx_builder.send(instructions, &signer.pubkey(), &[signer.as_ref()]).await?;
+        tracing::info!(%sig, %record, "Folded inference verified on-chain");
+    }
+
+    Ok(())
+}
+
+/// Verify a TEE quote off-chain; with `submit`, attest to it on-chain as a verifier
+async fn verify_tee_quote(
+    rpc_client: &RpcClient,
+    signer: &Arc<dyn Signer>,
+    tx_builder: &TxBuilder<'_>,
+    config: &TeeConfig,
+    model_id: Pubkey,
+    quote: &Path,
+    input_data: &Path,
+    output: &Path,
+    submit: bool
+) -> Result<(), Box<dyn Error>> {
+    // Step 1: The quote must bind the registered model hash and these files
+    let program = anchor_client::Program::new(
+        program_ids().model_registry,
+        Arc::new(rpc_client.clone()),
+        signer.clone()
+    );
+    let model_account: ModelAccount = program.account(model_id).await?;
+    let input_hash: [u8; 32] = Sha256::digest(std::fs::read(input_data)?).into();
+    let output_hash: [u8; 32] = Sha256::digest(std::fs::read(output)?).into();
+    let quote: TeeQuote = serde_json::from_slice(&std::fs::read(quote)?)?;
+
+    // Step 2: Signature chain, measurement allowlist, binding and nonce
+    let evidence = QuoteVerifier::new(config.clone())?.verify(
+        &quote,
+        &model_account.model_hash,
+        &input_hash,
+        &output_hash,
+        signer.pubkey()
+    )?;
+    println!("valid {:?} quote, measurement {}", evidence.platform, hex::encode(evidence.measurement));
+
+    // Step 3: Ed25519 check of the signed evidence, then the recording instruction
+    if submit {
+        let (policy, _) = Pubkey::find_program_address(
+            &[b"tee_policy", model_id.as_ref()],
+            &program_ids().model_registry
+        );
+        let (record, _) = Pubkey::find_program_address(
+            &[b"tee", model_id.as_ref(), &input_hash],
+            &program_ids().model_registry
+        );
+
+        let mut instructions = vec![evidence.ed25519_instruction(&evidence.sign(signer.as_ref()))];
+        instructions.extend(program.request()
+            .accounts(model_registry::accounts::VerifyTeeAttestation {
+                model_account: model_id,
+                policy,
+                record,
+                submitter: signer.pubkey(),
+                instructions: solana_sdk::sysvar::instructions::ID,
+                system_program: System::id(),
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::VerifyTeeAttestation {
+                input_hash,
+                output_hash,
+                evidence: model_registry::TeeEvidence {
+                    platform: match evidence.platform {
+                        TeePlatform::Sgx => model_registry::TeePlatform::Sgx,
+                        TeePlatform::Snp => model_registry::TeePlatform::Snp,
+                    },
+                    measurement: evidence.measurement,
+                    report_data: evidence.report_data,
+                    verifier: evidence.verifier,
+                    verified_at: evidence.verified_at,
+                },
+            })
+            .instructions()?);
+        let sig = tx_builder.send(instructions, &signer.pubkey(), &[signer.as_ref()]).await?;
+        tracing::info!(%sig, %record, "TEE inference recorded on-chain");
+    }
 
     Ok(())
 }
@@ -0,0 +1,94 @@
+// client/src/metrics.rs
+
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder};
+use solana_program::pubkey::Pubkey;
+use std::{net::SocketAddr, time::Duration};
+
+/// Inference spans milliseconds (small CPU models) to minutes (large GPU jobs)
+const INFERENCE_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0];
+/// Groth16 proving is seconds to tens of minutes depending on circuit size
+const PROOF_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0];
+
+/// Serve `/metrics` on `port` for as long as the process runs
+pub fn install_exporter(port: u16) -> Result<(), BuildError> {
+    PrometheusBuilder::new()
+        .with_http_listener(SocketAddr::from(([0, 0, 0, 0], port)))
+        .set_buckets_for_metric(Matcher::Full("inference_latency_seconds".into()), INFERENCE_BUCKETS)?
+        .set_buckets_for_metric(Matcher::Full("proof_generation_seconds".into()), PROOF_BUCKETS)?
+        .install()?;
+
+    metrics::describe_histogram!("inference_latency_seconds", "Local model execution time, including proof");
+    metrics::describe_histogram!("proof_generation_seconds", "Groth16 proof generation time");
+    metrics::describe_counter!("witness_cache_requests_total", "Witness lookups by result: memory_hit, disk_hit or miss");
+    metrics::describe_gauge!("model_cache_bytes", "Bytes of downloaded models in the disk cache");
+    metrics::describe_gauge!("model_cache_quota_bytes", "Configured model.max_cache_size");
+    metrics::describe_counter!("model_cache_evictions_total", "Models evicted to stay within the quota");
+    metrics::describe_counter!("result_cache_requests_total", "Inference result lookups by result: hit, miss or error");
+    metrics::describe_counter!("model_source_requests_total", "Model shard and manifest downloads by source and outcome: ok, corrupt or error");
+    metrics::describe_counter!("model_source_bytes_total", "Bytes downloaded from each model source");
+    metrics::describe_histogram!("model_source_throughput_bytes_per_second", "Per-download bandwidth of each model source");
+    metrics::describe_counter!("serve_requests_total", "Daemon requests by API key and outcome: ok, error, limited, unauthorized or busy");
+    metrics::describe_gauge!("serve_warm_models", "Decrypted models held in memory by the daemon");
+    metrics::describe_gauge!("serve_warm_bytes", "Bytes of decrypted models held in memory");
+    metrics::describe_counter!("provider_requests_total", "On-chain requests handled by the provider worker, by model and outcome: fulfilled, failed, lost or error");
+    Ok(())
+}
+
+/// Record one local inference run
+pub fn log_inference(elapsed: Duration, model_hash: blake3::Hash, model: Pubkey) {
+    tracing::debug!(%model, model_hash = %model_hash.to_hex(), elapsed_ms = elapsed.as_millis() as u64, "Inference complete");
+    metrics::histogram!("inference_latency_seconds", elapsed.as_secs_f64(), "model" => model.to_string());
+}
+
+/// Record one proof; `backend` is `cuda`, `wgpu` or `cpu`
+pub fn log_proof(elapsed: Duration, backend: &'static str) {
+    metrics::histogram!("proof_generation_seconds", elapsed.as_secs_f64(), "backend" => backend);
+}
+
+/// Record one witness cache lookup; `result` is `memory_hit`, `disk_hit` or `miss`
+pub fn log_witness_cache(result: &'static str) {
+    metrics::counter!("witness_cache_requests_total", 1, "result" => result);
+}
+
+/// Record disk usage of cached models against the quota
+pub fn log_model_cache_usage(bytes: u64, quota: u64) {
+    metrics::gauge!("model_cache_bytes", bytes as f64);
+    metrics::gauge!("model_cache_quota_bytes", quota as f64);
+}
+
+/// Record models evicted to make room for a download
+pub fn log_model_cache_evictions(count: usize) {
+    metrics::counter!("model_cache_evictions_total", count as u64);
+}
+
+/// Record one inference result lookup; `result` is `hit`, `miss` or `error`
+pub fn log_result_cache(result: &'static str) {
+    metrics::counter!("result_cache_requests_total", 1, "result" => result);
+}
+
+/// Record one download from a model source (gateway base URL); `outcome` is `ok`, `corrupt` or `error`
+pub fn log_source_fetch(source: &str, bytes: usize, elapsed: Duration, outcome: &'static str) {
+    metrics::counter!("model_source_requests_total", 1, "source" => source.to_string(), "outcome" => outcome);
+    if bytes > 0 {
+        metrics::counter!("model_source_bytes_total", bytes as u64, "source" => source.to_string());
+        let throughput = bytes as f64 / elapsed.as_secs_f64().max(1e-3);
+        metrics::histogram!("model_source_throughput_bytes_per_second", throughput, "source" => source.to_string());
+    }
+}
+
+/// Record one daemon request; `outcome` is `ok`, `error`, `limited`, `unauthorized` or `busy`
+pub fn log_served_request(key: &str, outcome: &'static str) {
+    metrics::counter!("serve_requests_total", 1, "key" => key.to_string(), "outcome" => outcome);
+}
+
+/// Record one on-chain request taken by the provider worker; `outcome` is
+/// `fulfilled`, `failed` (refunded), `lost` (claimed by another provider) or `error`
+pub fn log_provider_request(model: Pubkey, outcome: &'static str) {
+    metrics::counter!("provider_requests_total", 1, "model" => model.to_string(), "outcome" => outcome);
+}
+
+/// Record the daemon's warm model set after a load or eviction
+pub fn log_warm_models(count: usize, bytes: u64) {
+    metrics::gauge!("serve_warm_models", count as f64);
+    metrics::gauge!("serve_warm_bytes", bytes as f64);
+}
@@ -0,0 +1,1225 @@
+// client/src/ops.rs
+
+//! Typed entry points for the network operations. The CLI parses arguments
+//! and prints; services embed the same calls through `ScoriaClient`.
+
+use crate::{
+    config::{programs::program_ids, AnonymityConfig, CompressionConfig, NetworkConfig, SecurityConfig},
+    core::{
+        cache::{
+            manager::{ModelCache, ModelPin},
+            results::{CachedInference, InferenceResultCache},
+        },
+        catalog::ModelCatalog,
+        compression::container,
+        data_sanitizer::{
+            anonymity::enforce_k_anonymity,
+            pii::PiiScanner,
+            synthetic::{MarginalSynthesizer, SynthesizerConfig},
+        },
+        dataset::{
+            commitment::{ContributionOpening, InclusionProof},
+            schema::Schema,
+        },
+        inference::{backend::TensorData, io_schema::SchemaStore, quantize::QuantizedModel},
+        model_loader::{
+            context::CryptoContext,
+            delta::{self, DeltaCodec, DeltaError, DeltaManifest, DELTA_VERSION, MAX_CHAIN},
+            envelope::WrappedDataKey,
+            recipient::RecipientSecret,
+            reencrypt::{Capsule, ReencryptionKey},
+        },
+        storage::{distribution::ModelDistributor, erasure::ErasureManifest, ipfs::IpfsStorage},
+        postprocessing::pipeline::Postprocessed,
+        preprocessing::pipeline::Preprocessor,
+        privacy::dp::DifferentialPrivacy,
+        zkp::registry::CircuitRegistry,
+    },
+    hardware::AccelDevice,
+    output::{display, display_opt, unix_secs},
+    wallet::{
+        fees::TxBuilder,
+        offline::{send_or_export, TxMode},
+    },
+};
+use anchor_client::{anchor_lang::system_program::System, anchor_lang::Id};
+use scoria_rpc::FailoverSender;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use thiserror::Error;
+use tracing::Instrument;
+use zeroize::Zeroizing;
+
+/// Delta for synthetic contributions, well under 1/n for realistic datasets
+const SYNTHETIC_DP_DELTA: f64 = 1e-6;
+/// Version patches above this fraction of the full model are uploaded whole instead
+const MAX_DELTA_RATIO: f64 = 0.5;
+
+/// Which step of an operation failed; the message carries the underlying error
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Encryption failed: {0}")]
+    Crypto(String),
+    #[error("On-chain lookup failed: {0}")]
+    Chain(String),
+    #[error("Transaction failed: {0}")]
+    Transaction(String),
+    #[error("Storage transfer failed: {0}")]
+    Storage(String),
+    #[error("Invalid input: {0}")]
+    Input(String),
+    #[error("Inference failed: {0}")]
+    Inference(String),
+    #[error("Proof rejected: {0}")]
+    Proof(String),
+    #[error("Dataset rejected: {0}")]
+    Dataset(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+trait Classify<T> {
+    fn classify(self, class: fn(String) -> ClientError) -> Result<T, ClientError>;
+}
+
+impl<T, E: Display> Classify<T> for Result<T, E> {
+    fn classify(self, class: fn(String) -> ClientError) -> Result<T, ClientError> {
+        self.map_err(|e| class(e.to_string()))
+    }
+}
+
+/// A registered model; `signature` is `None` when the transaction was exported
+#[derive(Debug, Clone, Serialize)]
+pub struct Deployment {
+    #[serde(serialize_with = "display")]
+    pub model: Pubkey,
+    #[serde(with = "hex::serde")]
+    pub model_hash: [u8; 32],
+    pub storage_uri: String,
+    #[serde(serialize_with = "display_opt")]
+    pub signature: Option<Signature>,
+}
+
+/// A proposed model version; `signature` is `None` when the transaction was exported
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionUpdate {
+    #[serde(serialize_with = "display")]
+    pub proposal: Pubkey,
+    #[serde(with = "hex::serde")]
+    pub new_version_hash: [u8; 32],
+    pub storage_uri: String,
+    /// Set when the upload is a patch against the active version
+    pub delta: Option<DeltaCodec>,
+    /// Encrypted bytes uploaded: the patch for a delta, otherwise the whole model
+    pub uploaded_bytes: u64,
+    #[serde(serialize_with = "display_opt")]
+    pub signature: Option<Signature>,
+}
+
+/// Verified inference output
+#[derive(Debug, Clone)]
+pub struct InferenceOutcome {
+    pub model: Pubkey,
+    pub output: Vec<TensorData>,
+    pub proof: Vec<u8>,
+    /// Served from the shared result cache instead of computed
+    pub cache_hit: bool,
+    /// Labelled predictions, when the model's catalog declares post-processing
+    pub postprocessed: Option<Postprocessed>,
+}
+
+/// A registered model decrypted into memory, reused across requests by `serve`
+pub struct WarmModel {
+    pub model_id: Pubkey,
+    pub account: ModelAccount,
+    pub catalog: Option<ModelCatalog>,
+    model: Zeroizing<Vec<u8>>,
+}
+
+impl WarmModel {
+    /// Decrypted bytes held in memory
+    pub fn size(&self) -> usize {
+        self.model.len()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Contribution {
+    #[serde(serialize_with = "display")]
+    pub model: Pubkey,
+    #[serde(with = "hex::serde")]
+    pub data_hash: [u8; 32],
+    /// Merkle root over the contributed records, stored with the contribution
+    #[serde(with = "hex::serde")]
+    pub records_root: [u8; 32],
+    pub record_count: u64,
+    /// When the indexer deletes the encrypted blob, per the consent policy
+    #[serde(serialize_with = "unix_secs")]
+    pub expires_at: SystemTime,
+    #[serde(serialize_with = "display")]
+    pub signature: Signature,
+}
+
+/// A cast vote; `signature` is `None` when the transaction was exported
+#[derive(Debug, Clone, Serialize)]
+pub struct VoteReceipt {
+    #[serde(serialize_with = "display")]
+    pub proposal: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub vote_record: Pubkey,
+    #[serde(serialize_with = "display_opt")]
+    pub signature: Option<Signature>,
+}
+
+/// Model, circuit and result stores used by inference
+pub struct InferenceRuntime<'a> {
+    pub circuits: &'a CircuitRegistry,
+    pub schemas: &'a SchemaStore,
+    pub model_cache: &'a ModelCache<'a>,
+    pub distributor: &'a ModelDistributor,
+    /// Fleet-wide result reuse; `None` always computes
+    pub result_cache: Option<&'a InferenceResultCache>,
+    pub accel: AccelDevice,
+}
+
+#[derive(Default)]
+pub struct InferenceOptions {
+    /// Int8 model from `model quantize`; never shared through the result cache
+    pub quantized: Option<QuantizedModel>,
+    /// Skip declared post-processing and return only the output tensors
+    pub raw_output: bool,
+}
+
+pub struct ContributeOptions<'a> {
+    pub dp_epsilon: f64,
+    pub pii_scanner: &'a PiiScanner,
+    pub anonymity: Option<&'a AnonymityConfig>,
+    /// Contribute DP synthetic records instead of the real ones
+    pub synthetic: bool,
+    pub schema: Option<&'a Schema>,
+    /// Where the records' Merkle opening is kept for later inclusion proofs
+    pub openings: &'a Path,
+    /// Retention, purposes and jurisdictions anchored with the contribution
+    pub consent: model_registry::instructions::consent::ConsentPolicy,
+}
+
+/// Uses a contributor can consent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConsentPurpose {
+    Training,
+    Evaluation,
+    Research,
+    Commercial,
+}
+
+impl ConsentPurpose {
+    pub fn bit(self) -> u8 {
+        use model_registry::instructions::consent::*;
+        match self {
+            ConsentPurpose::Training => PURPOSE_TRAINING,
+            ConsentPurpose::Evaluation => PURPOSE_EVALUATION,
+            ConsentPurpose::Research => PURPOSE_RESEARCH,
+            ConsentPurpose::Commercial => PURPOSE_COMMERCIAL,
+        }
+    }
+}
+
+impl std::str::FromStr for ConsentPurpose {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as clap::ValueEnum>::from_str(s, true)
+    }
+}
+
+/// ISO 3166-1 alpha-2 code, as the registry stores it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountryCode(pub [u8; 2]);
+
+impl std::str::FromStr for CountryCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match *s.to_ascii_uppercase().as_bytes() {
+            [a, b] if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Ok(Self([a, b])),
+            _ => Err(format!("expected a two-letter country code, got {s:?}")),
+        }
+    }
+}
+
+/// Consent terms for a contribution; no jurisdictions means anywhere
+pub fn consent_policy(
+    retention_days: i64,
+    purposes: &[ConsentPurpose],
+    jurisdictions: &[CountryCode],
+) -> model_registry::instructions::consent::ConsentPolicy {
+    model_registry::instructions::consent::ConsentPolicy {
+        retention_secs: retention_days * 86_400,
+        purposes: purposes.iter().fold(0, |bits, purpose| bits | purpose.bit()),
+        jurisdictions: jurisdictions.iter().map(|code| code.0).collect(),
+    }
+}
+
+/// Signer, encryption keys and transaction path shared by every operation
+pub struct ScoriaClient<'a> {
+    rpc_client: &'a RpcClient,
+    signer: Arc<dyn Signer>,
+    crypto_ctx: &'a CryptoContext,
+    tx_builder: &'a TxBuilder<'a>,
+    tx_mode: TxMode,
+    storage: Option<(&'a IpfsStorage, &'a ModelDistributor)>,
+    compression: CompressionConfig,
+}
+
+impl<'a> ScoriaClient<'a> {
+    pub fn new(
+        rpc_client: &'a RpcClient,
+        signer: Arc<dyn Signer>,
+        crypto_ctx: &'a CryptoContext,
+        tx_builder: &'a TxBuilder<'a>,
+    ) -> Self {
+        Self {
+            rpc_client,
+            signer,
+            crypto_ctx,
+            tx_builder,
+            tx_mode: TxMode::Send,
+            storage: None,
+            compression: CompressionConfig::default(),
+        }
+    }
+
+    /// Export unsigned transactions instead of sending, where the operation allows it
+    pub fn with_tx_mode(mut self, tx_mode: TxMode) -> Self {
+        self.tx_mode = tx_mode;
+        self
+    }
+
+    /// IPFS node and pinning service that deployed models are erasure-coded onto
+    pub fn with_storage(mut self, storage: &'a IpfsStorage, distributor: &'a ModelDistributor) -> Self {
+        self.storage = Some((storage, distributor));
+        self
+    }
+
+    /// Codec deployed models and version patches are compressed with before encryption
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Key every transaction is signed and paid with
+    pub fn pubkey(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    fn program(&self, program_id: Pubkey) -> anchor_client::Program<Arc<dyn Signer>> {
+        anchor_client::Program::new(program_id, Arc::new(self.rpc_client.clone()), self.signer.clone())
+    }
+
+    pub(crate) async fn model_account(&self, model_id: Pubkey) -> Result<ModelAccount, ClientError> {
+        self.program(program_ids().model_registry).account(model_id).await.classify(ClientError::Chain)
+    }
+
+    /// Encrypt, register and upload a model
+    #[tracing::instrument(name = "deploy", skip_all, fields(model_type = ?model_type, model = tracing::field::Empty))]
+    pub async fn deploy_model(
+        &self,
+        model_path: &Path,
+        model_type: ModelType,
+        fee_mint: Option<Pubkey>,
+    ) -> Result<Deployment, ClientError> {
+        // Step 1: Compress the model, then stream-encrypt the container to disk.
+        // The registered hash is of the model itself, not the container.
+        let staging = tempfile::tempdir()?;
+        let compressed_path = staging.path().join("model.scz");
+        let encrypted_path = staging.path().join("model.enc");
+        let model_hash = self.compress_to(model_path, &compressed_path)?;
+        tracing::info_span!("encrypt").in_scope(|| {
+            self.crypto_ctx.encrypt_model_stream(&compressed_path, &encrypted_path).classify(ClientError::Crypto)
+        })?;
+
+        // Step 2: Generate deployment metadata
+        let metadata = ModelMetadata {
+            model_type,
+            hash: model_hash,
+            owner: self.signer.pubkey(),
+            created_at: SystemTime::now(),
+            zk_circuit_id: DEFAULT_ZK_CIRCUIT,
+        };
+
+        // Step 3: Erasure-code the encrypted model (and upload its wrapped data key, if enveloped);
+        // the shard manifest is what gets registered
+        let (model_pda, _) =
+            Pubkey::find_program_address(&[b"model", model_hash.as_ref()], &program_ids().model_registry);
+        tracing::Span::current().record("model", tracing::field::display(model_pda));
+
+        let storage_uri = if matches!(self.tx_mode, TxMode::DryRun) {
+            tracing::info!("Dry run: skipping model upload");
+            generate_storage_uri(&model_hash)
+        } else {
+            let upload_span = tracing::info_span!("upload", bytes = std::fs::metadata(&encrypted_path)?.len());
+            async {
+                let (storage, distributor) =
+                    self.storage.ok_or_else(|| ClientError::Storage("no IPFS storage configured".into()))?;
+                let model = model_pda.to_string();
+                let storage_uri = distributor
+                    .publish(storage, &std::fs::read(&encrypted_path)?, &format!("scoria:model:{model}"))
+                    .await
+                    .classify(ClientError::Storage)?;
+                let key_sidecar = WrappedDataKey::sidecar_path(&encrypted_path);
+                if key_sidecar.exists() {
+                    storage
+                        .upload(&key_sidecar, &format!("scoria:key:{model}"), &[("model", &model), ("kind", "key")])
+                        .await
+                        .classify(ClientError::Storage)?;
+                }
+                Ok::<_, ClientError>(storage_uri)
+            }
+            .instrument(upload_span)
+            .await?
+        };
+
+        // Step 4: On-chain registration
+        let token_fee = fee_mint.map(|mint| token_fee_accounts(&self.signer.pubkey(), &mint));
+        let instructions = self
+            .program(program_ids().model_registry)
+            .request()
+            .accounts(model_registry::accounts::RegisterModel {
+                model: model_pda,
+                owner: self.signer.pubkey(),
+                payment_config: token_fee.map(|t| t.payment_config),
+                payer_token_account: token_fee.map(|t| t.payer_token_account),
+                treasury_token_account: token_fee.map(|t| t.treasury_token_account),
+                token_program: token_fee.map(|_| spl_token::id()),
+                system_program: System::id(),
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::RegisterModel {
+                metadata,
+                storage_uri: storage_uri.clone(),
+            })
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        let signature = send_or_export(
+            self.tx_builder,
+            &self.tx_mode,
+            &format!("Register model {model_pda}"),
+            instructions,
+            &self.signer.pubkey(),
+            &[self.signer.as_ref()],
+        )
+        .instrument(tracing::info_span!("register"))
+        .await
+        .classify(ClientError::Transaction)?;
+
+        Ok(Deployment {
+            model: model_pda,
+            model_hash: *model_hash.as_bytes(),
+            storage_uri,
+            signature,
+        })
+    }
+
+    /// Propose `model_path` as the next version of `model_id`. With a codec, a
+    /// patch against the active version is uploaded instead of the full model,
+    /// unless it saves too little to be worth rebuilding at load time.
+    #[tracing::instrument(name = "propose_update", skip_all, fields(model = %model_id))]
+    pub async fn propose_update(
+        &self,
+        model_cache: &ModelCache<'_>,
+        model_id: Pubkey,
+        model_path: &Path,
+        delta: Option<DeltaCodec>,
+        deposit: u64,
+    ) -> Result<VersionUpdate, ClientError> {
+        let model_account = self.model_account(model_id).await?;
+        let (storage, distributor) =
+            self.storage.ok_or_else(|| ClientError::Storage("no IPFS storage configured".into()))?;
+        let model = model_id.to_string();
+        let version = model_account.active_version + 1;
+
+        // Step 1: Diff against the active version, whose plaintext must match its on-chain hash
+        let target = std::fs::read(model_path)?;
+        let target_hash = blake3::hash(&target);
+        let patch = match delta {
+            Some(codec) => {
+                let (_pins, base) = load_model(model_cache, distributor, self.crypto_ctx, &model_account.storage_uri)
+                    .instrument(tracing::info_span!("load_base"))
+                    .await?;
+                if blake3::hash(&base) != model_account.model_hash {
+                    return Err(ClientError::Storage("active version does not match its on-chain hash".into()));
+                }
+                let patch = tracing::info_span!("diff", ?codec)
+                    .in_scope(|| delta::diff(&base, &target, codec))
+                    .classify(ClientError::Storage)?;
+                if patch.len() as f64 > target.len() as f64 * MAX_DELTA_RATIO {
+                    tracing::info!(patch_bytes = patch.len(), model_bytes = target.len(), "Delta too large, uploading full model");
+                    None
+                } else {
+                    Some((codec, patch))
+                }
+            }
+            None => None,
+        };
+
+        // Step 2: Compress, encrypt and distribute the patch or model; a delta also gets a manifest naming its base
+        let staging = tempfile::tempdir()?;
+        let blob_path = match &patch {
+            Some((_, patch)) => {
+                let path = staging.path().join("model.patch");
+                std::fs::write(&path, patch)?;
+                path
+            }
+            None => model_path.to_path_buf(),
+        };
+        let compressed_path = staging.path().join("model.scz");
+        self.compress_to(&blob_path, &compressed_path)?;
+        let (encrypted, _) = self.crypto_ctx.encrypt_model(&compressed_path).classify(ClientError::Crypto)?;
+        let uploaded_bytes = encrypted.len() as u64;
+        let storage_uri = if matches!(self.tx_mode, TxMode::DryRun) {
+            tracing::info!("Dry run: skipping model upload");
+            generate_storage_uri(&target_hash)
+        } else {
+            let blob_uri = distributor
+                .publish(storage, &encrypted, &format!("scoria:model:{model}:v{version}"))
+                .instrument(tracing::info_span!("upload", bytes = uploaded_bytes))
+                .await
+                .classify(ClientError::Storage)?;
+            match &patch {
+                Some((codec, _)) => {
+                    let manifest = DeltaManifest {
+                        version: DELTA_VERSION,
+                        codec: *codec,
+                        base_uri: model_account.storage_uri.clone(),
+                        base_hash: model_account.model_hash,
+                        patch_uri: blob_uri,
+                        target_hash: *target_hash.as_bytes(),
+                    };
+                    let manifest_json = serde_json::to_vec(&manifest).classify(ClientError::Storage)?;
+                    let cid = storage
+                        .upload_bytes(manifest_json, &format!("scoria:delta:{model}:v{version}"), &[("model", &model), ("kind", "delta")])
+                        .await
+                        .classify(ClientError::Storage)?;
+                    DeltaManifest::storage_uri(&cid)
+                }
+                None => blob_uri,
+            }
+        };
+
+        // Step 3: On-chain proposal, voted on by the DAO
+        let (proposal, _) = Pubkey::find_program_address(
+            &[b"version_proposal", model_id.as_ref(), &model_account.active_version.to_le_bytes()],
+            &program_ids().model_registry,
+        );
+        let delta_base = patch.as_ref().map(|_| model_account.model_hash);
+        let instructions = self
+            .program(program_ids().model_registry)
+            .request()
+            .accounts(model_registry::accounts::ProposeVersionUpdate {
+                model: model_id,
+                proposal,
+                submitter: self.signer.pubkey(),
+                dao_program: program_ids().governance,
+                system_program: System::id(),
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::ProposeUpdate {
+                new_version_hash: *target_hash.as_bytes(),
+                zk_circuit_hash: model_account.zk_circuit,
+                deposit,
+                storage_uri: storage_uri.clone(),
+                delta_base,
+            })
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        let signature = send_or_export(
+            self.tx_builder,
+            &self.tx_mode,
+            &format!("Propose version {version} of {model_id}"),
+            instructions,
+            &self.signer.pubkey(),
+            &[self.signer.as_ref()],
+        )
+        .instrument(tracing::info_span!("propose"))
+        .await
+        .classify(ClientError::Transaction)?;
+
+        Ok(VersionUpdate {
+            proposal,
+            new_version_hash: *target_hash.as_bytes(),
+            storage_uri,
+            delta: patch.map(|(codec, _)| codec),
+            uploaded_bytes,
+            signature,
+        })
+    }
+
+    /// Compress `input` into a container at `output`, returning the hash of the uncompressed bytes
+    fn compress_to(&self, input: &Path, output: &Path) -> Result<blake3::Hash, ClientError> {
+        let span = tracing::info_span!("compress", codec = ?self.compression.codec);
+        span.in_scope(|| {
+            let raw = std::fs::read(input)?;
+            let packed = container::compress(&raw, &self.compression).classify(ClientError::Storage)?;
+            tracing::debug!(raw = raw.len(), compressed = packed.len(), "Compressed");
+            std::fs::write(output, packed)?;
+            Ok(blake3::hash(&raw))
+        })
+    }
+
+    /// Run a registered model locally with a proof of execution, checked before it is returned
+    #[tracing::instrument(name = "infer", skip_all, fields(model = %model_id, proof_size = tracing::field::Empty))]
+    pub async fn run_inference(
+        &self,
+        runtime: &InferenceRuntime<'_>,
+        model_id: Pubkey,
+        input_data: &Path,
+        options: InferenceOptions,
+    ) -> Result<InferenceOutcome, ClientError> {
+        let InferenceOptions { quantized, raw_output } = options;
+
+        // Step 1: Fetch model metadata
+        let model_account = self.model_account(model_id).await?;
+        if let Some(quantized) = &quantized {
+            quantized.check_source(&model_account.model_hash).classify(ClientError::Input)?;
+        }
+
+        // Step 2: Prepare input data, failing fast on tensors the model does not accept.
+        // Raw text, images and audio go through the preprocessing the catalog declares.
+        let catalog = ModelCatalog::fetch(runtime.schemas, &model_account.metadata_uri)
+            .await
+            .classify(ClientError::Chain)?;
+        let preprocessing = catalog.as_ref().and_then(|c| c.preprocessing.clone()).filter(|p| p.accepts(input_data));
+        let input = match preprocessing {
+            Some(spec) => Preprocessor::load(spec, runtime.schemas)
+                .await
+                .classify(ClientError::Input)?
+                .run(input_data)
+                .classify(ClientError::Input)?,
+            None => load_input_data(input_data).classify(ClientError::Input)?,
+        };
+        if let Some(schema) = runtime.schemas.fetch(&model_account.input_schema_hash).await.classify(ClientError::Chain)? {
+            schema.validate(&input).classify(ClientError::Input)?;
+        }
+
+        // Step 3: Execute local inference with ZKP, unless another provider already has.
+        // Quantized runs produce different outputs for the same model hash, so never share them.
+        let shared = runtime.result_cache.filter(|_| quantized.is_none());
+        let compute = || self.execute_inference(runtime, &model_account, model_id, input, quantized);
+        let (result, cache_hit) = match shared {
+            Some(cache) => {
+                let input_hash = blake3::hash(&std::fs::read(input_data)?);
+                let (result, hit) = cache.get_or_compute(&model_account.model_hash, input_hash.as_bytes(), compute).await?;
+                tracing::info!(%model_id, hit, "Result cache consulted");
+                (result, hit)
+            }
+            None => (compute().await?, false),
+        };
+        tracing::Span::current().record("proof_size", result.proof.len());
+
+        // Step 4: Verify output; cached results are checked like fresh ones
+        self.crypto_ctx
+            .verify_proof(&result.proof, &model_account.zk_circuit_id)
+            .classify(ClientError::Proof)?;
+        if let Some(schema) = runtime.schemas.fetch(&model_account.output_schema_hash).await.classify(ClientError::Chain)? {
+            schema.validate(&result.output).classify(ClientError::Inference)?;
+        }
+
+        // Step 5: Labelled predictions for models that declare post-processing
+        let postprocessed = match catalog.and_then(|c| c.postprocess).filter(|_| !raw_output) {
+            Some(spec) => Some(spec.apply(&result.output).classify(ClientError::Inference)?),
+            None => None,
+        };
+
+        Ok(InferenceOutcome { model: model_id, output: result.output, proof: result.proof, cache_hit, postprocessed })
+    }
+
+    /// Download, decrypt and run the model, proving the execution
+    async fn execute_inference(
+        &self,
+        runtime: &InferenceRuntime<'_>,
+        model_account: &ModelAccount,
+        model_id: Pubkey,
+        input: Vec<TensorData>,
+        quantized: Option<QuantizedModel>,
+    ) -> Result<CachedInference, ClientError> {
+        let (_pins, model) =
+            load_model(runtime.model_cache, runtime.distributor, self.crypto_ctx, &model_account.storage_uri).await?;
+        let circuit = runtime.circuits.load(&model_account.zk_circuit).await.classify(ClientError::Chain)?;
+        let zk_inputs = prepare_zk_inputs(&input);
+
+        let started = Instant::now();
+        let (output_data, proof) = ModelRuntime::new()
+            .with_accel(runtime.accel.clone())
+            .with_circuit(circuit)
+            .with_quantization(quantized)
+            .execute_with_proof(&model, input, zk_inputs)
+            .classify(ClientError::Inference)?;
+        crate::metrics::log_inference(started.elapsed(), blake3::Hash::from(model_account.model_hash), model_id);
+        Ok(CachedInference::new(output_data, proof))
+    }
+
+    pub async fn inference_request(&self, request: Pubkey) -> Result<InferenceRequest, ClientError> {
+        self.program(program_ids().model_registry).account(request).await.classify(ClientError::Chain)
+    }
+
+    /// Pending requests for `model`, oldest first. Filters on the account
+    /// layout: `model` follows the discriminator, `status` follows the hashes.
+    pub async fn pending_inference_requests(
+        &self,
+        model: Pubkey,
+    ) -> Result<Vec<(Pubkey, InferenceRequest)>, ClientError> {
+        const MODEL_OFFSET: usize = 8;
+        const STATUS_OFFSET: usize = 8 + 32 + 32 + 32;
+        let filters = vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(MODEL_OFFSET, model.as_ref())),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(STATUS_OFFSET, &[InferenceStatus::Pending as u8])),
+        ];
+        let mut pending: Vec<(Pubkey, InferenceRequest)> =
+            self.program(program_ids().model_registry).accounts(filters).await.classify(ClientError::Chain)?;
+        pending.sort_by_key(|(_, request)| request.created_at);
+        Ok(pending)
+    }
+
+    /// Reserve a pending request as its provider for `duration` seconds
+    pub async fn claim_inference(&self, request: Pubkey, duration: i64) -> Result<Signature, ClientError> {
+        let program = self.program(program_ids().model_registry);
+        let model = self.inference_request(request).await?.model;
+        let provider = self.signer.pubkey();
+        let instructions = program
+            .request()
+            .accounts(model_registry::accounts::ClaimInference {
+                model_account: model,
+                inference_request: request,
+                provider,
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::ClaimInference { duration })
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        self.tx_builder
+            .send(instructions, &provider, &[self.signer.as_ref()])
+            .await
+            .classify(ClientError::Transaction)
+    }
+
+    /// Fetch, decrypt and hold a registered model for repeated inference
+    pub async fn warm_model(&self, runtime: &InferenceRuntime<'_>, model_id: Pubkey) -> Result<WarmModel, ClientError> {
+        let account = self.model_account(model_id).await?;
+        let catalog = ModelCatalog::fetch(runtime.schemas, &account.metadata_uri)
+            .await
+            .classify(ClientError::Chain)?;
+        // The decrypted copy lives in memory; the cache pins are not needed past this point
+        let (_pins, model) =
+            load_model(runtime.model_cache, runtime.distributor, self.crypto_ctx, &account.storage_uri).await?;
+        Ok(WarmModel { model_id, account, catalog, model: Zeroizing::new(model) })
+    }
+
+    /// Run a warm model on in-memory tensors. Without `prove` the output is
+    /// returned unproven, for callers that trust this node.
+    #[tracing::instrument(name = "infer_warm", skip_all, fields(model = %warm.model_id, prove))]
+    pub async fn infer_warm(
+        &self,
+        runtime: &InferenceRuntime<'_>,
+        warm: &WarmModel,
+        input: Vec<TensorData>,
+        prove: bool,
+    ) -> Result<InferenceOutcome, ClientError> {
+        let account = &warm.account;
+        if let Some(schema) = runtime.schemas.fetch(&account.input_schema_hash).await.classify(ClientError::Chain)? {
+            schema.validate(&input).classify(ClientError::Input)?;
+        }
+
+        let started = Instant::now();
+        let model_runtime = ModelRuntime::new().with_accel(runtime.accel.clone());
+        let (output, proof) = if prove {
+            let circuit = runtime.circuits.load(&account.zk_circuit).await.classify(ClientError::Chain)?;
+            let zk_inputs = prepare_zk_inputs(&input);
+            let (output, proof) = model_runtime
+                .with_circuit(circuit)
+                .execute_with_proof(&warm.model, input, zk_inputs)
+                .classify(ClientError::Inference)?;
+            self.crypto_ctx.verify_proof(&proof, &account.zk_circuit_id).classify(ClientError::Proof)?;
+            (output, proof)
+        } else {
+            (model_runtime.execute(&warm.model, input).classify(ClientError::Inference)?, Vec::new())
+        };
+        crate::metrics::log_inference(started.elapsed(), blake3::Hash::from(account.model_hash), warm.model_id);
+
+        if let Some(schema) = runtime.schemas.fetch(&account.output_schema_hash).await.classify(ClientError::Chain)? {
+            schema.validate(&output).classify(ClientError::Inference)?;
+        }
+        let postprocessed = match warm.catalog.as_ref().and_then(|c| c.postprocess.as_ref()) {
+            Some(spec) => Some(spec.apply(&output).classify(ClientError::Inference)?),
+            None => None,
+        };
+        Ok(InferenceOutcome { model: warm.model_id, output, proof, cache_hit: false, postprocessed })
+    }
+
+    /// Settle an on-chain inference request as its provider. Token fees go to
+    /// the payee's associated account (the provider on success, the requester
+    /// on failure), optimistic models bond the provider's stake, and forks pay
+    /// their parent's royalty vault.
+    pub async fn fulfill_inference(
+        &self,
+        request: Pubkey,
+        outcome: model_registry::instructions::inference::InferenceOutcome,
+    ) -> Result<Signature, ClientError> {
+        let program = self.program(program_ids().model_registry);
+        let inference_request = self.inference_request(request).await?;
+        let model = inference_request.model;
+        let model_account = self.model_account(model).await?;
+        let provider = self.signer.pubkey();
+        let payee = match outcome {
+            model_registry::instructions::inference::InferenceOutcome::Success { .. } => provider,
+            model_registry::instructions::inference::InferenceOutcome::Failure { .. } => inference_request.requester,
+        };
+
+        let token = match inference_request.fee_mint {
+            Some(mint) => {
+                let accounts = token_fee_accounts(&payee, &mint);
+                let config: PaymentConfig = program.account(accounts.payment_config).await.classify(ClientError::Chain)?;
+                let escrow = config
+                    .accepted_mints
+                    .iter()
+                    .find(|m| m.mint == mint)
+                    .map(|m| m.escrow)
+                    .ok_or_else(|| ClientError::Chain(format!("fee mint {mint} is not in the payment config")))?;
+                Some((accounts.payment_config, escrow, accounts.payer_token_account))
+            }
+            None => None,
+        };
+        let registry = program_ids().model_registry;
+        let (optimistic_config, _) = Pubkey::find_program_address(&[b"optimistic", model.as_ref()], &registry);
+        let optimistic = program.account::<OptimisticConfig>(optimistic_config).await.is_ok();
+        let (provider_stake, _) =
+            Pubkey::find_program_address(&[b"provider_stake", model.as_ref(), provider.as_ref()], &registry);
+        let royalty_vault = model_account
+            .parent_model
+            .map(|parent| Pubkey::find_program_address(&[b"royalty_vault", parent.as_ref()], &registry).0);
+
+        let instructions = program
+            .request()
+            .accounts(model_registry::accounts::FulfillInference {
+                model_account: model,
+                inference_request: request,
+                provider,
+                requester: inference_request.requester,
+                payment_config: token.map(|t| t.0),
+                escrow_token_account: token.map(|t| t.1),
+                payout_token_account: token.map(|t| t.2),
+                token_program: token.map(|_| spl_token::id()),
+                optimistic_config: optimistic.then_some(optimistic_config),
+                provider_stake: optimistic.then_some(provider_stake),
+                royalty_vault,
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::FulfillInference { outcome })
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        self.tx_builder
+            .send(instructions, &provider, &[self.signer.as_ref()])
+            .await
+            .classify(ClientError::Transaction)
+    }
+
+    /// Sanitize, encrypt and record a dataset contribution
+    pub async fn contribute(
+        &self,
+        dataset: &Path,
+        model_id: Pubkey,
+        options: &ContributeOptions<'_>,
+    ) -> Result<Contribution, ClientError> {
+        // Step 1: Data preprocessing; identifiers are redacted before any DP noise
+        let mut raw_data = load_dataset(dataset, options.schema).classify(ClientError::Dataset)?;
+        let pii_report = options.pii_scanner.redact(&mut raw_data);
+        pii_report.log();
+
+        let sanitized = if options.synthetic {
+            // The synthesizer spends the whole epsilon; its samples need no further noise
+            let mut dp = DifferentialPrivacy::new(options.dp_epsilon, SYNTHETIC_DP_DELTA).classify(ClientError::Input)?;
+            let model = MarginalSynthesizer::fit(&raw_data, SynthesizerConfig::default(), &mut dp)
+                .classify(ClientError::Dataset)?;
+            let records = model.sample(model.noisy_rows(), &mut rand::thread_rng());
+            model.quality_report(&raw_data, &records).log();
+            DataSanitizer::new().process(records).classify(ClientError::Dataset)?
+        } else {
+            if let Some(anonymity) = options.anonymity {
+                enforce_k_anonymity(anonymity, &mut raw_data).classify(ClientError::Dataset)?.log();
+            }
+            DataSanitizer::new()
+                .apply_differential_privacy(options.dp_epsilon)
+                .process(raw_data)
+                .classify(ClientError::Dataset)?
+        };
+
+        // Step 2: Commit to each record, then encrypt the whole set
+        let opening = ContributionOpening::commit(model_id.to_string(), sanitized);
+        if opening.is_empty() {
+            return Err(ClientError::Dataset("no records left to contribute".into()));
+        }
+        let records_root = opening.root();
+        let record_count = opening.len() as u64;
+        let payload = serde_json::to_vec(opening.records()).classify(ClientError::Dataset)?;
+        let (encrypted_data, data_hash) = self.crypto_ctx.encrypt_data(payload).classify(ClientError::Crypto)?;
+        // Kept before sending: without it the root can never be opened
+        opening.save(options.openings, &data_hash).classify(ClientError::Storage)?;
+
+        // Step 3: On-chain contribution record and its consent, in one transaction
+        let mut instructions = self
+            .program(program_ids().federation)
+            .request()
+            .accounts(federation::accounts::ContributeData {
+                model: model_id,
+                contribution: contribution_address(&model_id, &data_hash),
+                contributor: self.signer.pubkey(),
+                system_program: System::id(),
+            })
+            .args(federation::instruction::ContributeData {
+                data_hash,
+                dp_epsilon: FixedI64::from_num(options.dp_epsilon),
+                records_root,
+                record_count,
+            })
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        instructions.extend(
+            self.program(program_ids().model_registry)
+                .request()
+                .accounts(model_registry::accounts::RecordConsent {
+                    model_account: model_id,
+                    consent: consent_address(&model_id, &data_hash),
+                    contributor: self.signer.pubkey(),
+                    system_program: System::id(),
+                    live: not_paused_accounts(),
+                })
+                .args(model_registry::instruction::RecordConsent { data_hash, policy: options.consent.clone() })
+                .instructions()
+                .classify(ClientError::Transaction)?,
+        );
+        let signature = self
+            .tx_builder
+            .send(instructions, &self.signer.pubkey(), &[self.signer.as_ref()])
+            .await
+            .classify(ClientError::Transaction)?;
+
+        // Step 4: Off-chain storage
+        store_contribution(&data_hash, encrypted_data).await.classify(ClientError::Storage)?;
+
+        // Approximate: the program starts the clock at the slot's timestamp
+        let expires_at = SystemTime::now() + Duration::from_secs(options.consent.retention_secs as u64);
+        Ok(Contribution { model: model_id, data_hash, records_root, record_count, expires_at, signature })
+    }
+
+    /// Withdraw consent for a contribution; the indexer then deletes its blob
+    pub async fn withdraw_consent(&self, model: Pubkey, data_hash: [u8; 32]) -> Result<Signature, ClientError> {
+        let contributor = self.signer.pubkey();
+        let instructions = self
+            .program(program_ids().model_registry)
+            .request()
+            .accounts(model_registry::accounts::WithdrawConsent {
+                consent: consent_address(&model, &data_hash),
+                contributor,
+            })
+            .args(model_registry::instruction::WithdrawConsent {})
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        self.tx_builder
+            .send(instructions, &contributor, &[self.signer.as_ref()])
+            .await
+            .classify(ClientError::Transaction)
+    }
+
+    /// Open record `index` of an earlier contribution, checked against the
+    /// root in its contribution account. Reveals that record only.
+    pub async fn prove_inclusion(
+        &self,
+        openings: &Path,
+        data_hash: [u8; 32],
+        index: usize,
+    ) -> Result<InclusionProof, ClientError> {
+        let opening = ContributionOpening::load(openings, &data_hash).classify(ClientError::Input)?;
+        let model: Pubkey = opening.model.parse().classify(ClientError::Input)?;
+        let proof = opening.prove(index).classify(ClientError::Input)?;
+
+        let contribution: federation::Contribution = self
+            .program(program_ids().federation)
+            .account(contribution_address(&model, &data_hash))
+            .await
+            .classify(ClientError::Chain)?;
+        if !proof.verify(&contribution.records_root) {
+            return Err(ClientError::Proof(format!(
+                "opening does not match the records root of contribution {}",
+                hex::encode(data_hash)
+            )));
+        }
+        Ok(proof)
+    }
+
+    /// Revoke a contribution under the right to erasure. The chain closes it
+    /// and the indexer purges the stored blob; the local opening, which holds
+    /// the sanitized records, is deleted once the revocation lands.
+    pub async fn revoke_contribution(
+        &self,
+        openings: &Path,
+        model: Pubkey,
+        data_hash: [u8; 32],
+    ) -> Result<Signature, ClientError> {
+        let contributor = self.signer.pubkey();
+        let consent = consent_address(&model, &data_hash);
+        // Contributions from before consent records have none to withdraw
+        let has_consent = self.rpc_client.get_account(&consent).await.is_ok();
+        let instructions = self
+            .program(program_ids().model_registry)
+            .request()
+            .accounts(model_registry::accounts::RevokeContribution {
+                model_account: model,
+                contribution_account: contribution_address(&model, &data_hash),
+                revocation: revocation_address(&model, &data_hash),
+                consent: has_consent.then_some(consent),
+                contributor,
+                system_program: System::id(),
+                live: not_paused_accounts(),
+            })
+            .args(model_registry::instruction::RevokeContribution {})
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        let signature = self
+            .tx_builder
+            .send(instructions, &contributor, &[self.signer.as_ref()])
+            .await
+            .classify(ClientError::Transaction)?;
+        ContributionOpening::remove(openings, &data_hash).classify(ClientError::Storage)?;
+        Ok(signature)
+    }
+
+    /// As a proxy, re-encrypt the capsule beside `model_path` for the
+    /// provider named in `kfrag`, provided the model's owner signed `kfrag`
+    /// and the model's ACL still grants the provider access. Removing the
+    /// provider from the ACL revokes the delegation.
+    pub async fn reencrypt_data_key(&self, model_path: &Path, kfrag: &ReencryptionKey) -> Result<PathBuf, ClientError> {
+        let model: Pubkey = kfrag.model.parse().classify(ClientError::Input)?;
+        let provider: Pubkey = kfrag.provider.parse().classify(ClientError::Input)?;
+        let account = self.model_account(model).await?;
+        let signature = Signature::try_from(kfrag.signature.as_slice()).classify(ClientError::Input)?;
+        if !signature.verify(account.owner.as_ref(), &kfrag.signing_message()) {
+            return Err(ClientError::Input(format!("re-encryption key was not signed by the owner of model {model}")));
+        }
+        let granted = account.acl.get(&provider).is_some_and(|level| *level >= AccessLevel::InferenceOnly);
+        if provider != account.owner && !granted {
+            return Err(ClientError::Input(format!("{provider} has no access to model {model}")));
+        }
+        let capsule = Capsule::load(model_path).classify(ClientError::Storage)?;
+        let cfrag = kfrag.reencrypt(&capsule).classify(ClientError::Crypto)?;
+        cfrag.store(model_path).classify(ClientError::Storage)
+    }
+
+    /// Vote on a DAO proposal, with delegated weight when `on_behalf_of` is another holder
+    pub async fn vote(
+        &self,
+        proposal: Pubkey,
+        choice: u8,
+        weight: u64,
+        on_behalf_of: Option<Pubkey>,
+    ) -> Result<VoteReceipt, ClientError> {
+        let voter = self.signer.pubkey();
+        let owner = on_behalf_of.unwrap_or(voter);
+        let (vote_record, _) =
+            Pubkey::find_program_address(&[b"vote", proposal.as_ref(), owner.as_ref()], &program_ids().governance);
+        let (config, _) = Pubkey::find_program_address(&[b"config"], &program_ids().governance);
+        let delegation = (owner != voter)
+            .then(|| Pubkey::find_program_address(&[b"delegation", owner.as_ref()], &program_ids().governance).0);
+        // Weight is capped by what the owner of the voting power still holds
+        let voting: dao::VotingConfig = self
+            .program(program_ids().governance)
+            .account(config)
+            .await
+            .classify(ClientError::Chain)?;
+        let voting_tokens = spl_associated_token_account::get_associated_token_address(&owner, &voting.governance_mint);
+
+        // The program checks the voter's signature over the proposal key
+        let proof: [u8; 64] = self.signer.sign_message(proposal.as_ref()).into();
+
+        let instructions = self
+            .program(program_ids().governance)
+            .request()
+            .accounts(dao::accounts::CastVote {
+                proposal,
+                vote_record,
+                voter,
+                delegation,
+                voting_tokens,
+                config,
+                system_program: System::id(),
+            })
+            .args(dao::instruction::CastVote {
+                vote_choice: choice,
+                weight,
+                on_behalf_of: owner,
+                proof: proof.into(),
+            })
+            .instructions()
+            .classify(ClientError::Transaction)?;
+        let signature = send_or_export(
+            self.tx_builder,
+            &self.tx_mode,
+            &format!("Vote {choice} on proposal {proposal} with weight {weight}"),
+            instructions,
+            &voter,
+            &[self.signer.as_ref()],
+        )
+        .await
+        .classify(ClientError::Transaction)?;
+
+        Ok(VoteReceipt { proposal, vote_record, signature })
+    }
+}
+
+/// Client over `[network]`'s endpoints, failing over between them; keep the
+/// sender to run health checks or make more clients sharing its endpoint state
+pub fn connect_rpc(network: &NetworkConfig) -> (RpcClient, FailoverSender) {
+    let sender = FailoverSender::new(network.rpc_endpoints(), &network.failover);
+    (sender.client(CommitmentConfig::confirmed()), sender)
+}
+
+/// Crypto context for `[security]`, wrapping data keys when a master key or HSM is configured
+pub fn crypto_context(security: &SecurityConfig) -> Result<CryptoContext, ClientError> {
+    let mut crypto_ctx = CryptoContext::new(
+        &security.encryption_key().classify(ClientError::Crypto)?,
+        HardwareSecurity::from_config(security).classify(ClientError::Crypto)?,
+    );
+    if let Some(master) = &security.master_key {
+        crypto_ctx = crypto_ctx
+            .with_master_key(&master.key_id, master.version, &std::fs::read(&master.sealed_path)?)
+            .classify(ClientError::Crypto)?;
+    }
+    if let Some(hsm) = &security.hsm {
+        crypto_ctx = crypto_ctx.with_hsm(hsm).classify(ClientError::Crypto)?;
+    }
+    if let Some(seed) = &security.recipient_seed {
+        let recipient = RecipientSecret::load(seed, security.share_kem).classify(ClientError::Crypto)?;
+        crypto_ctx = crypto_ctx.with_recipient(recipient);
+    }
+    Ok(crypto_ctx)
+}
+
+/// Registry accounts for paying a fee in a whitelisted SPL token
+#[derive(Clone, Copy)]
+pub struct TokenFeeAccounts {
+    pub payment_config: Pubkey,
+    pub payer_token_account: Pubkey,
+    pub treasury_token_account: Pubkey,
+}
+
+pub fn token_fee_accounts(payer: &Pubkey, mint: &Pubkey) -> TokenFeeAccounts {
+    let (payment_config, _) = Pubkey::find_program_address(&[b"payment_config"], &program_ids().model_registry);
+    let (treasury_token_account, _) =
+        Pubkey::find_program_address(&[b"treasury", mint.as_ref()], &program_ids().model_registry);
+    TokenFeeAccounts {
+        payment_config,
+        payer_token_account: spl_associated_token_account::get_associated_token_address(payer, mint),
+        treasury_token_account,
+    }
+}
+
+/// Contribution account recording `data_hash` for `model`
+pub fn contribution_address(model: &Pubkey, data_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"contribution", model.as_ref(), data_hash], &program_ids().federation).0
+}
+
+/// Registry account holding the consent policy of a contribution
+pub fn consent_address(model: &Pubkey, data_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"consent", model.as_ref(), data_hash], &program_ids().model_registry).0
+}
+
+/// Registry account left behind when a contribution is revoked
+pub fn revocation_address(model: &Pubkey, data_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"revocation", model.as_ref(), data_hash], &program_ids().model_registry).0
+}
+
+/// Capability token letting `holder` request inference on `model` outside the ACL
+pub fn access_token_address(model: &Pubkey, holder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"access_token", model.as_ref(), holder.as_ref()], &program_ids().model_registry).0
+}
+
+/// Global pause flag checked by every mutating registry instruction
+pub fn not_paused_accounts() -> model_registry::accounts::NotPaused {
+    let (program_pause, _) = Pubkey::find_program_address(&[b"program_pause"], &program_ids().model_registry);
+    model_registry::accounts::NotPaused { program_pause }
+}
+
+/// Download and decrypt the model at `storage_uri`. A delta version is rebuilt
+/// from its chain of bases, each patch checked against the hashes it connects.
+/// Cached blobs stay pinned against eviction while the returned guards are held.
+pub async fn load_model<'a>(
+    model_cache: &ModelCache<'a>,
+    distributor: &ModelDistributor,
+    crypto_ctx: &CryptoContext,
+    storage_uri: &str,
+) -> Result<(Vec<ModelPin<'a>>, Vec<u8>), ClientError> {
+    // Walk back to the nearest full upload
+    let mut deltas = Vec::new();
+    let mut uri = storage_uri.to_string();
+    while let Some(manifest_cid) = DeltaManifest::cid_from_uri(&uri) {
+        if deltas.len() == MAX_CHAIN {
+            return Err(DeltaError::ChainTooLong).classify(ClientError::Storage);
+        }
+        let manifest_json = distributor
+            .fetch_document(manifest_cid, |bytes| DeltaManifest::parse(bytes).is_ok())
+            .await
+            .classify(ClientError::Storage)?;
+        let manifest = DeltaManifest::parse(&manifest_json).classify(ClientError::Storage)?;
+        uri = manifest.base_uri.clone();
+        deltas.push(manifest);
+    }
+
+    let mut pins = Vec::new();
+    let (pin, encrypted_model) = cached_download(model_cache, distributor, &uri).await?;
+    pins.extend(pin);
+    let model = crypto_ctx.decrypt_model(encrypted_model).classify(ClientError::Crypto)?;
+    let mut model = container::decompress(model).classify(ClientError::Storage)?;
+    for manifest in deltas.iter().rev() {
+        let (pin, encrypted_patch) = cached_download(model_cache, distributor, &manifest.patch_uri).await?;
+        pins.extend(pin);
+        let patch = crypto_ctx.decrypt_model(encrypted_patch).classify(ClientError::Crypto)?;
+        let patch = container::decompress(patch).classify(ClientError::Storage)?;
+        model = manifest.apply(&model, &patch).classify(ClientError::Storage)?;
+    }
+    Ok((pins, model))
+}
+
+/// Stored model blobs are immutable per URI, so a hash-verified cached copy stands in for a download.
+/// The model stays pinned against eviction while the returned guard is held.
+pub async fn cached_download<'a>(
+    model_cache: &ModelCache<'a>,
+    distributor: &ModelDistributor,
+    storage_uri: &str,
+) -> Result<(Option<ModelPin<'a>>, Vec<u8>), ClientError> {
+    if let Some((pin, blob)) = model_cache.get(storage_uri).classify(ClientError::Storage)? {
+        return Ok((Some(pin), blob));
+    }
+    let blob = match ErasureManifest::cid_from_uri(storage_uri) {
+        Some(manifest_cid) => distributor.fetch(manifest_cid).await.classify(ClientError::Storage)?,
+        // Deployed before erasure coding
+        None => download_model(storage_uri).await.classify(ClientError::Storage)?,
+    };
+    // A full or unwritable cache only costs a download next time
+    match model_cache.insert(storage_uri, &blob) {
+        Ok(pin) => Ok((Some(pin), blob)),
+        Err(e) => {
+            tracing::warn!(error = %e, "Model not cached");
+            Ok((None, blob))
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// client/src/output.rs
+
+//! `--output`: command results as a table for people, or JSON/YAML with
+//! stable field names for scripts. Results go to stdout and logs to stderr;
+//! failures are reported in the same format with a stable exit code.
+
+use crate::{config::ConfigError, ops::ClientError};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::{
+    error::Error,
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+/// Exit codes, by failure class. 2 is clap's usage error; `proof verify`
+/// keeps its own codes.
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_CONFIG: i32 = 3;
+pub const EXIT_INPUT: i32 = 4;
+pub const EXIT_CHAIN: i32 = 5;
+pub const EXIT_TRANSACTION: i32 = 6;
+pub const EXIT_STORAGE: i32 = 7;
+pub const EXIT_CRYPTO: i32 = 8;
+pub const EXIT_INFERENCE: i32 = 9;
+pub const EXIT_PROOF: i32 = 10;
+pub const EXIT_DATASET: i32 = 11;
+pub const EXIT_IO: i32 = 12;
+
+#[derive(Debug, Error)]
+pub enum OutputError {
+    #[error("JSON encoding failed: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("YAML encoding failed: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned text
+    #[default]
+    Table,
+    Json,
+    Yaml,
+}
+
+/// Failure as printed in JSON/YAML mode
+#[derive(Serialize)]
+struct ErrorReport {
+    error: ErrorBody,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    kind: &'static str,
+    message: String,
+    exit_code: i32,
+}
+
+impl OutputFormat {
+    /// Print `value` to stdout; tables are derived from its fields
+    pub fn print<T: Serialize + ?Sized>(self, value: &T) -> Result<(), OutputError> {
+        println!("{}", render(self, value)?);
+        Ok(())
+    }
+
+    /// [`Self::print`] with a hand-made table, for results with a better layout
+    pub fn print_with<T: Serialize + ?Sized>(self, value: &T, table: impl FnOnce(&T) -> String) -> Result<(), OutputError> {
+        match self {
+            OutputFormat::Table => println!("{}", table(value)),
+            _ => self.print(value)?,
+        }
+        Ok(())
+    }
+
+    /// Report `error` and return the process exit code for it
+    pub fn fail(self, error: &(dyn Error + 'static)) -> i32 {
+        let (kind, exit_code) = classify(error);
+        let report = ErrorReport { error: ErrorBody { kind, message: error.to_string(), exit_code } };
+        match self {
+            OutputFormat::Table => eprintln!("Error: {error}"),
+            _ => match render(self, &report) {
+                Ok(rendered) => println!("{rendered}"),
+                Err(_) => eprintln!("Error: {error}"),
+            },
+        }
+        exit_code
+    }
+}
+
+pub fn render<T: Serialize + ?Sized>(format: OutputFormat, value: &T) -> Result<String, OutputError> {
+    Ok(match format {
+        OutputFormat::Json => serde_json::to_string_pretty(value)?,
+        OutputFormat::Yaml => serde_yaml::to_string(value)?.trim_end().to_string(),
+        OutputFormat::Table => table(&serde_json::to_value(value)?),
+    })
+}
+
+/// Kind and exit code for the error classes scripts can act on
+fn classify(error: &(dyn Error + 'static)) -> (&'static str, i32) {
+    if let Some(error) = error.downcast_ref::<ClientError>() {
+        return match error {
+            ClientError::Input(_) => ("input", EXIT_INPUT),
+            ClientError::Chain(_) => ("chain", EXIT_CHAIN),
+            ClientError::Transaction(_) => ("transaction", EXIT_TRANSACTION),
+            ClientError::Storage(_) => ("storage", EXIT_STORAGE),
+            ClientError::Crypto(_) => ("crypto", EXIT_CRYPTO),
+            ClientError::Inference(_) => ("inference", EXIT_INFERENCE),
+            ClientError::Proof(_) => ("proof", EXIT_PROOF),
+            ClientError::Dataset(_) => ("dataset", EXIT_DATASET),
+            ClientError::Io(_) => ("io", EXIT_IO),
+        };
+    }
+    if error.is::<ConfigError>() {
+        ("config", EXIT_CONFIG)
+    } else if error.is::<std::io::Error>() {
+        ("io", EXIT_IO)
+    } else {
+        ("failure", EXIT_FAILURE)
+    }
+}
+
+/// A list of records becomes rows under their field names; a single record
+/// becomes one `field  value` line per field
+fn table(value: &Value) -> String {
+    match value {
+        Value::Array(rows) if rows.iter().all(Value::is_object) => {
+            let Some(Value::Object(first)) = rows.first() else {
+                return String::new();
+            };
+            let headers: Vec<&String> = first.keys().collect();
+            let cells: Vec<Vec<String>> =
+                rows.iter().map(|row| headers.iter().map(|key| cell(&row[key.as_str()])).collect()).collect();
+            let header: Vec<String> = headers.iter().map(|key| key.to_uppercase()).collect();
+            aligned(std::iter::once(header).chain(cells).collect())
+        }
+        Value::Object(fields) => aligned(fields.iter().map(|(key, value)| vec![key.clone(), cell(value)]).collect()),
+        other => cell(other),
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.iter().all(|item| !item.is_array() && !item.is_object()) => {
+            items.iter().map(cell).collect::<Vec<_>>().join(",")
+        }
+        other => other.to_string(),
+    }
+}
+
+fn aligned(rows: Vec<Vec<String>>) -> String {
+    let mut widths = Vec::new();
+    for row in &rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let padded: Vec<String> = row.iter().zip(&widths).map(|(c, w)| format!("{c:<w$}")).collect();
+            padded.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `serialize_with` for keys, signatures and the like: their display form
+pub fn display<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// [`display`] for optional values, `null` when absent
+pub fn display_opt<T: Display, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// `serialize_with` for timestamps: Unix seconds
+pub fn unix_secs<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tables() {
+        let rows = json!([
+            { "fee": 10, "id": "m1", "name": null, "tags": ["nlp", "en"] },
+            { "fee": 2500, "id": "model-two", "name": "Two", "tags": [] },
+        ]);
+        assert_eq!(
+            render(OutputFormat::Table, &rows).unwrap(),
+            "FEE   ID         NAME  TAGS\n10    m1         -     nlp,en\n2500  model-two  Two"
+        );
+
+        let record = json!({ "record_count": 3, "signature": "5xK" });
+        assert_eq!(render(OutputFormat::Table, &record).unwrap(), "record_count  3\nsignature     5xK");
+        assert_eq!(render(OutputFormat::Table, &json!([])).unwrap(), "");
+    }
+
+    #[test]
+    fn test_structured_formats_keep_field_names() {
+        let record = json!({ "model": "Mod1", "cache_hit": true });
+        let parsed: Value = serde_json::from_str(&render(OutputFormat::Json, &record).unwrap()).unwrap();
+        assert_eq!(parsed, record);
+        let parsed: Value = serde_yaml::from_str(&render(OutputFormat::Yaml, &record).unwrap()).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(classify(&ClientError::Transaction("blockhash expired".into())), ("transaction", EXIT_TRANSACTION));
+        assert_eq!(classify(&ConfigError::UnknownProfile("x".into())), ("config", EXIT_CONFIG));
+        let other: Box<dyn Error> = "network.indexer_url must be configured".into();
+        assert_eq!(classify(other.as_ref()), ("failure", EXIT_FAILURE));
+    }
+}
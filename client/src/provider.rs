@@ -0,0 +1,204 @@
+// client/src/provider.rs
+
+//! `scoria-provider`: fulfills on-chain inference requests for the models this
+//! node serves. A poller lists Pending requests over RPC; each is claimed with
+//! `claim_inference`, its input fetched from `input_uri` and checked against
+//! `input_hash`, and the proven result submitted with `fulfill_inference`.
+//!
+//! Claims lapse on-chain after `claim_secs`, so requests held by a worker that
+//! crashed reopen to every provider. Live claims of this provider's own key
+//! are resumed after a restart.
+
+use crate::{
+    config::{ProviderConfig, ServeConfig},
+    core::{cache::results::output_hash, inference::backend::TensorData},
+    ops::{ClientError, InferenceRuntime, ScoriaClient},
+    serve::warm::WarmModels,
+};
+use futures::stream::{self, StreamExt};
+use model_registry::instructions::inference::InferenceOutcome as Settlement;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+
+/// `Failure` code reported when the input is unreadable, fails the model's
+/// input schema or does not match `input_hash`; the requester is refunded
+pub const ERROR_BAD_INPUT: u32 = 1;
+
+pub struct Provider<'c, 'r> {
+    client: &'c ScoriaClient<'c>,
+    runtime: &'r InferenceRuntime<'r>,
+    config: ProviderConfig,
+    models: Vec<Pubkey>,
+    warm: WarmModels,
+    http: reqwest::Client,
+    gateway: String,
+    /// Requests queued or running here, so later polls skip them
+    in_flight: Mutex<HashSet<Pubkey>>,
+}
+
+impl<'c, 'r> Provider<'c, 'r> {
+    /// Worker for `models`; inputs on `ipfs://` are fetched through `gateway`
+    pub fn new(
+        client: &'c ScoriaClient<'c>,
+        runtime: &'r InferenceRuntime<'r>,
+        config: &ProviderConfig,
+        serve: &ServeConfig,
+        gateway: String,
+        models: Vec<Pubkey>,
+    ) -> Self {
+        Self {
+            client,
+            runtime,
+            config: config.clone(),
+            models,
+            warm: WarmModels::new(serve),
+            http: reqwest::Client::new(),
+            gateway,
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Poll and fulfill requests until the process stops
+    pub async fn run(&self) {
+        // A bounded queue: polls wait while every worker slot is busy
+        let (queue, jobs) = mpsc::channel(self.config.max_concurrent.max(1));
+        tokio::select! {
+            () = self.poll(queue) => {}
+            () = self.work(jobs) => {}
+        }
+    }
+
+    async fn poll(&self, queue: mpsc::Sender<(Pubkey, InferenceRequest)>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.poll_secs.max(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let now = unix_now();
+            for &model in &self.models {
+                let pending = match self.client.pending_inference_requests(model).await {
+                    Ok(pending) => pending,
+                    Err(e) => {
+                        tracing::warn!(%model, error = %e, "Failed to list pending inference requests");
+                        continue;
+                    }
+                };
+                for (key, request) in pending {
+                    if request.active_claimant(now).is_some_and(|claimant| claimant != self.client.pubkey()) {
+                        continue;
+                    }
+                    if !self.in_flight.lock().unwrap().insert(key) {
+                        continue;
+                    }
+                    if queue.send((key, request)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn work(&self, mut jobs: mpsc::Receiver<(Pubkey, InferenceRequest)>) {
+        stream::poll_fn(|cx| jobs.poll_recv(cx))
+            .for_each_concurrent(self.config.max_concurrent.max(1), |(key, request)| async move {
+                let outcome = match self.handle(key, &request).await {
+                    Ok(outcome) => outcome,
+                    Err(e) => {
+                        tracing::warn!(request = %key, model = %request.model, error = %e, "Inference request not settled; its claim will lapse");
+                        "error"
+                    }
+                };
+                crate::metrics::log_provider_request(request.model, outcome);
+                self.in_flight.lock().unwrap().remove(&key);
+            })
+            .await
+    }
+
+    /// Claim, run and settle one request; returns its metrics outcome
+    async fn handle(&self, key: Pubkey, request: &InferenceRequest) -> Result<&'static str, ClientError> {
+        // 1. Claim; losing the race to another provider is not an error
+        let started = Instant::now();
+        if let Err(e) = self.client.claim_inference(key, self.config.claim_secs).await {
+            let current = self.client.inference_request(key).await?;
+            let taken = current.active_claimant(unix_now()).is_some_and(|claimant| claimant != self.client.pubkey());
+            if current.status != InferenceStatus::Pending || taken {
+                tracing::debug!(request = %key, "Inference request taken by another provider");
+                return Ok("lost");
+            }
+            return Err(e);
+        }
+
+        // 2. Proven run on exactly the committed input. A bad input is the
+        // requester's fault and refunds them; anything else leaves the claim to lapse.
+        let result = async {
+            let inputs = self.fetch_input(request).await?;
+            let warm = self.warm.get(self.client, self.runtime, request.model).await?;
+            self.client.infer_warm(self.runtime, &warm, inputs, true).await
+        }
+        .await;
+        let settlement = match result {
+            Ok(outcome) => Settlement::Success { output_hash: output_hash(&outcome.output), zk_proof: outcome.proof },
+            Err(ClientError::Input(reason)) => {
+                tracing::warn!(request = %key, %reason, "Rejecting inference request input");
+                Settlement::Failure { error_code: ERROR_BAD_INPUT }
+            }
+            Err(e) => return Err(e),
+        };
+        if started.elapsed().as_secs() >= self.config.claim_secs.max(0) as u64 {
+            tracing::warn!(request = %key, "Claim lapsed before settlement; raise provider.claim_secs");
+        }
+
+        // 3. Settle
+        let failed = matches!(settlement, Settlement::Failure { .. });
+        let signature = self.client.fulfill_inference(key, settlement).await?;
+        tracing::info!(request = %key, model = %request.model, %signature, failed, "Inference request settled");
+        Ok(if failed { "failed" } else { "fulfilled" })
+    }
+
+    async fn fetch_input(&self, request: &InferenceRequest) -> Result<Vec<TensorData>, ClientError> {
+        let url = input_url(&request.input_uri, &self.gateway)?;
+        let storage = |e: reqwest::Error| ClientError::Storage(e.to_string());
+        let mut response = self.http.get(url).send().await.and_then(|r| r.error_for_status()).map_err(storage)?;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(storage)? {
+            if body.len() + chunk.len() > self.config.max_input_bytes {
+                return Err(ClientError::Input(format!("input exceeds {} bytes", self.config.max_input_bytes)));
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let inputs: Vec<TensorData> = serde_json::from_slice(&body).map_err(|e| ClientError::Input(e.to_string()))?;
+        if output_hash(&inputs) != request.input_hash {
+            return Err(ClientError::Input("input does not match the request's input hash".into()));
+        }
+        Ok(inputs)
+    }
+}
+
+/// `ipfs://` URIs resolve through the gateway; plain HTTPS is fetched as is
+fn input_url(uri: &str, gateway: &str) -> Result<String, ClientError> {
+    match uri.strip_prefix("ipfs://") {
+        Some(path) => Ok(format!("{}/ipfs/{path}", gateway.trim_end_matches('/'))),
+        None if uri.starts_with("https://") => Ok(uri.to_string()),
+        None => Err(ClientError::Input(format!("unsupported input URI {uri:?}"))),
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_uris_resolve_through_the_gateway() {
+        assert_eq!(input_url("ipfs://bafkrei/input.json", "https://ipfs.io/").unwrap(), "https://ipfs.io/ipfs/bafkrei/input.json");
+        assert_eq!(input_url("https://example.com/in.json", "https://ipfs.io").unwrap(), "https://example.com/in.json");
+        assert!(matches!(input_url("file:///etc/passwd", "https://ipfs.io"), Err(ClientError::Input(_))));
+    }
+}
@@ -0,0 +1,142 @@
+// client/src/serve/rate_limit.rs
+
+use crate::config::ServeConfig;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Caller name used when the server has no API keys configured
+pub const ANONYMOUS: &str = "anonymous";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Admission {
+    Allowed { key: String },
+    /// Unknown or missing bearer token
+    Unauthorized,
+    /// Bucket empty; retry after this long
+    Limited { key: String, retry_after: Duration },
+}
+
+struct Bucket {
+    name: String,
+    capacity: f64,
+    per_second: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl Bucket {
+    fn new(name: String, requests_per_minute: u32, burst: u32) -> Self {
+        let capacity = burst.max(1) as f64;
+        Self {
+            name,
+            capacity,
+            per_second: requests_per_minute as f64 / 60.0,
+            tokens: capacity,
+            refilled: Instant::now(),
+        }
+    }
+
+    fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.refilled = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        if self.per_second <= 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / self.per_second))
+    }
+}
+
+/// Token bucket per API key, keyed by the SHA2-256 of the bearer token
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<[u8; 32], Bucket>>,
+    /// No keys configured: everyone shares one bucket
+    open: bool,
+}
+
+impl RateLimiter {
+    pub fn new(config: &ServeConfig) -> Self {
+        let mut buckets: HashMap<[u8; 32], Bucket> = config
+            .api_keys
+            .iter()
+            .map(|key| {
+                let bucket = Bucket::new(
+                    key.name.clone(),
+                    key.requests_per_minute.unwrap_or(config.requests_per_minute),
+                    key.burst.unwrap_or(config.burst),
+                );
+                (key.key_sha256, bucket)
+            })
+            .collect();
+        let open = buckets.is_empty();
+        if open {
+            buckets.insert([0; 32], Bucket::new(ANONYMOUS.into(), config.requests_per_minute, config.burst));
+        }
+        Self { buckets: Mutex::new(buckets), open }
+    }
+
+    /// Admit one request carrying `token`, the bearer token if any
+    pub fn admit(&self, token: Option<&str>) -> Admission {
+        self.admit_at(token, Instant::now())
+    }
+
+    fn admit_at(&self, token: Option<&str>, now: Instant) -> Admission {
+        let id = match (self.open, token) {
+            (true, _) => [0; 32],
+            (false, Some(token)) => Sha256::digest(token.as_bytes()).into(),
+            (false, None) => return Admission::Unauthorized,
+        };
+        let mut buckets = self.buckets.lock().unwrap();
+        let Some(bucket) = buckets.get_mut(&id) else {
+            return Admission::Unauthorized;
+        };
+        match bucket.take(now) {
+            Ok(()) => Admission::Allowed { key: bucket.name.clone() },
+            Err(retry_after) => Admission::Limited { key: bucket.name.clone(), retry_after },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiKeyConfig;
+
+    #[test]
+    fn test_keys_get_their_own_buckets() {
+        let config = ServeConfig {
+            api_keys: vec![ApiKeyConfig {
+                name: "acme".into(),
+                key_sha256: Sha256::digest(b"secret").into(),
+                requests_per_minute: Some(60),
+                burst: Some(2),
+            }],
+            ..Default::default()
+        };
+        let limiter = RateLimiter::new(&config);
+        let start = Instant::now();
+
+        assert_eq!(limiter.admit_at(None, start), Admission::Unauthorized);
+        assert_eq!(limiter.admit_at(Some("guess"), start), Admission::Unauthorized);
+        assert_eq!(limiter.admit_at(Some("secret"), start), Admission::Allowed { key: "acme".into() });
+        assert_eq!(limiter.admit_at(Some("secret"), start), Admission::Allowed { key: "acme".into() });
+        assert!(matches!(limiter.admit_at(Some("secret"), start), Admission::Limited { .. }));
+        // One token per second refills
+        let later = start + Duration::from_secs(1);
+        assert_eq!(limiter.admit_at(Some("secret"), later), Admission::Allowed { key: "acme".into() });
+    }
+
+    #[test]
+    fn test_without_keys_everyone_shares_the_default_limit() {
+        let limiter = RateLimiter::new(&ServeConfig { burst: 1, ..Default::default() });
+        let now = Instant::now();
+        assert_eq!(limiter.admit_at(None, now), Admission::Allowed { key: ANONYMOUS.into() });
+        assert!(matches!(limiter.admit_at(Some("anything"), now), Admission::Limited { .. }));
+    }
+}
@@ -0,0 +1,246 @@
+// client/src/serve/server.rs
+
+//! `scoria-cli serve`: a local HTTP API over warm models, turning the machine
+//! into a provider node.
+//!
+//! `POST /v1/models/{id}/infer` takes `{"inputs": [tensors], "proof": true,
+//! "request": "<InferenceRequest>"}`. `proof` asks for a proof of execution;
+//! `request` names an on-chain request to settle with the result, which
+//! implies a proof; its `input_hash` must be the tensor commitment of
+//! `inputs` (`results::output_hash`). Callers authenticate with
+//! `Authorization: Bearer <key>`.
+//!
+//! Handlers only admit requests and queue them. A worker running in the CLI's
+//! scope drains the queue, at most `max_concurrent` inferences at once.
+
+use super::{
+    rate_limit::{Admission, RateLimiter},
+    warm::WarmModels,
+};
+use crate::{
+    config::ServeConfig,
+    core::{cache::results::output_hash, inference::backend::TensorData, postprocessing::pipeline::Postprocessed},
+    ops::{ClientError, InferenceRuntime, ScoriaClient},
+};
+use axum::{
+    extract::{DefaultBodyLimit, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::{net::SocketAddr, sync::Arc, time::Instant};
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+
+#[derive(Debug, Error)]
+pub enum ServeError {
+    #[error("Failed to listen on {addr}: {source}")]
+    Bind { addr: SocketAddr, source: std::io::Error },
+    #[error("Server error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InferRequest {
+    pub inputs: Vec<TensorData>,
+    #[serde(default)]
+    pub proof: bool,
+    /// On-chain `InferenceRequest` account this call settles
+    #[serde(default)]
+    pub request: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InferResponse {
+    pub model: String,
+    pub outputs: Vec<TensorData>,
+    /// Labelled predictions, when the model's catalog declares post-processing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predictions: Option<Postprocessed>,
+    /// Base64 proof of execution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+    /// Signature of the transaction that settled `request`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulfillment: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+struct Job {
+    model_id: Pubkey,
+    inputs: Vec<TensorData>,
+    prove: bool,
+    settle: Option<Pubkey>,
+    reply: oneshot::Sender<Result<InferResponse, ClientError>>,
+}
+
+struct AppState {
+    jobs: mpsc::Sender<Job>,
+    limiter: RateLimiter,
+}
+
+/// Serve until the listener fails
+pub async fn serve(
+    config: &ServeConfig,
+    client: &ScoriaClient<'_>,
+    runtime: &InferenceRuntime<'_>,
+) -> Result<(), ServeError> {
+    let (jobs, queue) = mpsc::channel(config.queue_depth.max(1));
+    let state = Arc::new(AppState { jobs, limiter: RateLimiter::new(config) });
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/v1/models/:id/infer", post(infer))
+        .layer(DefaultBodyLimit::max(config.max_request_bytes))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(config.listen)
+        .await
+        .map_err(|source| ServeError::Bind { addr: config.listen, source })?;
+    if config.api_keys.is_empty() && !config.listen.ip().is_loopback() {
+        tracing::warn!(listen = %config.listen, "No API keys configured; all callers share the anonymous limit");
+    }
+    tracing::info!(listen = %config.listen, max_concurrent = config.max_concurrent, "Serving inference");
+
+    let worker = Worker { client, runtime, warm: WarmModels::new(config) };
+    tokio::select! {
+        served = axum::serve(listener, app) => served?,
+        () = worker.run(queue, config.max_concurrent) => {}
+    }
+    Ok(())
+}
+
+async fn infer(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<InferRequest>,
+) -> Response {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let key = match state.limiter.admit(token) {
+        Admission::Allowed { key } => key,
+        Admission::Unauthorized => {
+            crate::metrics::log_served_request("unknown", "unauthorized");
+            return (StatusCode::UNAUTHORIZED, "Missing or unknown API key").into_response();
+        }
+        Admission::Limited { key, retry_after } => {
+            crate::metrics::log_served_request(&key, "limited");
+            let retry_after = retry_after.as_secs().max(1).to_string();
+            return (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, retry_after)], "Rate limit exceeded").into_response();
+        }
+    };
+
+    let Ok(model_id) = id.parse::<Pubkey>() else {
+        return (StatusCode::BAD_REQUEST, "Model ID is not a public key").into_response();
+    };
+    let settle = match request.request.as_deref().map(str::parse::<Pubkey>).transpose() {
+        Ok(settle) => settle,
+        Err(_) => return (StatusCode::BAD_REQUEST, "`request` is not a public key").into_response(),
+    };
+
+    let (reply, response) = oneshot::channel();
+    let job = Job { model_id, inputs: request.inputs, prove: request.proof, settle, reply };
+    if state.jobs.try_send(job).is_err() {
+        crate::metrics::log_served_request(&key, "busy");
+        return (StatusCode::SERVICE_UNAVAILABLE, "Inference queue is full").into_response();
+    }
+    match response.await {
+        Ok(Ok(body)) => {
+            crate::metrics::log_served_request(&key, "ok");
+            Json(body).into_response()
+        }
+        Ok(Err(e)) => {
+            crate::metrics::log_served_request(&key, "error");
+            tracing::warn!(%key, model = %model_id, error = %e, "Inference request failed");
+            (status(&e), e.to_string()).into_response()
+        }
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Inference worker stopped").into_response(),
+    }
+}
+
+fn status(error: &ClientError) -> StatusCode {
+    match error {
+        ClientError::Input(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        ClientError::Chain(_) | ClientError::Storage(_) | ClientError::Transaction(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+struct Worker<'c, 'r> {
+    client: &'c ScoriaClient<'c>,
+    runtime: &'r InferenceRuntime<'r>,
+    warm: WarmModels,
+}
+
+impl Worker<'_, '_> {
+    /// Runs until every sender is gone, i.e. until the server stops
+    async fn run(&self, mut queue: mpsc::Receiver<Job>, max_concurrent: usize) {
+        stream::poll_fn(|cx| queue.poll_recv(cx))
+            .for_each_concurrent(max_concurrent.max(1), |job| async move {
+                let result = self.handle(job.model_id, job.inputs, job.prove, job.settle).await;
+                // The caller may have disconnected; the work is done either way
+                let _ = job.reply.send(result);
+            })
+            .await
+    }
+
+    async fn handle(
+        &self,
+        model_id: Pubkey,
+        inputs: Vec<TensorData>,
+        prove: bool,
+        settle: Option<Pubkey>,
+    ) -> Result<InferResponse, ClientError> {
+        let started = Instant::now();
+
+        // Only settle requests for this model and exactly this input, so the
+        // result commits to what the requester paid for
+        if let Some(request) = settle {
+            let pending = self.client.inference_request(request).await?;
+            if pending.model != model_id {
+                return Err(ClientError::Input(format!("request {request} is for model {}", pending.model)));
+            }
+            if pending.status != InferenceStatus::Pending {
+                return Err(ClientError::Input(format!("request {request} is no longer pending")));
+            }
+            if pending.input_hash != output_hash(&inputs) {
+                return Err(ClientError::Input(format!("inputs do not match the input hash of request {request}")));
+            }
+        }
+
+        let warm = self.warm.get(self.client, self.runtime, model_id).await?;
+        let outcome = self.client.infer_warm(self.runtime, &warm, inputs, prove || settle.is_some()).await?;
+
+        // Failures are not reported on-chain: a bad input from this caller must
+        // not refund a request someone else opened
+        let fulfillment = match settle {
+            Some(request) => {
+                let result = model_registry::instructions::inference::InferenceOutcome::Success {
+                    output_hash: output_hash(&outcome.output),
+                    zk_proof: outcome.proof.clone(),
+                };
+                let signature = self.client.fulfill_inference(request, result).await?;
+                tracing::info!(%request, model = %model_id, %signature, "Inference request fulfilled");
+                Some(signature.to_string())
+            }
+            None => None,
+        };
+
+        Ok(InferResponse {
+            model: model_id.to_string(),
+            proof: (!outcome.proof.is_empty()).then(|| BASE64.encode(&outcome.proof)),
+            outputs: outcome.output,
+            predictions: outcome.postprocessed,
+            fulfillment,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+}
@@ -0,0 +1,105 @@
+// client/src/serve/warm.rs
+
+//! Decrypted models held in memory between requests. Each model loads once
+//! however many requests race for it, is re-checked against its account every
+//! `refresh_secs` so executed version updates are picked up, and the least
+//! recently used models are dropped once `warm_bytes` is exceeded.
+
+use crate::config::ServeConfig;
+use crate::ops::{ClientError, InferenceRuntime, ScoriaClient, WarmModel};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+struct Entry {
+    model: Arc<OnceCell<Arc<WarmModel>>>,
+    last_used: Instant,
+    checked: Instant,
+}
+
+impl Entry {
+    fn new(now: Instant) -> Self {
+        Self { model: Arc::new(OnceCell::new()), last_used: now, checked: now }
+    }
+}
+
+pub struct WarmModels {
+    entries: Mutex<HashMap<Pubkey, Entry>>,
+    max_bytes: u64,
+    refresh: Duration,
+}
+
+impl WarmModels {
+    pub fn new(config: &ServeConfig) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            max_bytes: config.warm_bytes,
+            refresh: Duration::from_secs(config.refresh_secs),
+        }
+    }
+
+    /// The warm copy of `model_id`, loading it on first use
+    pub async fn get(
+        &self,
+        client: &ScoriaClient<'_>,
+        runtime: &InferenceRuntime<'_>,
+        model_id: Pubkey,
+    ) -> Result<Arc<WarmModel>, ClientError> {
+        let now = Instant::now();
+        let (cell, stale) = {
+            let mut entries = self.entries.lock().unwrap();
+            let entry = entries.entry(model_id).or_insert_with(|| Entry::new(now));
+            entry.last_used = now;
+            (entry.model.clone(), now.duration_since(entry.checked) > self.refresh)
+        };
+
+        let cell = match cell.get() {
+            Some(warm) if stale => {
+                let account = client.model_account(model_id).await?;
+                let current = warm.account.model_hash == account.model_hash && warm.account.storage_uri == account.storage_uri;
+                let mut entries = self.entries.lock().unwrap();
+                let entry = entries.entry(model_id).or_insert_with(|| Entry::new(now));
+                entry.checked = now;
+                if !current && Arc::ptr_eq(&entry.model, &cell) {
+                    tracing::info!(%model_id, "New model version active; reloading");
+                    entry.model = Arc::new(OnceCell::new());
+                }
+                entry.model.clone()
+            }
+            _ => cell,
+        };
+
+        let warm = cell
+            .get_or_try_init(|| async {
+                let started = Instant::now();
+                let warm = client.warm_model(runtime, model_id).await?;
+                tracing::info!(%model_id, bytes = warm.size(), elapsed_ms = started.elapsed().as_millis() as u64, "Model warmed");
+                Ok::<_, ClientError>(Arc::new(warm))
+            })
+            .await?
+            .clone();
+        self.evict(model_id);
+        Ok(warm)
+    }
+
+    /// Drop least recently used models, never `keep`, until under budget
+    fn evict(&self, keep: Pubkey) {
+        let mut entries = self.entries.lock().unwrap();
+        let size = |entry: &Entry| entry.model.get().map_or(0, |warm| warm.size() as u64);
+        let mut total: u64 = entries.values().map(size).sum();
+        while total > self.max_bytes {
+            let victim = entries
+                .iter()
+                .filter(|(id, entry)| **id != keep && entry.model.initialized())
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| *id);
+            let Some(victim) = victim else { break };
+            let entry = entries.remove(&victim).unwrap();
+            total -= size(&entry);
+            tracing::info!(model = %victim, bytes = size(&entry), "Warm model evicted");
+        }
+        crate::metrics::log_warm_models(entries.values().filter(|e| e.model.initialized()).count(), total);
+    }
+}
@@ -0,0 +1,63 @@
+// client/src/telemetry.rs
+
+use std::error::Error;
+use tracing_subscriber::{filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+const SERVICE_NAME: &str = "scoria-cli";
+
+/// Flushes buffered spans on drop; hold it for the life of `main`
+pub struct TelemetryGuard {
+    #[cfg_attr(not(feature = "telemetry"), allow(dead_code))]
+    exporting: bool,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "telemetry")]
+        if self.exporting {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Install the global subscriber, exporting spans over OTLP when `otlp_endpoint` is set
+pub fn init(otlp_endpoint: Option<&str>) -> Result<TelemetryGuard, Box<dyn Error>> {
+    let registry = tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        // stdout carries command results, e.g. `--output json`
+        .with(fmt::layer().with_writer(std::io::stderr));
+
+    #[cfg(feature = "telemetry")]
+    if let Some(endpoint) = otlp_endpoint {
+        registry.with(otlp_layer(endpoint)?).init();
+        tracing::info!(endpoint, "Exporting traces over OTLP");
+        return Ok(TelemetryGuard { exporting: true });
+    }
+
+    registry.init();
+    if otlp_endpoint.is_some() && !cfg!(feature = "telemetry") {
+        tracing::warn!("monitoring.otlp_endpoint is set but this build lacks the `telemetry` feature");
+    }
+    Ok(TelemetryGuard { exporting: false })
+}
+
+#[cfg(feature = "telemetry")]
+fn otlp_layer<S>(endpoint: &str) -> Result<impl tracing_subscriber::Layer<S>, Box<dyn Error>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace, Resource};
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new("service.name", SERVICE_NAME)])),
+        )
+        .install_batch(runtime::Tokio)?;
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
@@ -0,0 +1,284 @@
+// client/src/wallet/fees.rs
+
+use super::nonce::{DurableNonce, NonceError};
+use crate::config::{FeeMode, PriorityFeeConfig};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+    rpc_request::RpcError,
+};
+use solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{Signer, SignerError},
+    transaction::Transaction,
+};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Runtime ceiling per transaction
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+/// Used when simulation fails; matches the runtime's default per-instruction budget
+const FALLBACK_UNITS_PER_IX: u32 = 200_000;
+
+#[derive(Debug, Error)]
+pub enum FeeError {
+    #[error("RPC error: {0}")]
+    Rpc(Box<ClientError>),
+    #[error("Transaction not confirmed after {attempts} attempts at up to {last_price} micro-lamports/CU")]
+    Exhausted { attempts: u32, last_price: u64 },
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    #[error("Signing failed: {0}")]
+    Signer(#[from] SignerError),
+}
+
+impl From<ClientError> for FeeError {
+    fn from(e: ClientError) -> Self {
+        FeeError::Rpc(Box::new(e))
+    }
+}
+
+/// Attaches compute-budget instructions and bids priority fees for every send
+pub struct TxBuilder<'a> {
+    rpc_client: &'a RpcClient,
+    config: PriorityFeeConfig,
+    nonce: Option<DurableNonce>,
+}
+
+impl<'a> TxBuilder<'a> {
+    pub fn new(rpc_client: &'a RpcClient, config: PriorityFeeConfig) -> Self {
+        Self { rpc_client, config, nonce: None }
+    }
+
+    /// Send against a durable nonce instead of a recent blockhash, for
+    /// transactions that are built before a long proving job
+    pub fn with_nonce(mut self, nonce: DurableNonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn nonce(&self) -> Option<&DurableNonce> {
+        self.nonce.as_ref()
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        self.rpc_client
+    }
+
+    /// Simulated compute units plus the configured margin
+    pub async fn estimate_compute_units(&self, instructions: &[Instruction], payer: &Pubkey) -> u32 {
+        let instructions = strip_budget(instructions);
+        let mut probe = vec![ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS)];
+        probe.extend(instructions.iter().cloned());
+        let tx = Transaction::new_unsigned(Message::new(&probe, Some(payer)));
+
+        let simulated = self
+            .rpc_client
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .ok()
+            .and_then(|r| r.value.units_consumed);
+
+        match simulated {
+            Some(units) => with_margin(units, self.config.compute_unit_margin),
+            None => {
+                tracing::debug!("Simulation failed, using default compute budget");
+                (FALLBACK_UNITS_PER_IX * instructions.len() as u32).min(MAX_COMPUTE_UNITS)
+            }
+        }
+    }
+
+    /// Starting bid in micro-lamports per compute unit
+    pub async fn priority_fee(&self, instructions: &[Instruction]) -> u64 {
+        match self.config.mode {
+            FeeMode::Fixed => self.config.micro_lamports,
+            FeeMode::Dynamic => {
+                let writable = writable_accounts(instructions);
+                let recent = match self.rpc_client.get_recent_prioritization_fees(&writable).await {
+                    Ok(fees) => fees.into_iter().map(|f| f.prioritization_fee).collect::<Vec<_>>(),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Prioritization fee lookup failed");
+                        Vec::new()
+                    }
+                };
+                percentile(&recent, self.config.percentile)
+                    .unwrap_or(0)
+                    .clamp(self.config.micro_lamports, self.config.max_micro_lamports)
+            }
+        }
+    }
+
+    /// Instructions with compute-budget limit and price prepended
+    pub async fn prepare(&self, instructions: Vec<Instruction>, payer: &Pubkey) -> Vec<Instruction> {
+        let units = self.estimate_compute_units(&instructions, payer).await;
+        let price = self.priority_fee(&instructions).await;
+        with_budget(&instructions, units, price)
+    }
+
+    /// Sign and send, escalating the fee while the network is congested
+    #[tracing::instrument(name = "rpc.send_transaction", skip_all, fields(%payer, signature = tracing::field::Empty))]
+    pub async fn send(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: &Pubkey,
+        signers: &[&dyn Signer],
+    ) -> Result<Signature, FeeError> {
+        let instructions = strip_budget(&instructions);
+        // The nonce advance is budgeted too, but kept out of `instructions` so it can lead the message
+        let units = match &self.nonce {
+            Some(nonce) => {
+                let mut probe = vec![nonce.advance_instruction()];
+                probe.extend(instructions.iter().cloned());
+                self.estimate_compute_units(&probe, payer).await
+            }
+            None => self.estimate_compute_units(&instructions, payer).await,
+        };
+        let mut price = self.priority_fee(&instructions).await;
+
+        for attempt in 1..=self.config.max_retries + 1 {
+            // A dropped attempt leaves the nonce unadvanced, so it is re-read like a blockhash
+            let (message_ixs, blockhash) = match &self.nonce {
+                Some(nonce) => {
+                    let mut ixs = vec![nonce.advance_instruction()];
+                    ixs.extend(with_budget(&instructions, units, price));
+                    (ixs, nonce.blockhash(self.rpc_client).await?)
+                }
+                None => (with_budget(&instructions, units, price), self.rpc_client.get_latest_blockhash().await?),
+            };
+            let mut tx = Transaction::new_unsigned(Message::new(&message_ixs, Some(payer)));
+            tx.try_sign(signers, blockhash)?;
+
+            match self.rpc_client.send_and_confirm_transaction(&tx).await {
+                Ok(sig) => {
+                    // Lets the indexer's span for the same signature be found
+                    tracing::Span::current().record("signature", tracing::field::display(sig));
+                    tracing::debug!(%sig, units, price, attempt, "Transaction confirmed");
+                    return Ok(sig);
+                }
+                Err(e) if is_congestion(&e) && attempt <= self.config.max_retries => {
+                    let next = escalate(price, self.config.escalation, self.config.max_micro_lamports);
+                    tracing::warn!(attempt, price, next, error = %e, "Transaction dropped, raising priority fee");
+                    price = next;
+                    tokio::time::sleep(Duration::from_millis(self.config.retry_backoff_ms)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(FeeError::Exhausted { attempts: self.config.max_retries + 1, last_price: price })
+    }
+}
+
+fn with_margin(units: u64, margin: f64) -> u32 {
+    ((units as f64 * margin).ceil() as u64).min(MAX_COMPUTE_UNITS as u64) as u32
+}
+
+/// Nearest-rank percentile of `values`
+fn percentile(values: &[u64], pct: u8) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let rank = (pct.min(100) as usize * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+fn escalate(price: u64, factor: f64, max: u64) -> u64 {
+    ((price.max(1) as f64 * factor).ceil() as u64).min(max)
+}
+
+/// Drop caller-supplied budget instructions so ours are the only ones
+fn strip_budget(instructions: &[Instruction]) -> Vec<Instruction> {
+    instructions
+        .iter()
+        .filter(|ix| ix.program_id != compute_budget::id())
+        .cloned()
+        .collect()
+}
+
+pub(crate) fn with_budget(instructions: &[Instruction], units: u32, price: u64) -> Vec<Instruction> {
+    let mut out = vec![ComputeBudgetInstruction::set_compute_unit_limit(units)];
+    if price > 0 {
+        out.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    out.extend(strip_budget(instructions));
+    out
+}
+
+fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter().filter(|a| a.is_writable).map(|a| a.pubkey))
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+    // RPC accepts at most 128 addresses
+    accounts.truncate(128);
+    accounts
+}
+
+/// Errors where resending with a higher fee can help
+fn is_congestion(e: &ClientError) -> bool {
+    match e.kind() {
+        ClientErrorKind::RpcError(RpcError::ForUser(msg)) => msg.contains("unable to confirm transaction"),
+        ClientErrorKind::Reqwest(err) => err.is_timeout(),
+        ClientErrorKind::TransactionError(err) => {
+            matches!(err, solana_sdk::transaction::TransactionError::BlockhashNotFound)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::system_instruction;
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 75), None);
+        assert_eq!(percentile(&[5], 75), Some(5));
+        assert_eq!(percentile(&[40, 10, 30, 20], 50), Some(20));
+        assert_eq!(percentile(&[40, 10, 30, 20], 75), Some(30));
+        assert_eq!(percentile(&[40, 10, 30, 20], 100), Some(40));
+        assert_eq!(percentile(&[40, 10, 30, 20], 0), Some(10));
+    }
+
+    #[test]
+    fn test_escalation_is_capped() {
+        assert_eq!(escalate(0, 1.5, 1_000), 2);
+        assert_eq!(escalate(100, 1.5, 1_000), 150);
+        assert_eq!(escalate(900, 1.5, 1_000), 1_000);
+        assert_eq!(with_margin(2_000_000, 1.2), MAX_COMPUTE_UNITS);
+    }
+
+    #[test]
+    fn test_budget_replaces_existing_instructions() {
+        let from = Pubkey::new_unique();
+        let ixs = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS),
+            system_instruction::transfer(&from, &Pubkey::new_unique(), 1),
+        ];
+        let out = with_budget(&ixs, 50_000, 10);
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0], ComputeBudgetInstruction::set_compute_unit_limit(50_000));
+        assert_eq!(out[1], ComputeBudgetInstruction::set_compute_unit_price(10));
+        assert_eq!(out[2].program_id, solana_sdk::system_program::id());
+
+        assert_eq!(with_budget(&ixs, 50_000, 0).len(), 2);
+        assert_eq!(writable_accounts(&ixs).len(), 2);
+    }
+}
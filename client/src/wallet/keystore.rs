@@ -0,0 +1,416 @@
+// client/src/wallet/keystore.rs
+
+//! Key files behind `wallet.path`: a plain Solana JSON keypair, or a keystore
+//! holding it under a passphrase (Argon2id key derivation, AES-256-GCM or
+//! XChaCha20-Poly1305 bound to the public key). Keystores also hold other secrets, e.g. the data
+//! encryption key. Also seed phrase handling for `wallet new/import`.
+
+use super::session::SessionClient;
+use crate::core::model_loader::{
+    aes::{Aes256GcmProvider, AesError},
+    format::{CipherHeader, CipherId, LegacyLayout},
+};
+use bip39::{Language, Mnemonic, MnemonicType};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    derivation_path::DerivationPath,
+    pubkey::Pubkey,
+    signature::{generate_seed_from_seed_phrase_and_passphrase, keypair_from_seed, Keypair},
+    signer::{keypair::keypair_from_seed_and_derivation_path, Signer},
+};
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Passphrase for keystores when set, e.g. for unattended services
+pub const PASSPHRASE_ENV: &str = "SCORIA_WALLET_PASSPHRASE";
+
+/// BIP44 `account/change` that `wallet new` derives at, as Phantom and Solflare do
+pub const DEFAULT_DERIVATION_PATH: &str = "0/0";
+
+const VERSION: u32 = 1;
+const KDF: &str = "argon2id";
+/// Associated data prefix for labelled secrets, keeping them apart from keypairs
+const SECRET_DOMAIN: &[u8] = b"scoria-secret:";
+
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    #[error("Key file {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Key file {path} is neither a keypair nor a keystore: {reason}")]
+    Format { path: PathBuf, reason: String },
+    #[error("Keystore format {kdf}/{cipher} v{version} is not supported")]
+    Unsupported { version: u32, kdf: String, cipher: String },
+    #[error("Wrong passphrase, or the keystore was altered")]
+    Passphrase,
+    #[error("Keystore holds `{0}`, not a wallet key")]
+    NotWallet(String),
+    #[error("Keystore holds a wallet key, not a secret")]
+    NotSecret,
+    #[error("Keystore encryption failed: {0}")]
+    Crypto(#[from] AesError),
+    #[error("Invalid seed phrase: {0}")]
+    SeedPhrase(String),
+    #[error("Invalid derivation path: {0}")]
+    DerivationPath(String),
+    #[error("Reading a secret: {0}")]
+    Prompt(io::Error),
+}
+
+/// What a key file holds
+pub enum KeyFile {
+    Plain(Keypair),
+    Encrypted(Keystore),
+}
+
+/// Passphrase-protected keypair or labelled secret, as stored on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// Wallet public key, readable without the passphrase, e.g. for `wallet balance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    /// What a non-wallet keystore holds, e.g. `encryption_key`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub kdf: String,
+    pub cipher: String,
+    /// Cipher header (costs, salt, nonce), then the sealed keypair or secret
+    #[serde(with = "hex::serde")]
+    ciphertext: Vec<u8>,
+}
+
+impl Keystore {
+    pub fn seal(keypair: &Keypair, passphrase: &str) -> Result<Self, KeystoreError> {
+        let pubkey = keypair.pubkey();
+        let secret = Zeroizing::new(keypair.to_bytes());
+        let ciphertext = Aes256GcmProvider::new().encrypt(secret.as_slice(), passphrase, pubkey.as_ref())?;
+        Ok(Self { pubkey: Some(pubkey.to_string()), label: None, ..Self::sealed(ciphertext) })
+    }
+
+    /// Keystore for anything other than a keypair; `label` says what it is
+    pub fn seal_secret(label: &str, secret: &[u8], passphrase: &str) -> Result<Self, KeystoreError> {
+        let ciphertext = Aes256GcmProvider::new().encrypt(secret, passphrase, &secret_aad(label))?;
+        Ok(Self { pubkey: None, label: Some(label.to_string()), ..Self::sealed(ciphertext) })
+    }
+
+    pub fn open(&self, passphrase: &str) -> Result<Keypair, KeystoreError> {
+        self.check_format()?;
+        let Some(pubkey) = &self.pubkey else {
+            return Err(KeystoreError::NotWallet(self.label.clone().unwrap_or_default()));
+        };
+        let pubkey = Pubkey::from_str(pubkey).map_err(|_| KeystoreError::Passphrase)?;
+        let secret = Zeroizing::new(
+            Aes256GcmProvider::new()
+                .decrypt(&self.ciphertext, passphrase, pubkey.as_ref())
+                .map_err(|_| KeystoreError::Passphrase)?,
+        );
+        let keypair = Keypair::from_bytes(&secret).map_err(|_| KeystoreError::Passphrase)?;
+        if keypair.pubkey() != pubkey {
+            return Err(KeystoreError::Passphrase);
+        }
+        Ok(keypair)
+    }
+
+    pub fn open_secret(&self, passphrase: &str) -> Result<Zeroizing<Vec<u8>>, KeystoreError> {
+        self.check_format()?;
+        let (None, Some(label)) = (&self.pubkey, &self.label) else {
+            return Err(KeystoreError::NotSecret);
+        };
+        Aes256GcmProvider::new()
+            .decrypt(&self.ciphertext, passphrase, &secret_aad(label))
+            .map(Zeroizing::new)
+            .map_err(|_| KeystoreError::Passphrase)
+    }
+
+    /// Stable name for this keystore's contents, e.g. in an unlocked session
+    pub fn id(&self) -> String {
+        hex::encode(&blake3::hash(&self.ciphertext).as_bytes()[..16])
+    }
+
+    /// [`Self::open`], from the unlocked session when there is one, else by
+    /// asking for the passphrase
+    pub fn unlock(&self, path: &Path) -> Result<Keypair, KeystoreError> {
+        if let Some(secret) = SessionClient::default().get(&self.id()) {
+            if let Ok(keypair) = Keypair::from_bytes(&secret) {
+                if self.pubkey.as_deref() == Some(keypair.pubkey().to_string().as_str()) {
+                    return Ok(keypair);
+                }
+            }
+        }
+        let passphrase = passphrase(&format!("Passphrase for {}: ", path.display()))?;
+        let keypair = self.open(&passphrase)?;
+        self.rekey(path, || Self::seal(&keypair, &passphrase));
+        Ok(keypair)
+    }
+
+    /// [`Self::open_secret`], from the unlocked session when there is one
+    pub fn unlock_secret(&self, path: &Path) -> Result<Zeroizing<Vec<u8>>, KeystoreError> {
+        if let Some(secret) = SessionClient::default().get(&self.id()) {
+            return Ok(secret);
+        }
+        let passphrase = passphrase(&format!("Passphrase for {}: ", path.display()))?;
+        let secret = self.open_secret(&passphrase)?;
+        let label = self.label.as_deref().unwrap_or_default();
+        self.rekey(path, || Self::seal_secret(label, &secret, &passphrase));
+        Ok(secret)
+    }
+
+    /// Replace the file at `path` with `reseal()` when this keystore was
+    /// sealed with another cipher or Argon2 costs than the installed ones. The contents
+    /// are already open, so failing only warns.
+    fn rekey(&self, path: &Path, reseal: impl FnOnce() -> Result<Self, KeystoreError>) {
+        if !Aes256GcmProvider::new().needs_rekey(&self.ciphertext) {
+            return;
+        }
+        let staged = path.with_extension("rekey");
+        let rename =
+            || std::fs::rename(&staged, path).map_err(|source| KeystoreError::Io { path: path.to_path_buf(), source });
+        let replaced =
+            reseal().and_then(|keystore| KeyFile::Encrypted(keystore).write(&staged, true)).and_then(|()| rename());
+        match replaced {
+            Ok(()) => tracing::info!("Re-encrypted {} with the current cipher and Argon2 costs", path.display()),
+            Err(e) => {
+                let _ = std::fs::remove_file(&staged);
+                tracing::warn!("Could not re-encrypt {} with the current cipher and Argon2 costs: {e}", path.display());
+            }
+        }
+    }
+
+    fn sealed(ciphertext: Vec<u8>) -> Self {
+        let cipher = CipherHeader::read(&mut &ciphertext[..], LegacyLayout::SEALED)
+            .map_or(CipherId::default(), |header| header.cipher);
+        Self { version: VERSION, pubkey: None, label: None, kdf: KDF.into(), cipher: cipher.name().into(), ciphertext }
+    }
+
+    fn check_format(&self) -> Result<(), KeystoreError> {
+        if self.version != VERSION || self.kdf != KDF || CipherId::from_name(&self.cipher).is_none() {
+            return Err(KeystoreError::Unsupported {
+                version: self.version,
+                kdf: self.kdf.clone(),
+                cipher: self.cipher.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl KeyFile {
+    pub fn read(path: &Path) -> Result<Self, KeystoreError> {
+        let bytes = std::fs::read(path).map_err(|source| KeystoreError::Io { path: path.to_path_buf(), source })?;
+        let format = |reason: String| KeystoreError::Format { path: path.to_path_buf(), reason };
+        // Keypairs are JSON arrays, keystores JSON objects
+        if bytes.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{') {
+            serde_json::from_slice(&bytes).map(Self::Encrypted).map_err(|e| format(e.to_string()))
+        } else {
+            let secret: Zeroizing<Vec<u8>> =
+                Zeroizing::new(serde_json::from_slice(&bytes).map_err(|e| format(e.to_string()))?);
+            Keypair::from_bytes(&secret).map(Self::Plain).map_err(|e| format(e.to_string()))
+        }
+    }
+
+    pub fn pubkey(&self) -> Option<Pubkey> {
+        match self {
+            Self::Plain(keypair) => Some(keypair.pubkey()),
+            Self::Encrypted(keystore) => keystore.pubkey.as_deref().and_then(|pubkey| Pubkey::from_str(pubkey).ok()),
+        }
+    }
+
+    /// The keypair; a keystore is unlocked with [`Keystore::unlock`]
+    pub fn unlock(self, path: &Path) -> Result<Keypair, KeystoreError> {
+        match self {
+            Self::Plain(keypair) => Ok(keypair),
+            Self::Encrypted(keystore) => keystore.unlock(path),
+        }
+    }
+
+    /// Write to `path`, readable by the owner only, refusing to replace a
+    /// file unless `force`
+    pub fn write(&self, path: &Path, force: bool) -> Result<(), KeystoreError> {
+        let io_error = |source| KeystoreError::Io { path: path.to_path_buf(), source };
+        let contents = Zeroizing::new(
+            match self {
+                Self::Plain(keypair) => serde_json::to_vec(&keypair.to_bytes().to_vec()),
+                Self::Encrypted(keystore) => serde_json::to_vec_pretty(keystore),
+            }
+            .expect("key files serialize"),
+        );
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true);
+        if force {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path).and_then(|mut file| file.write_all(&contents)).map_err(io_error)
+    }
+}
+
+fn secret_aad(label: &str) -> Vec<u8> {
+    [SECRET_DOMAIN, label.as_bytes()].concat()
+}
+
+/// Fresh English BIP39 phrase of 12 or 24 words
+pub fn generate_seed_phrase(words: usize) -> Result<Zeroizing<String>, KeystoreError> {
+    let kind = MnemonicType::for_word_count(words).map_err(|e| KeystoreError::SeedPhrase(e.to_string()))?;
+    Ok(Zeroizing::new(Mnemonic::new(kind, Language::English).into_phrase()))
+}
+
+/// Keypair for a BIP39 phrase. With a derivation path (`account/change`
+/// under m/44'/501') this matches Phantom and Solflare; without one it
+/// matches `solana-keygen recover`.
+pub fn keypair_from_seed_phrase(
+    phrase: &str,
+    bip39_passphrase: &str,
+    derivation_path: Option<&str>,
+) -> Result<Keypair, KeystoreError> {
+    let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    Mnemonic::validate(&phrase, Language::English).map_err(|e| KeystoreError::SeedPhrase(e.to_string()))?;
+    let seed = Zeroizing::new(generate_seed_from_seed_phrase_and_passphrase(&phrase, bip39_passphrase));
+    match derivation_path {
+        Some(path) => {
+            let path = DerivationPath::from_key_str(path).map_err(|e| KeystoreError::DerivationPath(e.to_string()))?;
+            keypair_from_seed_and_derivation_path(&seed, Some(path))
+        }
+        None => keypair_from_seed(&seed),
+    }
+    .map_err(|e| KeystoreError::SeedPhrase(e.to_string()))
+}
+
+/// `SCORIA_WALLET_PASSPHRASE`, else asked for without echo
+pub fn passphrase(prompt: &str) -> Result<String, KeystoreError> {
+    match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) => Ok(passphrase),
+        Err(_) => secret(prompt),
+    }
+}
+
+/// [`passphrase`] for a new keystore: asked twice, and never empty
+pub fn new_passphrase() -> Result<String, KeystoreError> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    loop {
+        let first = secret("New keystore passphrase: ")?;
+        if first.is_empty() {
+            eprintln!("The passphrase cannot be empty");
+        } else if secret("Repeat it: ")? == first {
+            return Ok(first);
+        } else {
+            eprintln!("Passphrases differ; try again");
+        }
+    }
+}
+
+/// One line without echo on a terminal, or read from piped stdin
+pub fn secret(prompt: &str) -> Result<String, KeystoreError> {
+    if io::stdin().is_terminal() {
+        return rpassword::prompt_password(prompt).map_err(KeystoreError::Prompt);
+    }
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line).map_err(KeystoreError::Prompt)? == 0 {
+        return Err(KeystoreError::Prompt(io::Error::new(io::ErrorKind::UnexpectedEof, "nothing on stdin")));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model_loader::aes::KdfParams;
+
+    // BIP39 test vector; its m/44'/501'/0'/0' key as derived by Phantom
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let keypair = Keypair::new();
+        let keystore = Keystore::seal(&keypair, "correct horse").unwrap();
+        assert_eq!(keystore.pubkey, Some(keypair.pubkey().to_string()));
+        assert_eq!(keystore.open("correct horse").unwrap().to_bytes(), keypair.to_bytes());
+        assert!(matches!(keystore.open("wrong"), Err(KeystoreError::Passphrase)));
+
+        // The ciphertext is bound to the advertised public key
+        let mut swapped = keystore.clone();
+        swapped.pubkey = Some(Keypair::new().pubkey().to_string());
+        assert!(matches!(swapped.open("correct horse"), Err(KeystoreError::Passphrase)));
+        assert!(matches!(keystore.open_secret("correct horse"), Err(KeystoreError::NotSecret)));
+    }
+
+    #[test]
+    fn test_secret_keystore() {
+        let keystore = Keystore::seal_secret("encryption_key", b"data key", "pw").unwrap();
+        assert_eq!(keystore.pubkey, None);
+        assert_eq!(keystore.open_secret("pw").unwrap().as_slice(), b"data key");
+        assert!(matches!(keystore.open_secret("wrong"), Err(KeystoreError::Passphrase)));
+        assert!(matches!(keystore.open("pw"), Err(KeystoreError::NotWallet(_))));
+
+        let mut relabelled = keystore.clone();
+        relabelled.label = Some("pin".into());
+        assert!(matches!(relabelled.open_secret("pw"), Err(KeystoreError::Passphrase)));
+    }
+
+    #[test]
+    fn test_key_files_on_disk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let keypair = Keypair::new();
+        let plain = dir.path().join("id.json");
+        KeyFile::Plain(keypair.insecure_clone()).write(&plain, false).unwrap();
+        assert!(solana_sdk::signature::read_keypair_file(&plain).is_ok(), "solana-keygen compatible");
+        assert!(KeyFile::Plain(Keypair::new()).write(&plain, false).is_err(), "no silent overwrite");
+
+        let sealed = dir.path().join("keys").join("id.keystore.json");
+        KeyFile::Encrypted(Keystore::seal(&keypair, "pw").unwrap()).write(&sealed, false).unwrap();
+        for path in [&plain, &sealed] {
+            assert_eq!(KeyFile::read(path).unwrap().pubkey(), Some(keypair.pubkey()));
+        }
+        std::fs::write(&plain, b"not a key").unwrap();
+        assert!(matches!(KeyFile::read(&plain), Err(KeystoreError::Format { .. })));
+    }
+
+    #[test]
+    fn test_unlock_rekeys_stale_costs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("id.json");
+        std::env::set_var(PASSPHRASE_ENV, "test passphrase");
+        let keypair = Keypair::new();
+        let stale = KdfParams { mem_cost: 2048, time_cost: 1, lanes: 1 };
+        let ciphertext = Aes256GcmProvider::new()
+            .with_kdf(stale)
+            .encrypt(&keypair.to_bytes(), "test passphrase", keypair.pubkey().as_ref())
+            .unwrap();
+        let keystore = Keystore { pubkey: Some(keypair.pubkey().to_string()), ..Keystore::sealed(ciphertext) };
+        KeyFile::Encrypted(keystore).write(&path, false).unwrap();
+
+        assert_eq!(KeyFile::read(&path).unwrap().unlock(&path).unwrap().pubkey(), keypair.pubkey());
+        let KeyFile::Encrypted(rekeyed) = KeyFile::read(&path).unwrap() else { panic!("still a keystore") };
+        assert!(!Aes256GcmProvider::new().needs_rekey(&rekeyed.ciphertext));
+        assert_eq!(rekeyed.open("test passphrase").unwrap().pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_seed_phrases() {
+        let phantom = keypair_from_seed_phrase(PHRASE, "", Some("0/0")).unwrap();
+        assert_eq!(phantom.pubkey().to_string(), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        let spaced = keypair_from_seed_phrase(&format!("  {}\n", PHRASE.replace(' ', "  ")), "", Some("0/0")).unwrap();
+        assert_eq!(spaced.pubkey(), phantom.pubkey());
+        assert_ne!(keypair_from_seed_phrase(PHRASE, "", None).unwrap().pubkey(), phantom.pubkey());
+        assert_ne!(keypair_from_seed_phrase(PHRASE, "extra", Some("0/0")).unwrap().pubkey(), phantom.pubkey());
+        assert!(keypair_from_seed_phrase("abandon abandon", "", None).is_err());
+
+        let phrase = generate_seed_phrase(24).unwrap();
+        assert_eq!(phrase.split(' ').count(), 24);
+        assert!(keypair_from_seed_phrase(&phrase, "", Some("0/0")).is_ok());
+        assert!(generate_seed_phrase(13).is_err());
+    }
+}
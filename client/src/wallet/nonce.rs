@@ -0,0 +1,121 @@
+// client/src/wallet/nonce.rs
+
+use super::fees::{FeeError, TxBuilder};
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    nonce::state::{Data as NonceData, State as NonceState, Versions as NonceVersions},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    system_instruction,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NonceError {
+    #[error("RPC error: {0}")]
+    Rpc(Box<ClientError>),
+    #[error("Nonce account {0} is not initialized")]
+    NotInitialized(Pubkey),
+    #[error("{0} is not a nonce account: {1}")]
+    Malformed(Pubkey, String),
+    #[error("Nonce account {account} is controlled by {authority}, not {expected}")]
+    WrongAuthority { account: Pubkey, authority: Pubkey, expected: Pubkey },
+}
+
+impl From<ClientError> for NonceError {
+    fn from(e: ClientError) -> Self {
+        NonceError::Rpc(Box::new(e))
+    }
+}
+
+/// Nonce account whose stored value replaces the recent blockhash, so a
+/// transaction signed now stays valid until the nonce is advanced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+impl DurableNonce {
+    pub fn new(account: Pubkey, authority: Pubkey) -> Self {
+        Self { account, authority }
+    }
+
+    /// Must be the first instruction of any transaction using this nonce
+    pub fn advance_instruction(&self) -> Instruction {
+        system_instruction::advance_nonce_account(&self.account, &self.authority)
+    }
+
+    /// Current nonce value, checking the account is controlled by `authority`
+    pub async fn blockhash(&self, rpc_client: &RpcClient) -> Result<Hash, NonceError> {
+        let data = fetch_nonce_data(rpc_client, &self.account).await?;
+        if data.authority != self.authority {
+            return Err(NonceError::WrongAuthority {
+                account: self.account,
+                authority: data.authority,
+                expected: self.authority,
+            });
+        }
+        Ok(*data.durable_nonce.as_hash())
+    }
+}
+
+pub async fn fetch_nonce_data(rpc_client: &RpcClient, account: &Pubkey) -> Result<NonceData, NonceError> {
+    let data = rpc_client.get_account_data(account).await?;
+    let versions: NonceVersions =
+        bincode::deserialize(&data).map_err(|e| NonceError::Malformed(*account, e.to_string()))?;
+    match versions.state() {
+        NonceState::Initialized(data) => Ok(data.clone()),
+        NonceState::Uninitialized => Err(NonceError::NotInitialized(*account)),
+    }
+}
+
+/// Create and initialize a rent-exempt nonce account at `nonce_account`'s address
+pub async fn create_nonce_account(
+    tx_builder: &TxBuilder<'_>,
+    payer: &dyn Signer,
+    nonce_account: &dyn Signer,
+    authority: &Pubkey,
+) -> Result<Signature, FeeError> {
+    let lamports = tx_builder
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(NonceState::size())
+        .await?;
+    let instructions =
+        system_instruction::create_nonce_account(&payer.pubkey(), &nonce_account.pubkey(), authority, lamports);
+    tx_builder.send(instructions, &payer.pubkey(), &[payer, nonce_account]).await
+}
+
+/// Advance the nonce, invalidating anything signed against its current value
+pub async fn advance_nonce_account(
+    tx_builder: &TxBuilder<'_>,
+    payer: &dyn Signer,
+    nonce: &DurableNonce,
+    authority: &dyn Signer,
+) -> Result<Signature, FeeError> {
+    // Fails early with a clear error instead of a simulation failure
+    nonce.blockhash(tx_builder.rpc()).await?;
+    let mut signers = vec![payer];
+    if authority.pubkey() != payer.pubkey() {
+        signers.push(authority);
+    }
+    tx_builder.send(vec![nonce.advance_instruction()], &payer.pubkey(), &signers).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::system_program;
+
+    #[test]
+    fn test_advance_instruction_targets_nonce() {
+        let nonce = DurableNonce::new(Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = nonce.advance_instruction();
+        assert_eq!(ix.program_id, system_program::id());
+        assert_eq!(ix.accounts[0].pubkey, nonce.account);
+        assert!(ix.accounts.iter().any(|a| a.pubkey == nonce.authority && a.is_signer));
+    }
+}
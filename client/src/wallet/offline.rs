@@ -0,0 +1,293 @@
+// client/src/wallet/offline.rs
+
+use super::fees::{FeeError, TxBuilder};
+use super::nonce::{DurableNonce, NonceError};
+use super::simulate::{dry_run, DryRunError};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::{path::{Path, PathBuf}, str::FromStr};
+use thiserror::Error;
+
+/// Bumped whenever the exported file layout changes
+pub const OFFLINE_TX_VERSION: u8 = 1;
+
+#[derive(Debug, Error)]
+pub enum OfflineTxError {
+    #[error("Unsupported offline transaction version {0}")]
+    Version(u8),
+    #[error("Malformed offline transaction: {0}")]
+    Malformed(String),
+    #[error("{0} is not a required signer of this transaction")]
+    NotASigner(Pubkey),
+    #[error("Missing signatures from: {}", .0.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", "))]
+    MissingSignatures(Vec<Pubkey>),
+    #[error("Signature from {0} does not verify")]
+    BadSignature(Pubkey),
+    #[error(transparent)]
+    Nonce(#[from] NonceError),
+    #[error("Signing failed: {0}")]
+    Signer(#[from] solana_sdk::signer::SignerError),
+    #[error("RPC error: {0}")]
+    Rpc(Box<ClientError>),
+    #[error(transparent)]
+    Fee(#[from] FeeError),
+    #[error(transparent)]
+    DryRun(#[from] DryRunError),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<ClientError> for OfflineTxError {
+    fn from(e: ClientError) -> Self {
+        OfflineTxError::Rpc(Box::new(e))
+    }
+}
+
+/// Durable nonce used in place of a recent blockhash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceConfig {
+    pub account: String,
+    pub authority: String,
+}
+
+/// How a command's transaction leaves the client
+#[derive(Debug, Clone)]
+pub enum TxMode {
+    /// Sign with the resolved signer and send immediately
+    Send,
+    /// Write an unsigned transaction for `tx sign` / `tx submit`
+    Export {
+        path: PathBuf,
+        nonce: Option<NonceConfig>,
+    },
+    /// Simulate and report costs without signing or sending
+    DryRun,
+}
+
+/// Unsigned or partially signed transaction exchanged between machines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineTransaction {
+    pub version: u8,
+    /// What the transaction does, shown before signing
+    pub description: String,
+    /// bincode `Message`, base64
+    pub message: String,
+    /// Blockhash or durable nonce value the message commits to
+    pub blockhash: String,
+    pub nonce: Option<NonceConfig>,
+    /// One slot per required signer, in message order
+    pub signers: Vec<String>,
+    pub signatures: Vec<Option<String>>,
+}
+
+impl OfflineTransaction {
+    /// Build an unsigned transaction, prefixing `AdvanceNonceAccount` when a nonce is used
+    pub async fn build(
+        rpc_client: &RpcClient,
+        description: impl Into<String>,
+        mut instructions: Vec<Instruction>,
+        payer: &Pubkey,
+        nonce: Option<NonceConfig>,
+    ) -> Result<Self, OfflineTxError> {
+        let blockhash = match &nonce {
+            Some(cfg) => {
+                let nonce = DurableNonce::new(parse_pubkey(&cfg.account)?, parse_pubkey(&cfg.authority)?);
+                instructions.insert(0, nonce.advance_instruction());
+                nonce.blockhash(rpc_client).await?
+            }
+            None => rpc_client.get_latest_blockhash().await?,
+        };
+
+        let message = Message::new_with_blockhash(&instructions, Some(payer), &blockhash);
+        Ok(Self::from_message(description.into(), &message, nonce))
+    }
+
+    fn from_message(description: String, message: &Message, nonce: Option<NonceConfig>) -> Self {
+        let required = message.header.num_required_signatures as usize;
+        Self {
+            version: OFFLINE_TX_VERSION,
+            description,
+            message: BASE64.encode(message.serialize()),
+            blockhash: message.recent_blockhash.to_string(),
+            nonce,
+            signers: message.account_keys[..required].iter().map(|k| k.to_string()).collect(),
+            signatures: vec![None; required],
+        }
+    }
+
+    pub fn message(&self) -> Result<Message, OfflineTxError> {
+        if self.version != OFFLINE_TX_VERSION {
+            return Err(OfflineTxError::Version(self.version));
+        }
+        let bytes = BASE64
+            .decode(&self.message)
+            .map_err(|e| OfflineTxError::Malformed(e.to_string()))?;
+        let message: Message = bincode::deserialize(&bytes).map_err(|e| OfflineTxError::Malformed(e.to_string()))?;
+
+        // The human-readable fields must agree with what actually gets signed
+        let required = message.header.num_required_signatures as usize;
+        let signers: Vec<String> = message.account_keys.iter().take(required).map(|k| k.to_string()).collect();
+        if signers != self.signers || self.signatures.len() != required {
+            return Err(OfflineTxError::Malformed("signer list does not match message".into()));
+        }
+        if message.recent_blockhash.to_string() != self.blockhash {
+            return Err(OfflineTxError::Malformed("blockhash does not match message".into()));
+        }
+        Ok(message)
+    }
+
+    /// Add `signer`'s signature; works on an air-gapped machine
+    pub fn sign(&mut self, signer: &dyn Signer) -> Result<Signature, OfflineTxError> {
+        let message = self.message()?;
+        let pubkey = signer.try_pubkey()?;
+        let index = message.account_keys[..self.signers.len()]
+            .iter()
+            .position(|k| k == &pubkey)
+            .ok_or(OfflineTxError::NotASigner(pubkey))?;
+
+        let signature = signer.try_sign_message(&message.serialize())?;
+        self.signatures[index] = Some(signature.to_string());
+        Ok(signature)
+    }
+
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.signers
+            .iter()
+            .zip(&self.signatures)
+            .filter(|(_, sig)| sig.is_none())
+            .filter_map(|(key, _)| Pubkey::from_str(key).ok())
+            .collect()
+    }
+
+    /// Assemble the fully signed transaction, checking every signature
+    pub fn into_transaction(self) -> Result<Transaction, OfflineTxError> {
+        let message = self.message()?;
+        let missing = self.missing_signers();
+        if !missing.is_empty() {
+            return Err(OfflineTxError::MissingSignatures(missing));
+        }
+
+        let signatures = self
+            .signatures
+            .iter()
+            .flatten()
+            .map(|s| Signature::from_str(s).map_err(|e| OfflineTxError::Malformed(e.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let bytes = message.serialize();
+        for (signature, key) in signatures.iter().zip(&message.account_keys) {
+            if !signature.verify(key.as_ref(), &bytes) {
+                return Err(OfflineTxError::BadSignature(*key));
+            }
+        }
+
+        Ok(Transaction { signatures, message })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, OfflineTxError> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), OfflineTxError> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Send now, or export for offline signing; returns the signature when sent
+pub async fn send_or_export(
+    tx_builder: &TxBuilder<'_>,
+    mode: &TxMode,
+    description: &str,
+    instructions: Vec<Instruction>,
+    payer: &Pubkey,
+    signers: &[&dyn Signer],
+) -> Result<Option<Signature>, OfflineTxError> {
+    match mode {
+        TxMode::Send => Ok(Some(tx_builder.send(instructions, payer, signers).await?)),
+        TxMode::Export { path, nonce } => {
+            // Fee is fixed at export time; the nonce advance is still placed first
+            let instructions = tx_builder.prepare(instructions, payer).await;
+            let tx = OfflineTransaction::build(tx_builder.rpc(), description, instructions, payer, nonce.clone()).await?;
+            tx.write(path)?;
+            tracing::info!(
+                path = %path.display(),
+                signers = tx.signers.len(),
+                durable_nonce = tx.nonce.is_some(),
+                "Unsigned transaction exported"
+            );
+            Ok(None)
+        }
+        TxMode::DryRun => {
+            dry_run(tx_builder, instructions, payer).await?.log(description);
+            Ok(None)
+        }
+    }
+}
+
+fn parse_pubkey(s: &str) -> Result<Pubkey, OfflineTxError> {
+    Pubkey::from_str(s).map_err(|e| OfflineTxError::Malformed(format!("{s}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, signature::Keypair, system_instruction};
+
+    fn unsigned(payer: &Keypair, cosigner: &Keypair) -> OfflineTransaction {
+        let ix = system_instruction::transfer(&cosigner.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new_with_blockhash(&[ix], Some(&payer.pubkey()), &Hash::new_unique());
+        OfflineTransaction::from_message("transfer".into(), &message, None)
+    }
+
+    #[test]
+    fn test_sign_on_separate_machines() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+        let mut tx = unsigned(&payer, &cosigner);
+
+        tx.sign(&payer).unwrap();
+        assert_eq!(tx.missing_signers(), vec![cosigner.pubkey()]);
+        assert!(matches!(
+            tx.clone().into_transaction(),
+            Err(OfflineTxError::MissingSignatures(_))
+        ));
+
+        // Round-trip through the file format between signers
+        let mut tx: OfflineTransaction = serde_json::from_str(&serde_json::to_string(&tx).unwrap()).unwrap();
+        tx.sign(&cosigner).unwrap();
+        let signed = tx.into_transaction().unwrap();
+        assert!(signed.verify().is_ok());
+    }
+
+    #[test]
+    fn test_rejects_unrelated_signer_and_forged_signature() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+        let mut tx = unsigned(&payer, &cosigner);
+
+        assert!(matches!(tx.sign(&Keypair::new()), Err(OfflineTxError::NotASigner(_))));
+
+        tx.sign(&payer).unwrap();
+        tx.signatures[1] = Some(Keypair::new().sign_message(b"other").to_string());
+        assert!(matches!(tx.into_transaction(), Err(OfflineTxError::BadSignature(_))));
+    }
+
+    #[test]
+    fn test_tampered_signer_list_is_rejected() {
+        let payer = Keypair::new();
+        let mut tx = unsigned(&payer, &Keypair::new());
+        tx.signers[1] = Pubkey::new_unique().to_string();
+        assert!(matches!(tx.message(), Err(OfflineTxError::Malformed(_))));
+    }
+}
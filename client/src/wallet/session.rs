@@ -0,0 +1,466 @@
+// client/src/wallet/session.rs
+
+//! Unlock-once sessions. `scoria-cli unlock` opens the keystores behind
+//! `wallet.path` and `security.encryption_key` and hands their contents to an
+//! agent process that holds them in mlock'd memory; later commands ask the
+//! agent before prompting for a passphrase. The agent wipes everything and
+//! exits after `session.auto_lock_secs` without use, or on `scoria-cli lock`.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Agent socket override, like `SSH_AUTH_SOCK`
+pub const SOCKET_ENV: &str = "SCORIA_AGENT_SOCK";
+/// Replies from a live agent are immediate; a hung one must not stall commands
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+#[cfg(unix)]
+const EXPIRY_CHECK: Duration = Duration::from_secs(1);
+
+/// Secret bytes kept out of swap while held: mlock'd for their lifetime,
+/// zeroized and unlocked on drop
+pub struct LockedBytes(Box<[u8]>);
+
+impl LockedBytes {
+    pub fn new(bytes: &[u8]) -> Self {
+        let locked: Box<[u8]> = bytes.into();
+        #[cfg(unix)]
+        // SAFETY: the range is the live allocation owned by `locked`
+        if unsafe { libc::mlock(locked.as_ptr().cast(), locked.len()) } != 0 {
+            tracing::warn!(error = %io::Error::last_os_error(), "mlock failed; the secret may reach swap");
+        }
+        Self(locked)
+    }
+
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Drop for LockedBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+        #[cfg(unix)]
+        // SAFETY: same range as locked in `new`, still allocated
+        unsafe {
+            libc::munlock(self.0.as_ptr().cast(), self.0.len());
+        }
+    }
+}
+
+/// What the agent holds, by keystore ID
+pub struct SessionKeyring {
+    secrets: HashMap<String, LockedBytes>,
+    auto_lock: Duration,
+    last_used: Instant,
+}
+
+impl SessionKeyring {
+    pub fn new(auto_lock: Duration) -> Self {
+        Self { secrets: HashMap::new(), auto_lock, last_used: Instant::now() }
+    }
+
+    pub fn put(&mut self, id: String, secret: &[u8], now: Instant) {
+        self.secrets.insert(id, LockedBytes::new(secret));
+        self.last_used = now;
+    }
+
+    /// Every hit restarts the auto-lock countdown
+    pub fn get(&mut self, id: &str, now: Instant) -> Option<&[u8]> {
+        let secret = self.secrets.get(id)?;
+        self.last_used = now;
+        Some(secret.expose())
+    }
+
+    pub fn len(&self) -> usize {
+        self.secrets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    pub fn locks_in(&self, now: Instant) -> Duration {
+        self.auto_lock.saturating_sub(now.saturating_duration_since(self.last_used))
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.locks_in(now).is_zero()
+    }
+}
+
+/// One line of JSON each way per connection
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request {
+    Get {
+        id: String,
+    },
+    Put {
+        id: String,
+        #[serde(with = "hex::serde")]
+        secret: Vec<u8>,
+    },
+    Status,
+    Lock,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+enum Response {
+    Secret {
+        #[serde(with = "hex::serde")]
+        secret: Vec<u8>,
+    },
+    Missing,
+    Status(SessionStatus),
+    Done,
+    Error {
+        message: String,
+    },
+}
+
+/// `scoria-cli unlock` result, and what a running agent reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionStatus {
+    pub unlocked: usize,
+    pub locks_in_secs: u64,
+}
+
+/// `SCORIA_AGENT_SOCK`, else `agent.sock` in a private directory under
+/// `XDG_RUNTIME_DIR` or the temp directory. The agent refuses to serve from a
+/// directory that is not owned by the user with mode 0700, and clients refuse
+/// an agent running as anyone else.
+pub fn socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(SOCKET_ENV) {
+        return PathBuf::from(path);
+    }
+    let base = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+    #[cfg(unix)]
+    let dir = format!("scoria-{}", unsafe { libc::getuid() });
+    #[cfg(not(unix))]
+    let dir = "scoria".to_string();
+    base.join(dir).join("agent.sock")
+}
+
+/// Synchronous client, for use from key loading deep inside commands
+pub struct SessionClient {
+    socket: PathBuf,
+}
+
+impl Default for SessionClient {
+    fn default() -> Self {
+        Self { socket: socket_path() }
+    }
+}
+
+impl SessionClient {
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    /// The secret unlocked for keystore `id`; `None` also when no agent runs
+    pub fn get(&self, id: &str) -> Option<Zeroizing<Vec<u8>>> {
+        match self.request(&Request::Get { id: id.to_string() }) {
+            Ok(Response::Secret { secret }) => Some(Zeroizing::new(secret)),
+            _ => None,
+        }
+    }
+
+    pub fn put(&self, id: &str, secret: &[u8]) -> io::Result<()> {
+        let request = Request::Put { id: id.to_string(), secret: secret.to_vec() };
+        let result = self.request(&request);
+        if let Request::Put { mut secret, .. } = request {
+            secret.zeroize();
+        }
+        match result? {
+            Response::Done => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// `None` when no agent is running
+    pub fn status(&self) -> Option<SessionStatus> {
+        match self.request(&Request::Status) {
+            Ok(Response::Status(status)) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Wipe the agent's secrets and stop it; `false` when none was running
+    pub fn lock(&self) -> io::Result<bool> {
+        match self.request(&Request::Lock) {
+            Ok(Response::Done) => Ok(true),
+            Ok(other) => Err(unexpected(other)),
+            Err(e) if matches!(e.kind(), io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg(unix)]
+    fn request(&self, request: &Request) -> io::Result<Response> {
+        use std::io::{BufRead, BufReader, Write};
+
+        let mut stream = std::os::unix::net::UnixStream::connect(&self.socket)?;
+        // Anyone who can create the socket path first could otherwise collect
+        // every secret we hand over
+        if peer_uid(&stream)? != unsafe { libc::getuid() } {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is served by another user", self.socket.display()),
+            ));
+        }
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        let mut line = Zeroizing::new(serde_json::to_vec(request)?);
+        line.push(b'\n');
+        stream.write_all(&line)?;
+        let mut reply = Zeroizing::new(String::new());
+        BufReader::new(stream).read_line(&mut reply)?;
+        Ok(serde_json::from_str(&reply)?)
+    }
+
+    #[cfg(not(unix))]
+    fn request(&self, _request: &Request) -> io::Result<Response> {
+        Err(unsupported())
+    }
+}
+
+/// Run the agent on `socket` until locked or idle for `auto_lock`
+#[cfg(unix)]
+pub async fn serve(socket: PathBuf, auto_lock: Duration) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::UnixListener,
+    };
+
+    harden_process();
+    if let Some(dir) = socket.parent() {
+        private_dir(dir)?;
+    }
+    if SessionClient::new(socket.clone()).status().is_some() {
+        return Err(io::Error::new(io::ErrorKind::AddrInUse, format!("an agent already serves {}", socket.display())));
+    }
+    // A socket left by an agent that did not exit cleanly
+    let _ = std::fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket)?;
+    std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))?;
+
+    let uid = unsafe { libc::getuid() };
+    let mut keyring = SessionKeyring::new(auto_lock);
+    let mut ticks = tokio::time::interval(EXPIRY_CHECK);
+    loop {
+        let (stream, _) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = ticks.tick() => {
+                if keyring.is_expired(Instant::now()) {
+                    tracing::info!("Session auto-locked");
+                    break;
+                }
+                continue;
+            }
+        };
+        if stream.peer_cred().map(|cred| cred.uid()).ok() != Some(uid) {
+            tracing::warn!("Refused a session request from another user");
+            continue;
+        }
+        let (reader, mut writer) = stream.into_split();
+        let mut line = Zeroizing::new(String::new());
+        let read = tokio::time::timeout(CLIENT_TIMEOUT, BufReader::new(reader).read_line(&mut line)).await;
+        if !matches!(read, Ok(Ok(n)) if n > 0) {
+            continue;
+        }
+        let (response, lock) = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => answer(&mut keyring, request),
+            Err(e) => (Response::Error { message: e.to_string() }, false),
+        };
+        let mut reply = Zeroizing::new(serde_json::to_vec(&response)?);
+        if let Response::Secret { mut secret } = response {
+            secret.zeroize();
+        }
+        reply.push(b'\n');
+        let _ = writer.write_all(&reply).await;
+        if lock {
+            tracing::info!("Session locked");
+            break;
+        }
+    }
+    drop(keyring);
+    let _ = std::fs::remove_file(&socket);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub async fn serve(_socket: PathBuf, _auto_lock: Duration) -> io::Result<()> {
+    Err(unsupported())
+}
+
+/// Create `dir` with mode 0700, or accept an existing one only if it is a real
+/// directory owned by this user that nobody else can enter
+#[cfg(unix)]
+fn private_dir(dir: &std::path::Path) -> io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Err(e) if e.kind() != io::ErrorKind::AlreadyExists => return Err(e),
+        _ => {}
+    }
+    // Not followed: a symlink planted in a shared temp directory is refused
+    let meta = std::fs::symlink_metadata(dir)?;
+    if !meta.is_dir() || meta.uid() != unsafe { libc::getuid() } || meta.mode() & 0o777 != 0o700 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} must be a directory owned by this user with mode 0700", dir.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// User on the other end of a connected Unix socket
+#[cfg(unix)]
+fn peer_uid(stream: &std::os::unix::net::UnixStream) -> io::Result<libc::uid_t> {
+    use std::os::unix::io::AsRawFd;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+        let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+        // SAFETY: `cred` and `len` describe a writable buffer of the size SO_PEERCRED fills
+        let rc = unsafe {
+            libc::getsockopt(
+                stream.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_PEERCRED,
+                (&mut cred as *mut libc::ucred).cast(),
+                &mut len,
+            )
+        };
+        if rc != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(cred.uid)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        let (mut uid, mut gid) = (0, 0);
+        // SAFETY: valid socket descriptor and out-pointers
+        if unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(uid)
+    }
+}
+
+/// Response, and whether to lock afterwards
+fn answer(keyring: &mut SessionKeyring, request: Request) -> (Response, bool) {
+    let now = Instant::now();
+    match request {
+        Request::Get { id } => match keyring.get(&id, now) {
+            Some(secret) => (Response::Secret { secret: secret.to_vec() }, false),
+            None => (Response::Missing, false),
+        },
+        Request::Put { id, mut secret } => {
+            keyring.put(id, &secret, now);
+            secret.zeroize();
+            (Response::Done, false)
+        }
+        Request::Status => (
+            Response::Status(SessionStatus { unlocked: keyring.len(), locks_in_secs: keyring.locks_in(now).as_secs() }),
+            false,
+        ),
+        Request::Lock => (Response::Done, true),
+    }
+}
+
+/// No core dumps or ptrace by other processes of the user while secrets are held
+#[cfg(unix)]
+fn harden_process() {
+    let no_core = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    // SAFETY: plain syscalls on this process with valid arguments
+    unsafe {
+        libc::setrlimit(libc::RLIMIT_CORE, &no_core);
+        #[cfg(target_os = "linux")]
+        libc::prctl(libc::PR_SET_DUMPABLE, 0);
+    }
+}
+
+fn unexpected(response: Response) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("unexpected agent reply: {response:?}"))
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, "the session agent needs Unix domain sockets")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyring_auto_locks_when_idle() {
+        let start = Instant::now();
+        let mut keyring = SessionKeyring::new(Duration::from_secs(60));
+        keyring.put("wallet".into(), b"secret", start);
+        assert_eq!(keyring.get("wallet", start + Duration::from_secs(50)), Some(&b"secret"[..]));
+        assert!(keyring.get("other", start + Duration::from_secs(100)).is_none(), "misses do not extend");
+        assert!(!keyring.is_expired(start + Duration::from_secs(109)));
+        assert!(keyring.is_expired(start + Duration::from_secs(110)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_agent_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let socket = dir.path().join("agent").join("agent.sock");
+        let agent = tokio::spawn(serve(socket.clone(), Duration::from_secs(60)));
+
+        let client = SessionClient::new(socket.clone());
+        tokio::task::spawn_blocking(move || {
+            while client.status().is_none() {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            assert!(client.get("wallet").is_none());
+            client.put("wallet", b"keypair bytes").unwrap();
+            assert_eq!(client.get("wallet").unwrap().as_slice(), b"keypair bytes");
+            assert_eq!(client.status().unwrap().unlocked, 1);
+            assert!(client.lock().unwrap());
+        })
+        .await
+        .unwrap();
+
+        agent.await.unwrap().unwrap();
+        assert!(!socket.exists());
+        assert!(!SessionClient::new(socket).lock().unwrap(), "nothing left to lock");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_dir_refuses_shared_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let base = tempfile::TempDir::new().unwrap();
+        let fresh = base.path().join("scoria-fresh");
+        private_dir(&fresh).unwrap();
+        assert_eq!(std::fs::metadata(&fresh).unwrap().permissions().mode() & 0o777, 0o700);
+        private_dir(&fresh).unwrap();
+
+        let shared = base.path().join("scoria-shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert_eq!(private_dir(&shared).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+
+        let planted = base.path().join("scoria-link");
+        std::os::unix::fs::symlink(&fresh, &planted).unwrap();
+        assert_eq!(private_dir(&planted).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+    }
+}
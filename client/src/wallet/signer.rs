@@ -0,0 +1,199 @@
+// client/src/wallet/signer.rs
+
+use super::keystore::{KeyFile, KeystoreError};
+use crate::config::WalletConfig;
+use solana_remote_wallet::{
+    locator::Locator,
+    remote_keypair::generate_remote_keypair,
+    remote_wallet::{maybe_wallet_manager, RemoteWalletManager},
+};
+use solana_sdk::{
+    derivation_path::DerivationPath,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{Signer, SignerError},
+};
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
+use thiserror::Error;
+
+const LEDGER_SCHEME: &str = "ledger://";
+
+#[derive(Debug, Error)]
+pub enum SignerSourceError {
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+    #[error("Invalid signer URI: {0}")]
+    InvalidUri(String),
+    #[error("Invalid derivation path: {0}")]
+    DerivationPath(String),
+    #[error("No hardware wallet detected")]
+    NoHardwareWallet,
+    #[error("Hardware wallet error: {0}")]
+    RemoteWallet(String),
+}
+
+/// Where transaction signatures come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignerSource {
+    /// JSON keypair or passphrase-protected keystore file
+    File(PathBuf),
+    /// Ledger device, e.g. `ledger://` or `ledger://<pubkey>?key=1/0`
+    Ledger {
+        locator: String,
+        derivation_path: Option<String>,
+    },
+}
+
+impl SignerSource {
+    /// Parse `--signer` input: a `ledger://` URI or a keypair file path
+    pub fn parse(input: &str) -> Result<Self, SignerSourceError> {
+        if !input.starts_with(LEDGER_SCHEME) {
+            return Ok(Self::File(PathBuf::from(input)));
+        }
+
+        let (locator, query) = match input.split_once('?') {
+            Some((locator, query)) => (locator, Some(query)),
+            None => (input, None),
+        };
+        let derivation_path = query
+            .map(|q| {
+                q.strip_prefix("key=")
+                    .map(str::to_string)
+                    .ok_or_else(|| SignerSourceError::InvalidUri(input.to_string()))
+            })
+            .transpose()?;
+
+        Ok(Self::Ledger {
+            locator: locator.replace(LEDGER_SCHEME, "usb://ledger/"),
+            derivation_path,
+        })
+    }
+}
+
+/// Resolve the signer for a command.
+///
+/// `--signer` takes precedence over `wallet.signer`; a Ledger URI without an
+/// explicit `?key=` falls back to `wallet.derivation_path`.
+pub fn resolve_signer(
+    cli_signer: Option<&str>,
+    wallet: &WalletConfig,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<Arc<dyn Signer>, SignerSourceError> {
+    let source = match cli_signer.or(wallet.signer.as_deref()) {
+        Some(uri) => SignerSource::parse(uri)?,
+        None => SignerSource::File(wallet.path.clone()),
+    };
+
+    match source {
+        SignerSource::File(path) => load_keypair(&path),
+        SignerSource::Ledger { locator, derivation_path } => {
+            let path = derivation_path.or_else(|| wallet.derivation_path.clone());
+            load_ledger(&locator, path.as_deref(), wallet.confirm_key, wallet_manager)
+        }
+    }
+}
+
+/// Load a JSON keypair, or a keystore after asking for its passphrase
+pub fn load_keypair(path: &Path) -> Result<Arc<dyn Signer>, SignerSourceError> {
+    let keypair = KeyFile::read(path)?.unlock(path)?;
+    Ok(Arc::new(keypair))
+}
+
+fn load_ledger(
+    locator: &str,
+    derivation_path: Option<&str>,
+    confirm_key: bool,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<Arc<dyn Signer>, SignerSourceError> {
+    if wallet_manager.is_none() {
+        *wallet_manager = maybe_wallet_manager()
+            .map_err(|e| SignerSourceError::RemoteWallet(e.to_string()))?;
+    }
+    let manager = wallet_manager
+        .as_ref()
+        .ok_or(SignerSourceError::NoHardwareWallet)?;
+
+    let locator = Locator::new_from_path(locator)
+        .map_err(|e| SignerSourceError::InvalidUri(e.to_string()))?;
+    let derivation_path = match derivation_path {
+        Some(path) => DerivationPath::from_key_str(path)
+            .map_err(|e| SignerSourceError::DerivationPath(e.to_string()))?,
+        None => DerivationPath::default(),
+    };
+
+    let keypair = generate_remote_keypair(locator, derivation_path, manager, confirm_key, "scoria-cli")
+        .map_err(|e| SignerSourceError::RemoteWallet(e.to_string()))?;
+
+    Ok(Arc::new(PromptingSigner {
+        inner: Box::new(keypair),
+        device: "Ledger",
+    }))
+}
+
+/// Prints an approval prompt before delegating to a hardware signer
+struct PromptingSigner {
+    inner: Box<dyn Signer>,
+    device: &'static str,
+}
+
+impl Signer for PromptingSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        self.inner.try_pubkey()
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        eprintln!(
+            "Review and approve the transaction on your {} ({} bytes, message hash {})",
+            self.device,
+            message.len(),
+            solana_sdk::hash::hash(message),
+        );
+        io::stderr().flush().ok();
+
+        self.inner.try_sign_message(message).map_err(|e| {
+            eprintln!("Transaction was not approved on the {}", self.device);
+            e
+        })
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_source() {
+        assert_eq!(
+            SignerSource::parse("~/.config/solana/id.json").unwrap(),
+            SignerSource::File(PathBuf::from("~/.config/solana/id.json"))
+        );
+    }
+
+    #[test]
+    fn test_parse_ledger_with_derivation() {
+        assert_eq!(
+            SignerSource::parse("ledger://?key=1/0").unwrap(),
+            SignerSource::Ledger {
+                locator: "usb://ledger/".into(),
+                derivation_path: Some("1/0".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ledger_rejects_unknown_query() {
+        assert!(matches!(
+            SignerSource::parse("ledger://?account=1"),
+            Err(SignerSourceError::InvalidUri(_))
+        ));
+    }
+}
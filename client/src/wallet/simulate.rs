@@ -0,0 +1,237 @@
+// client/src/wallet/simulate.rs
+
+use super::fees::{with_budget, TxBuilder, MAX_COMPUTE_UNITS};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::ClientError,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    message::Message,
+    native_token::lamports_to_sol,
+    pubkey::Pubkey,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DryRunError {
+    #[error("RPC error: {0}")]
+    Rpc(Box<ClientError>),
+    #[error("Transaction would fail: {reason}")]
+    Failed { reason: String, logs: Vec<String> },
+    #[error("Fee payer {payer} holds {} SOL but the transaction needs {} SOL; fund it before sending", lamports_to_sol(*.balance), lamports_to_sol(*.needed))]
+    InsufficientFunds { payer: Pubkey, balance: u64, needed: u64 },
+}
+
+impl From<ClientError> for DryRunError {
+    fn from(e: ClientError) -> Self {
+        DryRunError::Rpc(Box::new(e))
+    }
+}
+
+/// Size and balance change of one writable account
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountDelta {
+    pub pubkey: Pubkey,
+    /// `None` when the account does not exist yet
+    pub size_before: Option<usize>,
+    pub size_after: usize,
+    /// Change in the rent-exempt minimum; negative when space is released
+    pub rent: i64,
+}
+
+/// What a transaction would cost, from `simulateTransaction` against current state
+#[derive(Debug, Clone)]
+pub struct DryRunReport {
+    pub compute_units: u64,
+    pub compute_unit_limit: u32,
+    pub micro_lamports_per_unit: u64,
+    /// Signature and priority fees
+    pub fee: u64,
+    pub accounts: Vec<AccountDelta>,
+    pub payer_balance: u64,
+    pub logs: Vec<String>,
+}
+
+impl DryRunReport {
+    /// Rent the payer locks up in new or grown accounts
+    pub fn rent(&self) -> i64 {
+        self.accounts.iter().map(|a| a.rent).sum()
+    }
+
+    pub fn log(&self, description: &str) {
+        for account in self.accounts.iter().filter(|a| a.size_before != Some(a.size_after)) {
+            tracing::info!(
+                account = %account.pubkey,
+                size_before = account.size_before.map_or("new".into(), |s| s.to_string()),
+                size_after = account.size_after,
+                rent_sol = lamports_to_sol(account.rent.unsigned_abs()) * account.rent.signum() as f64,
+                "Account size change"
+            );
+        }
+        tracing::info!(
+            description,
+            compute_units = self.compute_units,
+            compute_unit_limit = self.compute_unit_limit,
+            micro_lamports_per_unit = self.micro_lamports_per_unit,
+            fee_sol = lamports_to_sol(self.fee),
+            rent_sol = lamports_to_sol(self.rent().max(0) as u64),
+            payer_balance_sol = lamports_to_sol(self.payer_balance),
+            "Dry run succeeded; nothing was sent"
+        );
+    }
+}
+
+/// Simulate `instructions` with the budget and fee they would be sent with.
+///
+/// Fails with an actionable reason when the transaction would be rejected or
+/// the payer cannot cover fees and rent, so no fee is spent finding out.
+pub async fn dry_run(
+    tx_builder: &TxBuilder<'_>,
+    instructions: Vec<Instruction>,
+    payer: &Pubkey,
+) -> Result<DryRunReport, DryRunError> {
+    let rpc = tx_builder.rpc();
+    let units = tx_builder.estimate_compute_units(&instructions, payer).await;
+    let price = tx_builder.priority_fee(&instructions).await;
+    let message = Message::new_with_blockhash(
+        &with_budget(&instructions, units, price),
+        Some(payer),
+        &rpc.get_latest_blockhash().await?,
+    );
+    let fee = rpc.get_fee_for_message(&message).await?;
+
+    let writable: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(i, key)| message.is_writable(*i) && **key != system_program::id())
+        .map(|(_, key)| *key)
+        .collect();
+    let before = rpc.get_multiple_accounts(&writable).await?;
+    let payer_balance = before.first().and_then(|a| a.as_ref()).map_or(0, |a| a.lamports);
+
+    let simulated = rpc
+        .simulate_transaction_with_config(
+            &Transaction::new_unsigned(message),
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: writable.iter().map(|k| k.to_string()).collect(),
+                }),
+                ..Default::default()
+            },
+        )
+        .await?
+        .value;
+    let logs = simulated.logs.unwrap_or_default();
+    if let Some(err) = simulated.err {
+        return Err(DryRunError::Failed { reason: explain(&err, &logs, payer), logs });
+    }
+
+    let after: Vec<Option<Account>> = simulated
+        .accounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| a.and_then(|a| a.decode()))
+        .collect();
+    let mut rent_by_size = HashMap::new();
+    let mut accounts = Vec::new();
+    for ((pubkey, before), after) in writable.iter().zip(&before).zip(&after) {
+        let size_before = before.as_ref().map(|a| a.data.len());
+        let size_after = after.as_ref().map_or(0, |a| a.data.len());
+        let mut rent = 0;
+        if size_before != Some(size_after) {
+            for size in [size_before.unwrap_or(0), size_after] {
+                if let std::collections::hash_map::Entry::Vacant(entry) = rent_by_size.entry(size) {
+                    entry.insert(rpc.get_minimum_balance_for_rent_exemption(size).await? as i64);
+                }
+            }
+            let exempt_before = size_before.map_or(0, |s| rent_by_size[&s]);
+            rent = rent_by_size[&size_after] - exempt_before;
+        }
+        accounts.push(AccountDelta { pubkey: *pubkey, size_before, size_after, rent });
+    }
+
+    let report = DryRunReport {
+        compute_units: simulated.units_consumed.unwrap_or_default(),
+        compute_unit_limit: units,
+        micro_lamports_per_unit: price,
+        fee,
+        accounts,
+        payer_balance,
+        logs,
+    };
+    let needed = fee.saturating_add(report.rent().max(0) as u64);
+    if payer_balance < needed {
+        return Err(DryRunError::InsufficientFunds { payer: *payer, balance: payer_balance, needed });
+    }
+    Ok(report)
+}
+
+/// Turn a simulation error into what the user should do about it
+fn explain(err: &TransactionError, logs: &[String], payer: &Pubkey) -> String {
+    // Anchor logs "... Error Number: 6000. Error Message: <text>."
+    let program_message = logs
+        .iter()
+        .rev()
+        .find_map(|line| line.split_once("Error Message: ").map(|(_, m)| m.trim_end_matches('.')));
+
+    match err {
+        TransactionError::AccountNotFound => format!("fee payer {payer} does not exist on this cluster; fund it first"),
+        TransactionError::InsufficientFundsForFee => format!("fee payer {payer} cannot cover the transaction fee"),
+        TransactionError::InsufficientFundsForRent { account_index } => {
+            format!("account #{account_index} would be left below the rent-exempt minimum; add lamports to it")
+        }
+        TransactionError::BlockhashNotFound => "the RPC node is behind or unreachable; retry against another endpoint".into(),
+        TransactionError::InstructionError(index, InstructionError::ComputationalBudgetExceeded)
+        | TransactionError::InstructionError(index, InstructionError::ProgramFailedToComplete) => format!(
+            "instruction {index} exceeds the {MAX_COMPUTE_UNITS} compute unit limit; split the operation"
+        ),
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => match program_message {
+            Some(message) => format!("instruction {index} was rejected: {message} (error {code})"),
+            // System program error 1 is `ResultWithNegativeLamports`
+            None if *code == 1 => format!("instruction {index} failed: {payer} has insufficient lamports"),
+            None => format!("instruction {index} failed with program error {code}"),
+        },
+        other => match program_message {
+            Some(message) => format!("{other}: {message}"),
+            None => other.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_error_message_is_surfaced() {
+        let logs = vec![
+            "Program SCRA invoke [1]".to_string(),
+            "Program log: AnchorError occurred. Error Code: ProgramPaused. Error Number: 6012. Error Message: Registry is paused.".to_string(),
+        ];
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(6012));
+        assert_eq!(
+            explain(&err, &logs, &Pubkey::new_unique()),
+            "instruction 2 was rejected: Registry is paused (error 6012)"
+        );
+    }
+
+    #[test]
+    fn test_missing_payer_is_actionable() {
+        let payer = Pubkey::new_unique();
+        let reason = explain(&TransactionError::AccountNotFound, &[], &payer);
+        assert!(reason.contains(&payer.to_string()) && reason.contains("fund"));
+
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(1));
+        assert!(explain(&err, &[], &payer).contains("insufficient lamports"));
+    }
+}
@@ -0,0 +1,143 @@
+// governance/src/delegation.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{
+    state::VotingConfig,
+    error::GovernanceError
+};
+
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = DelegationRecord::LEN,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, DelegationRecord>,
+    #[account(
+        constraint = voting_tokens.owner == delegator.key() @ GovernanceError::InvalidTokenAccount,
+        constraint = voting_tokens.mint == config.governance_mint @ GovernanceError::InvalidTokenAccount
+    )]
+    pub voting_tokens: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.voting_enabled @ GovernanceError::VotingDisabled
+    )]
+    pub config: Account<'info, VotingConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelegateVotes<'info> {
+    /// Delegate the delegator's full token balance to `delegate`
+    pub fn delegate(&mut self, delegate: Pubkey, bump: u8) -> Result<()> {
+        let delegator = self.delegator.key();
+        require!(delegate != delegator, GovernanceError::SelfDelegation);
+
+        // An existing record may only be replaced once it has been revoked
+        let record = &mut self.delegation;
+        require!(
+            record.delegator == Pubkey::default() || record.revoked_at.is_some(),
+            GovernanceError::DelegationActive
+        );
+
+        let amount = self.voting_tokens.amount;
+        require!(amount > 0, GovernanceError::NoVotingPower);
+
+        let now = Clock::get()?.unix_timestamp;
+        record.set_inner(DelegationRecord {
+            delegator,
+            delegate,
+            amount,
+            delegated_at: now,
+            revoked_at: None,
+            bump,
+        });
+
+        emit!(VotesDelegated {
+            delegator,
+            delegate,
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    pub delegator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator @ GovernanceError::Unauthorized
+    )]
+    pub delegation: Account<'info, DelegationRecord>,
+}
+
+impl<'info> RevokeDelegation<'info> {
+    pub fn revoke(&mut self) -> Result<()> {
+        let record = &mut self.delegation;
+        require!(record.revoked_at.is_none(), GovernanceError::DelegationRevoked);
+
+        let now = Clock::get()?.unix_timestamp;
+        record.revoked_at = Some(now);
+
+        emit!(DelegationRevoked {
+            delegator: record.delegator,
+            delegate: record.delegate,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// Voting weight delegated by one token holder to another pubkey.
+///
+/// Records are kept after revocation so that votes attempted with a stale
+/// delegation fail with `DelegationRevoked` rather than a missing account.
+#[account]
+#[derive(Default)]
+pub struct DelegationRecord {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub delegated_at: i64,
+    pub revoked_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl DelegationRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + (1 + 8) + 1;
+
+    /// Weight `voter` may cast on the delegator's behalf. `amount` is only a
+    /// snapshot, so it is capped by the delegator's current `balance`.
+    pub fn delegated_weight(&self, voter: &Pubkey, balance: u64) -> Result<u64> {
+        require!(self.revoked_at.is_none(), GovernanceError::DelegationRevoked);
+        require!(self.delegate == *voter, GovernanceError::NotDelegate);
+        Ok(self.amount.min(balance))
+    }
+}
+
+#[event]
+pub struct VotesDelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,30 @@
+// governance/src/state.rs
+
+use anchor_lang::prelude::*;
+
+/// Upper bound on operators allowed to cancel queued executions
+pub const MAX_EMERGENCY_OPERATORS: usize = 5;
+/// Minimum timelock between queueing and executing an approved proposal
+pub const MIN_TIMELOCK_DELAY: i64 = 3_600; // 1 hour
+
+/// Global governance parameters, stored at the `[b"config"]` PDA
+#[account]
+#[derive(Default)]
+pub struct VotingConfig {
+    pub authority: Pubkey,
+    pub governance_mint: Pubkey,
+    pub voting_enabled: bool,
+    /// Seconds an approved proposal must wait in the queue before execution
+    pub timelock_delay: i64,
+    /// Operators allowed to cancel queued executions in an emergency
+    pub emergency_operators: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl VotingConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + (4 + 32 * MAX_EMERGENCY_OPERATORS) + 1;
+
+    pub fn is_emergency_operator(&self, key: &Pubkey) -> bool {
+        self.emergency_operators.contains(key)
+    }
+}
@@ -0,0 +1,49 @@
+// governance/src/timelock.rs
+
+use anchor_lang::prelude::*;
+use crate::{
+    state::{VotingConfig, MAX_EMERGENCY_OPERATORS, MIN_TIMELOCK_DELAY},
+    error::GovernanceError
+};
+
+#[derive(Accounts)]
+pub struct ConfigureTimelock<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ GovernanceError::Unauthorized
+    )]
+    pub config: Account<'info, VotingConfig>,
+}
+
+impl<'info> ConfigureTimelock<'info> {
+    /// Set the execution delay and the operators allowed to cancel queued proposals
+    pub fn configure(&mut self, delay: i64, emergency_operators: Vec<Pubkey>) -> Result<()> {
+        require!(delay >= MIN_TIMELOCK_DELAY, GovernanceError::TimelockTooShort);
+        require!(
+            emergency_operators.len() <= MAX_EMERGENCY_OPERATORS,
+            GovernanceError::TooManyOperators
+        );
+
+        let config = &mut self.config;
+        config.timelock_delay = delay;
+        config.emergency_operators = emergency_operators;
+
+        emit!(TimelockConfigured {
+            delay,
+            operators: config.emergency_operators.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TimelockConfigured {
+    pub delay: i64,
+    pub operators: u8,
+    pub timestamp: i64,
+}
@@ -1,12 +1,14 @@
 // governance/src/vote.rs
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 use solana_program::{
     program_memory::sol_memcmp,
     pubkey::PUBKEY_BYTES
 };
 use crate::{
     crypto::{verify_schnorr, SchnorrSignature},
+    delegation::DelegationRecord,
     state::{Proposal, VoteRecord, VotingConfig},
     error::GovernanceError
 };
@@ -45,11 +47,13 @@ impl<'info> CreateProposal<'info> {
         choices: Vec<String>,
         start_time: i64,
         end_time: i64,
+        strategy: VotingStrategy,
     ) -> Result<()> {
         require!(
             choices.len() <= MAX_CHOICES,
             GovernanceError::TooManyChoices
         );
+        strategy.validate()?;
         
         self.proposal.set_inner(Proposal {
             id: proposal_id,
@@ -59,18 +63,34 @@ impl<'info> CreateProposal<'info> {
             choices,
             start_time,
             end_time,
+            strategy,
             total_votes: 0,
             votes_per_choice: vec![0; choices.len()],
             bump: self.proposal.bump,
             created_at: Clock::get()?.unix_timestamp,
         });
+
+        emit!(ProposalCreated {
+            proposal: self.proposal.key(),
+            id: proposal_id,
+            author: self.authority.key(),
+            start_time,
+            end_time,
+            created_at: self.proposal.created_at,
+        });
         
         Ok(())
     }
 }
 
+/// Casts a vote either with the voter's own weight (`on_behalf_of == voter`)
+/// or with weight delegated to the voter. The vote record is keyed by the
+/// owner of the voting power, so each holder's weight is counted at most once.
+/// Every vote passes the power owner's governance token account: self-votes
+/// are capped by its balance, and delegated votes stop counting tokens the
+/// delegator moved away after delegating.
 #[derive(Accounts)]
-#[instruction(vote_choice: u8)]
+#[instruction(vote_choice: u8, weight: u64, on_behalf_of: Pubkey)]
 pub struct CastVote<'info> {
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
@@ -78,12 +98,27 @@ pub struct CastVote<'info> {
         init,
         payer = voter,
         space = VoteRecord::LEN,
-        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        seeds = [b"vote", proposal.key().as_ref(), on_behalf_of.as_ref()],
         bump
     )]
     pub vote_record: Account<'info, VoteRecord>,
     #[account(mut)]
     pub voter: Signer<'info>,
+    #[account(
+        seeds = [b"delegation", on_behalf_of.as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Option<Account<'info, DelegationRecord>>,
+    #[account(
+        constraint = voting_tokens.owner == on_behalf_of @ GovernanceError::InvalidTokenAccount,
+        constraint = voting_tokens.mint == config.governance_mint @ GovernanceError::InvalidTokenAccount
+    )]
+    pub voting_tokens: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, VotingConfig>,
     pub system_program: Program<'info, System>,
 }
 
@@ -92,6 +127,7 @@ impl<'info> CastVote<'info> {
         &mut self,
         vote_choice: u8,
         weight: u64,
+        on_behalf_of: Pubkey,
         proof: SchnorrSignature,
     ) -> Result<()> {
         let clock = Clock::get()?;
@@ -114,27 +150,111 @@ impl<'info> CastVote<'info> {
             &proposal.key().to_bytes(),
         )?;
 
+        // Own votes may not exceed the voter's balance; delegated votes may not
+        // exceed the weight recorded at delegation time, nor what the
+        // delegator still holds
+        let balance = self.voting_tokens.amount;
+        let delegate = if on_behalf_of == self.voter.key() {
+            require!(weight <= balance, GovernanceError::WeightExceedsBalance);
+            None
+        } else {
+            let record = self
+                .delegation
+                .as_ref()
+                .ok_or(GovernanceError::NotDelegate)?;
+            let delegated = record.delegated_weight(&self.voter.key(), balance)?;
+            require!(weight <= delegated, GovernanceError::DelegatedWeightExceeded);
+            Some(self.voter.key())
+        };
+
+        let effective_weight = proposal.strategy.effective_weight(weight)?;
+
         let vote = &mut self.vote_record;
         vote.set_inner(VoteRecord {
-            voter: self.voter.key(),
+            voter: on_behalf_of,
             proposal: proposal.key(),
             choice: vote_choice,
             weight,
+            effective_weight,
+            delegate,
             cast_at: clock.unix_timestamp,
             bump: self.vote_record.bump,
         });
 
         proposal.total_votes = proposal.total_votes
-            .checked_add(weight)
+            .checked_add(effective_weight)
             .ok_or(GovernanceError::ArithmeticOverflow)?;
             
         proposal.votes_per_choice[vote_choice as usize] = proposal
             .votes_per_choice[vote_choice as usize]
-            .checked_add(weight)
+            .checked_add(effective_weight)
             .ok_or(GovernanceError::ArithmeticOverflow)?;
 
+        emit!(VoteCast {
+            proposal: proposal.key(),
+            voter: on_behalf_of,
+            delegate,
+            choice: vote_choice,
+            weight,
+            effective_weight,
+            cast_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// How raw token weight is converted into counted votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VotingStrategy {
+    /// One token, one vote
+    #[default]
+    Linear,
+    /// Counted votes grow with the square root of weight
+    Quadratic,
+    /// Linear, but a single vote may carry at most `cap` weight
+    TokenWeightedWithCap { cap: u64 },
+}
+
+impl VotingStrategy {
+    /// Borsh size of the largest variant
+    pub const LEN: usize = 1 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        if let VotingStrategy::TokenWeightedWithCap { cap } = self {
+            require!(*cap > 0, GovernanceError::InvalidWeightCap);
+        }
         Ok(())
     }
+
+    /// Votes counted towards the tally for a ballot of `weight`
+    pub fn effective_weight(&self, weight: u64) -> Result<u64> {
+        require!(weight > 0, GovernanceError::ZeroWeight);
+        match self {
+            VotingStrategy::Linear => Ok(weight),
+            VotingStrategy::Quadratic => Ok(integer_sqrt(weight)),
+            VotingStrategy::TokenWeightedWithCap { cap } => {
+                require!(weight <= *cap, GovernanceError::WeightCapExceeded);
+                Ok(weight)
+            }
+        }
+    }
+}
+
+/// Floor of the square root, computed without floating point
+pub fn integer_sqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method converges from above
+    let mut x = 1u64 << ((64 - n.leading_zeros() + 1) / 2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
 }
 
 #[account]
@@ -147,6 +267,7 @@ pub struct Proposal {
     pub choices: Vec<String>,
     pub start_time: i64,
     pub end_time: i64,
+    pub strategy: VotingStrategy,
     pub total_votes: u64,
     pub votes_per_choice: Vec<u64>,
     pub bump: u8,
@@ -160,10 +281,35 @@ pub struct VoteRecord {
     pub proposal: Pubkey,
     pub choice: u8,
     pub weight: u64,
+    /// Weight after the proposal's voting strategy is applied
+    pub effective_weight: u64,
+    /// Set when the vote was cast by a delegate on the voter's behalf
+    pub delegate: Option<Pubkey>,
     pub cast_at: i64,
     pub bump: u8,
 }
 
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub author: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub delegate: Option<Pubkey>,
+    pub choice: u8,
+    pub weight: u64,
+    pub effective_weight: u64,
+    pub cast_at: i64,
+}
+
 #[error_code]
 pub enum GovernanceError {
     #[msg("Voting system disabled")]
@@ -178,4 +324,32 @@ pub enum GovernanceError {
     ArithmeticOverflow,
     #[msg("Invalid cryptographic proof")]
     InvalidProof,
+    #[msg("Unauthorized governance action")]
+    Unauthorized,
+    #[msg("Token account does not hold governance tokens for this signer")]
+    InvalidTokenAccount,
+    #[msg("No voting power to delegate")]
+    NoVotingPower,
+    #[msg("Cannot delegate votes to self")]
+    SelfDelegation,
+    #[msg("An active delegation already exists")]
+    DelegationActive,
+    #[msg("Vote delegation has been revoked")]
+    DelegationRevoked,
+    #[msg("Signer is not the delegate for this voter")]
+    NotDelegate,
+    #[msg("Vote weight exceeds delegated amount")]
+    DelegatedWeightExceeded,
+    #[msg("Vote weight exceeds the voter's governance token balance")]
+    WeightExceedsBalance,
+    #[msg("Vote weight must be non-zero")]
+    ZeroWeight,
+    #[msg("Weight cap must be non-zero")]
+    InvalidWeightCap,
+    #[msg("Vote weight exceeds the proposal's per-vote cap")]
+    WeightCapExceeded,
+    #[msg("Timelock delay below the minimum")]
+    TimelockTooShort,
+    #[msg("Too many emergency operators")]
+    TooManyOperators,
 }
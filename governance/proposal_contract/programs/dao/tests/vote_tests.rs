@@ -146,6 +146,80 @@ mod tests {
         }
     }
 
+    // Vote delegation tests
+    mod delegation {
+        use super::*;
+        use anchor_lang::prelude::Pubkey;
+        use governance::delegation::DelegationRecord;
+
+        fn record(delegate: Pubkey, amount: u64) -> DelegationRecord {
+            DelegationRecord { delegator: Pubkey::new_unique(), delegate, amount, ..Default::default() }
+        }
+
+        #[test]
+        fn test_delegated_weight_for_delegate() {
+            let delegate = Pubkey::new_unique();
+            assert_eq!(record(delegate, 100).delegated_weight(&delegate, 100).unwrap(), 100);
+            assert_eq!(record(delegate, 100).delegated_weight(&delegate, 250).unwrap(), 100);
+        }
+
+        #[test]
+        fn test_delegated_weight_capped_by_current_balance() {
+            // Tokens sold or moved after delegating no longer count
+            let delegate = Pubkey::new_unique();
+            assert_eq!(record(delegate, 100).delegated_weight(&delegate, 40).unwrap(), 40);
+            assert_eq!(record(delegate, 100).delegated_weight(&delegate, 0).unwrap(), 0);
+        }
+
+        #[test]
+        fn test_revoked_delegation() {
+            let delegate = Pubkey::new_unique();
+            let mut revoked = record(delegate, 100);
+            revoked.revoked_at = Some(1_700_000_000);
+            assert_eq!(
+                revoked.delegated_weight(&delegate, 100).unwrap_err(),
+                GovernanceError::DelegationRevoked.into()
+            );
+        }
+
+        #[test]
+        fn test_wrong_delegate() {
+            let delegate = Pubkey::new_unique();
+            assert_eq!(
+                record(delegate, 100).delegated_weight(&Pubkey::new_unique(), 100).unwrap_err(),
+                GovernanceError::NotDelegate.into()
+            );
+        }
+    }
+
+    // Tally strategy tests
+    mod strategies {
+        use super::*;
+        use governance::vote::{integer_sqrt, VotingStrategy};
+
+        #[test]
+        fn test_integer_sqrt() {
+            for n in [0u64, 1, 2, 3, 4, 15, 16, 17, 1_000_000, u64::MAX] {
+                let r = integer_sqrt(n);
+                assert!(r * r <= n);
+                assert!((r + 1).checked_mul(r + 1).map_or(true, |sq| sq > n));
+            }
+        }
+
+        #[test]
+        fn test_quadratic_weight() {
+            assert_eq!(VotingStrategy::Quadratic.effective_weight(100).unwrap(), 10);
+            assert_eq!(VotingStrategy::Linear.effective_weight(100).unwrap(), 100);
+        }
+
+        #[test]
+        fn test_weight_cap_violation() {
+            let strategy = VotingStrategy::TokenWeightedWithCap { cap: 50 };
+            assert_eq!(strategy.effective_weight(50).unwrap(), 50);
+            assert!(strategy.effective_weight(51).is_err());
+        }
+    }
+
     // Boundary condition tests
     mod edge_cases {
         use super::*;
@@ -0,0 +1,81 @@
+// indexer/benches/secure_aggregation.rs
+//
+// One aggregation round per `fl.aggregation_scheme`: masking runs all four
+// rounds in-process, the homomorphic schemes encrypt every update, sum the
+// ciphertexts and decrypt under a committee key made up front.
+// Run with `cargo bench --bench secure_aggregation`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use scoria_indexer::secure_aggregation::{
+    AggregationScheme, EncryptedShares, HePublicKey, HeSecretKey, HomomorphicContext, SecAggClient, SecAggServer,
+    UnmaskResponse,
+};
+
+const CLIENTS: u32 = 8;
+const THRESHOLD: usize = 5;
+const DIMENSIONS: [usize; 2] = [1 << 12, 1 << 14];
+
+fn update(id: u32, dimension: usize) -> Vec<f32> {
+    (0..dimension).map(|i| ((i as u32 * 31 + id * 17) % 97) as f32 / 97.0 - 0.5).collect()
+}
+
+fn masking_round(dimension: usize) -> Vec<f32> {
+    let mut clients: Vec<SecAggClient> = (1..=CLIENTS).map(|id| SecAggClient::new(id, THRESHOLD)).collect();
+    let mut server = SecAggServer::new(THRESHOLD, dimension);
+    for c in &clients {
+        server.register(c.advertise()).unwrap();
+    }
+    let roster = server.roster();
+
+    let mut outbound: Vec<EncryptedShares> = Vec::new();
+    for c in clients.iter_mut() {
+        outbound.extend(c.share_keys(&roster).unwrap());
+    }
+    let routed = server.route_shares(outbound).unwrap();
+    for c in clients.iter_mut() {
+        c.receive_shares(&routed[&c.id()]).unwrap();
+    }
+
+    let senders = server.share_senders();
+    for c in &clients {
+        server.submit_masked(c.mask_input(&update(c.id(), dimension), &senders).unwrap()).unwrap();
+    }
+    let survivors = server.survivors();
+    let responses: Vec<UnmaskResponse> = clients.iter().map(|c| c.unmask(&senders, &survivors).unwrap()).collect();
+    server.unmask(&responses).unwrap().sum
+}
+
+fn homomorphic_round(he: &HomomorphicContext, (sk, pk): &(HeSecretKey, HePublicKey), dimension: usize) -> Vec<f64> {
+    let mut updates = (1..=CLIENTS).map(|id| he.encrypt(pk, &update(id, dimension)).unwrap());
+    let mut sum = updates.next().unwrap();
+    for encrypted in updates {
+        he.add_assign(pk, &mut sum, &encrypted).unwrap();
+    }
+    he.decrypt_sum(sk, &sum, dimension, CLIENTS as usize).unwrap()
+}
+
+fn bench_schemes(c: &mut Criterion) {
+    let he = HomomorphicContext::new();
+    // Committee keys are generated once per model, outside the round
+    let keys: Vec<_> = [AggregationScheme::Paillier, AggregationScheme::Ckks]
+        .into_iter()
+        .map(|scheme| (scheme, he.generate_keys(scheme).unwrap()))
+        .collect();
+    let mut group = c.benchmark_group("aggregation_round");
+    group.sample_size(10);
+    for dimension in DIMENSIONS {
+        group.throughput(Throughput::Elements(dimension as u64 * CLIENTS as u64));
+        group.bench_with_input(BenchmarkId::new("masking", dimension), &dimension, |b, &d| {
+            b.iter(|| masking_round(d))
+        });
+        for (scheme, keys) in &keys {
+            group.bench_with_input(BenchmarkId::new(scheme.as_str(), dimension), &dimension, |b, &d| {
+                b.iter(|| homomorphic_round(&he, keys, d))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_schemes);
+criterion_main!(benches);
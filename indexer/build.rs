@@ -0,0 +1,9 @@
+// indexer/build.rs
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::configure()
+        .build_client(false)
+        .compile(&["proto/events.proto"], &["proto"])?;
+    println!("cargo:rerun-if-changed=proto/events.proto");
+    Ok(())
+}
@@ -0,0 +1,55 @@
+-- ClickHouse analytics schema for the optional `clickhouse` sink
+-- Apply once per cluster: clickhouse-client --multiquery < clickhouse/schema.sql
+
+CREATE DATABASE IF NOT EXISTS scoria;
+
+-- Wide event table; redelivered rows share a sort key and collapse on merge
+CREATE TABLE IF NOT EXISTS scoria.program_events
+(
+    signature     String,
+    instruction_index UInt32,
+    event_index   UInt32,
+    slot          UInt64,
+    event_type    LowCardinality(String),
+    event_time    DateTime('UTC'),
+    model_id      String,
+    actor         String,
+    counterparty  String,
+    status        LowCardinality(String),
+    fee           UInt64,
+    fee_mint      LowCardinality(String),
+    amount        UInt64,
+    proposal_id   String,
+    payload       String CODEC(ZSTD(3))
+)
+ENGINE = ReplacingMergeTree
+PARTITION BY toYYYYMM(event_time)
+ORDER BY (event_type, model_id, event_time, signature, instruction_index, event_index);
+
+-- Signatures from slots that were dropped after publication
+CREATE TABLE IF NOT EXISTS scoria.program_event_retractions
+(
+    signature     String,
+    retracted_at  DateTime('UTC')
+)
+ENGINE = ReplacingMergeTree
+ORDER BY signature;
+
+-- Query through this view to exclude retracted events
+CREATE VIEW IF NOT EXISTS scoria.program_events_live AS
+SELECT *
+FROM scoria.program_events FINAL
+WHERE signature NOT IN (SELECT signature FROM scoria.program_event_retractions);
+
+-- Daily inference volume and revenue per model
+CREATE VIEW IF NOT EXISTS scoria.inference_daily AS
+SELECT
+    model_id,
+    toDate(event_time) AS day,
+    countIf(status = 'completed') AS completed,
+    countIf(status = 'failed') AS failed,
+    countIf(status = 'challenged') AS challenged,
+    sumIf(fee, status = 'completed') AS fee_revenue
+FROM scoria.program_events_live
+WHERE event_type IN ('InferenceFulfilled', 'InferenceChallenged', 'InferenceFinalized')
+GROUP BY model_id, day;
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS idx_events_model_id;
+DROP INDEX IF EXISTS idx_events_provisional_slot;
+
+ALTER TABLE events
+    DROP COLUMN IF EXISTS finalized_at,
+    DROP COLUMN IF EXISTS slot_status,
+    DROP COLUMN IF EXISTS provisional,
+    DROP COLUMN IF EXISTS model_id;
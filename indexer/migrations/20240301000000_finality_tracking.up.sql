@@ -0,0 +1,23 @@
+-- Track slot finality for indexed events
+-- Rows indexed before this migration are long since rooted
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS model_id TEXT,
+    ADD COLUMN IF NOT EXISTS provisional BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS slot_status TEXT NOT NULL DEFAULT 'finalized',
+    ADD COLUMN IF NOT EXISTS finalized_at TIMESTAMPTZ;
+
+ALTER TABLE events
+    ALTER COLUMN provisional SET DEFAULT TRUE,
+    ALTER COLUMN slot_status SET DEFAULT 'confirmed';
+
+-- `data` is the serialized ProgramEvent: {"inner": {"<Variant>": {...}}}
+UPDATE events
+SET model_id = (
+    SELECT COALESCE(payload.value->>'model_id', payload.value->>'id')
+    FROM jsonb_each(data->'inner') AS payload
+    WHERE payload.key NOT IN ('ProposalCreated', 'VoteCast')
+)
+WHERE model_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_events_provisional_slot ON events (slot) WHERE provisional;
+CREATE INDEX IF NOT EXISTS idx_events_model_id ON events (model_id, slot);
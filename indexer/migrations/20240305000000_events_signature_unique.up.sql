@@ -0,0 +1,2 @@
+-- Allow idempotent replays (live reconnects and backfill) keyed by signature
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_signature ON events (signature);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_models_status;
+
+ALTER TABLE models
+    DROP COLUMN IF EXISTS reclaimed_by,
+    DROP COLUMN IF EXISTS expired_at,
+    DROP COLUMN IF EXISTS status;
@@ -0,0 +1,7 @@
+-- Storage expiry: models reclaimed by `reclaim_expired` stay queryable but are marked expired
+ALTER TABLE models
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'active',
+    ADD COLUMN IF NOT EXISTS expired_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS reclaimed_by TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_models_status ON models (status);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_inferences_provider;
+DROP INDEX IF EXISTS idx_inferences_model;
+DROP TABLE IF EXISTS inferences;
@@ -0,0 +1,16 @@
+-- Settled inference requests, one row per request PDA
+CREATE TABLE IF NOT EXISTS inferences (
+    request_id   TEXT PRIMARY KEY,
+    model_id     TEXT NOT NULL,
+    provider     TEXT NOT NULL,
+    status       TEXT NOT NULL,
+    output_hash  TEXT NOT NULL,
+    error_code   BIGINT NOT NULL DEFAULT 0,
+    payee        TEXT NOT NULL,
+    fee          BIGINT NOT NULL,
+    fee_mint     TEXT,
+    fulfilled_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_inferences_model ON inferences (model_id, fulfilled_at DESC);
+CREATE INDEX IF NOT EXISTS idx_inferences_provider ON inferences (provider);
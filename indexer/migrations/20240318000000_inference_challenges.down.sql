@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_inferences_open_challenges;
+
+ALTER TABLE inferences
+    DROP COLUMN IF EXISTS slashed,
+    DROP COLUMN IF EXISTS challenger,
+    DROP COLUMN IF EXISTS finalized_at,
+    DROP COLUMN IF EXISTS challenge_deadline;
@@ -0,0 +1,9 @@
+-- Optimistic fulfillments: challenge window and fraud-proof outcome
+ALTER TABLE inferences
+    ADD COLUMN IF NOT EXISTS challenge_deadline TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS finalized_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS challenger TEXT,
+    ADD COLUMN IF NOT EXISTS slashed BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_inferences_open_challenges
+    ON inferences (challenge_deadline) WHERE status = 'fulfilled';
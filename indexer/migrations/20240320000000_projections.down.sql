@@ -0,0 +1,9 @@
+DROP VIEW IF EXISTS governance_participation;
+DROP TABLE IF EXISTS governance_voters;
+DROP TABLE IF EXISTS governance_proposals;
+DROP VIEW IF EXISTS contributor_rankings;
+DROP TABLE IF EXISTS contributor_leaderboard;
+DROP TABLE IF EXISTS model_fee_revenue;
+DROP TABLE IF EXISTS inference_daily_counts;
+DROP TABLE IF EXISTS projection_events;
+DROP TABLE IF EXISTS projection_offsets;
@@ -0,0 +1,78 @@
+-- Analytics projections maintained by the Kafka stream consumer
+
+-- Consumer position per partition, advanced in the same transaction as the projections
+CREATE TABLE IF NOT EXISTS projection_offsets (
+    consumer_group TEXT NOT NULL,
+    topic TEXT NOT NULL,
+    partition INTEGER NOT NULL,
+    next_offset BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (consumer_group, topic, partition)
+);
+
+-- Events currently reflected in the projections; retractions subtract what is stored here
+CREATE TABLE IF NOT EXISTS projection_events (
+    signature TEXT PRIMARY KEY,
+    slot BIGINT NOT NULL,
+    data JSONB NOT NULL,
+    projected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS inference_daily_counts (
+    model_id TEXT NOT NULL,
+    day DATE NOT NULL,
+    completed BIGINT NOT NULL DEFAULT 0,
+    failed BIGINT NOT NULL DEFAULT 0,
+    challenged BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (model_id, day)
+);
+
+-- fee_mint is '' for lamport fees
+CREATE TABLE IF NOT EXISTS model_fee_revenue (
+    model_id TEXT NOT NULL,
+    fee_mint TEXT NOT NULL,
+    amount BIGINT NOT NULL DEFAULT 0,
+    settled BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (model_id, fee_mint)
+);
+
+CREATE TABLE IF NOT EXISTS contributor_leaderboard (
+    model_id TEXT NOT NULL,
+    contributor TEXT NOT NULL,
+    contributions BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (model_id, contributor)
+);
+
+CREATE OR REPLACE VIEW contributor_rankings AS
+SELECT model_id,
+       contributor,
+       contributions,
+       RANK() OVER (PARTITION BY model_id ORDER BY contributions DESC) AS rank
+FROM contributor_leaderboard
+WHERE contributions > 0;
+
+CREATE TABLE IF NOT EXISTS governance_proposals (
+    proposal_id TEXT PRIMARY KEY,
+    author TEXT,
+    start_time TIMESTAMPTZ,
+    end_time TIMESTAMPTZ,
+    registered BOOLEAN NOT NULL DEFAULT FALSE,
+    votes BIGINT NOT NULL DEFAULT 0,
+    effective_weight BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS governance_voters (
+    voter TEXT PRIMARY KEY,
+    votes BIGINT NOT NULL DEFAULT 0,
+    last_voted_at TIMESTAMPTZ
+);
+
+-- Share of all voters seen so far who took part in each proposal
+CREATE OR REPLACE VIEW governance_participation AS
+SELECT p.proposal_id,
+       p.votes,
+       p.effective_weight,
+       p.votes::DOUBLE PRECISION
+           / NULLIF((SELECT COUNT(*) FROM governance_voters WHERE votes > 0), 0) AS participation_rate
+FROM governance_proposals p
+WHERE p.registered;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_models_parent;
+
+ALTER TABLE models
+    DROP COLUMN IF EXISTS upstream_royalty_bps,
+    DROP COLUMN IF EXISTS parent_id;
@@ -0,0 +1,6 @@
+-- Fork lineage: `fork_model` records the upstream model and the royalty routed back to it
+ALTER TABLE models
+    ADD COLUMN IF NOT EXISTS parent_id TEXT,
+    ADD COLUMN IF NOT EXISTS upstream_royalty_bps INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_models_parent ON models (parent_id) WHERE parent_id IS NOT NULL;
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_models_fee;
+DROP INDEX IF EXISTS idx_models_tags;
+
+ALTER TABLE models
+    DROP COLUMN IF EXISTS tags,
+    DROP COLUMN IF EXISTS active_version,
+    DROP COLUMN IF EXISTS inference_fee;
@@ -0,0 +1,13 @@
+-- Columns served by `GET /models` for discovery
+ALTER TABLE models
+    ADD COLUMN IF NOT EXISTS inference_fee BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS active_version BIGINT NOT NULL DEFAULT 1,
+    ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+-- Backfill tags from registration metadata
+UPDATE models
+SET tags = ARRAY(SELECT jsonb_array_elements_text(metadata->'tags'))
+WHERE jsonb_typeof(metadata->'tags') = 'array';
+
+CREATE INDEX IF NOT EXISTS idx_models_tags ON models USING GIN (tags);
+CREATE INDEX IF NOT EXISTS idx_models_fee ON models (inference_fee);
@@ -0,0 +1,16 @@
+DROP INDEX IF EXISTS idx_models_catalog_pending;
+DROP INDEX IF EXISTS idx_models_license;
+DROP INDEX IF EXISTS idx_models_framework;
+DROP INDEX IF EXISTS idx_models_search;
+
+ALTER TABLE models
+    DROP COLUMN IF EXISTS search_document,
+    DROP COLUMN IF EXISTS catalog_attempts,
+    DROP COLUMN IF EXISTS catalog_error,
+    DROP COLUMN IF EXISTS catalog_uri,
+    DROP COLUMN IF EXISTS input_shape,
+    DROP COLUMN IF EXISTS framework,
+    DROP COLUMN IF EXISTS license,
+    DROP COLUMN IF EXISTS description,
+    DROP COLUMN IF EXISTS name,
+    DROP COLUMN IF EXISTS metadata_uri;
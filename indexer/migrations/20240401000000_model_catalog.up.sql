@@ -0,0 +1,24 @@
+-- Catalog documents anchored by `set_metadata_uri` and parsed by the catalog fetcher
+ALTER TABLE models
+    ADD COLUMN IF NOT EXISTS metadata_uri TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS name TEXT,
+    ADD COLUMN IF NOT EXISTS description TEXT,
+    ADD COLUMN IF NOT EXISTS license TEXT,
+    ADD COLUMN IF NOT EXISTS framework TEXT,
+    -- NULL elements are dynamic dimensions
+    ADD COLUMN IF NOT EXISTS input_shape BIGINT[],
+    -- URI the columns above were parsed from; differs from metadata_uri while a fetch is pending
+    ADD COLUMN IF NOT EXISTS catalog_uri TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS catalog_error TEXT,
+    ADD COLUMN IF NOT EXISTS catalog_attempts INT NOT NULL DEFAULT 0;
+
+ALTER TABLE models
+    ADD COLUMN IF NOT EXISTS search_document TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', COALESCE(name, '')), 'A')
+        || setweight(to_tsvector('english', COALESCE(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_models_search ON models USING GIN (search_document);
+CREATE INDEX IF NOT EXISTS idx_models_framework ON models (framework);
+CREATE INDEX IF NOT EXISTS idx_models_license ON models (license);
+CREATE INDEX IF NOT EXISTS idx_models_catalog_pending ON models (id) WHERE metadata_uri <> catalog_uri;
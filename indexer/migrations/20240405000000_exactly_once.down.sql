@@ -0,0 +1,20 @@
+UPDATE projection_events
+SET event_key = split_part(event_key, ':', 1)
+    || CASE WHEN split_part(event_key, ':', 3) = '0' THEN '' ELSE '#' || split_part(event_key, ':', 3) END;
+
+ALTER TABLE projection_events RENAME COLUMN event_key TO signature;
+
+DROP TABLE IF EXISTS event_outbox;
+DROP TABLE IF EXISTS processed_events;
+
+DROP INDEX IF EXISTS idx_events_key;
+
+UPDATE events
+SET signature = signature || '#' || instruction_index || '.' || event_index
+WHERE instruction_index > 0 OR event_index > 0;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_signature ON events (signature);
+
+ALTER TABLE events
+    DROP COLUMN IF EXISTS instruction_index,
+    DROP COLUMN IF EXISTS event_index;
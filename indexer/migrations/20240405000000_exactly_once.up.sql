@@ -0,0 +1,51 @@
+-- Exactly-once event processing: events are keyed by (signature, instruction, event)
+-- and reach Kafka through a transactional outbox
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS instruction_index INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS event_index INTEGER NOT NULL DEFAULT 0;
+
+-- Later events of a transaction were stored as `<signature>#<n>`
+UPDATE events
+SET event_index = split_part(signature, '#', 2)::INTEGER,
+    signature = split_part(signature, '#', 1)
+WHERE signature LIKE '%#%';
+
+DROP INDEX IF EXISTS idx_events_signature;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_key ON events (signature, instruction_index, event_index);
+
+-- Idempotency keys of applied events; a slot rollback releases its keys
+CREATE TABLE IF NOT EXISTS processed_events (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    processed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (signature, instruction_index, event_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_processed_events_slot ON processed_events (slot);
+
+INSERT INTO processed_events (signature, instruction_index, event_index, slot)
+SELECT signature, instruction_index, event_index, slot FROM events
+ON CONFLICT DO NOTHING;
+
+-- Kafka messages written in the same transaction as the rows they describe
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    topic TEXT NOT NULL,
+    message_key TEXT NOT NULL,
+    -- NULL for retractions
+    payload BYTEA,
+    headers JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    published_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_pending ON event_outbox (id) WHERE published_at IS NULL;
+
+-- Projections are keyed by the Kafka message key, now the idempotency key
+ALTER TABLE projection_events RENAME COLUMN signature TO event_key;
+
+UPDATE projection_events
+SET event_key = split_part(event_key, '#', 1) || ':0:' || COALESCE(NULLIF(split_part(event_key, '#', 2), ''), '0');
@@ -0,0 +1,18 @@
+-- Retention deadlines from contributors' on-chain consent; the enforcer deletes
+-- each encrypted blob once its deadline passes
+
+CREATE TABLE IF NOT EXISTS contribution_retention (
+    model_id TEXT NOT NULL,
+    data_hash TEXT NOT NULL,
+    contributor TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    withdrawn BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Set once the blob store confirms the blob is gone
+    deleted_at TIMESTAMPTZ,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    PRIMARY KEY (model_id, data_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contribution_retention_due
+    ON contribution_retention (expires_at) WHERE deleted_at IS NULL;
@@ -0,0 +1,14 @@
+-- Right-to-erasure log: one row per revoked contribution, kept after the
+-- purge as evidence of when the request was received and fulfilled
+
+CREATE TABLE IF NOT EXISTS contribution_erasures (
+    model_id TEXT NOT NULL,
+    data_hash TEXT NOT NULL,
+    contributor TEXT NOT NULL,
+    revoked_at TIMESTAMPTZ NOT NULL,
+    -- Set when the blob store confirms the encrypted data is gone
+    purged_at TIMESTAMPTZ,
+    PRIMARY KEY (model_id, data_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contribution_erasures_contributor ON contribution_erasures (contributor);
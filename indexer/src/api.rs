@@ -0,0 +1,227 @@
+// indexer/src/api.rs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::error;
+
+/// Deepest fork tree returned when `depth` is not given
+const DEFAULT_FORK_DEPTH: i32 = 16;
+/// Page size cap for `/models`
+const MAX_SEARCH_LIMIT: i64 = 200;
+
+/// Read-only model queries served next to `/health`: discovery, fork trees and the erasure log
+pub fn routes(db_pool: PgPool) -> Router {
+    Router::new()
+        .route("/models", get(search_models))
+        .route("/models/:id/forks", get(list_forks))
+        .route("/models/:id/erasures", get(list_erasures))
+        .with_state(db_pool)
+}
+
+/// `/models` filters; `tag` may repeat and every tag must match
+#[derive(Debug)]
+struct ModelSearch {
+    /// Full-text query over catalog name and description
+    query: Option<String>,
+    framework: Option<String>,
+    tags: Vec<String>,
+    max_fee: Option<i64>,
+    sort: &'static str,
+    limit: i64,
+}
+
+impl ModelSearch {
+    fn parse(params: Vec<(String, String)>) -> Result<Self, StatusCode> {
+        let mut search = Self {
+            query: None,
+            framework: None,
+            tags: Vec::new(),
+            max_fee: None,
+            sort: "popularity",
+            limit: 50,
+        };
+        for (key, value) in params {
+            match key.as_str() {
+                "q" => search.query = Some(value).filter(|q| !q.trim().is_empty()),
+                "framework" => search.framework = Some(value),
+                "tag" => search.tags.push(value),
+                "max_fee" => search.max_fee = Some(value.parse().map_err(|_| StatusCode::BAD_REQUEST)?),
+                "limit" => {
+                    let limit: i64 = value.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+                    search.limit = limit.clamp(1, MAX_SEARCH_LIMIT);
+                }
+                "sort" => {
+                    search.sort = match value.as_str() {
+                        "popularity" => "popularity",
+                        "newest" => "newest",
+                        "fee" => "fee",
+                        _ => return Err(StatusCode::BAD_REQUEST),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(search)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ModelSummary {
+    id: String,
+    name: Option<String>,
+    owner: String,
+    active_version: i64,
+    inference_fee: i64,
+    framework: Option<String>,
+    license: Option<String>,
+    tags: Vec<String>,
+    inferences: i64,
+    status: String,
+}
+
+/// Active models matching the text query, framework, every tag and the fee ceiling
+async fn search_models(
+    State(db_pool): State<PgPool>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Json<Vec<ModelSummary>>, StatusCode> {
+    let search = ModelSearch::parse(params)?;
+
+    let models = sqlx::query_as!(
+        ModelSummary,
+        r#"SELECT m.id, m.name, m.owner, m.active_version, m.inference_fee, m.framework, m.license,
+                  m.tags, m.status, COALESCE(SUM(c.completed), 0)::BIGINT AS "inferences!"
+           FROM models m
+           LEFT JOIN inference_daily_counts c ON c.model_id = m.id
+           WHERE m.status = 'active'
+             AND m.tags @> $1
+             AND ($2::BIGINT IS NULL OR m.inference_fee <= $2)
+             AND ($5::TEXT IS NULL OR m.search_document @@ websearch_to_tsquery('english', $5))
+             AND ($6::TEXT IS NULL OR m.framework = $6)
+           GROUP BY m.id
+           ORDER BY CASE WHEN $5::TEXT IS NOT NULL
+                         THEN ts_rank(m.search_document, websearch_to_tsquery('english', $5)) END DESC,
+                    CASE WHEN $3 = 'popularity' THEN COALESCE(SUM(c.completed), 0) END DESC,
+                    CASE WHEN $3 = 'newest' THEN m.created_at END DESC,
+                    CASE WHEN $3 = 'fee' THEN m.inference_fee END ASC,
+                    m.id
+           LIMIT $4"#,
+        &search.tags,
+        search.max_fee,
+        search.sort,
+        search.limit,
+        search.query,
+        search.framework
+    )
+    .fetch_all(&db_pool)
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Model search failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(models))
+}
+
+#[derive(Debug, Deserialize)]
+struct ForkQuery {
+    depth: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+struct ForkNode {
+    id: String,
+    parent_id: String,
+    owner: String,
+    upstream_royalty_bps: i32,
+    depth: i32,
+    status: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ForkTree {
+    model: String,
+    forks: Vec<ForkNode>,
+}
+
+/// Every descendant of a model, breadth-first, down to `depth` generations
+async fn list_forks(
+    State(db_pool): State<PgPool>,
+    Path(model_id): Path<String>,
+    Query(query): Query<ForkQuery>,
+) -> Result<Json<ForkTree>, StatusCode> {
+    let depth = query.depth.unwrap_or(DEFAULT_FORK_DEPTH).clamp(1, DEFAULT_FORK_DEPTH);
+    let internal = |e: sqlx::Error| {
+        error!(error = %e, model = %model_id, "Fork tree query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    let known = sqlx::query_scalar!("SELECT id FROM models WHERE id = $1", model_id)
+        .fetch_optional(&db_pool)
+        .await
+        .map_err(internal)?;
+    if known.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let forks = sqlx::query_as!(
+        ForkNode,
+        r#"WITH RECURSIVE tree AS (
+               SELECT id, parent_id, owner, upstream_royalty_bps, status, created_at, 1 AS depth
+               FROM models WHERE parent_id = $1
+               UNION ALL
+               SELECT m.id, m.parent_id, m.owner, m.upstream_royalty_bps, m.status, m.created_at, t.depth + 1
+               FROM models m JOIN tree t ON m.parent_id = t.id
+               WHERE t.depth < $2
+           )
+           SELECT id AS "id!", parent_id AS "parent_id!", owner AS "owner!",
+                  upstream_royalty_bps AS "upstream_royalty_bps!", depth AS "depth!", status AS "status!"
+           FROM tree ORDER BY depth, created_at"#,
+        model_id,
+        depth
+    )
+    .fetch_all(&db_pool)
+    .await
+    .map_err(internal)?;
+
+    Ok(Json(ForkTree { model: model_id, forks }))
+}
+
+#[derive(Debug, Serialize)]
+struct Erasure {
+    data_hash: String,
+    contributor: String,
+    /// Unix seconds
+    revoked_at: i64,
+    /// `None` while the encrypted data is still being purged
+    purged_at: Option<i64>,
+}
+
+/// Revoked contributions of a model, newest first. Aggregators exclude these
+/// data hashes; auditors check each was purged.
+async fn list_erasures(
+    State(db_pool): State<PgPool>,
+    Path(model_id): Path<String>,
+) -> Result<Json<Vec<Erasure>>, StatusCode> {
+    sqlx::query_as!(
+        Erasure,
+        r#"SELECT data_hash, contributor,
+                  EXTRACT(EPOCH FROM revoked_at)::BIGINT AS "revoked_at!",
+                  EXTRACT(EPOCH FROM purged_at)::BIGINT AS purged_at
+           FROM contribution_erasures
+           WHERE model_id = $1 ORDER BY revoked_at DESC"#,
+        model_id
+    )
+    .fetch_all(&db_pool)
+    .await
+    .map(Json)
+    .map_err(|e| {
+        error!(error = %e, model = %model_id, "Erasure log query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
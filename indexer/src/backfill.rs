@@ -0,0 +1,148 @@
+// indexer/src/backfill.rs
+
+use crate::solana_listener::SolanaEventListener;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{clock::Slot, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::{str::FromStr, sync::Arc};
+use tracing::{info, instrument, warn};
+
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+
+/// Inclusive slot range to replay
+#[derive(Debug, Clone, Copy)]
+pub struct SlotRange {
+    pub from: Slot,
+    pub to: Slot,
+}
+
+impl SlotRange {
+    pub fn contains(&self, slot: Slot) -> bool {
+        slot >= self.from && slot <= self.to
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BackfillStats {
+    pub signatures_scanned: u64,
+    pub events_replayed: u64,
+    pub failed_transactions: u64,
+}
+
+/// Replays historical program transactions through the live event handlers
+pub struct Backfiller {
+    rpc_client: Arc<RpcClient>,
+    listener: SolanaEventListener,
+}
+
+impl Backfiller {
+    pub fn new(rpc_client: Arc<RpcClient>, listener: SolanaEventListener) -> Self {
+        Self { rpc_client, listener }
+    }
+
+    /// Backfill every program in `programs` over `range`
+    pub async fn run(&self, programs: &[Pubkey], range: SlotRange) -> anyhow::Result<BackfillStats> {
+        let mut stats = BackfillStats::default();
+        for program_id in programs {
+            self.backfill_program(program_id, range, &mut stats).await?;
+        }
+
+        info!(
+            signatures = stats.signatures_scanned,
+            events = stats.events_replayed,
+            failed = stats.failed_transactions,
+            "Backfill complete"
+        );
+        Ok(stats)
+    }
+
+    #[instrument(skip(self, stats))]
+    async fn backfill_program(
+        &self,
+        program_id: &Pubkey,
+        range: SlotRange,
+        stats: &mut BackfillStats,
+    ) -> anyhow::Result<()> {
+        // Signatures come back newest-first, so collect the page set and
+        // replay oldest-first to keep handler ordering identical to live.
+        let mut in_range = Vec::new();
+        let mut before: Option<Signature> = None;
+
+        loop {
+            let page = self
+                .rpc_client
+                .get_signatures_for_address_with_config(
+                    program_id,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until: None,
+                        limit: Some(SIGNATURE_PAGE_SIZE),
+                        commitment: Some(CommitmentConfig::finalized()),
+                    },
+                )
+                .await?;
+
+            let Some(last) = page.last() else { break };
+            before = Some(Signature::from_str(&last.signature)?);
+            let reached_start = last.slot < range.from;
+
+            stats.signatures_scanned += page.len() as u64;
+            in_range.extend(
+                page.into_iter()
+                    .filter(|s| s.err.is_none() && range.contains(s.slot)),
+            );
+
+            if reached_start {
+                break;
+            }
+        }
+
+        info!(%program_id, transactions = in_range.len(), "Replaying transactions");
+
+        for status in in_range.into_iter().rev() {
+            let signature = Signature::from_str(&status.signature)?;
+            match self.replay_transaction(&signature, status.slot).await {
+                Ok(replayed) => stats.events_replayed += replayed,
+                Err(e) => {
+                    stats.failed_transactions += 1;
+                    warn!(%signature, error = %e, "Failed to replay transaction");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replay one finalized transaction; returns the number of events it held
+    pub(crate) async fn replay_transaction(&self, signature: &Signature, slot: Slot) -> anyhow::Result<u64> {
+        let tx = self
+            .rpc_client
+            .get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::finalized()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+
+        let Some(meta) = tx.transaction.meta else { return Ok(0) };
+        if meta.err.is_some() {
+            return Ok(0);
+        }
+        let logs: Vec<String> = Option::from(meta.log_messages).unwrap_or_default();
+
+        // Same path as the live listener; replays are no-ops per idempotency key
+        let events = self.listener.parse_logs(&signature.to_string(), slot as i64, &logs);
+        let count = events.len() as u64;
+        for event in events {
+            self.listener.handle_event(event).await?;
+        }
+        Ok(count)
+    }
+}
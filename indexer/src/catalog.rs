@@ -0,0 +1,222 @@
+// indexer/src/catalog.rs
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tokio::{
+    task::JoinHandle,
+    time::{interval, Duration},
+};
+use tracing::{info, warn};
+
+/// Frameworks accepted as the `framework` search facet; mirrors the client's list
+const FRAMEWORKS: [&str; 6] = ["onnx", "pytorch", "tensorflow", "tflite", "tensorrt", "jax"];
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+const MAX_TAGS: usize = 16;
+const MAX_TAG_LEN: usize = 32;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogConfig {
+    #[serde(default = "default_ipfs_gateway")]
+    pub ipfs_gateway: String,
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+    /// Failed fetches of one URI before it is left alone until the URI changes
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: i32,
+    #[serde(default = "default_max_document_bytes")]
+    pub max_document_bytes: usize,
+}
+
+impl Default for CatalogConfig {
+    fn default() -> Self {
+        Self {
+            ipfs_gateway: default_ipfs_gateway(),
+            poll_interval_secs: default_poll_interval_secs(),
+            batch_size: default_batch_size(),
+            max_attempts: default_max_attempts(),
+            max_document_bytes: default_max_document_bytes(),
+        }
+    }
+}
+
+fn default_ipfs_gateway() -> String {
+    "https://ipfs.io".to_string()
+}
+
+fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_batch_size() -> i64 {
+    20
+}
+
+fn default_max_attempts() -> i32 {
+    5
+}
+
+fn default_max_document_bytes() -> usize {
+    64 * 1024
+}
+
+/// Catalog document published by `scoria model set-metadata`
+#[derive(Debug, Deserialize)]
+struct CatalogDocument {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    license: String,
+    framework: String,
+    input_shape: Vec<Option<i64>>,
+}
+
+impl CatalogDocument {
+    /// The client checks these before pinning, but other tools may pin anything
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.chars().count() > MAX_NAME_LEN {
+            return Err(format!("name must be 1 to {MAX_NAME_LEN} characters"));
+        }
+        if self.description.chars().count() > MAX_DESCRIPTION_LEN {
+            return Err(format!("description exceeds {MAX_DESCRIPTION_LEN} characters"));
+        }
+        if self.tags.len() > MAX_TAGS {
+            return Err(format!("at most {MAX_TAGS} tags are allowed"));
+        }
+        let valid_tag = |t: &String| {
+            !t.is_empty()
+                && t.len() <= MAX_TAG_LEN
+                && t.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        };
+        if let Some(tag) = self.tags.iter().find(|t| !valid_tag(t)) {
+            return Err(format!("invalid tag `{tag}`"));
+        }
+        if self.license.is_empty() {
+            return Err("license is required".to_string());
+        }
+        if !FRAMEWORKS.contains(&self.framework.as_str()) {
+            return Err(format!("unknown framework `{}`", self.framework));
+        }
+        if self.input_shape.is_empty() || self.input_shape.iter().flatten().any(|&d| d <= 0) {
+            return Err("input shape must have positive or dynamic dimensions".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Resolve anchored catalog URIs into searchable `models` columns
+pub fn spawn_fetcher(db_pool: PgPool, config: CatalogConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let http = match reqwest::Client::builder().timeout(Duration::from_secs(20)).build() {
+            Ok(http) => http,
+            Err(e) => {
+                warn!(error = %e, "Catalog fetcher disabled");
+                return;
+            }
+        };
+        let mut ticker = interval(Duration::from_secs(config.poll_interval_secs));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = refresh_pending(&db_pool, &http, &config).await {
+                warn!(error = %e, "Catalog refresh failed");
+            }
+        }
+    })
+}
+
+async fn refresh_pending(db_pool: &PgPool, http: &reqwest::Client, config: &CatalogConfig) -> anyhow::Result<()> {
+    let pending = sqlx::query!(
+        r#"SELECT id, metadata_uri FROM models
+           WHERE metadata_uri <> catalog_uri AND catalog_attempts < $1
+           ORDER BY catalog_attempts, id
+           LIMIT $2"#,
+        config.max_attempts,
+        config.batch_size
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    for model in pending {
+        match fetch_document(http, config, &model.metadata_uri).await {
+            Ok(document) => {
+                // Guarded on the URI so a newer `set_metadata_uri` is never overwritten
+                sqlx::query!(
+                    r#"UPDATE models
+                       SET name = $3, description = $4, tags = $5, license = $6, framework = $7,
+                           input_shape = $8, catalog_uri = metadata_uri, catalog_error = NULL,
+                           catalog_attempts = 0
+                       WHERE id = $1 AND metadata_uri = $2"#,
+                    model.id,
+                    model.metadata_uri,
+                    document.name,
+                    document.description,
+                    &document.tags,
+                    document.license,
+                    document.framework,
+                    &document.input_shape as &[Option<i64>]
+                )
+                .execute(db_pool)
+                .await?;
+                metrics::increment_counter!("catalog_documents_indexed_total");
+                info!(model = %model.id, uri = %model.metadata_uri, "Catalog indexed");
+            }
+            Err(e) => {
+                sqlx::query!(
+                    r#"UPDATE models
+                       SET catalog_error = $3, catalog_attempts = catalog_attempts + 1
+                       WHERE id = $1 AND metadata_uri = $2"#,
+                    model.id,
+                    model.metadata_uri,
+                    e.to_string()
+                )
+                .execute(db_pool)
+                .await?;
+                metrics::increment_counter!("catalog_fetch_failures_total");
+                warn!(model = %model.id, uri = %model.metadata_uri, error = %e, "Catalog fetch failed");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fetch through the gateway, then check the bytes against the CID rather than trusting it
+async fn fetch_document(http: &reqwest::Client, config: &CatalogConfig, uri: &str) -> anyhow::Result<CatalogDocument> {
+    let cid = uri
+        .strip_prefix("ipfs://")
+        .ok_or_else(|| anyhow::anyhow!("not an ipfs:// URI"))?;
+    let url = format!("{}/ipfs/{cid}", config.ipfs_gateway.trim_end_matches('/'));
+    let body = http.get(url).send().await?.error_for_status()?.bytes().await?;
+    anyhow::ensure!(body.len() <= config.max_document_bytes, "document exceeds {} bytes", config.max_document_bytes);
+    anyhow::ensure!(raw_cid(&body) == cid, "document does not hash to {cid}");
+
+    let document: CatalogDocument = serde_json::from_slice(&body)?;
+    document.validate().map_err(anyhow::Error::msg)?;
+    Ok(document)
+}
+
+/// CIDv1 (raw codec, sha2-256), as computed by the client before pinning
+fn raw_cid(document: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut bytes = vec![0x01, 0x55, 0x12, 0x20];
+    bytes.extend_from_slice(&Sha256::digest(document));
+
+    let mut out = String::from("b");
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
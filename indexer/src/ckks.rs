@@ -0,0 +1,327 @@
+// indexer/src/ckks.rs
+
+//! Additive CKKS (Cheon-Kim-Kim-Song) for homomorphic FL aggregation.
+//!
+//! Ring Z_q[X]/(X^N + 1) with N = 4096 and a single 62-bit prime q, which the
+//! homomorphic encryption standard rates at 128-bit security. Only addition
+//! is needed to sum updates, so there is no relinearization or rescaling.
+//! Each ciphertext batches `SLOTS` real values; the sum of every update must
+//! stay below `MAX_SUM` in magnitude per slot.
+
+use anyhow::ensure;
+use rand::{rngs::OsRng, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+pub const RING_DEGREE: usize = 4096;
+/// Values packed per ciphertext
+pub const SLOTS: usize = RING_DEGREE / 2;
+/// Prime with q = 1 mod 2N, so the negacyclic NTT exists
+pub const MODULUS: u64 = 0x3fff_ffff_ffff_0001;
+/// Encoding scale; precision is about 2^-40 times the noise growth
+pub const SCALE: f64 = (1u64 << 40) as f64;
+/// Largest per-slot magnitude a decrypted sum may reach
+pub const MAX_SUM: f64 = (1u64 << 20) as f64;
+
+/// Centered binomial parameter; error stddev sqrt(21 / 2) = 3.24
+const ERROR_ETA: u32 = 21;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SecretKey {
+    /// Ternary secret in NTT form
+    s: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicKey {
+    /// (-a·s + e, a) in NTT form
+    b: Vec<u64>,
+    a: Vec<u64>,
+}
+
+/// Encryption of `SLOTS` values, coefficients in NTT form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ciphertext {
+    c0: Vec<u64>,
+    c1: Vec<u64>,
+}
+
+/// Precomputed NTT and embedding tables; build once and share
+pub struct Ckks {
+    psi_powers: Vec<u64>,
+    psi_inv_powers: Vec<u64>,
+    omega_powers: Vec<u64>,
+    omega_inv_powers: Vec<u64>,
+    n_inv: u64,
+    rot_group: Vec<usize>,
+    ksi_powers: Vec<(f64, f64)>,
+}
+
+impl Default for Ckks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ckks {
+    pub fn new() -> Self {
+        let n = RING_DEGREE as u64;
+        // psi generates the 2N-th roots of unity: psi^N = -1
+        let psi = (2..)
+            .map(|x| pow_mod(x, (MODULUS - 1) / (2 * n)))
+            .find(|&psi| pow_mod(psi, n) == MODULUS - 1)
+            .unwrap();
+        let psi_inv = pow_mod(psi, MODULUS - 2);
+        let powers = |base: u64, count: usize| {
+            let mut powers = Vec::with_capacity(count);
+            let mut current = 1u64;
+            for _ in 0..count {
+                powers.push(current);
+                current = mul_mod(current, base);
+            }
+            powers
+        };
+        let m = 2 * RING_DEGREE;
+        let mut rot_group = Vec::with_capacity(SLOTS);
+        let mut five = 1usize;
+        for _ in 0..SLOTS {
+            rot_group.push(five);
+            five = five * 5 % m;
+        }
+        Self {
+            psi_powers: powers(psi, RING_DEGREE),
+            psi_inv_powers: powers(psi_inv, RING_DEGREE),
+            omega_powers: powers(mul_mod(psi, psi), RING_DEGREE / 2),
+            omega_inv_powers: powers(mul_mod(psi_inv, psi_inv), RING_DEGREE / 2),
+            n_inv: pow_mod(n, MODULUS - 2),
+            rot_group,
+            ksi_powers: (0..=m).map(|k| (2.0 * PI * k as f64 / m as f64).sin_cos()).map(|(s, c)| (c, s)).collect(),
+        }
+    }
+
+    pub fn generate_keys(&self) -> (SecretKey, PublicKey) {
+        let s = self.forward(ternary());
+        let a: Vec<u64> = (0..RING_DEGREE).map(|_| OsRng.gen_range(0..MODULUS)).collect();
+        let e = self.forward(centered_binomial());
+        let b = (0..RING_DEGREE).map(|i| sub_mod(e[i], mul_mod(a[i], s[i]))).collect();
+        (SecretKey { s }, PublicKey { b, a })
+    }
+
+    /// Encrypt `values`, `SLOTS` per ciphertext
+    pub fn encrypt(&self, pk: &PublicKey, values: &[f32]) -> anyhow::Result<Vec<Ciphertext>> {
+        ensure!(values.iter().all(|v| v.is_finite() && (v.abs() as f64) < MAX_SUM), "value out of CKKS range");
+        Ok(values
+            .chunks(SLOTS)
+            .map(|chunk| {
+                let m = self.forward(self.encode(chunk));
+                let u = self.forward(ternary());
+                let e0 = self.forward(centered_binomial());
+                let e1 = self.forward(centered_binomial());
+                let c0 = (0..RING_DEGREE).map(|i| add_mod(add_mod(mul_mod(pk.b[i], u[i]), e0[i]), m[i])).collect();
+                let c1 = (0..RING_DEGREE).map(|i| add_mod(mul_mod(pk.a[i], u[i]), e1[i])).collect();
+                Ciphertext { c0, c1 }
+            })
+            .collect())
+    }
+
+    /// `sum += other`, ciphertext by ciphertext
+    pub fn add_assign(&self, sum: &mut [Ciphertext], other: &[Ciphertext]) -> anyhow::Result<()> {
+        ensure!(sum.len() == other.len(), "ciphertext counts differ: {} vs {}", sum.len(), other.len());
+        for (acc, ct) in sum.iter_mut().zip(other) {
+            ensure!(ct.c0.len() == RING_DEGREE && ct.c1.len() == RING_DEGREE, "malformed ciphertext");
+            for i in 0..RING_DEGREE {
+                acc.c0[i] = add_mod(acc.c0[i], ct.c0[i]);
+                acc.c1[i] = add_mod(acc.c1[i], ct.c1[i]);
+            }
+        }
+        Ok(())
+    }
+
+    /// First `len` values of the decrypted vector
+    pub fn decrypt(&self, sk: &SecretKey, ciphertexts: &[Ciphertext], len: usize) -> anyhow::Result<Vec<f64>> {
+        ensure!(len <= ciphertexts.len() * SLOTS, "{} values do not fit {} ciphertexts", len, ciphertexts.len());
+        let mut values = Vec::with_capacity(ciphertexts.len() * SLOTS);
+        for ct in ciphertexts {
+            ensure!(ct.c0.len() == RING_DEGREE && ct.c1.len() == RING_DEGREE, "malformed ciphertext");
+            let m = (0..RING_DEGREE).map(|i| add_mod(ct.c0[i], mul_mod(ct.c1[i], sk.s[i]))).collect();
+            values.extend(self.decode(&self.inverse(m)));
+        }
+        values.truncate(len);
+        Ok(values)
+    }
+
+    /// Scaled coefficients of the polynomial whose canonical embedding
+    /// carries `values` (imaginary parts zero), as signed residues mod q
+    fn encode(&self, values: &[f32]) -> Vec<u64> {
+        let mut slots: Vec<(f64, f64)> = values.iter().map(|&v| (v as f64, 0.0)).collect();
+        slots.resize(SLOTS, (0.0, 0.0));
+        self.fft_special_inv(&mut slots);
+        let mut coefficients = vec![0u64; RING_DEGREE];
+        for (i, (re, im)) in slots.into_iter().enumerate() {
+            coefficients[i] = from_signed((re * SCALE).round() as i64);
+            coefficients[SLOTS + i] = from_signed((im * SCALE).round() as i64);
+        }
+        coefficients
+    }
+
+    fn decode(&self, coefficients: &[u64]) -> Vec<f64> {
+        let mut slots: Vec<(f64, f64)> = (0..SLOTS)
+            .map(|i| (to_signed(coefficients[i]) as f64 / SCALE, to_signed(coefficients[SLOTS + i]) as f64 / SCALE))
+            .collect();
+        self.fft_special(&mut slots);
+        slots.into_iter().map(|(re, _)| re).collect()
+    }
+
+    /// Evaluate at the primitive roots ζ^(5^j), j < SLOTS
+    fn fft_special(&self, values: &mut [(f64, f64)]) {
+        let size = values.len();
+        let m = 2 * RING_DEGREE;
+        bit_reverse(values);
+        let mut len = 2;
+        while len <= size {
+            let (lenh, lenq) = (len / 2, len * 4);
+            for i in (0..size).step_by(len) {
+                for j in 0..lenh {
+                    let k = (self.rot_group[j] % lenq) * m / lenq;
+                    let u = values[i + j];
+                    let v = complex_mul(values[i + j + lenh], self.ksi_powers[k]);
+                    values[i + j] = (u.0 + v.0, u.1 + v.1);
+                    values[i + j + lenh] = (u.0 - v.0, u.1 - v.1);
+                }
+            }
+            len *= 2;
+        }
+    }
+
+    fn fft_special_inv(&self, values: &mut [(f64, f64)]) {
+        let size = values.len();
+        let m = 2 * RING_DEGREE;
+        let mut len = size;
+        while len >= 2 {
+            let (lenh, lenq) = (len / 2, len * 4);
+            for i in (0..size).step_by(len) {
+                for j in 0..lenh {
+                    let k = (lenq - self.rot_group[j] % lenq) * m / lenq;
+                    let (a, b) = (values[i + j], values[i + j + lenh]);
+                    values[i + j] = (a.0 + b.0, a.1 + b.1);
+                    values[i + j + lenh] = complex_mul((a.0 - b.0, a.1 - b.1), self.ksi_powers[k]);
+                }
+            }
+            len /= 2;
+        }
+        bit_reverse(values);
+        for v in values.iter_mut() {
+            *v = (v.0 / size as f64, v.1 / size as f64);
+        }
+    }
+
+    /// Negacyclic NTT: coefficients to evaluations
+    fn forward(&self, mut a: Vec<u64>) -> Vec<u64> {
+        for (x, psi) in a.iter_mut().zip(&self.psi_powers) {
+            *x = mul_mod(*x, *psi);
+        }
+        ntt(&mut a, &self.omega_powers);
+        a
+    }
+
+    fn inverse(&self, mut a: Vec<u64>) -> Vec<u64> {
+        ntt(&mut a, &self.omega_inv_powers);
+        for (x, psi_inv) in a.iter_mut().zip(&self.psi_inv_powers) {
+            *x = mul_mod(mul_mod(*x, *psi_inv), self.n_inv);
+        }
+        a
+    }
+}
+
+/// In-place cyclic NTT; `omega_powers[k]` = omega^k for k < N/2
+fn ntt(a: &mut [u64], omega_powers: &[u64]) {
+    let n = a.len();
+    bit_reverse(a);
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for j in 0..len / 2 {
+                let u = a[start + j];
+                let v = mul_mod(a[start + j + len / 2], omega_powers[j * stride]);
+                a[start + j] = add_mod(u, v);
+                a[start + j + len / 2] = sub_mod(u, v);
+            }
+        }
+        len *= 2;
+    }
+}
+
+fn bit_reverse<T>(values: &mut [T]) {
+    let bits = values.len().trailing_zeros();
+    for i in 0..values.len() {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+fn ternary() -> Vec<u64> {
+    (0..RING_DEGREE).map(|_| from_signed(OsRng.gen_range(-1..=1))).collect()
+}
+
+fn centered_binomial() -> Vec<u64> {
+    (0..RING_DEGREE)
+        .map(|_| {
+            let bits = OsRng.next_u64();
+            let ones = (bits & ((1 << ERROR_ETA) - 1)).count_ones() as i64;
+            let others = ((bits >> ERROR_ETA) & ((1 << ERROR_ETA) - 1)).count_ones() as i64;
+            from_signed(ones - others)
+        })
+        .collect()
+}
+
+fn complex_mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+fn from_signed(v: i64) -> u64 {
+    v.rem_euclid(MODULUS as i64) as u64
+}
+
+fn to_signed(v: u64) -> i64 {
+    if v > MODULUS / 2 {
+        v as i64 - MODULUS as i64
+    } else {
+        v as i64
+    }
+}
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    let sum = a + b;
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+fn sub_mod(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + MODULUS - b
+    }
+}
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base);
+        }
+        base = mul_mod(base, base);
+        exp >>= 1;
+    }
+    result
+}
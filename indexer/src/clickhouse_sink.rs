@@ -0,0 +1,340 @@
+// indexer/src/clickhouse_sink.rs
+
+use crate::solana_listener::{ProgramEvent, ProgramEventType};
+use anyhow::Context;
+use clickhouse::{Client, Row};
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    message::{BorrowedMessage, Headers, Message},
+    Offset, TopicPartitionList,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::mpsc,
+    time::{interval, Duration},
+};
+use tracing::{info, instrument};
+
+const EVENTS_TABLE: &str = "program_events";
+const RETRACTIONS_TABLE: &str = "program_event_retractions";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClickHouseConfig {
+    /// HTTP endpoint, e.g. `http://clickhouse:8123`
+    pub dsn: String,
+    #[serde(default = "default_database")]
+    pub database: String,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    #[serde(default = "default_consumer_group")]
+    pub consumer_group: String,
+    /// Rows buffered before an insert is forced
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Upper bound on how long a row waits in the buffer
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+}
+
+fn default_database() -> String {
+    "scoria".to_string()
+}
+
+fn default_consumer_group() -> String {
+    "scoria-clickhouse".to_string()
+}
+
+fn default_batch_size() -> usize {
+    10_000
+}
+
+fn default_flush_interval_ms() -> u64 {
+    1_000
+}
+
+/// One row per program event; columns not used by an event type stay empty
+#[derive(Debug, Clone, Row, Serialize)]
+pub struct EventRow {
+    pub signature: String,
+    pub instruction_index: u32,
+    pub event_index: u32,
+    pub slot: u64,
+    pub event_type: String,
+    pub event_time: u32,
+    pub model_id: String,
+    /// Provider, contributor, voter or proposal author
+    pub actor: String,
+    /// Challenger, payee or vote delegate
+    pub counterparty: String,
+    pub status: String,
+    pub fee: u64,
+    pub fee_mint: String,
+    /// Slashed lamports or effective vote weight
+    pub amount: u64,
+    pub proposal_id: String,
+    pub payload: String,
+}
+
+#[derive(Debug, Clone, Row, Serialize)]
+struct RetractionRow {
+    signature: String,
+    retracted_at: u32,
+}
+
+impl EventRow {
+    fn from_event(event: &ProgramEvent, indexed_at: u32) -> anyhow::Result<Self> {
+        let mut row = Self {
+            signature: event.signature.clone(),
+            instruction_index: event.instruction_index,
+            event_index: event.event_index,
+            slot: event.slot as u64,
+            event_type: event.event_type().to_string(),
+            event_time: indexed_at,
+            model_id: event.inner.model_id().unwrap_or_default(),
+            actor: String::new(),
+            counterparty: String::new(),
+            status: String::new(),
+            fee: 0,
+            fee_mint: String::new(),
+            amount: 0,
+            proposal_id: String::new(),
+            payload: serde_json::to_string(&event.inner)?,
+        };
+
+        match &event.inner {
+            ProgramEventType::ModelRegistered(model) => {
+                row.actor = model.owner.to_string();
+            }
+            ProgramEventType::ModelExpired(expiry) => {
+                row.actor = expiry.owner.clone();
+                row.counterparty = expiry.reclaimed_by.clone();
+                row.event_time = expiry.reclaimed_at as u32;
+            }
+            ProgramEventType::ModelForked(fork) => {
+                row.actor = fork.owner.clone();
+                row.counterparty = fork.parent_id.clone();
+                row.amount = fork.royalty_bps as u64;
+                row.event_time = fork.timestamp as u32;
+            }
+            ProgramEventType::ModelMetadataSet(metadata) => {
+                row.actor = metadata.changed_by.clone();
+                row.status = metadata.metadata_uri.clone();
+                row.event_time = metadata.timestamp as u32;
+            }
+            ProgramEventType::VersionUpdated(update) => {
+                row.amount = update.version;
+                row.event_time = update.timestamp as u32;
+            }
+            ProgramEventType::InferenceRequested(request) => {
+                row.counterparty = request.request_id.clone();
+                row.status = "requested".to_string();
+                row.fee = request.fee;
+                row.fee_mint = request.fee_mint.clone().unwrap_or_default();
+                row.event_time = request.timestamp as u32;
+            }
+            ProgramEventType::InferenceFulfilled(fulfillment) => {
+                row.actor = fulfillment.provider.clone();
+                row.counterparty = fulfillment.payee.clone();
+                row.status = fulfillment.status.clone();
+                row.fee = fulfillment.fee;
+                row.fee_mint = fulfillment.fee_mint.clone().unwrap_or_default();
+                row.event_time = fulfillment.timestamp as u32;
+            }
+            ProgramEventType::InferenceChallenged(challenge) => {
+                row.actor = challenge.provider.clone();
+                row.counterparty = challenge.challenger.clone();
+                row.status = "challenged".to_string();
+                row.amount = challenge.slashed;
+                row.event_time = challenge.timestamp as u32;
+            }
+            ProgramEventType::InferenceFinalized(finalized) => {
+                row.actor = finalized.provider.clone();
+                row.status = "completed".to_string();
+                row.fee = finalized.fee;
+                row.fee_mint = finalized.fee_mint.clone().unwrap_or_default();
+                row.event_time = finalized.timestamp as u32;
+            }
+            ProgramEventType::DataContributed(contribution) => {
+                row.actor = contribution.contributor.clone();
+            }
+            ProgramEventType::ConsentChanged(change) => {
+                row.actor = change.contributor.clone();
+                row.counterparty = change.data_hash.clone();
+                row.status = if change.withdrawn { "withdrawn" } else { "consented" }.to_string();
+            }
+            ProgramEventType::ContributionRevoked(revocation) => {
+                row.actor = revocation.contributor.clone();
+                row.counterparty = revocation.data_hash.clone();
+                row.status = "revoked".to_string();
+                row.event_time = revocation.revoked_at as u32;
+            }
+            ProgramEventType::ProposalCreated(proposal) => {
+                row.actor = proposal.author.clone();
+                row.proposal_id = proposal.proposal_id.clone();
+                row.event_time = proposal.created_at as u32;
+            }
+            ProgramEventType::VoteCast(vote) => {
+                row.actor = vote.voter.clone();
+                row.counterparty = vote.delegate.clone().unwrap_or_default();
+                row.status = vote.choice.to_string();
+                row.amount = vote.effective_weight;
+                row.proposal_id = vote.proposal_id.clone();
+                row.event_time = vote.cast_at as u32;
+            }
+            ProgramEventType::ModelUpdated(_) | ProgramEventType::ModelDeleted(_) => {}
+        }
+
+        Ok(row)
+    }
+}
+
+/// Batches the event topic into ClickHouse alongside the Postgres path.
+///
+/// Kafka offsets are committed only after a batch is inserted, so delivery is
+/// at-least-once; `program_events` is a ReplacingMergeTree whose sort key
+/// includes the idempotency key, so redeliveries collapse on merge.
+pub struct ClickHouseSink {
+    consumer: StreamConsumer,
+    client: Client,
+    topic: String,
+    config: ClickHouseConfig,
+    batch: Batch,
+}
+
+/// Rows awaiting insert and the offsets they cover
+#[derive(Default)]
+struct Batch {
+    events: Vec<EventRow>,
+    retractions: Vec<RetractionRow>,
+    /// Next offset to commit per partition
+    offsets: HashMap<i32, i64>,
+}
+
+impl ClickHouseSink {
+    pub fn new(mut client_config: ClientConfig, topic: String, config: ClickHouseConfig) -> anyhow::Result<Self> {
+        let consumer: StreamConsumer = client_config
+            .set("group.id", &config.consumer_group)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .set("isolation.level", "read_committed")
+            .create()?;
+        consumer.subscribe(&[&topic])?;
+
+        let mut client = Client::default()
+            .with_url(&config.dsn)
+            .with_database(&config.database);
+        if let Some(user) = &config.user {
+            client = client.with_user(user);
+        }
+        if let Some(password) = &config.password {
+            client = client.with_password(password);
+        }
+
+        Ok(Self {
+            consumer,
+            client,
+            topic,
+            config,
+            batch: Batch::default(),
+        })
+    }
+
+    #[instrument(skip_all, fields(topic = %self.topic))]
+    pub async fn run(&mut self, shutdown: mpsc::Sender<()>) -> anyhow::Result<()> {
+        let mut ticker = interval(Duration::from_millis(self.config.flush_interval_ms));
+        info!(dsn = %self.config.dsn, batch_size = self.config.batch_size, "ClickHouse sink started");
+
+        loop {
+            tokio::select! {
+                message = self.consumer.recv() => {
+                    self.batch.push(&message?)?;
+                    if self.batch.len() >= self.config.batch_size {
+                        self.batch.flush(&self.client, &self.consumer, &self.topic).await?;
+                    }
+                }
+                _ = ticker.tick() => self.batch.flush(&self.client, &self.consumer, &self.topic).await?,
+                _ = shutdown.closed() => break,
+            }
+        }
+
+        self.batch.flush(&self.client, &self.consumer, &self.topic).await
+    }
+}
+
+impl Batch {
+    fn len(&self) -> usize {
+        self.events.len() + self.retractions.len()
+    }
+
+    fn push(&mut self, message: &BorrowedMessage<'_>) -> anyhow::Result<()> {
+        let now = unix_now();
+        let retracted = message
+            .headers()
+            .map(|h| h.iter().any(|h| h.key == "retracted"))
+            .unwrap_or(false);
+
+        if retracted {
+            // Rollbacks drop whole slots, so retracting the transaction is exact
+            let key = String::from_utf8_lossy(message.key().context("Retraction without key")?).into_owned();
+            let signature = key.split(':').next().unwrap_or_default();
+            self.retractions.push(RetractionRow {
+                signature: signature.to_string(),
+                retracted_at: now,
+            });
+        } else if let Some(payload) = message.payload() {
+            let event: ProgramEvent = serde_json::from_slice(payload)?;
+            self.events.push(EventRow::from_event(&event, now)?);
+        }
+
+        self.offsets.insert(message.partition(), message.offset() + 1);
+        Ok(())
+    }
+
+    /// Insert buffered rows, then commit the offsets they came from
+    async fn flush(&mut self, client: &Client, consumer: &StreamConsumer, topic: &str) -> anyhow::Result<()> {
+        if self.offsets.is_empty() {
+            return Ok(());
+        }
+        let started = Instant::now();
+
+        if !self.events.is_empty() {
+            let mut insert = client.insert(EVENTS_TABLE)?;
+            for row in &self.events {
+                insert.write(row).await?;
+            }
+            insert.end().await?;
+        }
+        if !self.retractions.is_empty() {
+            let mut insert = client.insert(RETRACTIONS_TABLE)?;
+            for row in &self.retractions {
+                insert.write(row).await?;
+            }
+            insert.end().await?;
+        }
+
+        let mut commit = TopicPartitionList::new();
+        for (partition, offset) in &self.offsets {
+            commit.add_partition_offset(topic, *partition, Offset::Offset(*offset))?;
+        }
+        consumer.commit(&commit, CommitMode::Async)?;
+
+        metrics::counter!("clickhouse_rows_written_total", self.len() as u64);
+        metrics::histogram!("clickhouse_flush_seconds", started.elapsed().as_secs_f64());
+        self.events.clear();
+        self.retractions.clear();
+        self.offsets.clear();
+        Ok(())
+    }
+}
+
+fn unix_now() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as u32)
+        .unwrap_or_default()
+}
@@ -0,0 +1,160 @@
+// indexer/src/compression.rs
+
+//! Compression for individually submitted FL updates. Top-k sparsification
+//! sends only the largest-magnitude coordinates and 8-bit quantization shrinks
+//! the values sent. Whatever is dropped or rounded off is carried into the
+//! node's next update (error feedback), so it is delayed rather than lost.
+//!
+//! Decompression is exact: the aggregator rebuilds precisely the vector the
+//! node's residual was computed against.
+
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct CompressionConfig {
+    /// Fraction of coordinates sent, by magnitude; all of them when unset
+    pub top_k: Option<f32>,
+    /// Send values as 8-bit integers sharing one f32 scale
+    pub quantize: bool,
+}
+
+impl CompressionConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.top_k.is_some() || self.quantize
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(fraction) = self.top_k {
+            ensure!(fraction > 0.0 && fraction <= 1.0, "top_k must be in (0, 1]");
+        }
+        Ok(())
+    }
+}
+
+/// An update as submitted on-chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompressedUpdate {
+    /// Dense dimension
+    pub len: u32,
+    /// Coordinates sent, ascending; `None` when every coordinate is
+    pub indices: Option<Vec<u32>>,
+    pub values: CompressedValues,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CompressedValues {
+    F32(Vec<f32>),
+    /// Each value is `q * scale`
+    I8 { scale: f32, values: Vec<i8> },
+}
+
+impl CompressedUpdate {
+    /// Dense update; rejects malformed payloads
+    pub fn decompress(&self) -> anyhow::Result<Vec<f32>> {
+        let values: Vec<f32> = match &self.values {
+            CompressedValues::F32(values) => values.clone(),
+            CompressedValues::I8 { scale, values } => values.iter().map(|&q| q as f32 * scale).collect(),
+        };
+        let Some(indices) = &self.indices else {
+            ensure!(values.len() == self.len as usize, "dense update has {} values, expected {}", values.len(), self.len);
+            return Ok(values);
+        };
+
+        ensure!(indices.len() == values.len(), "{} indices for {} values", indices.len(), values.len());
+        let mut dense = vec![0f32; self.len as usize];
+        let mut previous = None;
+        for (&i, value) in indices.iter().zip(values) {
+            ensure!(
+                i < self.len && previous < Some(i),
+                "sparse indices must ascend within the update"
+            );
+            dense[i as usize] = value;
+            previous = Some(i);
+        }
+        Ok(dense)
+    }
+
+    /// Payload size in bytes
+    pub fn encoded_len(&self) -> usize {
+        let indices = self.indices.as_ref().map_or(0, |indices| 4 * indices.len());
+        let values = match &self.values {
+            CompressedValues::F32(values) => 4 * values.len(),
+            CompressedValues::I8 { values, .. } => 4 + values.len(),
+        };
+        4 + indices + values
+    }
+
+    /// Dense f32 size over payload size
+    pub fn ratio(&self) -> f64 {
+        (4 * self.len as usize) as f64 / self.encoded_len() as f64
+    }
+}
+
+/// A node's compressor; holds the error-feedback residual between rounds
+#[derive(Debug, Clone, Default)]
+pub struct GradientCompressor {
+    config: CompressionConfig,
+    residual: Vec<f32>,
+}
+
+impl GradientCompressor {
+    pub fn new(config: CompressionConfig) -> Self {
+        Self {
+            config,
+            residual: Vec::new(),
+        }
+    }
+
+    /// Compress `gradients` plus the carried residual, keeping what was not sent
+    pub fn compress(&mut self, gradients: &[f32]) -> CompressedUpdate {
+        if self.residual.len() != gradients.len() {
+            self.residual = vec![0.0; gradients.len()];
+        }
+        let corrected: Vec<f32> = gradients.iter().zip(&self.residual).map(|(g, r)| g + r).collect();
+
+        let indices = self.config.top_k.map(|fraction| top_k(&corrected, fraction));
+        let kept = match &indices {
+            Some(indices) => indices.iter().map(|&i| corrected[i as usize]).collect(),
+            None => corrected.clone(),
+        };
+        let values = if self.config.quantize {
+            quantize(&kept)
+        } else {
+            CompressedValues::F32(kept)
+        };
+        let update = CompressedUpdate {
+            len: gradients.len() as u32,
+            indices,
+            values,
+        };
+
+        let sent = update.decompress().expect("compressor output is well-formed");
+        self.residual = corrected.iter().zip(&sent).map(|(c, s)| c - s).collect();
+        update
+    }
+}
+
+/// Ascending indices of the `fraction` largest-magnitude values, at least one
+fn top_k(values: &[f32], fraction: f32) -> Vec<u32> {
+    let k = ((values.len() as f32 * fraction).ceil() as usize).clamp(values.len().min(1), values.len());
+    let mut order: Vec<u32> = (0..values.len() as u32).collect();
+    if k < values.len() {
+        order.select_nth_unstable_by(k, |&a, &b| values[b as usize].abs().total_cmp(&values[a as usize].abs()));
+        order.truncate(k);
+    }
+    order.sort_unstable();
+    order
+}
+
+/// Symmetric 8-bit quantization scaled to the largest magnitude
+fn quantize(values: &[f32]) -> CompressedValues {
+    let max = values.iter().filter(|v| v.is_finite()).fold(0f32, |max, v| max.max(v.abs()));
+    let scale = max / i8::MAX as f32;
+    let values = values
+        .iter()
+        .map(|v| if scale > 0.0 { (v / scale).round().clamp(-127.0, 127.0) as i8 } else { 0 })
+        .collect();
+    CompressedValues::I8 { scale, values }
+}
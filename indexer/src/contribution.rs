@@ -0,0 +1,50 @@
+// indexer/src/contribution.rs
+
+//! Contribution scores for a federated round, computed by its aggregator and
+//! committed on-chain with `score_contribution`. A contributor's share of the
+//! new version's rewards is proportional to its score.
+//!
+//! Scores are leave-one-out validation-loss deltas: how much worse the
+//! aggregate does on the aggregator's holdout set without that update.
+
+use anyhow::ensure;
+
+/// Scores are fixed point with this many units per unit of loss
+pub const SCORE_SCALE: f64 = 1_000_000.0;
+
+/// Score each update by `max(0, loss(mean without it) - loss(mean of all))`.
+/// `loss` evaluates a candidate aggregate update on the holdout set. Updates
+/// that did not help score zero; if none helped, all score equally so the
+/// round's reward pool is still split.
+pub fn leave_one_out<F>(updates: &[Vec<f32>], loss: F) -> anyhow::Result<Vec<u64>>
+where
+    F: Fn(&[f32]) -> anyhow::Result<f64>,
+{
+    let Some(dim) = updates.first().map(Vec::len) else {
+        return Ok(Vec::new());
+    };
+    ensure!(updates.iter().all(|u| u.len() == dim), "updates differ in length");
+
+    let mut sum = vec![0f64; dim];
+    for update in updates {
+        for (s, v) in sum.iter_mut().zip(update) {
+            *s += *v as f64;
+        }
+    }
+    let mean_of = |sum: &[f64], count: usize| -> Vec<f32> {
+        sum.iter().map(|s| if count == 0 { 0.0 } else { (s / count as f64) as f32 }).collect()
+    };
+
+    let baseline = loss(&mean_of(&sum, updates.len()))?;
+    let mut scores = Vec::with_capacity(updates.len());
+    for update in updates {
+        let without: Vec<f64> = sum.iter().zip(update).map(|(s, v)| s - *v as f64).collect();
+        let delta = loss(&mean_of(&without, updates.len() - 1))? - baseline;
+        scores.push(if delta.is_finite() && delta > 0.0 { (delta * SCORE_SCALE).round() as u64 } else { 0 });
+    }
+
+    if scores.iter().all(|&s| s == 0) {
+        scores.fill(1);
+    }
+    Ok(scores)
+}
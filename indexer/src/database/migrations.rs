@@ -1,17 +1,20 @@
 // local_engine/src/db/migrations.rs
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt,
     path::{Path, PathBuf},
-    time::SystemTime,
+    time::Instant,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tokio_postgres::{Client, Transaction};
 
-const MIGRATIONS_DIR: &str = "migrations";
+pub const MIGRATIONS_DIR: &str = "migrations";
 const SCHEMA_VERSION: &str = "scoria_schema_version";
+/// First line of a migration that must run outside a transaction, e.g. `CREATE INDEX CONCURRENTLY`
+const NO_TRANSACTION: &str = "-- scoria:no-transaction";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Migration {
@@ -24,32 +27,42 @@ pub struct Migration {
 
 #[derive(Debug)]
 pub enum MigrationError {
-    VersionMismatch,
-    DirtyDatabase,
+    VersionMismatch(String),
+    /// A migration failed partway outside a transaction; fix the schema by hand, then the history row
+    DirtyDatabase(i64),
     ExecutionFailed(String),
     RollbackFailed(String),
-    ChecksumMismatch,
-    HistoryCorrupted,
+    ChecksumMismatch(i64),
+    HistoryCorrupted(String),
+    InvalidMigration(String),
 }
 
 impl fmt::Display for MigrationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::VersionMismatch => write!(f, "Database version mismatch"),
-            Self::DirtyDatabase => write!(f, "Database in dirty state"),
+            Self::VersionMismatch(s) => write!(f, "Database version mismatch: {}", s),
+            Self::DirtyDatabase(v) => write!(f, "Database in dirty state: migration {} did not complete", v),
             Self::ExecutionFailed(s) => write!(f, "Migration failed: {}", s),
             Self::RollbackFailed(s) => write!(f, "Rollback failed: {}", s),
-            Self::ChecksumMismatch => write!(f, "Migration checksum mismatch"),
-            Self::HistoryCorrupted => write!(f, "Migration history corrupted"),
+            Self::ChecksumMismatch(v) => write!(f, "Migration {} was modified after it was applied", v),
+            Self::HistoryCorrupted(s) => write!(f, "Migration history corrupted: {}", s),
+            Self::InvalidMigration(s) => write!(f, "Invalid migration: {}", s),
         }
     }
 }
 
+impl std::error::Error for MigrationError {}
+
+fn exec_err(e: tokio_postgres::Error) -> MigrationError {
+    MigrationError::ExecutionFailed(e.to_string())
+}
+
 #[async_trait]
 pub trait MigrationStore {
     async fn load(&self) -> Result<Vec<Migration>, MigrationError>;
 }
 
+/// Migrations as `<version>_<description>.up.sql` and `.down.sql` pairs in one directory
 pub struct FileMigrationStore {
     path: PathBuf,
 }
@@ -65,71 +78,335 @@ impl FileMigrationStore {
 #[async_trait]
 impl MigrationStore for FileMigrationStore {
     async fn load(&self) -> Result<Vec<Migration>, MigrationError> {
-        // Implementation for loading migrations from filesystem
-        // (omitted for brevity)
-        Ok(vec![])
+        let invalid = |s: String| MigrationError::InvalidMigration(s);
+        let mut entries = tokio::fs::read_dir(&self.path)
+            .await
+            .map_err(|e| invalid(format!("{}: {}", self.path.display(), e)))?;
+
+        // version -> (description, up, down)
+        let mut found: BTreeMap<i64, (String, Option<String>, Option<String>)> = BTreeMap::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| invalid(e.to_string()))? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Some((stem, direction)) = name
+                .strip_suffix(".up.sql")
+                .map(|s| (s, "up"))
+                .or_else(|| name.strip_suffix(".down.sql").map(|s| (s, "down")))
+            else {
+                continue;
+            };
+            let (version, description) = stem
+                .split_once('_')
+                .and_then(|(v, d)| Some((v.parse::<i64>().ok()?, d.replace('_', " "))))
+                .ok_or_else(|| invalid(format!("{name}: expected <version>_<description>")))?;
+            let sql = tokio::fs::read_to_string(entry.path())
+                .await
+                .map_err(|e| invalid(format!("{name}: {e}")))?;
+
+            let slot = found.entry(version).or_insert_with(|| (description.clone(), None, None));
+            if slot.0 != description {
+                return Err(invalid(format!("version {version} has two descriptions: `{}` and `{description}`", slot.0)));
+            }
+            let file = if direction == "up" { &mut slot.1 } else { &mut slot.2 };
+            if file.replace(sql).is_some() {
+                return Err(invalid(format!("duplicate {direction} migration for version {version}")));
+            }
+        }
+
+        found
+            .into_iter()
+            .map(|(version, (description, up, down))| {
+                let up = up.ok_or_else(|| invalid(format!("version {version} has no .up.sql")))?;
+                let down = down.ok_or_else(|| invalid(format!("version {version} has no .down.sql")))?;
+                Ok(Migration { version, description, checksum: checksum(&up), up, down })
+            })
+            .collect()
     }
 }
 
+fn transactional(sql: &str) -> bool {
+    !sql.trim_start().starts_with(NO_TRANSACTION)
+}
+
+/// Hex SHA-256 of the up script; editing an applied migration is an error, not a silent drift
+fn checksum(sql: &str) -> String {
+    Sha256::digest(sql.as_bytes()).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    Dirty,
+    /// Applied, but the file no longer matches the recorded checksum
+    Modified,
+    /// Recorded as applied with no file on disk
+    Missing,
+}
+
+/// Row of `scoria_schema_version`
+struct Applied {
+    description: String,
+    checksum: String,
+    dirty: bool,
+}
+
 pub struct MigrationRunner<'a> {
-    client: &'a Client,
+    client: &'a mut Client,
     store: Box<dyn MigrationStore + Send + Sync>,
 }
 
 impl<'a> MigrationRunner<'a> {
-    pub fn new(client: &'a Client, store: Box<dyn MigrationStore + Send + Sync>) -> Self {
+    pub fn new(client: &'a mut Client, store: Box<dyn MigrationStore + Send + Sync>) -> Self {
         Self { client, store }
     }
 
-    pub async fn migrate(&self, target_version: Option<i64>) -> Result<(), MigrationError> {
-        let mut tx = self.client.transaction().await.map_err(|e| {
-            MigrationError::ExecutionFailed(format!("Transaction start failed: {}", e))
-        })?;
-
-        self.create_migration_table(&mut tx).await?;
-
-        let current_version = self.get_current_version(&mut tx).await?;
+    /// Apply pending migrations up to `target_version` (default: latest), each in its own
+    /// transaction so a failure keeps the ones before it. Returns the versions applied.
+    pub async fn migrate(&mut self, target_version: Option<i64>) -> Result<Vec<i64>, MigrationError> {
+        self.create_migration_table().await?;
         let migrations = self.store.load().await?;
-        let target = target_version.unwrap_or(migrations.last().map(|m| m.version).unwrap_or(0));
+        let applied = self.applied().await?;
+        self.check_history(&migrations, &applied)?;
 
-        let pending = self.get_pending_migrations(&migrations, current_version, target)?;
+        let current = applied.keys().next_back().copied().unwrap_or(0);
+        let target = target_version.unwrap_or(migrations.last().map(|m| m.version).unwrap_or(0));
+        if target < current {
+            return Err(MigrationError::VersionMismatch(format!(
+                "target {target} is below the current version {current}; use `migrate down`"
+            )));
+        }
 
+        let pending = self.get_pending_migrations(&migrations, &applied, target)?;
+        let mut done = Vec::with_capacity(pending.len());
         for migration in pending {
-            self.apply_migration(&mut tx, migration).await?;
+            self.apply_migration(migration).await?;
+            done.push(migration.version);
         }
+        Ok(done)
+    }
 
-        tx.commit().await.map_err(|e| {
-            MigrationError::ExecutionFailed(format!("Commit failed: {}", e))
-        })?;
+    fn get_pending_migrations<'m>(
+        &self,
+        migrations: &'m [Migration],
+        applied: &BTreeMap<i64, Applied>,
+        target: i64,
+    ) -> Result<Vec<&'m Migration>, MigrationError> {
+        let current = applied.keys().next_back().copied().unwrap_or(0);
+        let pending: Vec<&Migration> = migrations
+            .iter()
+            .filter(|m| m.version <= target && !applied.contains_key(&m.version))
+            .collect();
+        // A branch merged after newer migrations shipped would otherwise run out of order
+        if let Some(stale) = pending.iter().find(|m| m.version < current) {
+            return Err(MigrationError::VersionMismatch(format!(
+                "migration {} is older than the current version {current} but was never applied",
+                stale.version
+            )));
+        }
+        Ok(pending)
+    }
 
-        Ok(())
+    async fn apply_migration(&mut self, migration: &Migration) -> Result<(), MigrationError> {
+        let started = Instant::now();
+        // Recorded dirty first, so a crash in a non-transactional migration is detected
+        self.client
+            .execute(
+                &format!("INSERT INTO {SCHEMA_VERSION} (version, description, checksum, dirty) VALUES ($1, $2, $3, TRUE)"),
+                &[&migration.version, &migration.description, &migration.checksum],
+            )
+            .await
+            .map_err(exec_err)?;
+
+        let result = if transactional(&migration.up) {
+            let tx = self.client.transaction().await.map_err(exec_err)?;
+            Self::run_in(tx, migration, "up").await
+        } else {
+            self.client.batch_execute(&migration.up).await.map_err(exec_err)
+        };
+
+        match result {
+            Ok(()) => {
+                self.client
+                    .execute(
+                        &format!("UPDATE {SCHEMA_VERSION} SET dirty = FALSE, applied_at = NOW() WHERE version = $1"),
+                        &[&migration.version],
+                    )
+                    .await
+                    .map_err(exec_err)?;
+                log::info!("Applied migration {} ({}) in {:?}", migration.version, migration.description, started.elapsed());
+                Ok(())
+            }
+            Err(e) if transactional(&migration.up) => {
+                // Rolled back with the transaction, so nothing is left half-done
+                self.client
+                    .execute(&format!("DELETE FROM {SCHEMA_VERSION} WHERE version = $1"), &[&migration.version])
+                    .await
+                    .map_err(exec_err)?;
+                Err(MigrationError::ExecutionFailed(format!("{}: {}", migration.version, e)))
+            }
+            Err(e) => {
+                log::error!("Migration {} failed outside a transaction; database is dirty", migration.version);
+                Err(MigrationError::ExecutionFailed(format!("{}: {}", migration.version, e)))
+            }
+        }
     }
 
-    async fn apply_migration(&self, tx: &mut Transaction<'_>, migration: &Migration) -> Result<(), MigrationError> {
-        // Full implementation includes:
-        // 1. Pre-flight checks
-        // 2. Transactional execution
-        // 3. Checksum validation
-        // 4. Audit logging
-        // 5. Performance metrics
-        Ok(())
+    /// Execute one script and its audit record, then commit
+    async fn run_in(tx: Transaction<'_>, migration: &Migration, action: &str) -> Result<(), MigrationError> {
+        let sql = if action == "up" { &migration.up } else { &migration.down };
+        tx.batch_execute(sql).await.map_err(exec_err)?;
+        Self::audit_log(&tx, migration, action).await?;
+        tx.commit().await.map_err(exec_err)
+    }
+
+    /// Run down scripts, newest first, until `target_version` is the latest applied
+    pub async fn rollback(&mut self, target_version: i64) -> Result<Vec<i64>, MigrationError> {
+        self.create_migration_table().await?;
+        let migrations = self.store.load().await?;
+        let applied = self.applied().await?;
+        if let Some((version, _)) = applied.iter().find(|(_, a)| a.dirty) {
+            return Err(MigrationError::DirtyDatabase(*version));
+        }
+        let by_version: HashMap<i64, &Migration> = migrations.iter().map(|m| (m.version, m)).collect();
+
+        let mut done = Vec::new();
+        for (&version, recorded) in applied.iter().rev().take_while(|(v, _)| **v > target_version) {
+            let migration = by_version.get(&version).ok_or_else(|| {
+                MigrationError::RollbackFailed(format!("no down script for applied migration {version}"))
+            })?;
+            if migration.checksum != recorded.checksum {
+                return Err(MigrationError::ChecksumMismatch(version));
+            }
+
+            let result = if transactional(&migration.down) {
+                let tx = self.client.transaction().await.map_err(exec_err)?;
+                tx.execute(&format!("DELETE FROM {SCHEMA_VERSION} WHERE version = $1"), &[&version])
+                    .await
+                    .map_err(exec_err)?;
+                Self::run_in(tx, migration, "down").await
+            } else {
+                self.client
+                    .execute(&format!("UPDATE {SCHEMA_VERSION} SET dirty = TRUE WHERE version = $1"), &[&version])
+                    .await
+                    .map_err(exec_err)?;
+                match self.client.batch_execute(&migration.down).await {
+                    Ok(()) => self
+                        .client
+                        .execute(&format!("DELETE FROM {SCHEMA_VERSION} WHERE version = $1"), &[&version])
+                        .await
+                        .map(drop)
+                        .map_err(exec_err),
+                    Err(e) => Err(exec_err(e)),
+                }
+            };
+            result.map_err(|e| MigrationError::RollbackFailed(format!("{version}: {e}")))?;
+            log::info!("Rolled back migration {} ({})", version, migration.description);
+            done.push(version);
+        }
+        Ok(done)
     }
 
-    async fn rollback(&self, target_version: i64) -> Result<(), MigrationError> {
-        // Similar structure to migrate() but executes down scripts
+    /// Every migration on disk or in the history table, oldest first
+    pub async fn status(&mut self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        self.create_migration_table().await?;
+        let migrations = self.store.load().await?;
+        let applied = self.applied().await?;
+
+        let mut status: BTreeMap<i64, MigrationStatus> = migrations
+            .iter()
+            .map(|m| {
+                let state = match applied.get(&m.version) {
+                    None => MigrationState::Pending,
+                    Some(a) if a.dirty => MigrationState::Dirty,
+                    Some(a) if a.checksum != m.checksum => MigrationState::Modified,
+                    Some(_) => MigrationState::Applied,
+                };
+                (m.version, MigrationStatus { version: m.version, description: m.description.clone(), state })
+            })
+            .collect();
+        for (version, a) in &applied {
+            status.entry(*version).or_insert_with(|| MigrationStatus {
+                version: *version,
+                description: a.description.clone(),
+                state: if a.dirty { MigrationState::Dirty } else { MigrationState::Missing },
+            });
+        }
+        Ok(status.into_values().collect())
+    }
+
+    /// Refuse to build on a dirty, edited or unknown history
+    fn check_history(&self, migrations: &[Migration], applied: &BTreeMap<i64, Applied>) -> Result<(), MigrationError> {
+        let by_version: HashMap<i64, &Migration> = migrations.iter().map(|m| (m.version, m)).collect();
+        for (version, recorded) in applied {
+            if recorded.dirty {
+                return Err(MigrationError::DirtyDatabase(*version));
+            }
+            match by_version.get(version) {
+                Some(m) if m.checksum != recorded.checksum => return Err(MigrationError::ChecksumMismatch(*version)),
+                Some(_) => {}
+                None => {
+                    return Err(MigrationError::HistoryCorrupted(format!(
+                        "applied migration {version} has no file in the migrations directory"
+                    )))
+                }
+            }
+        }
         Ok(())
     }
+
+    async fn applied(&self) -> Result<BTreeMap<i64, Applied>, MigrationError> {
+        let rows = self
+            .client
+            .query(&format!("SELECT version, description, checksum, dirty FROM {SCHEMA_VERSION}"), &[])
+            .await
+            .map_err(exec_err)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get(0), Applied { description: row.get(1), checksum: row.get(2), dirty: row.get(3) }))
+            .collect())
+    }
+
+    async fn create_migration_table(&self) -> Result<(), MigrationError> {
+        self.client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {SCHEMA_VERSION} (
+                    version BIGINT PRIMARY KEY,
+                    description TEXT NOT NULL,
+                    checksum TEXT NOT NULL,
+                    dirty BOOLEAN NOT NULL DEFAULT FALSE,
+                    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                );
+                CREATE TABLE IF NOT EXISTS scoria_migration_audit (
+                    id BIGSERIAL PRIMARY KEY,
+                    version BIGINT NOT NULL,
+                    action TEXT NOT NULL,
+                    checksum TEXT NOT NULL,
+                    executed_by TEXT NOT NULL,
+                    executed_at TIMESTAMPTZ NOT NULL
+                );"
+            ))
+            .await
+            .map_err(exec_err)
+    }
 }
 
 // Enterprise Features
 impl MigrationRunner<'_> {
-    async fn audit_log(&self, tx: &mut Transaction<'_>, migration: &Migration, action: &str) -> Result<(), MigrationError> {
+    async fn audit_log(tx: &Transaction<'_>, migration: &Migration, action: &str) -> Result<(), MigrationError> {
         tx.execute(
             "INSERT INTO scoria_migration_audit (
                 version, action, checksum, executed_by, executed_at
-            ) VALUES (\$1, \$2, \$3, current_user, NOW())",
+            ) VALUES ($1, $2, $3, current_user, NOW())",
             &[&migration.version, &action, &migration.checksum],
-        ).map_err(|e| MigrationError::ExecutionFailed(e.to_string()))?;
+        ).await.map_err(|e| MigrationError::ExecutionFailed(e.to_string()))?;
         Ok(())
     }
 
@@ -174,9 +451,9 @@ SELECT create_hypertable('scoria.models', 'created_at');
 
 // Usage Example:
 /*
-let client = pool.get().await?;
+let mut client = connect_direct(&config).await?;
 let store = Box::new(FileMigrationStore::new(MIGRATIONS_DIR));
-let runner = MigrationRunner::new(&client, store);
+let mut runner = MigrationRunner::new(&mut client, store);
 
 // Apply all pending migrations
 runner.migrate(None).await?;
@@ -5,17 +5,59 @@ use futures_util::future::BoxFuture;
 use metrics::{counter, gauge};
 use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
 use postgres_openssl::MakeTlsConnector;
+use serde::Deserialize;
 use std::{
     time::{Duration, Instant},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
 };
-use tokio::time::timeout;
+use tokio::{task::JoinHandle, time::timeout};
 use tokio_postgres::{Client, Config, Error, Socket, tls::TlsConnect};
 
 const CONNECTION_TIMEOUT: Duration = Duration::from_secs(15);
 const VALIDATION_TIMEOUT: Duration = Duration::from_secs(5);
 const MAX_RETRIES: u32 = 3;
 
+/// Read replicas under `database.replicas`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplicaConfig {
+    /// Replica DSNs, e.g. `postgres://indexer@replica-1/scoria`
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Replay lag beyond which a replica stops receiving reads
+    #[serde(default = "default_max_lag_bytes")]
+    pub max_lag_bytes: u64,
+    #[serde(default = "default_lag_check_interval_secs")]
+    pub lag_check_interval_secs: u64,
+    #[serde(default = "default_replica_pool_size")]
+    pub pool_size: usize,
+}
+
+impl Default for ReplicaConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            max_lag_bytes: default_max_lag_bytes(),
+            lag_check_interval_secs: default_lag_check_interval_secs(),
+            pool_size: default_replica_pool_size(),
+        }
+    }
+}
+
+fn default_max_lag_bytes() -> u64 {
+    16 * 1024 * 1024
+}
+
+fn default_lag_check_interval_secs() -> u64 {
+    5
+}
+
+fn default_replica_pool_size() -> usize {
+    20
+}
+
 #[derive(Clone)]
 pub struct PgManager {
     config: Arc<Config>,
@@ -67,40 +109,65 @@ impl Manager for PgManager {
 #[derive(Clone)]
 pub struct PgPool {
     inner: Pool<PgManager>,
+    replicas: Arc<Vec<Replica>>,
+    /// Round-robin cursor over `replicas`
+    next_replica: Arc<AtomicUsize>,
     metrics: MetricsCollector,
 }
 
+struct Replica {
+    /// Host and database, for logs and metric labels; never the password
+    name: String,
+    pool: Pool<PgManager>,
+    /// Cleared when the replica lags too far, is unreachable, or is not in recovery
+    eligible: AtomicBool,
+    lag_bytes: AtomicU64,
+}
+
 impl PgPool {
     pub async fn new(
         config: Config,
         max_size: usize,
         min_idle: usize,
     ) -> Result<Self, Error> {
-        // Configure TLS
-        let mut ssl_builder = SslConnector::builder(SslMethod::tls())?;
-        ssl_builder.set_verify(SslVerifyMode::PEER);
-        
-        if let Some(ca_path) = config.get_ssl_root_cert() {
-            ssl_builder.set_ca_file(ca_path)?;
+        Ok(Self {
+            inner: Self::build_pool(config, max_size, min_idle)?,
+            replicas: Arc::new(Vec::new()),
+            next_replica: Arc::new(AtomicUsize::new(0)),
+            metrics: MetricsCollector::new(),
+        })
+    }
+
+    /// Route `get_read` across `replicas.urls`; writes stay on the primary.
+    /// Replicas start excluded until the first lag check admits them.
+    pub fn with_replicas(mut self, replicas: &ReplicaConfig) -> Result<Self, Error> {
+        let mut pools = Vec::with_capacity(replicas.urls.len());
+        for url in &replicas.urls {
+            let config: Config = url.parse()?;
+            pools.push(Replica {
+                name: replica_name(&config),
+                pool: Self::build_pool(config, replicas.pool_size, 1)?,
+                eligible: AtomicBool::new(false),
+                lag_bytes: AtomicU64::new(0),
+            });
         }
+        self.replicas = Arc::new(pools);
+        Ok(self)
+    }
 
+    fn build_pool(config: Config, max_size: usize, min_idle: usize) -> Result<Pool<PgManager>, Error> {
         let manager = PgManager {
+            tls_connector: tls_connector(&config)?,
             config: Arc::new(config),
-            tls_connector: MakeTlsConnector::new(ssl_builder.build()),
         };
 
-        let pool = Pool::builder(manager)
+        Pool::builder(manager)
             .max_size(max_size)
             .min_idle(Some(min_idle))
             .create_timeout(Some(CONNECTION_TIMEOUT))
             .recycle_timeout(Some(VALIDATION_TIMEOUT))
             .post_create(Self::warmup_connection)
-            .build()?;
-
-        Ok(Self {
-            inner: pool,
-            metrics: MetricsCollector::new(),
-        })
+            .build()
     }
 
     async fn warmup_connection(client: &mut Client) -> Result<(), Error> {
@@ -134,11 +201,148 @@ impl PgPool {
         }
     }
 
+    /// Connection for reads: the next eligible replica in round-robin order,
+    /// or the primary when none is eligible or reachable
+    pub async fn get_read(&self) -> Result<PooledClient, Error> {
+        let count = self.replicas.len();
+        let start = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..count {
+            let replica = &self.replicas[(start + offset) % count];
+            if !replica.eligible.load(Ordering::Relaxed) {
+                continue;
+            }
+            match replica.pool.get().await {
+                Ok(client) => {
+                    counter!("db.pool.replica_reads", 1, "replica" => replica.name.clone());
+                    self.metrics.obtain_success();
+                    return Ok(PooledClient::new(client, self.metrics.clone()));
+                }
+                Err(e) => {
+                    // Out of rotation until the next lag check finds it reachable
+                    replica.eligible.store(false, Ordering::Relaxed);
+                    log::warn!("Replica {} unavailable, trying the next: {}", replica.name, e);
+                }
+            }
+        }
+        if count > 0 {
+            counter!("db.pool.replica_fallbacks", 1);
+        }
+        self.get().await
+    }
+
+    /// Compare each replica's replay position with the primary's WAL position
+    /// every `lag_check_interval_secs`, admitting only those within `max_lag_bytes`
+    pub fn spawn_lag_monitor(&self, config: &ReplicaConfig) -> JoinHandle<()> {
+        let pool = self.clone();
+        let max_lag = config.max_lag_bytes;
+        let period = Duration::from_secs(config.lag_check_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = pool.check_replica_lag(max_lag).await {
+                    // Without the primary's position lag is unknown; keep the last verdicts
+                    log::warn!("Replica lag check skipped: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn check_replica_lag(&self, max_lag: u64) -> Result<(), Error> {
+        if self.replicas.is_empty() {
+            return Ok(());
+        }
+        let primary = self.get().await?;
+        let row = primary.client.query_one("SELECT pg_current_wal_lsn()::text", &[]).await?;
+        let Some(primary_lsn) = parse_lsn(row.get(0)) else {
+            log::warn!("Unparseable primary WAL position");
+            return Ok(());
+        };
+        drop(primary);
+
+        for replica in self.replicas.iter() {
+            let replayed = async {
+                let client = replica.pool.get().await?;
+                let row = client.query_one("SELECT pg_last_wal_replay_lsn()::text", &[]).await?;
+                Ok::<Option<String>, Error>(row.get(0))
+            };
+            let lag = match timeout(VALIDATION_TIMEOUT, replayed).await {
+                // NULL when the server is not in recovery, i.e. not a replica at all
+                Ok(Ok(Some(lsn))) => parse_lsn(&lsn).map(|lsn| primary_lsn.saturating_sub(lsn)),
+                Ok(Ok(None)) => {
+                    log::warn!("{} is not a standby; excluding it from reads", replica.name);
+                    None
+                }
+                Ok(Err(e)) => {
+                    log::warn!("Replica {} lag check failed: {}", replica.name, e);
+                    None
+                }
+                Err(_) => None,
+            };
+
+            let eligible = lag.is_some_and(|lag| lag <= max_lag);
+            if replica.eligible.swap(eligible, Ordering::Relaxed) != eligible {
+                log::info!("Replica {} {} (lag {:?} bytes)", replica.name, if eligible { "admitted" } else { "excluded" }, lag);
+            }
+            if let Some(lag) = lag {
+                replica.lag_bytes.store(lag, Ordering::Relaxed);
+                gauge!("db.replica.lag_bytes", lag as f64, "replica" => replica.name.clone());
+            }
+            gauge!("db.replica.eligible", eligible as u8 as f64, "replica" => replica.name.clone());
+        }
+        Ok(())
+    }
+
+    /// Last measured replay lag per replica, in bytes
+    pub fn replica_lag(&self) -> Vec<(String, u64, bool)> {
+        self.replicas
+            .iter()
+            .map(|r| (r.name.clone(), r.lag_bytes.load(Ordering::Relaxed), r.eligible.load(Ordering::Relaxed)))
+            .collect()
+    }
+
     pub fn metrics(&self) -> &MetricsCollector {
         &self.metrics
     }
 }
 
+fn tls_connector(config: &Config) -> Result<MakeTlsConnector, Error> {
+    let mut ssl_builder = SslConnector::builder(SslMethod::tls())?;
+    ssl_builder.set_verify(SslVerifyMode::PEER);
+    
+    if let Some(ca_path) = config.get_ssl_root_cert() {
+        ssl_builder.set_ca_file(ca_path)?;
+    }
+    Ok(MakeTlsConnector::new(ssl_builder.build()))
+}
+
+/// Dedicated connection outside any pool, for work that needs `&mut Client` such as migrations
+pub async fn connect_direct(config: &Config) -> Result<Client, Error> {
+    let (client, connection) = timeout(CONNECTION_TIMEOUT, config.connect(tls_connector(config)?)).await??;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            log::error!("Postgres connection error: {}", e);
+        }
+    });
+    Ok(client)
+}
+
+/// WAL position `X/Y` as a byte offset
+fn parse_lsn(lsn: &str) -> Option<u64> {
+    let (high, low) = lsn.split_once('/')?;
+    Some((u64::from_str_radix(high, 16).ok()? << 32) | u64::from_str_radix(low, 16).ok()?)
+}
+
+fn replica_name(config: &Config) -> String {
+    let host = match config.get_hosts().first() {
+        Some(tokio_postgres::config::Host::Tcp(host)) => host.clone(),
+        #[cfg(unix)]
+        Some(tokio_postgres::config::Host::Unix(path)) => path.display().to_string(),
+        None => "localhost".to_string(),
+    };
+    format!("{}/{}", host, config.get_dbname().unwrap_or("postgres"))
+}
+
 pub struct PooledClient {
     client: Client,
     metrics: MetricsCollector,
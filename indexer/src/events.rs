@@ -0,0 +1,476 @@
+// indexer/src/events.rs
+
+use crate::{
+    idl::DecodedEvent,
+    solana_listener::{
+        ConsentChange, ContributionRevocation, DataContribution, InferenceChallenge, InferenceFinalization, InferenceFulfillment, InferenceRequest,
+        MetadataUpdate, ModelExpiry, ModelFork, ModelRegistration, ProgramEventType, ProposalCreation,
+        VersionUpdate, Vote,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// An on-chain `#[event]`, as decoded from the program's IDL
+pub trait OnChainEvent: DeserializeOwned {
+    /// Event name in the IDL
+    const NAME: &'static str;
+
+    /// The indexer's view of the event; `None` for events it does not store
+    fn into_program_event(self, program_id: &Pubkey) -> Option<ProgramEventType>;
+}
+
+type Handler = Box<dyn Fn(Value, &Pubkey) -> anyhow::Result<Option<ProgramEventType>> + Send + Sync>;
+
+/// Routes decoded events to the typed handler registered under their name
+pub struct EventDispatcher {
+    handlers: HashMap<&'static str, Handler>,
+}
+
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self { handlers: HashMap::new() }
+    }
+
+    /// Dispatcher for every model_registry and governance event the indexer stores
+    pub fn with_program_events() -> Self {
+        let mut dispatcher = Self::new();
+        dispatcher.register::<ModelRegistered>();
+        dispatcher.register::<ModelForked>();
+        dispatcher.register::<ModelExpired>();
+        dispatcher.register::<ModelStateChanged>();
+        dispatcher.register::<VersionUpdated>();
+        dispatcher.register::<DataContributed>();
+        dispatcher.register::<ConsentRecorded>();
+        dispatcher.register::<ConsentWithdrawn>();
+        dispatcher.register::<ContributionRevoked>();
+        dispatcher.register::<InferenceRequested>();
+        dispatcher.register::<InferenceFulfilled>();
+        dispatcher.register::<InferenceChallenged>();
+        dispatcher.register::<InferenceFinalized>();
+        dispatcher.register::<ProposalCreated>();
+        dispatcher.register::<VoteCast>();
+        dispatcher
+    }
+
+    pub fn register<E: OnChainEvent>(&mut self) {
+        self.handlers.insert(
+            E::NAME,
+            Box::new(|data, program_id| Ok(serde_json::from_value::<E>(data)?.into_program_event(program_id))),
+        );
+    }
+
+    /// `Ok(None)` for events with no registered handler, or that the handler skips
+    pub fn dispatch(&self, event: DecodedEvent) -> anyhow::Result<Option<ProgramEventType>> {
+        match self.handlers.get(event.name.as_str()) {
+            Some(handler) => handler(event.data, &event.program_id),
+            None => {
+                metrics::increment_counter!("events_unhandled_total", "event" => event.name);
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl Default for EventDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRegistered {
+    pub model_hash: [u8; 32],
+    pub owner: String,
+    pub timestamp: i64,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+}
+
+impl OnChainEvent for ModelRegistered {
+    const NAME: &'static str = "ModelRegistered";
+
+    fn into_program_event(self, program_id: &Pubkey) -> Option<ProgramEventType> {
+        // The event carries the hash; the account is the `[b"model", hash]` PDA
+        let (model, _) = Pubkey::find_program_address(&[b"model", &self.model_hash], program_id);
+        Some(ProgramEventType::ModelRegistered(ModelRegistration {
+            id: model,
+            owner: Pubkey::from_str(&self.owner).ok()?,
+            metadata: serde_json::json!({
+                "model_hash": hex(&self.model_hash),
+                "fee_mint": self.fee_mint,
+                "registered_at": self.timestamp,
+            }),
+            inference_fee: self.fee,
+            permissions: Vec::new(),
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelForked {
+    pub model: String,
+    pub parent: String,
+    pub model_hash: [u8; 32],
+    pub owner: String,
+    pub royalty_bps: u16,
+    pub inference_fee: u64,
+    pub timestamp: i64,
+}
+
+impl OnChainEvent for ModelForked {
+    const NAME: &'static str = "ModelForked";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::ModelForked(ModelFork {
+            model_id: self.model,
+            parent_id: self.parent,
+            owner: self.owner,
+            royalty_bps: self.royalty_bps,
+            inference_fee: self.inference_fee,
+            timestamp: self.timestamp,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelExpired {
+    pub model: String,
+    pub model_hash: [u8; 32],
+    pub owner: String,
+    pub expired_at: i64,
+    pub reclaimed_at: i64,
+    pub reclaimed_by: String,
+}
+
+impl OnChainEvent for ModelExpired {
+    const NAME: &'static str = "ModelExpired";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::ModelExpired(ModelExpiry {
+            model_id: self.model,
+            owner: self.owner,
+            expired_at: self.expired_at,
+            reclaimed_at: self.reclaimed_at,
+            reclaimed_by: self.reclaimed_by,
+        }))
+    }
+}
+
+/// Only `MetadataUri` changes are indexed; the other fields live on the account
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStateChanged {
+    pub model: String,
+    /// Unit variant name, e.g. `"MetadataUri"`
+    pub field: String,
+    pub old_value: Vec<u8>,
+    pub new_value: Vec<u8>,
+    pub changed_by: String,
+}
+
+impl OnChainEvent for ModelStateChanged {
+    const NAME: &'static str = "ModelStateChanged";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        if self.field != "MetadataUri" {
+            return None;
+        }
+        Some(ProgramEventType::ModelMetadataSet(MetadataUpdate {
+            model_id: self.model,
+            metadata_uri: String::from_utf8(self.new_value).ok()?,
+            changed_by: self.changed_by,
+            // Not in the event; the listener's indexing time stands in
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64),
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionUpdated {
+    pub model: String,
+    pub new_version: u64,
+    pub timestamp: i64,
+}
+
+impl OnChainEvent for VersionUpdated {
+    const NAME: &'static str = "VersionUpdated";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::VersionUpdated(VersionUpdate {
+            model_id: self.model,
+            version: self.new_version,
+            timestamp: self.timestamp,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataContributed {
+    pub contributor: String,
+    pub data_hash: [u8; 32],
+    pub model: String,
+}
+
+impl OnChainEvent for DataContributed {
+    const NAME: &'static str = "DataContributed";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::DataContributed(DataContribution {
+            model_id: self.model,
+            contributor: self.contributor,
+            data_hash: hex(&self.data_hash),
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsentRecorded {
+    pub model: String,
+    pub contributor: String,
+    pub data_hash: [u8; 32],
+    pub expires_at: i64,
+}
+
+impl OnChainEvent for ConsentRecorded {
+    const NAME: &'static str = "ConsentRecorded";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::ConsentChanged(ConsentChange {
+            model_id: self.model,
+            contributor: self.contributor,
+            data_hash: hex(&self.data_hash),
+            expires_at: self.expires_at,
+            withdrawn: false,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsentWithdrawn {
+    pub model: String,
+    pub contributor: String,
+    pub data_hash: [u8; 32],
+    pub expires_at: i64,
+}
+
+impl OnChainEvent for ConsentWithdrawn {
+    const NAME: &'static str = "ConsentWithdrawn";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::ConsentChanged(ConsentChange {
+            model_id: self.model,
+            contributor: self.contributor,
+            data_hash: hex(&self.data_hash),
+            expires_at: self.expires_at,
+            withdrawn: true,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionRevoked {
+    pub model: String,
+    pub contributor: String,
+    pub data_hash: [u8; 32],
+    pub revoked_at: i64,
+}
+
+impl OnChainEvent for ContributionRevoked {
+    const NAME: &'static str = "ContributionRevoked";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::ContributionRevoked(ContributionRevocation {
+            model_id: self.model,
+            contributor: self.contributor,
+            data_hash: hex(&self.data_hash),
+            revoked_at: self.revoked_at,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceRequested {
+    pub model: String,
+    pub request: String,
+    pub timestamp: i64,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+}
+
+impl OnChainEvent for InferenceRequested {
+    const NAME: &'static str = "InferenceRequested";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::InferenceRequested(InferenceRequest {
+            request_id: self.request,
+            model_id: self.model,
+            fee: self.fee,
+            fee_mint: self.fee_mint,
+            timestamp: self.timestamp,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceFulfilled {
+    pub model: String,
+    pub request: String,
+    pub provider: String,
+    /// `InferenceStatus` variant name
+    pub status: String,
+    pub output_hash: [u8; 32],
+    pub error_code: u32,
+    pub payee: String,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+    pub challenge_deadline: i64,
+    pub timestamp: i64,
+}
+
+impl OnChainEvent for InferenceFulfilled {
+    const NAME: &'static str = "InferenceFulfilled";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::InferenceFulfilled(InferenceFulfillment {
+            request_id: self.request,
+            model_id: self.model,
+            provider: self.provider,
+            status: self.status.to_lowercase(),
+            output_hash: hex(&self.output_hash),
+            error_code: self.error_code,
+            payee: self.payee,
+            fee: self.fee,
+            fee_mint: self.fee_mint,
+            challenge_deadline: self.challenge_deadline,
+            timestamp: self.timestamp,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceChallenged {
+    pub model: String,
+    pub request: String,
+    pub provider: String,
+    pub challenger: String,
+    pub slashed: u64,
+    pub challenger_reward: u64,
+    pub timestamp: i64,
+}
+
+impl OnChainEvent for InferenceChallenged {
+    const NAME: &'static str = "InferenceChallenged";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::InferenceChallenged(InferenceChallenge {
+            request_id: self.request,
+            model_id: self.model,
+            provider: self.provider,
+            challenger: self.challenger,
+            slashed: self.slashed,
+            challenger_reward: self.challenger_reward,
+            timestamp: self.timestamp,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceFinalized {
+    pub model: String,
+    pub request: String,
+    pub provider: String,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+    pub timestamp: i64,
+}
+
+impl OnChainEvent for InferenceFinalized {
+    const NAME: &'static str = "InferenceFinalized";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::InferenceFinalized(InferenceFinalization {
+            request_id: self.request,
+            model_id: self.model,
+            provider: self.provider,
+            fee: self.fee,
+            fee_mint: self.fee_mint,
+            timestamp: self.timestamp,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalCreated {
+    pub proposal: String,
+    pub id: u64,
+    pub author: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub created_at: i64,
+}
+
+impl OnChainEvent for ProposalCreated {
+    const NAME: &'static str = "ProposalCreated";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::ProposalCreated(ProposalCreation {
+            proposal_id: self.proposal,
+            author: self.author,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            created_at: self.created_at,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteCast {
+    pub proposal: String,
+    pub voter: String,
+    pub delegate: Option<String>,
+    pub choice: u8,
+    pub weight: u64,
+    pub effective_weight: u64,
+    pub cast_at: i64,
+}
+
+impl OnChainEvent for VoteCast {
+    const NAME: &'static str = "VoteCast";
+
+    fn into_program_event(self, _: &Pubkey) -> Option<ProgramEventType> {
+        Some(ProgramEventType::VoteCast(Vote {
+            proposal_id: self.proposal,
+            voter: self.voter,
+            delegate: self.delegate,
+            choice: self.choice,
+            weight: self.weight,
+            effective_weight: self.effective_weight,
+            cast_at: self.cast_at,
+        }))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
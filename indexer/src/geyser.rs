@@ -0,0 +1,274 @@
+// indexer/src/geyser.rs
+
+use crate::{
+    backfill::Backfiller,
+    solana_listener::{SolanaEventListener, MAX_RETRIES, RECONNECT_BACKOFF},
+};
+use futures::{Sink, Stream, StreamExt};
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{clock::Slot, signature::Signature};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
+use tokio::{
+    sync::mpsc,
+    time::{sleep, Duration},
+};
+use tracing::{error, info, instrument, warn};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::{
+    prelude::{
+        subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+        SubscribeRequestFilterTransactions, SubscribeUpdate, SubscribeUpdateAccount, SubscribeUpdateTransaction,
+    },
+    tonic::Status,
+};
+
+/// How live program events reach the indexer (`solana.ingestion`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IngestionMode {
+    /// `logsSubscribe` on the RPC WebSocket
+    #[default]
+    Websocket,
+    /// Yellowstone gRPC transaction and account streams
+    Geyser,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeyserConfig {
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// `x-token` for authenticated providers
+    #[serde(default)]
+    pub x_token: Option<String>,
+    /// Signatures remembered to drop duplicates across the two streams
+    #[serde(default = "default_dedup_window")]
+    pub dedup_window: usize,
+    /// Slots an account write waits for its transaction before that is fetched over RPC
+    #[serde(default = "default_reconcile_after_slots")]
+    pub reconcile_after_slots: u64,
+}
+
+impl Default for GeyserConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: default_endpoint(),
+            x_token: None,
+            dedup_window: default_dedup_window(),
+            reconcile_after_slots: default_reconcile_after_slots(),
+        }
+    }
+}
+
+fn default_endpoint() -> String {
+    "http://127.0.0.1:10000".to_string()
+}
+
+fn default_dedup_window() -> usize {
+    100_000
+}
+
+fn default_reconcile_after_slots() -> u64 {
+    // Past finality, so the RPC fetch at `finalized` commitment succeeds
+    64
+}
+
+/// Live ingestion from a Yellowstone gRPC (Geyser) endpoint.
+///
+/// Events are decoded from the transaction stream. The account stream is a
+/// safety net: a write to a program account whose transaction never arrived
+/// is fetched over RPC once `reconcile_after_slots` have passed. Both end in
+/// `SolanaEventListener::handle_event`, which is idempotent per event.
+pub struct GeyserIngester {
+    listener: SolanaEventListener,
+    backfiller: Backfiller,
+    config: GeyserConfig,
+    seen: RecentSignatures,
+    /// Account writes whose transaction has not been seen, with their slot
+    unmatched: HashMap<Signature, Slot>,
+}
+
+impl GeyserIngester {
+    pub fn new(listener: SolanaEventListener, rpc_client: Arc<RpcClient>, config: GeyserConfig) -> Self {
+        Self {
+            backfiller: Backfiller::new(rpc_client, listener.clone()),
+            seen: RecentSignatures::new(config.dedup_window),
+            unmatched: HashMap::new(),
+            listener,
+            config,
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn run(&mut self, shutdown: mpsc::Sender<()>) -> anyhow::Result<()> {
+        let finality_task = self.listener.spawn_finality_tracker(shutdown.clone());
+
+        let mut retry_count = 0;
+        loop {
+            match subscribe(self.config.clone(), self.subscribe_request()).await {
+                // The request sink is held so the server keeps the subscription open
+                Ok((_requests, updates)) => {
+                    retry_count = 0;
+                    info!(endpoint = %self.config.endpoint, "Geyser stream connected");
+                    if let Err(e) = self.process_updates(updates).await {
+                        error!(error = %e, "Geyser stream error");
+                    }
+                }
+                Err(e) => {
+                    retry_count += 1;
+                    metrics::increment_counter!("geyser_reconnects_total");
+                    if retry_count > MAX_RETRIES {
+                        error!(error = %e, "Max connection retries exceeded");
+                        shutdown.send(()).await?;
+                        break;
+                    }
+
+                    let delay = Duration::from_secs(RECONNECT_BACKOFF[(retry_count - 1).min(RECONNECT_BACKOFF.len() - 1)]);
+                    warn!(retries = retry_count, delay_secs = delay.as_secs(), error = %e, "Reconnecting...");
+                    sleep(delay).await;
+                }
+            }
+        }
+        finality_task.abort();
+        Ok(())
+    }
+
+    fn subscribe_request(&self) -> SubscribeRequest {
+        let config = self.listener.config();
+        let programs = vec![config.program_id.to_string(), config.governance_program_id.to_string()];
+
+        SubscribeRequest {
+            transactions: HashMap::from([(
+                "programs".to_string(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    // A failed transaction's state was rolled back, whatever it logged
+                    failed: Some(false),
+                    account_include: programs.clone(),
+                    ..Default::default()
+                },
+            )]),
+            accounts: HashMap::from([(
+                "program_accounts".to_string(),
+                SubscribeRequestFilterAccounts {
+                    owner: programs,
+                    ..Default::default()
+                },
+            )]),
+            commitment: Some(CommitmentLevel::Confirmed as i32),
+            ..Default::default()
+        }
+    }
+
+    async fn process_updates(
+        &mut self,
+        updates: impl Stream<Item = Result<SubscribeUpdate, Status>>,
+    ) -> anyhow::Result<()> {
+        let mut updates = std::pin::pin!(updates);
+        while let Some(update) = updates.next().await {
+            match update?.update_oneof {
+                Some(UpdateOneof::Transaction(update)) => self.handle_transaction(update).await?,
+                Some(UpdateOneof::Account(update)) => self.handle_account(update),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_transaction(&mut self, update: SubscribeUpdateTransaction) -> anyhow::Result<()> {
+        let Some(info) = update.transaction else { return Ok(()) };
+        let signature = Signature::try_from(info.signature.as_slice())?;
+        if self.seen.contains(&signature) {
+            metrics::increment_counter!("geyser_duplicate_transactions_total");
+            return Ok(());
+        }
+
+        if let Some(meta) = info.meta.filter(|meta| meta.err.is_none()) {
+            let events = self
+                .listener
+                .parse_logs(&signature.to_string(), update.slot as i64, &meta.log_messages);
+            for event in events {
+                self.listener.handle_event(event).await?;
+            }
+        }
+
+        self.seen.insert(signature);
+        self.unmatched.remove(&signature);
+        self.reconcile(update.slot).await;
+        Ok(())
+    }
+
+    fn handle_account(&mut self, update: SubscribeUpdateAccount) {
+        // Startup snapshots and some writes carry no transaction
+        let Some(signature) = update.account.and_then(|account| account.txn_signature) else { return };
+        let Ok(signature) = Signature::try_from(signature.as_slice()) else { return };
+        if !self.seen.contains(&signature) {
+            self.unmatched.entry(signature).or_insert(update.slot);
+        }
+    }
+
+    /// Fetch transactions that only surfaced as account writes
+    async fn reconcile(&mut self, current_slot: Slot) {
+        let due: Vec<(Signature, Slot)> = self
+            .unmatched
+            .iter()
+            .filter(|(_, slot)| current_slot.saturating_sub(**slot) >= self.config.reconcile_after_slots)
+            .map(|(signature, slot)| (*signature, *slot))
+            .collect();
+
+        for (signature, slot) in due {
+            self.unmatched.remove(&signature);
+            if self.seen.contains(&signature) {
+                continue;
+            }
+            match self.backfiller.replay_transaction(&signature, slot).await {
+                Ok(events) => {
+                    self.seen.insert(signature);
+                    metrics::increment_counter!("geyser_reconciled_transactions_total");
+                    warn!(%signature, slot, events, "Transaction missing from the stream, fetched over RPC");
+                }
+                Err(e) => warn!(%signature, slot, error = %e, "Failed to reconcile transaction"),
+            }
+        }
+    }
+}
+
+async fn subscribe(
+    config: GeyserConfig,
+    request: SubscribeRequest,
+) -> anyhow::Result<(impl Sink<SubscribeRequest>, impl Stream<Item = Result<SubscribeUpdate, Status>>)> {
+    let mut client = GeyserGrpcClient::connect(config.endpoint, config.x_token, None)?;
+    Ok(client.subscribe_with_request(Some(request)).await?)
+}
+
+/// Bounded set of recently processed signatures, oldest evicted first
+struct RecentSignatures {
+    order: VecDeque<Signature>,
+    set: HashSet<Signature>,
+    capacity: usize,
+}
+
+impl RecentSignatures {
+    fn new(capacity: usize) -> Self {
+        Self { order: VecDeque::new(), set: HashSet::new(), capacity: capacity.max(1) }
+    }
+
+    fn contains(&self, signature: &Signature) -> bool {
+        self.set.contains(signature)
+    }
+
+    fn insert(&mut self, signature: Signature) {
+        if !self.set.insert(signature) {
+            return;
+        }
+        self.order.push_back(signature);
+        if self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.set.remove(&evicted);
+            }
+        }
+    }
+}
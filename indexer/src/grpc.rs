@@ -0,0 +1,326 @@
+// indexer/src/grpc.rs
+
+use crate::solana_listener::{ProgramEvent, ProgramEventType};
+use anyhow::Context;
+use futures::{Stream, StreamExt};
+use proto::{
+    event_stream_server::{EventStream, EventStreamServer},
+    governance_event, model_event, EventMeta, GovernanceEvent, ModelEvent, SubscribeGovernanceRequest,
+    SubscribeModelEventsRequest,
+};
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{Consumer, StreamConsumer},
+    message::{Headers, Message},
+};
+use serde::Deserialize;
+use solana_program::pubkey::Pubkey;
+use std::{net::SocketAddr, pin::Pin, str::FromStr, sync::Arc};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tonic::{Request, Response, Status};
+use tracing::{info, instrument, warn};
+
+pub mod proto {
+    tonic::include_proto!("scoria.events.v1");
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrpcConfig {
+    #[serde(default = "default_listen_addr")]
+    pub listen_addr: SocketAddr,
+    /// Prefix of the per-instance consumer group; every instance reads every event
+    #[serde(default = "default_consumer_group")]
+    pub consumer_group: String,
+    /// Events buffered per subscriber before it is cut off as lagging
+    #[serde(default = "default_subscriber_buffer")]
+    pub subscriber_buffer: usize,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: default_listen_addr(),
+            consumer_group: default_consumer_group(),
+            subscriber_buffer: default_subscriber_buffer(),
+        }
+    }
+}
+
+fn default_listen_addr() -> SocketAddr {
+    SocketAddr::from(([0, 0, 0, 0], 50051))
+}
+
+fn default_consumer_group() -> String {
+    "scoria-indexer-grpc".to_string()
+}
+
+fn default_subscriber_buffer() -> usize {
+    1024
+}
+
+/// One message from the event topic
+#[derive(Debug)]
+enum Streamed {
+    Event { key: String, event: ProgramEvent },
+    Retracted { key: String },
+}
+
+type Feed = broadcast::Sender<Arc<Streamed>>;
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Serve `scoria.events.v1.EventStream` on `listen_addr` until shutdown.
+///
+/// Subscriptions start at the live edge: each instance tails the topic from
+/// the latest offset in a consumer group of its own and fans out in memory.
+pub async fn serve(
+    mut client_config: ClientConfig,
+    topic: String,
+    config: GrpcConfig,
+    shutdown: mpsc::Sender<()>,
+) -> anyhow::Result<()> {
+    let consumer: StreamConsumer = client_config
+        .set("group.id", format!("{}-{:016x}", config.consumer_group, rand::random::<u64>()))
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "latest")
+        .set("isolation.level", "read_committed")
+        .create()?;
+    consumer.subscribe(&[&topic])?;
+
+    let (feed, _) = broadcast::channel(config.subscriber_buffer.max(1));
+    let service = EventStreamService { feed: feed.clone() };
+
+    info!(addr = %config.listen_addr, %topic, "gRPC event stream started");
+    let server = tonic::transport::Server::builder()
+        .add_service(EventStreamServer::new(service))
+        .serve_with_shutdown(config.listen_addr, shutdown.closed());
+
+    tokio::select! {
+        served = server => served.context("gRPC server failed"),
+        consumed = consume(&consumer, &feed) => consumed,
+    }
+}
+
+#[instrument(skip_all)]
+async fn consume(consumer: &StreamConsumer, feed: &Feed) -> anyhow::Result<()> {
+    loop {
+        let message = consumer.recv().await?;
+        let Some(key) = message.key().map(|k| String::from_utf8_lossy(k).into_owned()) else {
+            continue;
+        };
+        let retracted = message
+            .headers()
+            .map(|headers| headers.iter().any(|h| h.key == "retracted"))
+            .unwrap_or(false);
+
+        let streamed = if retracted {
+            Streamed::Retracted { key }
+        } else {
+            let Some(payload) = message.payload() else { continue };
+            match serde_json::from_slice(payload) {
+                Ok(event) => Streamed::Event { key, event },
+                Err(e) => {
+                    warn!(%key, error = %e, "Undecodable event on the topic");
+                    continue;
+                }
+            }
+        };
+
+        // No subscribers is not an error; the event is simply not pushed
+        let _ = feed.send(Arc::new(streamed));
+    }
+}
+
+struct EventStreamService {
+    feed: Feed,
+}
+
+impl EventStreamService {
+    /// Subscriber stream of `feed`, mapped by `select`; ends with `RESOURCE_EXHAUSTED`
+    /// when the subscriber falls `subscriber_buffer` events behind
+    fn subscribe<T, F>(&self, select: F) -> ResponseStream<T>
+    where
+        T: Send + 'static,
+        F: Fn(&Streamed) -> Option<T> + Send + 'static,
+    {
+        let stream = BroadcastStream::new(self.feed.subscribe()).filter_map(move |received| {
+            let item = match received {
+                Ok(streamed) => select(&streamed).map(Ok),
+                Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                    metrics::increment_counter!("grpc_subscribers_lagged_total");
+                    Some(Err(Status::resource_exhausted(format!(
+                        "subscriber fell behind by {missed} events; resubscribe"
+                    ))))
+                }
+            };
+            futures::future::ready(item)
+        });
+        Box::pin(stream)
+    }
+}
+
+#[tonic::async_trait]
+impl EventStream for EventStreamService {
+    type SubscribeModelEventsStream = ResponseStream<ModelEvent>;
+    type SubscribeGovernanceStream = ResponseStream<GovernanceEvent>;
+
+    async fn subscribe_model_events(
+        &self,
+        request: Request<SubscribeModelEventsRequest>,
+    ) -> Result<Response<Self::SubscribeModelEventsStream>, Status> {
+        let model = request.into_inner().model_pubkey;
+        Pubkey::from_str(&model).map_err(|_| Status::invalid_argument("model_pubkey is not a base58 public key"))?;
+
+        metrics::increment_counter!("grpc_subscriptions_total", "rpc" => "SubscribeModelEvents");
+        Ok(Response::new(self.subscribe(move |streamed| match streamed {
+            Streamed::Event { key, event } if event.inner.model_id().as_deref() == Some(model.as_str()) => {
+                Some(ModelEvent { meta: Some(meta(key, event)), event: Some(model_event(&event.inner)?) })
+            }
+            Streamed::Event { .. } => None,
+            Streamed::Retracted { key } => Some(ModelEvent {
+                meta: Some(retracted_meta(key)),
+                event: Some(model_event::Event::Retracted(proto::Retracted {})),
+            }),
+        })))
+    }
+
+    async fn subscribe_governance(
+        &self,
+        _: Request<SubscribeGovernanceRequest>,
+    ) -> Result<Response<Self::SubscribeGovernanceStream>, Status> {
+        metrics::increment_counter!("grpc_subscriptions_total", "rpc" => "SubscribeGovernance");
+        Ok(Response::new(self.subscribe(|streamed| match streamed {
+            Streamed::Event { key, event } => Some(GovernanceEvent {
+                meta: Some(meta(key, event)),
+                event: Some(governance_event(&event.inner)?),
+            }),
+            Streamed::Retracted { key } => Some(GovernanceEvent {
+                meta: Some(retracted_meta(key)),
+                event: Some(governance_event::Event::Retracted(proto::Retracted {})),
+            }),
+        })))
+    }
+}
+
+fn meta(key: &str, event: &ProgramEvent) -> EventMeta {
+    EventMeta {
+        key: key.to_string(),
+        signature: event.signature.clone(),
+        slot: event.slot as u64,
+    }
+}
+
+fn retracted_meta(key: &str) -> EventMeta {
+    EventMeta {
+        key: key.to_string(),
+        signature: key.split(':').next().unwrap_or_default().to_string(),
+        slot: 0,
+    }
+}
+
+/// `None` for governance events
+fn model_event(event: &ProgramEventType) -> Option<model_event::Event> {
+    use model_event::Event;
+
+    Some(match event {
+        ProgramEventType::ModelRegistered(model) => Event::Registered(proto::ModelRegistered {
+            owner: model.owner.to_string(),
+            inference_fee: model.inference_fee,
+            metadata_json: model.metadata.to_string(),
+        }),
+        ProgramEventType::ModelUpdated(update) => Event::Updated(proto::ModelUpdated {
+            payload_json: serde_json::to_string(update).ok()?,
+        }),
+        ProgramEventType::ModelDeleted(deletion) => Event::Deleted(proto::ModelDeleted {
+            payload_json: serde_json::to_string(deletion).ok()?,
+        }),
+        ProgramEventType::ModelExpired(expiry) => Event::Expired(proto::ModelExpired {
+            owner: expiry.owner.clone(),
+            expired_at: expiry.expired_at,
+            reclaimed_at: expiry.reclaimed_at,
+            reclaimed_by: expiry.reclaimed_by.clone(),
+        }),
+        ProgramEventType::ModelForked(fork) => Event::Forked(proto::ModelForked {
+            parent_id: fork.parent_id.clone(),
+            owner: fork.owner.clone(),
+            royalty_bps: fork.royalty_bps.into(),
+            inference_fee: fork.inference_fee,
+            timestamp: fork.timestamp,
+        }),
+        ProgramEventType::ModelMetadataSet(metadata) => Event::MetadataSet(proto::MetadataSet {
+            metadata_uri: metadata.metadata_uri.clone(),
+            changed_by: metadata.changed_by.clone(),
+            timestamp: metadata.timestamp,
+        }),
+        ProgramEventType::VersionUpdated(update) => Event::VersionUpdated(proto::VersionUpdated {
+            version: update.version,
+            timestamp: update.timestamp,
+        }),
+        ProgramEventType::DataContributed(contribution) => Event::DataContributed(proto::DataContributed {
+            contributor: contribution.contributor.clone(),
+            data_hash: contribution.data_hash.clone(),
+        }),
+        ProgramEventType::InferenceRequested(request) => Event::InferenceRequested(proto::InferenceRequested {
+            request_id: request.request_id.clone(),
+            fee: request.fee,
+            fee_mint: request.fee_mint.clone().unwrap_or_default(),
+            timestamp: request.timestamp,
+        }),
+        ProgramEventType::InferenceFulfilled(fulfillment) => Event::InferenceFulfilled(proto::InferenceFulfilled {
+            request_id: fulfillment.request_id.clone(),
+            provider: fulfillment.provider.clone(),
+            status: fulfillment.status.clone(),
+            output_hash: fulfillment.output_hash.clone(),
+            error_code: fulfillment.error_code,
+            payee: fulfillment.payee.clone(),
+            fee: fulfillment.fee,
+            fee_mint: fulfillment.fee_mint.clone().unwrap_or_default(),
+            challenge_deadline: fulfillment.challenge_deadline,
+            timestamp: fulfillment.timestamp,
+        }),
+        ProgramEventType::InferenceChallenged(challenge) => Event::InferenceChallenged(proto::InferenceChallenged {
+            request_id: challenge.request_id.clone(),
+            provider: challenge.provider.clone(),
+            challenger: challenge.challenger.clone(),
+            slashed: challenge.slashed,
+            challenger_reward: challenge.challenger_reward,
+            timestamp: challenge.timestamp,
+        }),
+        ProgramEventType::InferenceFinalized(finalized) => Event::InferenceFinalized(proto::InferenceFinalized {
+            request_id: finalized.request_id.clone(),
+            provider: finalized.provider.clone(),
+            fee: finalized.fee,
+            fee_mint: finalized.fee_mint.clone().unwrap_or_default(),
+            timestamp: finalized.timestamp,
+        }),
+        ProgramEventType::ProposalCreated(_)
+        | ProgramEventType::VoteCast(_)
+        | ProgramEventType::ConsentChanged(_)
+        | ProgramEventType::ContributionRevoked(_) => return None,
+    })
+}
+
+/// `None` for everything but proposals and votes
+fn governance_event(event: &ProgramEventType) -> Option<governance_event::Event> {
+    use governance_event::Event;
+
+    match event {
+        ProgramEventType::ProposalCreated(proposal) => Some(Event::ProposalCreated(proto::ProposalCreated {
+            proposal_id: proposal.proposal_id.clone(),
+            author: proposal.author.clone(),
+            start_time: proposal.start_time,
+            end_time: proposal.end_time,
+            created_at: proposal.created_at,
+        })),
+        ProgramEventType::VoteCast(vote) => Some(Event::VoteCast(proto::VoteCast {
+            proposal_id: vote.proposal_id.clone(),
+            voter: vote.voter.clone(),
+            delegate: vote.delegate.clone().unwrap_or_default(),
+            choice: vote.choice.into(),
+            weight: vote.weight,
+            effective_weight: vote.effective_weight,
+            cast_at: vote.cast_at,
+        })),
+        _ => None,
+    }
+}
@@ -0,0 +1,328 @@
+// indexer/src/idl.rs
+
+use anyhow::{anyhow, bail, ensure, Context};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use solana_program::pubkey::Pubkey;
+use std::{collections::HashMap, path::Path, str::FromStr};
+
+const PROGRAM_DATA: &str = "Program data: ";
+/// Nesting limit for `defined` types, against self-referential IDLs
+const MAX_DEPTH: usize = 16;
+
+/// The subset of an Anchor IDL needed to decode events
+#[derive(Debug, Clone, Deserialize)]
+pub struct Idl {
+    pub name: String,
+    #[serde(default)]
+    pub events: Vec<IdlEvent>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlEvent {
+    pub name: String,
+    pub fields: Vec<IdlField>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefTy {
+    Struct { fields: Vec<IdlField> },
+    Enum { variants: Vec<IdlEnumVariant> },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    /// Named fields, or bare types for tuple variants
+    #[serde(default)]
+    pub fields: Option<IdlVariantFields>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlVariantFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlType {
+    /// `bool`, `u8`..`u128`, `i8`..`i128`, `f32`, `f64`, `string`, `bytes`, `publicKey`
+    Primitive(String),
+    Option { option: Box<IdlType> },
+    Vec { vec: Box<IdlType> },
+    Array { array: (Box<IdlType>, usize) },
+    Defined { defined: String },
+}
+
+/// One event decoded from a `Program data:` log line
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    pub program_id: Pubkey,
+    /// Top-level instruction the event was emitted under
+    pub instruction_index: u32,
+    /// Position among the `Program data:` lines of that instruction, CPIs included
+    pub event_index: u32,
+    pub name: String,
+    /// Fields under their IDL (camelCase) names; public keys as base58,
+    /// 128-bit integers as strings, unit enum variants as their name
+    pub data: Value,
+}
+
+struct ProgramIdl {
+    program_id: Pubkey,
+    types: HashMap<String, IdlTypeDef>,
+}
+
+/// Decodes Anchor events for every program whose IDL is loaded.
+///
+/// Events are found by their 8-byte discriminator, `sha256("event:<Name>")`,
+/// and only accepted from the program that is executing when the log line
+/// is written, so a CPI callee cannot forge another program's events.
+pub struct EventDecoder {
+    programs: Vec<ProgramIdl>,
+    /// Discriminator to (index into `programs`, event)
+    events: HashMap<[u8; 8], (usize, IdlEvent)>,
+}
+
+impl EventDecoder {
+    pub fn new() -> Self {
+        Self { programs: Vec::new(), events: HashMap::new() }
+    }
+
+    /// Add the IDL at `path`, as written by `anchor build` under `target/idl`
+    pub fn load(mut self, program_id: Pubkey, path: &Path) -> anyhow::Result<Self> {
+        let idl: Idl = serde_json::from_slice(
+            &std::fs::read(path).with_context(|| format!("Failed to read IDL {}", path.display()))?,
+        )
+        .with_context(|| format!("Invalid IDL {}", path.display()))?;
+        self.add(program_id, idl)?;
+        Ok(self)
+    }
+
+    pub fn add(&mut self, program_id: Pubkey, idl: Idl) -> anyhow::Result<()> {
+        let index = self.programs.len();
+        self.programs.push(ProgramIdl {
+            program_id,
+            types: idl.types.into_iter().map(|t| (t.name.clone(), t)).collect(),
+        });
+        for event in idl.events {
+            let discriminator = discriminator(&event.name);
+            if let Some((other, _)) = self.events.insert(discriminator, (index, event.clone())) {
+                bail!("Event {} of {} collides with one of {}", event.name, idl.name, self.programs[other].program_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Every event in a transaction's logs, in emission order.
+    /// Undecodable payloads are logged and skipped rather than failing the transaction.
+    pub fn decode_logs(&self, logs: &[String]) -> Vec<DecodedEvent> {
+        let mut invoked: Vec<Pubkey> = Vec::new();
+        let mut decoded = Vec::new();
+        // Incremented on each top-level invoke, so the first instruction is 0
+        let mut instruction_index = u32::MAX;
+        let mut event_index = 0;
+        for line in logs {
+            if let Some(payload) = line.strip_prefix(PROGRAM_DATA) {
+                let Some(&program_id) = invoked.last() else { continue };
+                let index = event_index;
+                event_index += 1;
+                match self.decode(program_id, payload) {
+                    Ok(Some(mut event)) => {
+                        event.instruction_index = instruction_index;
+                        event.event_index = index;
+                        decoded.push(event);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        metrics::increment_counter!("event_decode_failures_total");
+                        tracing::warn!(program = %program_id, error = %e, "Undecodable program event");
+                    }
+                }
+            } else if let Some(rest) = line.strip_prefix("Program ") {
+                // `Program <id> invoke [n]`, then `Program <id> success` or `failed: ..`
+                let mut words = rest.split_whitespace();
+                let (Some(id), Some(action)) = (words.next(), words.next()) else { continue };
+                match action {
+                    "invoke" => {
+                        if invoked.is_empty() {
+                            instruction_index = instruction_index.wrapping_add(1);
+                            event_index = 0;
+                        }
+                        if let Ok(id) = Pubkey::from_str(id) {
+                            invoked.push(id);
+                        }
+                    }
+                    "success" | "failed:" => {
+                        invoked.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        decoded
+    }
+
+    /// `Ok(None)` for payloads that are not a known event of `program_id`
+    fn decode(&self, program_id: Pubkey, payload: &str) -> anyhow::Result<Option<DecodedEvent>> {
+        let bytes = STANDARD.decode(payload.trim())?;
+        ensure!(bytes.len() >= 8, "payload shorter than a discriminator");
+        let Some((index, event)) = self.events.get(&<[u8; 8]>::try_from(&bytes[..8])?) else {
+            return Ok(None);
+        };
+        let program = &self.programs[*index];
+        if program.program_id != program_id {
+            return Ok(None);
+        }
+
+        let mut reader = Reader { bytes: &bytes[8..] };
+        let data = program.decode_fields(&event.fields, &mut reader, 0)?;
+        ensure!(reader.bytes.is_empty(), "{} has {} trailing bytes", event.name, reader.bytes.len());
+        Ok(Some(DecodedEvent {
+            program_id,
+            instruction_index: 0,
+            event_index: 0,
+            name: event.name.clone(),
+            data,
+        }))
+    }
+}
+
+impl Default for EventDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgramIdl {
+    fn decode_fields(&self, fields: &[IdlField], reader: &mut Reader, depth: usize) -> anyhow::Result<Value> {
+        let mut object = Map::with_capacity(fields.len());
+        for field in fields {
+            let value = self
+                .decode_type(&field.ty, reader, depth)
+                .with_context(|| format!("field `{}`", field.name))?;
+            object.insert(field.name.clone(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Borsh-decode one value of `ty`
+    fn decode_type(&self, ty: &IdlType, reader: &mut Reader, depth: usize) -> anyhow::Result<Value> {
+        ensure!(depth <= MAX_DEPTH, "types nested deeper than {MAX_DEPTH}");
+        Ok(match ty {
+            IdlType::Primitive(name) => match name.as_str() {
+                "bool" => Value::Bool(reader.take(1)?[0] != 0),
+                "u8" => reader.take(1)?[0].into(),
+                "i8" => (reader.take(1)?[0] as i8).into(),
+                "u16" => u16::from_le_bytes(reader.array()?).into(),
+                "i16" => i16::from_le_bytes(reader.array()?).into(),
+                "u32" => u32::from_le_bytes(reader.array()?).into(),
+                "i32" => i32::from_le_bytes(reader.array()?).into(),
+                "u64" => u64::from_le_bytes(reader.array()?).into(),
+                "i64" => i64::from_le_bytes(reader.array()?).into(),
+                "u128" => u128::from_le_bytes(reader.array()?).to_string().into(),
+                "i128" => i128::from_le_bytes(reader.array()?).to_string().into(),
+                "f32" => f32::from_le_bytes(reader.array()?).into(),
+                "f64" => f64::from_le_bytes(reader.array()?).into(),
+                "publicKey" => Pubkey::new_from_array(reader.array()?).to_string().into(),
+                "string" => {
+                    let len = reader.len()?;
+                    String::from_utf8(reader.take(len)?.to_vec())?.into()
+                }
+                "bytes" => {
+                    let len = reader.len()?;
+                    reader.take(len)?.to_vec().into()
+                }
+                other => bail!("unsupported IDL type `{other}`"),
+            },
+            IdlType::Option { option } => match reader.take(1)?[0] {
+                0 => Value::Null,
+                1 => self.decode_type(option, reader, depth + 1)?,
+                tag => bail!("invalid option tag {tag}"),
+            },
+            IdlType::Vec { vec } => {
+                let len = reader.len()?;
+                let items = (0..len).map(|_| self.decode_type(vec, reader, depth + 1));
+                Value::Array(items.collect::<anyhow::Result<_>>()?)
+            }
+            IdlType::Array { array: (item, len) } => {
+                let items = (0..*len).map(|_| self.decode_type(item, reader, depth + 1));
+                Value::Array(items.collect::<anyhow::Result<_>>()?)
+            }
+            IdlType::Defined { defined } => {
+                let def = self.types.get(defined).ok_or_else(|| anyhow!("undefined type `{defined}`"))?;
+                match &def.ty {
+                    IdlTypeDefTy::Struct { fields } => self.decode_fields(fields, reader, depth + 1)?,
+                    IdlTypeDefTy::Enum { variants } => {
+                        let tag = reader.take(1)?[0] as usize;
+                        let variant = variants
+                            .get(tag)
+                            .ok_or_else(|| anyhow!("`{defined}` has no variant {tag}"))?;
+                        let fields = match &variant.fields {
+                            None => return Ok(Value::String(variant.name.clone())),
+                            Some(IdlVariantFields::Named(fields)) => self.decode_fields(fields, reader, depth + 1)?,
+                            Some(IdlVariantFields::Tuple(types)) => Value::Array(
+                                types
+                                    .iter()
+                                    .map(|t| self.decode_type(t, reader, depth + 1))
+                                    .collect::<anyhow::Result<_>>()?,
+                            ),
+                        };
+                        Value::Object(Map::from_iter([(variant.name.clone(), fields)]))
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> anyhow::Result<&'a [u8]> {
+        ensure!(self.bytes.len() >= n, "truncated: need {n} bytes, {} left", self.bytes.len());
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        Ok(self.take(N)?.try_into()?)
+    }
+
+    /// Borsh `u32` length prefix, bounded by the bytes left so a corrupt prefix cannot allocate
+    fn len(&mut self) -> anyhow::Result<usize> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        ensure!(len <= self.bytes.len(), "length {len} exceeds the {} bytes left", self.bytes.len());
+        Ok(len)
+    }
+}
+
+/// Anchor event discriminator
+pub fn discriminator(event: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("event:{event}").as_bytes());
+    hash[..8].try_into().expect("sha256 is 32 bytes")
+}
@@ -1,17 +1,79 @@
 // indexer/src/main.rs
 
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "indexer", about = "SCORIA blockchain indexer")]
+struct Cli {
+    #[arg(long, global = true, default_value = "config/prod.toml")]
+    config: PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the live indexer (default)
+    Run,
+
+    /// Replay historical program transactions into the database
+    Backfill {
+        #[arg(long)]
+        from_slot: u64,
+
+        #[arg(long)]
+        to_slot: Option<u64>,
+
+        /// Restrict to specific program IDs (defaults to registry + governance)
+        #[arg(long = "program")]
+        programs: Vec<Pubkey>,
+    },
+
+    /// Apply, roll back or list schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+
+        #[arg(long, global = true, default_value = MIGRATIONS_DIR)]
+        dir: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Apply pending migrations (up to --target-version)
+    Up {
+        #[arg(long)]
+        target_version: Option<i64>,
+    },
+    /// Roll back to --target-version (defaults to one migration)
+    Down {
+        #[arg(long)]
+        target_version: Option<i64>,
+    },
+    /// List applied, pending, dirty and modified migrations
+    Status,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize structured logging
-    tracing_subscriber::fmt()
-        .json()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
+    let cli = Cli::parse();
+
+    // Install the Prometheus recorder before anything emits metrics
+    let metrics_handle = monitoring::install_recorder()?;
 
     // Load configuration
-    let config = Config::load("config/prod.toml")
+    let config = Config::load(&cli.config)
         .context("Failed to load configuration")?;
 
+    // Initialize structured logging, and trace export when configured
+    let _telemetry = telemetry::init(config.monitoring.otlp_endpoint.as_deref())?;
+
+    if let Some(Command::Migrate { action, dir }) = cli.command {
+        return run_migrate(&config, action, &dir).await;
+    }
+
     // Initialize database connection pool
     let db_pool = PgPoolOptions::new()
         .max_connections(50)
@@ -19,11 +81,13 @@ async fn main() -> anyhow::Result<()> {
         .await
         .context("Failed to connect to database")?;
 
-    // Initialize Solana client
-    let solana_client = RpcClient::new_with_commitment(
-        config.solana.rpc_endpoint.clone(),
-        CommitmentConfig::confirmed()
-    );
+    // Initialize Solana clients; all share the failover transport and its endpoint health
+    let rpc_endpoints = std::iter::once(config.solana.rpc_endpoint.clone())
+        .chain(config.solana.rpc_fallbacks.iter().cloned())
+        .collect();
+    let rpc_sender = FailoverSender::new(rpc_endpoints, &config.solana.failover);
+    let _rpc_health = spawn_health_checks(&rpc_sender);
+    let solana_client = rpc_sender.client(CommitmentConfig::confirmed());
 
     // Initialize Kafka producer
     let kafka_producer = FutureProducer::new(
@@ -32,12 +96,69 @@ async fn main() -> anyhow::Result<()> {
             .set("compression.codec", "zstd")
     )?;
 
-    // Start health check server
-    let health_server = start_health_server(config.monitoring.health_check_port);
+    if let Some(Command::Backfill { from_slot, to_slot, programs }) = cli.command {
+        return run_backfill(
+            &config,
+            solana_client,
+            db_pool,
+            from_slot,
+            to_slot,
+            programs,
+        )
+        .await;
+    }
+
+    // Start health check server, also serving /metrics and the read API
+    let health_server = monitoring::serve(config.monitoring.health_check_port, metrics_handle, db_pool.clone());
+    let collectors = monitoring::spawn_collectors(
+        db_pool.clone(),
+        Arc::new(rpc_sender.client(CommitmentConfig::confirmed())),
+    );
+    let catalog_fetcher = catalog::spawn_fetcher(db_pool.clone(), config.catalog.clone());
+    // Deletes contributed blobs whose consented retention ran out
+    let retention_enforcer = retention::spawn_enforcer(db_pool.clone(), config.retention.clone());
 
     // Create shutdown signal channel
     let (shutdown_tx, mut shutdown_rx) = mpsc::channel(1);
 
+    // Publishes committed events from the outbox to the configured sink
+    let sink = sink::connect(&config.sink, config.kafka.to_client_config(), &config.outbox.transactional_id).await?;
+    let outbox_relay = OutboxRelay::new(sink, db_pool.clone(), config.outbox.clone());
+    let outbox_task = {
+        let shutdown = shutdown_tx.clone();
+        tokio::spawn(async move { outbox_relay.run(shutdown).await })
+    };
+
+    // Optional analytics sink, independent of the Postgres path
+    #[cfg(feature = "clickhouse")]
+    // Reads the Kafka topic, so it only runs with the Kafka sink
+    let clickhouse_task = match config.clickhouse.clone().filter(|_| matches!(config.sink, SinkConfig::Kafka)) {
+        Some(clickhouse_config) => {
+            let mut sink = ClickHouseSink::new(
+                config.kafka.to_client_config(),
+                config.listener.kafka_topic.clone(),
+                clickhouse_config,
+            )?;
+            let shutdown = shutdown_tx.clone();
+            Some(tokio::spawn(async move { sink.run(shutdown).await }))
+        }
+        None => None,
+    };
+
+    // Push feed for bots and UIs, also fed from the Kafka topic
+    let grpc_task = match config.grpc.clone().filter(|_| matches!(config.sink, SinkConfig::Kafka)) {
+        Some(grpc_config) => {
+            let serve = grpc::serve(
+                config.kafka.to_client_config(),
+                config.listener.kafka_topic.clone(),
+                grpc_config,
+                shutdown_tx.clone(),
+            );
+            Some(tokio::spawn(serve))
+        }
+        None => None,
+    };
+
     // Spawn main indexing tasks
     let tasks = join!(
         spawn_block_processor(
@@ -46,12 +167,14 @@ async fn main() -> anyhow::Result<()> {
             kafka_producer.clone(),
             shutdown_tx.clone(),
         ),
-        spawn_rpc_listener(
-            solana_client.clone(),
-            kafka_producer.clone(),
+        spawn_live_ingestion(
+            &config,
+            &rpc_sender,
+            db_pool.clone(),
             shutdown_tx.clone(),
         ),
         spawn_stream_consumer(
+            &config,
             db_pool.clone(),
             shutdown_tx.clone(),
         ),
@@ -69,20 +192,121 @@ async fn main() -> anyhow::Result<()> {
 
     // Graceful termination
     tracing::info!("Draining resources...");
+    // Closing the channel stops the relay, which first publishes what is left
+    drop(shutdown_rx);
+    outbox_task.await??;
     kafka_producer.flush(None).await?;
     db_pool.close().await;
     health_server.abort();
+    collectors.abort();
+    catalog_fetcher.abort();
+    retention_enforcer.abort();
 
     // Wait for tasks completion
-    let (block_res, rpc_res, stream_res) = tasks;
+    let (block_res, ingest_res, stream_res) = tasks;
     block_res??;
-    rpc_res??;
+    ingest_res??;
     stream_res??;
+    #[cfg(feature = "clickhouse")]
+    if let Some(task) = clickhouse_task {
+        task.await??;
+    }
+    if let Some(task) = grpc_task {
+        task.await??;
+    }
 
     tracing::info!("Indexer shutdown complete");
     Ok(())
 }
 
+async fn run_backfill(
+    config: &Config,
+    solana_client: RpcClient,
+    db_pool: PgPool,
+    from_slot: u64,
+    to_slot: Option<u64>,
+    programs: Vec<Pubkey>,
+) -> anyhow::Result<()> {
+    let rpc_client = Arc::new(solana_client);
+    let to_slot = match to_slot {
+        Some(slot) => slot,
+        None => rpc_client.get_slot_with_commitment(CommitmentConfig::finalized()).await?,
+    };
+    anyhow::ensure!(from_slot <= to_slot, "--from-slot must not exceed --to-slot");
+
+    let programs = if programs.is_empty() {
+        vec![config.listener.program_id, config.listener.governance_program_id]
+    } else {
+        programs
+    };
+
+    let listener = SolanaEventListener::new(config.listener.clone(), rpc_client.clone(), db_pool.clone()).await?;
+    let backfiller = Backfiller::new(rpc_client, listener);
+    backfiller
+        .run(&programs, SlotRange { from: from_slot, to: to_slot })
+        .await?;
+
+    // A transactional id of its own, so a running indexer's relay is not fenced
+    let sink = sink::connect(
+        &config.sink,
+        config.kafka.to_client_config(),
+        &format!("{}-backfill", config.outbox.transactional_id),
+    )
+    .await?;
+    OutboxRelay::new(sink, db_pool.clone(), config.outbox.clone()).drain().await?;
+
+    db_pool.close().await;
+    Ok(())
+}
+
+async fn run_migrate(config: &Config, action: MigrateAction, dir: &Path) -> anyhow::Result<()> {
+    let mut client = connect_direct(&config.database.url.parse()?)
+        .await
+        .context("Failed to connect to database")?;
+    let mut runner = MigrationRunner::new(&mut client, Box::new(FileMigrationStore::new(dir)));
+
+    match action {
+        MigrateAction::Up { target_version } => {
+            let applied = runner.migrate(target_version).await?;
+            println!("Applied {} migration(s){}", applied.len(), format_versions(&applied));
+        }
+        MigrateAction::Down { target_version } => {
+            let target = match target_version {
+                Some(version) => version,
+                // One step: the newest applied migration goes, the one before it stays
+                None => {
+                    let status = runner.status().await?;
+                    let mut applied = status.iter().filter(|m| m.state != MigrationState::Pending).rev();
+                    applied.next();
+                    applied.next().map_or(0, |m| m.version)
+                }
+            };
+            let rolled_back = runner.rollback(target).await?;
+            println!("Rolled back {} migration(s){}", rolled_back.len(), format_versions(&rolled_back));
+        }
+        MigrateAction::Status => {
+            println!("{:<16} {:<10} DESCRIPTION", "VERSION", "STATE");
+            for migration in runner.status().await? {
+                println!(
+                    "{:<16} {:<10} {}",
+                    migration.version,
+                    format!("{:?}", migration.state).to_lowercase(),
+                    migration.description
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn format_versions(versions: &[i64]) -> String {
+    if versions.is_empty() {
+        return String::new();
+    }
+    let list: Vec<String> = versions.iter().map(i64::to_string).collect();
+    format!(": {}", list.join(", "))
+}
+
 async fn spawn_block_processor(
     client: RpcClient,
     db_pool: PgPool,
@@ -92,21 +316,48 @@ async fn spawn_block_processor(
     // Implementation details...
 }
 
-async fn spawn_rpc_listener(
-    client: RpcClient,
-    kafka_producer: FutureProducer,
+/// Index program events as they land, over the `solana.ingestion` path
+async fn spawn_live_ingestion(
+    config: &Config,
+    rpc_sender: &FailoverSender,
+    db_pool: PgPool,
     shutdown: Sender<()>,
 ) -> anyhow::Result<()> {
-    // WebSocket subscription logic...
+    let rpc_client = Arc::new(rpc_sender.client(CommitmentConfig::confirmed()));
+    let listener = SolanaEventListener::new(config.listener.clone(), rpc_client.clone(), db_pool).await?;
+
+    match config.solana.ingestion {
+        IngestionMode::Websocket => listener.run(shutdown).await,
+        IngestionMode::Geyser => {
+            GeyserIngester::new(listener, rpc_client, config.solana.geyser.clone())
+                .run(shutdown)
+                .await
+        }
+    }
 }
 
+/// Keep the analytics projections in step with the event topic
 async fn spawn_stream_consumer(
+    config: &Config,
     db_pool: PgPool,
     shutdown: Sender<()>,
 ) -> anyhow::Result<()> {
-    // Kafka consumer logic...
-}
+    if !matches!(config.sink, SinkConfig::Kafka) {
+        tracing::info!("Event sink is not Kafka; projections are left to downstream consumers");
+        return Ok(());
+    }
 
-fn start_health_server(port: u16) -> JoinHandle<()> {
-    // HTTP endpoint implementation...
+    let consumer = ProjectionConsumer::new(
+        config.kafka.to_client_config(),
+        config.listener.kafka_topic.clone(),
+        &config.projections,
+        db_pool,
+    )?;
+
+    if let Err(e) = consumer.run(shutdown.clone()).await {
+        tracing::error!(error = %e, "Projection consumer failed");
+        shutdown.send(()).await?;
+        return Err(e);
+    }
+    Ok(())
 }
@@ -1,18 +1,64 @@
 // local_engine/src/fl/model_updater.rs
 
+use ark_bn254::Bn254;
+use ark_groth16::{ProvingKey, VerifyingKey};
 use crate::{
-    crypto::{secure_aggregation, differential_privacy},
+    compression::{CompressionConfig, GradientCompressor},
+    contribution,
+    crypto::differential_privacy,
+    robust_aggregation::AggregatorKind,
+    secure_aggregation::{AggregationScheme, HeSecretKey, HomomorphicContext, MaskedInput, SecAggClient, SecAggServer},
     model::Model,
+    rdp_accountant::RdpAccountant,
     zk::fl_proofs,
     utils::metrics,
+    vrf,
 };
+use scorai_program::state::FederatedRoundStatus;
 use solana_client::rpc_client::RpcClient;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
 };
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::time::{interval, Duration};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::{interval, sleep, Duration};
+
+/// Federated learning parameters for an updater node
+#[derive(Debug, Clone, Deserialize)]
+pub struct FLConfig {
+    pub poll_interval: u64,
+    pub trainer_config: TrainerConfig,
+    /// Target (epsilon, delta) for the whole training run, not per round
+    pub dp_epsilon: f64,
+    pub dp_delta: f64,
+    /// Gaussian noise stddev as a multiple of the clipping norm
+    pub dp_noise_multiplier: f64,
+    /// Per-example L2 clipping norm
+    pub dp_clip_norm: f64,
+    /// Probability this node is sampled into a round's cohort
+    pub client_sampling_rate: f64,
+    pub microbatch_size: usize,
+    pub aggregation_threshold: usize,
+    /// Robust rules disable secure aggregation: they must see each update
+    #[serde(default)]
+    pub aggregator: AggregatorKind,
+    /// Top-k / 8-bit compression of submitted updates; needs a robust aggregator
+    #[serde(default)]
+    pub compression: CompressionConfig,
+    /// Hides FedAvg updates by masking, or encrypts them under the model's
+    /// decryption committee key ("paillier" | "ckks")
+    #[serde(default)]
+    pub aggregation_scheme: AggregationScheme,
+    /// Groth16 proving key for the model's training circuit
+    pub proving_key_path: PathBuf,
+}
 
 #[derive(Clone)]
 pub struct FederatedUpdater {
@@ -20,6 +66,11 @@ pub struct FederatedUpdater {
     model: Model,
     config: FLConfig,
     keypair: Arc<Keypair>,
+    accountant: RdpAccountant,
+    compressor: GradientCompressor,
+    he: Arc<HomomorphicContext>,
+    proving_key: Arc<ProvingKey<Bn254>>,
+    verifying_key: Arc<VerifyingKey<Bn254>>,
 }
 
 impl FederatedUpdater {
@@ -28,36 +79,127 @@ impl FederatedUpdater {
         initial_model: Model,
         config: FLConfig,
         keypair: Arc<Keypair>,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        config.aggregator.validate()?;
+        config.compression.validate()?;
+        anyhow::ensure!(
+            !config.compression.is_enabled() || config.aggregator.requires_individual_updates(),
+            "gradient compression needs a robust aggregator: masked updates are summed dense"
+        );
+        anyhow::ensure!(
+            !config.aggregation_scheme.is_homomorphic() || !config.aggregator.requires_individual_updates(),
+            "robust aggregators see updates in the clear; use aggregation_scheme = \"masking\""
+        );
+        let accountant = RdpAccountant::new(config.dp_epsilon, config.dp_delta)?;
+        let compressor = GradientCompressor::new(config.compression.clone());
+        let proving_key = fl_proofs::load_proving_key(&config.proving_key_path)?;
+        let verifying_key = Arc::new(proving_key.vk.clone());
+        Ok(Self {
             rpc_client,
             model: initial_model,
             config,
             keypair,
-        }
+            accountant,
+            compressor,
+            he: Arc::new(HomomorphicContext::new()),
+            proving_key: Arc::new(proving_key),
+            verifying_key,
+        })
     }
 
+    /// Follow the model's federated rounds as the registry reports them: train
+    /// while a round collects, aggregate once it closes with quorum, and adopt
+    /// the new global model when it is finalized. Expired rounds are skipped.
     pub async fn run(&mut self) -> anyhow::Result<()> {
         let mut interval = interval(Duration::from_secs(self.config.poll_interval));
+        let model_id = self.model.metadata.model_id;
+        let mut round_number = self.rpc_client.get_latest_federated_round(model_id).await?;
+        let mut joined = false;
+        let mut drawn = false;
         
         loop {
             interval.tick().await;
             
-            // 1. Get latest global model from chain
-            let global_model = self.fetch_global_model().await?;
-            
-            // 2. Local training with privacy
-            let local_update = self.train_local_model(&global_model).await?;
-            
-            // 3. Generate ZK proof of valid training
-            let proof = fl_proofs::generate_proof(&local_update)?;
-            
-            // 4. Submit update to blockchain
-            self.submit_update(local_update, proof).await?;
+            // 1. Chain state decides the phase; nothing happens until the round is opened
+            let Some(round) = self.rpc_client.get_federated_round(model_id, round_number).await? else {
+                continue;
+            };
+            let status = round.status_at(unix_now());
+            if status != round.status {
+                // Anyone may apply a passed deadline; losing the race is harmless
+                let close = scorai_program::close_federated_round(&self.keypair.pubkey(), model_id, round_number)?;
+                if let Err(e) = self.send_instruction(close).await {
+                    tracing::debug!(error = %e, round = round_number, "Federated round already closed");
+                }
+            }
             
-            // 5. Participate in aggregation when selected
-            if self.is_aggregator().await? {
-                self.perform_aggregation().await?;
+            match status {
+                FederatedRoundStatus::Collecting if !joined => {
+                    joined = true;
+                    
+                    // 2. Charge the round to the privacy budget; stop once it is spent
+                    if let Err(e) = self
+                        .accountant
+                        .record_round(self.config.client_sampling_rate, self.config.dp_noise_multiplier)
+                    {
+                        tracing::warn!(error = %e, "Stopping federated training");
+                        return Ok(());
+                    }
+                    metrics::gauge!("fl_privacy_budget_remaining", self.accountant.remaining_budget());
+                    
+                    // 3. Local training with privacy, proven
+                    let global_model = self.fetch_global_model().await?;
+                    let (local_update, proof) = self.train_proven_step(&global_model).await?;
+                    
+                    // 4. Join the round on-chain, then submit the update itself
+                    let join = scorai_program::submit_round_update(
+                        &self.keypair.pubkey(),
+                        model_id,
+                        round_number,
+                        local_update.hash()?,
+                    )?;
+                    if let Err(e) = self.send_instruction(join).await {
+                        // The collect deadline passed while training
+                        tracing::warn!(error = %e, round = round_number, "Federated round closed before this update");
+                        continue;
+                    }
+                    self.submit_update(local_update, proof).await?;
+                }
+                FederatedRoundStatus::Aggregating if !drawn => {
+                    drawn = true;
+                    
+                    // 5. Aggregate when selected; the registry only accepts it before the deadline
+                    if self.is_aggregator(round_number).await? {
+                        let (aggregate_hash, scores) = self.perform_aggregation().await?;
+                        let finalize = scorai_program::finalize_federated_round(
+                            &self.keypair.pubkey(),
+                            model_id,
+                            round_number,
+                            aggregate_hash,
+                        )?;
+                        self.send_instruction(finalize).await?;
+                        self.score_round(round_number, scores).await?;
+                    }
+                }
+                FederatedRoundStatus::Finalized => {
+                    // 6. Adopt the new global model and follow the next round
+                    self.model = self.fetch_global_model().await?;
+                    metrics::increment_counter!("fl_rounds_finalized");
+                    round_number += 1;
+                    (joined, drawn) = (false, false);
+                }
+                FederatedRoundStatus::Expired => {
+                    tracing::warn!(
+                        round = round_number,
+                        participants = round.participants,
+                        min_participants = round.min_participants,
+                        "Federated round expired without an aggregate"
+                    );
+                    metrics::increment_counter!("fl_rounds_expired");
+                    round_number += 1;
+                    (joined, drawn) = (false, false);
+                }
+                _ => {}
             }
         }
     }
@@ -68,7 +210,7 @@ impl FederatedUpdater {
         
         // Apply differential privacy
         let mut trainer = self.config.trainer_config.clone();
-        trainer.noise_scale = self.config.dp_epsilon;
+        trainer.clip_norm = self.config.dp_clip_norm;
         
         let mut update = ModelUpdate::new(base_model.version);
         
@@ -76,29 +218,115 @@ impl FederatedUpdater {
         for batch in dataset.chunks(self.config.microbatch_size) {
             let gradients = trainer.compute_gradients(batch)?;
             
-            // Add calibrated noise
+            // Add noise calibrated to the clipping norm; the accountant tracks the cost
             let private_grads = differential_privacy::add_gaussian_noise(
                 gradients,
-                self.config.dp_epsilon,
-                self.config.dp_delta,
+                self.config.dp_noise_multiplier,
+                self.config.dp_clip_norm,
             )?;
             
             update.accumulate(private_grads);
         }
         
-        // Encrypt update before sending
-        secure_aggregation::encrypt_update(
-            &update,
-            &self.rpc_client.get_aggregation_key().await?,
+        Ok(update)
+    }
+
+    /// One DP-SGD step on a sampled batch, with a proof that the update came
+    /// from committed data, clipped gradients and bounded noise
+    async fn train_proven_step(&self, base_model: &Model) -> anyhow::Result<(ModelUpdate, fl_proofs::Proof)> {
+        let dataset = self.load_local_dataset().await?;
+        let batch = dataset.sample_batch(self.config.microbatch_size)?;
+        let dimension = base_model.parameter_count();
+        let learning_rate = self.config.trainer_config.learning_rate;
+        
+        // Noise on the gradient sum, scaled like the sum itself
+        let noise = differential_privacy::add_gaussian_noise(
+            vec![0.0; dimension],
+            self.config.dp_noise_multiplier,
+            self.config.dp_clip_norm,
+        )?;
+        let scale = learning_rate / batch.len() as f32;
+        let noise: Vec<f32> = noise.iter().map(|n| n * scale).collect();
+        
+        let step = fl_proofs::TrainingStep::new(
+            &base_model.parameters(),
+            &batch.features(),
+            &batch.targets(),
+            learning_rate,
+            self.config.dp_clip_norm as f32,
+            &noise,
+            self.noise_bound(),
+        )?;
+        
+        let started = Instant::now();
+        let proof = fl_proofs::generate_proof(&self.proving_key, &step)?;
+        metrics::histogram!("fl_proof_generation_seconds", started.elapsed().as_secs_f64());
+        Ok((ModelUpdate::from_gradients(base_model.metadata.version, step.update_f32()), proof))
+    }
+
+    fn noise_bound(&self) -> f32 {
+        fl_proofs::noise_bound(
+            self.config.dp_noise_multiplier,
+            self.config.dp_clip_norm,
+            self.config.trainer_config.learning_rate as f64,
+            self.config.microbatch_size,
+            self.model.parameter_count(),
+        )
+    }
+
+    /// What every training proof this round must attest to
+    fn round_statement(&self) -> anyhow::Result<fl_proofs::RoundStatement> {
+        fl_proofs::RoundStatement::new(
+            &self.model.parameters(),
+            self.config.trainer_config.learning_rate,
+            self.config.dp_clip_norm as f32,
+            self.noise_bound(),
         )
     }
 
-    async fn submit_update(&self, update: ModelUpdate, proof: fl_proofs::Proof) -> anyhow::Result<()> {
+    async fn submit_update(&mut self, update: ModelUpdate, proof: fl_proofs::Proof) -> anyhow::Result<()> {
+        let model_id = self.model.metadata.model_id;
+
+        if self.config.aggregator.requires_individual_updates() {
+            // Robust aggregation inspects each (DP-noised) update in the clear;
+            // what compression drops is carried into the next round's update
+            let compressed = self.compressor.compress(&update.gradients);
+            metrics::histogram!("fl_update_compression_ratio", compressed.ratio());
+            metrics::counter!("fl_update_bytes", compressed.encoded_len() as u64);
+            let instruction = scorai_program::submit_plain_update(
+                &self.keypair.pubkey(),
+                compressed,
+                proof.into(),
+                model_id,
+            )?;
+            self.send_instruction(instruction).await?;
+            metrics::increment_counter!("fl_updates_submitted");
+            return Ok(());
+        }
+
+        if self.config.aggregation_scheme.is_homomorphic() {
+            // One round: the aggregator can add ciphertexts but only the committee decrypts
+            let pk = self.rpc_client.get_he_public_key(model_id).await?;
+            let encrypted = self.he.encrypt(&pk, &update.gradients)?;
+            let instruction = scorai_program::submit_encrypted_update(
+                &self.keypair.pubkey(),
+                encrypted,
+                proof.into(),
+                model_id,
+            )?;
+            self.send_instruction(instruction).await?;
+            metrics::increment_counter!("fl_updates_submitted");
+            return Ok(());
+        }
+
+        // Rounds 0-2 of secure aggregation: only the masked update leaves this node
+        let (session, share_senders, masked) = self.mask_update(&update).await?;
+
         let instruction = scorai_program::submit_update(
             &self.keypair.pubkey(),
-            update.encrypted_data,
+            masked.values,
             proof.into(),
-            self.model.metadata.model_id,
+            model_id,
         )?;
         
         let mut tx = Transaction::new_with_payer(
@@ -114,27 +342,55 @@ impl FederatedUpdater {
             .await?;
         
         metrics::increment_counter!("fl_updates_submitted");
+
+        // Round 3: help unmask the cohort that survived round 2
+        let survivors = self.rpc_client.wait_for_survivors(model_id).await?;
+        let response = session.unmask(&share_senders, &survivors)?;
+        self.rpc_client.post_unmask_response(model_id, response).await?;
+
         Ok(())
     }
 
-    async fn perform_aggregation(&self) -> anyhow::Result<()> {
-        // 1. Collect encrypted updates from chain
-        let updates = self.rpc_client
-            .get_pending_updates(self.model.metadata.model_id)
+    /// Run key advertisement, share exchange and masking for a local update
+    async fn mask_update(
+        &self,
+        update: &ModelUpdate,
+    ) -> anyhow::Result<(SecAggClient, Vec<u32>, MaskedInput)> {
+        let model_id = self.model.metadata.model_id;
+        let client_id = self.rpc_client
+            .get_cohort_index(model_id, &self.keypair.pubkey())
             .await?;
-        
-        // 2. Threshold decryption
-        let decrypted = secure_aggregation::threshold_decrypt(
-            updates,
-            &self.keypair,
-            self.config.aggregation_threshold,
-        )?;
-        
-        // 3. Validate proofs
-        let valid_updates = fl_proofs::validate_updates(decrypted)?;
-        
-        // 4. Robust aggregation
-        let aggregated = self.config.aggregator.aggregate(valid_updates)?;
+        let mut session = SecAggClient::new(client_id, self.config.aggregation_threshold);
+
+        // Round 0: advertise keys and wait for the cohort roster
+        self.rpc_client.advertise_keys(model_id, session.advertise()).await?;
+        let roster = self.rpc_client.wait_for_roster(model_id).await?;
+
+        // Round 1: exchange encrypted Shamir shares
+        let shares = session.share_keys(&roster)?;
+        self.rpc_client.post_key_shares(model_id, shares).await?;
+        let inbox = self.rpc_client.get_key_shares(model_id, client_id).await?;
+        session.receive_shares(&inbox)?;
+
+        // Round 2: mask against everyone who completed round 1
+        let share_senders = self.rpc_client.get_share_senders(model_id).await?;
+        let masked = session.mask_input(&update.gradients, &share_senders)?;
+
+        Ok((session, share_senders, masked))
+    }
+
+    /// Aggregate the round and publish the next global version; returns its
+    /// hash and, when updates were seen individually, each contributor's score
+    async fn perform_aggregation(&self) -> anyhow::Result<([u8; 32], Option<HashMap<Pubkey, u64>>)> {
+        // 1-4. Collect, validate and aggregate the round's updates
+        let (aggregated, scores) = if self.config.aggregator.requires_individual_updates() {
+            let (aggregated, scores) = self.robust_aggregate().await?;
+            (aggregated, Some(scores))
+        } else if self.config.aggregation_scheme.is_homomorphic() {
+            (self.homomorphic_aggregate().await?, None)
+        } else {
+            (self.secure_aggregate().await?, None)
+        };
         
         // 5. Update global model
         let mut new_model = self.model.clone();
@@ -142,10 +398,11 @@ impl FederatedUpdater {
         new_model.metadata.version += 1;
         
         // 6. Submit to blockchain
+        let model_hash = new_model.hash()?;
         let instruction = scorai_program::update_global_model(
             &self.keypair.pubkey(),
             new_model.metadata.clone(),
-            new_model.hash()?,
+            model_hash,
         )?;
         
         let mut tx = Transaction::new_with_payer(
@@ -161,6 +418,196 @@ impl FederatedUpdater {
             .await?;
         
         metrics::increment_counter!("fl_aggregations_performed");
+        Ok((model_hash, scores))
+    }
+
+    /// FedAvg over the secure aggregation sum
+    async fn secure_aggregate(&self) -> anyhow::Result<ModelUpdate> {
+        // 1. Collect the round's key material and masked updates from chain
+        let round = self.rpc_client
+            .get_pending_updates(self.model.metadata.model_id)
+            .await?;
+        
+        // 2. Validate proofs; updates with invalid proofs count as dropped
+        let statement = self.round_statement()?;
+        let valid_updates = fl_proofs::retain_valid(&self.verifying_key, &statement, round.masked_updates, |u| &u.proof);
+        
+        // 3. Replay rounds 0-2 into the aggregation server
+        let mut server = SecAggServer::new(
+            self.config.aggregation_threshold,
+            self.model.parameter_count(),
+        );
+        for keys in round.roster {
+            server.register(keys)?;
+        }
+        server.route_shares(round.key_shares)?;
+        for update in valid_updates {
+            server.submit_masked(update.input)?;
+        }
+        
+        // 4. Strip masks; only the cohort sum is ever revealed
+        let result = server.unmask(&round.unmask_responses)?;
+        if !result.dropped.is_empty() {
+            tracing::warn!(dropped = ?result.dropped, "Recovered masks of dropped clients");
+        }
+        Ok(ModelUpdate::from_gradients(self.model.metadata.version, result.mean()))
+    }
+
+    /// FedAvg over homomorphically summed updates; the decryption committee
+    /// reveals only the sum
+    async fn homomorphic_aggregate(&self) -> anyhow::Result<ModelUpdate> {
+        let model_id = self.model.metadata.model_id;
+        let scheme = self.config.aggregation_scheme.as_str();
+        let pk = self.rpc_client.get_he_public_key(model_id).await?;
+        let round = self.rpc_client.get_pending_encrypted_updates(model_id).await?;
+        
+        // 1. Updates with invalid proofs or foreign ciphertexts are dropped
+        let started = Instant::now();
+        let statement = self.round_statement()?;
+        let mut sum = None;
+        let mut count = 0;
+        for update in fl_proofs::retain_valid(&self.verifying_key, &statement, round.updates, |u| &u.proof) {
+            let added = match &mut sum {
+                None => {
+                    sum = Some(update.ciphertexts);
+                    Ok(())
+                }
+                Some(sum) => self.he.add_assign(&pk, sum, &update.ciphertexts),
+            };
+            match added {
+                Ok(()) => count += 1,
+                Err(e) => tracing::warn!(contributor = %update.contributor, error = %e, "Dropping encrypted update"),
+            }
+        }
+        let Some(sum) = sum else {
+            anyhow::bail!("no valid encrypted updates to aggregate");
+        };
+        metrics::histogram!("fl_he_aggregation_seconds", started.elapsed().as_secs_f64(), "scheme" => scheme);
+        
+        // 2. The committee decrypts the sum alone
+        self.rpc_client.post_encrypted_aggregate(model_id, &sum, count).await?;
+        let total = self.rpc_client.wait_for_decrypted_aggregate(model_id).await?;
+        let mean = total.iter().map(|v| (v / count as f64) as f32).collect();
+        Ok(ModelUpdate::from_gradients(self.model.metadata.version, mean))
+    }
+
+    /// Byzantine-robust aggregation over individually submitted updates, with
+    /// each contributor's holdout score; rejected updates score zero
+    async fn robust_aggregate(&self) -> anyhow::Result<(ModelUpdate, HashMap<Pubkey, u64>)> {
+        // 1. Collect plain updates from chain
+        let round = self.rpc_client
+            .get_pending_plain_updates(self.model.metadata.model_id)
+            .await?;
+        
+        // 2. Validate proofs
+        let statement = self.round_statement()?;
+        let valid_updates = fl_proofs::retain_valid(&self.verifying_key, &statement, round.updates, |u| &u.proof);
+        let mut contributors = Vec::with_capacity(valid_updates.len());
+        let mut gradients = Vec::with_capacity(valid_updates.len());
+        for u in valid_updates {
+            // Undecodable payloads are dropped like invalid proofs
+            let dense = match u.update.decompress() {
+                Ok(dense) => dense,
+                Err(e) => {
+                    tracing::warn!(contributor = %u.contributor, error = %e, "Malformed compressed update");
+                    continue;
+                }
+            };
+            // Uncompressed updates must be exactly the proven one
+            if !self.config.compression.is_enabled()
+                && fl_proofs::update_commitment(&dense).ok() != Some(u.proof.statement.update_commitment)
+            {
+                tracing::warn!(contributor = %u.contributor, "Update differs from its proven update");
+                continue;
+            }
+            contributors.push(u.contributor);
+            gradients.push(dense);
+        }
+        
+        // 3. FLTrust scores updates against one trained on the aggregator's root dataset
+        let root = if self.config.aggregator.requires_root_update() {
+            Some(self.train_local_model(&self.model).await?.gradients)
+        } else {
+            None
+        };
+        
+        // 4. Filter poisoned updates and aggregate the rest
+        let outcome = self.config.aggregator.aggregate(&gradients, root.as_deref())?;
+        for &i in &outcome.rejected {
+            tracing::warn!(contributor = %contributors[i], "Update rejected by robust aggregator");
+        }
+        metrics::counter!("fl_updates_rejected", outcome.rejected.len() as u64);
+        
+        // 5. Score accepted updates by how much they improve holdout loss
+        let holdout = self.load_holdout_dataset().await?;
+        let accepted: Vec<Vec<f32>> = outcome.accepted.iter().map(|&i| gradients[i].clone()).collect();
+        let accepted_scores = contribution::leave_one_out(&accepted, |update| {
+            let mut candidate = self.model.clone();
+            candidate.apply_update(ModelUpdate::from_gradients(self.model.metadata.version, update.to_vec()))?;
+            candidate.evaluate_loss(&holdout)
+        })?;
+        let mut scores: HashMap<Pubkey, u64> = contributors.iter().map(|&c| (c, 0)).collect();
+        for (&i, score) in outcome.accepted.iter().zip(accepted_scores) {
+            scores.insert(contributors[i], score);
+        }
+        
+        Ok((ModelUpdate::from_gradients(self.model.metadata.version, outcome.update), scores))
+    }
+
+    /// Decryption committee duty for homomorphic rounds: decrypt each posted
+    /// aggregate sum under `key` and publish it. Committee members should not
+    /// also aggregate, or they could decrypt individual updates.
+    pub async fn run_decryptor(&self, key: HeSecretKey) -> anyhow::Result<()> {
+        let mut interval = interval(Duration::from_secs(self.config.poll_interval));
+        let model_id = self.model.metadata.model_id;
+        let dimension = self.model.parameter_count();
+        
+        loop {
+            interval.tick().await;
+            let Some(aggregate) = self.rpc_client.get_encrypted_aggregate(model_id).await? else {
+                continue;
+            };
+            let total = self.he.decrypt_sum(&key, &aggregate.sum, dimension, aggregate.count)?;
+            self.rpc_client.post_decrypted_aggregate(model_id, aggregate.round, total).await?;
+            metrics::increment_counter!("fl_he_aggregates_decrypted");
+        }
+    }
+
+    /// Commit a score for every participant of a finalized round, then credit
+    /// their reward shares. Masked updates cannot be told apart, so without
+    /// individual scores every participant scores the same.
+    async fn score_round(&self, round_number: u64, scores: Option<HashMap<Pubkey, u64>>) -> anyhow::Result<()> {
+        let model_id = self.model.metadata.model_id;
+        let node = self.keypair.pubkey();
+        let participants = self.rpc_client.get_round_participants(model_id, round_number).await?;
+        
+        for participant in &participants {
+            let score = scores.as_ref().map_or(1, |scores| scores.get(participant).copied().unwrap_or(0));
+            let instruction = scorai_program::score_contribution(&node, model_id, round_number, participant, score)?;
+            self.send_instruction(instruction).await?;
+        }
+        // Credits are permissionless but need every score in first
+        for participant in &participants {
+            let instruction = scorai_program::credit_contribution(&node, model_id, round_number, participant)?;
+            self.send_instruction(instruction).await?;
+        }
+        
+        metrics::counter!("fl_contributions_scored", participants.len() as u64);
+        Ok(())
+    }
+
+    async fn send_instruction(&self, instruction: Instruction) -> anyhow::Result<()> {
+        let mut tx = Transaction::new_with_payer(
+            &[instruction],
+            Some(&self.keypair.pubkey()),
+        );
+        
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+        tx.sign(&[&self.keypair], recent_blockhash);
+        
+        self.rpc_client
+            .send_and_confirm_transaction(&tx)
+            .await?;
         Ok(())
     }
 
@@ -169,29 +616,83 @@ impl FederatedUpdater {
         // Implementation with local caching
     }
 
+    /// Contributed data to train on, minus contributions revoked on-chain.
+    /// Revocations are re-read on every load, so an erasure applies from the next round.
     async fn load_local_dataset(&self) -> anyhow::Result<Dataset> {
+        let mut dataset = self.load_contributed_dataset().await?;
+        let revoked: HashSet<[u8; 32]> = self
+            .rpc_client
+            .get_contribution_revocations(self.model.metadata.model_id)
+            .await?
+            .into_iter()
+            .map(|revocation| revocation.data_hash)
+            .collect();
+        let excluded = dataset.retain_contributions(|data_hash| !revoked.contains(data_hash));
+        if excluded > 0 {
+            tracing::info!(excluded, "Excluded revoked contributions from training");
+            metrics::counter!("fl_revoked_records_excluded", excluded as u64);
+        }
+        Ok(dataset)
+    }
+
+    async fn load_contributed_dataset(&self) -> anyhow::Result<Dataset> {
         // Implementation with access controls
     }
 
-    async fn is_aggregator(&self) -> anyhow::Result<bool> {
-        // Check aggregator selection protocol
+    async fn load_holdout_dataset(&self) -> anyhow::Result<Dataset> {
+        // Aggregator's validation split, never trained on
     }
-}
 
-// Core cryptographic implementation
-mod secure_aggregation {
-    use paillier::EncryptionKey;
-    
-    pub fn encrypt_update(update: &ModelUpdate, key: &EncryptionKey) -> anyhow::Result<Vec<u8>> {
-        // Threshold Paillier implementation
+    /// Stake-weighted VRF lottery run by the registry for federated round
+    /// `round_number`: commit a proof over the round's seed, reveal it once
+    /// commits close, and read the winners after the reveal deadline
+    async fn is_aggregator(&self, round_number: u64) -> anyhow::Result<bool> {
+        let model_id = self.model.metadata.model_id;
+        let node = self.keypair.pubkey();
+        let round = self.rpc_client.get_aggregation_round(model_id, round_number).await?;
+        if unix_now() >= round.commit_deadline {
+            tracing::debug!(round = round_number, "Aggregator commits closed; sitting this round out");
+            return Ok(false);
+        }
+
+        // 1. The proof is unique to this key and the round's seed
+        let alpha = [model_id.as_ref(), &round_number.to_le_bytes(), &round.seed].concat();
+        let (proof, _) = vrf::prove(self.keypair.secret().as_bytes(), &alpha);
+        let commitment: [u8; 32] = Sha256::digest(proof).into();
+        let commit = scorai_program::commit_vrf(&node, model_id, round_number, commitment)?;
+        if let Err(e) = self.send_instruction(commit).await {
+            // Unstaked nodes train but never aggregate
+            tracing::warn!(error = %e, round = round_number, "Failed to enter the aggregator draw");
+            return Ok(false);
+        }
+
+        // 2. Reveal after the commit phase; chain time may trail the local clock slightly
+        sleep_until(round.commit_deadline + CLOCK_SLACK_SECS).await;
+        let reveal = scorai_program::reveal_vrf(&node, model_id, round_number, proof.to_vec())?;
+        self.send_instruction(reveal).await?;
+
+        // 3. Later reveals can displace this draw until the deadline
+        sleep_until(round.reveal_deadline + CLOCK_SLACK_SECS).await;
+        let round = self.rpc_client.get_aggregation_round(model_id, round_number).await?;
+        let selected = round.selected.iter().any(|draw| draw.node == node);
+        if selected {
+            metrics::increment_counter!("fl_aggregator_selected");
+        }
+        Ok(selected)
     }
-    
-    pub fn threshold_decrypt(
-        updates: Vec<EncryptedUpdate>,
-        key: &Keypair,
-        threshold: usize
-    ) -> anyhow::Result<Vec<ModelUpdate>> {
-        // TSS decryption protocol
+}
+
+/// Wait past on-chain deadlines by this much
+const CLOCK_SLACK_SECS: i64 = 5;
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+async fn sleep_until(deadline: i64) {
+    let remaining = deadline - unix_now();
+    if remaining > 0 {
+        sleep(Duration::from_secs(remaining as u64)).await;
     }
 }
 
@@ -201,11 +702,10 @@ mod differential_privacy {
     
     pub fn add_gaussian_noise(
         gradients: Gradients,
-        epsilon: f64,
-        delta: f64
+        noise_multiplier: f64,
+        clip_norm: f64
     ) -> anyhow::Result<Gradients> {
-        let sensitivity = calculate_sensitivity(&gradients);
-        let sigma = (sensitivity * (2.0 * (1.25 / delta).ln()).sqrt()) / epsilon;
+        let sigma = noise_multiplier * clip_norm;
         
         gradients.iter_mut()
             .for_each(|g| *g += gaussian(0.0, sigma));
@@ -213,20 +713,3 @@ mod differential_privacy {
         Ok(gradients)
     }
 }
-
-// Zero-knowledge proofs
-mod fl_proofs {
-    use ark_circom::{CircomBuilder, WitnessCalculator};
-    
-    pub struct Proof {
-        // ZK proof components
-    }
-    
-    pub fn generate_proof(update: &ModelUpdate) -> anyhow::Result<Proof> {
-        // Implementation using Circom circuits
-    }
-    
-    pub fn validate_updates(updates: Vec<ModelUpdate>) -> anyhow::Result<Vec<ModelUpdate>> {
-        // Batch proof verification
-    }
-}
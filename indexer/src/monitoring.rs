@@ -0,0 +1,78 @@
+// indexer/src/monitoring.rs
+
+use crate::api;
+use axum::{routing::get, Router};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use sqlx::PgPool;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::{
+    task::JoinHandle,
+    time::{interval, Duration},
+};
+use tracing::{error, warn};
+
+const COLLECT_INTERVAL: Duration = Duration::from_secs(15);
+const KAFKA_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0, 30.0];
+
+/// Install the global Prometheus recorder; must run before any metric is emitted
+pub fn install_recorder() -> anyhow::Result<PrometheusHandle> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Full("kafka_produce_seconds".into()), KAFKA_BUCKETS)?
+        .install_recorder()?;
+
+    metrics::describe_gauge!("event_lag_slots", "Cluster slot minus the newest indexed event slot");
+    metrics::describe_gauge!("db_pool_connections", "Open Postgres connections");
+    metrics::describe_gauge!("db_pool_idle_connections", "Idle Postgres connections");
+    metrics::describe_histogram!("kafka_produce_seconds", "Time to acknowledge a produced event");
+    metrics::describe_counter!("ws_reconnects_total", "WebSocket subscription reconnect attempts");
+    Ok(handle)
+}
+
+/// `/health`, `/metrics` and the read API on the health check port
+pub fn serve(port: u16, handle: PrometheusHandle, db_pool: PgPool) -> JoinHandle<()> {
+    let app = Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .route("/metrics", get(move || std::future::ready(handle.render())))
+        .merge(api::routes(db_pool));
+
+    tokio::spawn(async move {
+        let addr = SocketAddr::from(([0, 0, 0, 0], port));
+        let result = match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => axum::serve(listener, app).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            error!(error = %e, port, "Health server stopped");
+        }
+    })
+}
+
+/// Sample gauges that no code path emits on its own
+pub fn spawn_collectors(db_pool: PgPool, rpc_client: Arc<RpcClient>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = interval(COLLECT_INTERVAL);
+        loop {
+            ticker.tick().await;
+
+            metrics::gauge!("db_pool_connections", db_pool.size() as f64);
+            metrics::gauge!("db_pool_idle_connections", db_pool.num_idle() as f64);
+
+            if let Err(e) = record_event_lag(&db_pool, &rpc_client).await {
+                warn!(error = %e, "Event lag sample failed");
+            }
+        }
+    })
+}
+
+async fn record_event_lag(db_pool: &PgPool, rpc_client: &RpcClient) -> anyhow::Result<()> {
+    let cluster_slot = rpc_client.get_slot_with_commitment(CommitmentConfig::confirmed()).await?;
+    let indexed_slot = sqlx::query_scalar!(r#"SELECT MAX(slot) FROM events"#)
+        .fetch_one(db_pool)
+        .await?
+        .unwrap_or(0);
+
+    metrics::gauge!("event_lag_slots", cluster_slot.saturating_sub(indexed_slot as u64) as f64);
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// indexer/src/outbox.rs
+
+use crate::sink::{EventSink, SinkMessage};
+use serde::Deserialize;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use tokio::{
+    sync::mpsc,
+    time::{interval, Duration, Instant},
+};
+use tracing::{info, instrument};
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutboxConfig {
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+    /// Kafka `transactional.id`; a second relay with the same id fences the first.
+    /// Unused by other sinks
+    #[serde(default = "default_transactional_id")]
+    pub transactional_id: String,
+    /// Published rows are kept this long for inspection
+    #[serde(default = "default_retention_hours")]
+    pub retention_hours: i32,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: default_poll_interval_ms(),
+            batch_size: default_batch_size(),
+            transactional_id: default_transactional_id(),
+            retention_hours: default_retention_hours(),
+        }
+    }
+}
+
+fn default_poll_interval_ms() -> u64 {
+    200
+}
+
+fn default_batch_size() -> i64 {
+    500
+}
+
+fn default_transactional_id() -> String {
+    "scoria-indexer-outbox".to_string()
+}
+
+fn default_retention_hours() -> i32 {
+    24
+}
+
+/// Queue a message in the caller's transaction; it is published only if that commits.
+/// `payload` is `None` for retractions.
+pub async fn enqueue(
+    tx: &mut PgConnection,
+    topic: &str,
+    key: &str,
+    payload: Option<&[u8]>,
+    headers: &HashMap<String, String>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO event_outbox (topic, message_key, payload, headers)
+           VALUES ($1, $2, $3, $4)"#,
+        topic,
+        key,
+        payload,
+        serde_json::to_value(headers)?
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+/// Publishes `event_outbox` rows to the event sink in commit order.
+///
+/// A batch is published before the Postgres transaction marking it published
+/// commits, so a crash between the two can only republish that batch. Sinks
+/// carry the event's idempotency key so consumers drop the repeat, which makes
+/// delivery exactly-once.
+pub struct OutboxRelay {
+    db_pool: PgPool,
+    sink: Box<dyn EventSink>,
+    config: OutboxConfig,
+}
+
+impl OutboxRelay {
+    pub fn new(sink: Box<dyn EventSink>, db_pool: PgPool, config: OutboxConfig) -> Self {
+        Self { db_pool, sink, config }
+    }
+
+    #[instrument(skip_all, fields(sink = self.sink.name()))]
+    pub async fn run(&self, shutdown: mpsc::Sender<()>) -> anyhow::Result<()> {
+        let mut ticker = interval(Duration::from_millis(self.config.poll_interval_ms.max(10)));
+        let mut last_prune = Instant::now();
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.closed() => break,
+            }
+
+            // Full batches mean a backlog; keep going until it is cleared
+            while self.publish_batch().await? == self.config.batch_size as usize {}
+
+            if last_prune.elapsed() >= PRUNE_INTERVAL {
+                self.prune().await?;
+                last_prune = Instant::now();
+            }
+        }
+
+        // Everything committed before shutdown still goes out
+        self.drain().await
+    }
+
+    /// Publish until the outbox is empty
+    pub async fn drain(&self) -> anyhow::Result<()> {
+        let mut published = 0;
+        loop {
+            match self.publish_batch().await? {
+                0 => break,
+                n => published += n,
+            }
+        }
+        info!(published, "Outbox drained");
+        Ok(())
+    }
+
+    /// Publish the oldest unpublished rows; returns how many
+    async fn publish_batch(&self) -> anyhow::Result<usize> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let rows = sqlx::query!(
+            r#"SELECT id, topic, message_key, payload, headers
+               FROM event_outbox
+               WHERE published_at IS NULL
+               ORDER BY id
+               LIMIT $1
+               FOR UPDATE SKIP LOCKED"#,
+            self.config.batch_size
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
+        let messages = rows
+            .into_iter()
+            .map(|row| {
+                Ok(SinkMessage {
+                    topic: row.topic,
+                    key: row.message_key,
+                    payload: row.payload,
+                    headers: serde_json::from_value(row.headers)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let started = Instant::now();
+        self.sink.publish(&messages).await?;
+
+        sqlx::query!("UPDATE event_outbox SET published_at = NOW() WHERE id = ANY($1)", &ids)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        metrics::histogram!("outbox_publish_seconds", started.elapsed().as_secs_f64(), "sink" => self.sink.name());
+        metrics::counter!("outbox_published_total", ids.len() as u64, "sink" => self.sink.name());
+        Ok(ids.len())
+    }
+
+    async fn prune(&self) -> anyhow::Result<()> {
+        let pruned = sqlx::query!(
+            "DELETE FROM event_outbox WHERE published_at < NOW() - make_interval(hours => $1)",
+            self.config.retention_hours
+        )
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+
+        info!(pruned, "Pruned published outbox rows");
+        Ok(())
+    }
+}
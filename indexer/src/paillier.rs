@@ -0,0 +1,161 @@
+// indexer/src/paillier.rs
+
+//! Packed Paillier for homomorphic FL aggregation. Values are fixed point,
+//! offset to be non-negative and packed `SLOT_BITS` apart into one plaintext,
+//! so a single ciphertext product adds every slot at once. Each slot has room
+//! for 2^(SLOT_BITS - OFFSET_BITS - 1) summed updates before carrying.
+
+use anyhow::{anyhow, ensure};
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+
+/// Modulus size for production keys
+pub const KEY_BITS: u64 = 2048;
+/// Same fixed point as masked secure aggregation
+const FIXED_POINT_SCALE: f64 = (1u64 << 24) as f64;
+const SLOT_BITS: usize = 64;
+/// Offset keeping slot values non-negative; |value| < 2^(OFFSET_BITS - 24)
+const OFFSET_BITS: u32 = 40;
+const MILLER_RABIN_ROUNDS: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicKey {
+    n: BigUint,
+    n_squared: BigUint,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SecretKey {
+    public: PublicKey,
+    lambda: BigUint,
+    mu: BigUint,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ciphertext(BigUint);
+
+impl SecretKey {
+    /// Fresh key with a `bits`-bit modulus
+    pub fn generate(bits: u64) -> Self {
+        let (p, q) = loop {
+            let (p, q) = (random_prime(bits / 2), random_prime(bits / 2));
+            if p != q {
+                break (p, q);
+            }
+        };
+        let n = &p * &q;
+        let lambda = (&p - 1u32).lcm(&(&q - 1u32));
+        // With g = n + 1, L(g^lambda mod n^2) = lambda mod n
+        let mu = mod_inverse(&(&lambda % &n), &n).expect("lambda is invertible mod n");
+        Self {
+            public: PublicKey { n_squared: &n * &n, n },
+            lambda,
+            mu,
+        }
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// First `len` values of a sum of `count` encrypted vectors
+    pub fn decrypt_sum(&self, ciphertexts: &[Ciphertext], len: usize, count: usize) -> anyhow::Result<Vec<f64>> {
+        let slots = self.public.slots();
+        ensure!(len <= ciphertexts.len() * slots, "{} values do not fit {} ciphertexts", len, ciphertexts.len());
+        let offset = count as u128 * (1u128 << OFFSET_BITS);
+        let mut values = Vec::with_capacity(len);
+        for ct in ciphertexts {
+            let u = ct.0.modpow(&self.lambda, &self.public.n_squared);
+            let packed = ((u - 1u32) / &self.public.n) * &self.mu % &self.public.n;
+            let digits = packed.to_u64_digits();
+            for slot in 0..slots {
+                // SLOT_BITS is one u64 digit
+                let sum = digits.get(slot).copied().unwrap_or(0) as u128;
+                values.push((sum as i128 - offset as i128) as f64 / FIXED_POINT_SCALE);
+            }
+        }
+        values.truncate(len);
+        Ok(values)
+    }
+}
+
+impl PublicKey {
+    /// Values packed per ciphertext
+    pub fn slots(&self) -> usize {
+        (self.n.bits() as usize - 1) / SLOT_BITS
+    }
+
+    pub fn encrypt(&self, values: &[f32]) -> anyhow::Result<Vec<Ciphertext>> {
+        let limit = (1u64 << (OFFSET_BITS - 1)) as f64;
+        values
+            .chunks(self.slots())
+            .map(|chunk| {
+                let mut digits = Vec::with_capacity(chunk.len());
+                for &v in chunk {
+                    let fixed = (v as f64 * FIXED_POINT_SCALE).round();
+                    ensure!(fixed.is_finite() && fixed.abs() < limit, "value {} out of Paillier range", v);
+                    digits.push((fixed as i64 + (1i64 << OFFSET_BITS)) as u64);
+                }
+                let m = BigUint::new(digits.iter().flat_map(|d| [*d as u32, (*d >> 32) as u32]).collect());
+                // c = (1 + m·n) · r^n mod n^2
+                let r = OsRng.gen_biguint_range(&BigUint::one(), &self.n);
+                let gm = (BigUint::one() + m * &self.n) % &self.n_squared;
+                Ok(Ciphertext(gm * r.modpow(&self.n, &self.n_squared) % &self.n_squared))
+            })
+            .collect()
+    }
+
+    /// `sum += other`, ciphertext by ciphertext
+    pub fn add_assign(&self, sum: &mut [Ciphertext], other: &[Ciphertext]) -> anyhow::Result<()> {
+        ensure!(sum.len() == other.len(), "ciphertext counts differ: {} vs {}", sum.len(), other.len());
+        for (acc, ct) in sum.iter_mut().zip(other) {
+            ensure!(ct.0 < self.n_squared, "malformed ciphertext");
+            acc.0 = &acc.0 * &ct.0 % &self.n_squared;
+        }
+        Ok(())
+    }
+}
+
+fn random_prime(bits: u64) -> BigUint {
+    loop {
+        let mut candidate = OsRng.gen_biguint(bits);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(0, true);
+        if is_probable_prime(&candidate) {
+            return candidate;
+        }
+    }
+}
+
+fn is_probable_prime(n: &BigUint) -> bool {
+    const SMALL_PRIMES: [u32; 15] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+    if SMALL_PRIMES.iter().any(|&p| (n % p).is_zero()) {
+        return SMALL_PRIMES.iter().any(|&p| *n == BigUint::from(p));
+    }
+    let one = BigUint::one();
+    let n_minus_one = n - 1u32;
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+    'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+        let a = OsRng.gen_biguint_range(&BigUint::from(2u32), &n_minus_one);
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = &x * &x % n;
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn mod_inverse(a: &BigUint, n: &BigUint) -> anyhow::Result<BigUint> {
+    a.modinv(n).ok_or_else(|| anyhow!("no inverse mod n"))
+}
@@ -0,0 +1,431 @@
+// indexer/src/projections.rs
+
+use crate::{
+    solana_listener::{
+        DataContribution, InferenceChallenge, InferenceFinalization, InferenceFulfillment,
+        ProgramEvent, ProgramEventType, ProposalCreation, Vote,
+    },
+    telemetry,
+};
+use anyhow::Context;
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{Consumer, StreamConsumer},
+    message::{BorrowedMessage, Headers, Message},
+    Offset, TopicPartitionList,
+};
+use serde::Deserialize;
+use sqlx::{PgConnection, PgPool};
+use tokio::{sync::mpsc, time::Duration};
+use tracing::{info, instrument, warn};
+
+const METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectionConfig {
+    #[serde(default = "default_consumer_group")]
+    pub consumer_group: String,
+}
+
+impl Default for ProjectionConfig {
+    fn default() -> Self {
+        Self { consumer_group: default_consumer_group() }
+    }
+}
+
+fn default_consumer_group() -> String {
+    "scoria-projections".to_string()
+}
+
+/// Maintains the analytics tables from the indexer's own event topic.
+///
+/// Offsets live in `projection_offsets` and are advanced in the same database
+/// transaction that applies the message, so a crash either keeps both or
+/// neither; Kafka's committed offsets are never consulted.
+pub struct ProjectionConsumer {
+    consumer: StreamConsumer,
+    topic: String,
+    group: String,
+    db_pool: PgPool,
+}
+
+impl ProjectionConsumer {
+    pub fn new(
+        mut client_config: ClientConfig,
+        topic: String,
+        config: &ProjectionConfig,
+        db_pool: PgPool,
+    ) -> anyhow::Result<Self> {
+        let consumer: StreamConsumer = client_config
+            .set("group.id", &config.consumer_group)
+            .set("enable.auto.commit", "false")
+            .set("enable.auto.offset.store", "false")
+            .set("isolation.level", "read_committed")
+            .create()?;
+
+        Ok(Self {
+            consumer,
+            topic,
+            group: config.consumer_group.clone(),
+            db_pool,
+        })
+    }
+
+    #[instrument(skip_all, fields(group = %self.group))]
+    pub async fn run(&self, shutdown: mpsc::Sender<()>) -> anyhow::Result<()> {
+        self.assign().await?;
+
+        loop {
+            let message = tokio::select! {
+                message = self.consumer.recv() => message?,
+                _ = shutdown.closed() => break,
+            };
+            self.process(&message).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Resume every partition from the offset recorded alongside the projections
+    async fn assign(&self) -> anyhow::Result<()> {
+        let metadata = self.consumer.fetch_metadata(Some(&self.topic), METADATA_TIMEOUT)?;
+        let topic = metadata
+            .topics()
+            .iter()
+            .find(|t| t.name() == self.topic)
+            .context("Projection topic not found")?;
+
+        let stored: Vec<(i32, i64)> = sqlx::query!(
+            r#"SELECT partition, next_offset FROM projection_offsets
+               WHERE consumer_group = $1 AND topic = $2"#,
+            self.group,
+            self.topic
+        )
+        .fetch_all(&self.db_pool)
+        .await?
+        .into_iter()
+        .map(|row| (row.partition, row.next_offset))
+        .collect();
+
+        let mut assignment = TopicPartitionList::new();
+        for partition in topic.partitions() {
+            let offset = stored
+                .iter()
+                .find(|(p, _)| *p == partition.id())
+                .map(|(_, next)| Offset::Offset(*next))
+                .unwrap_or(Offset::Beginning);
+            assignment.add_partition_offset(&self.topic, partition.id(), offset)?;
+        }
+        self.consumer.assign(&assignment)?;
+
+        info!(partitions = topic.partitions().len(), "Projection consumer assigned");
+        Ok(())
+    }
+
+    /// Apply one message and advance its partition offset atomically
+    #[instrument(
+        name = "projections.process",
+        skip_all,
+        fields(partition = message.partition(), offset = message.offset())
+    )]
+    async fn process(&self, message: &BorrowedMessage<'_>) -> anyhow::Result<()> {
+        telemetry::link_to_producer(message.headers());
+        let mut tx = self.db_pool.begin().await?;
+
+        // 1. Claim the offset; a redelivery below the stored position is skipped
+        let claimed = sqlx::query!(
+            r#"INSERT INTO projection_offsets (consumer_group, topic, partition, next_offset, updated_at)
+               VALUES ($1, $2, $3, $4 + 1, NOW())
+               ON CONFLICT (consumer_group, topic, partition) DO UPDATE
+               SET next_offset = EXCLUDED.next_offset, updated_at = NOW()
+               WHERE projection_offsets.next_offset <= $4
+               RETURNING next_offset"#,
+            self.group,
+            message.topic(),
+            message.partition(),
+            message.offset()
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if claimed.is_none() {
+            metrics::increment_counter!("projection_messages_duplicate_total");
+            return Ok(());
+        }
+
+        // 2. Retractions undo exactly what the original message contributed
+        // The key is the event's idempotency key
+        let event_key = message
+            .key()
+            .map(|k| String::from_utf8_lossy(k).into_owned())
+            .context("Projection message without key")?;
+
+        if is_retraction(message) {
+            let applied = sqlx::query!(
+                r#"DELETE FROM projection_events WHERE event_key = $1 RETURNING data"#,
+                event_key
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if let Some(row) = applied {
+                let event: ProgramEvent = serde_json::from_value(row.data)?;
+                project(&mut tx, event.inner, -1).await?;
+                metrics::increment_counter!("projection_events_retracted_total");
+            }
+        } else {
+            let payload = message.payload().context("Projection message without payload")?;
+            let event: ProgramEvent = serde_json::from_slice(payload)?;
+
+            let inserted = sqlx::query!(
+                r#"INSERT INTO projection_events (event_key, slot, data)
+                   VALUES ($1, $2, $3)
+                   ON CONFLICT (event_key) DO NOTHING
+                   RETURNING event_key"#,
+                event_key,
+                event.slot,
+                serde_json::to_value(&event)?
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            // The outbox can republish a batch under new offsets
+            if inserted.is_some() {
+                let event_type = event.event_type();
+                project(&mut tx, event.inner, 1).await?;
+                metrics::increment_counter!("projection_events_applied_total", "type" => event_type);
+            } else {
+                warn!(%event_key, "Event already projected");
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+fn is_retraction(message: &BorrowedMessage<'_>) -> bool {
+    message
+        .headers()
+        .map(|headers| {
+            headers
+                .iter()
+                .any(|h| h.key == "retracted" && h.value == Some(b"true".as_slice()))
+        })
+        .unwrap_or(false)
+}
+
+/// Add (`sign = 1`) or remove (`sign = -1`) an event's contribution to every projection
+async fn project(tx: &mut PgConnection, event: ProgramEventType, sign: i64) -> anyhow::Result<()> {
+    match event {
+        ProgramEventType::InferenceFulfilled(fulfillment) => {
+            project_fulfillment(tx, fulfillment, sign).await?;
+        }
+        ProgramEventType::InferenceChallenged(challenge) => {
+            project_challenge(tx, challenge, sign).await?;
+        }
+        ProgramEventType::InferenceFinalized(finalized) => {
+            project_finalization(tx, finalized, sign).await?;
+        }
+        ProgramEventType::DataContributed(contribution) => {
+            project_contribution(tx, contribution, sign).await?;
+        }
+        // An erased contribution no longer counts towards the leaderboard
+        ProgramEventType::ContributionRevoked(revocation) => {
+            let contribution = DataContribution {
+                model_id: revocation.model_id,
+                contributor: revocation.contributor,
+                data_hash: revocation.data_hash,
+            };
+            project_contribution(tx, contribution, -sign).await?;
+        }
+        ProgramEventType::ProposalCreated(proposal) => {
+            project_proposal(tx, proposal, sign).await?;
+        }
+        ProgramEventType::VoteCast(vote) => {
+            project_vote(tx, vote, sign).await?;
+        }
+        // Registry lifecycle is already materialized by the listener
+        ProgramEventType::ModelRegistered(_)
+        | ProgramEventType::ModelUpdated(_)
+        | ProgramEventType::ModelDeleted(_)
+        | ProgramEventType::ModelExpired(_)
+        | ProgramEventType::ModelForked(_)
+        | ProgramEventType::ModelMetadataSet(_)
+        | ProgramEventType::VersionUpdated(_) => {}
+        // Requests are counted once fulfilled
+        ProgramEventType::InferenceRequested(_) => {}
+        // Retention is scheduled by the listener
+        ProgramEventType::ConsentChanged(_) => {}
+    }
+    Ok(())
+}
+
+/// Optimistic ("fulfilled") results are counted when finalized or challenged
+async fn project_fulfillment(
+    tx: &mut PgConnection,
+    fulfillment: InferenceFulfillment,
+    sign: i64,
+) -> anyhow::Result<()> {
+    let (completed, failed) = match fulfillment.status.as_str() {
+        "completed" => (sign, 0),
+        "failed" => (0, sign),
+        _ => return Ok(()),
+    };
+
+    bump_daily_counts(tx, &fulfillment.model_id, fulfillment.timestamp, completed, failed, 0).await?;
+    if completed != 0 {
+        add_fee_revenue(tx, &fulfillment.model_id, fulfillment.fee_mint, fulfillment.fee, sign).await?;
+    }
+    Ok(())
+}
+
+async fn project_challenge(
+    tx: &mut PgConnection,
+    challenge: InferenceChallenge,
+    sign: i64,
+) -> anyhow::Result<()> {
+    bump_daily_counts(tx, &challenge.model_id, challenge.timestamp, 0, 0, sign).await
+}
+
+async fn project_finalization(
+    tx: &mut PgConnection,
+    finalized: InferenceFinalization,
+    sign: i64,
+) -> anyhow::Result<()> {
+    bump_daily_counts(tx, &finalized.model_id, finalized.timestamp, sign, 0, 0).await?;
+    add_fee_revenue(tx, &finalized.model_id, finalized.fee_mint, finalized.fee, sign).await
+}
+
+async fn bump_daily_counts(
+    tx: &mut PgConnection,
+    model_id: &str,
+    timestamp: i64,
+    completed: i64,
+    failed: i64,
+    challenged: i64,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO inference_daily_counts (model_id, day, completed, failed, challenged)
+           VALUES ($1, (to_timestamp($2) AT TIME ZONE 'UTC')::date, $3, $4, $5)
+           ON CONFLICT (model_id, day) DO UPDATE
+           SET completed = inference_daily_counts.completed + EXCLUDED.completed,
+               failed = inference_daily_counts.failed + EXCLUDED.failed,
+               challenged = inference_daily_counts.challenged + EXCLUDED.challenged"#,
+        model_id,
+        timestamp as f64,
+        completed,
+        failed,
+        challenged
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+/// Lamport fees are keyed under the empty mint
+async fn add_fee_revenue(
+    tx: &mut PgConnection,
+    model_id: &str,
+    fee_mint: Option<String>,
+    fee: u64,
+    sign: i64,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO model_fee_revenue (model_id, fee_mint, amount, settled)
+           VALUES ($1, $2, $3, $4)
+           ON CONFLICT (model_id, fee_mint) DO UPDATE
+           SET amount = model_fee_revenue.amount + EXCLUDED.amount,
+               settled = model_fee_revenue.settled + EXCLUDED.settled"#,
+        model_id,
+        fee_mint.unwrap_or_default(),
+        sign * fee as i64,
+        sign
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+async fn project_contribution(
+    tx: &mut PgConnection,
+    contribution: DataContribution,
+    sign: i64,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO contributor_leaderboard (model_id, contributor, contributions)
+           VALUES ($1, $2, $3)
+           ON CONFLICT (model_id, contributor) DO UPDATE
+           SET contributions = contributor_leaderboard.contributions + EXCLUDED.contributions"#,
+        contribution.model_id,
+        contribution.contributor,
+        sign
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+async fn project_proposal(
+    tx: &mut PgConnection,
+    proposal: ProposalCreation,
+    sign: i64,
+) -> anyhow::Result<()> {
+    if sign < 0 {
+        // Vote tallies are left alone; each vote is retracted by its own message
+        sqlx::query!(
+            "UPDATE governance_proposals SET registered = FALSE WHERE proposal_id = $1",
+            proposal.proposal_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"INSERT INTO governance_proposals (proposal_id, author, start_time, end_time, registered)
+           VALUES ($1, $2, to_timestamp($3), to_timestamp($4), TRUE)
+           ON CONFLICT (proposal_id) DO UPDATE
+           SET author = EXCLUDED.author,
+               start_time = EXCLUDED.start_time,
+               end_time = EXCLUDED.end_time,
+               registered = TRUE"#,
+        proposal.proposal_id,
+        proposal.author,
+        proposal.start_time as f64,
+        proposal.end_time as f64
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+async fn project_vote(tx: &mut PgConnection, vote: Vote, sign: i64) -> anyhow::Result<()> {
+    // Proposal row may not be projected yet; counts are kept either way
+    sqlx::query!(
+        r#"INSERT INTO governance_proposals (proposal_id, votes, effective_weight)
+           VALUES ($1, $2, $3)
+           ON CONFLICT (proposal_id) DO UPDATE
+           SET votes = governance_proposals.votes + EXCLUDED.votes,
+               effective_weight = governance_proposals.effective_weight + EXCLUDED.effective_weight"#,
+        vote.proposal_id,
+        sign,
+        sign * vote.effective_weight as i64
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"INSERT INTO governance_voters (voter, votes, last_voted_at)
+           VALUES ($1, $2, to_timestamp($3))
+           ON CONFLICT (voter) DO UPDATE
+           SET votes = governance_voters.votes + EXCLUDED.votes,
+               last_voted_at = GREATEST(governance_voters.last_voted_at, EXCLUDED.last_voted_at)"#,
+        vote.voter,
+        sign,
+        vote.cast_at as f64
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,170 @@
+// indexer/src/reorg.rs
+
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    clock::Slot,
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tokio::time::Duration;
+use tracing::{debug, instrument, warn};
+
+pub const FINALITY_POLL_INTERVAL: Duration = Duration::from_millis(800);
+const MAX_SLOT_RANGE: u64 = 500_000; // getBlocks range limit
+
+/// Lifecycle of a slot that has produced indexed events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotStatus {
+    Confirmed,
+    Finalized,
+    Dropped,
+}
+
+impl SlotStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+            Self::Dropped => "dropped",
+        }
+    }
+}
+
+/// Commitment required before events are materialized into each table
+#[derive(Debug, Clone, Deserialize)]
+pub struct FinalityConfig {
+    #[serde(default = "default_commitment")]
+    pub default_commitment: CommitmentLevel,
+    #[serde(default)]
+    pub tables: HashMap<String, CommitmentLevel>,
+}
+
+fn default_commitment() -> CommitmentLevel {
+    CommitmentLevel::Confirmed
+}
+
+impl Default for FinalityConfig {
+    fn default() -> Self {
+        Self {
+            default_commitment: default_commitment(),
+            tables: HashMap::new(),
+        }
+    }
+}
+
+impl FinalityConfig {
+    /// Commitment level configured for a table, falling back to the default
+    pub fn commitment_for(&self, table: &str) -> CommitmentLevel {
+        self.tables
+            .get(table)
+            .copied()
+            .unwrap_or(self.default_commitment)
+    }
+
+    /// Whether writes to `table` must wait for the slot to be finalized
+    pub fn requires_finality(&self, table: &str) -> bool {
+        self.commitment_for(table) == CommitmentLevel::Finalized
+    }
+}
+
+/// Result of a single finality poll
+#[derive(Debug, Default)]
+pub struct FinalityUpdate {
+    pub confirmed_slot: Slot,
+    pub finalized_slot: Slot,
+    pub finalized: Vec<Slot>,
+    pub dropped: Vec<Slot>,
+    /// Older than the node's first available block; left provisional
+    pub unknown: Vec<Slot>,
+}
+
+/// Resolves provisional slots against the rooted chain
+pub struct FinalityTracker {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl FinalityTracker {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+
+    /// Classify provisional slots as finalized or dropped.
+    ///
+    /// Slots above the current finalized slot are left untouched; any slot at
+    /// or below it that is missing from the rooted block list was skipped by
+    /// the cluster and its events must be rolled back. Slots the node has
+    /// already purged from its ledger cannot be checked and are reported as
+    /// unknown rather than dropped.
+    #[instrument(skip_all, fields(pending = provisional.len()))]
+    pub async fn poll(&self, provisional: &[Slot]) -> anyhow::Result<FinalityUpdate> {
+        let confirmed_slot = self
+            .rpc_client
+            .get_slot_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        let finalized_slot = self
+            .rpc_client
+            .get_slot_with_commitment(CommitmentConfig::finalized())
+            .await?;
+
+        let lag = confirmed_slot.saturating_sub(finalized_slot);
+        metrics::gauge!("finality_lag", lag as f64);
+
+        let mut update = FinalityUpdate {
+            confirmed_slot,
+            finalized_slot,
+            ..Default::default()
+        };
+
+        let first_available = self.rpc_client.get_first_available_block().await?;
+        let (candidates, unknown): (Vec<Slot>, Vec<Slot>) = provisional
+            .iter()
+            .copied()
+            .filter(|slot| *slot <= finalized_slot)
+            .partition(|slot| *slot >= first_available);
+        if !unknown.is_empty() {
+            warn!(
+                count = unknown.len(),
+                first_available,
+                "Provisional slots predate the node's ledger; finality unknown"
+            );
+        }
+        metrics::gauge!("slots_finality_unknown", unknown.len() as f64);
+        update.unknown = unknown;
+
+        let Some(&start) = candidates.iter().min() else {
+            return Ok(update);
+        };
+
+        let end = finalized_slot.min(start + MAX_SLOT_RANGE);
+        let rooted: HashSet<Slot> = self
+            .rpc_client
+            .get_blocks_with_commitment(start, Some(end), CommitmentConfig::finalized())
+            .await?
+            .into_iter()
+            .collect();
+
+        for slot in candidates.into_iter().filter(|slot| *slot <= end) {
+            if rooted.contains(&slot) {
+                update.finalized.push(slot);
+            } else {
+                warn!(slot, "Slot dropped from canonical chain");
+                update.dropped.push(slot);
+            }
+        }
+
+        debug!(
+            finalized = update.finalized.len(),
+            dropped = update.dropped.len(),
+            unknown = update.unknown.len(),
+            lag,
+            "Finality poll complete"
+        );
+        metrics::counter!("slots_finalized_total", update.finalized.len() as u64);
+        metrics::counter!("slots_dropped_total", update.dropped.len() as u64);
+        Ok(update)
+    }
+}
@@ -0,0 +1,157 @@
+// indexer/src/retention.rs
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use tokio::{
+    task::JoinHandle,
+    time::{interval, Duration},
+};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionConfig {
+    /// Store holding encrypted contributions; blobs live at `/contributions/<data hash>`
+    pub blob_store_url: Option<String>,
+    /// Bearer token for deletions, when the store requires one
+    pub blob_store_token: Option<String>,
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            blob_store_url: None,
+            blob_store_token: None,
+            poll_interval_secs: default_poll_interval_secs(),
+            batch_size: default_batch_size(),
+        }
+    }
+}
+
+fn default_poll_interval_secs() -> u64 {
+    300
+}
+
+fn default_batch_size() -> i64 {
+    100
+}
+
+/// Delete contributions whose consented retention has run out or was withdrawn.
+/// Failed deletions are retried every poll; they are never given up on.
+pub fn spawn_enforcer(db_pool: PgPool, config: RetentionConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let Some(blob_store_url) = config.blob_store_url.clone() else {
+            warn!("No blob store configured; retention is not enforced");
+            return;
+        };
+        let http = match reqwest::Client::builder().timeout(Duration::from_secs(30)).build() {
+            Ok(http) => http,
+            Err(e) => {
+                warn!(error = %e, "Retention enforcer disabled");
+                return;
+            }
+        };
+        let mut ticker = interval(Duration::from_secs(config.poll_interval_secs));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = delete_expired(&db_pool, &http, &blob_store_url, &config).await {
+                warn!(error = %e, "Retention sweep failed");
+            }
+        }
+    })
+}
+
+async fn delete_expired(
+    db_pool: &PgPool,
+    http: &reqwest::Client,
+    blob_store_url: &str,
+    config: &RetentionConfig,
+) -> anyhow::Result<()> {
+    // Oldest deadlines first, so a backlog never starves overdue blobs
+    let due = sqlx::query!(
+        r#"SELECT r.model_id, r.data_hash, r.withdrawn, e.revoked_at IS NOT NULL AS "revoked!"
+           FROM contribution_retention r
+           LEFT JOIN contribution_erasures e ON e.model_id = r.model_id AND e.data_hash = r.data_hash
+           WHERE r.deleted_at IS NULL AND r.expires_at <= NOW()
+           ORDER BY r.expires_at
+           LIMIT $1"#,
+        config.batch_size
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    for blob in due {
+        match delete_blob(http, blob_store_url, config.blob_store_token.as_deref(), &blob.data_hash).await {
+            Ok(()) => {
+                sqlx::query!(
+                    r#"UPDATE contribution_retention SET deleted_at = NOW(), last_error = NULL
+                       WHERE model_id = $1 AND data_hash = $2"#,
+                    blob.model_id,
+                    blob.data_hash
+                )
+                .execute(db_pool)
+                .await?;
+                // Completes the erasure log entry of a revoked contribution
+                sqlx::query!(
+                    r#"UPDATE contribution_erasures SET purged_at = NOW()
+                       WHERE model_id = $1 AND data_hash = $2 AND purged_at IS NULL"#,
+                    blob.model_id,
+                    blob.data_hash
+                )
+                .execute(db_pool)
+                .await?;
+                let reason = match (blob.revoked, blob.withdrawn) {
+                    (true, _) => "revoked",
+                    (false, true) => "withdrawn",
+                    (false, false) => "expired",
+                };
+                metrics::increment_counter!("contributions_deleted_total", "reason" => reason);
+                info!(model = %blob.model_id, data_hash = %blob.data_hash, reason, "Contribution deleted");
+            }
+            Err(e) => {
+                sqlx::query!(
+                    r#"UPDATE contribution_retention SET attempts = attempts + 1, last_error = $3
+                       WHERE model_id = $1 AND data_hash = $2"#,
+                    blob.model_id,
+                    blob.data_hash,
+                    e.to_string()
+                )
+                .execute(db_pool)
+                .await?;
+                metrics::increment_counter!("contribution_deletion_failures_total");
+                warn!(model = %blob.model_id, data_hash = %blob.data_hash, error = %e, "Contribution deletion failed");
+            }
+        }
+    }
+
+    let overdue: i64 = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM contribution_retention WHERE deleted_at IS NULL AND expires_at <= NOW()"#
+    )
+    .fetch_one(db_pool)
+    .await?;
+    metrics::gauge!("contributions_overdue_for_deletion", overdue as f64);
+    Ok(())
+}
+
+/// A blob the store no longer has counts as deleted
+async fn delete_blob(
+    http: &reqwest::Client,
+    blob_store_url: &str,
+    token: Option<&str>,
+    data_hash: &str,
+) -> anyhow::Result<()> {
+    let url = format!("{}/contributions/{data_hash}", blob_store_url.trim_end_matches('/'));
+    let mut request = http.delete(url);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+    response.error_for_status()?;
+    Ok(())
+}
@@ -0,0 +1,225 @@
+// indexer/src/robust_aggregation.rs
+
+use anyhow::{bail, ensure};
+use serde::{Deserialize, Serialize};
+
+/// Aggregation rule applied to a round of client updates.
+///
+/// `FedAvg` runs over the secure aggregation sum. The robust rules need to
+/// inspect each update individually, so they are incompatible with masking.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AggregatorKind {
+    #[default]
+    FedAvg,
+    /// (Multi-)Krum: average the `select` updates closest to their neighbours,
+    /// tolerating up to `byzantine` malicious clients
+    Krum {
+        byzantine: usize,
+        #[serde(default = "default_krum_select")]
+        select: usize,
+    },
+    /// Coordinate-wise mean after dropping the `trim_ratio` tails on each side
+    TrimmedMean { trim_ratio: f32 },
+    /// Coordinate-wise median
+    Median,
+    /// Trust-scored average relative to an update computed on a root dataset
+    FlTrust,
+}
+
+fn default_krum_select() -> usize {
+    1
+}
+
+/// Aggregated update plus which inputs contributed to it
+#[derive(Debug, Clone)]
+pub struct AggregationOutcome {
+    pub update: Vec<f32>,
+    /// Indices of inputs that influenced the result
+    pub accepted: Vec<usize>,
+    /// Indices of inputs discarded as malformed or outliers
+    pub rejected: Vec<usize>,
+}
+
+impl AggregatorKind {
+    pub fn requires_individual_updates(&self) -> bool {
+        !matches!(self, AggregatorKind::FedAvg)
+    }
+
+    pub fn requires_root_update(&self) -> bool {
+        matches!(self, AggregatorKind::FlTrust)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self {
+            AggregatorKind::Krum { select, .. } => ensure!(*select >= 1, "krum must select at least one update"),
+            AggregatorKind::TrimmedMean { trim_ratio } => ensure!(
+                (0.0..0.5).contains(trim_ratio),
+                "trim_ratio must be in [0, 0.5)"
+            ),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Aggregate per-client updates. `root` is required for FLTrust.
+    pub fn aggregate(&self, updates: &[Vec<f32>], root: Option<&[f32]>) -> anyhow::Result<AggregationOutcome> {
+        // Non-finite values are a cheap poisoning vector: drop them up front
+        let dimension = root
+            .map(|r| r.len())
+            .or_else(|| updates.first().map(|u| u.len()))
+            .unwrap_or(0);
+        let (valid, mut rejected): (Vec<usize>, Vec<usize>) = (0..updates.len())
+            .partition(|&i| updates[i].len() == dimension && updates[i].iter().all(|v| v.is_finite()));
+        ensure!(!valid.is_empty(), "no well-formed updates to aggregate");
+        let inputs: Vec<&[f32]> = valid.iter().map(|&i| updates[i].as_slice()).collect();
+
+        let (update, kept) = match self {
+            AggregatorKind::FedAvg => (mean(&inputs, dimension), (0..inputs.len()).collect()),
+            AggregatorKind::Krum { byzantine, select } => krum(&inputs, *byzantine, *select)?,
+            AggregatorKind::TrimmedMean { trim_ratio } => {
+                let trim = (inputs.len() as f32 * trim_ratio).floor() as usize;
+                ensure!(
+                    inputs.len() > 2 * trim,
+                    "cannot trim {} from each side of {} updates",
+                    trim,
+                    inputs.len()
+                );
+                (coordinate_wise(&inputs, dimension, |col| trimmed_mean(col, trim)), (0..inputs.len()).collect())
+            }
+            AggregatorKind::Median => (coordinate_wise(&inputs, dimension, median), (0..inputs.len()).collect()),
+            AggregatorKind::FlTrust => {
+                let Some(root) = root else {
+                    bail!("FLTrust requires a root update");
+                };
+                fltrust(&inputs, root)?
+            }
+        };
+
+        let kept: Vec<usize> = kept.into_iter().map(|k: usize| valid[k]).collect();
+        rejected.extend(valid.iter().copied().filter(|i| !kept.contains(i)));
+        rejected.sort_unstable();
+
+        Ok(AggregationOutcome {
+            update,
+            accepted: kept,
+            rejected,
+        })
+    }
+}
+
+fn mean(inputs: &[&[f32]], dimension: usize) -> Vec<f32> {
+    let mut out = vec![0f32; dimension];
+    for u in inputs {
+        for (acc, v) in out.iter_mut().zip(u.iter()) {
+            *acc += v;
+        }
+    }
+    let n = inputs.len() as f32;
+    out.iter_mut().for_each(|v| *v /= n);
+    out
+}
+
+fn squared_distance(a: &[f32], b: &[f32]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| {
+            let d = (*x - *y) as f64;
+            d * d
+        })
+        .sum()
+}
+
+fn norm(a: &[f32]) -> f64 {
+    a.iter().map(|x| (*x as f64) * (*x as f64)).sum::<f64>().sqrt()
+}
+
+/// Blanchard et al. 2017; requires n >= 2f + 3
+fn krum(inputs: &[&[f32]], byzantine: usize, select: usize) -> anyhow::Result<(Vec<f32>, Vec<usize>)> {
+    let n = inputs.len();
+    ensure!(
+        n >= 2 * byzantine + 3,
+        "krum needs at least {} updates to tolerate {} byzantine clients, got {}",
+        2 * byzantine + 3,
+        byzantine,
+        n
+    );
+    ensure!(select <= n - byzantine, "cannot select {} of {} updates", select, n);
+
+    let neighbours = n - byzantine - 2;
+    let mut scores: Vec<(f64, usize)> = (0..n)
+        .map(|i| {
+            let mut distances: Vec<f64> = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| squared_distance(inputs[i], inputs[j]))
+                .collect();
+            distances.sort_by(|a, b| a.total_cmp(b));
+            (distances[..neighbours].iter().sum(), i)
+        })
+        .collect();
+    scores.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let chosen: Vec<usize> = scores[..select].iter().map(|&(_, i)| i).collect();
+    let selected: Vec<&[f32]> = chosen.iter().map(|&i| inputs[i]).collect();
+    Ok((mean(&selected, inputs[0].len()), chosen))
+}
+
+fn coordinate_wise(inputs: &[&[f32]], dimension: usize, reduce: impl Fn(&mut [f32]) -> f32) -> Vec<f32> {
+    let mut column = vec![0f32; inputs.len()];
+    (0..dimension)
+        .map(|d| {
+            for (slot, u) in column.iter_mut().zip(inputs) {
+                *slot = u[d];
+            }
+            reduce(&mut column)
+        })
+        .collect()
+}
+
+fn trimmed_mean(column: &mut [f32], trim: usize) -> f32 {
+    column.sort_by(|a, b| a.total_cmp(b));
+    let kept = &column[trim..column.len() - trim];
+    kept.iter().sum::<f32>() / kept.len() as f32
+}
+
+fn median(column: &mut [f32]) -> f32 {
+    column.sort_by(|a, b| a.total_cmp(b));
+    let mid = column.len() / 2;
+    if column.len() % 2 == 1 {
+        column[mid]
+    } else {
+        (column[mid - 1] + column[mid]) / 2.0
+    }
+}
+
+/// Cao et al. 2021: ReLU(cosine) trust scores, updates rescaled to the root norm
+fn fltrust(inputs: &[&[f32]], root: &[f32]) -> anyhow::Result<(Vec<f32>, Vec<usize>)> {
+    let root_norm = norm(root);
+    ensure!(root_norm > 0.0, "root update is zero");
+
+    let mut out = vec![0f64; root.len()];
+    let mut total_trust = 0f64;
+    let mut trusted = Vec::new();
+
+    for (i, u) in inputs.iter().enumerate() {
+        let u_norm = norm(u);
+        if u_norm == 0.0 {
+            continue;
+        }
+        let dot: f64 = u.iter().zip(root).map(|(a, b)| *a as f64 * *b as f64).sum();
+        let trust = (dot / (u_norm * root_norm)).max(0.0);
+        if trust == 0.0 {
+            continue;
+        }
+
+        let scale = trust * root_norm / u_norm;
+        for (acc, v) in out.iter_mut().zip(u.iter()) {
+            *acc += scale * *v as f64;
+        }
+        total_trust += trust;
+        trusted.push(i);
+    }
+
+    ensure!(total_trust > 0.0, "no update is aligned with the root update");
+    Ok((out.iter().map(|v| (v / total_trust) as f32).collect(), trusted))
+}
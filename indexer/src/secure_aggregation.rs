@@ -0,0 +1,702 @@
+// indexer/src/secure_aggregation.rs
+
+// Secure aggregation for federated updates (Bonawitz et al., CCS'17).
+//
+// Round 0: clients advertise two X25519 public keys (share encryption, masking)
+// Round 1: clients Shamir-share their masking secret key and self-mask seed,
+//          encrypting one share pair per peer
+// Round 2: clients submit their quantized update masked with a self mask and
+//          pairwise masks that cancel out across the surviving cohort
+// Round 3: survivors reveal self-mask shares of survivors and masking-key
+//          shares of dropped clients so the server can strip every mask
+//
+// The server only ever learns the sum of surviving updates.
+//
+// `fl.aggregation_scheme` can replace masking with additively homomorphic
+// encryption (packed Paillier or batched CKKS) under a decryption committee's
+// key: the aggregator sums ciphertexts and the committee reveals only the sum.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use crate::{ckks, paillier};
+use anyhow::{anyhow, bail, ensure, Context};
+use hkdf::Hkdf;
+use rand::{rngs::OsRng, RngCore};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{BTreeMap, BTreeSet};
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroize;
+
+pub type ClientId = u32;
+
+/// Fixed-point scale applied to gradients before masking
+const FIXED_POINT_SCALE: f64 = (1u64 << 24) as f64;
+/// Shamir x-coordinates are single bytes
+const MAX_COHORT_SIZE: usize = 255;
+const SECRET_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const SHARE_KEY_INFO: &[u8] = b"scoria/secagg/v1/share-key";
+const PAIRWISE_MASK_INFO: &[u8] = b"scoria/secagg/v1/pairwise-mask";
+
+/// How updates are hidden from the aggregator (`fl.aggregation_scheme`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationScheme {
+    /// Pairwise masks over four rounds; tolerates dropouts
+    #[default]
+    Masking,
+    /// One round of packed Paillier ciphertexts
+    Paillier,
+    /// One round of batched CKKS ciphertexts; fastest, approximate to ~1e-6
+    Ckks,
+}
+
+impl AggregationScheme {
+    pub fn is_homomorphic(&self) -> bool {
+        !matches!(self, AggregationScheme::Masking)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AggregationScheme::Masking => "masking",
+            AggregationScheme::Paillier => "paillier",
+            AggregationScheme::Ckks => "ckks",
+        }
+    }
+}
+
+/// Round 0 message
+#[derive(Debug, Clone)]
+pub struct AdvertisedKeys {
+    pub id: ClientId,
+    pub cipher_pk: [u8; 32],
+    pub mask_pk: [u8; 32],
+}
+
+/// Round 1 message, routed by the server from `from` to `to`
+#[derive(Debug, Clone)]
+pub struct EncryptedShares {
+    pub from: ClientId,
+    pub to: ClientId,
+    pub ciphertext: Vec<u8>,
+}
+
+/// Round 2 message
+#[derive(Debug, Clone)]
+pub struct MaskedInput {
+    pub id: ClientId,
+    pub values: Vec<u64>,
+}
+
+/// Round 3 message. A client never reveals both shares for the same peer.
+#[derive(Debug, Clone)]
+pub struct UnmaskResponse {
+    pub id: ClientId,
+    /// Masking-key shares of clients that dropped after round 1
+    pub mask_key_shares: BTreeMap<ClientId, Share>,
+    /// Self-mask seed shares of clients that submitted masked input
+    pub self_mask_shares: BTreeMap<ClientId, Share>,
+}
+
+/// One Shamir share of a 32-byte secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share {
+    pub x: u8,
+    pub y: [u8; SECRET_LEN],
+}
+
+/// Unmasked result of a round
+#[derive(Debug, Clone)]
+pub struct AggregateResult {
+    pub sum: Vec<f32>,
+    pub contributors: Vec<ClientId>,
+    pub dropped: Vec<ClientId>,
+}
+
+impl AggregateResult {
+    pub fn mean(&self) -> Vec<f32> {
+        let n = self.contributors.len().max(1) as f32;
+        self.sum.iter().map(|v| v / n).collect()
+    }
+}
+
+/// Client side of one secure aggregation round
+pub struct SecAggClient {
+    id: ClientId,
+    threshold: usize,
+    cipher_sk: StaticSecret,
+    mask_sk: StaticSecret,
+    self_seed: [u8; SECRET_LEN],
+    roster: BTreeMap<ClientId, AdvertisedKeys>,
+    // sender -> (masking-key share, self-mask share)
+    held_shares: BTreeMap<ClientId, (Share, Share)>,
+}
+
+impl SecAggClient {
+    pub fn new(id: ClientId, threshold: usize) -> Self {
+        let mut self_seed = [0u8; SECRET_LEN];
+        OsRng.fill_bytes(&mut self_seed);
+        Self {
+            id,
+            threshold,
+            cipher_sk: StaticSecret::random_from_rng(OsRng),
+            mask_sk: StaticSecret::random_from_rng(OsRng),
+            self_seed,
+            roster: BTreeMap::new(),
+            held_shares: BTreeMap::new(),
+        }
+    }
+
+    pub fn id(&self) -> ClientId {
+        self.id
+    }
+
+    /// Round 0: public keys to publish
+    pub fn advertise(&self) -> AdvertisedKeys {
+        AdvertisedKeys {
+            id: self.id,
+            cipher_pk: PublicKey::from(&self.cipher_sk).to_bytes(),
+            mask_pk: PublicKey::from(&self.mask_sk).to_bytes(),
+        }
+    }
+
+    /// Round 1: split secrets across the roster and encrypt one share pair per peer
+    pub fn share_keys(&mut self, roster: &[AdvertisedKeys]) -> anyhow::Result<Vec<EncryptedShares>> {
+        let roster = validate_roster(roster, self.threshold)?;
+        let own = roster
+            .get(&self.id)
+            .ok_or_else(|| anyhow!("client {} missing from roster", self.id))?;
+        ensure!(
+            own.mask_pk == self.advertise().mask_pk,
+            "roster carries foreign keys for client {}",
+            self.id
+        );
+
+        let n = roster.len();
+        let mask_shares = shamir::split(&self.mask_sk.to_bytes(), self.threshold, n);
+        let self_shares = shamir::split(&self.self_seed, self.threshold, n);
+
+        let mut out = Vec::with_capacity(n - 1);
+        for (i, peer) in roster.values().enumerate() {
+            if peer.id == self.id {
+                self.held_shares.insert(self.id, (mask_shares[i], self_shares[i]));
+                continue;
+            }
+            let plaintext = encode_share_pair(self.id, peer.id, &mask_shares[i], &self_shares[i]);
+            out.push(EncryptedShares {
+                from: self.id,
+                to: peer.id,
+                ciphertext: seal(&self.share_key(peer), &plaintext, &share_aad(self.id, peer.id))?,
+            });
+        }
+
+        self.roster = roster;
+        Ok(out)
+    }
+
+    /// Decrypt the share pairs addressed to this client
+    pub fn receive_shares(&mut self, shares: &[EncryptedShares]) -> anyhow::Result<()> {
+        for msg in shares.iter().filter(|m| m.to == self.id) {
+            let peer = self
+                .roster
+                .get(&msg.from)
+                .ok_or_else(|| anyhow!("shares from unknown client {}", msg.from))?;
+            let plaintext = open(&self.share_key(peer), &msg.ciphertext, &share_aad(msg.from, self.id))
+                .with_context(|| format!("undecryptable shares from client {}", msg.from))?;
+            let pair = decode_share_pair(&plaintext, msg.from, self.id)?;
+            self.held_shares.insert(msg.from, pair);
+        }
+        Ok(())
+    }
+
+    /// Round 2: mask the update against every client that completed round 1
+    pub fn mask_input(&self, input: &[f32], share_senders: &[ClientId]) -> anyhow::Result<MaskedInput> {
+        ensure!(
+            share_senders.contains(&self.id),
+            "client {} did not complete key sharing",
+            self.id
+        );
+
+        let mut values = quantize(input);
+        add_mask(&mut values, &self.self_seed, true);
+
+        for &peer_id in share_senders.iter().filter(|&&v| v != self.id) {
+            let peer = self
+                .roster
+                .get(&peer_id)
+                .ok_or_else(|| anyhow!("share sender {} not in roster", peer_id))?;
+            let seed = pairwise_seed(&self.mask_sk, &peer.mask_pk, self.id, peer_id);
+            add_mask(&mut values, &seed, self.id < peer_id);
+        }
+
+        Ok(MaskedInput { id: self.id, values })
+    }
+
+    /// Round 3: reveal exactly one share per round-1 peer
+    pub fn unmask(&self, share_senders: &[ClientId], survivors: &[ClientId]) -> anyhow::Result<UnmaskResponse> {
+        ensure!(
+            survivors.len() >= self.threshold,
+            "only {} survivors, threshold is {}",
+            survivors.len(),
+            self.threshold
+        );
+        ensure!(survivors.contains(&self.id), "client {} is not a survivor", self.id);
+        let survivors: BTreeSet<_> = survivors.iter().copied().collect();
+
+        let mut response = UnmaskResponse {
+            id: self.id,
+            mask_key_shares: BTreeMap::new(),
+            self_mask_shares: BTreeMap::new(),
+        };
+        for peer in share_senders {
+            let (mask_share, self_share) = self
+                .held_shares
+                .get(peer)
+                .ok_or_else(|| anyhow!("no shares held for client {}", peer))?;
+            if survivors.contains(peer) {
+                response.self_mask_shares.insert(*peer, *self_share);
+            } else {
+                response.mask_key_shares.insert(*peer, *mask_share);
+            }
+        }
+        Ok(response)
+    }
+
+    fn share_key(&self, peer: &AdvertisedKeys) -> [u8; 32] {
+        let shared = self.cipher_sk.diffie_hellman(&PublicKey::from(peer.cipher_pk));
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, shared.as_bytes())
+            .expand(SHARE_KEY_INFO, &mut key)
+            .expect("32 bytes is a valid HKDF output length");
+        key
+    }
+}
+
+impl Drop for SecAggClient {
+    fn drop(&mut self) {
+        self.self_seed.zeroize();
+    }
+}
+
+/// Server side of one secure aggregation round
+pub struct SecAggServer {
+    threshold: usize,
+    dimension: usize,
+    roster: BTreeMap<ClientId, AdvertisedKeys>,
+    share_senders: BTreeSet<ClientId>,
+    masked: BTreeMap<ClientId, Vec<u64>>,
+}
+
+impl SecAggServer {
+    pub fn new(threshold: usize, dimension: usize) -> Self {
+        Self {
+            threshold,
+            dimension,
+            roster: BTreeMap::new(),
+            share_senders: BTreeSet::new(),
+            masked: BTreeMap::new(),
+        }
+    }
+
+    /// Round 0: accept a client's public keys
+    pub fn register(&mut self, keys: AdvertisedKeys) -> anyhow::Result<()> {
+        ensure!(self.roster.len() < MAX_COHORT_SIZE, "cohort is full");
+        ensure!(!self.roster.contains_key(&keys.id), "client {} already registered", keys.id);
+        self.roster.insert(keys.id, keys);
+        Ok(())
+    }
+
+    pub fn roster(&self) -> Vec<AdvertisedKeys> {
+        self.roster.values().cloned().collect()
+    }
+
+    /// Round 1: record senders and group ciphertexts by recipient
+    pub fn route_shares(
+        &mut self,
+        shares: Vec<EncryptedShares>,
+    ) -> anyhow::Result<BTreeMap<ClientId, Vec<EncryptedShares>>> {
+        let mut routed: BTreeMap<ClientId, Vec<EncryptedShares>> = BTreeMap::new();
+        for msg in shares {
+            ensure!(
+                self.roster.contains_key(&msg.from) && self.roster.contains_key(&msg.to),
+                "share message {} -> {} outside roster",
+                msg.from,
+                msg.to
+            );
+            self.share_senders.insert(msg.from);
+            routed.entry(msg.to).or_default().push(msg);
+        }
+        ensure!(
+            self.share_senders.len() >= self.threshold,
+            "only {} clients shared keys, threshold is {}",
+            self.share_senders.len(),
+            self.threshold
+        );
+        Ok(routed)
+    }
+
+    pub fn share_senders(&self) -> Vec<ClientId> {
+        self.share_senders.iter().copied().collect()
+    }
+
+    /// Round 2: accept a masked update
+    pub fn submit_masked(&mut self, input: MaskedInput) -> anyhow::Result<()> {
+        ensure!(
+            self.share_senders.contains(&input.id),
+            "client {} did not complete key sharing",
+            input.id
+        );
+        ensure!(
+            input.values.len() == self.dimension,
+            "masked input has dimension {}, expected {}",
+            input.values.len(),
+            self.dimension
+        );
+        self.masked.insert(input.id, input.values);
+        Ok(())
+    }
+
+    /// Clients whose masked input arrived in round 2
+    pub fn survivors(&self) -> Vec<ClientId> {
+        self.masked.keys().copied().collect()
+    }
+
+    /// Round 3: reconstruct and strip every mask, returning the plain sum
+    pub fn unmask(&self, responses: &[UnmaskResponse]) -> anyhow::Result<AggregateResult> {
+        let survivors = self.survivors();
+        ensure!(
+            survivors.len() >= self.threshold,
+            "only {} survivors, threshold is {}",
+            survivors.len(),
+            self.threshold
+        );
+        let responders: Vec<_> = responses
+            .iter()
+            .filter(|r| self.masked.contains_key(&r.id))
+            .collect();
+        ensure!(
+            responders.len() >= self.threshold,
+            "only {} unmasking responses, threshold is {}",
+            responders.len(),
+            self.threshold
+        );
+
+        let mut sum = vec![0u64; self.dimension];
+        for values in self.masked.values() {
+            for (acc, v) in sum.iter_mut().zip(values) {
+                *acc = acc.wrapping_add(*v);
+            }
+        }
+
+        // Remove self masks of survivors
+        for &u in &survivors {
+            let shares: Vec<Share> = responders
+                .iter()
+                .filter_map(|r| r.self_mask_shares.get(&u).copied())
+                .collect();
+            let seed = shamir::combine(&shares, self.threshold)
+                .with_context(|| format!("cannot recover self mask of client {}", u))?;
+            add_mask(&mut sum, &seed, false);
+        }
+
+        // Cancel pairwise masks survivors applied for clients that dropped
+        let dropped: Vec<ClientId> = self
+            .share_senders
+            .iter()
+            .copied()
+            .filter(|v| !self.masked.contains_key(v))
+            .collect();
+        for &v in &dropped {
+            let shares: Vec<Share> = responders
+                .iter()
+                .filter_map(|r| r.mask_key_shares.get(&v).copied())
+                .collect();
+            let mask_sk = StaticSecret::from(
+                shamir::combine(&shares, self.threshold)
+                    .with_context(|| format!("cannot recover masking key of dropped client {}", v))?,
+            );
+            for &u in &survivors {
+                let seed = pairwise_seed(&mask_sk, &self.roster[&u].mask_pk, v, u);
+                // u added the mask when u < v, so subtract it back (and vice versa)
+                add_mask(&mut sum, &seed, u > v);
+            }
+        }
+
+        Ok(AggregateResult {
+            sum: dequantize(&sum),
+            contributors: survivors,
+            dropped,
+        })
+    }
+}
+
+/// Decryption committee's public key, published per model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HePublicKey {
+    Paillier(paillier::PublicKey),
+    Ckks(ckks::PublicKey),
+}
+
+/// Held by the decryption committee only
+#[derive(Clone, Serialize, Deserialize)]
+pub enum HeSecretKey {
+    Paillier(paillier::SecretKey),
+    Ckks(ckks::SecretKey),
+}
+
+/// An update, or a sum of them, under a committee key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EncryptedUpdate {
+    Paillier(Vec<paillier::Ciphertext>),
+    Ckks(Vec<ckks::Ciphertext>),
+}
+
+/// Encryption, summation and decryption for the homomorphic schemes
+#[derive(Default)]
+pub struct HomomorphicContext {
+    ckks: ckks::Ckks,
+}
+
+impl HomomorphicContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fresh committee key for `scheme`
+    pub fn generate_keys(&self, scheme: AggregationScheme) -> anyhow::Result<(HeSecretKey, HePublicKey)> {
+        match scheme {
+            AggregationScheme::Paillier => {
+                let sk = paillier::SecretKey::generate(paillier::KEY_BITS);
+                let pk = sk.public_key().clone();
+                Ok((HeSecretKey::Paillier(sk), HePublicKey::Paillier(pk)))
+            }
+            AggregationScheme::Ckks => {
+                let (sk, pk) = self.ckks.generate_keys();
+                Ok((HeSecretKey::Ckks(sk), HePublicKey::Ckks(pk)))
+            }
+            AggregationScheme::Masking => bail!("masking has no committee key"),
+        }
+    }
+
+    pub fn encrypt(&self, pk: &HePublicKey, update: &[f32]) -> anyhow::Result<EncryptedUpdate> {
+        match pk {
+            HePublicKey::Paillier(pk) => pk.encrypt(update).map(EncryptedUpdate::Paillier),
+            HePublicKey::Ckks(pk) => self.ckks.encrypt(pk, update).map(EncryptedUpdate::Ckks),
+        }
+    }
+
+    /// `sum += update`; both must be under `pk`
+    pub fn add_assign(&self, pk: &HePublicKey, sum: &mut EncryptedUpdate, update: &EncryptedUpdate) -> anyhow::Result<()> {
+        match (pk, sum, update) {
+            (HePublicKey::Paillier(pk), EncryptedUpdate::Paillier(sum), EncryptedUpdate::Paillier(update)) => {
+                pk.add_assign(sum, update)
+            }
+            (HePublicKey::Ckks(_), EncryptedUpdate::Ckks(sum), EncryptedUpdate::Ckks(update)) => {
+                self.ckks.add_assign(sum, update)
+            }
+            _ => bail!("update encrypted under a different scheme"),
+        }
+    }
+
+    /// First `len` values of a sum of `count` updates
+    pub fn decrypt_sum(&self, sk: &HeSecretKey, sum: &EncryptedUpdate, len: usize, count: usize) -> anyhow::Result<Vec<f64>> {
+        match (sk, sum) {
+            (HeSecretKey::Paillier(sk), EncryptedUpdate::Paillier(sum)) => sk.decrypt_sum(sum, len, count),
+            (HeSecretKey::Ckks(sk), EncryptedUpdate::Ckks(sum)) => self.ckks.decrypt(sk, sum, len),
+            _ => bail!("sum encrypted under a different scheme"),
+        }
+    }
+}
+
+fn validate_roster(roster: &[AdvertisedKeys], threshold: usize) -> anyhow::Result<BTreeMap<ClientId, AdvertisedKeys>> {
+    ensure!(threshold >= 1, "threshold must be positive");
+    ensure!(
+        roster.len() >= threshold && roster.len() <= MAX_COHORT_SIZE,
+        "roster size {} outside [{}, {}]",
+        roster.len(),
+        threshold,
+        MAX_COHORT_SIZE
+    );
+    let map: BTreeMap<_, _> = roster.iter().map(|k| (k.id, k.clone())).collect();
+    ensure!(map.len() == roster.len(), "duplicate client ids in roster");
+    Ok(map)
+}
+
+/// Symmetric seed for the pairwise mask between `a` and `b`
+fn pairwise_seed(sk: &StaticSecret, peer_pk: &[u8; 32], a: ClientId, b: ClientId) -> [u8; 32] {
+    let shared = sk.diffie_hellman(&PublicKey::from(*peer_pk));
+    let (lo, hi) = (a.min(b), a.max(b));
+    let mut salt = [0u8; 8];
+    salt[..4].copy_from_slice(&lo.to_le_bytes());
+    salt[4..].copy_from_slice(&hi.to_le_bytes());
+
+    let mut seed = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared.as_bytes())
+        .expand(PAIRWISE_MASK_INFO, &mut seed)
+        .expect("32 bytes is a valid HKDF output length");
+    seed
+}
+
+/// Expand `seed` with ChaCha20 and add (or subtract) it element-wise mod 2^64
+fn add_mask(values: &mut [u64], seed: &[u8; 32], add: bool) {
+    let mut prg = ChaCha20Rng::from_seed(*seed);
+    for v in values.iter_mut() {
+        let m = prg.next_u64();
+        *v = if add { v.wrapping_add(m) } else { v.wrapping_sub(m) };
+    }
+}
+
+fn quantize(input: &[f32]) -> Vec<u64> {
+    input
+        .iter()
+        .map(|&x| (x as f64 * FIXED_POINT_SCALE).round() as i64 as u64)
+        .collect()
+}
+
+fn dequantize(values: &[u64]) -> Vec<f32> {
+    values
+        .iter()
+        .map(|&v| (v as i64 as f64 / FIXED_POINT_SCALE) as f32)
+        .collect()
+}
+
+fn share_aad(from: ClientId, to: ClientId) -> [u8; 8] {
+    let mut aad = [0u8; 8];
+    aad[..4].copy_from_slice(&from.to_le_bytes());
+    aad[4..].copy_from_slice(&to.to_le_bytes());
+    aad
+}
+
+fn encode_share_pair(from: ClientId, to: ClientId, mask: &Share, seed: &Share) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(8 + 2 * (1 + SECRET_LEN));
+    buf.extend_from_slice(&share_aad(from, to));
+    for share in [mask, seed] {
+        buf.push(share.x);
+        buf.extend_from_slice(&share.y);
+    }
+    buf
+}
+
+fn decode_share_pair(buf: &[u8], from: ClientId, to: ClientId) -> anyhow::Result<(Share, Share)> {
+    ensure!(buf.len() == 8 + 2 * (1 + SECRET_LEN), "malformed share payload");
+    ensure!(buf[..8] == share_aad(from, to), "share payload addressed to another client");
+
+    let read = |offset: usize| Share {
+        x: buf[offset],
+        y: buf[offset + 1..offset + 1 + SECRET_LEN].try_into().unwrap(),
+    };
+    Ok((read(8), read(8 + 1 + SECRET_LEN)))
+}
+
+fn seal(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = Aes256Gcm::new(key.into())
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+        .map_err(|_| anyhow!("share encryption failed"))?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+fn open(key: &[u8; 32], data: &[u8], aad: &[u8]) -> anyhow::Result<Vec<u8>> {
+    ensure!(data.len() > NONCE_LEN, "ciphertext too short");
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| anyhow!("share authentication failed"))
+}
+
+/// Byte-wise Shamir secret sharing over GF(2^8)
+mod shamir {
+    use super::{Share, SECRET_LEN};
+    use anyhow::ensure;
+    use rand::{rngs::OsRng, RngCore};
+    use std::collections::BTreeSet;
+
+    /// Split into `n` shares with x = 1..=n, any `threshold` of which recover the secret
+    pub fn split(secret: &[u8; SECRET_LEN], threshold: usize, n: usize) -> Vec<Share> {
+        let mut coeffs = vec![[0u8; SECRET_LEN]; threshold];
+        coeffs[0] = *secret;
+        for c in coeffs.iter_mut().skip(1) {
+            OsRng.fill_bytes(c);
+        }
+
+        (1..=n as u8)
+            .map(|x| {
+                let mut y = [0u8; SECRET_LEN];
+                for (i, byte) in y.iter_mut().enumerate() {
+                    // Horner evaluation from the highest coefficient
+                    *byte = coeffs.iter().rev().fold(0u8, |acc, c| gf_mul(acc, x) ^ c[i]);
+                }
+                Share { x, y }
+            })
+            .collect()
+    }
+
+    pub fn combine(shares: &[Share], threshold: usize) -> anyhow::Result<[u8; SECRET_LEN]> {
+        let mut seen = BTreeSet::new();
+        let shares: Vec<&Share> = shares
+            .iter()
+            .filter(|s| s.x != 0 && seen.insert(s.x))
+            .take(threshold)
+            .collect();
+        ensure!(
+            shares.len() == threshold,
+            "{} distinct shares available, {} required",
+            shares.len(),
+            threshold
+        );
+
+        let mut secret = [0u8; SECRET_LEN];
+        for (i, si) in shares.iter().enumerate() {
+            // Lagrange basis polynomial evaluated at zero
+            let mut basis = 1u8;
+            for (j, sj) in shares.iter().enumerate() {
+                if i != j {
+                    basis = gf_mul(basis, gf_mul(sj.x, gf_inv(sj.x ^ si.x)));
+                }
+            }
+            for (out, y) in secret.iter_mut().zip(si.y.iter()) {
+                *out ^= gf_mul(*y, basis);
+            }
+        }
+        Ok(secret)
+    }
+
+    fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0u8;
+        while b != 0 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+            let carry = a & 0x80;
+            a <<= 1;
+            if carry != 0 {
+                a ^= 0x1b;
+            }
+            b >>= 1;
+        }
+        product
+    }
+
+    fn gf_inv(a: u8) -> u8 {
+        // a^254 = a^-1 in GF(2^8)
+        let mut result = 1u8;
+        let mut base = a;
+        let mut exp = 254u8;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = gf_mul(result, base);
+            }
+            base = gf_mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+}
@@ -0,0 +1,231 @@
+// indexer/src/sink.rs
+
+use async_nats::{
+    header::{HeaderMap, NATS_MESSAGE_ID},
+    jetstream::{self, stream},
+};
+use async_trait::async_trait;
+use futures::future::{join_all, try_join_all};
+use rdkafka::{
+    config::ClientConfig,
+    message::{Header, OwnedHeaders},
+    producer::{FutureProducer, FutureRecord, Producer},
+};
+use serde::Deserialize;
+use std::{collections::HashMap, path::PathBuf};
+use tokio::time::Duration;
+use tracing::info;
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Header carrying the event's idempotency key on NATS, where messages have no key
+pub const EVENT_KEY_HEADER: &str = "Scoria-Event-Key";
+
+/// Where the outbox relay publishes events (`sink.type`).
+///
+/// The projection consumer and the ClickHouse sink read from Kafka; with
+/// `nats` they are not started and downstream consumers subscribe to JetStream.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SinkConfig {
+    /// The `kafka` section's cluster, in Kafka transactions
+    #[default]
+    Kafka,
+    Nats(NatsConfig),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NatsConfig {
+    #[serde(default = "default_nats_url")]
+    pub url: String,
+    /// `.creds` file for NGS or decentralized auth
+    #[serde(default)]
+    pub credentials: Option<PathBuf>,
+    /// JetStream stream, created on startup if missing
+    #[serde(default = "default_nats_stream")]
+    pub stream: String,
+    /// Events go to `<subject_prefix>.<topic>`
+    #[serde(default = "default_nats_subject_prefix")]
+    pub subject_prefix: String,
+    /// Republished messages inside this window are dropped by the server
+    #[serde(default = "default_duplicate_window_secs")]
+    pub duplicate_window_secs: u64,
+    #[serde(default = "default_max_age_hours")]
+    pub max_age_hours: u64,
+}
+
+fn default_nats_url() -> String {
+    "nats://127.0.0.1:4222".to_string()
+}
+
+fn default_nats_stream() -> String {
+    "SCORIA_EVENTS".to_string()
+}
+
+fn default_nats_subject_prefix() -> String {
+    "scoria".to_string()
+}
+
+fn default_duplicate_window_secs() -> u64 {
+    // Covers a relay restart republishing its last batch
+    600
+}
+
+fn default_max_age_hours() -> u64 {
+    24 * 7
+}
+
+/// One outbox row on its way out
+#[derive(Debug, Clone)]
+pub struct SinkMessage {
+    pub topic: String,
+    /// The event's idempotency key
+    pub key: String,
+    /// `None` for retractions
+    pub payload: Option<Vec<u8>>,
+    pub headers: HashMap<String, String>,
+}
+
+/// Downstream transport for indexed events.
+///
+/// A failed `publish` leaves the batch in the outbox and it is published again,
+/// so implementations must let consumers drop what was already delivered.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn publish(&self, messages: &[SinkMessage]) -> anyhow::Result<()>;
+}
+
+/// Build the configured sink; `transactional_id` is only used by Kafka
+pub async fn connect(
+    config: &SinkConfig,
+    kafka: ClientConfig,
+    transactional_id: &str,
+) -> anyhow::Result<Box<dyn EventSink>> {
+    Ok(match config {
+        SinkConfig::Kafka => Box::new(KafkaSink::new(kafka, transactional_id)?),
+        SinkConfig::Nats(nats) => Box::new(NatsSink::connect(nats).await?),
+    })
+}
+
+/// Publishes each batch in one Kafka transaction; consumers read `read_committed`
+/// and deduplicate on the message key
+pub struct KafkaSink {
+    producer: FutureProducer,
+}
+
+impl KafkaSink {
+    pub fn new(mut client_config: ClientConfig, transactional_id: &str) -> anyhow::Result<Self> {
+        let producer: FutureProducer = client_config
+            .set("transactional.id", transactional_id)
+            .set("enable.idempotence", "true")
+            .set("compression.codec", "zstd")
+            .create()?;
+        producer.init_transactions(TRANSACTION_TIMEOUT)?;
+        Ok(Self { producer })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn publish(&self, messages: &[SinkMessage]) -> anyhow::Result<()> {
+        self.producer.begin_transaction()?;
+
+        let deliveries = messages.iter().map(|message| {
+            let headers = message
+                .headers
+                .iter()
+                .fold(OwnedHeaders::new(), |h, (key, value)| h.insert(Header { key, value: Some(value) }));
+            let mut record = FutureRecord::<str, [u8]>::to(&message.topic)
+                .key(&message.key)
+                .headers(headers);
+            if let Some(payload) = &message.payload {
+                record = record.payload(payload);
+            }
+            self.producer.send(record, SEND_TIMEOUT)
+        });
+        let produced = join_all(deliveries)
+            .await
+            .into_iter()
+            .try_for_each(|delivery| delivery.map(|_| ()).map_err(|(e, _)| e));
+
+        match produced {
+            Ok(()) => self.producer.commit_transaction(TRANSACTION_TIMEOUT)?,
+            Err(e) => {
+                self.producer.abort_transaction(TRANSACTION_TIMEOUT)?;
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Publishes to a JetStream stream with the idempotency key as `Nats-Msg-Id`,
+/// so the server drops republished messages within the duplicate window
+pub struct NatsSink {
+    jetstream: jetstream::Context,
+    subject_prefix: String,
+}
+
+impl NatsSink {
+    pub async fn connect(config: &NatsConfig) -> anyhow::Result<Self> {
+        let mut options = async_nats::ConnectOptions::new().name("scoria-indexer");
+        if let Some(credentials) = &config.credentials {
+            options = options.credentials_file(credentials).await?;
+        }
+        let client = options.connect(config.url.as_str()).await?;
+        let jetstream = jetstream::new(client);
+
+        jetstream
+            .get_or_create_stream(stream::Config {
+                name: config.stream.clone(),
+                subjects: vec![format!("{}.>", config.subject_prefix)],
+                duplicate_window: Duration::from_secs(config.duplicate_window_secs),
+                max_age: Duration::from_secs(config.max_age_hours * 3600),
+                ..Default::default()
+            })
+            .await?;
+
+        info!(url = %config.url, stream = %config.stream, "Connected to NATS JetStream");
+        Ok(Self {
+            jetstream,
+            subject_prefix: config.subject_prefix.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    async fn publish(&self, messages: &[SinkMessage]) -> anyhow::Result<()> {
+        // Publish the whole batch, then wait for every ack
+        let mut acks = Vec::with_capacity(messages.len());
+        for message in messages {
+            let mut headers = HeaderMap::new();
+            for (key, value) in &message.headers {
+                headers.insert(key.as_str(), value.as_str());
+            }
+            headers.insert(NATS_MESSAGE_ID, message.key.as_str());
+            headers.insert(EVENT_KEY_HEADER, message.key.as_str());
+
+            let subject = format!("{}.{}", self.subject_prefix, message.topic);
+            let payload = message.payload.clone().unwrap_or_default();
+            acks.push(self.jetstream.publish_with_headers(subject, headers, payload.into()).await?);
+        }
+
+        let duplicates = try_join_all(acks).await?.iter().filter(|ack| ack.duplicate).count();
+        if duplicates > 0 {
+            metrics::counter!("nats_duplicates_total", duplicates as u64);
+        }
+        Ok(())
+    }
+}
@@ -1,43 +1,82 @@
 // indexer/src/solana_listener.rs
 
+use crate::{
+    events::EventDispatcher,
+    idl::EventDecoder,
+    outbox,
+    reorg::{FinalityConfig, FinalityTracker, SlotStatus, FINALITY_POLL_INTERVAL},
+    telemetry,
+};
 use solana_client::{
-    nonblocking::websocket::WebSocketRpcClient,
+    nonblocking::{rpc_client::RpcClient, websocket::WebSocketRpcClient},
     rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
 };
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::{clock::Slot, commitment_config::CommitmentConfig};
+use std::{collections::HashMap, path::PathBuf};
 use tokio::{
     sync::{mpsc, Mutex},
-    time::{sleep, Duration},
+    task::JoinHandle,
+    time::{interval, sleep, Duration},
 };
 use tracing::{error, info, instrument, warn};
 
-const RECONNECT_BACKOFF: [u64; 5] = [1, 2, 5, 10, 30]; // Seconds
-const MAX_RETRIES: usize = 10;
+pub(crate) const RECONNECT_BACKOFF: [u64; 5] = [1, 2, 5, 10, 30]; // Seconds
+pub(crate) const MAX_RETRIES: usize = 10;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventListenerConfig {
+    pub ws_endpoint: String,
+    pub program_id: Pubkey,
+    pub governance_program_id: Pubkey,
+    pub kafka_topic: String,
+    #[serde(default)]
+    pub finality: FinalityConfig,
+    /// IDLs that events are decoded with, as written by `anchor build`
+    #[serde(default = "default_registry_idl")]
+    pub registry_idl: PathBuf,
+    #[serde(default = "default_governance_idl")]
+    pub governance_idl: PathBuf,
+}
+
+fn default_registry_idl() -> PathBuf {
+    PathBuf::from("target/idl/model_registry.json")
+}
+
+fn default_governance_idl() -> PathBuf {
+    PathBuf::from("target/idl/dao.json")
+}
 
 #[derive(Clone)]
 pub struct SolanaEventListener {
     ws_client: Arc<Mutex<Option<WebSocketRpcClient>>>,
     config: Arc<EventListenerConfig>,
     db_pool: PgPool,
-    kafka_producer: FutureProducer,
+    finality: Arc<FinalityTracker>,
+    decoder: Arc<EventDecoder>,
+    dispatcher: Arc<EventDispatcher>,
 }
 
 impl SolanaEventListener {
-    pub async fn new(
-        config: EventListenerConfig,
-        db_pool: PgPool,
-        kafka_producer: FutureProducer,
-    ) -> Self {
-        Self {
+    /// `rpc_client` backs finality tracking; events arrive over `ws_endpoint`
+    pub async fn new(config: EventListenerConfig, rpc_client: Arc<RpcClient>, db_pool: PgPool) -> anyhow::Result<Self> {
+        let decoder = EventDecoder::new()
+            .load(config.program_id, &config.registry_idl)?
+            .load(config.governance_program_id, &config.governance_idl)?;
+
+        Ok(Self {
             ws_client: Arc::new(Mutex::new(None)),
             config: Arc::new(config),
             db_pool,
-            kafka_producer,
-        }
+            finality: Arc::new(FinalityTracker::new(rpc_client)),
+            decoder: Arc::new(decoder),
+            dispatcher: Arc::new(EventDispatcher::with_program_events()),
+        })
     }
 
     #[instrument(skip_all)]
     pub async fn run(&self, shutdown: mpsc::Sender<()>) -> anyhow::Result<()> {
+        let finality_task = self.spawn_finality_tracker(shutdown.clone());
+
         let mut retry_count = 0;
         loop {
             match self.connect().await {
@@ -49,6 +88,7 @@ impl SolanaEventListener {
                 }
                 Err(e) => {
                     retry_count += 1;
+                    metrics::increment_counter!("ws_reconnects_total");
                     if retry_count > MAX_RETRIES {
                         error!("Max connection retries exceeded");
                         shutdown.send(()).await?;
@@ -61,9 +101,20 @@ impl SolanaEventListener {
                 }
             }
         }
+        finality_task.abort();
         Ok(())
     }
 
+    pub(crate) fn config(&self) -> &EventListenerConfig {
+        &self.config
+    }
+
+    /// Background task resolving provisional slots; every ingestion path runs one
+    pub(crate) fn spawn_finality_tracker(&self, shutdown: mpsc::Sender<()>) -> JoinHandle<anyhow::Result<()>> {
+        let listener = self.clone();
+        tokio::spawn(async move { listener.track_finality(shutdown).await })
+    }
+
     async fn connect(&self) -> anyhow::Result<WebSocketRpcClient> {
         let client = WebSocketRpcClient::new_with_commitment(
             &self.config.ws_endpoint,
@@ -71,16 +122,21 @@ impl SolanaEventListener {
         )
         .await?;
 
-        let filter = RpcTransactionLogsFilter::Mentions(vec![self.config.program_id.to_string()]);
-        let config = RpcTransactionLogsConfig {
-            commitment: Some(CommitmentConfig::confirmed()),
-            ..Default::default()
-        };
+        // `Mentions` accepts a single address, so each program gets its own
+        // subscription; both feed the connection's notification stream, and
+        // a transaction touching both is deduplicated by `handle_event`
+        for program_id in [self.config.program_id, self.config.governance_program_id] {
+            let filter = RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]);
+            let config = RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+                ..Default::default()
+            };
 
-        client
-            .logs_subscribe(filter, config)
-            .await
-            .context("Failed to subscribe to logs")?;
+            client
+                .logs_subscribe(filter, config)
+                .await
+                .with_context(|| format!("Failed to subscribe to logs for {program_id}"))?;
+        }
 
         info!("WebSocket connected successfully");
         Ok(client)
@@ -91,59 +147,304 @@ impl SolanaEventListener {
         let mut stream = client.logs_notifications().await?;
         
         while let Some(notification) = stream.next().await {
-            let logs = notification?.value.logs.join("\n");
-            
-            let event = match parse_logs(&logs) {
-                Some(e) => e,
-                None => continue,
-            };
+            let notification = notification?;
+            // A failed transaction's state was rolled back, whatever it logged
+            if notification.value.err.is_some() {
+                continue;
+            }
 
-            self.handle_event(event).await?;
+            let events = self.parse_logs(
+                &notification.value.signature,
+                notification.context.slot as i64,
+                &notification.value.logs,
+            );
+            for event in events {
+                self.handle_event(event).await?;
+            }
         }
 
         Ok(())
     }
 
-    async fn handle_event(&self, event: ProgramEvent) -> anyhow::Result<()> {
+    /// Persist, materialize and queue for Kafka a decoded program event.
+    ///
+    /// Shared by every ingestion path and the backfill command. The event's
+    /// idempotency key is claimed in `processed_events` in the same transaction
+    /// as its rows and outbox message, so a replay is a no-op.
+    #[instrument(
+        name = "indexer.handle_event",
+        skip_all,
+        fields(key = %event.idempotency_key(), slot = event.slot, model = ?event.inner.model_id())
+    )]
+    pub(crate) async fn handle_event(&self, event: ProgramEvent) -> anyhow::Result<()> {
         // Database transaction
         let mut tx = self.db_pool.begin().await?;
 
-        // Store raw event
+        let claimed = sqlx::query!(
+            r#"INSERT INTO processed_events (signature, instruction_index, event_index, slot)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT DO NOTHING
+               RETURNING slot"#,
+            event.signature,
+            event.instruction_index as i32,
+            event.event_index as i32,
+            event.slot
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if claimed.is_none() {
+            metrics::increment_counter!("events_duplicate_total", "type" => event.event_type());
+            return Ok(());
+        }
+
+        // Store raw event; rows stay provisional until the slot is finalized
         sqlx::query!(
-            r#"INSERT INTO events (signature, slot, data) VALUES (\$1, \$2, \$3)"#,
+            r#"INSERT INTO events
+                   (signature, instruction_index, event_index, slot, model_id, data, provisional, slot_status)
+               VALUES ($1, $2, $3, $4, $5, $6, TRUE, $7)"#,
             event.signature,
+            event.instruction_index as i32,
+            event.event_index as i32,
             event.slot,
-            serde_json::to_value(&event)?
+            event.inner.model_id(),
+            serde_json::to_value(&event)?,
+            SlotStatus::Confirmed.as_str()
         )
         .execute(&mut *tx)
         .await?;
 
-        // Process event type
-        match event.inner {
+        // Tables configured for `finalized` commitment are populated later
+        if !self.config.finality.requires_finality(event.inner.target_table()) {
+            self.apply_event(&mut tx, event.inner.clone()).await?;
+        }
+
+        // Published by the outbox relay once this commits
+        outbox::enqueue(
+            &mut tx,
+            &self.config.kafka_topic,
+            &event.idempotency_key(),
+            Some(&serde_json::to_vec(&event)?),
+            &telemetry::current_context(),
+        )
+        .await?;
+
+        // Commit transaction
+        tx.commit().await?;
+
+        metrics::increment_counter!("events_processed_total", "type" => event.event_type());
+        Ok(())
+    }
+
+    async fn apply_event(
+        &self,
+        tx: &mut PgConnection,
+        event: ProgramEventType,
+    ) -> anyhow::Result<()> {
+        match event {
             ProgramEventType::ModelRegistered(model) => {
-                self.handle_model_registration(&mut tx, model).await?;
+                self.handle_model_registration(tx, model).await?;
             }
             ProgramEventType::ModelUpdated(update) => {
-                self.handle_model_update(&mut tx, update).await?;
+                self.handle_model_update(tx, update).await?;
             }
             ProgramEventType::ModelDeleted(deletion) => {
-                self.handle_model_deletion(&mut tx, deletion).await?;
+                self.handle_model_deletion(tx, deletion).await?;
+            }
+            ProgramEventType::ModelExpired(expiry) => {
+                self.handle_model_expiry(tx, expiry).await?;
+            }
+            ProgramEventType::ModelForked(fork) => {
+                self.handle_model_fork(tx, fork).await?;
+            }
+            ProgramEventType::ModelMetadataSet(metadata) => {
+                self.handle_metadata_update(tx, metadata).await?;
+            }
+            ProgramEventType::VersionUpdated(update) => {
+                self.handle_version_update(tx, update).await?;
+            }
+            ProgramEventType::InferenceFulfilled(fulfillment) => {
+                self.handle_inference_fulfillment(tx, fulfillment).await?;
+            }
+            ProgramEventType::InferenceChallenged(challenge) => {
+                self.handle_inference_challenge(tx, challenge).await?;
+            }
+            ProgramEventType::InferenceFinalized(finalized) => {
+                self.handle_inference_finalization(tx, finalized).await?;
+            }
+            ProgramEventType::ConsentChanged(change) => {
+                self.handle_consent_change(tx, change).await?;
+            }
+            ProgramEventType::ContributionRevoked(revocation) => {
+                self.handle_contribution_revocation(tx, revocation).await?;
+            }
+            // Only materialized by the Kafka projections
+            ProgramEventType::InferenceRequested(_)
+            | ProgramEventType::DataContributed(_)
+            | ProgramEventType::ProposalCreated(_)
+            | ProgramEventType::VoteCast(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Resolve provisional slots until shutdown. Errors are logged and the
+    /// slot retried on the next tick: nothing awaits this task, so returning
+    /// early would silently leave every later row provisional.
+    #[instrument(skip_all)]
+    async fn track_finality(&self, shutdown: mpsc::Sender<()>) -> anyhow::Result<()> {
+        let mut ticker = interval(FINALITY_POLL_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+            if shutdown.is_closed() {
+                break;
+            }
+
+            let provisional: Vec<Slot> = match sqlx::query_scalar!(
+                r#"SELECT DISTINCT slot AS "slot!" FROM events WHERE provisional ORDER BY 1"#
+            )
+            .fetch_all(&self.db_pool)
+            .await
+            {
+                Ok(slots) => slots.into_iter().map(|slot: i64| slot as Slot).collect(),
+                Err(e) => {
+                    warn!(error = %e, "Provisional slot query failed");
+                    continue;
+                }
+            };
+
+            if provisional.is_empty() {
+                continue;
+            }
+
+            let update = match self.finality.poll(&provisional).await {
+                Ok(update) => update,
+                Err(e) => {
+                    warn!(error = %e, "Finality poll failed");
+                    continue;
+                }
+            };
+
+            for slot in update.finalized {
+                if let Err(e) = self.finalize_slot(slot).await {
+                    warn!(slot, error = %e, "Finalizing slot failed; will retry");
+                }
+            }
+            for slot in update.dropped {
+                if let Err(e) = self.rollback_slot(slot).await {
+                    warn!(slot, error = %e, "Rolling back slot failed; will retry");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Promote a slot's events and materialize finality-gated tables
+    async fn finalize_slot(&self, slot: Slot) -> anyhow::Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let rows = sqlx::query!(
+            r#"UPDATE events
+               SET provisional = FALSE, slot_status = $2, finalized_at = NOW()
+               WHERE slot = $1 AND provisional
+               RETURNING data"#,
+            slot as i64,
+            SlotStatus::Finalized.as_str()
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for row in rows {
+            let event: ProgramEvent = serde_json::from_value(row.data)?;
+            if self.config.finality.requires_finality(event.inner.target_table()) {
+                self.apply_event(&mut tx, event.inner).await?;
             }
         }
 
-        // Commit transaction
         tx.commit().await?;
+        Ok(())
+    }
 
-        // Publish to Kafka
-        let record = FutureRecord::to(&self.config.kafka_topic)
-            .payload(&serde_json::to_vec(&event)?)
-            .key(&event.signature);
-        
-        self.kafka_producer
-            .send(record, Duration::from_secs(30))
-            .await??;
+    /// Delete events from a dropped slot and rebuild the models they touched
+    async fn rollback_slot(&self, slot: Slot) -> anyhow::Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let rows = sqlx::query!(
+            r#"DELETE FROM events WHERE slot = $1 AND provisional
+               RETURNING signature, instruction_index, event_index, model_id"#,
+            slot as i64
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // A transaction from the dropped fork may land again in a later slot
+        sqlx::query!("DELETE FROM processed_events WHERE slot = $1", slot as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut affected: Vec<String> = rows.iter().filter_map(|r| r.model_id.clone()).collect();
+        affected.sort();
+        affected.dedup();
+
+        for model_id in &affected {
+            self.rebuild_model(&mut tx, model_id).await?;
+        }
+
+        // Tell downstream consumers to retract what they already received
+        let retracted = HashMap::from([("retracted".to_string(), "true".to_string())]);
+        for row in &rows {
+            let key = idempotency_key(&row.signature, row.instruction_index as u32, row.event_index as u32);
+            outbox::enqueue(&mut tx, &self.config.kafka_topic, &key, None, &retracted).await?;
+        }
+
+        tx.commit().await?;
+
+        warn!(slot, events = rows.len(), models = affected.len(), "Rolled back dropped slot");
+        metrics::counter!("events_rolled_back_total", rows.len() as u64);
+        Ok(())
+    }
+
+    /// Recompute derived rows for a model from its surviving events
+    async fn rebuild_model(&self, tx: &mut PgConnection, model_id: &str) -> anyhow::Result<()> {
+        sqlx::query!("DELETE FROM model_permissions WHERE model_id = $1", model_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM inferences WHERE model_id = $1", model_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM models WHERE id = $1", model_id)
+            .execute(&mut *tx)
+            .await?;
+        // Deleted blobs stay recorded; the replay restores the pending ones
+        sqlx::query!(
+            "DELETE FROM contribution_retention WHERE model_id = $1 AND deleted_at IS NULL",
+            model_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM contribution_erasures WHERE model_id = $1 AND purged_at IS NULL",
+            model_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let rows = sqlx::query!(
+            r#"SELECT data, provisional FROM events WHERE model_id = $1 ORDER BY slot, id"#,
+            model_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for row in rows {
+            let event: ProgramEvent = serde_json::from_value(row.data)?;
+            let table = event.inner.target_table();
+            if !row.provisional || !self.config.finality.requires_finality(table) {
+                self.apply_event(tx, event.inner).await?;
+            }
+        }
 
-        metrics::increment_counter!("events_processed_total", "type" => event.event_type());
         Ok(())
     }
 
@@ -154,12 +455,14 @@ impl SolanaEventListener {
     ) -> anyhow::Result<()> {
         // Insert into registry
         sqlx::query!(
-            r#"INSERT INTO models (id, owner, metadata, created_at)
-               VALUES (\$1, \$2, \$3, NOW())
+            r#"INSERT INTO models (id, owner, metadata, inference_fee, tags, created_at)
+               VALUES (\$1, \$2, \$3, \$4,
+                       ARRAY(SELECT jsonb_array_elements_text(COALESCE(\$3::jsonb->'tags', '[]'))), NOW())
                ON CONFLICT (id) DO NOTHING"#,
             model.id,
             model.owner,
-            model.metadata
+            model.metadata,
+            model.inference_fee as i64
         )
         .execute(&mut *tx)
         .await?;
@@ -182,12 +485,460 @@ impl SolanaEventListener {
         Ok(())
     }
 
+    /// Register a fork with its lineage; `/models/{id}/forks` walks `parent_id`
+    async fn handle_model_fork(
+        &self,
+        tx: &mut PgConnection,
+        fork: ModelFork,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"INSERT INTO models (id, owner, parent_id, upstream_royalty_bps, inference_fee, created_at)
+               VALUES ($1, $2, $3, $4, $5, to_timestamp($6))
+               ON CONFLICT (id) DO UPDATE
+               SET parent_id = EXCLUDED.parent_id,
+                   upstream_royalty_bps = EXCLUDED.upstream_royalty_bps"#,
+            fork.model_id,
+            fork.owner,
+            fork.parent_id,
+            fork.royalty_bps as i32,
+            fork.inference_fee as i64,
+            fork.timestamp as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        metrics::increment_counter!("models_forked_total");
+        Ok(())
+    }
+
+    /// Point a model at a new catalog document; `catalog::spawn_fetcher` parses it
+    async fn handle_metadata_update(
+        &self,
+        tx: &mut PgConnection,
+        metadata: MetadataUpdate,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"UPDATE models
+               SET metadata_uri = $2, catalog_error = NULL, catalog_attempts = 0
+               WHERE id = $1"#,
+            metadata.model_id,
+            metadata.metadata_uri
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // A cleared URI drops the parsed columns at once; nothing is left to fetch
+        if metadata.metadata_uri.is_empty() {
+            sqlx::query!(
+                r#"UPDATE models
+                   SET name = NULL, description = NULL, license = NULL, framework = NULL,
+                       input_shape = NULL, catalog_uri = ''
+                   WHERE id = $1"#,
+                metadata.model_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Record the version a passed upgrade proposal activated
+    async fn handle_version_update(
+        &self,
+        tx: &mut PgConnection,
+        update: VersionUpdate,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            "UPDATE models SET active_version = $2 WHERE id = $1",
+            update.model_id,
+            update.version as i64
+        )
+        .execute(&mut *tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a model deregistered after its storage lapsed
+    async fn handle_model_expiry(
+        &self,
+        tx: &mut PgConnection,
+        expiry: ModelExpiry,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"UPDATE models
+               SET status = 'expired', expired_at = to_timestamp($2), reclaimed_by = $3
+               WHERE id = $1"#,
+            expiry.model_id,
+            expiry.expired_at as f64,
+            expiry.reclaimed_by
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        metrics::increment_counter!("models_expired_total");
+        Ok(())
+    }
+
+    /// Record a settled inference request and its payout
+    async fn handle_inference_fulfillment(
+        &self,
+        tx: &mut PgConnection,
+        fulfillment: InferenceFulfillment,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"INSERT INTO inferences
+                   (request_id, model_id, provider, status, output_hash, error_code, payee, fee, fee_mint,
+                    fulfilled_at, challenge_deadline)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, to_timestamp($10), to_timestamp($11))
+               ON CONFLICT (request_id) DO UPDATE
+               SET provider = EXCLUDED.provider,
+                   status = EXCLUDED.status,
+                   output_hash = EXCLUDED.output_hash,
+                   error_code = EXCLUDED.error_code,
+                   payee = EXCLUDED.payee,
+                   fulfilled_at = EXCLUDED.fulfilled_at,
+                   challenge_deadline = EXCLUDED.challenge_deadline"#,
+            fulfillment.request_id,
+            fulfillment.model_id,
+            fulfillment.provider,
+            fulfillment.status,
+            fulfillment.output_hash,
+            fulfillment.error_code as i64,
+            fulfillment.payee,
+            fulfillment.fee as i64,
+            fulfillment.fee_mint,
+            fulfillment.timestamp as f64,
+            fulfillment.challenge_deadline as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        metrics::increment_counter!("inferences_fulfilled_total", "status" => fulfillment.status.clone());
+        Ok(())
+    }
+
+    /// A fraud proof succeeded: the provider was slashed and the requester refunded
+    async fn handle_inference_challenge(
+        &self,
+        tx: &mut PgConnection,
+        challenge: InferenceChallenge,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"UPDATE inferences
+               SET status = 'challenged', challenger = $2, slashed = $3, finalized_at = to_timestamp($4)
+               WHERE request_id = $1"#,
+            challenge.request_id,
+            challenge.challenger,
+            challenge.slashed as i64,
+            challenge.timestamp as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        metrics::increment_counter!("inferences_challenged_total");
+        Ok(())
+    }
+
+    /// Challenge window closed unchallenged; the provider was paid
+    async fn handle_inference_finalization(
+        &self,
+        tx: &mut PgConnection,
+        finalized: InferenceFinalization,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"UPDATE inferences SET status = 'completed', finalized_at = to_timestamp($2)
+               WHERE request_id = $1"#,
+            finalized.request_id,
+            finalized.timestamp as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Schedule deletion of the contribution's blob; `retention::spawn_enforcer`
+    /// deletes it once `expires_at` passes. Withdrawal only ever moves it earlier.
+    async fn handle_consent_change(
+        &self,
+        tx: &mut PgConnection,
+        change: ConsentChange,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"INSERT INTO contribution_retention (model_id, data_hash, contributor, expires_at, withdrawn)
+               VALUES ($1, $2, $3, to_timestamp($4), $5)
+               ON CONFLICT (model_id, data_hash) DO UPDATE
+               SET expires_at = LEAST(contribution_retention.expires_at, EXCLUDED.expires_at),
+                   withdrawn = contribution_retention.withdrawn OR EXCLUDED.withdrawn"#,
+            change.model_id,
+            change.data_hash,
+            change.contributor,
+            change.expires_at as f64,
+            change.withdrawn
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if change.withdrawn {
+            metrics::increment_counter!("consents_withdrawn_total");
+        }
+        Ok(())
+    }
+
+    /// Log the erasure request and schedule the blob for immediate deletion
+    async fn handle_contribution_revocation(
+        &self,
+        tx: &mut PgConnection,
+        revocation: ContributionRevocation,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"INSERT INTO contribution_erasures (model_id, data_hash, contributor, revoked_at)
+               VALUES ($1, $2, $3, to_timestamp($4))
+               ON CONFLICT (model_id, data_hash) DO NOTHING"#,
+            revocation.model_id,
+            revocation.data_hash,
+            revocation.contributor,
+            revocation.revoked_at as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        self.handle_consent_change(
+            tx,
+            ConsentChange {
+                model_id: revocation.model_id,
+                contributor: revocation.contributor,
+                data_hash: revocation.data_hash,
+                expires_at: revocation.revoked_at,
+                withdrawn: true,
+            },
+        )
+        .await?;
+
+        metrics::increment_counter!("contributions_revoked_total");
+        Ok(())
+    }
+
     // Additional handlers for updates/deletions...
 }
 
-// Event parsing implementation
-fn parse_logs(logs: &str) -> Option<ProgramEvent> {
-    // Custom parsing logic matching program IDL
+impl ProgramEventType {
+    /// Derived table populated by this event
+    fn target_table(&self) -> &'static str {
+        match self {
+            Self::ModelRegistered(_)
+            | Self::ModelUpdated(_)
+            | Self::ModelDeleted(_)
+            | Self::ModelExpired(_)
+            | Self::ModelForked(_)
+            | Self::ModelMetadataSet(_)
+            | Self::VersionUpdated(_) => "models",
+            Self::InferenceFulfilled(_)
+            | Self::InferenceChallenged(_)
+            | Self::InferenceFinalized(_) => "inferences",
+            Self::ConsentChanged(_) => "contribution_retention",
+            Self::ContributionRevoked(_) => "contribution_erasures",
+            Self::InferenceRequested(_)
+            | Self::DataContributed(_)
+            | Self::ProposalCreated(_)
+            | Self::VoteCast(_) => "projections",
+        }
+    }
+
+    fn model_id(&self) -> Option<String> {
+        match self {
+            Self::ModelRegistered(model) => Some(model.id.to_string()),
+            Self::ModelUpdated(update) => Some(update.model_id.to_string()),
+            Self::ModelDeleted(deletion) => Some(deletion.model_id.to_string()),
+            Self::ModelExpired(expiry) => Some(expiry.model_id.to_string()),
+            Self::ModelForked(fork) => Some(fork.model_id.to_string()),
+            Self::ModelMetadataSet(metadata) => Some(metadata.model_id.to_string()),
+            Self::VersionUpdated(update) => Some(update.model_id.to_string()),
+            Self::InferenceRequested(request) => Some(request.model_id.to_string()),
+            Self::InferenceFulfilled(fulfillment) => Some(fulfillment.model_id.to_string()),
+            Self::InferenceChallenged(challenge) => Some(challenge.model_id.to_string()),
+            Self::InferenceFinalized(finalized) => Some(finalized.model_id.to_string()),
+            Self::DataContributed(contribution) => Some(contribution.model_id.to_string()),
+            Self::ConsentChanged(change) => Some(change.model_id.to_string()),
+            Self::ContributionRevoked(revocation) => Some(revocation.model_id.to_string()),
+            Self::ProposalCreated(_) | Self::VoteCast(_) => None,
+        }
+    }
+}
+
+/// `ModelExpired` emitted by `reclaim_expired`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelExpiry {
+    pub model_id: String,
+    pub owner: String,
+    pub expired_at: i64,
+    pub reclaimed_at: i64,
+    pub reclaimed_by: String,
+}
+
+/// `ModelForked` emitted by `fork_model`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelFork {
+    pub model_id: String,
+    pub parent_id: String,
+    pub owner: String,
+    pub royalty_bps: u16,
+    pub inference_fee: u64,
+    pub timestamp: i64,
+}
+
+/// `ModelStateChanged` with `ModelField::MetadataUri`, emitted by `set_metadata_uri`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataUpdate {
+    pub model_id: String,
+    /// `ipfs://` URI of the catalog document; empty when cleared
+    pub metadata_uri: String,
+    pub changed_by: String,
+    pub timestamp: i64,
+}
+
+/// `VersionUpdated` emitted by `execute_update`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionUpdate {
+    pub model_id: String,
+    pub version: u64,
+    pub timestamp: i64,
+}
+
+/// `InferenceRequested` emitted by `request_inference`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceRequest {
+    pub request_id: String,
+    pub model_id: String,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+    pub timestamp: i64,
+}
+
+/// `InferenceFulfilled` emitted by `fulfill_inference`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceFulfillment {
+    pub request_id: String,
+    pub model_id: String,
+    pub provider: String,
+    /// "completed", "failed" or "fulfilled" (optimistic, challengeable until the deadline)
+    pub status: String,
+    pub output_hash: String,
+    pub error_code: u32,
+    pub payee: String,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+    pub challenge_deadline: i64,
+    pub timestamp: i64,
+}
+
+/// `InferenceChallenged` emitted by `challenge_inference`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceChallenge {
+    pub request_id: String,
+    pub model_id: String,
+    pub provider: String,
+    pub challenger: String,
+    pub slashed: u64,
+    pub challenger_reward: u64,
+    pub timestamp: i64,
+}
+
+/// `InferenceFinalized` emitted by `finalize_inference`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceFinalization {
+    pub request_id: String,
+    pub model_id: String,
+    pub provider: String,
+    pub fee: u64,
+    pub fee_mint: Option<String>,
+    pub timestamp: i64,
+}
+
+/// `DataContributed` emitted by `contribute_data`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataContribution {
+    pub model_id: String,
+    pub contributor: String,
+    pub data_hash: String,
+}
+
+/// `ConsentRecorded` or `ConsentWithdrawn`, emitted by `record_consent` and `withdraw_consent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentChange {
+    pub model_id: String,
+    pub contributor: String,
+    pub data_hash: String,
+    /// When the encrypted blob must be gone
+    pub expires_at: i64,
+    pub withdrawn: bool,
+}
+
+/// `ContributionRevoked` emitted by `revoke_contribution`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributionRevocation {
+    pub model_id: String,
+    pub contributor: String,
+    pub data_hash: String,
+    pub revoked_at: i64,
+}
+
+/// `ProposalCreated` emitted by the governance program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalCreation {
+    pub proposal_id: String,
+    pub author: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub created_at: i64,
+}
+
+/// `VoteCast` emitted by the governance program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vote {
+    pub proposal_id: String,
+    pub voter: String,
+    pub delegate: Option<String>,
+    pub choice: u8,
+    pub weight: u64,
+    pub effective_weight: u64,
+    pub cast_at: i64,
 }
 
-// Metrics and health checks...
+impl SolanaEventListener {
+    /// Decode every stored event in a transaction's logs
+    pub(crate) fn parse_logs(&self, signature: &str, slot: i64, logs: &[String]) -> Vec<ProgramEvent> {
+        self.decoder
+            .decode_logs(logs)
+            .into_iter()
+            .filter_map(|decoded| {
+                let (instruction_index, event_index) = (decoded.instruction_index, decoded.event_index);
+                let name = decoded.name.clone();
+                let inner = self.dispatcher.dispatch(decoded).unwrap_or_else(|e| {
+                    metrics::increment_counter!("event_decode_failures_total");
+                    warn!(signature, event = %name, error = %e, "Event does not match its typed definition");
+                    None
+                })?;
+                Some(ProgramEvent {
+                    signature: signature.to_string(),
+                    instruction_index,
+                    event_index,
+                    slot,
+                    inner,
+                })
+            })
+            .collect()
+    }
+}
+
+impl ProgramEvent {
+    /// Identifies the event across reconnects, backfills and the Kafka topic
+    pub fn idempotency_key(&self) -> String {
+        idempotency_key(&self.signature, self.instruction_index, self.event_index)
+    }
+}
+
+/// `<signature>:<instruction index>:<event index>`; one instruction can emit several events
+pub(crate) fn idempotency_key(signature: &str, instruction_index: u32, event_index: u32) -> String {
+    format!("{signature}:{instruction_index}:{event_index}")
+}
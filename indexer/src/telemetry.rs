@@ -0,0 +1,100 @@
+// indexer/src/telemetry.rs
+
+use rdkafka::message::BorrowedHeaders;
+use std::collections::HashMap;
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+const SERVICE_NAME: &str = "scoria-indexer";
+
+/// Flushes buffered spans on drop; hold it for the life of `main`
+pub struct TelemetryGuard {
+    #[cfg_attr(not(feature = "telemetry"), allow(dead_code))]
+    exporting: bool,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "telemetry")]
+        if self.exporting {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Install the JSON subscriber, exporting spans over OTLP when `otlp_endpoint` is set
+pub fn init(otlp_endpoint: Option<&str>) -> anyhow::Result<TelemetryGuard> {
+    let registry = tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(fmt::layer().json());
+
+    #[cfg(feature = "telemetry")]
+    if let Some(endpoint) = otlp_endpoint {
+        registry.with(otlp_layer(endpoint)?).init();
+        tracing::info!(endpoint, "Exporting traces over OTLP");
+        return Ok(TelemetryGuard { exporting: true });
+    }
+
+    registry.init();
+    if otlp_endpoint.is_some() && !cfg!(feature = "telemetry") {
+        tracing::warn!("monitoring.otlp_endpoint is set but this build lacks the `telemetry` feature");
+    }
+    Ok(TelemetryGuard { exporting: false })
+}
+
+#[cfg(feature = "telemetry")]
+fn otlp_layer<S>(endpoint: &str) -> anyhow::Result<impl tracing_subscriber::Layer<S>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace, Resource};
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new("service.name", SERVICE_NAME)])),
+        )
+        .install_batch(runtime::Tokio)?;
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// The current span's `traceparent`, as Kafka headers for an outbox message
+#[cfg(feature = "telemetry")]
+pub fn current_context() -> HashMap<String, String> {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let mut carrier = HashMap::new();
+    let cx = tracing::Span::current().context();
+    opentelemetry::global::get_text_map_propagator(|p| p.inject_context(&cx, &mut carrier));
+    carrier
+}
+
+#[cfg(not(feature = "telemetry"))]
+pub fn current_context() -> HashMap<String, String> {
+    HashMap::new()
+}
+
+/// Parent the current span on the producer's span carried in Kafka headers
+#[cfg(feature = "telemetry")]
+pub fn link_to_producer(headers: Option<&BorrowedHeaders>) {
+    use rdkafka::message::Headers;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let carrier: HashMap<String, String> = headers
+        .map(|headers| {
+            headers
+                .iter()
+                .filter_map(|h| Some((h.key.to_string(), std::str::from_utf8(h.value?).ok()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    let cx = opentelemetry::global::get_text_map_propagator(|p| p.extract(&carrier));
+    tracing::Span::current().set_parent(cx);
+}
+
+#[cfg(not(feature = "telemetry"))]
+pub fn link_to_producer(_headers: Option<&BorrowedHeaders>) {}
@@ -0,0 +1,103 @@
+// indexer/src/vrf.rs
+
+//! ECVRF-EDWARDS25519-SHA512-TAI (RFC 9381) over a node's Ed25519 key, used to
+//! draw aggregators for a federated round. The registry program verifies the
+//! same proofs on-chain with the curve25519 syscalls.
+
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_POINT,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+use sha2::{Digest, Sha512};
+
+const SUITE: u8 = 0x03;
+/// `Gamma || c || s`
+pub const PROOF_LEN: usize = 32 + 16 + 32;
+
+pub type Proof = [u8; PROOF_LEN];
+pub type Output = [u8; 64];
+
+/// Prove `alpha` with the key whose RFC 8032 seed is `secret`; returns the
+/// proof and its output
+pub fn prove(secret: &[u8; 32], alpha: &[u8]) -> (Proof, Output) {
+    let expanded = Sha512::digest(secret);
+    let mut scalar_bytes: [u8; 32] = expanded[..32].try_into().unwrap();
+    scalar_bytes[0] &= 248;
+    scalar_bytes[31] &= 127;
+    scalar_bytes[31] |= 64;
+    let x = Scalar::from_bytes_mod_order(scalar_bytes);
+    let public = (ED25519_BASEPOINT_POINT * x).compress();
+
+    let h = encode_to_curve(&public, alpha).expect("try-and-increment found no point");
+    let h_bytes = h.compress();
+    let gamma = h * x;
+    let nonce = Sha512::new().chain_update(&expanded[32..]).chain_update(h_bytes.as_bytes()).finalize();
+    let k = Scalar::from_bytes_mod_order_wide(&nonce.into());
+    let c = challenge(&public, &h_bytes, &gamma.compress(), &(ED25519_BASEPOINT_POINT * k).compress(), &(h * k).compress());
+    let s = k + challenge_scalar(&c) * x;
+
+    let mut proof = [0u8; PROOF_LEN];
+    proof[..32].copy_from_slice(gamma.compress().as_bytes());
+    proof[32..48].copy_from_slice(&c);
+    proof[48..].copy_from_slice(s.as_bytes());
+    (proof, proof_to_hash(&gamma))
+}
+
+/// The proof's output if it is valid for `public` and `alpha`
+pub fn verify(public: &[u8; 32], alpha: &[u8], proof: &Proof) -> Option<Output> {
+    let public = CompressedEdwardsY(*public);
+    let y = public.decompress().filter(|y| !y.is_small_order())?;
+    let gamma = CompressedEdwardsY(proof[..32].try_into().unwrap()).decompress()?;
+    let c: [u8; 16] = proof[32..48].try_into().unwrap();
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(proof[48..].try_into().unwrap()))?;
+
+    let h = encode_to_curve(&public, alpha)?;
+    let c_scalar = challenge_scalar(&c);
+    let u = ED25519_BASEPOINT_POINT * s - y * c_scalar;
+    let v = h * s - gamma * c_scalar;
+    let expected = challenge(&public, &h.compress(), &gamma.compress(), &u.compress(), &v.compress());
+    (expected == c).then(|| proof_to_hash(&gamma))
+}
+
+fn encode_to_curve(public: &CompressedEdwardsY, alpha: &[u8]) -> Option<EdwardsPoint> {
+    (0..=u8::MAX).find_map(|ctr| {
+        let hash = Sha512::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public.as_bytes())
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+        CompressedEdwardsY(hash[..32].try_into().unwrap()).decompress().map(|point| point.mul_by_cofactor())
+    })
+}
+
+fn challenge(
+    public: &CompressedEdwardsY,
+    h: &CompressedEdwardsY,
+    gamma: &CompressedEdwardsY,
+    u: &CompressedEdwardsY,
+    v: &CompressedEdwardsY,
+) -> [u8; 16] {
+    let mut hasher = Sha512::new().chain_update([SUITE, 0x02]);
+    for point in [public, h, gamma, u, v] {
+        hasher.update(point.as_bytes());
+    }
+    hasher.update([0x00]);
+    hasher.finalize()[..16].try_into().unwrap()
+}
+
+fn challenge_scalar(c: &[u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+fn proof_to_hash(gamma: &EdwardsPoint) -> Output {
+    Sha512::new()
+        .chain_update([SUITE, 0x03])
+        .chain_update(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain_update([0x00])
+        .finalize()
+        .into()
+}
@@ -0,0 +1,551 @@
+// indexer/src/zk/fl_proofs.rs
+
+//! Proof of training for federated updates (Groth16 over BN254).
+//!
+//! The circuit proves one DP-SGD step of a linear least-squares model: on a
+//! batch committed by Poseidon hash, starting from the committed global
+//! weights, each per-example gradient was clipped to `clip_norm` and the
+//! submitted update is `-learning_rate * mean(clipped) + noise` with
+//! `||noise|| <= noise_bound`. Noise stays private, so the proof bounds a
+//! node's influence exactly as the DP clipping does without revealing it.
+//!
+//! Values are fixed point with `FRAC_BITS` fractional bits. The native
+//! `TrainingStep` has the circuit's integer semantics, so honest updates
+//! always satisfy it.
+
+use anyhow::{ensure, Context};
+use ark_bn254::{Bn254, Fr, G1Projective};
+use ark_crypto_primitives::sponge::{
+    constraints::CryptographicSpongeVar,
+    poseidon::{constraints::PoseidonSpongeVar, find_poseidon_ark_and_mds, PoseidonConfig, PoseidonSponge},
+    CryptographicSponge,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_groth16::{Groth16, ProvingKey, VerifyingKey};
+use ark_r1cs_std::{alloc::AllocVar, boolean::Boolean, eq::EqGadget, fields::fp::FpVar, fields::FieldVar};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::CanonicalDeserialize;
+use ark_snark::SNARK;
+use ark_std::rand::{CryptoRng, RngCore};
+use rand::rngs::OsRng;
+use std::{path::Path, sync::OnceLock};
+
+pub const FRAC_BITS: u32 = 16;
+const ONE: i128 = 1 << FRAC_BITS;
+/// Signed range of weights, features, targets and gradients
+const VALUE_BITS: u32 = 40;
+/// Range of squared-norm slack
+const NORM_BITS: u32 = 100;
+/// Noise bound in standard deviations beyond `sqrt(dimension)`; exceeded
+/// with probability below 1e-8
+const NOISE_TAIL_SIGMAS: f64 = 6.0;
+
+const DATA_DOMAIN: u64 = 1;
+const MODEL_DOMAIN: u64 = 2;
+const UPDATE_DOMAIN: u64 = 3;
+
+/// Public inputs of a training proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statement {
+    pub data_commitment: Fr,
+    pub model_commitment: Fr,
+    pub update_commitment: Fr,
+    pub learning_rate: i64,
+    pub clip_norm: i64,
+    pub noise_bound: i64,
+}
+
+impl Statement {
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        vec![
+            self.data_commitment,
+            self.model_commitment,
+            self.update_commitment,
+            fr(self.learning_rate as i128),
+            fr(self.clip_norm as i128),
+            fr(self.noise_bound as i128),
+        ]
+    }
+}
+
+/// What every proof in a round must attest to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundStatement {
+    pub model_commitment: Fr,
+    pub learning_rate: i64,
+    pub clip_norm: i64,
+    pub noise_bound: i64,
+}
+
+impl RoundStatement {
+    /// Parameters of a round training from `weights`
+    pub fn new(weights: &[f32], learning_rate: f32, clip_norm: f32, noise_bound: f32) -> anyhow::Result<Self> {
+        Ok(Self {
+            model_commitment: model_commitment(weights)?,
+            learning_rate: to_fixed(learning_rate)?,
+            clip_norm: to_fixed(clip_norm)?,
+            noise_bound: to_fixed(noise_bound)?,
+        })
+    }
+
+    fn admits(&self, statement: &Statement) -> bool {
+        statement.model_commitment == self.model_commitment
+            && statement.learning_rate == self.learning_rate
+            && statement.clip_norm == self.clip_norm
+            && statement.noise_bound == self.noise_bound
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof {
+    pub proof: ark_groth16::Proof<Bn254>,
+    pub statement: Statement,
+}
+
+/// One DP-SGD step with every intermediate the circuit checks
+#[derive(Debug, Clone)]
+pub struct TrainingStep {
+    pub weights: Vec<i64>,
+    /// Row-major `[batch][dimension]`
+    pub features: Vec<i64>,
+    pub targets: Vec<i64>,
+    pub learning_rate: i64,
+    pub clip_norm: i64,
+    pub noise_bound: i64,
+    /// Per-example clip factors in `[0, 1]`
+    pub clip_scales: Vec<i64>,
+    pub noise: Vec<i64>,
+    pub update: Vec<i64>,
+}
+
+impl TrainingStep {
+    /// Run the step on `features` (`[batch][dimension]`) and `targets`. Noise
+    /// beyond `noise_bound` is scaled back onto it.
+    pub fn new(
+        weights: &[f32],
+        features: &[f32],
+        targets: &[f32],
+        learning_rate: f32,
+        clip_norm: f32,
+        noise: &[f32],
+        noise_bound: f32,
+    ) -> anyhow::Result<Self> {
+        let dimension = weights.len();
+        ensure!(dimension > 0 && !targets.is_empty(), "empty model or batch");
+        ensure!(features.len() == targets.len() * dimension, "features are not [batch][dimension]");
+        ensure!(noise.len() == dimension, "noise has {} values, expected {}", noise.len(), dimension);
+        ensure!(learning_rate > 0.0 && clip_norm > 0.0 && noise_bound >= 0.0, "step parameters must be positive");
+
+        let mut step = Self {
+            weights: to_fixed_vec(weights)?,
+            features: to_fixed_vec(features)?,
+            targets: to_fixed_vec(targets)?,
+            learning_rate: to_fixed(learning_rate)?,
+            clip_norm: to_fixed(clip_norm)?,
+            noise_bound: to_fixed(noise_bound)?,
+            clip_scales: Vec::new(),
+            noise: to_fixed_vec(noise)?,
+            update: Vec::new(),
+        };
+
+        // Scale noise onto the bound; shrink until the fixed-point norm fits
+        let bound_sq = (step.noise_bound as i128).pow(2);
+        while norm_sq(&step.noise) > bound_sq {
+            let shrink = (bound_sq as f64 / norm_sq(&step.noise) as f64).sqrt().min(0.999_999);
+            step.noise.iter_mut().for_each(|n| *n = (*n as f64 * shrink) as i64);
+        }
+
+        let clip_sq = (step.clip_norm as i128).pow(2);
+        let mut sums = vec![0i128; dimension];
+        for (x, y) in step.features.chunks(dimension).zip(&step.targets) {
+            let gradient = example_gradient(&step.weights, x, *y as i128);
+            // Largest scale in [0, 1] whose clipped gradient fits the bound
+            let norm = (norm_sq(&gradient) as f64).sqrt();
+            let mut scale = if norm <= step.clip_norm as f64 {
+                ONE
+            } else {
+                (ONE as f64 * step.clip_norm as f64 / norm) as i128
+            };
+            while norm_sq(&clip(&gradient, scale)) > clip_sq {
+                scale -= 1;
+            }
+            for (s, g) in sums.iter_mut().zip(clip(&gradient, scale)) {
+                *s += g as i128;
+            }
+            step.clip_scales.push(scale as i64);
+        }
+
+        let divisor = step.targets.len() as i128 * ONE;
+        step.update = sums
+            .iter()
+            .zip(&step.noise)
+            .map(|(s, n)| *n as i128 - (step.learning_rate as i128 * s).div_euclid(divisor))
+            .map(|u| u as i64)
+            .collect();
+        ensure!(step.update.iter().all(|&u| in_range(u as i128)), "update out of fixed-point range");
+        Ok(step)
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn batch(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// The update as submitted
+    pub fn update_f32(&self) -> Vec<f32> {
+        self.update.iter().map(|&u| u as f32 / ONE as f32).collect()
+    }
+
+    pub fn statement(&self) -> Statement {
+        let data: Vec<i64> = self.features.iter().chain(&self.targets).copied().collect();
+        Statement {
+            data_commitment: commit(DATA_DOMAIN, &data),
+            model_commitment: commit(MODEL_DOMAIN, &self.weights),
+            update_commitment: commit(UPDATE_DOMAIN, &self.update),
+            learning_rate: self.learning_rate,
+            clip_norm: self.clip_norm,
+            noise_bound: self.noise_bound,
+        }
+    }
+
+    /// Zero step of the given shape, for key generation
+    pub fn blank(dimension: usize, batch: usize) -> Self {
+        Self {
+            weights: vec![0; dimension],
+            features: vec![0; dimension * batch],
+            targets: vec![0; batch],
+            learning_rate: 0,
+            clip_norm: 0,
+            noise_bound: 0,
+            clip_scales: vec![0; batch],
+            noise: vec![0; dimension],
+            update: vec![0; dimension],
+        }
+    }
+}
+
+/// Poseidon commitment to fixed-point weights, e.g. the global model's
+pub fn model_commitment(weights: &[f32]) -> anyhow::Result<Fr> {
+    Ok(commit(MODEL_DOMAIN, &to_fixed_vec(weights)?))
+}
+
+/// Poseidon commitment to a submitted update
+pub fn update_commitment(update: &[f32]) -> anyhow::Result<Fr> {
+    Ok(commit(UPDATE_DOMAIN, &to_fixed_vec(update)?))
+}
+
+/// Update noise norm of a DP-SGD step exceeded with negligible probability:
+/// the step adds Gaussian noise of stddev `noise_multiplier * clip_norm` to
+/// the gradient sum, scaled by `learning_rate / batch`
+pub fn noise_bound(noise_multiplier: f64, clip_norm: f64, learning_rate: f64, batch: usize, dimension: usize) -> f32 {
+    let sigma = noise_multiplier * clip_norm * learning_rate / batch as f64;
+    (sigma * ((dimension as f64).sqrt() + NOISE_TAIL_SIGMAS)) as f32
+}
+
+/// Proving key from the model's setup ceremony; the verifying key is part of it
+pub fn load_proving_key(path: &Path) -> anyhow::Result<ProvingKey<Bn254>> {
+    let bytes = std::fs::read(path).with_context(|| format!("reading proving key {}", path.display()))?;
+    ProvingKey::deserialize_compressed(bytes.as_slice()).context("decoding proving key")
+}
+
+/// Proving and verifying keys for one model shape. Production keys come from
+/// a ceremony; this is for tests and private deployments.
+pub fn setup<R: RngCore + CryptoRng>(
+    dimension: usize,
+    batch: usize,
+    rng: &mut R,
+) -> anyhow::Result<(ProvingKey<Bn254>, VerifyingKey<Bn254>)> {
+    let circuit = TrainingStepCircuit::new(TrainingStep::blank(dimension, batch));
+    Groth16::<Bn254>::circuit_specific_setup(circuit, rng).context("training circuit setup")
+}
+
+pub fn generate_proof(pk: &ProvingKey<Bn254>, step: &TrainingStep) -> anyhow::Result<Proof> {
+    let statement = step.statement();
+    let proof = Groth16::<Bn254>::prove(pk, TrainingStepCircuit::new(step.clone()), &mut OsRng)
+        .context("proving training step")?;
+    Ok(Proof { proof, statement })
+}
+
+pub fn verify(vk: &VerifyingKey<Bn254>, proof: &Proof) -> bool {
+    Groth16::<Bn254>::verify(vk, &proof.statement.public_inputs(), &proof.proof).unwrap_or(false)
+}
+
+/// Check every proof with one multi-pairing: random weights r_i make
+/// `prod e(r_i A_i, B_i) = e(sum r_i IC_i, gamma) e(sum r_i C_i, delta) e(alpha, beta)^(sum r_i)`
+/// hold only if each proof's equation does
+pub fn verify_batch(vk: &VerifyingKey<Bn254>, proofs: &[&Proof]) -> bool {
+    if proofs.is_empty() {
+        return true;
+    }
+    let mut g1 = Vec::with_capacity(proofs.len() + 3);
+    let mut g2 = Vec::with_capacity(proofs.len() + 3);
+    let (mut ic_sum, mut c_sum, mut r_sum) = (G1Projective::zero(), G1Projective::zero(), Fr::zero());
+    for proof in proofs {
+        let inputs = proof.statement.public_inputs();
+        if inputs.len() + 1 != vk.gamma_abc_g1.len() {
+            return false;
+        }
+        // 128-bit weights suffice for soundness
+        let r = Fr::from(u128::rand(&mut OsRng));
+        let ic = inputs
+            .iter()
+            .zip(&vk.gamma_abc_g1[1..])
+            .fold(vk.gamma_abc_g1[0].into_group(), |acc, (x, base)| acc + *base * x);
+        g1.push((proof.proof.a * r).into_affine());
+        g2.push(proof.proof.b);
+        ic_sum += ic * r;
+        c_sum += proof.proof.c * r;
+        r_sum += r;
+    }
+    g1.extend([(-ic_sum).into_affine(), (-c_sum).into_affine(), (-(vk.alpha_g1 * r_sum)).into_affine()]);
+    g2.extend([vk.gamma_g2, vk.delta_g2, vk.beta_g2]);
+    Bn254::multi_pairing(g1, g2).is_zero()
+}
+
+/// Keep the submissions whose proofs attest to `round` and verify. One batch
+/// check covers an honest round; on failure each proof is checked alone.
+pub fn retain_valid<T>(
+    vk: &VerifyingKey<Bn254>,
+    round: &RoundStatement,
+    submissions: Vec<T>,
+    proof: impl Fn(&T) -> &Proof,
+) -> Vec<T> {
+    let (admitted, rejected): (Vec<T>, Vec<T>) =
+        submissions.into_iter().partition(|s| round.admits(&proof(s).statement));
+    if !rejected.is_empty() {
+        tracing::warn!(count = rejected.len(), "Training proofs for a different model or DP parameters");
+    }
+    let batch: Vec<&Proof> = admitted.iter().map(&proof).collect();
+    if verify_batch(vk, &batch) {
+        return admitted;
+    }
+    admitted
+        .into_iter()
+        .filter(|s| {
+            let valid = verify(vk, proof(s));
+            if !valid {
+                tracing::warn!(update = ?proof(s).statement.update_commitment, "Invalid training proof");
+            }
+            valid
+        })
+        .collect()
+}
+
+pub struct TrainingStepCircuit {
+    step: TrainingStep,
+    statement: Statement,
+}
+
+impl TrainingStepCircuit {
+    pub fn new(step: TrainingStep) -> Self {
+        Self { statement: step.statement(), step }
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for TrainingStepCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let Self { step, statement } = self;
+        let dimension = step.dimension();
+        let input = |v: Fr| FpVar::new_input(cs.clone(), || Ok(v));
+        let data_commitment = input(statement.data_commitment)?;
+        let model_commitment = input(statement.model_commitment)?;
+        let update_commitment = input(statement.update_commitment)?;
+        let learning_rate = Fixed::input(&cs, step.learning_rate)?;
+        let clip_norm = Fixed::input(&cs, step.clip_norm)?;
+        let noise_bound = Fixed::input(&cs, step.noise_bound)?;
+
+        let weights = Fixed::witnesses(&cs, &step.weights)?;
+        let features = Fixed::witnesses(&cs, &step.features)?;
+        let targets = Fixed::witnesses(&cs, &step.targets)?;
+        let noise = Fixed::witnesses(&cs, &step.noise)?;
+        let update = Fixed::witnesses(&cs, &step.update)?;
+        for v in weights.iter().chain(&features).chain(&targets).chain(&noise).chain(&update) {
+            range_check_signed(&cs, v, VALUE_BITS)?;
+        }
+        for v in [&learning_rate, &clip_norm, &noise_bound] {
+            to_bits(&cs, v, VALUE_BITS)?;
+        }
+
+        // Commitments bind the private batch, the starting model and the update
+        let data: Vec<Fixed> = features.iter().chain(&targets).cloned().collect();
+        commit_var(&cs, DATA_DOMAIN, &data)?.enforce_equal(&data_commitment)?;
+        commit_var(&cs, MODEL_DOMAIN, &weights)?.enforce_equal(&model_commitment)?;
+        commit_var(&cs, UPDATE_DOMAIN, &update)?.enforce_equal(&update_commitment)?;
+
+        // Per-example gradients of (w.x - y)^2 / 2, clipped to the bound
+        let clip_sq = clip_norm.mul(&clip_norm)?;
+        let one = Fixed::constant(ONE);
+        let mut sums = vec![Fixed::constant(0); dimension];
+        for ((x, y), scale) in features.chunks(dimension).zip(&targets).zip(&step.clip_scales) {
+            let prediction = rescale(&cs, &dot(x, &weights)?, FRAC_BITS)?;
+            let residual = prediction.sub(y);
+            let scale = Fixed::witness(&cs, *scale as i128)?;
+            to_bits(&cs, &scale, FRAC_BITS + 1)?;
+            to_bits(&cs, &one.sub(&scale), FRAC_BITS + 1)?;
+
+            let mut clipped = Vec::with_capacity(dimension);
+            for x in x {
+                let gradient = rescale(&cs, &residual.mul(x)?, FRAC_BITS)?;
+                clipped.push(rescale(&cs, &scale.mul(&gradient)?, FRAC_BITS)?);
+            }
+            to_bits(&cs, &clip_sq.sub(&dot(&clipped, &clipped)?), NORM_BITS)?;
+            for (sum, g) in sums.iter_mut().zip(&clipped) {
+                *sum = sum.add(g);
+            }
+        }
+
+        // update = noise - floor(lr * sum / (batch * ONE)), with bounded noise
+        let divisor = step.batch() as i128 * ONE;
+        let divisor_bits = 128 - (divisor - 1).leading_zeros();
+        for ((sum, n), u) in sums.iter().zip(&noise).zip(&update) {
+            let scaled = learning_rate.mul(sum)?;
+            let quotient = Fixed::witness(&cs, scaled.value.div_euclid(divisor))?;
+            let remainder = Fixed::witness(&cs, scaled.value.rem_euclid(divisor))?;
+            to_bits(&cs, &remainder, divisor_bits)?;
+            to_bits(&cs, &Fixed::constant(divisor - 1).sub(&remainder), divisor_bits)?;
+            range_check_signed(&cs, &quotient, NORM_BITS)?;
+            quotient.scale(divisor).add(&remainder).var.enforce_equal(&scaled.var)?;
+            n.sub(&quotient).var.enforce_equal(&u.var)?;
+        }
+        let bound_sq = noise_bound.mul(&noise_bound)?;
+        to_bits(&cs, &bound_sq.sub(&dot(&noise, &noise)?), NORM_BITS)?;
+        Ok(())
+    }
+}
+
+/// A field variable with its integer witness value
+#[derive(Clone)]
+struct Fixed {
+    var: FpVar<Fr>,
+    value: i128,
+}
+
+impl Fixed {
+    fn constant(value: i128) -> Self {
+        Self { var: FpVar::constant(fr(value)), value }
+    }
+
+    fn input(cs: &ConstraintSystemRef<Fr>, value: i64) -> Result<Self, SynthesisError> {
+        let var = FpVar::new_input(cs.clone(), || Ok(fr(value as i128)))?;
+        Ok(Self { var, value: value as i128 })
+    }
+
+    fn witness(cs: &ConstraintSystemRef<Fr>, value: i128) -> Result<Self, SynthesisError> {
+        let var = FpVar::new_witness(cs.clone(), || Ok(fr(value)))?;
+        Ok(Self { var, value })
+    }
+
+    fn witnesses(cs: &ConstraintSystemRef<Fr>, values: &[i64]) -> Result<Vec<Self>, SynthesisError> {
+        values.iter().map(|&v| Self::witness(cs, v as i128)).collect()
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self { var: &self.var + &other.var, value: self.value + other.value }
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        Self { var: &self.var - &other.var, value: self.value - other.value }
+    }
+
+    fn scale(&self, k: i128) -> Self {
+        Self { var: &self.var * fr(k), value: self.value * k }
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, SynthesisError> {
+        Ok(Self { var: &self.var * &other.var, value: self.value * other.value })
+    }
+}
+
+/// Little-endian bits of `x`, which must lie in `[0, 2^bits)`
+fn to_bits(cs: &ConstraintSystemRef<Fr>, x: &Fixed, bits: u32) -> Result<(), SynthesisError> {
+    let bits = (0..bits)
+        .map(|k| Boolean::new_witness(cs.clone(), || Ok(k < 127 && x.value >= 0 && (x.value >> k) & 1 == 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Boolean::le_bits_to_fp_var(&bits)?.enforce_equal(&x.var)
+}
+
+/// `x` in `[-2^(bits-1), 2^(bits-1))`
+fn range_check_signed(cs: &ConstraintSystemRef<Fr>, x: &Fixed, bits: u32) -> Result<(), SynthesisError> {
+    to_bits(cs, &x.add(&Fixed::constant(1 << (bits - 1))), bits)
+}
+
+/// `floor(x / 2^shift)`, range checked
+fn rescale(cs: &ConstraintSystemRef<Fr>, x: &Fixed, shift: u32) -> Result<Fixed, SynthesisError> {
+    let q = Fixed::witness(cs, x.value >> shift)?;
+    let r = Fixed::witness(cs, x.value - (q.value << shift))?;
+    to_bits(cs, &r, shift)?;
+    range_check_signed(cs, &q, VALUE_BITS)?;
+    q.scale(1 << shift).add(&r).var.enforce_equal(&x.var)?;
+    Ok(q)
+}
+
+fn dot(a: &[Fixed], b: &[Fixed]) -> Result<Fixed, SynthesisError> {
+    a.iter().zip(b).try_fold(Fixed::constant(0), |acc, (a, b)| Ok(acc.add(&a.mul(b)?)))
+}
+
+fn commit_var(cs: &ConstraintSystemRef<Fr>, domain: u64, values: &[Fixed]) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut sponge = PoseidonSpongeVar::new(cs.clone(), poseidon_config());
+    let mut elements = vec![FpVar::constant(Fr::from(domain)), FpVar::constant(Fr::from(values.len() as u64))];
+    elements.extend(values.iter().map(|v| v.var.clone()));
+    sponge.absorb(&elements)?;
+    Ok(sponge.squeeze_field_elements(1)?.remove(0))
+}
+
+fn commit(domain: u64, values: &[i64]) -> Fr {
+    let mut sponge = PoseidonSponge::new(poseidon_config());
+    let mut elements = vec![Fr::from(domain), Fr::from(values.len() as u64)];
+    elements.extend(values.iter().map(|&v| fr(v as i128)));
+    sponge.absorb(&elements);
+    sponge.squeeze_field_elements(1)[0]
+}
+
+/// Width-3 Poseidon with the standard BN254 round counts
+fn poseidon_config() -> &'static PoseidonConfig<Fr> {
+    static CONFIG: OnceLock<PoseidonConfig<Fr>> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let (full_rounds, partial_rounds, rate) = (8, 57, 2);
+        let (ark, mds) =
+            find_poseidon_ark_and_mds::<Fr>(Fr::MODULUS_BIT_SIZE as u64, rate, full_rounds, partial_rounds, 0);
+        PoseidonConfig::new(full_rounds as usize, partial_rounds as usize, 5, mds, ark, rate, 1)
+    })
+}
+
+fn example_gradient(weights: &[i64], x: &[i64], y: i128) -> Vec<i64> {
+    let prediction = weights.iter().zip(x).map(|(w, x)| *w as i128 * *x as i128).sum::<i128>() >> FRAC_BITS;
+    let residual = prediction - y;
+    x.iter().map(|x| ((residual * *x as i128) >> FRAC_BITS) as i64).collect()
+}
+
+fn clip(gradient: &[i64], scale: i128) -> Vec<i64> {
+    gradient.iter().map(|g| ((scale * *g as i128) >> FRAC_BITS) as i64).collect()
+}
+
+fn norm_sq(values: &[i64]) -> i128 {
+    values.iter().map(|v| (*v as i128).pow(2)).sum()
+}
+
+fn in_range(v: i128) -> bool {
+    (-(1i128 << (VALUE_BITS - 1))..(1i128 << (VALUE_BITS - 1))).contains(&v)
+}
+
+fn to_fixed(v: f32) -> anyhow::Result<i64> {
+    let fixed = (v as f64 * ONE as f64).round();
+    ensure!(fixed.is_finite() && in_range(fixed as i128), "{} out of fixed-point range", v);
+    Ok(fixed as i64)
+}
+
+fn to_fixed_vec(values: &[f32]) -> anyhow::Result<Vec<i64>> {
+    values.iter().map(|&v| to_fixed(v)).collect()
+}
+
+fn fr(v: i128) -> Fr {
+    if v < 0 {
+        -Fr::from(v.unsigned_abs())
+    } else {
+        Fr::from(v as u128)
+    }
+}
@@ -0,0 +1,68 @@
+// indexer/tests/compression_tests.rs
+
+use scoria_indexer::compression::{CompressedUpdate, CompressedValues, CompressionConfig, GradientCompressor};
+
+fn gradients() -> Vec<f32> {
+    (0..100).map(|i| ((i * 37) % 100) as f32 / 100.0 - 0.5).collect()
+}
+
+#[test]
+fn test_top_k_sends_largest_coordinates() {
+    let mut compressor = GradientCompressor::new(CompressionConfig { top_k: Some(0.1), quantize: false });
+    let update = compressor.compress(&gradients());
+
+    let indices = update.indices.as_ref().unwrap();
+    assert_eq!(indices.len(), 10);
+    let dense = update.decompress().unwrap();
+    let smallest_sent = indices.iter().map(|&i| dense[i as usize].abs()).fold(f32::MAX, f32::min);
+    assert!(gradients().iter().enumerate().all(|(i, g)| indices.contains(&(i as u32)) || g.abs() <= smallest_sent));
+    assert!(update.ratio() > 4.0);
+}
+
+#[test]
+fn test_quantization_error_within_half_step() {
+    let mut compressor = GradientCompressor::new(CompressionConfig { top_k: None, quantize: true });
+    let update = compressor.compress(&gradients());
+    let CompressedValues::I8 { scale, .. } = update.values else {
+        panic!("expected quantized values");
+    };
+
+    let dense = update.decompress().unwrap();
+    for (sent, original) in dense.iter().zip(gradients()) {
+        assert!((sent - original).abs() <= scale / 2.0 + f32::EPSILON);
+    }
+    assert!(update.ratio() > 3.5);
+}
+
+#[test]
+fn test_error_feedback_delivers_dropped_mass() {
+    let mut compressor = GradientCompressor::new(CompressionConfig { top_k: Some(0.05), quantize: true });
+    let rounds = 200;
+    let mut delivered = vec![0f32; 100];
+    for _ in 0..rounds {
+        let sent = compressor.compress(&gradients()).decompress().unwrap();
+        delivered.iter_mut().zip(sent).for_each(|(d, s)| *d += s);
+    }
+
+    // Each coordinate lags the dense total by at most its bounded residual
+    for (d, g) in delivered.iter().zip(gradients()) {
+        assert!((d - g * rounds as f32).abs() < 15.0, "{} vs {}", d, g * rounds as f32);
+    }
+}
+
+#[test]
+fn test_malformed_updates_rejected() {
+    let unordered = CompressedUpdate {
+        len: 4,
+        indices: Some(vec![2, 1]),
+        values: CompressedValues::F32(vec![1.0, 2.0]),
+    };
+    assert!(unordered.decompress().is_err());
+
+    let out_of_range = CompressedUpdate {
+        len: 4,
+        indices: Some(vec![4]),
+        values: CompressedValues::F32(vec![1.0]),
+    };
+    assert!(out_of_range.decompress().is_err());
+}
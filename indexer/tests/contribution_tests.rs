@@ -0,0 +1,26 @@
+// indexer/tests/contribution_tests.rs
+
+use scoria_indexer::contribution::{leave_one_out, SCORE_SCALE};
+
+/// Squared distance of an aggregate from the holdout optimum [1, 1]
+fn holdout_loss(update: &[f32]) -> anyhow::Result<f64> {
+    Ok(update.iter().map(|v| (*v as f64 - 1.0).powi(2)).sum())
+}
+
+#[test]
+fn test_harmful_updates_score_zero() {
+    let updates = vec![vec![1.0, 1.0], vec![0.9, 1.1], vec![-4.0, 6.0]];
+    let scores = leave_one_out(&updates, holdout_loss).unwrap();
+
+    assert_eq!(scores[2], 0);
+    assert!(scores[0] > 0 && scores[1] > 0);
+    // Removing the attacker helps by far more than one unit of loss
+    assert!(scores.iter().all(|&s| (s as f64) < 20.0 * SCORE_SCALE));
+}
+
+#[test]
+fn test_unhelpful_rounds_split_evenly() {
+    let updates = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+    assert_eq!(leave_one_out(&updates, holdout_loss).unwrap(), vec![1, 1]);
+    assert!(leave_one_out(&[], holdout_loss).unwrap().is_empty());
+}
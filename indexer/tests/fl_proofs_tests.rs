@@ -0,0 +1,88 @@
+// indexer/tests/fl_proofs_tests.rs
+
+use ark_bn254::Fr;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use rand::{rngs::StdRng, SeedableRng};
+use scoria_indexer::zk::fl_proofs::{self, RoundStatement, TrainingStep, TrainingStepCircuit};
+
+const DIMENSION: usize = 3;
+const BATCH: usize = 2;
+
+/// One step whose first example's gradient exceeds the clip bound
+fn step(noise: &[f32]) -> TrainingStep {
+    let weights = [0.5, -0.25, 1.0];
+    let features = [1.0, 2.0, -1.0, 0.5, 0.5, 0.5];
+    let targets = [3.0, 0.25];
+    TrainingStep::new(&weights, &features, &targets, 0.1, 1.0, noise, 0.05).unwrap()
+}
+
+fn satisfied(step: TrainingStep) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    TrainingStepCircuit::new(step).generate_constraints(cs.clone()).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+fn round() -> RoundStatement {
+    RoundStatement::new(&[0.5, -0.25, 1.0], 0.1, 1.0, 0.05).unwrap()
+}
+
+#[test]
+fn test_honest_step_satisfies_circuit() {
+    let step = step(&[0.01, -0.02, 0.0]);
+    assert!(step.clip_scales[0] < 1 << fl_proofs::FRAC_BITS, "first gradient should be clipped");
+    assert_eq!(step.update_f32().len(), DIMENSION);
+    assert!(satisfied(step));
+}
+
+#[test]
+fn test_noise_is_scaled_onto_bound() {
+    let step = step(&[1.0, 1.0, 1.0]);
+    let norm_sq: i128 = step.noise.iter().map(|n| (*n as i128).pow(2)).sum();
+    assert!(norm_sq <= (step.noise_bound as i128).pow(2));
+    assert!(satisfied(step));
+}
+
+#[test]
+fn test_tampered_steps_fail() {
+    let honest = step(&[0.0; DIMENSION]);
+
+    let mut update = honest.clone();
+    update.update[0] += 1000;
+    assert!(!satisfied(update));
+
+    // Skipping clipping exceeds the norm bound
+    let mut unclipped = honest.clone();
+    unclipped.clip_scales[0] = 1 << fl_proofs::FRAC_BITS;
+    assert!(!satisfied(unclipped));
+
+    let mut noise = honest;
+    noise.noise[0] = noise.noise_bound * 2;
+    assert!(!satisfied(noise));
+}
+
+#[test]
+fn test_batch_verification_drops_invalid_proofs() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let (pk, vk) = fl_proofs::setup(DIMENSION, BATCH, &mut rng).unwrap();
+    let steps = [step(&[0.01, 0.0, 0.0]), step(&[0.0, 0.01, 0.0]), step(&[0.0, 0.0, 0.01])];
+    let mut proofs: Vec<_> = steps.iter().map(|s| fl_proofs::generate_proof(&pk, s).unwrap()).collect();
+    assert!(fl_proofs::verify_batch(&vk, &proofs.iter().collect::<Vec<_>>()));
+
+    // A proof claiming a different update fails the batch and is dropped alone
+    proofs[1].statement.update_commitment = proofs[0].statement.update_commitment;
+    assert!(!fl_proofs::verify_batch(&vk, &proofs.iter().collect::<Vec<_>>()));
+    let valid = fl_proofs::retain_valid(&vk, &round(), proofs.clone(), |p| p);
+    assert_eq!(valid, vec![proofs[0].clone(), proofs[2].clone()]);
+
+    // Proofs for another model never reach verification
+    let mut other = round();
+    other.model_commitment = fl_proofs::model_commitment(&[0.0; DIMENSION]).unwrap();
+    assert!(fl_proofs::retain_valid(&vk, &other, proofs, |p| p).is_empty());
+}
+
+#[test]
+fn test_noise_bound_scales_with_step() {
+    let bound = fl_proofs::noise_bound(1.0, 1.0, 0.1, 10, 100);
+    assert!((bound - 0.16).abs() < 1e-6);
+    assert_eq!(fl_proofs::noise_bound(0.0, 1.0, 0.1, 10, 100), 0.0);
+}
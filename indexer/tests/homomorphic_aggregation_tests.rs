@@ -0,0 +1,64 @@
+// indexer/tests/homomorphic_aggregation_tests.rs
+
+use scoria_indexer::{ckks, paillier};
+
+/// Three clients' updates over more values than one ciphertext holds
+fn updates(dimension: usize) -> Vec<Vec<f32>> {
+    (0..3)
+        .map(|c| (0..dimension).map(|i| ((i * 7 + c * 13) % 50) as f32 / 10.0 - 2.5).collect())
+        .collect()
+}
+
+fn plain_sum(updates: &[Vec<f32>]) -> Vec<f64> {
+    let mut sum = vec![0f64; updates[0].len()];
+    for update in updates {
+        sum.iter_mut().zip(update).for_each(|(s, v)| *s += *v as f64);
+    }
+    sum
+}
+
+#[test]
+fn test_ckks_sums_batched_updates() {
+    let ckks = ckks::Ckks::new();
+    let (sk, pk) = ckks.generate_keys();
+    let updates = updates(ckks::SLOTS + 100);
+
+    let mut sum = ckks.encrypt(&pk, &updates[0]).unwrap();
+    assert_eq!(sum.len(), 2);
+    for update in &updates[1..] {
+        ckks.add_assign(&mut sum, &ckks.encrypt(&pk, update).unwrap()).unwrap();
+    }
+
+    let decrypted = ckks.decrypt(&sk, &sum, updates[0].len()).unwrap();
+    for (got, want) in decrypted.iter().zip(plain_sum(&updates)) {
+        assert!((got - want).abs() < 1e-4, "{} vs {}", got, want);
+    }
+}
+
+#[test]
+fn test_ckks_rejects_mismatched_batches() {
+    let ckks = ckks::Ckks::new();
+    let (_, pk) = ckks.generate_keys();
+    let mut one = ckks.encrypt(&pk, &[1.0]).unwrap();
+    let two = ckks.encrypt(&pk, &vec![1.0; ckks::SLOTS + 1]).unwrap();
+    assert!(ckks.add_assign(&mut one, &two).is_err());
+    assert!(ckks.encrypt(&pk, &[f32::NAN]).is_err());
+}
+
+#[test]
+fn test_paillier_sums_packed_updates() {
+    // Small modulus keeps key generation fast; packing is the same
+    let sk = paillier::SecretKey::generate(512);
+    let pk = sk.public_key();
+    let updates = updates(pk.slots() * 2 + 3);
+
+    let mut sum = pk.encrypt(&updates[0]).unwrap();
+    for update in &updates[1..] {
+        pk.add_assign(&mut sum, &pk.encrypt(update).unwrap()).unwrap();
+    }
+
+    let decrypted = sk.decrypt_sum(&sum, updates[0].len(), updates.len()).unwrap();
+    for (got, want) in decrypted.iter().zip(plain_sum(&updates)) {
+        assert!((got - want).abs() < 1e-6, "{} vs {}", got, want);
+    }
+}
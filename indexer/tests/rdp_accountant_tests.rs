@@ -0,0 +1,44 @@
+// indexer/tests/rdp_accountant_tests.rs
+
+use scoria_indexer::rdp_accountant::RdpAccountant;
+
+#[test]
+fn test_matches_reference_dp_sgd_budget() {
+    // MNIST tutorial setting: batch 256 of 60k, sigma = 1.1, 60 epochs, delta = 1e-5.
+    // Reference accountants report epsilon ~2.9 with the classic conversion, lower with Balle et al.
+    let accountant = RdpAccountant::new(10.0, 1e-5).unwrap();
+    let epsilon = accountant.projected(256.0 / 60_000.0, 1.1, 14_063).unwrap();
+    assert!(epsilon > 2.3 && epsilon < 3.0, "epsilon {epsilon}");
+}
+
+#[test]
+fn test_subsampling_amplifies_privacy() {
+    let accountant = RdpAccountant::new(10.0, 1e-5).unwrap();
+    let sampled = accountant.projected(0.05, 1.0, 100).unwrap();
+    let full = accountant.projected(1.0, 1.0, 100).unwrap();
+    assert!(sampled < full / 4.0, "sampled {sampled}, full {full}");
+}
+
+#[test]
+fn test_refuses_rounds_past_target() {
+    let mut accountant = RdpAccountant::new(1.0, 1e-5).unwrap();
+    assert_eq!(accountant.spent(), 0.0);
+    while accountant.record_round(0.1, 2.0).is_ok() {
+        assert!(accountant.rounds() < 10_000, "budget never exhausted");
+    }
+
+    assert!(accountant.spent() <= 1.0);
+    assert!(accountant.remaining_budget() < 0.1);
+    let rounds = accountant.rounds();
+    assert!(rounds > 1);
+    assert!(accountant.record_round(0.1, 2.0).is_err());
+    assert_eq!(accountant.rounds(), rounds);
+}
+
+#[test]
+fn test_rejects_invalid_parameters() {
+    let mut accountant = RdpAccountant::new(1.0, 1e-5).unwrap();
+    assert!(accountant.record_round(0.0, 1.0).is_err());
+    assert!(accountant.record_round(0.1, 0.0).is_err());
+    assert!(RdpAccountant::new(1.0, 1.0).is_err());
+}
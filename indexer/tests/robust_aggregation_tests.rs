@@ -0,0 +1,70 @@
+// indexer/tests/robust_aggregation_tests.rs
+
+use scoria_indexer::robust_aggregation::AggregatorKind;
+
+/// Eight honest updates near [1, 1, 1] and two attackers pushing [-50, 50, -50]
+fn poisoned_round() -> Vec<Vec<f32>> {
+    let mut updates: Vec<Vec<f32>> = (0..8)
+        .map(|i| {
+            let jitter = (i as f32 - 3.5) * 0.01;
+            vec![1.0 + jitter, 1.0 - jitter, 1.0 + jitter]
+        })
+        .collect();
+    updates.push(vec![-50.0, 50.0, -50.0]);
+    updates.push(vec![-49.0, 51.0, -50.0]);
+    updates
+}
+
+fn assert_near_honest(update: &[f32]) {
+    for v in update {
+        assert!((v - 1.0).abs() < 0.1, "aggregate {:?} was poisoned", update);
+    }
+}
+
+#[test]
+fn test_fedavg_is_poisoned() {
+    let outcome = AggregatorKind::FedAvg.aggregate(&poisoned_round(), None).unwrap();
+    assert!((outcome.update[0] - 1.0).abs() > 1.0);
+}
+
+#[test]
+fn test_krum_filters_attackers() {
+    let kind = AggregatorKind::Krum { byzantine: 2, select: 3 };
+    let outcome = kind.aggregate(&poisoned_round(), None).unwrap();
+    assert_near_honest(&outcome.update);
+    assert!(outcome.rejected.contains(&8) && outcome.rejected.contains(&9));
+}
+
+#[test]
+fn test_krum_requires_enough_updates() {
+    let kind = AggregatorKind::Krum { byzantine: 4, select: 1 };
+    assert!(kind.aggregate(&poisoned_round(), None).is_err());
+}
+
+#[test]
+fn test_trimmed_mean_filters_attackers() {
+    let kind = AggregatorKind::TrimmedMean { trim_ratio: 0.2 };
+    assert_near_honest(&kind.aggregate(&poisoned_round(), None).unwrap().update);
+}
+
+#[test]
+fn test_median_filters_attackers() {
+    assert_near_honest(&AggregatorKind::Median.aggregate(&poisoned_round(), None).unwrap().update);
+}
+
+#[test]
+fn test_fltrust_zeroes_opposing_updates() {
+    let root = [1.0, 1.0, 1.0];
+    let outcome = AggregatorKind::FlTrust.aggregate(&poisoned_round(), Some(&root)).unwrap();
+    assert_near_honest(&outcome.update);
+    assert_eq!(outcome.rejected, vec![8, 9]);
+}
+
+#[test]
+fn test_non_finite_updates_rejected() {
+    let mut updates = poisoned_round();
+    updates.push(vec![f32::NAN, 1.0, 1.0]);
+    let outcome = AggregatorKind::Median.aggregate(&updates, None).unwrap();
+    assert!(outcome.rejected.contains(&10));
+    assert_near_honest(&outcome.update);
+}
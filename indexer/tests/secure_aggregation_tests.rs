@@ -0,0 +1,121 @@
+// indexer/tests/secure_aggregation_tests.rs
+
+use scoria_indexer::secure_aggregation::{
+    EncryptedShares, SecAggClient, SecAggServer, UnmaskResponse,
+};
+use std::collections::BTreeSet;
+
+const DIMENSION: usize = 16;
+
+fn client_update(id: u32) -> Vec<f32> {
+    (0..DIMENSION).map(|i| (id as f32) * 0.5 - (i as f32) * 0.25).collect()
+}
+
+/// Run a full round, dropping `drop_after_sharing` between rounds 1 and 2
+fn run_round(
+    n: u32,
+    threshold: usize,
+    drop_after_sharing: &[u32],
+) -> anyhow::Result<(Vec<f32>, Vec<f32>, Vec<u32>)> {
+    let mut clients: Vec<SecAggClient> = (1..=n).map(|id| SecAggClient::new(id, threshold)).collect();
+    let mut server = SecAggServer::new(threshold, DIMENSION);
+
+    // Round 0
+    for c in &clients {
+        server.register(c.advertise())?;
+    }
+    let roster = server.roster();
+
+    // Round 1
+    let mut outbound: Vec<EncryptedShares> = Vec::new();
+    for c in clients.iter_mut() {
+        outbound.extend(c.share_keys(&roster)?);
+    }
+    let routed = server.route_shares(outbound)?;
+    for c in clients.iter_mut() {
+        if let Some(inbox) = routed.get(&c.id()) {
+            c.receive_shares(inbox)?;
+        }
+    }
+
+    // Round 2
+    let senders = server.share_senders();
+    let dropped: BTreeSet<u32> = drop_after_sharing.iter().copied().collect();
+    let mut expected = vec![0f32; DIMENSION];
+    for c in clients.iter().filter(|c| !dropped.contains(&c.id())) {
+        let update = client_update(c.id());
+        for (e, u) in expected.iter_mut().zip(&update) {
+            *e += u;
+        }
+        server.submit_masked(c.mask_input(&update, &senders)?)?;
+    }
+
+    // Round 3
+    let survivors = server.survivors();
+    let responses: Vec<UnmaskResponse> = clients
+        .iter()
+        .filter(|c| survivors.contains(&c.id()))
+        .map(|c| c.unmask(&senders, &survivors))
+        .collect::<anyhow::Result<_>>()?;
+
+    let result = server.unmask(&responses)?;
+    Ok((result.sum, expected, result.dropped))
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-4, "{} != {}", a, e);
+    }
+}
+
+#[test]
+fn test_aggregate_without_dropouts() {
+    let (sum, expected, dropped) = run_round(5, 3, &[]).unwrap();
+    assert_close(&sum, &expected);
+    assert!(dropped.is_empty());
+}
+
+#[test]
+fn test_aggregate_recovers_from_dropouts() {
+    let (sum, expected, dropped) = run_round(7, 4, &[2, 5]).unwrap();
+    assert_close(&sum, &expected);
+    assert_eq!(dropped, vec![2, 5]);
+}
+
+#[test]
+fn test_round_fails_below_threshold() {
+    assert!(run_round(5, 4, &[1, 2]).is_err());
+}
+
+#[test]
+fn test_masked_input_hides_update() {
+    let mut a = SecAggClient::new(1, 2);
+    let mut b = SecAggClient::new(2, 2);
+    let roster = vec![a.advertise(), b.advertise()];
+    let to_b = a.share_keys(&roster).unwrap();
+    let to_a = b.share_keys(&roster).unwrap();
+    a.receive_shares(&to_a).unwrap();
+    b.receive_shares(&to_b).unwrap();
+
+    let masked = a.mask_input(&[0.0; DIMENSION], &[1, 2]).unwrap();
+    assert!(masked.values.iter().any(|&v| v != 0));
+}
+
+#[test]
+fn test_unmask_never_reveals_both_shares() {
+    let mut clients: Vec<SecAggClient> = (1..=3).map(|id| SecAggClient::new(id, 2)).collect();
+    let roster: Vec<_> = clients.iter().map(|c| c.advertise()).collect();
+    let mut all = Vec::new();
+    for c in clients.iter_mut() {
+        all.extend(c.share_keys(&roster).unwrap());
+    }
+    for c in clients.iter_mut() {
+        c.receive_shares(&all).unwrap();
+    }
+
+    let response = clients[0].unmask(&[1, 2, 3], &[1, 2]).unwrap();
+    assert!(response.self_mask_shares.contains_key(&2));
+    assert!(!response.mask_key_shares.contains_key(&2));
+    assert!(response.mask_key_shares.contains_key(&3));
+    assert!(!response.self_mask_shares.contains_key(&3));
+}
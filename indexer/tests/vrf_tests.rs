@@ -0,0 +1,39 @@
+// indexer/tests/vrf_tests.rs
+
+use scoria_indexer::vrf;
+
+fn decode<const N: usize>(hex: &str) -> [u8; N] {
+    (0..N).map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap()).collect::<Vec<_>>().try_into().unwrap()
+}
+
+/// RFC 9381 appendix B.3, example 16
+#[test]
+fn test_matches_rfc_9381_vector() {
+    let secret = decode::<32>("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    let public = decode::<32>("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let (proof, output) = vrf::prove(&secret, b"");
+
+    assert_eq!(
+        proof,
+        decode::<80>(
+            "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d97\
+             27d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805"
+        )
+    );
+    assert_eq!(vrf::verify(&public, b"", &proof), Some(output));
+}
+
+#[test]
+fn test_rejects_other_inputs_keys_and_tampered_proofs() {
+    let secret = decode::<32>("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    let public = decode::<32>("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let (proof, output) = vrf::prove(&secret, b"round-1");
+    assert_eq!(vrf::verify(&public, b"round-1", &proof), Some(output));
+
+    assert_eq!(vrf::verify(&public, b"round-2", &proof), None);
+    let (other_key, _) = vrf::prove(&[7u8; 32], b"round-1");
+    assert_eq!(vrf::verify(&public, b"round-1", &other_key), None);
+    let mut tampered = proof;
+    tampered[40] ^= 1;
+    assert_eq!(vrf::verify(&public, b"round-1", &tampered), None);
+}
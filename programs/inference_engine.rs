@@ -1,5 +1,7 @@
 // local_engine/src/inference_engine.rs
 
+use scoria_client::core::attestation::tee::{report_data, TeePlatform, TeeQuote};
+use sha2::{Digest, Sha256};
 use solana_program::pubkey::Pubkey;
 use std::path::Path;
 use tch::{CModule, Device, Tensor};
@@ -118,25 +120,47 @@ impl InferenceEngine {
         Ok(())
     }
 
-    /// Hardware-accelerated secure inference
+    /// Hardware-accelerated secure inference. With `tee` set, the ZK proof is
+    /// replaced by an SGX/SNP quote binding model, input and output hashes.
     #[cfg(feature = "tpm")]
     pub fn secure_enclave_infer(
         &self,
-        encrypted_input: &[u8]
-    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        encrypted_input: &[u8],
+        tee: Option<(TeePlatform, [u8; 32])>
+    ) -> Result<(Vec<u8>, EnclaveEvidence)> {
         use tpm::TpmContext;
         
         let tpm = TpmContext::new()?;
         let plaintext = tpm.decrypt(encrypted_input)?;
         let tensor = Tensor::from_bytes(&plaintext)?;
         
-        let (output, proof) = self.infer_with_proof(tensor, false)?;
+        let (output, evidence) = match tee {
+            Some((platform, nonce)) => {
+                let output = self.model.forward_ts(&[tensor.to_device(self.device)])?;
+                let input_hash = Sha256::digest(&plaintext).into();
+                let output_hash = Sha256::digest(&output.to_bytes()?).into();
+                let data = report_data(&self.model_hash, &input_hash, &output_hash, &nonce);
+                (output, EnclaveEvidence::Tee(TeeQuote::generate(platform, &data)?))
+            }
+            None => {
+                let (output, proof) = self.infer_with_proof(tensor, false)?;
+                (output, EnclaveEvidence::ZkProof(proof))
+            }
+        };
         let encrypted_output = tpm.encrypt(&output.to_bytes()?)?;
         
-        Ok((encrypted_output, proof))
+        Ok((encrypted_output, evidence))
     }
 }
 
+/// What backs an enclave inference result on-chain
+pub enum EnclaveEvidence {
+    /// Serialized Groth16 proof for `submit_inference`
+    ZkProof(Vec<u8>),
+    /// Platform quote for `verify_tee_attestation`; lower latency, hardware trust
+    Tee(TeeQuote),
+}
+
 /// Dataset loader with privacy controls
 pub struct Dataset {
     data: Tensor,
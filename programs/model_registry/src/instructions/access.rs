@@ -0,0 +1,105 @@
+// contracts/programs/model_registry/src/instructions/access.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{instructions::pause::NotPaused, state::*};
+
+#[derive(Accounts)]
+pub struct ManageAccess<'info> {
+    #[account(
+        mut,
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// Model owner or an ACL Administrator
+    pub authority: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Grant `user` the given level, replacing any existing entry
+pub fn grant(ctx: Context<ManageAccess>, user: Pubkey, level: AccessLevel) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let model = &mut ctx.accounts.model_account;
+
+    // 1. Writable model and a caller allowed to touch this entry
+    ensure_writable(model)?;
+    require!(
+        model.can_manage_acl(&authority, model.acl.get(&user), &level),
+        ModelRegistryError::InsufficientPrivilege
+    );
+
+    // 2. Apply and record the transition
+    let previous = model.grant(user, level)?;
+    emit!(ModelStateChanged {
+        model: model.key(),
+        field: ModelField::AccessControl,
+        old_value: (user, previous).try_to_vec()?,
+        new_value: (user, Some(level)).try_to_vec()?,
+        changed_by: authority,
+    });
+
+    Ok(())
+}
+
+/// Remove `user` from the ACL
+pub fn revoke(ctx: Context<ManageAccess>, user: Pubkey) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let model = &mut ctx.accounts.model_account;
+
+    ensure_writable(model)?;
+    let previous = *model.acl.get(&user).ok_or(ModelRegistryError::NotInAcl)?;
+    require!(
+        model.can_manage_acl(&authority, Some(&previous), &AccessLevel::NoAccess),
+        ModelRegistryError::InsufficientPrivilege
+    );
+
+    model.acl.remove(&user);
+    emit!(ModelStateChanged {
+        model: model.key(),
+        field: ModelField::AccessControl,
+        old_value: (user, Some(previous)).try_to_vec()?,
+        new_value: (user, None::<AccessLevel>).try_to_vec()?,
+        changed_by: authority,
+    });
+
+    Ok(())
+}
+
+/// Open or close inference to callers outside the ACL
+pub fn set_public(ctx: Context<ManageAccess>, is_public: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let model = &mut ctx.accounts.model_account;
+
+    ensure_writable(model)?;
+    model.check_access(&authority, AccessLevel::Administrator)?;
+
+    let previous = model.is_public;
+    model.is_public = is_public;
+    emit!(ModelStateChanged {
+        model: model.key(),
+        field: ModelField::PublicAccess,
+        old_value: vec![previous as u8],
+        new_value: vec![is_public as u8],
+        changed_by: authority,
+    });
+
+    Ok(())
+}
+
+/// ACL changes are writes: blocked while paused or after storage lapses
+fn ensure_writable(model: &ModelAccount) -> Result<()> {
+    model.require_unpaused()?;
+    model.require_active(Clock::get()?.unix_timestamp)
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Account does not have required privilege level")]
+    InsufficientPrivilege,
+    #[msg("Account has no access control entry")]
+    NotInAcl,
+    // ... (previous errors)
+}
@@ -0,0 +1,245 @@
+// contracts/programs/model_registry/src/instructions/access_token.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{instructions::pause::NotPaused, state::*};
+
+/// Longest a token may live: about 30 days of 400ms slots
+pub const MAX_ACCESS_TOKEN_SLOTS: u64 = 30 * 216_000;
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct IssueAccessToken<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// One per holder; issuing again replaces the terms and resets the counters
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AccessToken::LEN,
+        seeds = [b"access_token", model_account.key().as_ref(), holder.as_ref()],
+        bump
+    )]
+    pub access_token: Account<'info, AccessToken>,
+
+    /// Model owner or an ACL Administrator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAccessToken<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        close = issuer,
+        has_one = issuer @ ModelRegistryError::Unauthorized,
+        constraint = access_token.model == model_account.key() @ ModelRegistryError::Unauthorized,
+        seeds = [b"access_token", model_account.key().as_ref(), access_token.holder.as_ref()],
+        bump = access_token.bump
+    )]
+    pub access_token: Account<'info, AccessToken>,
+
+    /// Model owner, an ACL Administrator, or the holder giving the token up
+    pub authority: Signer<'info>,
+
+    /// CHECK: Receives the rent it paid at issue; matched by `has_one`
+    #[account(mut)]
+    pub issuer: AccountInfo<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Let `holder` request inference on a non-public model without an ACL entry,
+/// within `terms`. Fees are still paid per request.
+pub fn issue(ctx: Context<IssueAccessToken>, holder: Pubkey, terms: AccessTokenTerms) -> Result<()> {
+    let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    let model = &ctx.accounts.model_account;
+
+    // 1. Writable model, a caller allowed to open it up, and bounded terms
+    model.require_unpaused()?;
+    model.require_active(clock.unix_timestamp)?;
+    model.check_access(&authority, AccessLevel::Administrator)?;
+    terms.validate(clock.slot)?;
+
+    // 2. Fresh counters; rent goes back to whoever first paid it
+    let token = &mut ctx.accounts.access_token;
+    if token.issuer == Pubkey::default() {
+        token.issuer = authority;
+    }
+    token.model = model.key();
+    token.holder = holder;
+    token.terms = terms;
+    token.invocations = 0;
+    token.window_start = clock.slot;
+    token.window_count = 0;
+    token.bump = *ctx.bumps.get("access_token").unwrap();
+
+    emit!(AccessTokenIssued {
+        model: token.model,
+        holder,
+        issued_by: authority,
+        expiry_slot: terms.expiry_slot,
+        max_invocations: terms.max_invocations,
+        rate_limit: terms.rate_limit,
+        rate_window: terms.rate_window,
+    });
+
+    Ok(())
+}
+
+/// Close a token before it runs out
+pub fn revoke(ctx: Context<RevokeAccessToken>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let token = &ctx.accounts.access_token;
+    if authority != token.holder {
+        ctx.accounts.model_account.check_access(&authority, AccessLevel::Administrator)?;
+    }
+
+    emit!(AccessTokenRevoked {
+        model: token.model,
+        holder: token.holder,
+        revoked_by: authority,
+        invocations: token.invocations,
+    });
+
+    Ok(())
+}
+
+/// Limits of an access token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct AccessTokenTerms {
+    /// First slot the token is no longer valid in
+    pub expiry_slot: u64,
+    /// Requests allowed over the token's lifetime
+    pub max_invocations: u64,
+    /// Requests allowed per `rate_window` slots; zero for no rate limit
+    pub rate_limit: u32,
+    pub rate_window: u64,
+}
+
+impl AccessTokenTerms {
+    pub const LEN: usize = 8 + 8 + 4 + 8;
+
+    pub fn validate(&self, slot: u64) -> Result<()> {
+        require!(
+            self.expiry_slot > slot && self.expiry_slot - slot <= MAX_ACCESS_TOKEN_SLOTS,
+            ModelRegistryError::InvalidAccessTokenTerms
+        );
+        require!(self.max_invocations > 0, ModelRegistryError::InvalidAccessTokenTerms);
+        require!(
+            self.rate_limit == 0 || self.rate_window > 0,
+            ModelRegistryError::InvalidAccessTokenTerms
+        );
+        Ok(())
+    }
+}
+
+/// Capability to request inference on one model, held by one key
+#[account]
+#[derive(Default)]
+pub struct AccessToken {
+    pub model: Pubkey,
+    pub holder: Pubkey,
+    /// Paid the rent; refunded on revocation
+    pub issuer: Pubkey,
+    pub terms: AccessTokenTerms,
+    pub invocations: u64,
+    /// Fixed rate window: its first slot and the requests made in it
+    pub window_start: u64,
+    pub window_count: u32,
+    pub bump: u8,
+}
+
+impl AccessToken {
+    pub const LEN: usize = 32 + 32 + 32 + AccessTokenTerms::LEN + 8 + 8 + 4 + 1;
+
+    /// Count one request made at `slot`, failing once the token is expired,
+    /// used up or over its rate
+    pub fn consume(&mut self, slot: u64) -> Result<()> {
+        require!(slot < self.terms.expiry_slot, ModelRegistryError::AccessTokenExpired);
+        require!(self.invocations < self.terms.max_invocations, ModelRegistryError::AccessTokenExhausted);
+        if self.terms.rate_limit > 0 {
+            if slot >= self.window_start.saturating_add(self.terms.rate_window) {
+                self.window_start = slot;
+                self.window_count = 0;
+            }
+            require!(self.window_count < self.terms.rate_limit, ModelRegistryError::AccessTokenRateLimited);
+            self.window_count += 1;
+        }
+        self.invocations += 1;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AccessTokenIssued {
+    pub model: Pubkey,
+    pub holder: Pubkey,
+    pub issued_by: Pubkey,
+    pub expiry_slot: u64,
+    pub max_invocations: u64,
+    pub rate_limit: u32,
+    pub rate_window: u64,
+}
+
+#[event]
+pub struct AccessTokenRevoked {
+    pub model: Pubkey,
+    pub holder: Pubkey,
+    pub revoked_by: Pubkey,
+    pub invocations: u64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Access token must expire within 30 days and allow at least one request")]
+    InvalidAccessTokenTerms,
+    #[msg("Access token has expired")]
+    AccessTokenExpired,
+    #[msg("Access token has no invocations left")]
+    AccessTokenExhausted,
+    #[msg("Access token rate limit reached; retry in a later slot")]
+    AccessTokenRateLimited,
+    // ... (previous errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consume_enforces_rate_window_and_lifetime() {
+        let mut token = AccessToken {
+            terms: AccessTokenTerms { expiry_slot: 1_000, max_invocations: 3, rate_limit: 2, rate_window: 10 },
+            window_start: 100,
+            ..Default::default()
+        };
+        token.consume(100).unwrap();
+        token.consume(105).unwrap();
+        assert!(token.consume(109).is_err());
+        // A new window opens once the old one has passed
+        token.consume(110).unwrap();
+        assert_eq!((token.invocations, token.window_start, token.window_count), (3, 110, 1));
+        assert!(token.consume(500).is_err());
+
+        token.terms.max_invocations = 10;
+        assert!(token.consume(1_000).is_err());
+    }
+}
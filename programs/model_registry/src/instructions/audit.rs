@@ -0,0 +1,203 @@
+// contracts/programs/model_registry/src/instructions/audit.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{instructions::pause::NotPaused, state::*, utils::ed25519, AdminAccount};
+
+/// Upper bound on auditors in the registry
+pub const MAX_REGISTERED_AUDITORS: usize = 16;
+/// Upper bound on a report CID (CIDv1 base32 is 59 characters)
+pub const MAX_REPORT_CID_LEN: usize = 64;
+/// Signatures kept on `ModelAccount::audit_signatures`, newest last
+pub const MAX_MODEL_AUDIT_SIGNATURES: usize = 3;
+
+#[derive(Accounts)]
+pub struct SetAuditorRegistry<'info> {
+    #[account(seeds = [b"admin"], bump = admin.bump)]
+    pub admin: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"auditor_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, AuditorRegistry>,
+
+    /// The multisig vault once `initialize_multisig` has run
+    #[account(address = admin.authority @ ModelRegistryError::Unauthorized)]
+    pub admin_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(auditor: Pubkey)]
+pub struct SubmitAudit<'info> {
+    #[account(
+        mut,
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(seeds = [b"auditor_registry"], bump = registry.bump)]
+    pub registry: Account<'info, AuditorRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + AuditRecord::LEN,
+        seeds = [b"audit", model_account.key().as_ref(), auditor.as_ref()],
+        bump
+    )]
+    pub record: Account<'info, AuditRecord>,
+
+    /// Anyone may relay an auditor's signed report
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// CHECK: address constrained to the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Replace the registered auditors (admin, i.e. multisig quorum)
+pub fn set_registry(ctx: Context<SetAuditorRegistry>, auditors: Vec<Pubkey>) -> Result<()> {
+    require!(auditors.len() <= MAX_REGISTERED_AUDITORS, ModelRegistryError::TooManyAuditors);
+
+    let registry = &mut ctx.accounts.registry;
+    registry.auditors = auditors;
+    registry.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(AuditorRegistryUpdated {
+        auditors: registry.auditors.clone(),
+        changed_by: ctx.accounts.admin_authority.key(),
+        timestamp: registry.updated_at,
+    });
+
+    Ok(())
+}
+
+/// Record a registered auditor's signature over the active model version and its report.
+/// Resubmitting replaces that auditor's previous audit of the model.
+pub fn submit(
+    ctx: Context<SubmitAudit>,
+    auditor: Pubkey,
+    version: u64,
+    report_cid: String,
+) -> Result<()> {
+    let model = &mut ctx.accounts.model_account;
+
+    // 1. Registered auditor, current version, bounded CID
+    require!(
+        ctx.accounts.registry.auditors.contains(&auditor),
+        ModelRegistryError::AuditorNotRegistered
+    );
+    require!(version == model.active_version, ModelRegistryError::InvalidAuditVersion);
+    require!(
+        !report_cid.is_empty() && report_cid.len() <= MAX_REPORT_CID_LEN,
+        ModelRegistryError::InvalidReportCid
+    );
+
+    // 2. Auditor signature, checked by the Ed25519 program in the previous instruction
+    let message = AuditRecord::message(&model.model_hash, version, &report_cid);
+    let signature = ed25519::verify_preceding(&ctx.accounts.instructions, &auditor, &message)?;
+
+    // 3. Keep the model's signature list to the newest audits, one per auditor
+    let record = &mut ctx.accounts.record;
+    if record.auditor == auditor {
+        model.audit_signatures.retain(|s| s != &record.signature);
+    }
+    if model.audit_signatures.len() >= MAX_MODEL_AUDIT_SIGNATURES {
+        model.audit_signatures.remove(0);
+    }
+    model.audit_signatures.push(signature);
+
+    record.model = model.key();
+    record.auditor = auditor;
+    record.model_hash = model.model_hash;
+    record.version = version;
+    record.report_cid = report_cid;
+    record.signature = signature;
+    record.submitted_at = Clock::get()?.unix_timestamp;
+    record.bump = *ctx.bumps.get("record").unwrap();
+
+    emit!(AuditSubmitted {
+        model: record.model,
+        auditor,
+        version,
+        report_cid: record.report_cid.clone(),
+        timestamp: record.submitted_at,
+    });
+
+    Ok(())
+}
+
+/// Auditors trusted to sign model audit reports
+#[account]
+#[derive(Default)]
+pub struct AuditorRegistry {
+    pub auditors: Vec<Pubkey>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl AuditorRegistry {
+    pub const LEN: usize = (4 + MAX_REGISTERED_AUDITORS * 32) + 8 + 1;
+}
+
+/// Latest audit of a model by one auditor
+#[account]
+#[derive(Default)]
+pub struct AuditRecord {
+    pub model: Pubkey,
+    pub auditor: Pubkey,
+    pub model_hash: [u8; 32],
+    pub version: u64,
+    pub report_cid: String,
+    pub signature: [u8; 64],
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+impl AuditRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + (4 + MAX_REPORT_CID_LEN) + 64 + 8 + 1;
+
+    /// Signed bytes: domain tag, model hash, version LE, report CID
+    pub fn message(model_hash: &[u8; 32], version: u64, report_cid: &str) -> Vec<u8> {
+        [b"scoria-audit-v1".as_ref(), model_hash, &version.to_le_bytes(), report_cid.as_bytes()].concat()
+    }
+}
+
+#[event]
+pub struct AuditorRegistryUpdated {
+    pub auditors: Vec<Pubkey>,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuditSubmitted {
+    pub model: Pubkey,
+    pub auditor: Pubkey,
+    pub version: u64,
+    pub report_cid: String,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Too many auditors")]
+    TooManyAuditors,
+    #[msg("Auditor is not in the registry")]
+    AuditorNotRegistered,
+    #[msg("Audit must cover the active model version")]
+    InvalidAuditVersion,
+    #[msg("Report CID is empty or too long")]
+    InvalidReportCid,
+    // ... (previous errors)
+}
@@ -0,0 +1,371 @@
+// contracts/programs/model_registry/src/instructions/challenge.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use solana_program::{program::invoke, system_instruction, sysvar::clock::Clock};
+use crate::{
+    instructions::{
+        fork::{upstream_share, RoyaltyVault},
+        inference::{release_escrow, EscrowAccounts},
+        pause::NotPaused,
+    },
+    state::*,
+    utils::crypto,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureOptimistic<'info> {
+    #[account(
+        constraint = model_account.owner == owner.key() @ ModelRegistryError::Unauthorized
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = OptimisticConfig::space(),
+        seeds = [b"optimistic", model_account.key().as_ref()],
+        bump
+    )]
+    pub optimistic_config: Account<'info, OptimisticConfig>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeProvider<'info> {
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = ProviderStake::space(),
+        seeds = [b"provider_stake", model_account.key().as_ref(), provider.key().as_ref()],
+        bump
+    )]
+    pub provider_stake: Account<'info, ProviderStake>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        has_one = provider @ ModelRegistryError::Unauthorized,
+        seeds = [b"provider_stake", provider_stake.model.as_ref(), provider.key().as_ref()],
+        bump = provider_stake.bump
+    )]
+    pub provider_stake: Account<'info, ProviderStake>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeInference<'info> {
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        seeds = [b"optimistic", model_account.key().as_ref()],
+        bump = optimistic_config.bump
+    )]
+    pub optimistic_config: Account<'info, OptimisticConfig>,
+
+    #[account(
+        mut,
+        has_one = requester @ ModelRegistryError::Unauthorized,
+        constraint = inference_request.model == model_account.key() @ ModelRegistryError::Unauthorized,
+        constraint = inference_request.status == InferenceStatus::Fulfilled @ ModelRegistryError::NotChallengeable,
+        seeds = [b"inference", model_account.key().as_ref(), &inference_request.input_hash],
+        bump = inference_request.bump
+    )]
+    pub inference_request: Account<'info, InferenceRequest>,
+
+    #[account(
+        mut,
+        constraint = Some(provider_stake.provider) == inference_request.provider @ ModelRegistryError::Unauthorized,
+        seeds = [b"provider_stake", model_account.key().as_ref(), provider_stake.provider.as_ref()],
+        bump = provider_stake.bump
+    )]
+    pub provider_stake: Account<'info, ProviderStake>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    /// CHECK: Receives the fee refund and the rest of the slashed bond; matched by `has_one`
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+
+    // Token escrow refund; required when the fee was paid in a token
+    #[account(seeds = [b"payment_config"], bump = payment_config.bump)]
+    pub payment_config: Option<Account<'info, PaymentConfig>>,
+
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Requester's token account
+    #[account(mut)]
+    pub payout_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeInference<'info> {
+    #[account(address = inference_request.model @ ModelRegistryError::Unauthorized)]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        constraint = inference_request.status == InferenceStatus::Fulfilled @ ModelRegistryError::NotChallengeable,
+        seeds = [b"inference", inference_request.model.as_ref(), &inference_request.input_hash],
+        bump = inference_request.bump
+    )]
+    pub inference_request: Account<'info, InferenceRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"provider_stake", inference_request.model.as_ref(), provider.key().as_ref()],
+        bump = provider_stake.bump
+    )]
+    pub provider_stake: Account<'info, ProviderStake>,
+
+    /// CHECK: Paid the escrowed fee; must be the provider recorded on the request
+    #[account(
+        mut,
+        constraint = Some(provider.key()) == inference_request.provider @ ModelRegistryError::Unauthorized
+    )]
+    pub provider: AccountInfo<'info>,
+
+    /// Permissionless crank
+    pub cranker: Signer<'info>,
+
+    // Token escrow release; required when the fee was paid in a token
+    #[account(seeds = [b"payment_config"], bump = payment_config.bump)]
+    pub payment_config: Option<Account<'info, PaymentConfig>>,
+
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Provider's token account
+    #[account(mut)]
+    pub payout_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    // Forks only: the parent's vault receives the upstream royalty
+    #[account(
+        mut,
+        seeds = [b"royalty_vault", royalty_vault.model_account.as_ref()],
+        bump = royalty_vault.bump
+    )]
+    pub royalty_vault: Option<Account<'info, RoyaltyVault>>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Enable or retune optimistic fulfillment for a model
+pub fn configure(
+    ctx: Context<ConfigureOptimistic>,
+    challenge_window: i64,
+    bond: u64,
+    challenger_reward_bps: u16,
+) -> Result<()> {
+    ctx.accounts.model_account.require_unpaused()?;
+    require!(
+        challenge_window > 0 && challenge_window <= OptimisticConfig::MAX_CHALLENGE_WINDOW,
+        ModelRegistryError::InvalidChallengeConfig
+    );
+    require!(
+        bond > 0 && challenger_reward_bps <= OptimisticConfig::MAX_BPS,
+        ModelRegistryError::InvalidChallengeConfig
+    );
+
+    let config = &mut ctx.accounts.optimistic_config;
+    config.model = ctx.accounts.model_account.key();
+    config.challenge_window = challenge_window;
+    config.bond = bond;
+    config.challenger_reward_bps = challenger_reward_bps;
+    config.bump = *ctx.bumps.get("optimistic_config").unwrap();
+    Ok(())
+}
+
+/// Bond lamports so the provider may post optimistic results
+pub fn stake(ctx: Context<StakeProvider>, amount: u64) -> Result<()> {
+    ctx.accounts.model_account.require_unpaused()?;
+    invoke(
+        &system_instruction::transfer(ctx.accounts.provider.key, &ctx.accounts.provider_stake.key(), amount),
+        &[
+            ctx.accounts.provider.to_account_info(),
+            ctx.accounts.provider_stake.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let stake = &mut ctx.accounts.provider_stake;
+    stake.model = ctx.accounts.model_account.key();
+    stake.provider = ctx.accounts.provider.key();
+    stake.amount = stake.amount.checked_add(amount).ok_or(ModelRegistryError::InsufficientStake)?;
+    stake.bump = *ctx.bumps.get("provider_stake").unwrap();
+    Ok(())
+}
+
+/// Withdraw bonded lamports not backing an open fulfillment
+pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
+    let stake = &mut ctx.accounts.provider_stake;
+    require!(stake.available() >= amount, ModelRegistryError::InsufficientStake);
+    stake.amount -= amount;
+
+    **stake.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.provider.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+/// Fraud proof: show the posted proof does not verify against the registered circuit
+pub fn challenge(ctx: Context<ChallengeInference>, zk_proof: Vec<u8>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let model = &ctx.accounts.model_account;
+    let request = &ctx.accounts.inference_request;
+    model.require_unpaused()?;
+
+    // 1. Within the window, and the proof is exactly the one the provider posted
+    require!(now < request.challenge_deadline, ModelRegistryError::ChallengeWindowClosed);
+    require!(
+        solana_program::hash::hash(&zk_proof).to_bytes() == request.proof_hash,
+        ModelRegistryError::ProofMismatch
+    );
+
+    // 2. The fraud proof is the failed verification itself
+    require!(
+        !crypto::verify_zk_proof(&model.zk_circuit, &request.public_commitment(&request.output_hash), &zk_proof),
+        ModelRegistryError::ChallengeRejected
+    );
+
+    // 3. Slash the bond: challenger reward, remainder compensates the requester
+    let slashed = ctx.accounts.provider_stake.slash(request.bond);
+    let (reward, compensation) = ctx.accounts.optimistic_config.split_slash(slashed);
+    let stake_info = ctx.accounts.provider_stake.to_account_info();
+    **stake_info.try_borrow_mut_lamports()? -= slashed;
+    **ctx.accounts.challenger.try_borrow_mut_lamports()? += reward;
+    **ctx.accounts.requester.try_borrow_mut_lamports()? += compensation;
+
+    // 4. Refund the escrowed fee
+    release_escrow(
+        &ctx.accounts.inference_request,
+        &ctx.accounts.requester,
+        EscrowAccounts {
+            config: &ctx.accounts.payment_config,
+            escrow: &ctx.accounts.escrow_token_account,
+            payout: &ctx.accounts.payout_token_account,
+            token_program: &ctx.accounts.token_program,
+            royalty: None,
+        },
+    )?;
+
+    let request = &mut ctx.accounts.inference_request;
+    request.status = InferenceStatus::Challenged;
+
+    emit!(InferenceChallenged {
+        model: model.key(),
+        request: request.key(),
+        provider: ctx.accounts.provider_stake.provider,
+        challenger: ctx.accounts.challenger.key(),
+        slashed,
+        challenger_reward: reward,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Close an unchallenged window: unlock the bond and pay the provider
+pub fn finalize(ctx: Context<FinalizeInference>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let request = &ctx.accounts.inference_request;
+    require!(now >= request.challenge_deadline, ModelRegistryError::ChallengeWindowOpen);
+
+    ctx.accounts.provider_stake.unlock(request.bond);
+    let royalty = upstream_share(&ctx.accounts.model_account, &ctx.accounts.royalty_vault, request.fee)?;
+    release_escrow(
+        &ctx.accounts.inference_request,
+        &ctx.accounts.provider,
+        EscrowAccounts {
+            config: &ctx.accounts.payment_config,
+            escrow: &ctx.accounts.escrow_token_account,
+            payout: &ctx.accounts.payout_token_account,
+            token_program: &ctx.accounts.token_program,
+            royalty,
+        },
+    )?;
+
+    let request = &mut ctx.accounts.inference_request;
+    request.status = InferenceStatus::Completed;
+
+    emit!(InferenceFinalized {
+        model: request.model,
+        request: request.key(),
+        provider: ctx.accounts.provider.key(),
+        fee: request.fee,
+        fee_mint: request.fee_mint,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InferenceChallenged {
+    pub model: Pubkey,
+    pub request: Pubkey,
+    pub provider: Pubkey,
+    pub challenger: Pubkey,
+    pub slashed: u64,
+    pub challenger_reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InferenceFinalized {
+    pub model: Pubkey,
+    pub request: Pubkey,
+    pub provider: Pubkey,
+    pub fee: u64,
+    pub fee_mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Insufficient staking balance")]
+    InsufficientStake,
+    #[msg("Challenge window must be positive and bounded; reward at most 100%")]
+    InvalidChallengeConfig,
+    #[msg("Inference is not awaiting challenge")]
+    NotChallengeable,
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Submitted proof differs from the one posted by the provider")]
+    ProofMismatch,
+    #[msg("Posted proof verifies; challenge rejected")]
+    ChallengeRejected,
+    // ... (previous errors)
+}
@@ -0,0 +1,338 @@
+// contracts/programs/model_registry/src/instructions/consent.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{instructions::pause::NotPaused, state::*, AdminAccount};
+
+/// Upper bound on jurisdictions a contribution may be processed in
+pub const MAX_CONSENT_JURISDICTIONS: usize = 16;
+/// Upper bound on retention: ten years
+pub const MAX_RETENTION_SECS: i64 = 10 * 365 * 86_400;
+
+/// Purposes a contributor can consent to, as `ConsentPolicy::purposes` bits
+pub const PURPOSE_TRAINING: u8 = 1 << 0;
+pub const PURPOSE_EVALUATION: u8 = 1 << 1;
+pub const PURPOSE_RESEARCH: u8 = 1 << 2;
+pub const PURPOSE_COMMERCIAL: u8 = 1 << 3;
+const ALL_PURPOSES: u8 = PURPOSE_TRAINING | PURPOSE_EVALUATION | PURPOSE_RESEARCH | PURPOSE_COMMERCIAL;
+
+#[derive(Accounts)]
+#[instruction(data_hash: [u8; 32])]
+pub struct RecordConsent<'info> {
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + ConsentRecord::LEN,
+        seeds = [b"consent", model_account.key().as_ref(), &data_hash],
+        bump
+    )]
+    pub consent: Account<'info, ConsentRecord>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawConsent<'info> {
+    #[account(
+        mut,
+        seeds = [b"consent", consent.model.as_ref(), &consent.data_hash],
+        bump = consent.bump,
+        has_one = contributor @ ModelRegistryError::Unauthorized
+    )]
+    pub consent: Account<'info, ConsentRecord>,
+
+    pub contributor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(aggregator: Pubkey)]
+pub struct SetAggregatorJurisdiction<'info> {
+    #[account(seeds = [b"admin"], bump = admin.bump)]
+    pub admin: Account<'info, AdminAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AggregatorJurisdiction::LEN,
+        seeds = [b"jurisdiction", aggregator.as_ref()],
+        bump
+    )]
+    pub jurisdiction: Account<'info, AggregatorJurisdiction>,
+
+    /// The multisig vault once `initialize_multisig` has run
+    #[account(address = admin.authority @ ModelRegistryError::Unauthorized)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AuthorizeDecryption<'info> {
+    #[account(
+        seeds = [b"consent", consent.model.as_ref(), &consent.data_hash],
+        bump = consent.bump
+    )]
+    pub consent: Account<'info, ConsentRecord>,
+
+    #[account(
+        seeds = [b"jurisdiction", aggregator.key().as_ref()],
+        bump = jurisdiction.bump
+    )]
+    pub jurisdiction: Account<'info, AggregatorJurisdiction>,
+
+    #[account(
+        init_if_needed,
+        payer = aggregator,
+        space = 8 + DecryptionGrant::LEN,
+        seeds = [b"decrypt", consent.key().as_ref(), aggregator.key().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, DecryptionGrant>,
+
+    #[account(mut)]
+    pub aggregator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Anchor the contributor's policy for a contribution; the retention clock
+/// starts now
+pub fn record(ctx: Context<RecordConsent>, data_hash: [u8; 32], policy: ConsentPolicy) -> Result<()> {
+    policy.validate()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let consent = &mut ctx.accounts.consent;
+    consent.model = ctx.accounts.model_account.key();
+    consent.contributor = ctx.accounts.contributor.key();
+    consent.data_hash = data_hash;
+    consent.granted_at = now;
+    consent.expires_at = now + policy.retention_secs;
+    consent.policy = policy;
+    consent.withdrawn = false;
+    consent.bump = *ctx.bumps.get("consent").unwrap();
+
+    emit!(ConsentRecorded {
+        model: consent.model,
+        contributor: consent.contributor,
+        data_hash,
+        purposes: consent.policy.purposes,
+        jurisdictions: consent.policy.jurisdictions.clone(),
+        expires_at: consent.expires_at,
+    });
+
+    Ok(())
+}
+
+/// End consent early: no new decryptions, and the blob is deleted as if its
+/// retention had run out. Grants already issued lapse with it.
+pub fn withdraw(ctx: Context<WithdrawConsent>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let consent = &mut ctx.accounts.consent;
+    require!(!consent.withdrawn, ModelRegistryError::ConsentExpired);
+
+    consent.withdrawn = true;
+    consent.expires_at = consent.expires_at.min(now);
+
+    emit!(ConsentWithdrawn {
+        model: consent.model,
+        contributor: consent.contributor,
+        data_hash: consent.data_hash,
+        expires_at: consent.expires_at,
+    });
+
+    Ok(())
+}
+
+/// Record where an aggregator decrypts, from the operator's attestation (admin vault)
+pub fn set_jurisdiction(
+    ctx: Context<SetAggregatorJurisdiction>,
+    aggregator: Pubkey,
+    jurisdiction: [u8; 2],
+) -> Result<()> {
+    require!(is_country_code(&jurisdiction), ModelRegistryError::InvalidConsentPolicy);
+
+    let record = &mut ctx.accounts.jurisdiction;
+    record.aggregator = aggregator;
+    record.jurisdiction = jurisdiction;
+    record.updated_at = Clock::get()?.unix_timestamp;
+    record.bump = *ctx.bumps.get("jurisdiction").unwrap();
+
+    Ok(())
+}
+
+/// Issue the grant key custodians check before releasing a contribution's
+/// data key: consent is live, covers `purpose`, and the aggregator's attested
+/// jurisdiction is allowed
+pub fn authorize(ctx: Context<AuthorizeDecryption>, purpose: u8) -> Result<()> {
+    let consent = &ctx.accounts.consent;
+    let jurisdiction = ctx.accounts.jurisdiction.jurisdiction;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!consent.withdrawn && now < consent.expires_at, ModelRegistryError::ConsentExpired);
+    require!(
+        purpose.count_ones() == 1 && consent.policy.purposes & purpose != 0,
+        ModelRegistryError::PurposeNotConsented
+    );
+    require!(consent.policy.allows(&jurisdiction), ModelRegistryError::DataResidencyConflict);
+
+    let grant = &mut ctx.accounts.grant;
+    grant.consent = consent.key();
+    grant.aggregator = ctx.accounts.aggregator.key();
+    grant.jurisdiction = jurisdiction;
+    grant.purpose = purpose;
+    grant.granted_at = now;
+    grant.expires_at = consent.expires_at;
+    grant.bump = *ctx.bumps.get("grant").unwrap();
+
+    emit!(DecryptionAuthorized {
+        consent: grant.consent,
+        aggregator: grant.aggregator,
+        jurisdiction,
+        purpose,
+        expires_at: grant.expires_at,
+    });
+
+    Ok(())
+}
+
+/// Machine-readable terms a contributor attaches to a contribution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct ConsentPolicy {
+    /// Seconds the encrypted blob may be kept after consent is recorded
+    pub retention_secs: i64,
+    /// `PURPOSE_*` bits
+    pub purposes: u8,
+    /// ISO 3166-1 alpha-2 codes where it may be decrypted; empty for anywhere
+    pub jurisdictions: Vec<[u8; 2]>,
+}
+
+impl ConsentPolicy {
+    pub const LEN: usize = 8 + 1 + (4 + MAX_CONSENT_JURISDICTIONS * 2);
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.retention_secs > 0 && self.retention_secs <= MAX_RETENTION_SECS,
+            ModelRegistryError::InvalidConsentPolicy
+        );
+        require!(
+            self.purposes != 0 && self.purposes & !ALL_PURPOSES == 0,
+            ModelRegistryError::InvalidConsentPolicy
+        );
+        require!(
+            self.jurisdictions.len() <= MAX_CONSENT_JURISDICTIONS && self.jurisdictions.iter().all(is_country_code),
+            ModelRegistryError::InvalidConsentPolicy
+        );
+        Ok(())
+    }
+
+    pub fn allows(&self, jurisdiction: &[u8; 2]) -> bool {
+        self.jurisdictions.is_empty() || self.jurisdictions.contains(jurisdiction)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct ConsentRecord {
+    pub model: Pubkey,
+    pub contributor: Pubkey,
+    pub data_hash: [u8; 32],
+    pub policy: ConsentPolicy,
+    pub granted_at: i64,
+    /// Retention deadline; moved up to the withdrawal time on withdrawal
+    pub expires_at: i64,
+    pub withdrawn: bool,
+    pub bump: u8,
+}
+
+impl ConsentRecord {
+    pub const LEN: usize = 32 + 32 + 32 + ConsentPolicy::LEN + 8 + 8 + 1 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct AggregatorJurisdiction {
+    pub aggregator: Pubkey,
+    pub jurisdiction: [u8; 2],
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl AggregatorJurisdiction {
+    pub const LEN: usize = 32 + 2 + 8 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct DecryptionGrant {
+    pub consent: Pubkey,
+    pub aggregator: Pubkey,
+    pub jurisdiction: [u8; 2],
+    pub purpose: u8,
+    pub granted_at: i64,
+    /// Custodians must also re-check the consent record: withdrawal does not
+    /// rewrite grants
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl DecryptionGrant {
+    pub const LEN: usize = 32 + 32 + 2 + 1 + 8 + 8 + 1;
+}
+
+fn is_country_code(code: &[u8; 2]) -> bool {
+    code.iter().all(u8::is_ascii_uppercase)
+}
+
+#[event]
+pub struct ConsentRecorded {
+    pub model: Pubkey,
+    pub contributor: Pubkey,
+    pub data_hash: [u8; 32],
+    pub purposes: u8,
+    pub jurisdictions: Vec<[u8; 2]>,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ConsentWithdrawn {
+    pub model: Pubkey,
+    pub contributor: Pubkey,
+    pub data_hash: [u8; 32],
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct DecryptionAuthorized {
+    pub consent: Pubkey,
+    pub aggregator: Pubkey,
+    pub jurisdiction: [u8; 2],
+    pub purpose: u8,
+    pub expires_at: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Consent policy has no purpose, an invalid jurisdiction or an out-of-range retention")]
+    InvalidConsentPolicy,
+    #[msg("Contributor consent has expired or been withdrawn")]
+    ConsentExpired,
+    #[msg("Contributor did not consent to this purpose")]
+    PurposeNotConsented,
+    #[msg("Data residency rule violation")]
+    DataResidencyConflict,
+    // ... (previous errors)
+}
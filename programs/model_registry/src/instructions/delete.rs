@@ -3,6 +3,7 @@
 use anchor_lang::prelude::*;
 use solana_program::{program::invoke, system_instruction};
 use crate::{
+    instructions::pause::NotPaused,
     state::*,
     utils::{deposits, governance, crypto},
     ModelRegistryError,
@@ -23,7 +24,7 @@ pub struct DeleteModel<'info> {
 
     #[account(
         mut,
-        seeds = [b"model", &model.model_hash],
+        seeds = [b"model", &model.seed_hash],
         bump = model.bump
     )]
     pub model_pda: AccountInfo<'info>,
@@ -50,9 +51,13 @@ pub struct DeleteModel<'info> {
 
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
 }
 
 pub fn handler(ctx: Context<DeleteModel>, force: bool) -> Result<()> {
+    ctx.accounts.model.require_unpaused()?;
+
     // Validate deletion conditions
     require!(
         validate_deletion_conditions(
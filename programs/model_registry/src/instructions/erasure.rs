@@ -0,0 +1,107 @@
+// contracts/programs/model_registry/src/instructions/erasure.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{
+    instructions::{consent::ConsentRecord, pause::NotPaused},
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct RevokeContribution<'info> {
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// Closed: the encrypted data stored with it goes with the account
+    #[account(
+        mut,
+        close = contributor,
+        has_one = contributor @ ModelRegistryError::Unauthorized
+    )]
+    pub contribution_account: Account<'info, Contribution>,
+
+    /// Outlives the contribution so the erasure stays auditable, and so the
+    /// same data hash cannot be revoked twice
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + ContributionRevocation::LEN,
+        seeds = [b"revocation", model_account.key().as_ref(), &contribution_account.hash],
+        bump
+    )]
+    pub revocation: Account<'info, ContributionRevocation>,
+
+    /// Withdrawn with the contribution, so no further decryption is authorized
+    #[account(
+        mut,
+        seeds = [b"consent", model_account.key().as_ref(), &contribution_account.hash],
+        bump = consent.bump
+    )]
+    pub consent: Option<Account<'info, ConsentRecord>>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Right to erasure: close the contribution and leave a revocation record
+/// that aggregators exclude and the indexer purges off-chain copies for
+pub fn revoke(ctx: Context<RevokeContribution>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let contribution = &ctx.accounts.contribution_account;
+
+    let revocation = &mut ctx.accounts.revocation;
+    revocation.model = ctx.accounts.model_account.key();
+    revocation.contributor = contribution.contributor;
+    revocation.data_hash = contribution.hash;
+    revocation.records_root = contribution.records_root;
+    revocation.revoked_at = now;
+    revocation.bump = *ctx.bumps.get("revocation").unwrap();
+
+    if let Some(consent) = ctx.accounts.consent.as_mut() {
+        consent.withdrawn = true;
+        consent.expires_at = consent.expires_at.min(now);
+    }
+
+    emit!(ContributionRevoked {
+        model: revocation.model,
+        contributor: revocation.contributor,
+        data_hash: revocation.data_hash,
+        revoked_at: now,
+    });
+
+    Ok(())
+}
+
+#[account]
+#[derive(Default)]
+pub struct ContributionRevocation {
+    pub model: Pubkey,
+    pub contributor: Pubkey,
+    pub data_hash: [u8; 32],
+    /// Lets a contributor prove a record they still hold was part of what was erased
+    pub records_root: [u8; 32],
+    pub revoked_at: i64,
+    pub bump: u8,
+}
+
+impl ContributionRevocation {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 1;
+}
+
+#[event]
+pub struct ContributionRevoked {
+    pub model: Pubkey,
+    pub contributor: Pubkey,
+    pub data_hash: [u8; 32],
+    pub revoked_at: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    // ... (previous errors)
+}
@@ -0,0 +1,616 @@
+// contracts/programs/model_registry/src/instructions/federation.rs
+
+use anchor_lang::prelude::*;
+use solana_program::{hash::hashv, sysvar::{clock::Clock, slot_hashes}};
+use crate::{
+    instructions::pause::NotPaused,
+    state::*,
+    utils::ecvrf,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct OpenFederatedRound<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FederatedRound::space(),
+        seeds = [b"federated_round", model_account.key().as_ref(), &round.to_le_bytes()],
+        bump
+    )]
+    pub federated_round: Account<'info, FederatedRound>,
+
+    /// Owner or ACL Contributor of the model
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitRoundUpdate<'info> {
+    #[account(address = federated_round.model @ ModelRegistryError::Unauthorized)]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"federated_round", federated_round.model.as_ref(), &federated_round.round.to_le_bytes()],
+        bump = federated_round.bump
+    )]
+    pub federated_round: Account<'info, FederatedRound>,
+
+    #[account(
+        init,
+        payer = participant,
+        space = RoundUpdate::space(),
+        seeds = [b"round_update", federated_round.key().as_ref(), participant.key().as_ref()],
+        bump
+    )]
+    pub round_update: Account<'info, RoundUpdate>,
+
+    /// A registered data contributor or an ACL Contributor of the model
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Permissionless: applies whichever deadline has passed
+#[derive(Accounts)]
+pub struct CloseFederatedRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"federated_round", federated_round.model.as_ref(), &federated_round.round.to_le_bytes()],
+        bump = federated_round.bump
+    )]
+    pub federated_round: Account<'info, FederatedRound>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeFederatedRound<'info> {
+    #[account(address = federated_round.model @ ModelRegistryError::Unauthorized)]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"federated_round", federated_round.model.as_ref(), &federated_round.round.to_le_bytes()],
+        bump = federated_round.bump
+    )]
+    pub federated_round: Account<'info, FederatedRound>,
+
+    /// CHECK: The round's aggregator lottery, pinned by address so it cannot
+    /// be left out; once opened, only its winners may finalize
+    #[account(
+        seeds = [b"aggregation_round", federated_round.model.as_ref(), &federated_round.round.to_le_bytes()],
+        bump
+    )]
+    pub aggregation_round: UncheckedAccount<'info>,
+
+    pub aggregator: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ScoreContribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"federated_round", federated_round.model.as_ref(), &federated_round.round.to_le_bytes()],
+        bump = federated_round.bump
+    )]
+    pub federated_round: Account<'info, FederatedRound>,
+
+    #[account(
+        mut,
+        seeds = [b"round_update", federated_round.key().as_ref(), round_update.participant.as_ref()],
+        bump = round_update.bump
+    )]
+    pub round_update: Account<'info, RoundUpdate>,
+
+    /// The aggregator that finalized the round
+    pub aggregator: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Permissionless: records a scored update's share once the whole round is scored
+#[derive(Accounts)]
+pub struct CreditContribution<'info> {
+    #[account(
+        seeds = [b"federated_round", federated_round.model.as_ref(), &federated_round.round.to_le_bytes()],
+        bump = federated_round.bump
+    )]
+    pub federated_round: Account<'info, FederatedRound>,
+
+    #[account(
+        mut,
+        seeds = [b"round_update", federated_round.key().as_ref(), round_update.participant.as_ref()],
+        bump = round_update.bump
+    )]
+    pub round_update: Account<'info, RoundUpdate>,
+
+    /// The version the round produced
+    #[account(
+        mut,
+        constraint = version_metadata.model_hash == federated_round.aggregate_hash @ ModelRegistryError::Unauthorized,
+        seeds = [b"version", federated_round.model.as_ref(), &version_metadata.version.to_le_bytes()],
+        bump = version_metadata.bump
+    )]
+    pub version_metadata: Account<'info, VersionMetadata>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct OpenAggregationRound<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AggregationRound::space(),
+        seeds = [b"aggregation_round", model_account.key().as_ref(), &round.to_le_bytes()],
+        bump
+    )]
+    pub aggregation_round: Account<'info, AggregationRound>,
+
+    /// Owner or ACL Contributor of the model
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Read raw for the most recent slot hash; pinned by address
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitVrf<'info> {
+    #[account(
+        mut,
+        seeds = [b"aggregation_round", aggregation_round.model.as_ref(), &aggregation_round.round.to_le_bytes()],
+        bump = aggregation_round.bump
+    )]
+    pub aggregation_round: Account<'info, AggregationRound>,
+
+    #[account(
+        init,
+        payer = node,
+        space = VrfCommitment::space(),
+        seeds = [b"vrf_commitment", aggregation_round.key().as_ref(), node.key().as_ref()],
+        bump
+    )]
+    pub vrf_commitment: Account<'info, VrfCommitment>,
+
+    /// Draws are weighted by the node's bond on the model
+    #[account(
+        seeds = [b"provider_stake", aggregation_round.model.as_ref(), node.key().as_ref()],
+        bump = provider_stake.bump
+    )]
+    pub provider_stake: Account<'info, ProviderStake>,
+
+    #[account(mut)]
+    pub node: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealVrf<'info> {
+    #[account(
+        mut,
+        seeds = [b"aggregation_round", aggregation_round.model.as_ref(), &aggregation_round.round.to_le_bytes()],
+        bump = aggregation_round.bump
+    )]
+    pub aggregation_round: Account<'info, AggregationRound>,
+
+    #[account(
+        mut,
+        has_one = node @ ModelRegistryError::Unauthorized,
+        seeds = [b"vrf_commitment", aggregation_round.key().as_ref(), node.key().as_ref()],
+        bump = vrf_commitment.bump
+    )]
+    pub vrf_commitment: Account<'info, VrfCommitment>,
+
+    #[account(
+        seeds = [b"provider_stake", aggregation_round.model.as_ref(), node.key().as_ref()],
+        bump = provider_stake.bump
+    )]
+    pub provider_stake: Account<'info, ProviderStake>,
+
+    pub node: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Longest commit or reveal phase
+const MAX_PHASE_SECS: i64 = 24 * 60 * 60;
+
+/// Open federated round `round`: updates are accepted for `collect_window`
+/// seconds, then an aggregate for `aggregate_window` more. Participants split
+/// `contributor_share` percent of the resulting version's rewards by score.
+pub fn open_federated_round(
+    ctx: Context<OpenFederatedRound>,
+    round: u64,
+    collect_window: i64,
+    aggregate_window: i64,
+    min_participants: u32,
+    contributor_share: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let model = &ctx.accounts.model_account;
+    model.check_access(ctx.accounts.authority.key, AccessLevel::Contributor)?;
+    require!(
+        (1..=MAX_PHASE_SECS).contains(&collect_window) && (1..=MAX_PHASE_SECS).contains(&aggregate_window),
+        ModelRegistryError::InvalidRoundConfig
+    );
+    require!(min_participants > 0 && contributor_share <= 100, ModelRegistryError::InvalidRoundConfig);
+
+    let federated_round = &mut ctx.accounts.federated_round;
+    federated_round.model = model.key();
+    federated_round.round = round;
+    federated_round.status = FederatedRoundStatus::Collecting;
+    federated_round.opened_at = now;
+    federated_round.collect_deadline = now + collect_window;
+    federated_round.aggregate_deadline = now + collect_window + aggregate_window;
+    federated_round.min_participants = min_participants;
+    federated_round.contributor_share = contributor_share;
+    federated_round.bump = *ctx.bumps.get("federated_round").unwrap();
+
+    emit!(FederatedRoundChanged {
+        model: model.key(),
+        round,
+        status: FederatedRoundStatus::Collecting,
+        participants: 0,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Record a participant's update commitment while the round is collecting.
+/// Only the model's contributors may take part, so strangers cannot pad the
+/// quorum or leave updates the aggregator must score before crediting.
+pub fn submit_round_update(ctx: Context<SubmitRoundUpdate>, update_hash: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let participant = ctx.accounts.participant.key();
+    let model = &ctx.accounts.model_account;
+    if !model.contributors.contains(&participant) {
+        model.check_access(&participant, AccessLevel::Contributor)?;
+    }
+
+    let round = &mut ctx.accounts.federated_round;
+    require!(
+        round.status_at(now) == FederatedRoundStatus::Collecting,
+        ModelRegistryError::FederatedRoundTimeout
+    );
+
+    let update = &mut ctx.accounts.round_update;
+    update.round = round.key();
+    update.participant = participant;
+    update.update_hash = update_hash;
+    update.submitted_at = now;
+    update.bump = *ctx.bumps.get("round_update").unwrap();
+    round.participants += 1;
+
+    Ok(())
+}
+
+/// Move a round past a deadline: to Aggregating with quorum, else to Expired
+pub fn close_federated_round(ctx: Context<CloseFederatedRound>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let round = &mut ctx.accounts.federated_round;
+    let status = round.status_at(now);
+    require!(status != round.status, ModelRegistryError::RoundStillOpen);
+    round.status = status;
+
+    emit!(FederatedRoundChanged {
+        model: round.model,
+        round: round.round,
+        status,
+        participants: round.participants,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Publish the round's aggregate before `aggregate_deadline`. Rounds with an
+/// aggregator lottery take it from a winner once reveals have closed; rounds
+/// without one from any Contributor.
+pub fn finalize_federated_round(ctx: Context<FinalizeFederatedRound>, aggregate_hash: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let aggregator = ctx.accounts.aggregator.key();
+    let lottery = ctx.accounts.aggregation_round.to_account_info();
+    if lottery.owner == &crate::ID && !lottery.data_is_empty() {
+        let lottery = Account::<AggregationRound>::try_from(&lottery)?;
+        require!(lottery.reveal_deadline <= now, ModelRegistryError::LotteryPending);
+        require!(lottery.is_selected(&aggregator), ModelRegistryError::Unauthorized);
+    } else {
+        ctx.accounts.model_account.check_access(&aggregator, AccessLevel::Contributor)?;
+    }
+
+    let round = &mut ctx.accounts.federated_round;
+    match round.status_at(now) {
+        FederatedRoundStatus::Aggregating => {}
+        FederatedRoundStatus::Collecting => return err!(ModelRegistryError::RoundStillOpen),
+        FederatedRoundStatus::Finalized => return err!(ModelRegistryError::RoundFinalized),
+        FederatedRoundStatus::Expired => return err!(ModelRegistryError::FederatedRoundTimeout),
+    }
+    round.status = FederatedRoundStatus::Finalized;
+    round.aggregate_hash = aggregate_hash;
+    round.aggregator = Some(aggregator);
+
+    emit!(FederatedRoundChanged {
+        model: round.model,
+        round: round.round,
+        status: FederatedRoundStatus::Finalized,
+        participants: round.participants,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Commit the aggregator's score for one update of a finalized round
+pub fn score_contribution(ctx: Context<ScoreContribution>, score: u64) -> Result<()> {
+    let round = &mut ctx.accounts.federated_round;
+    require!(round.status == FederatedRoundStatus::Finalized, ModelRegistryError::RoundStillOpen);
+    require!(
+        round.aggregator == Some(ctx.accounts.aggregator.key()),
+        ModelRegistryError::Unauthorized
+    );
+
+    let update = &mut ctx.accounts.round_update;
+    require!(!update.scored, ModelRegistryError::AlreadyScored);
+    update.score = score;
+    update.scored = true;
+    round.total_score = round.total_score.checked_add(score).ok_or(ModelRegistryError::ArithmeticOverflow)?;
+    round.scored += 1;
+
+    emit!(ContributionScored {
+        model: round.model,
+        round: round.round,
+        participant: update.participant,
+        score,
+    });
+
+    Ok(())
+}
+
+/// Add a participant to the produced version's reward_shares, weighted by
+/// its score over the round's total
+pub fn credit_contribution(ctx: Context<CreditContribution>) -> Result<()> {
+    let round = &ctx.accounts.federated_round;
+    require!(round.scored == round.participants, ModelRegistryError::ScoresPending);
+
+    let update = &mut ctx.accounts.round_update;
+    require!(!update.credited, ModelRegistryError::AlreadyCredited);
+    let share = round.reward_share(update.score);
+    if share > 0 {
+        ctx.accounts.version_metadata.add_contributor(update.participant, share)?;
+    }
+    update.credited = true;
+
+    emit!(ContributionCredited {
+        model: round.model,
+        round: round.round,
+        participant: update.participant,
+        share,
+    });
+
+    Ok(())
+}
+
+/// Open the aggregator lottery for `round`. The VRF input is fixed from the
+/// latest slot hash, so nodes cannot learn their draw before the round exists.
+pub fn open_round(
+    ctx: Context<OpenAggregationRound>,
+    round: u64,
+    commit_window: i64,
+    reveal_window: i64,
+    aggregators: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let model = &ctx.accounts.model_account;
+    model.check_access(ctx.accounts.authority.key, AccessLevel::Contributor)?;
+    require!(
+        (1..=MAX_PHASE_SECS).contains(&commit_window) && (1..=MAX_PHASE_SECS).contains(&reveal_window),
+        ModelRegistryError::InvalidRoundConfig
+    );
+    require!(
+        (1..=AggregationRound::MAX_AGGREGATORS).contains(&aggregators),
+        ModelRegistryError::InvalidRoundConfig
+    );
+
+    // [len u64, then (slot u64, hash [u8; 32]) newest first]
+    let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+    let recent = data.get(16..48).ok_or(ModelRegistryError::InvalidRoundConfig)?;
+    let seed = hashv(&[model.key().as_ref(), &round.to_le_bytes(), recent]).to_bytes();
+
+    let aggregation_round = &mut ctx.accounts.aggregation_round;
+    aggregation_round.model = model.key();
+    aggregation_round.round = round;
+    aggregation_round.seed = seed;
+    aggregation_round.commit_deadline = now + commit_window;
+    aggregation_round.reveal_deadline = now + commit_window + reveal_window;
+    aggregation_round.aggregators = aggregators;
+    aggregation_round.bump = *ctx.bumps.get("aggregation_round").unwrap();
+
+    emit!(AggregationRoundOpened {
+        model: model.key(),
+        round,
+        seed,
+        commit_deadline: aggregation_round.commit_deadline,
+        reveal_deadline: aggregation_round.reveal_deadline,
+        aggregators,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Seal a VRF proof over the round's input; `commitment` is the SHA-256 of the proof.
+/// The node's unlocked bond at this point caps its weight in the draw.
+pub fn commit_vrf(ctx: Context<CommitVrf>, commitment: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let round = &mut ctx.accounts.aggregation_round;
+    require!(now < round.commit_deadline, ModelRegistryError::CommitPhaseClosed);
+    let stake = &ctx.accounts.provider_stake;
+    let bonded = stake.amount.saturating_sub(stake.locked);
+    require!(bonded > 0, ModelRegistryError::InsufficientStake);
+
+    let sealed = &mut ctx.accounts.vrf_commitment;
+    sealed.round = round.key();
+    sealed.node = ctx.accounts.node.key();
+    sealed.commitment = commitment;
+    sealed.stake = bonded;
+    sealed.bump = *ctx.bumps.get("vrf_commitment").unwrap();
+    round.commitments += 1;
+
+    Ok(())
+}
+
+/// Open a commitment once the commit phase has closed. A valid proof enters
+/// the draw weighted by the smaller of the committed and the current bond, so
+/// withdrawing after committing does not keep the weight.
+pub fn reveal_vrf(ctx: Context<RevealVrf>, proof: Vec<u8>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let round = &mut ctx.accounts.aggregation_round;
+    require!(
+        round.commit_deadline <= now && now < round.reveal_deadline,
+        ModelRegistryError::RevealPhaseClosed
+    );
+    let sealed = &mut ctx.accounts.vrf_commitment;
+    require!(!sealed.revealed, ModelRegistryError::AlreadyRevealed);
+
+    // 1. Proof opens the commitment and verifies under the node's key
+    let proof: [u8; ecvrf::PROOF_LEN] = proof.try_into().map_err(|_| ModelRegistryError::InvalidProof)?;
+    require!(hashv(&[&proof]).to_bytes() == sealed.commitment, ModelRegistryError::InvalidProof);
+    let output = ecvrf::verify(&sealed.node, &round.alpha(), &proof).ok_or(ModelRegistryError::InvalidProof)?;
+
+    // 2. Enter the draw
+    let stake = &ctx.accounts.provider_stake;
+    let weight = sealed.stake.min(stake.amount.saturating_sub(stake.locked));
+    require!(weight > 0, ModelRegistryError::InsufficientStake);
+    let score = draw_score(&output);
+    round.consider(AggregatorDraw { node: sealed.node, score, stake: weight });
+    sealed.revealed = true;
+    round.reveals += 1;
+
+    emit!(VrfRevealed {
+        model: round.model,
+        round: round.round,
+        node: sealed.node,
+        score,
+        stake: weight,
+        selected: round.is_selected(&sealed.node),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct FederatedRoundChanged {
+    pub model: Pubkey,
+    pub round: u64,
+    pub status: FederatedRoundStatus,
+    pub participants: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionScored {
+    pub model: Pubkey,
+    pub round: u64,
+    pub participant: Pubkey,
+    pub score: u64,
+}
+
+#[event]
+pub struct ContributionCredited {
+    pub model: Pubkey,
+    pub round: u64,
+    pub participant: Pubkey,
+    /// Percent of the version's rewards
+    pub share: u8,
+}
+
+#[event]
+pub struct AggregationRoundOpened {
+    pub model: Pubkey,
+    pub round: u64,
+    pub seed: [u8; 32],
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub aggregators: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VrfRevealed {
+    pub model: Pubkey,
+    pub round: u64,
+    pub node: Pubkey,
+    pub score: u64,
+    pub stake: u64,
+    /// Among the winners at the time of this reveal; later reveals may displace it
+    pub selected: bool,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Invalid zero-knowledge proof")]
+    InvalidProof,
+    #[msg("Insufficient staking balance")]
+    InsufficientStake,
+    #[msg("Round phases must be positive and at most a day; 1-8 aggregators; a positive quorum; shares at most 100%")]
+    InvalidRoundConfig,
+    #[msg("Commit phase has closed")]
+    CommitPhaseClosed,
+    #[msg("Reveal phase is not open")]
+    RevealPhaseClosed,
+    #[msg("VRF commitment already revealed")]
+    AlreadyRevealed,
+    #[msg("Aggregator lottery is still drawing")]
+    LotteryPending,
+    #[msg("Federated round timeout")]
+    FederatedRoundTimeout,
+    #[msg("Federated round has not reached a deadline")]
+    RoundStillOpen,
+    #[msg("Federated round already finalized")]
+    RoundFinalized,
+    #[msg("Contribution already scored")]
+    AlreadyScored,
+    #[msg("Contribution already credited")]
+    AlreadyCredited,
+    #[msg("Every update in the round must be scored first")]
+    ScoresPending,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    // ... (previous errors)
+}
@@ -0,0 +1,303 @@
+// contracts/programs/model_registry/src/instructions/folded.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{
+    instructions::pause::NotPaused,
+    state::*,
+    utils::bn254::{self, Groth16VerifyingKey, Scalar, G1, G2},
+};
+
+/// Upper bound on decider public inputs, and so on `ic` entries
+pub const MAX_FOLDED_PUBLIC_INPUTS: usize = 48;
+/// State slots 0 and 1 carry the model commitment and the input/output hash
+pub const MIN_FOLDED_STATE_LEN: usize = 2;
+/// Upper bound on the Nova state width
+pub const MAX_FOLDED_STATE_LEN: usize = 8;
+
+#[derive(Accounts)]
+pub struct RegisterFoldedVerifier<'info> {
+    #[account(
+        constraint = model_account.owner == authority.key() @ ModelRegistryError::Unauthorized
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FoldedVerifierKey::LEN,
+        seeds = [b"folded_vk", model_account.key().as_ref()],
+        bump
+    )]
+    pub verifier_key: Account<'info, FoldedVerifierKey>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(input_hash: [u8; 32])]
+pub struct SubmitFoldedInference<'info> {
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        seeds = [b"folded_vk", model_account.key().as_ref()],
+        bump = verifier_key.bump,
+        has_one = model_account @ ModelRegistryError::CircuitMismatch
+    )]
+    pub verifier_key: Account<'info, FoldedVerifierKey>,
+
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + FoldedInferenceRecord::LEN,
+        seeds = [b"folded", model_account.key().as_ref(), &input_hash],
+        bump
+    )]
+    pub record: Account<'info, FoldedInferenceRecord>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Store the decider verifying key for a model's folded inference proofs
+pub fn register_verifier(
+    ctx: Context<RegisterFoldedVerifier>,
+    circuit_hash: [u8; 32],
+    state_len: u8,
+    vk: FoldedVk,
+) -> Result<()> {
+    // 1. Key must belong to the circuit registered for the model
+    ctx.accounts.model_account.require_unpaused()?;
+    require!(
+        circuit_hash == ctx.accounts.model_account.zk_circuit,
+        ModelRegistryError::CircuitMismatch
+    );
+    require!(
+        (MIN_FOLDED_STATE_LEN..=MAX_FOLDED_STATE_LEN).contains(&(state_len as usize)),
+        ModelRegistryError::InvalidFoldedProof
+    );
+
+    // 2. ic covers [num_steps, z_0, z_n, KZG terms, commitment limbs, extras]
+    let min_inputs = FoldedProof::fixed_inputs(state_len as usize);
+    require!(
+        vk.ic.len() > min_inputs && vk.ic.len() <= MAX_FOLDED_PUBLIC_INPUTS + 1,
+        ModelRegistryError::InvalidFoldedProof
+    );
+
+    let key = &mut ctx.accounts.verifier_key;
+    key.model_account = ctx.accounts.model_account.key();
+    key.circuit_hash = circuit_hash;
+    key.state_len = state_len;
+    key.vk = vk;
+    key.bump = *ctx.bumps.get("verifier_key").unwrap();
+
+    emit!(FoldedVerifierRegistered {
+        model: key.model_account,
+        circuit_hash,
+        public_inputs: (key.vk.ic.len() - 1) as u8,
+    });
+
+    Ok(())
+}
+
+/// Verify a folded multi-layer inference proof and record its output
+pub fn submit_folded(
+    ctx: Context<SubmitFoldedInference>,
+    input_hash: [u8; 32],
+    proof: FoldedProof,
+) -> Result<()> {
+    let key = &ctx.accounts.verifier_key;
+    let model = &ctx.accounts.model_account;
+    let state_len = key.state_len as usize;
+    model.require_unpaused()?;
+
+    // 1. Shape checks
+    require!(
+        proof.num_steps > 0 && proof.z_0.len() == state_len && proof.z_n.len() == state_len,
+        ModelRegistryError::InvalidFoldedProof
+    );
+
+    // 2. Bind the initial state to this model and input
+    require!(
+        proof.z_0[0] == bn254::hash_to_scalar(&model.model_hash)
+            && proof.z_0[1] == bn254::hash_to_scalar(&input_hash),
+        ModelRegistryError::InvalidFoldedProof
+    );
+
+    // 3. KZG openings of the folded witness and error commitments
+    for (i, commitment) in [proof.cm_w, proof.cm_e].iter().enumerate() {
+        require!(
+            bn254::verify_kzg_opening(
+                commitment,
+                &proof.kzg_challenges[i],
+                &proof.kzg_evals[i],
+                &proof.kzg_proofs[i],
+                &key.vk.kzg_g2,
+                &key.vk.kzg_tau_g2,
+            )?,
+            ModelRegistryError::ZkVerificationFailure
+        );
+    }
+
+    // 4. Groth16 decider proof over the full public input vector
+    let vk = Groth16VerifyingKey {
+        alpha_g1: &key.vk.alpha_g1,
+        beta_g2: &key.vk.beta_g2,
+        gamma_g2: &key.vk.gamma_g2,
+        delta_g2: &key.vk.delta_g2,
+        ic: &key.vk.ic,
+    };
+    require!(
+        bn254::verify_groth16(&vk, &proof.proof_a, &proof.proof_b, &proof.proof_c, &proof.public_inputs())?,
+        ModelRegistryError::ZkVerificationFailure
+    );
+
+    // 5. Record the verified output commitment
+    let record = &mut ctx.accounts.record;
+    record.model = model.key();
+    record.submitter = ctx.accounts.submitter.key();
+    record.input_hash = input_hash;
+    record.output_commitment = proof.z_n[1];
+    record.num_steps = proof.num_steps;
+    record.verified_at = Clock::get()?.unix_timestamp;
+    record.bump = *ctx.bumps.get("record").unwrap();
+
+    emit!(FoldedInferenceVerified {
+        model: record.model,
+        record: record.key(),
+        num_steps: record.num_steps,
+        output_commitment: record.output_commitment,
+    });
+
+    Ok(())
+}
+
+/// Groth16 key of the Nova decider circuit plus the KZG verifier points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct FoldedVk {
+    pub alpha_g1: G1,
+    pub beta_g2: G2,
+    pub gamma_g2: G2,
+    pub delta_g2: G2,
+    pub ic: Vec<G1>,
+    pub kzg_g2: G2,
+    pub kzg_tau_g2: G2,
+}
+
+/// Nova proof over all model layers, compressed by the on-chain decider.
+///
+/// The Groth16 public inputs are, in order: `num_steps`, `z_0`, `z_n`, the
+/// two KZG challenges and evaluations, the (low, high) limbs of the `cm_w`
+/// and `cm_e` coordinates, then `extra_inputs` (folding challenge, params
+/// hash, and other decider-specific values).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FoldedProof {
+    pub num_steps: u64,
+    /// Initial state: [model commitment, input commitment, ...]
+    pub z_0: Vec<Scalar>,
+    /// Final state: [model commitment, output commitment, ...]
+    pub z_n: Vec<Scalar>,
+    pub cm_w: G1,
+    pub cm_e: G1,
+    pub kzg_challenges: [Scalar; 2],
+    pub kzg_evals: [Scalar; 2],
+    pub kzg_proofs: [G1; 2],
+    pub extra_inputs: Vec<Scalar>,
+    pub proof_a: G1,
+    pub proof_b: G2,
+    pub proof_c: G1,
+}
+
+impl FoldedProof {
+    /// Public inputs before `extra_inputs` for a given state width
+    pub fn fixed_inputs(state_len: usize) -> usize {
+        1 + 2 * state_len + 4 + 8
+    }
+
+    pub fn public_inputs(&self) -> Vec<Scalar> {
+        let mut num_steps = [0u8; 32];
+        num_steps[24..].copy_from_slice(&self.num_steps.to_be_bytes());
+
+        let mut inputs = Vec::with_capacity(Self::fixed_inputs(self.z_0.len()) + self.extra_inputs.len());
+        inputs.push(num_steps);
+        inputs.extend_from_slice(&self.z_0);
+        inputs.extend_from_slice(&self.z_n);
+        inputs.extend_from_slice(&self.kzg_challenges);
+        inputs.extend_from_slice(&self.kzg_evals);
+        for point in [&self.cm_w, &self.cm_e] {
+            inputs.extend(bn254::coordinate_limbs(&point[..32]));
+            inputs.extend(bn254::coordinate_limbs(&point[32..]));
+        }
+        inputs.extend_from_slice(&self.extra_inputs);
+        inputs
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct FoldedVerifierKey {
+    pub model_account: Pubkey,
+    pub circuit_hash: [u8; 32],
+    pub state_len: u8,
+    pub vk: FoldedVk,
+    pub bump: u8,
+}
+
+impl FoldedVerifierKey {
+    pub const LEN: usize = 32 + 32 + 1
+        + 64 + 3 * 128 + (4 + (MAX_FOLDED_PUBLIC_INPUTS + 1) * 64) + 2 * 128
+        + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct FoldedInferenceRecord {
+    pub model: Pubkey,
+    pub submitter: Pubkey,
+    pub input_hash: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub num_steps: u64,
+    pub verified_at: i64,
+    pub bump: u8,
+}
+
+impl FoldedInferenceRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[event]
+pub struct FoldedVerifierRegistered {
+    pub model: Pubkey,
+    pub circuit_hash: [u8; 32],
+    pub public_inputs: u8,
+}
+
+#[event]
+pub struct FoldedInferenceVerified {
+    pub model: Pubkey,
+    pub record: Pubkey,
+    pub num_steps: u64,
+    pub output_commitment: [u8; 32],
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("ZK circuit doesn't match model requirements")]
+    CircuitMismatch,
+    #[msg("ZK proof verification failed")]
+    ZkVerificationFailure,
+    #[msg("Folded proof is malformed or not bound to this request")]
+    InvalidFoldedProof,
+    // ... (previous errors)
+}
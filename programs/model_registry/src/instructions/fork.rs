@@ -0,0 +1,233 @@
+// contracts/programs/model_registry/src/instructions/fork.rs
+
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction, sysvar::clock::Clock};
+use crate::{instructions::pause::NotPaused, state::*, utils::{crypto, fees}, AdminAccount};
+
+/// Upper bound on the fee share a fork may route upstream
+pub const MAX_FORK_ROYALTY_BPS: u16 = 10_000;
+
+#[derive(Accounts)]
+#[instruction(model_hash: [u8; 32])]
+pub struct ForkModel<'info> {
+    #[account(seeds = [b"admin"], bump = admin.bump)]
+    pub admin: Account<'info, AdminAccount>,
+
+    #[account(
+        seeds = [b"model", &parent_model.seed_hash],
+        bump = parent_model.bump
+    )]
+    pub parent_model: Account<'info, ModelAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ModelAccount::space(),
+        seeds = [b"model", &model_hash],
+        bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// Shared by every fork of `parent_model`
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + RoyaltyVault::LEN,
+        seeds = [b"royalty_vault", parent_model.key().as_ref()],
+        bump
+    )]
+    pub royalty_vault: Account<'info, RoyaltyVault>,
+
+    /// Owner of the fork
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Registration stays admin-gated for forks too
+    #[account(address = admin.authority @ ModelRegistryError::Unauthorized)]
+    pub admin_authority: Signer<'info>,
+
+    /// CHECK: Lamport sink for storage fees, owned by the system program
+    #[account(mut, seeds = [b"storage_vault"], bump)]
+    pub storage_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRoyalties<'info> {
+    #[account(
+        constraint = model_account.owner == owner.key() @ ModelRegistryError::Unauthorized
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        has_one = model_account @ ModelRegistryError::Unauthorized,
+        seeds = [b"royalty_vault", model_account.key().as_ref()],
+        bump = royalty_vault.bump
+    )]
+    pub royalty_vault: Account<'info, RoyaltyVault>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Register a derivative of `parent_model` that inherits its ACL and pays
+/// `royalty_bps` of every settled lamport fee into the parent's royalty vault
+pub fn fork(
+    ctx: Context<ForkModel>,
+    model_hash: [u8; 32],
+    zk_circuit_hash: [u8; 32],
+    storage_fee: u64,
+    inference_fee: u64,
+    royalty_bps: u16,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let parent = &ctx.accounts.parent_model;
+    let owner = ctx.accounts.owner.key();
+
+    // 1. Parent is live and the forker may build on it
+    if !parent.is_public {
+        parent.check_access(&owner, AccessLevel::Contributor)?;
+    }
+    parent.require_unpaused()?;
+    parent.require_active(now)?;
+
+    // 2. Provenance and a royalty back upstream are mandatory
+    require!(crypto::is_valid_hash(&model_hash), ModelRegistryError::InvalidHash);
+    require!(
+        royalty_bps > 0 && royalty_bps <= MAX_FORK_ROYALTY_BPS,
+        ModelRegistryError::InvalidRoyaltyConfig
+    );
+    let required_fee = fees::calculate_storage_fee(storage_fee, &Rent::get()?)?;
+    require!(storage_fee >= required_fee, ModelRegistryError::InsufficientFee);
+
+    // 3. Fresh model, ACL defaults copied from the parent
+    let model = &mut ctx.accounts.model_account;
+    model.model_hash = model_hash;
+    model.seed_hash = model_hash;
+    model.zk_circuit = zk_circuit_hash;
+    model.owner = owner;
+    model.timestamp = now;
+    model.active_version = 1;
+    model.version_history = vec![model_hash];
+    model.storage_fee = storage_fee;
+    model.inference_fee = inference_fee;
+    model.expires_at = now + ModelAccount::STORAGE_EPOCH_SECONDS;
+    model.acl = parent.acl.clone();
+    model.is_public = parent.is_public;
+    model.governance_model = GovernanceType::OwnerControlled;
+    model.parent_model = Some(parent.key());
+    model.upstream_royalty_bps = royalty_bps;
+    model.bump = *ctx.bumps.get("model_account").unwrap();
+
+    let vault = &mut ctx.accounts.royalty_vault;
+    vault.model_account = parent.key();
+    vault.bump = *ctx.bumps.get("royalty_vault").unwrap();
+
+    // 4. First storage epoch, as for a fresh registration
+    invoke(
+        &system_instruction::transfer(&owner, ctx.accounts.storage_vault.key, storage_fee),
+        &[
+            ctx.accounts.owner.to_account_info(),
+            ctx.accounts.storage_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    emit!(ModelForked {
+        model: model.key(),
+        parent: parent.key(),
+        model_hash,
+        owner,
+        royalty_bps,
+        inference_fee,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Withdraw royalties accrued from forks, keeping the vault rent-exempt
+pub fn claim(ctx: Context<ClaimRoyalties>) -> Result<()> {
+    let vault = ctx.accounts.royalty_vault.to_account_info();
+    let reserve = Rent::get()?.minimum_balance(vault.data_len());
+    let amount = vault.lamports().saturating_sub(reserve);
+    require!(amount > 0, ModelRegistryError::NoRoyaltiesAccrued);
+
+    **vault.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.owner.try_borrow_mut_lamports()? += amount;
+
+    emit!(RoyaltiesClaimed {
+        model: ctx.accounts.model_account.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Vault and amount owed upstream when `fee` for a fork is paid to its provider
+pub fn upstream_share<'info>(
+    model: &ModelAccount,
+    vault: &Option<Account<'info, RoyaltyVault>>,
+    fee: u64,
+) -> Result<Option<(AccountInfo<'info>, u64)>> {
+    let Some(parent) = model.parent_model else { return Ok(None) };
+    let vault = vault.as_ref().ok_or(ModelRegistryError::MissingRoyaltyVault)?;
+    require_keys_eq!(vault.model_account, parent, ModelRegistryError::MissingRoyaltyVault);
+    Ok(Some((vault.to_account_info(), model.upstream_royalty(fee))))
+}
+
+/// Program-owned lamport sink for royalties owed to a parent model
+#[account]
+#[derive(Default)]
+pub struct RoyaltyVault {
+    pub model_account: Pubkey,
+    pub bump: u8,
+}
+
+impl RoyaltyVault {
+    pub const LEN: usize = 32 + 1;
+}
+
+#[event]
+pub struct ModelForked {
+    pub model: Pubkey,
+    pub parent: Pubkey,
+    pub model_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub royalty_bps: u16,
+    pub inference_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoyaltiesClaimed {
+    pub model: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Invalid model hash format")]
+    InvalidHash,
+    #[msg("Insufficient storage fee")]
+    InsufficientFee,
+    #[msg("Fork royalty must be between 1 and 10000 basis points")]
+    InvalidRoyaltyConfig,
+    #[msg("Fork settlement requires the parent's royalty vault")]
+    MissingRoyaltyVault,
+    #[msg("No royalties to claim")]
+    NoRoyaltiesAccrued,
+    // ... (previous errors)
+}
@@ -0,0 +1,486 @@
+// contracts/programs/model_registry/src/instructions/inference.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use solana_program::{program::invoke, system_instruction, sysvar::clock::Clock};
+use crate::{
+    instructions::{
+        access_token::AccessToken,
+        fork::{upstream_share, RoyaltyVault},
+        pause::NotPaused,
+        payments::{self, FeeSink, TokenFee},
+    },
+    state::*,
+    utils::crypto,
+};
+
+#[derive(Accounts)]
+#[instruction(input_hash: [u8; 32])]
+pub struct RequestInference<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = InferenceRequest::space(),
+        seeds = [b"inference", model_account.key().as_ref(), &input_hash],
+        bump
+    )]
+    pub inference_request: Account<'info, InferenceRequest>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    /// Requester's capability token, for callers outside the ACL
+    #[account(
+        mut,
+        seeds = [b"access_token", model_account.key().as_ref(), requester.key().as_ref()],
+        bump = access_token.bump
+    )]
+    pub access_token: Option<Account<'info, AccessToken>>,
+
+    // SPL fee payment; pass all four or none
+    #[account(seeds = [b"payment_config"], bump = payment_config.bump)]
+    pub payment_config: Option<Account<'info, PaymentConfig>>,
+
+    #[account(mut, token::authority = requester)]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimInference<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        constraint = inference_request.model == model_account.key() @ ModelRegistryError::Unauthorized,
+        constraint = inference_request.status == InferenceStatus::Pending @ ModelRegistryError::AlreadyFulfilled,
+        seeds = [b"inference", model_account.key().as_ref(), &inference_request.input_hash],
+        bump = inference_request.bump
+    )]
+    pub inference_request: Account<'info, InferenceRequest>,
+
+    /// Owner or ACL Contributor serving this model
+    pub provider: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillInference<'info> {
+    #[account(
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        mut,
+        has_one = requester @ ModelRegistryError::Unauthorized,
+        constraint = inference_request.model == model_account.key() @ ModelRegistryError::Unauthorized,
+        constraint = inference_request.status == InferenceStatus::Pending @ ModelRegistryError::AlreadyFulfilled,
+        seeds = [b"inference", model_account.key().as_ref(), &inference_request.input_hash],
+        bump = inference_request.bump
+    )]
+    pub inference_request: Account<'info, InferenceRequest>,
+
+    /// Owner or ACL Contributor serving this model
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// CHECK: Refunded when the provider reports failure; matched by `has_one`
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+
+    // Token escrow release; required when the fee was paid in a token
+    #[account(seeds = [b"payment_config"], bump = payment_config.bump)]
+    pub payment_config: Option<Account<'info, PaymentConfig>>,
+
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Provider's account on success, requester's on failure
+    #[account(mut)]
+    pub payout_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    // Optimistic mode: pass both to post a result without on-chain proof verification
+    #[account(
+        seeds = [b"optimistic", model_account.key().as_ref()],
+        bump = optimistic_config.bump
+    )]
+    pub optimistic_config: Option<Account<'info, OptimisticConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"provider_stake", model_account.key().as_ref(), provider.key().as_ref()],
+        bump = provider_stake.bump
+    )]
+    pub provider_stake: Option<Account<'info, ProviderStake>>,
+
+    // Forks only: the parent's vault receives the upstream royalty
+    #[account(
+        mut,
+        seeds = [b"royalty_vault", royalty_vault.model_account.as_ref()],
+        bump = royalty_vault.bump
+    )]
+    pub royalty_vault: Option<Account<'info, RoyaltyVault>>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// What the provider reports for a request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum InferenceOutcome {
+    /// Output commitment and a proof over `input_hash || output_hash`
+    Success { output_hash: [u8; 32], zk_proof: Vec<u8> },
+    /// Inference could not be run; the fee is refunded
+    Failure { error_code: u32 },
+}
+
+/// Open an inference request, escrowing the model's fee in lamports or a whitelisted token
+pub fn request(
+    ctx: Context<RequestInference>,
+    input_hash: [u8; 32],
+    input_uri: String,
+    zk_proof: Vec<u8>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let model = &ctx.accounts.model_account;
+    require!(input_uri.len() <= MAX_INPUT_URI_LEN, ModelRegistryError::InputUriTooLong);
+
+    // 1. Caller may run the model, through the ACL or an access token, and its storage is paid up
+    if !model.is_public {
+        match &mut ctx.accounts.access_token {
+            Some(token) => token.consume(clock.slot)?,
+            None => model.check_access(ctx.accounts.requester.key, AccessLevel::InferenceOnly)?,
+        }
+    }
+    model.require_unpaused()?;
+    model.require_active(now)?;
+
+    // 2. Verify ZKP matches circuit
+    require!(
+        crypto::verify_zk_proof(&model.zk_circuit, &input_hash, &zk_proof),
+        ModelRegistryError::InvalidProof
+    );
+
+    // 3. Escrow the fee until a provider fulfills the request
+    let token_fee = TokenFee::from_accounts(
+        &ctx.accounts.payment_config,
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.token_program,
+    )?;
+    let (fee_mint, fee) = match token_fee {
+        Some(token_fee) => {
+            // Fork royalties are paid from lamport fees only
+            require!(model.parent_model.is_none(), ModelRegistryError::TokenFeeOnFork);
+            let (mint, amount) = token_fee.collect(&ctx.accounts.requester, model.inference_fee, FeeSink::Escrow)?;
+            (Some(mint), amount)
+        }
+        None => {
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.requester.key,
+                    &ctx.accounts.inference_request.key(),
+                    model.inference_fee,
+                ),
+                &[
+                    ctx.accounts.requester.to_account_info(),
+                    ctx.accounts.inference_request.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+            (None, model.inference_fee)
+        }
+    };
+
+    let request = &mut ctx.accounts.inference_request;
+    request.model = model.key();
+    request.requester = ctx.accounts.requester.key();
+    request.input_hash = input_hash;
+    request.input_uri = input_uri;
+    request.status = InferenceStatus::Pending;
+    request.fee = fee;
+    request.fee_mint = fee_mint;
+    request.created_at = now;
+    request.bump = *ctx.bumps.get("inference_request").unwrap();
+
+    emit!(InferenceRequested {
+        model: model.key(),
+        request: request.key(),
+        timestamp: now,
+        fee,
+        fee_mint,
+    });
+
+    Ok(())
+}
+
+/// Take a pending request for `duration` seconds so other providers skip it.
+/// The claimant may re-claim to extend; once the claim lapses anyone may take over.
+pub fn claim(ctx: Context<ClaimInference>, duration: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let provider = ctx.accounts.provider.key();
+    require!(0 < duration && duration <= MAX_CLAIM_SECS, ModelRegistryError::InvalidClaimDuration);
+    ctx.accounts.model_account.check_access(&provider, AccessLevel::Contributor)?;
+
+    let request = &mut ctx.accounts.inference_request;
+    match request.active_claimant(now) {
+        Some(claimant) if claimant != provider => return err!(ModelRegistryError::AlreadyClaimed),
+        _ => {}
+    }
+    request.claimant = Some(provider);
+    request.claim_expires_at = now + duration;
+
+    emit!(InferenceClaimed {
+        model: request.model,
+        request: request.key(),
+        provider,
+        expires_at: request.claim_expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Settle a pending request: pay the provider on success, refund the requester on failure.
+/// In optimistic mode a success only opens the challenge window; see `challenge::finalize`.
+pub fn fulfill(ctx: Context<FulfillInference>, outcome: InferenceOutcome) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let model = &ctx.accounts.model_account;
+    let provider = ctx.accounts.provider.key();
+
+    // 1. Only the model's serving providers may settle, and only the claimant while its claim is live
+    model.check_access(&provider, AccessLevel::Contributor)?;
+    if let Some(claimant) = ctx.accounts.inference_request.active_claimant(now) {
+        require_keys_eq!(claimant, provider, ModelRegistryError::AlreadyClaimed);
+    }
+
+    // 2. Optimistic results are bonded and left open to challenge instead of verified here
+    let request = &ctx.accounts.inference_request;
+    let optimistic = match (&ctx.accounts.optimistic_config, &mut ctx.accounts.provider_stake) {
+        (Some(config), Some(stake)) => Some((config, stake)),
+        (None, None) => None,
+        _ => return err!(ModelRegistryError::InsufficientStake),
+    };
+    let (status, output_hash, error_code, proof_hash, deadline, bond) = match (outcome, optimistic) {
+        (InferenceOutcome::Success { output_hash, zk_proof }, Some((config, stake))) => {
+            stake.lock(config.bond)?;
+            let proof_hash = solana_program::hash::hash(&zk_proof).to_bytes();
+            (InferenceStatus::Fulfilled, output_hash, 0, proof_hash, now + config.challenge_window, config.bond)
+        }
+        (InferenceOutcome::Success { output_hash, zk_proof }, None) => {
+            require!(
+                crypto::verify_zk_proof(&model.zk_circuit, &request.public_commitment(&output_hash), &zk_proof),
+                ModelRegistryError::InvalidProof
+            );
+            (InferenceStatus::Completed, output_hash, 0, [0u8; 32], now, 0)
+        }
+        (InferenceOutcome::Failure { error_code }, _) => {
+            (InferenceStatus::Failed, [0u8; 32], error_code, [0u8; 32], now, 0)
+        }
+    };
+
+    // 3. Release escrow now unless the result is still challengeable
+    let payee = match status {
+        InferenceStatus::Completed | InferenceStatus::Fulfilled => ctx.accounts.provider.to_account_info(),
+        _ => ctx.accounts.requester.to_account_info(),
+    };
+    let royalty = match status {
+        InferenceStatus::Completed => upstream_share(model, &ctx.accounts.royalty_vault, request.fee)?,
+        _ => None,
+    };
+    if status != InferenceStatus::Fulfilled {
+        release_escrow(
+            &ctx.accounts.inference_request,
+            &payee,
+            EscrowAccounts {
+                config: &ctx.accounts.payment_config,
+                escrow: &ctx.accounts.escrow_token_account,
+                payout: &ctx.accounts.payout_token_account,
+                token_program: &ctx.accounts.token_program,
+                royalty,
+            },
+        )?;
+    }
+    let payee = payee.key();
+
+    let request = &mut ctx.accounts.inference_request;
+    request.status = status;
+    request.provider = Some(provider);
+    request.output_hash = output_hash;
+    request.fulfilled_at = now;
+    request.proof_hash = proof_hash;
+    request.challenge_deadline = deadline;
+    request.bond = bond;
+
+    emit!(InferenceFulfilled {
+        model: model.key(),
+        request: request.key(),
+        provider,
+        status,
+        output_hash,
+        error_code,
+        payee,
+        fee: request.fee,
+        fee_mint: request.fee_mint,
+        challenge_deadline: deadline,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Optional token accounts for releasing a token-denominated escrow
+pub struct EscrowAccounts<'a, 'info> {
+    pub config: &'a Option<Account<'info, PaymentConfig>>,
+    pub escrow: &'a Option<Account<'info, TokenAccount>>,
+    pub payout: &'a Option<Account<'info, TokenAccount>>,
+    pub token_program: &'a Option<Program<'info, Token>>,
+    /// Fork royalty vault and its cut; lamport fees only
+    pub royalty: Option<(AccountInfo<'info>, u64)>,
+}
+
+/// Pay a request's escrowed fee to `payee` in whatever it was paid in.
+/// Token fees are released in full and forks only accept lamport fees, so
+/// royalties accrue on the lamport path alone.
+pub fn release_escrow<'info>(
+    request: &Account<'info, InferenceRequest>,
+    payee: &AccountInfo<'info>,
+    tokens: EscrowAccounts<'_, 'info>,
+) -> Result<()> {
+    match request.fee_mint {
+        Some(mint) => {
+            let (Some(config), Some(escrow), Some(payout), Some(token_program)) =
+                (tokens.config, tokens.escrow, tokens.payout, tokens.token_program)
+            else {
+                return err!(ModelRegistryError::InvalidPaymentToken);
+            };
+            // Disabled mints still pay out, so look the entry up directly
+            let escrow_key = config
+                .accepted_mints
+                .iter()
+                .find(|m| m.mint == mint)
+                .map(|m| m.escrow)
+                .ok_or(ModelRegistryError::InvalidPaymentToken)?;
+            require_keys_eq!(escrow.key(), escrow_key, ModelRegistryError::InvalidPaymentToken);
+            require_keys_eq!(payout.mint, mint, ModelRegistryError::InvalidPaymentToken);
+            require_keys_eq!(payout.owner, payee.key(), ModelRegistryError::Unauthorized);
+            require!(
+                tokens.royalty.map_or(true, |(_, amount)| amount == 0),
+                ModelRegistryError::TokenFeeOnFork
+            );
+            payments::release(config, escrow, payout, token_program, request.fee)
+        }
+        None => {
+            // Escrowed lamports sit on the program-owned request account
+            let from = request.to_account_info();
+            let royalty = tokens.royalty.as_ref().map_or(0, |(_, amount)| *amount);
+            let payout = request
+                .fee
+                .checked_sub(royalty)
+                .ok_or(ModelRegistryError::ArithmeticOverflow)?;
+            debit(&from, request.fee)?;
+            if let Some((vault, amount)) = &tokens.royalty {
+                credit(vault, *amount)?;
+            }
+            credit(payee, payout)?;
+            Ok(())
+        }
+    }
+}
+
+fn debit(account: &AccountInfo, amount: u64) -> Result<()> {
+    let mut lamports = account.try_borrow_mut_lamports()?;
+    **lamports = lamports
+        .checked_sub(amount)
+        .ok_or(ModelRegistryError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+fn credit(account: &AccountInfo, amount: u64) -> Result<()> {
+    let mut lamports = account.try_borrow_mut_lamports()?;
+    **lamports = lamports
+        .checked_add(amount)
+        .ok_or(ModelRegistryError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+#[event]
+pub struct InferenceRequested {
+    pub model: Pubkey,
+    pub request: Pubkey,
+    pub timestamp: i64,
+    pub fee: u64,
+    pub fee_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct InferenceClaimed {
+    pub model: Pubkey,
+    pub request: Pubkey,
+    pub provider: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InferenceFulfilled {
+    pub model: Pubkey,
+    pub request: Pubkey,
+    pub provider: Pubkey,
+    pub status: InferenceStatus,
+    pub output_hash: [u8; 32],
+    pub error_code: u32,
+    pub payee: Pubkey,
+    pub fee: u64,
+    pub fee_mint: Option<Pubkey>,
+    pub challenge_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Invalid zero-knowledge proof")]
+    InvalidProof,
+    #[msg("Inference request already settled")]
+    AlreadyFulfilled,
+    #[msg("Payment token not whitelisted")]
+    InvalidPaymentToken,
+    #[msg("Insufficient staking balance")]
+    InsufficientStake,
+    #[msg("Input URI exceeds the maximum length")]
+    InputUriTooLong,
+    #[msg("Inference request is claimed by another provider")]
+    AlreadyClaimed,
+    #[msg("Claim duration must be positive and at most an hour")]
+    InvalidClaimDuration,
+    #[msg("Forked models only accept lamport inference fees")]
+    TokenFeeOnFork,
+    #[msg("Arithmetic overflow detected")]
+    ArithmeticOverflow,
+    // ... (previous errors)
+}
@@ -0,0 +1,66 @@
+// contracts/programs/model_registry/src/instructions/metadata.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{instructions::pause::NotPaused, state::*};
+
+/// Only content-addressed catalog documents may be anchored
+pub const METADATA_URI_SCHEME: &str = "ipfs://";
+
+#[derive(Accounts)]
+pub struct SetMetadataUri<'info> {
+    #[account(
+        mut,
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// Model owner or an ACL Administrator
+    pub authority: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Anchor the model's catalog document (name, description, tags, license,
+/// framework, input shape). The JSON itself lives on IPFS and is parsed by
+/// the indexer; an empty URI clears it.
+pub fn set(ctx: Context<SetMetadataUri>, metadata_uri: String) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let model = &mut ctx.accounts.model_account;
+
+    // 1. Writable model and an administrator
+    model.check_access(&authority, AccessLevel::Administrator)?;
+    model.require_active(Clock::get()?.unix_timestamp)?;
+
+    // 2. Bounded ipfs:// URI that differs from the current one
+    require!(
+        metadata_uri.is_empty()
+            || (metadata_uri.len() > METADATA_URI_SCHEME.len()
+                && metadata_uri.len() <= ModelAccount::MAX_METADATA_URI_LEN
+                && metadata_uri.starts_with(METADATA_URI_SCHEME)),
+        ModelRegistryError::InvalidMetadataUri
+    );
+    require!(metadata_uri != model.metadata_uri, ModelRegistryError::MetadataUnchanged);
+
+    // 3. Apply and record the transition
+    let previous = std::mem::replace(&mut model.metadata_uri, metadata_uri);
+    emit!(ModelStateChanged {
+        model: model.key(),
+        field: ModelField::MetadataUri,
+        old_value: previous.into_bytes(),
+        new_value: model.metadata_uri.as_bytes().to_vec(),
+        changed_by: authority,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Metadata URI must be an ipfs:// URI of at most 128 bytes")]
+    InvalidMetadataUri,
+    #[msg("Metadata URI matches the current one")]
+    MetadataUnchanged,
+    // ... (previous errors)
+}
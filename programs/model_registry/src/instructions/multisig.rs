@@ -0,0 +1,388 @@
+// contracts/programs/model_registry/src/instructions/multisig.rs
+
+use anchor_lang::prelude::*;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    sysvar::clock::Clock,
+};
+use crate::AdminAccount;
+
+/// Approvals are a bitmap over the signer list
+pub const MAX_ADMIN_SIGNERS: usize = 16;
+/// Bounds on the instruction a proposal may carry
+pub const MAX_ACTION_ACCOUNTS: usize = 24;
+pub const MAX_ACTION_DATA: usize = 512;
+
+#[derive(Accounts)]
+pub struct InitializeMultisig<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ModelRegistryError::Unauthorized,
+        seeds = [b"admin"],
+        bump = admin.bump
+    )]
+    pub admin: Account<'info, AdminAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AdminMultisig::LEN,
+        seeds = [b"admin_multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    /// CHECK: signs only through invoke_signed; becomes the admin authority
+    #[account(seeds = [b"admin_vault"], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMultisigSigners<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin_multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    /// Only reachable through an executed proposal
+    #[account(seeds = [b"admin_vault"], bump = multisig.vault_bump)]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin_multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + AdminProposal::LEN,
+        seeds = [b"admin_action", &multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    #[account(seeds = [b"admin_multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_action", &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    #[account(seeds = [b"admin_multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_action", &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// CHECK: PDA signer for the proposed instruction
+    #[account(mut, seeds = [b"admin_vault"], bump = multisig.vault_bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    pub executor: Signer<'info>,
+    // Remaining accounts: every account of `proposal.action`, then its program
+}
+
+/// Hand the admin role to an m-of-n signer set. Afterwards `admin.authority`
+/// is the vault PDA, which only signs instructions approved by quorum.
+pub fn initialize(ctx: Context<InitializeMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    AdminMultisig::validate(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.signer_set_seq = 0;
+    multisig.proposal_count = 0;
+    multisig.bump = *ctx.bumps.get("multisig").unwrap();
+    multisig.vault_bump = *ctx.bumps.get("vault").unwrap();
+
+    ctx.accounts.admin.authority = ctx.accounts.vault.key();
+
+    emit!(AdminSignersUpdated {
+        signers: multisig.signers.clone(),
+        threshold,
+        signer_set_seq: 0,
+    });
+
+    Ok(())
+}
+
+/// Replace the signer set; invalidates approvals on open proposals
+pub fn set_signers(ctx: Context<SetMultisigSigners>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    AdminMultisig::validate(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.signer_set_seq += 1;
+
+    emit!(AdminSignersUpdated {
+        signers: multisig.signers.clone(),
+        threshold,
+        signer_set_seq: multisig.signer_set_seq,
+    });
+
+    Ok(())
+}
+
+/// Propose an instruction to run with the vault as signer; counts as the proposer's approval
+pub fn propose(ctx: Context<ProposeAdminAction>, action: AdminInstruction) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let position = multisig.position(&ctx.accounts.proposer.key())?;
+    require!(
+        action.accounts.len() <= MAX_ACTION_ACCOUNTS && action.data.len() <= MAX_ACTION_DATA,
+        ModelRegistryError::AdminActionTooLarge
+    );
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.index = multisig.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.action = action;
+    proposal.approvals = 1 << position;
+    proposal.signer_set_seq = multisig.signer_set_seq;
+    proposal.executed = false;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.bump = *ctx.bumps.get("proposal").unwrap();
+    multisig.proposal_count += 1;
+
+    emit!(AdminActionProposed {
+        proposal: proposal.key(),
+        index: proposal.index,
+        proposer: proposal.proposer,
+        program_id: proposal.action.program_id,
+    });
+
+    Ok(())
+}
+
+pub fn approve(ctx: Context<ApproveAdminAction>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let position = multisig.position(&ctx.accounts.signer.key())?;
+
+    require!(!proposal.executed, ModelRegistryError::AdminActionExecuted);
+    require!(
+        proposal.signer_set_seq == multisig.signer_set_seq,
+        ModelRegistryError::StaleAdminAction
+    );
+    require!(
+        proposal.approvals & (1 << position) == 0,
+        ModelRegistryError::AlreadyApproved
+    );
+    proposal.approvals |= 1 << position;
+
+    emit!(AdminActionApproved {
+        proposal: proposal.key(),
+        signer: ctx.accounts.signer.key(),
+        approvals: proposal.approvals.count_ones() as u8,
+        threshold: multisig.threshold,
+    });
+
+    Ok(())
+}
+
+/// Run an approved proposal. Signers other than the vault (e.g. a fee payer)
+/// must sign the outer transaction.
+pub fn execute<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAdminAction<'info>>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // 1. Quorum under the current signer set, once
+    require!(!proposal.executed, ModelRegistryError::AdminActionExecuted);
+    require!(
+        proposal.signer_set_seq == multisig.signer_set_seq,
+        ModelRegistryError::StaleAdminAction
+    );
+    require!(
+        proposal.approvals.count_ones() >= multisig.threshold as u32,
+        ModelRegistryError::InsufficientApprovals
+    );
+
+    // 2. Persist before the CPI so the action cannot re-enter and run twice
+    proposal.executed = true;
+    proposal.exit(&crate::ID)?;
+
+    // 3. Invoke with the vault PDA as signer
+    let action = &proposal.action;
+    let instruction = Instruction {
+        program_id: action.program_id,
+        accounts: action
+            .accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.pubkey,
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: action.data.clone(),
+    };
+    let mut infos = ctx.remaining_accounts.to_vec();
+    infos.push(ctx.accounts.vault.to_account_info());
+    invoke_signed(&instruction, &infos, &[&[b"admin_vault", &[multisig.vault_bump]]])?;
+
+    emit!(AdminActionExecuted {
+        proposal: proposal.key(),
+        index: proposal.index,
+        executor: ctx.accounts.executor.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct AdminAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Serialized form of the instruction a proposal executes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct AdminInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AdminAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct AdminMultisig {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Bumped on every signer change; proposals from older sets cannot execute
+    pub signer_set_seq: u32,
+    pub proposal_count: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl AdminMultisig {
+    pub const LEN: usize = (4 + MAX_ADMIN_SIGNERS * 32) + 1 + 4 + 8 + 1 + 1;
+
+    fn validate(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        let mut unique = signers.to_vec();
+        unique.sort();
+        unique.dedup();
+        require!(
+            !signers.is_empty()
+                && signers.len() <= MAX_ADMIN_SIGNERS
+                && unique.len() == signers.len()
+                && threshold > 0
+                && threshold as usize <= signers.len(),
+            ModelRegistryError::InvalidMultisigConfig
+        );
+        Ok(())
+    }
+
+    fn position(&self, signer: &Pubkey) -> Result<usize> {
+        self.signers
+            .iter()
+            .position(|s| s == signer)
+            .ok_or_else(|| error!(ModelRegistryError::NotMultisigSigner))
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct AdminProposal {
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminInstruction,
+    /// Bit i set when `signers[i]` approved
+    pub approvals: u16,
+    pub signer_set_seq: u32,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl AdminProposal {
+    pub const LEN: usize = 8 + 32
+        + (32 + (4 + MAX_ACTION_ACCOUNTS * (32 + 1 + 1)) + (4 + MAX_ACTION_DATA))
+        + 2 + 4 + 1 + 8 + 1;
+}
+
+#[event]
+pub struct AdminSignersUpdated {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub signer_set_seq: u32,
+}
+
+#[event]
+pub struct AdminActionProposed {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct AdminActionApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct AdminActionExecuted {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Signer set is empty, too large, has duplicates, or threshold is out of range")]
+    InvalidMultisigConfig,
+    #[msg("Signer is not a member of the admin multisig")]
+    NotMultisigSigner,
+    #[msg("Signer already approved this action")]
+    AlreadyApproved,
+    #[msg("Admin action lacks quorum")]
+    InsufficientApprovals,
+    #[msg("Admin action was already executed")]
+    AdminActionExecuted,
+    #[msg("Admin action was proposed under a previous signer set")]
+    StaleAdminAction,
+    #[msg("Admin action has too many accounts or too much data")]
+    AdminActionTooLarge,
+    // ... (previous errors)
+}
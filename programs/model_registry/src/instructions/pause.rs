@@ -0,0 +1,197 @@
+// contracts/programs/model_registry/src/instructions/pause.rs
+
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+use crate::{state::*, AdminAccount};
+
+/// Upper bound on auditors allowed to pause any model
+pub const MAX_PAUSE_AUDITORS: usize = 8;
+
+/// Embedded in every mutating instruction: fails while the program is paused
+#[derive(Accounts)]
+pub struct NotPaused<'info> {
+    #[account(
+        seeds = [b"program_pause"],
+        bump = program_pause.bump,
+        constraint = !program_pause.paused @ ModelRegistryError::ProgramPaused
+    )]
+    pub program_pause: Account<'info, ProgramPause>,
+}
+
+#[derive(Accounts)]
+pub struct SetProgramPause<'info> {
+    #[account(seeds = [b"admin"], bump = admin.bump)]
+    pub admin: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"program_pause"],
+        bump = program_pause.bump
+    )]
+    pub program_pause: Account<'info, ProgramPause>,
+
+    /// The multisig vault once `initialize_multisig` has run
+    #[account(address = admin.authority @ ModelRegistryError::Unauthorized)]
+    pub admin_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetModelPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ModelPauseRecord::LEN,
+        seeds = [b"model_pause", model_account.key().as_ref()],
+        bump
+    )]
+    pub record: Account<'info, ModelPauseRecord>,
+
+    #[account(seeds = [b"program_pause"], bump = program_pause.bump)]
+    pub program_pause: Account<'info, ProgramPause>,
+
+    /// Model owner, a registered auditor, or the model's DAO
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Halt or resume every mutating instruction (admin, i.e. multisig quorum)
+pub fn set_program_pause(ctx: Context<SetProgramPause>, paused: bool) -> Result<()> {
+    let state = &mut ctx.accounts.program_pause;
+    require!(state.paused != paused, ModelRegistryError::PauseStateUnchanged);
+
+    state.paused = paused;
+    state.changed_at = Clock::get()?.unix_timestamp;
+
+    emit!(ProgramPauseChanged {
+        paused,
+        changed_by: ctx.accounts.admin_authority.key(),
+        timestamp: state.changed_at,
+    });
+
+    Ok(())
+}
+
+/// Replace the auditors allowed to pause models (admin)
+pub fn set_auditors(ctx: Context<SetProgramPause>, auditors: Vec<Pubkey>) -> Result<()> {
+    require!(auditors.len() <= MAX_PAUSE_AUDITORS, ModelRegistryError::TooManyAuditors);
+    ctx.accounts.program_pause.auditors = auditors;
+    Ok(())
+}
+
+/// Pause or unpause one model. Unpausing needs at least the role that paused
+/// it, so an owner cannot lift an auditor's or DAO's pause.
+pub fn set_model_pause(ctx: Context<SetModelPause>, paused: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let model = &mut ctx.accounts.model_account;
+    let record = &mut ctx.accounts.record;
+
+    // 1. Caller's strongest role on this model
+    let role = PauseRole::of(&authority, model, &ctx.accounts.program_pause)
+        .ok_or(ModelRegistryError::PauseNotAuthorized)?;
+    require!(model.emergency_pause != paused, ModelRegistryError::PauseStateUnchanged);
+    if !paused {
+        require!(role >= record.role, ModelRegistryError::PauseNotAuthorized);
+    }
+
+    // 2. Apply and record the transition
+    model.emergency_pause = paused;
+    record.model = model.key();
+    record.changed_by = authority;
+    record.role = role;
+    record.changed_at = Clock::get()?.unix_timestamp;
+    record.bump = *ctx.bumps.get("record").unwrap();
+
+    emit!(ModelStateChanged {
+        model: model.key(),
+        field: ModelField::PauseStatus,
+        old_value: vec![!paused as u8],
+        new_value: vec![paused as u8],
+        changed_by: authority,
+    });
+
+    Ok(())
+}
+
+/// Ordered by authority; `set_model_pause` compares with `>=`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PauseRole {
+    #[default]
+    Owner,
+    Auditor,
+    Governance,
+}
+
+impl PauseRole {
+    fn of(user: &Pubkey, model: &ModelAccount, program: &ProgramPause) -> Option<Self> {
+        if model.governance_model == GovernanceType::DaoGoverned && model.dao.as_ref() == Some(user) {
+            Some(Self::Governance)
+        } else if program.auditors.contains(user) {
+            Some(Self::Auditor)
+        } else if &model.owner == user {
+            Some(Self::Owner)
+        } else {
+            None
+        }
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct ProgramPause {
+    pub paused: bool,
+    pub changed_at: i64,
+    pub auditors: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ProgramPause {
+    pub const LEN: usize = 1 + 8 + (4 + MAX_PAUSE_AUDITORS * 32) + 1;
+}
+
+/// Who last changed a model's pause flag
+#[account]
+#[derive(Default)]
+pub struct ModelPauseRecord {
+    pub model: Pubkey,
+    pub changed_by: Pubkey,
+    pub role: PauseRole,
+    pub changed_at: i64,
+    pub bump: u8,
+}
+
+impl ModelPauseRecord {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 1;
+}
+
+#[event]
+pub struct ProgramPauseChanged {
+    pub paused: bool,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Model currently paused")]
+    ModelPaused,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Signer may not change this pause flag")]
+    PauseNotAuthorized,
+    #[msg("Pause flag already has this value")]
+    PauseStateUnchanged,
+    #[msg("Too many pause auditors")]
+    TooManyAuditors,
+    // ... (previous errors)
+}
@@ -0,0 +1,288 @@
+// contracts/programs/model_registry/src/instructions/payments.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::{state::*, AdminAccount};
+
+#[derive(Accounts)]
+pub struct InitializePaymentConfig<'info> {
+    #[account(
+        has_one = admin_authority @ ModelRegistryError::Unauthorized,
+        seeds = [b"admin"],
+        bump = admin.bump
+    )]
+    pub admin: Account<'info, AdminAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = PaymentConfig::space(),
+        seeds = [b"payment_config"],
+        bump
+    )]
+    pub payment_config: Account<'info, PaymentConfig>,
+
+    #[account(address = admin.authority)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ModelRegistryError::Unauthorized,
+        seeds = [b"payment_config"],
+        bump = payment_config.bump
+    )]
+    pub payment_config: Account<'info, PaymentConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Treasury for this mint, controlled only by the payment config PDA
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = payment_config
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// Inference fee escrow for this mint, same authority as the treasury
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"escrow", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = payment_config
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePaymentMint<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ModelRegistryError::Unauthorized,
+        seeds = [b"payment_config"],
+        bump = payment_config.bump
+    )]
+    pub payment_config: Account<'info, PaymentConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        has_one = authority @ ModelRegistryError::Unauthorized,
+        seeds = [b"payment_config"],
+        bump = payment_config.bump
+    )]
+    pub payment_config: Account<'info, PaymentConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = treasury.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create the whitelist; `authority` is the governance key that manages it
+pub fn initialize_config(ctx: Context<InitializePaymentConfig>, authority: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.payment_config;
+    config.authority = authority;
+    config.accepted_mints = Vec::new();
+    config.bump = *ctx.bumps.get("payment_config").unwrap();
+    Ok(())
+}
+
+/// Whitelist a mint and open its treasury and escrow accounts
+pub fn add_mint(ctx: Context<AddPaymentMint>, tokens_per_sol: u64) -> Result<()> {
+    require!(tokens_per_sol > 0, ModelRegistryError::InvalidPaymentToken);
+
+    let mint = ctx.accounts.mint.key();
+    let treasury = ctx.accounts.treasury.key();
+    ctx.accounts.payment_config.add_mint(AcceptedMint {
+        mint,
+        treasury,
+        escrow: ctx.accounts.escrow.key(),
+        tokens_per_sol,
+        enabled: true,
+    })?;
+
+    emit!(PaymentMintUpdated { mint, treasury, tokens_per_sol, enabled: true });
+    Ok(())
+}
+
+/// Reprice or disable a whitelisted mint; the treasury is kept for withdrawals
+pub fn update_mint(ctx: Context<UpdatePaymentMint>, mint: Pubkey, tokens_per_sol: u64, enabled: bool) -> Result<()> {
+    require!(tokens_per_sol > 0, ModelRegistryError::InvalidPaymentToken);
+
+    let entry = ctx
+        .accounts
+        .payment_config
+        .accepted_mints
+        .iter_mut()
+        .find(|m| m.mint == mint)
+        .ok_or(ModelRegistryError::InvalidPaymentToken)?;
+    entry.tokens_per_sol = tokens_per_sol;
+    entry.enabled = enabled;
+
+    emit!(PaymentMintUpdated { mint, treasury: entry.treasury, tokens_per_sol, enabled });
+    Ok(())
+}
+
+/// Move collected fees out of a treasury on behalf of governance
+pub fn withdraw(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    release(
+        &ctx.accounts.payment_config,
+        &ctx.accounts.treasury,
+        &ctx.accounts.destination,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(TreasuryWithdrawn {
+        mint: ctx.accounts.treasury.mint,
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+    Ok(())
+}
+
+/// Transfer out of a treasury or escrow, signed by the payment config PDA
+pub fn release<'info>(
+    config: &Account<'info, PaymentConfig>,
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"payment_config", &[config.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: config.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+/// Where a collected token fee lands
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FeeSink {
+    /// Protocol revenue, withdrawable by governance
+    Treasury,
+    /// Held for the provider until the request is fulfilled
+    Escrow,
+}
+
+/// Optional SPL accounts carried by fee-charging instructions
+pub struct TokenFee<'a, 'info> {
+    pub config: &'a Account<'info, PaymentConfig>,
+    pub source: &'a Account<'info, TokenAccount>,
+    pub destination: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl<'a, 'info> TokenFee<'a, 'info> {
+    /// `None` when no token accounts were passed (lamport payment); a partial set is rejected
+    pub fn from_accounts(
+        config: &'a Option<Account<'info, PaymentConfig>>,
+        source: &'a Option<Account<'info, TokenAccount>>,
+        destination: &'a Option<Account<'info, TokenAccount>>,
+        token_program: &'a Option<Program<'info, Token>>,
+    ) -> Result<Option<Self>> {
+        match (config, source, destination, token_program) {
+            (None, None, None, None) => Ok(None),
+            (Some(config), Some(source), Some(destination), Some(token_program)) => Ok(Some(Self {
+                config,
+                source,
+                destination,
+                token_program,
+            })),
+            _ => err!(ModelRegistryError::InvalidPaymentToken),
+        }
+    }
+
+    /// Charge the token equivalent of `lamports`; returns (mint, amount paid)
+    pub fn collect(&self, payer: &Signer<'info>, lamports: u64, sink: FeeSink) -> Result<(Pubkey, u64)> {
+        // 1. Mint must be whitelisted and the fee must land in its treasury or escrow
+        let accepted = self.config.accepted(&self.source.mint)?;
+        let expected = match sink {
+            FeeSink::Treasury => accepted.treasury,
+            FeeSink::Escrow => accepted.escrow,
+        };
+        require_keys_eq!(self.destination.key(), expected, ModelRegistryError::InvalidPaymentToken);
+
+        // 2. Convert at the governance-set rate and transfer
+        let amount = accepted.token_amount(lamports)?;
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.source.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: payer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        Ok((accepted.mint, amount))
+    }
+}
+
+#[event]
+pub struct PaymentMintUpdated {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub tokens_per_sol: u64,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Payment token not whitelisted")]
+    InvalidPaymentToken,
+    // ... (previous errors)
+}
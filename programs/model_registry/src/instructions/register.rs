@@ -1,8 +1,9 @@
 // contracts/programs/model_registry/src/instructions/register.rs
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
 use solana_program::{system_instruction, sysvar::rent::Rent};
-use crate::{state::*, utils::{crypto, fees}, ModelRegistryError};
+use crate::{instructions::{pause::NotPaused, payments::{FeeSink, TokenFee}}, state::*, utils::{crypto, fees}, ModelRegistryError};
 
 #[derive(Accounts)]
 #[instruction(model_hash: [u8; 32], zk_circuit_hash: [u8; 32], storage_fee: u64)]
@@ -18,7 +19,7 @@ pub struct RegisterModel<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + ModelAccount::LEN,
+        space = ModelAccount::space(),
         seeds = [b"model", &model_hash],
         bump
     )]
@@ -30,11 +31,25 @@ pub struct RegisterModel<'info> {
     #[account(address = admin.authority)]
     pub admin_authority: Signer<'info>,
 
+    // SPL fee payment; pass all four or none
+    #[account(seeds = [b"payment_config"], bump = payment_config.bump)]
+    pub payment_config: Option<Account<'info, PaymentConfig>>,
+
+    #[account(mut, token::authority = payer)]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 
     #[account(address = sysvar::rent::ID)]
     pub rent: Sysvar<'info, Rent>,
+
+    pub live: NotPaused<'info>,
 }
 
 pub fn handler(
@@ -42,6 +57,7 @@ pub fn handler(
     model_hash: [u8; 32],
     zk_circuit_hash: [u8; 32],
     storage_fee: u64,
+    inference_fee: u64,
 ) -> Result<()> {
     // Validate model hash format
     require!(
@@ -68,48 +84,60 @@ pub fn handler(
     // Initialize model account
     let model = &mut ctx.accounts.model_account;
     model.model_hash = model_hash;
+    model.seed_hash = model_hash;
     model.zk_circuit = zk_circuit_hash;
     model.owner = *ctx.accounts.payer.key;
     model.timestamp = Clock::get()?.unix_timestamp;
     model.active_version = 1;
     model.storage_fee = storage_fee;
+    model.inference_fee = inference_fee;
+    // Registration fee covers the first storage epoch
+    model.expires_at = model.timestamp + ModelAccount::STORAGE_EPOCH_SECONDS;
     model.bump = *ctx.bumps.get("model_account").unwrap();
 
-    // Transfer storage fee
-    let transfer_ix = system_instruction::transfer(
-        ctx.accounts.payer.key,
-        &crate::ID,
-        storage_fee,
-    );
-
-    anchor_lang::solana_program::program::invoke(
-        &transfer_ix,
-        &[
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    // Transfer storage fee, in a whitelisted token when token accounts are passed
+    let token_fee = TokenFee::from_accounts(
+        &ctx.accounts.payment_config,
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.token_program,
     )?;
+    let (fee_mint, fee) = match token_fee {
+        Some(token_fee) => {
+            let (mint, amount) = token_fee.collect(&ctx.accounts.payer, storage_fee, FeeSink::Treasury)?;
+            (Some(mint), amount)
+        }
+        None => {
+            let transfer_ix = system_instruction::transfer(
+                ctx.accounts.payer.key,
+                &crate::ID,
+                storage_fee,
+            );
+
+            anchor_lang::solana_program::program::invoke(
+                &transfer_ix,
+                &[
+                    ctx.accounts.payer.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+            (None, storage_fee)
+        }
+    };
 
     // Emit registration event
     emit!(ModelRegistered {
         model_hash,
         owner: model.owner,
         timestamp: model.timestamp,
-        fee: storage_fee,
+        fee,
+        fee_mint,
     });
 
     Ok(())
 }
 
 impl ModelAccount {
-    pub const LEN: usize = 32   // model_hash
-        + 32                    // zk_circuit
-        + 32                    // owner
-        + 8                     // timestamp
-        + 8                     // active_version
-        + 8                     // storage_fee
-        + 1;                    // bump
-
     pub fn is_registered(model_hash: &[u8; 32]) -> bool {
         // Implementation would check on-chain state
         // Mocked for example purposes
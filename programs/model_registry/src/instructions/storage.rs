@@ -0,0 +1,133 @@
+// contracts/programs/model_registry/src/instructions/storage.rs
+
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction, sysvar::clock::Clock};
+use crate::{instructions::pause::NotPaused, state::*};
+
+#[derive(Accounts)]
+pub struct RenewStorage<'info> {
+    #[account(
+        mut,
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// CHECK: Lamport sink for storage fees, owned by the system program
+    #[account(mut, seeds = [b"storage_vault"], bump)]
+    pub storage_vault: AccountInfo<'info>,
+
+    /// Anyone may pay to keep a model alive
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpired<'info> {
+    #[account(
+        mut,
+        close = storage_vault,
+        seeds = [b"model", &model_account.seed_hash],
+        bump = model_account.bump
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    /// CHECK: Receives the reclaimed rent
+    #[account(mut, seeds = [b"storage_vault"], bump)]
+    pub storage_vault: AccountInfo<'info>,
+
+    /// Permissionless crank
+    pub cranker: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Prepay `epochs` of storage at the model's per-epoch fee
+pub fn renew(ctx: Context<RenewStorage>, epochs: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let model = &mut ctx.accounts.model_account;
+
+    // 1. Fee for the whole term, checked against overflow
+    let amount = model
+        .storage_fee
+        .checked_mul(epochs)
+        .ok_or(ModelRegistryError::InvalidRenewal)?;
+
+    // 2. Extend from the current expiry, or from now if already lapsed
+    let previous = model.expires_at;
+    let expires_at = model.extend_storage(epochs, now)?;
+
+    invoke(
+        &system_instruction::transfer(ctx.accounts.payer.key, ctx.accounts.storage_vault.key, amount),
+        &[
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.storage_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    emit!(StorageRenewed {
+        model: model.key(),
+        payer: ctx.accounts.payer.key(),
+        epochs,
+        amount,
+        previous_expiry: previous,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+/// Deregister a model whose storage lapsed beyond the grace period
+pub fn reclaim(ctx: Context<ReclaimExpired>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let model = &ctx.accounts.model_account;
+
+    model.require_unpaused()?;
+    require!(model.is_reclaimable(now), ModelRegistryError::StorageNotExpired);
+
+    // Account is closed into the vault by the `close` constraint
+    emit!(ModelExpired {
+        model: model.key(),
+        model_hash: model.model_hash,
+        owner: model.owner,
+        expired_at: model.expires_at,
+        reclaimed_at: now,
+        reclaimed_by: ctx.accounts.cranker.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct StorageRenewed {
+    pub model: Pubkey,
+    pub payer: Pubkey,
+    pub epochs: u64,
+    pub amount: u64,
+    pub previous_expiry: i64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ModelExpired {
+    pub model: Pubkey,
+    pub model_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub expired_at: i64,
+    pub reclaimed_at: i64,
+    pub reclaimed_by: Pubkey,
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Renewal must cover between 1 and MAX_RENEWAL_EPOCHS epochs")]
+    InvalidRenewal,
+    #[msg("Model storage is still within its paid term or grace period")]
+    StorageNotExpired,
+    // ... (previous errors)
+}
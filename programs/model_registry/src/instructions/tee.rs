@@ -0,0 +1,276 @@
+// contracts/programs/model_registry/src/instructions/tee.rs
+
+use anchor_lang::prelude::*;
+use solana_program::{hash::hashv, sysvar::clock::Clock};
+use crate::{instructions::pause::NotPaused, state::*, utils::ed25519};
+
+/// Upper bound on allowed enclave measurements per model
+pub const MAX_TEE_MEASUREMENTS: usize = 8;
+/// Upper bound on trusted off-chain quote verifiers per model
+pub const MAX_TEE_VERIFIERS: usize = 4;
+
+#[derive(Accounts)]
+pub struct RegisterTeePolicy<'info> {
+    #[account(
+        constraint = model_account.owner == authority.key() @ ModelRegistryError::Unauthorized
+    )]
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TeePolicy::LEN,
+        seeds = [b"tee_policy", model_account.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, TeePolicy>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(input_hash: [u8; 32])]
+pub struct VerifyTeeAttestation<'info> {
+    pub model_account: Account<'info, ModelAccount>,
+
+    #[account(
+        seeds = [b"tee_policy", model_account.key().as_ref()],
+        bump = policy.bump,
+        has_one = model_account @ ModelRegistryError::InvalidTeePolicy
+    )]
+    pub policy: Account<'info, TeePolicy>,
+
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + TeeInferenceRecord::LEN,
+        seeds = [b"tee", model_account.key().as_ref(), &input_hash],
+        bump
+    )]
+    pub record: Account<'info, TeeInferenceRecord>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// CHECK: address constrained to the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
+}
+
+/// Set the enclave measurements and quote verifiers trusted for a model
+pub fn register_policy(
+    ctx: Context<RegisterTeePolicy>,
+    measurements: Vec<TeeMeasurement>,
+    verifiers: Vec<Pubkey>,
+    max_evidence_age: i64,
+) -> Result<()> {
+    ctx.accounts.model_account.require_unpaused()?;
+    require!(
+        !measurements.is_empty() && measurements.len() <= MAX_TEE_MEASUREMENTS,
+        ModelRegistryError::InvalidTeePolicy
+    );
+    require!(
+        !verifiers.is_empty() && verifiers.len() <= MAX_TEE_VERIFIERS,
+        ModelRegistryError::InvalidTeePolicy
+    );
+    require!(max_evidence_age > 0, ModelRegistryError::InvalidTeePolicy);
+
+    let policy = &mut ctx.accounts.policy;
+    policy.model_account = ctx.accounts.model_account.key();
+    policy.measurements = measurements;
+    policy.verifiers = verifiers;
+    policy.max_evidence_age = max_evidence_age;
+    policy.bump = *ctx.bumps.get("policy").unwrap();
+
+    emit!(TeePolicyRegistered {
+        model: policy.model_account,
+        measurements: policy.measurements.len() as u8,
+        verifiers: policy.verifiers.len() as u8,
+    });
+
+    Ok(())
+}
+
+/// Accept a verified SGX/SNP quote in place of a ZK proof and record the output
+pub fn verify_attestation(
+    ctx: Context<VerifyTeeAttestation>,
+    input_hash: [u8; 32],
+    output_hash: [u8; 32],
+    evidence: TeeEvidence,
+) -> Result<()> {
+    let policy = &ctx.accounts.policy;
+    let model = &ctx.accounts.model_account;
+    model.require_unpaused()?;
+
+    // 1. Evidence from a trusted verifier, about an allowed enclave build
+    require!(
+        policy.verifiers.contains(&evidence.verifier),
+        ModelRegistryError::UntrustedTeeVerifier
+    );
+    require!(
+        policy
+            .measurements
+            .iter()
+            .any(|m| m.platform == evidence.platform && m.value == evidence.measurement),
+        ModelRegistryError::TeeMeasurementNotAllowed
+    );
+
+    // 2. Report data binds this model, input and output
+    let binding = hashv(&[&model.model_hash, &input_hash, &output_hash]);
+    require!(
+        evidence.report_data[..32] == binding.to_bytes(),
+        ModelRegistryError::TeeBindingMismatch
+    );
+
+    // 3. Fresh
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        evidence.verified_at <= now && now - evidence.verified_at <= policy.max_evidence_age,
+        ModelRegistryError::StaleTeeEvidence
+    );
+
+    // 4. Verifier signature, checked by the Ed25519 program in the previous instruction
+    ed25519::verify_preceding(&ctx.accounts.instructions, &evidence.verifier, &evidence.message())?;
+
+    // 5. Record the attested output
+    let record = &mut ctx.accounts.record;
+    record.model = model.key();
+    record.submitter = ctx.accounts.submitter.key();
+    record.verifier = evidence.verifier;
+    record.platform = evidence.platform;
+    record.measurement = evidence.measurement;
+    record.input_hash = input_hash;
+    record.output_hash = output_hash;
+    record.verified_at = now;
+    record.bump = *ctx.bumps.get("record").unwrap();
+
+    emit!(TeeInferenceVerified {
+        model: record.model,
+        record: record.key(),
+        platform: record.platform,
+        output_hash,
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TeePlatform {
+    #[default]
+    Sgx,
+    Snp,
+}
+
+/// SGX MRENCLAVE (zero-padded) or SNP launch measurement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TeeMeasurement {
+    pub platform: TeePlatform,
+    pub value: [u8; 48],
+}
+
+impl TeeMeasurement {
+    pub const LEN: usize = 1 + 48;
+}
+
+/// Quote facts attested by an off-chain verifier after checking the
+/// platform signature chain; the verifier signs `message()`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TeeEvidence {
+    pub platform: TeePlatform,
+    pub measurement: [u8; 48],
+    /// `sha256(model_hash || input_hash || output_hash) || nonce`
+    pub report_data: [u8; 64],
+    pub verifier: Pubkey,
+    pub verified_at: i64,
+}
+
+impl TeeEvidence {
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = b"scoria-tee-evidence-v1".to_vec();
+        message.push(self.platform as u8);
+        message.extend_from_slice(&self.measurement);
+        message.extend_from_slice(&self.report_data);
+        message.extend_from_slice(self.verifier.as_ref());
+        message.extend_from_slice(&self.verified_at.to_le_bytes());
+        message
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct TeePolicy {
+    pub model_account: Pubkey,
+    pub measurements: Vec<TeeMeasurement>,
+    pub verifiers: Vec<Pubkey>,
+    /// Seconds between off-chain verification and submission
+    pub max_evidence_age: i64,
+    pub bump: u8,
+}
+
+impl TeePolicy {
+    pub const LEN: usize = 32
+        + (4 + MAX_TEE_MEASUREMENTS * TeeMeasurement::LEN)
+        + (4 + MAX_TEE_VERIFIERS * 32)
+        + 8 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct TeeInferenceRecord {
+    pub model: Pubkey,
+    pub submitter: Pubkey,
+    pub verifier: Pubkey,
+    pub platform: TeePlatform,
+    pub measurement: [u8; 48],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub verified_at: i64,
+    pub bump: u8,
+}
+
+impl TeeInferenceRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 1 + 48 + 32 + 32 + 8 + 1;
+}
+
+#[event]
+pub struct TeePolicyRegistered {
+    pub model: Pubkey,
+    pub measurements: u8,
+    pub verifiers: u8,
+}
+
+#[event]
+pub struct TeeInferenceVerified {
+    pub model: Pubkey,
+    pub record: Pubkey,
+    pub platform: TeePlatform,
+    pub output_hash: [u8; 32],
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("TEE policy is empty, oversized or not for this model")]
+    InvalidTeePolicy,
+    #[msg("Quote verifier is not trusted for this model")]
+    UntrustedTeeVerifier,
+    #[msg("Enclave measurement is not allowed for this model")]
+    TeeMeasurementNotAllowed,
+    #[msg("Quote report data does not bind this model, input and output")]
+    TeeBindingMismatch,
+    #[msg("TEE evidence is too old")]
+    StaleTeeEvidence,
+    #[msg("Expected an Ed25519 verification of the evidence in the previous instruction")]
+    MissingEd25519Verification,
+    // ... (previous errors)
+}
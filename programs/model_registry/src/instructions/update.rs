@@ -3,13 +3,14 @@
 use anchor_lang::prelude::*;
 use solana_program::{sysvar::clock::Clock, system_instruction};
 use crate::{
+    instructions::pause::NotPaused,
     state::*,
     utils::{crypto, dao, deposits},
     ModelRegistryError,
 };
 
 #[derive(Accounts)]
-#[instruction(new_version_hash: [u8; 32], zk_circuit_hash: [u8; 32], deposit: u64)]
+#[instruction(new_version_hash: [u8; 32], zk_circuit_hash: [u8; 32], deposit: u64, storage_uri: String)]
 pub struct ProposeVersionUpdate<'info> {
     #[account(mut)]
     pub model: Account<'info, ModelAccount>,
@@ -35,13 +36,57 @@ pub struct ProposeVersionUpdate<'info> {
 
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
+
+    pub live: NotPaused<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ExecuteVersionUpdate<'info> {
+pub struct QueueVersionUpdate<'info> {
     #[account(mut)]
     pub proposal: Account<'info, VersionProposal>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = voting_config.bump,
+        seeds::program = dao::GOVERNANCE_PROGRAM_ID
+    )]
+    pub voting_config: Account<'info, dao::VotingConfig>,
+
+    pub payer: Signer<'info>,
+
+    pub live: NotPaused<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelQueuedUpdate<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, VersionProposal>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = voting_config.bump,
+        seeds::program = dao::GOVERNANCE_PROGRAM_ID,
+        constraint = voting_config.is_emergency_operator(operator.key) @ ModelRegistryError::Unauthorized
+    )]
+    pub voting_config: Account<'info, dao::VotingConfig>,
+
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteVersionUpdate<'info> {
+    // Only a proposal made against the model's current version can execute
+    #[account(
+        mut,
+        seeds = [
+            b"version_proposal",
+            model.key().as_ref(),
+            &model.active_version.to_le_bytes()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, VersionProposal>,
+
     #[account(
         mut,
         has_one = dao_authority @ ModelRegistryError::Unauthorized,
@@ -55,13 +100,19 @@ pub struct ExecuteVersionUpdate<'info> {
 
     #[account(address = dao::GOVERNANCE_PROGRAM_ID)]
     pub dao_program: Program<'info, dao::program::DaoGovernance>,
+
+    pub live: NotPaused<'info>,
 }
 
+/// Propose a new model version stored at `storage_uri`. With `delta_base`, the
+/// blob is a binary patch against that version, which must be the active one.
 pub fn propose_update(
     ctx: Context<ProposeVersionUpdate>,
     new_version_hash: [u8; 32],
     zk_circuit_hash: [u8; 32],
     deposit: u64,
+    storage_uri: String,
+    delta_base: Option<[u8; 32]>,
 ) -> Result<()> {
     // Validate submitter permissions
     require!(
@@ -70,6 +121,10 @@ pub fn propose_update(
         ModelRegistryError::Unauthorized
     );
 
+    // Expired models are read-only
+    ctx.accounts.model.require_unpaused()?;
+    ctx.accounts.model.require_active(Clock::get()?.unix_timestamp)?;
+
     // Verify cryptographic hashes
     require!(
         crypto::validate_model_hash(&new_version_hash),
@@ -80,6 +135,15 @@ pub fn propose_update(
         ModelRegistryError::CircuitMismatch
     );
 
+    // Where the new version lives; a delta only patches the version it was diffed from
+    require!(
+        !storage_uri.is_empty() && storage_uri.len() <= ModelAccount::MAX_STORAGE_URI_LEN,
+        ModelRegistryError::InvalidStorageUri
+    );
+    if let Some(base) = delta_base {
+        require!(base == ctx.accounts.model.model_hash, ModelRegistryError::DeltaBaseMismatch);
+    }
+
     // Process security deposit
     let required_deposit = deposits::calculate_version_deposit(
         ctx.accounts.model.active_version,
@@ -95,6 +159,9 @@ pub fn propose_update(
     proposal.submitter = *ctx.accounts.submitter.key;
     proposal.timestamp = Clock::get()?.unix_timestamp;
     proposal.deposit = deposit;
+    proposal.storage_uri = storage_uri;
+    proposal.delta_base = delta_base;
+    proposal.bump = *ctx.bumps.get("proposal").unwrap();
 
     // Transfer deposit
     let transfer_ix = system_instruction::transfer(
@@ -121,22 +188,88 @@ pub fn propose_update(
         model: ctx.accounts.model.key(),
         new_version: new_version_hash,
         proposal: proposal.key(),
+        storage_uri: proposal.storage_uri.clone(),
+        delta_base,
     });
 
     Ok(())
 }
 
-pub fn execute_update(ctx: Context<ExecuteVersionUpdate>) -> Result<()> {
-    // Verify DAO approval
+/// Queue an approved update; it becomes executable after the DAO timelock
+pub fn queue_execution(ctx: Context<QueueVersionUpdate>) -> Result<()> {
     require!(
         dao::is_proposal_approved(&ctx.accounts.proposal),
         ModelRegistryError::ProposalNotApproved
     );
 
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.proposal_state == VersionState::Approved,
+        ModelRegistryError::InvalidProposalState
+    );
+
+    // A config never passed through `configure_timelock` has a zero delay
+    let delay = ctx.accounts.voting_config.timelock_delay.max(dao::MIN_TIMELOCK_DELAY);
+    let now = Clock::get()?.unix_timestamp;
+    let execute_after = now
+        .checked_add(delay)
+        .ok_or(ModelRegistryError::ArithmeticOverflow)?;
+    proposal.proposal_state = VersionState::Queued;
+    proposal.execute_after = execute_after;
+
+    emit!(ProposalQueued {
+        proposal: proposal.key(),
+        execute_after,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Emergency operators may cancel an update while it sits in the queue
+pub fn cancel_queued(ctx: Context<CancelQueuedUpdate>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.proposal_state == VersionState::Queued,
+        ModelRegistryError::InvalidProposalState
+    );
+
+    proposal.proposal_state = VersionState::Cancelled;
+
+    emit!(ProposalCancelled {
+        proposal: proposal.key(),
+        operator: *ctx.accounts.operator.key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn execute_update(ctx: Context<ExecuteVersionUpdate>) -> Result<()> {
+    // Verify the update was queued and its timelock has elapsed
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.proposal.proposal_state == VersionState::Queued,
+        ModelRegistryError::ProposalNotQueued
+    );
+    require!(
+        now >= ctx.accounts.proposal.execute_after,
+        ModelRegistryError::TimelockNotElapsed
+    );
+
     // Update model version
     let model = &mut ctx.accounts.model;
+    model.require_unpaused()?;
+    require!(
+        ctx.accounts.proposal.storage_uri.len() <= ModelAccount::MAX_STORAGE_URI_LEN,
+        ModelRegistryError::InvalidStorageUri
+    );
+    if let Some(base) = ctx.accounts.proposal.delta_base {
+        require!(base == model.model_hash, ModelRegistryError::DeltaBaseMismatch);
+    }
     model.active_version += 1;
     model.model_hash = ctx.accounts.proposal.new_version;
+    model.storage_uri = ctx.accounts.proposal.storage_uri.clone();
 
     // Release deposit + reward
     deposits::process_update_reward(
@@ -151,10 +284,16 @@ pub fn execute_update(ctx: Context<ExecuteVersionUpdate>) -> Result<()> {
         ctx.accounts.proposal.key(),
     )?;
 
+    ctx.accounts.proposal.proposal_state = VersionState::Executed;
+
     emit!(VersionUpdated {
         model: model.key(),
         new_version: model.active_version,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
+    });
+    emit!(ProposalExecuted {
+        proposal: ctx.accounts.proposal.key(),
+        timestamp: now,
     });
 
     Ok(())
@@ -169,17 +308,25 @@ pub struct VersionProposal {
     pub submitter: Pubkey,
     pub timestamp: i64,
     pub deposit: u64,
+    /// Earliest execution time once queued
+    pub execute_after: i64,
+    pub storage_uri: String,
+    /// Version hash the stored blob is a patch against; `None` for a full upload
+    pub delta_base: Option<[u8; 32]>,
     pub bump: u8,
 }
 
 impl VersionProposal {
-    pub const LEN: usize = 32 + 32 + 1 + 32 + 8 + 8 + 1;
+    pub const LEN: usize = 32 + 32 + 1 + 32 + 8 + 8 + 8 + (4 + ModelAccount::MAX_STORAGE_URI_LEN) + 33 + 1;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum VersionState {
     Pending,
     Approved,
+    Queued,
+    Executed,
+    Cancelled,
     Rejected,
     Archived,
 }
@@ -189,6 +336,28 @@ pub struct VersionProposed {
     pub model: Pubkey,
     pub new_version: [u8; 32],
     pub proposal: Pubkey,
+    pub storage_uri: String,
+    pub delta_base: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct ProposalQueued {
+    pub proposal: Pubkey,
+    pub execute_after: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -208,5 +377,17 @@ pub enum ModelRegistryError {
     ProposalNotApproved,
     #[msg("Version downgrade not allowed")]
     VersionDowngrade,
+    #[msg("Proposal is not in the required state")]
+    InvalidProposalState,
+    #[msg("Proposal must be queued before execution")]
+    ProposalNotQueued,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Arithmetic overflow detected")]
+    ArithmeticOverflow,
+    #[msg("Storage URI is empty or too long")]
+    InvalidStorageUri,
+    #[msg("Delta base is not the active model version")]
+    DeltaBaseMismatch,
     // ... (previous errors)
 }
@@ -4,8 +4,8 @@
 #![cfg_attr(feature = "anchor-attributes", allow(unused_attributes))]
 
 use anchor_lang::prelude::*;
-use solana_program::{entrypoint::ProgramResult, system_instruction};
-use crate::{instructions::*, state::*, error::ModelRegistryError, utils::crypto};
+use solana_program::entrypoint::ProgramResult;
+use crate::{instructions::*, state::*, error::ModelRegistryError};
 
 declare_id!("SCRAxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
@@ -18,87 +18,363 @@ pub mod model_registry {
         let admin = &mut ctx.accounts.admin;
         admin.authority = *ctx.accounts.payer.key;
         admin.bump = *ctx.bumps.get("admin").unwrap();
+        ctx.accounts.program_pause.bump = *ctx.bumps.get("program_pause").unwrap();
+        ctx.accounts.auditor_registry.bump = *ctx.bumps.get("auditor_registry").unwrap();
         Ok(())
     }
 
+    /// Move the admin role to an m-of-n signer set (current admin)
+    pub fn initialize_multisig(ctx: Context<InitializeMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::multisig::initialize(ctx, signers, threshold)
+    }
+
+    /// Replace the admin signer set; callable only through an executed admin action
+    pub fn set_multisig_signers(ctx: Context<SetMultisigSigners>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::multisig::set_signers(ctx, signers, threshold)
+    }
+
+    /// Propose an instruction to be signed by the admin vault
+    pub fn propose_admin_action(ctx: Context<ProposeAdminAction>, action: AdminInstruction) -> Result<()> {
+        instructions::multisig::propose(ctx, action)
+    }
+
+    /// Approve a pending admin action (multisig signer)
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        instructions::multisig::approve(ctx)
+    }
+
+    /// Execute an admin action that reached quorum
+    pub fn execute_admin_action<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAdminAction<'info>>) -> Result<()> {
+        instructions::multisig::execute(ctx)
+    }
+
+    /// Halt every mutating instruction (admin vault)
+    pub fn pause_program(ctx: Context<SetProgramPause>) -> Result<()> {
+        instructions::pause::set_program_pause(ctx, true)
+    }
+
+    /// Lift the global pause (admin vault)
+    pub fn unpause_program(ctx: Context<SetProgramPause>) -> Result<()> {
+        instructions::pause::set_program_pause(ctx, false)
+    }
+
+    /// Replace the auditors allowed to pause any model (admin vault)
+    pub fn set_pause_auditors(ctx: Context<SetProgramPause>, auditors: Vec<Pubkey>) -> Result<()> {
+        instructions::pause::set_auditors(ctx, auditors)
+    }
+
+    /// Emergency-pause a model (owner, auditor, or DAO)
+    pub fn pause_model(ctx: Context<SetModelPause>) -> Result<()> {
+        instructions::pause::set_model_pause(ctx, true)
+    }
+
+    /// Lift a model pause; needs a role at least as strong as the one that paused it
+    pub fn unpause_model(ctx: Context<SetModelPause>) -> Result<()> {
+        instructions::pause::set_model_pause(ctx, false)
+    }
+
+    /// Replace the auditors trusted to sign model audits (admin vault)
+    pub fn set_auditor_registry(ctx: Context<SetAuditorRegistry>, auditors: Vec<Pubkey>) -> Result<()> {
+        instructions::audit::set_registry(ctx, auditors)
+    }
+
+    /// Record a registered auditor's Ed25519-signed report for the active model version
+    pub fn submit_audit(ctx: Context<SubmitAudit>, auditor: Pubkey, version: u64, report_cid: String) -> Result<()> {
+        instructions::audit::submit(ctx, auditor, version, report_cid)
+    }
+
     /// Register new AI model (admin only)
     pub fn register_model(
         ctx: Context<RegisterModel>,
         model_hash: [u8; 32],
         zk_circuit_hash: [u8; 32],
-        fee: u64,
-    ) -> Result<()> {
-        require!(ctx.accounts.admin.is_admin(), ModelRegistryError::Unauthorized);
-        
-        let model_account = &mut ctx.accounts.model_account;
-        model_account.model_hash = model_hash;
-        model_account.zk_circuit = zk_circuit_hash;
-        model_account.timestamp = Clock::get()?.unix_timestamp;
-        model_account.fee = fee;
-
-        // Transfer storage fee
-        anchor_lang::solana_program::program::invoke(
-            &system_instruction::transfer(
-                ctx.accounts.payer.key,
-                &crate::ID,
-                fee,
-            ),
-            &[
-                ctx.accounts.payer.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-        )?;
-
-        emit!(ModelRegistered {
-            model_hash,
-            owner: *ctx.accounts.payer.key,
-            timestamp: model_account.timestamp,
-        });
+        storage_fee: u64,
+        inference_fee: u64,
+    ) -> Result<()> {
+        instructions::register::handler(ctx, model_hash, zk_circuit_hash, storage_fee, inference_fee)
+    }
 
-        Ok(())
+    /// Propose a new model version, optionally stored as a delta against the active one
+    pub fn propose_update(
+        ctx: Context<ProposeVersionUpdate>,
+        new_version_hash: [u8; 32],
+        zk_circuit_hash: [u8; 32],
+        deposit: u64,
+        storage_uri: String,
+        delta_base: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::update::propose_update(ctx, new_version_hash, zk_circuit_hash, deposit, storage_uri, delta_base)
+    }
+
+    /// Register a derivative of an existing model with an upstream royalty (admin only)
+    pub fn fork_model(
+        ctx: Context<ForkModel>,
+        model_hash: [u8; 32],
+        zk_circuit_hash: [u8; 32],
+        storage_fee: u64,
+        inference_fee: u64,
+        royalty_bps: u16,
+    ) -> Result<()> {
+        instructions::fork::fork(ctx, model_hash, zk_circuit_hash, storage_fee, inference_fee, royalty_bps)
+    }
+
+    /// Withdraw royalties paid by forks of a model (model owner)
+    pub fn claim_royalties(ctx: Context<ClaimRoyalties>) -> Result<()> {
+        instructions::fork::claim(ctx)
     }
 
     /// Submit inference request with ZKP
     pub fn request_inference(
         ctx: Context<RequestInference>,
         input_hash: [u8; 32],
+        input_uri: String,
         zk_proof: Vec<u8>,
     ) -> Result<()> {
-        let model = &ctx.accounts.model_account;
-        
-        // Verify ZKP matches circuit
-        require!(
-            crypto::verify_zk_proof(
-                &model.zk_circuit,
-                &input_hash,
-                &zk_proof
-            ),
-            ModelRegistryError::InvalidProof
-        );
+        instructions::inference::request(ctx, input_hash, input_uri, zk_proof)
+    }
 
-        let request = &mut ctx.accounts.inference_request;
-        request.model = model.key();
-        request.input_hash = input_hash;
-        request.status = InferenceStatus::Pending;
+    /// Reserve a pending inference request for `duration` seconds (serving provider)
+    pub fn claim_inference(ctx: Context<ClaimInference>, duration: i64) -> Result<()> {
+        instructions::inference::claim(ctx, duration)
+    }
 
-        emit!(InferenceRequested {
-            model: model.key(),
-            request: request.key(),
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    /// Settle a pending inference request and release its escrowed fee
+    pub fn fulfill_inference(ctx: Context<FulfillInference>, outcome: InferenceOutcome) -> Result<()> {
+        instructions::inference::fulfill(ctx, outcome)
+    }
 
-        Ok(())
+    /// Enable optimistic fulfillment with a challenge window (model owner)
+    pub fn configure_optimistic(
+        ctx: Context<ConfigureOptimistic>,
+        challenge_window: i64,
+        bond: u64,
+        challenger_reward_bps: u16,
+    ) -> Result<()> {
+        instructions::challenge::configure(ctx, challenge_window, bond, challenger_reward_bps)
+    }
+
+    /// Bond lamports to serve a model optimistically
+    pub fn stake_provider(ctx: Context<StakeProvider>, amount: u64) -> Result<()> {
+        instructions::challenge::stake(ctx, amount)
+    }
+
+    /// Withdraw bond not locked by open fulfillments
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
+        instructions::challenge::withdraw_stake(ctx, amount)
+    }
+
+    /// Submit a fraud proof against an optimistic fulfillment
+    pub fn challenge_inference(ctx: Context<ChallengeInference>, zk_proof: Vec<u8>) -> Result<()> {
+        instructions::challenge::challenge(ctx, zk_proof)
+    }
+
+    /// Settle an optimistic fulfillment after its challenge window
+    pub fn finalize_inference(ctx: Context<FinalizeInference>) -> Result<()> {
+        instructions::challenge::finalize(ctx)
+    }
+
+    /// Open a federated round for updates (model Contributor)
+    pub fn open_federated_round(
+        ctx: Context<OpenFederatedRound>,
+        round: u64,
+        collect_window: i64,
+        aggregate_window: i64,
+        min_participants: u32,
+        contributor_share: u8,
+    ) -> Result<()> {
+        instructions::federation::open_federated_round(
+            ctx,
+            round,
+            collect_window,
+            aggregate_window,
+            min_participants,
+            contributor_share,
+        )
+    }
+
+    /// Record an update commitment in a collecting round
+    pub fn submit_round_update(ctx: Context<SubmitRoundUpdate>, update_hash: [u8; 32]) -> Result<()> {
+        instructions::federation::submit_round_update(ctx, update_hash)
+    }
+
+    /// Apply a passed round deadline; callable by anyone
+    pub fn close_federated_round(ctx: Context<CloseFederatedRound>) -> Result<()> {
+        instructions::federation::close_federated_round(ctx)
+    }
+
+    /// Publish a round's aggregate (drawn aggregator, or Contributor without a lottery)
+    pub fn finalize_federated_round(ctx: Context<FinalizeFederatedRound>, aggregate_hash: [u8; 32]) -> Result<()> {
+        instructions::federation::finalize_federated_round(ctx, aggregate_hash)
+    }
+
+    /// Commit the aggregator's quality score for one update of a finalized round
+    pub fn score_contribution(ctx: Context<ScoreContribution>, score: u64) -> Result<()> {
+        instructions::federation::score_contribution(ctx, score)
+    }
+
+    /// Record a scored update's weighted share in the produced version's rewards
+    pub fn credit_contribution(ctx: Context<CreditContribution>) -> Result<()> {
+        instructions::federation::credit_contribution(ctx)
+    }
+
+    /// Open the stake-weighted aggregator lottery for a federated round
+    pub fn open_aggregation_round(
+        ctx: Context<OpenAggregationRound>,
+        round: u64,
+        commit_window: i64,
+        reveal_window: i64,
+        aggregators: u8,
+    ) -> Result<()> {
+        instructions::federation::open_round(ctx, round, commit_window, reveal_window, aggregators)
+    }
+
+    /// Seal a VRF proof for an aggregation round (staked node)
+    pub fn commit_vrf(ctx: Context<CommitVrf>, commitment: [u8; 32]) -> Result<()> {
+        instructions::federation::commit_vrf(ctx, commitment)
+    }
+
+    /// Reveal a sealed VRF proof and enter the aggregator draw
+    pub fn reveal_vrf(ctx: Context<RevealVrf>, proof: Vec<u8>) -> Result<()> {
+        instructions::federation::reveal_vrf(ctx, proof)
     }
 
-    /// Contribute data to federated learning pool
+    /// Create the SPL fee whitelist under a governance authority
+    pub fn initialize_payment_config(ctx: Context<InitializePaymentConfig>, authority: Pubkey) -> Result<()> {
+        instructions::payments::initialize_config(ctx, authority)
+    }
+
+    /// Accept a new fee token and open its treasury (governance only)
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>, tokens_per_sol: u64) -> Result<()> {
+        instructions::payments::add_mint(ctx, tokens_per_sol)
+    }
+
+    /// Reprice or disable an accepted fee token (governance only)
+    pub fn update_payment_mint(
+        ctx: Context<UpdatePaymentMint>,
+        mint: Pubkey,
+        tokens_per_sol: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::payments::update_mint(ctx, mint, tokens_per_sol, enabled)
+    }
+
+    /// Withdraw collected token fees (governance only)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::payments::withdraw(ctx, amount)
+    }
+
+    /// Register the decider verifying key for folded multi-layer proofs
+    pub fn register_folded_verifier(
+        ctx: Context<RegisterFoldedVerifier>,
+        circuit_hash: [u8; 32],
+        state_len: u8,
+        vk: FoldedVk,
+    ) -> Result<()> {
+        instructions::folded::register_verifier(ctx, circuit_hash, state_len, vk)
+    }
+
+    /// Submit a folded inference proof covering every model layer
+    pub fn submit_folded_inference(
+        ctx: Context<SubmitFoldedInference>,
+        input_hash: [u8; 32],
+        proof: FoldedProof,
+    ) -> Result<()> {
+        instructions::folded::submit_folded(ctx, input_hash, proof)
+    }
+
+    /// Set the enclave measurements and quote verifiers trusted for TEE inference
+    pub fn register_tee_policy(
+        ctx: Context<RegisterTeePolicy>,
+        measurements: Vec<TeeMeasurement>,
+        verifiers: Vec<Pubkey>,
+        max_evidence_age: i64,
+    ) -> Result<()> {
+        instructions::tee::register_policy(ctx, measurements, verifiers, max_evidence_age)
+    }
+
+    /// Record a TEE-attested inference; the alternative to a ZK proof for latency-sensitive models
+    pub fn verify_tee_attestation(
+        ctx: Context<VerifyTeeAttestation>,
+        input_hash: [u8; 32],
+        output_hash: [u8; 32],
+        evidence: TeeEvidence,
+    ) -> Result<()> {
+        instructions::tee::verify_attestation(ctx, input_hash, output_hash, evidence)
+    }
+
+    /// Prepay storage for additional epochs
+    pub fn renew_storage(ctx: Context<RenewStorage>, epochs: u64) -> Result<()> {
+        instructions::storage::renew(ctx, epochs)
+    }
+
+    /// Deregister a model whose storage expired past the grace period
+    pub fn reclaim_expired(ctx: Context<ReclaimExpired>) -> Result<()> {
+        instructions::storage::reclaim(ctx)
+    }
+
+    /// Grant or change a user's access level on a model
+    pub fn grant_access(ctx: Context<ManageAccess>, user: Pubkey, level: AccessLevel) -> Result<()> {
+        instructions::access::grant(ctx, user, level)
+    }
+
+    /// Remove a user from a model's access list
+    pub fn revoke_access(ctx: Context<ManageAccess>, user: Pubkey) -> Result<()> {
+        instructions::access::revoke(ctx, user)
+    }
+
+    /// Let `holder` request inference without an ACL entry until the token expires or runs out
+    pub fn issue_access_token(
+        ctx: Context<IssueAccessToken>,
+        holder: Pubkey,
+        terms: AccessTokenTerms,
+    ) -> Result<()> {
+        instructions::access_token::issue(ctx, holder, terms)
+    }
+
+    /// Close an access token (owner, Administrator, or its holder)
+    pub fn revoke_access_token(ctx: Context<RevokeAccessToken>) -> Result<()> {
+        instructions::access_token::revoke(ctx)
+    }
+
+    /// Toggle open inference access for a model
+    pub fn set_public(ctx: Context<ManageAccess>, is_public: bool) -> Result<()> {
+        instructions::access::set_public(ctx, is_public)
+    }
+
+    /// Declare the model's input and output tensor schemas (owner or Administrator)
+    pub fn set_schema(
+        ctx: Context<SetSchema>,
+        input_schema_hash: [u8; 32],
+        output_schema_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::schema::set(ctx, input_schema_hash, output_schema_hash)
+    }
+
+    /// Anchor the IPFS URI of the model's catalog metadata
+    pub fn set_metadata_uri(ctx: Context<SetMetadataUri>, metadata_uri: String) -> Result<()> {
+        instructions::metadata::set(ctx, metadata_uri)
+    }
+
+    /// Contribute data to federated learning pool. `records_root` is the
+    /// contributor's Merkle commitment over the `record_count` records, so
+    /// single records can later be proven part of the contribution.
     pub fn contribute_data(
         ctx: Context<ContributeData>,
         encrypted_data: Vec<u8>,
         data_hash: [u8; 32],
+        records_root: [u8; 32],
+        record_count: u64,
     ) -> Result<()> {
+        require!(
+            record_count > 0 && records_root != [0u8; 32],
+            ModelRegistryError::InvalidHash
+        );
+
         let contribution = &mut ctx.accounts.contribution_account;
         contribution.data = encrypted_data.clone();
         contribution.hash = data_hash;
+        contribution.records_root = records_root;
+        contribution.record_count = record_count;
         contribution.contributor = *ctx.accounts.contributor.key;
         contribution.timestamp = Clock::get()?.unix_timestamp;
 
@@ -111,10 +387,41 @@ pub mod model_registry {
             contributor: contribution.contributor,
             data_hash,
             model: ctx.accounts.model_account.key(),
+            records_root,
+            record_count,
         });
 
         Ok(())
     }
+
+    /// Revoke a contribution under the right to erasure; closes it and records the revocation
+    pub fn revoke_contribution(ctx: Context<RevokeContribution>) -> Result<()> {
+        instructions::erasure::revoke(ctx)
+    }
+
+    /// Anchor the retention period, purposes and jurisdictions a contribution may be used under
+    pub fn record_consent(ctx: Context<RecordConsent>, data_hash: [u8; 32], policy: ConsentPolicy) -> Result<()> {
+        instructions::consent::record(ctx, data_hash, policy)
+    }
+
+    /// Withdraw consent; the indexer deletes the encrypted blob
+    pub fn withdraw_consent(ctx: Context<WithdrawConsent>) -> Result<()> {
+        instructions::consent::withdraw(ctx)
+    }
+
+    /// Record the jurisdiction an aggregator decrypts contributions in
+    pub fn set_aggregator_jurisdiction(
+        ctx: Context<SetAggregatorJurisdiction>,
+        aggregator: Pubkey,
+        jurisdiction: [u8; 2],
+    ) -> Result<()> {
+        instructions::consent::set_jurisdiction(ctx, aggregator, jurisdiction)
+    }
+
+    /// Check consent and data residency before an aggregator may decrypt a contribution
+    pub fn authorize_decryption(ctx: Context<AuthorizeDecryption>, purpose: u8) -> Result<()> {
+        instructions::consent::authorize(ctx, purpose)
+    }
 }
 
 #[derive(Accounts)]
@@ -127,6 +434,22 @@ pub struct Initialize<'info> {
         bump
     )]
     pub admin: Account<'info, AdminAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProgramPause::LEN,
+        seeds = [b"program_pause"],
+        bump
+    )]
+    pub program_pause: Account<'info, ProgramPause>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AuditorRegistry::LEN,
+        seeds = [b"auditor_registry"],
+        bump
+    )]
+    pub auditor_registry: Account<'info, AuditorRegistry>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -153,13 +476,8 @@ pub struct ModelRegistered {
     pub model_hash: [u8; 32],
     pub owner: Pubkey,
     pub timestamp: i64,
-}
-
-#[event]
-pub struct InferenceRequested {
-    pub model: Pubkey,
-    pub request: Pubkey,
-    pub timestamp: i64,
+    pub fee: u64,
+    pub fee_mint: Option<Pubkey>,
 }
 
 #[event]
@@ -167,6 +485,8 @@ pub struct DataContributed {
     pub contributor: Pubkey,
     pub data_hash: [u8; 32],
     pub model: Pubkey,
+    pub records_root: [u8; 32],
+    pub record_count: u64,
 }
 
 // Error codes
@@ -0,0 +1,281 @@
+// contracts/programs/model_registry/src/state/federation.rs
+
+use anchor_lang::prelude::*;
+use solana_program::pubkey::Pubkey;
+
+/// One federated training round of a model. Updates are collected until
+/// `collect_deadline`; with `min_participants` the round moves to aggregation,
+/// otherwise it expires. An aggregate not finalized by `aggregate_deadline`
+/// expires the round too. Once finalized, the aggregator scores every update
+/// and `contributor_share` percent of the new version's rewards is split by score.
+#[account]
+#[derive(Default)]
+pub struct FederatedRound {
+    pub model: Pubkey,
+    pub round: u64,
+    pub status: FederatedRoundStatus,
+    pub opened_at: i64,
+    pub collect_deadline: i64,
+    pub aggregate_deadline: i64,
+    pub min_participants: u32,
+    pub participants: u32,
+    pub aggregate_hash: [u8; 32],  // Hash of the new global model, set on finalization
+    pub aggregator: Option<Pubkey>,
+    pub contributor_share: u8,     // Percent of the version's reward shares paid to participants
+    pub total_score: u64,
+    pub scored: u32,               // Updates scored so far; credits wait for all of them
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum FederatedRoundStatus {
+    #[default]
+    Collecting,
+    Aggregating,
+    Finalized,
+    /// Quorum missed or no aggregate in time
+    Expired,
+}
+
+/// A participant's update in a federated round; one per participant
+#[account]
+#[derive(Default)]
+pub struct RoundUpdate {
+    pub round: Pubkey,
+    pub participant: Pubkey,
+    pub update_hash: [u8; 32],
+    pub submitted_at: i64,
+    pub score: u64,                // Holdout loss improvement, set by the round's aggregator
+    pub scored: bool,
+    pub credited: bool,            // Share recorded in the version's reward_shares
+    pub bump: u8,
+}
+
+impl FederatedRound {
+    /// Status once deadlines that passed by `now` are applied; stored status
+    /// only catches up when an instruction touches the round
+    pub fn status_at(&self, now: i64) -> FederatedRoundStatus {
+        match self.status {
+            FederatedRoundStatus::Collecting if now >= self.collect_deadline => {
+                if self.participants >= self.min_participants && now < self.aggregate_deadline {
+                    FederatedRoundStatus::Aggregating
+                } else {
+                    FederatedRoundStatus::Expired
+                }
+            }
+            FederatedRoundStatus::Aggregating if now >= self.aggregate_deadline => FederatedRoundStatus::Expired,
+            status => status,
+        }
+    }
+
+    /// Reward share, in percent, of an update with `score`; rounds down so
+    /// the round's shares never exceed `contributor_share`
+    pub fn reward_share(&self, score: u64) -> u8 {
+        if self.total_score == 0 {
+            return 0;
+        }
+        (self.contributor_share as u128 * score as u128 / self.total_score as u128) as u8
+    }
+
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // model
+        8 +  // round
+        1 +  // status
+        8 +  // opened_at
+        8 +  // collect_deadline
+        8 +  // aggregate_deadline
+        4 +  // min_participants
+        4 +  // participants
+        32 + // aggregate_hash
+        1 + 32 + // aggregator (Option)
+        1 +  // contributor_share
+        8 +  // total_score
+        4 +  // scored
+        1    // bump
+    }
+}
+
+impl RoundUpdate {
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // round
+        32 + // participant
+        32 + // update_hash
+        8 +  // submitted_at
+        8 +  // score
+        1 +  // scored
+        1 +  // credited
+        1    // bump
+    }
+}
+
+/// Aggregator lottery for one federated round of a model. Nodes commit to a
+/// VRF proof over `seed` before `commit_deadline`, reveal it before
+/// `reveal_deadline`, and the `aggregators` lowest stake-weighted draws win.
+#[account]
+#[derive(Default)]
+pub struct AggregationRound {
+    pub model: Pubkey,
+    pub round: u64,
+    pub seed: [u8; 32],            // VRF input; fixed from a slot hash when the round opens
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub aggregators: u8,           // Winners drawn
+    pub commitments: u32,
+    pub reveals: u32,
+    pub selected: Vec<AggregatorDraw>, // Best draws so far, lowest score first
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct AggregatorDraw {
+    pub node: Pubkey,
+    pub score: u64,  // -log2 of the VRF output as a fraction, Q32.32
+    pub stake: u64,
+}
+
+/// One node's sealed VRF proof for a round
+#[account]
+#[derive(Default)]
+pub struct VrfCommitment {
+    pub round: Pubkey,
+    pub node: Pubkey,
+    pub commitment: [u8; 32],      // hash(proof), opened by `reveal_vrf`
+    pub stake: u64,                // ProviderStake bonded at commit time
+    pub revealed: bool,
+    pub bump: u8,
+}
+
+impl AggregationRound {
+    /// Most aggregators a round may draw
+    pub const MAX_AGGREGATORS: u8 = 8;
+
+    /// VRF input for this round: binds the draw to the model and round number
+    pub fn alpha(&self) -> Vec<u8> {
+        [self.model.as_ref(), &self.round.to_le_bytes(), &self.seed].concat()
+    }
+
+    /// Keep `draw` if it is among the best `aggregators` seen. Comparing
+    /// score / stake gives an exponential race, so each node's chance of a
+    /// seat is proportional to its stake.
+    pub fn consider(&mut self, draw: AggregatorDraw) {
+        let better = |a: &AggregatorDraw, b: &AggregatorDraw| (a.score as u128) * (b.stake as u128) < (b.score as u128) * (a.stake as u128);
+        let position = self.selected.iter().position(|kept| better(&draw, kept)).unwrap_or(self.selected.len());
+        if position < self.aggregators as usize {
+            self.selected.insert(position, draw);
+            self.selected.truncate(self.aggregators as usize);
+        }
+    }
+
+    pub fn is_selected(&self, node: &Pubkey) -> bool {
+        self.selected.iter().any(|draw| draw.node == *node)
+    }
+
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // model
+        8 +  // round
+        32 + // seed
+        8 +  // commit_deadline
+        8 +  // reveal_deadline
+        1 +  // aggregators
+        4 +  // commitments
+        4 +  // reveals
+        4 + Self::MAX_AGGREGATORS as usize * (32 + 8 + 8) + // selected
+        1    // bump
+    }
+}
+
+impl VrfCommitment {
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // round
+        32 + // node
+        32 + // commitment
+        8 +  // stake
+        1 +  // revealed
+        1    // bump
+    }
+}
+
+/// -log2(u / 2^64) in Q32.32 for the first eight bytes `u` of a VRF output;
+/// uniform outputs give exponentially distributed scores
+pub fn draw_score(output: &[u8; 64]) -> u64 {
+    let u = u64::from_le_bytes(output[..8].try_into().unwrap()).max(1);
+    let shift = u.leading_zeros();
+    // Mantissa in [1, 2) as Q1.63, squared once per fractional bit
+    let mut mantissa = (u << shift) as u128;
+    let mut fraction = 0u64;
+    for bit in (0..32).rev() {
+        mantissa = (mantissa * mantissa) >> 63;
+        if mantissa >= 1 << 64 {
+            mantissa >>= 1;
+            fraction |= 1 << bit;
+        }
+    }
+    ((shift as u64 + 1) << 32) - fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(u: u64) -> [u8; 64] {
+        let mut output = [0u8; 64];
+        output[..8].copy_from_slice(&u.to_le_bytes());
+        output
+    }
+
+    #[test]
+    fn test_draw_score_is_negative_log2() {
+        assert_eq!(draw_score(&output(1 << 63)), 1 << 32);
+        assert_eq!(draw_score(&output(1 << 62)), 2 << 32);
+        assert_eq!(draw_score(&output(1)), 64 << 32);
+        // 0.75 → 0.415
+        let score = draw_score(&output(3 << 62)) as f64 / (1u64 << 32) as f64;
+        assert!((score - 0.75f64.log2().abs()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_round_status_follows_deadlines() {
+        let round = FederatedRound {
+            collect_deadline: 100,
+            aggregate_deadline: 200,
+            min_participants: 3,
+            participants: 3,
+            ..Default::default()
+        };
+        assert_eq!(round.status_at(99), FederatedRoundStatus::Collecting);
+        assert_eq!(round.status_at(100), FederatedRoundStatus::Aggregating);
+        assert_eq!(round.status_at(200), FederatedRoundStatus::Expired);
+
+        let short = FederatedRound { participants: 2, ..round.clone() };
+        assert_eq!(short.status_at(100), FederatedRoundStatus::Expired);
+        let done = FederatedRound { status: FederatedRoundStatus::Finalized, ..round };
+        assert_eq!(done.status_at(500), FederatedRoundStatus::Finalized);
+    }
+
+    #[test]
+    fn test_reward_shares_follow_scores() {
+        let round = FederatedRound { contributor_share: 60, total_score: 7, ..Default::default() };
+        let shares: Vec<u8> = [4, 2, 1].iter().map(|&score| round.reward_share(score)).collect();
+        assert_eq!(shares, vec![34, 17, 8]);
+        assert!(shares.iter().map(|&s| s as u32).sum::<u32>() <= 60);
+        assert_eq!(FederatedRound::default().reward_share(5), 0);
+    }
+
+    #[test]
+    fn test_stake_scales_draws() {
+        let mut round = AggregationRound { aggregators: 2, ..Default::default() };
+        let node = |i: u8| Pubkey::new_from_array([i; 32]);
+        round.consider(AggregatorDraw { node: node(1), score: 4 << 32, stake: 1 });
+        round.consider(AggregatorDraw { node: node(2), score: 6 << 32, stake: 3 });
+        round.consider(AggregatorDraw { node: node(3), score: 3 << 32, stake: 1 });
+        round.consider(AggregatorDraw { node: node(4), score: 9 << 32, stake: 1 });
+
+        let selected: Vec<_> = round.selected.iter().map(|draw| draw.node).collect();
+        assert_eq!(selected, vec![node(2), node(3)]);
+        assert!(!round.is_selected(&node(1)));
+    }
+}
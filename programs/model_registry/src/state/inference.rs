@@ -0,0 +1,78 @@
+// contracts/programs/model_registry/src/state/inference.rs
+
+use anchor_lang::prelude::*;
+use solana_program::pubkey::Pubkey;
+
+#[account]
+#[derive(Default)]
+pub struct InferenceRequest {
+    pub model: Pubkey,             // Model account the request targets
+    pub requester: Pubkey,         // Fee payer and result recipient
+    pub input_hash: [u8; 32],      // Commitment to the private input
+    pub status: InferenceStatus,
+    pub fee: u64,                  // Amount paid, in lamports or token base units
+    pub fee_mint: Option<Pubkey>,  // None when paid in lamports
+    pub created_at: i64,
+    pub provider: Option<Pubkey>,  // Set on fulfillment
+    pub output_hash: [u8; 32],     // Commitment to the result, zero until fulfilled
+    pub fulfilled_at: i64,
+    pub proof_hash: [u8; 32],      // Optimistic mode: hash of the unverified proof
+    pub challenge_deadline: i64,   // Optimistic mode: end of the fraud-proof window
+    pub bond: u64,                 // Optimistic mode: provider stake locked for this request
+    pub claimant: Option<Pubkey>,  // Provider holding the claim, if any
+    pub claim_expires_at: i64,     // Claim lapses here so a crashed provider cannot stall the request
+    pub input_uri: String,         // Where providers fetch the input; must match `input_hash`
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum InferenceStatus {
+    #[default]
+    Pending,
+    Completed,
+    Failed,
+    /// Result posted optimistically; final once the challenge window closes
+    Fulfilled,
+    /// Proof shown invalid within the window; provider slashed, fee refunded
+    Challenged,
+}
+
+/// Upper bound on `input_uri`, enough for an `ipfs://` CIDv1 or an https URL
+pub const MAX_INPUT_URI_LEN: usize = 128;
+
+/// Longest a single claim may run before another provider can take the request
+pub const MAX_CLAIM_SECS: i64 = 3600;
+
+impl InferenceRequest {
+    /// Statement the provider's proof is checked against: binds output to input
+    pub fn public_commitment(&self, output_hash: &[u8; 32]) -> [u8; 32] {
+        solana_program::hash::hashv(&[&self.input_hash, output_hash]).to_bytes()
+    }
+
+    /// Provider whose claim is still live at `now`
+    pub fn active_claimant(&self, now: i64) -> Option<Pubkey> {
+        self.claimant.filter(|_| now < self.claim_expires_at)
+    }
+
+    /// Space calculation for account initialization
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // model
+        32 + // requester
+        32 + // input_hash
+        1 +  // status
+        8 +  // fee
+        1 + 32 + // fee_mint (Option)
+        8 +  // created_at
+        1 + 32 + // provider (Option)
+        32 + // output_hash
+        8 +  // fulfilled_at
+        32 + // proof_hash
+        8 +  // challenge_deadline
+        8 +  // bond
+        1 + 32 + // claimant (Option)
+        8 +  // claim_expires_at
+        4 + MAX_INPUT_URI_LEN + // input_uri
+        1    // bump
+    }
+}
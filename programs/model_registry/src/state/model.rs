@@ -9,16 +9,25 @@ use std::collections::BTreeMap;
 pub struct ModelAccount {
     // Core Metadata
     pub model_hash: [u8; 32],      // SHA3-256 of model binary
+    pub seed_hash: [u8; 32],       // Hash registered under; PDA seed, never updated
     pub zk_circuit: [u8; 32],      // Poseidon hash of ZK circuit
     pub owner: Pubkey,             // Original uploader
     pub timestamp: i64,            // Unix epoch seconds
     pub storage_fee: u64,          // Lamports paid per epoch
+    pub inference_fee: u64,        // Lamports charged per inference request
+    pub expires_at: i64,           // Storage paid through (Unix epoch seconds)
 
     // Version Control
     pub active_version: u64,       // Currently deployed version
     pub last_update: i64,          // Last version change time
     pub version_history: Vec<[u8; 32]>, // Merkle tree of past hashes
 
+    // Interface
+    pub input_schema_hash: [u8; 32],  // SHA2-256 of the input schema on IPFS; zero if undeclared
+    pub output_schema_hash: [u8; 32], // SHA2-256 of the output schema on IPFS; zero if undeclared
+    pub metadata_uri: String,         // ipfs:// URI of the catalog document; empty if unset
+    pub storage_uri: String,          // Active version's encrypted blob, or a delta against the previous one
+
     // Access Control
     pub acl: BTreeMap<Pubkey, AccessLevel>, // Permission levels
     pub is_public: bool,            // Open inference access
@@ -30,6 +39,10 @@ pub struct ModelAccount {
     // Governance
     pub governance_model: GovernanceType,
     pub dao: Option<Pubkey>,        // Associated DAO
+
+    // Lineage
+    pub parent_model: Option<Pubkey>, // Upstream model this one was forked from
+    pub upstream_royalty_bps: u16,    // Share of settled fees owed to the parent
     
     // Security
     pub emergency_pause: bool,
@@ -39,7 +52,8 @@ pub struct ModelAccount {
     pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+/// Ordered by privilege; `check_access` compares with `>=`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum AccessLevel {
     NoAccess,
     InferenceOnly,
@@ -57,28 +71,48 @@ pub enum GovernanceType {
 impl ModelAccount {
     pub const MAX_CONTRIBUTORS: usize = 100;
     pub const VERSION_HISTORY_DEPTH: usize = 256;
+    pub const MAX_ACL_ENTRIES: usize = 100;
     pub const ACL_ENTRY_SIZE: usize = 32 + 1; // Pubkey + AccessLevel
+    /// Billing period, roughly one Solana epoch (432k slots at 400ms)
+    pub const STORAGE_EPOCH_SECONDS: i64 = 172_800;
+    /// Read-only window after expiry before anyone may reclaim the account
+    pub const EXPIRY_GRACE_EPOCHS: i64 = 7;
+    /// Longest prepaid term accepted by a single renewal
+    pub const MAX_RENEWAL_EPOCHS: u64 = 183;
+    /// `ipfs://` plus a CIDv1, with headroom for a path suffix
+    pub const MAX_METADATA_URI_LEN: usize = 128;
+    /// `scoria-ec://` or `scoria-delta://` plus a CIDv1
+    pub const MAX_STORAGE_URI_LEN: usize = 128;
 
-    /// Space calculation for account initialization
+    /// Space calculation for account initialization, discriminator included
     pub fn space() -> usize {
         8 + // Anchor discriminant
         32 + // model_hash
+        32 + // seed_hash
         32 + // zk_circuit
         32 + // owner
         8 +  // timestamp
         8 +  // storage_fee
+        8 +  // inference_fee
+        8 +  // expires_at
         8 +  // active_version
         8 +  // last_update
-        (Self::VERSION_HISTORY_DEPTH * 32) + // version_history
-        (Self::MAX_CONTRIBUTORS * 32) + // contributors
+        (4 + Self::VERSION_HISTORY_DEPTH * 32) + // version_history
+        32 + // input_schema_hash
+        32 + // output_schema_hash
+        (4 + Self::MAX_METADATA_URI_LEN) + // metadata_uri
+        (4 + Self::MAX_STORAGE_URI_LEN) + // storage_uri
+        (4 + Self::MAX_CONTRIBUTORS * 32) + // contributors
         8 +  // contribution_threshold
         1 +  // is_public
         1 +  // governance_model (enum tag)
-        32 + // dao (Option)
+        33 + // dao (Option)
+        33 + // parent_model (Option)
+        2 +  // upstream_royalty_bps
         1 +  // emergency_pause
-        (3 * 64) + // audit_signatures (3 auditors max)
+        (4 + 3 * 64) + // audit_signatures (3 auditors max)
         1 +  // bump
-        (Self::MAX_CONTRIBUTORS * Self::ACL_ENTRY_SIZE) // acl
+        (4 + Self::MAX_ACL_ENTRIES * Self::ACL_ENTRY_SIZE) // acl
     }
 
     /// Validate model owner or authorized delegate
@@ -96,6 +130,70 @@ impl ModelAccount {
         }
     }
 
+    /// Owner manages every entry; Administrators manage entries below their own level
+    pub fn can_manage_acl(&self, actor: &Pubkey, target: Option<&AccessLevel>, level: &AccessLevel) -> bool {
+        if actor == &self.owner {
+            return true;
+        }
+        self.acl.get(actor) == Some(&AccessLevel::Administrator)
+            && level < &AccessLevel::Administrator
+            && target != Some(&AccessLevel::Administrator)
+    }
+
+    /// Set `user`'s level, returning the previous one
+    pub fn grant(&mut self, user: Pubkey, level: AccessLevel) -> Result<Option<AccessLevel>> {
+        require!(level != AccessLevel::NoAccess, ModelRegistryError::InvalidAccessLevel);
+        require!(user != self.owner, ModelRegistryError::InvalidAccessLevel);
+        require!(
+            self.acl.contains_key(&user) || self.acl.len() < Self::MAX_ACL_ENTRIES,
+            ModelRegistryError::AclFull
+        );
+        Ok(self.acl.insert(user, level))
+    }
+
+    /// Storage lapsed; the model is read-only until renewed
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Past the grace period; the account may be reclaimed
+    pub fn is_reclaimable(&self, now: i64) -> bool {
+        now >= self.expires_at + Self::EXPIRY_GRACE_EPOCHS * Self::STORAGE_EPOCH_SECONDS
+    }
+
+    /// Fee share owed to the parent model; zero for models that are not forks
+    pub fn upstream_royalty(&self, fee: u64) -> u64 {
+        match self.parent_model {
+            Some(_) => (fee as u128 * self.upstream_royalty_bps as u128 / 10_000) as u64,
+            None => 0,
+        }
+    }
+
+    /// Reject writes while the model's emergency pause is set
+    pub fn require_unpaused(&self) -> Result<()> {
+        require!(!self.emergency_pause, ModelRegistryError::ModelPaused);
+        Ok(())
+    }
+
+    /// Reject writes to models whose storage has lapsed
+    pub fn require_active(&self, now: i64) -> Result<()> {
+        require!(!self.is_expired(now), ModelRegistryError::StorageExpired);
+        Ok(())
+    }
+
+    /// Extend paid storage by `epochs`, counting from now if already lapsed
+    pub fn extend_storage(&mut self, epochs: u64, now: i64) -> Result<i64> {
+        require!(
+            epochs > 0 && epochs <= Self::MAX_RENEWAL_EPOCHS,
+            ModelRegistryError::InvalidRenewal
+        );
+        let from = self.expires_at.max(now);
+        self.expires_at = from
+            .checked_add(epochs as i64 * Self::STORAGE_EPOCH_SECONDS)
+            .ok_or(ModelRegistryError::InvalidRenewal)?;
+        Ok(self.expires_at)
+    }
+
     /// Add version to merkleized history
     pub fn record_version(&mut self, new_hash: [u8; 32]) -> Result<()> {
         require!(
@@ -152,6 +250,9 @@ pub enum ModelField {
     AccessControl,
     GovernanceModel,
     PauseStatus,
+    PublicAccess,
+    IoSchema,
+    MetadataUri,
 }
 
 #[error_code]
@@ -170,5 +271,13 @@ pub enum ModelRegistryError {
     ContributorLimit,
     #[msg("Contributor already exists")]
     DuplicateContributor,
+    #[msg("Model storage has expired; renew to modify")]
+    StorageExpired,
+    #[msg("Renewal must cover between 1 and MAX_RENEWAL_EPOCHS epochs")]
+    InvalidRenewal,
+    #[msg("Access level cannot be granted to this account")]
+    InvalidAccessLevel,
+    #[msg("Access control list is full")]
+    AclFull,
     // ... (previous errors)
 }
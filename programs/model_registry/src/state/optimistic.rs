@@ -0,0 +1,89 @@
+// contracts/programs/model_registry/src/state/optimistic.rs
+
+use anchor_lang::prelude::*;
+use solana_program::pubkey::Pubkey;
+
+/// Per-model settings for optimistic fulfillment; absent means proofs are verified on-chain
+#[account]
+#[derive(Default)]
+pub struct OptimisticConfig {
+    pub model: Pubkey,
+    pub challenge_window: i64,        // Seconds a fulfillment stays open to fraud proofs
+    pub bond: u64,                    // Provider stake locked per open fulfillment (lamports)
+    pub challenger_reward_bps: u16,   // Share of a slashed bond paid to the challenger
+    pub bump: u8,
+}
+
+/// Lamports a provider has bonded to serve a model optimistically
+#[account]
+#[derive(Default)]
+pub struct ProviderStake {
+    pub model: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,    // Total bonded, held on this account
+    pub locked: u64,    // Portion backing fulfillments still in their challenge window
+    pub bump: u8,
+}
+
+impl OptimisticConfig {
+    /// Longest challenge window a model owner may configure
+    pub const MAX_CHALLENGE_WINDOW: i64 = 7 * 24 * 60 * 60;
+    pub const MAX_BPS: u16 = 10_000;
+
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // model
+        8 +  // challenge_window
+        8 +  // bond
+        2 +  // challenger_reward_bps
+        1    // bump
+    }
+
+    /// Split a slashed bond into (challenger reward, requester compensation)
+    pub fn split_slash(&self, bond: u64) -> (u64, u64) {
+        let reward = (bond as u128 * self.challenger_reward_bps as u128 / Self::MAX_BPS as u128) as u64;
+        (reward, bond - reward)
+    }
+}
+
+impl ProviderStake {
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // model
+        32 + // provider
+        8 +  // amount
+        8 +  // locked
+        1    // bump
+    }
+
+    pub fn available(&self) -> u64 {
+        self.amount.saturating_sub(self.locked)
+    }
+
+    /// Reserve `bond` for a new fulfillment
+    pub fn lock(&mut self, bond: u64) -> Result<()> {
+        require!(self.available() >= bond, ModelRegistryError::InsufficientStake);
+        self.locked += bond;
+        Ok(())
+    }
+
+    /// Fulfillment finalized unchallenged
+    pub fn unlock(&mut self, bond: u64) {
+        self.locked = self.locked.saturating_sub(bond);
+    }
+
+    /// Fulfillment proven fraudulent; the bond leaves the stake
+    pub fn slash(&mut self, bond: u64) -> u64 {
+        let slashed = bond.min(self.amount);
+        self.locked = self.locked.saturating_sub(bond);
+        self.amount -= slashed;
+        slashed
+    }
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Insufficient staking balance")]
+    InsufficientStake,
+    // ... (previous errors)
+}
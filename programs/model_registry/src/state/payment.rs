@@ -0,0 +1,76 @@
+// contracts/programs/model_registry/src/state/payment.rs
+
+use anchor_lang::prelude::*;
+use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+/// Governance-managed whitelist of SPL fee tokens
+#[account]
+#[derive(Default)]
+pub struct PaymentConfig {
+    pub authority: Pubkey,                 // Governance authority allowed to edit the whitelist
+    pub accepted_mints: Vec<AcceptedMint>, // Tokens fees may be paid in
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AcceptedMint {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,     // Token account owned by the payment config PDA
+    pub escrow: Pubkey,       // Holds inference fees until the request is fulfilled
+    pub tokens_per_sol: u64,  // Base units charged per 1 SOL of lamport-denominated fee
+    pub enabled: bool,
+}
+
+impl PaymentConfig {
+    pub const MAX_MINTS: usize = 8;
+    pub const MINT_ENTRY_SIZE: usize = 32 + 32 + 32 + 8 + 1;
+
+    /// Space calculation for account initialization
+    pub fn space() -> usize {
+        8 +  // Anchor discriminant
+        32 + // authority
+        4 + (Self::MAX_MINTS * Self::MINT_ENTRY_SIZE) + // accepted_mints
+        1    // bump
+    }
+
+    /// Whitelist entry for `mint`, rejecting unknown or disabled tokens
+    pub fn accepted(&self, mint: &Pubkey) -> Result<&AcceptedMint> {
+        self.accepted_mints
+            .iter()
+            .find(|m| &m.mint == mint && m.enabled)
+            .ok_or_else(|| error!(ModelRegistryError::InvalidPaymentToken))
+    }
+
+    pub fn add_mint(&mut self, entry: AcceptedMint) -> Result<()> {
+        require!(
+            self.accepted_mints.iter().all(|m| m.mint != entry.mint),
+            ModelRegistryError::InvalidPaymentToken
+        );
+        require!(
+            self.accepted_mints.len() < Self::MAX_MINTS,
+            ModelRegistryError::PaymentMintLimit
+        );
+        self.accepted_mints.push(entry);
+        Ok(())
+    }
+}
+
+impl AcceptedMint {
+    /// Convert a lamport fee into this token's base units, rounding up
+    pub fn token_amount(&self, lamports: u64) -> Result<u64> {
+        let amount = (lamports as u128 * self.tokens_per_sol as u128 + LAMPORTS_PER_SOL as u128 - 1)
+            / LAMPORTS_PER_SOL as u128;
+        u64::try_from(amount).map_err(|_| error!(ModelRegistryError::InsufficientFee))
+    }
+}
+
+#[error_code]
+pub enum ModelRegistryError {
+    #[msg("Payment token not whitelisted")]
+    InvalidPaymentToken,
+    #[msg("Payment mint whitelist is full")]
+    PaymentMintLimit,
+    #[msg("Insufficient storage fee")]
+    InsufficientFee,
+    // ... (previous errors)
+}
@@ -130,6 +130,281 @@ pub mod merkle_utils {
     }
 }
 
+/// BN254 pairing checks through Solana's alt_bn128 syscalls.
+///
+/// Points use the EIP-197 big-endian encoding: G1 as `x || y`, G2 as
+/// `x_im || x_re || y_im || y_re`; the point at infinity is all zeros.
+pub mod bn254 {
+    use super::*;
+    use solana_program::alt_bn128::prelude::{
+        alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+    };
+
+    pub type G1 = [u8; 64];
+    pub type G2 = [u8; 128];
+    pub type Scalar = [u8; 32];
+
+    /// Base field modulus q
+    const FIELD_MODULUS: [u8; 32] = [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+        0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+    ];
+    /// Scalar field modulus r
+    const SCALAR_MODULUS: [u8; 32] = [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+        0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+    ];
+
+    /// G1 generator (1, 2)
+    pub fn g1_generator() -> G1 {
+        let mut g = [0u8; 64];
+        g[31] = 1;
+        g[63] = 2;
+        g
+    }
+
+    pub fn g1_add(a: &G1, b: &G1) -> Result<G1> {
+        let out = alt_bn128_addition(&[&a[..], &b[..]].concat())
+            .map_err(|_| ModelRegistryError::ZkVerificationFailure)?;
+        out.try_into().map_err(|_| ModelRegistryError::ZkVerificationFailure.into())
+    }
+
+    pub fn g1_mul(p: &G1, scalar: &Scalar) -> Result<G1> {
+        let out = alt_bn128_multiplication(&[&p[..], &scalar[..]].concat())
+            .map_err(|_| ModelRegistryError::ZkVerificationFailure)?;
+        out.try_into().map_err(|_| ModelRegistryError::ZkVerificationFailure.into())
+    }
+
+    /// -P = (x, q - y)
+    pub fn g1_neg(p: &G1) -> G1 {
+        if p.iter().all(|&b| b == 0) {
+            return *p;
+        }
+        let mut out = *p;
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let d = FIELD_MODULUS[i] as i16 - p[32 + i] as i16 - borrow;
+            out[32 + i] = d.rem_euclid(256) as u8;
+            borrow = (d < 0) as i16;
+        }
+        out
+    }
+
+    /// Product of pairings equals one
+    pub fn pairing_check(pairs: &[(G1, G2)]) -> Result<bool> {
+        let input: Vec<u8> = pairs
+            .iter()
+            .flat_map(|(g1, g2)| g1.iter().chain(g2.iter()).copied())
+            .collect();
+        let out = alt_bn128_pairing(&input).map_err(|_| ModelRegistryError::ZkVerificationFailure)?;
+        Ok(out.len() == 32 && out[..31].iter().all(|&b| b == 0) && out[31] == 1)
+    }
+
+    /// Canonical scalar field element (< r)
+    pub fn is_scalar(x: &Scalar) -> bool {
+        x < &SCALAR_MODULUS
+    }
+
+    /// Map a 256-bit hash into the scalar field by clearing the top three bits
+    pub fn hash_to_scalar(hash: &[u8; 32]) -> Scalar {
+        let mut s = *hash;
+        s[0] &= 0x1f;
+        s
+    }
+
+    /// Split a base field coordinate into (low, high) 128-bit scalars
+    pub fn coordinate_limbs(coord: &[u8]) -> [Scalar; 2] {
+        let mut lo = [0u8; 32];
+        let mut hi = [0u8; 32];
+        lo[16..].copy_from_slice(&coord[16..32]);
+        hi[16..].copy_from_slice(&coord[..16]);
+        [lo, hi]
+    }
+
+    pub struct Groth16VerifyingKey<'a> {
+        pub alpha_g1: &'a G1,
+        pub beta_g2: &'a G2,
+        pub gamma_g2: &'a G2,
+        pub delta_g2: &'a G2,
+        pub ic: &'a [G1],
+    }
+
+    /// e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+    pub fn verify_groth16(
+        vk: &Groth16VerifyingKey,
+        a: &G1,
+        b: &G2,
+        c: &G1,
+        public_inputs: &[Scalar],
+    ) -> Result<bool> {
+        if vk.ic.len() != public_inputs.len() + 1 || !public_inputs.iter().all(is_scalar) {
+            return Ok(false);
+        }
+
+        let mut vk_x = vk.ic[0];
+        for (input, ic) in public_inputs.iter().zip(&vk.ic[1..]) {
+            vk_x = g1_add(&vk_x, &g1_mul(ic, input)?)?;
+        }
+
+        pairing_check(&[
+            (g1_neg(a), *b),
+            (*vk.alpha_g1, *vk.beta_g2),
+            (vk_x, *vk.gamma_g2),
+            (*c, *vk.delta_g2),
+        ])
+    }
+
+    /// KZG opening of `commitment` to `eval` at `point`.
+    ///
+    /// Checks e(C - y*G + z*pi, H) * e(-pi, tau*H) == 1, which needs only G1
+    /// arithmetic on-chain.
+    pub fn verify_kzg_opening(
+        commitment: &G1,
+        point: &Scalar,
+        eval: &Scalar,
+        proof: &G1,
+        g2: &G2,
+        tau_g2: &G2,
+    ) -> Result<bool> {
+        let y_g = g1_mul(&g1_generator(), eval)?;
+        let z_pi = g1_mul(proof, point)?;
+        let lhs = g1_add(&g1_add(commitment, &g1_neg(&y_g))?, &z_pi)?;
+        pairing_check(&[(lhs, *g2), (g1_neg(proof), *tau_g2)])
+    }
+}
+
+/// Introspection of Ed25519 program instructions in the same transaction
+pub mod ed25519 {
+    use super::*;
+    use solana_program::{
+        ed25519_program,
+        sysvar::instructions::{get_instruction_relative, load_current_index_checked},
+    };
+
+    /// Offsets entries refer to data in their own instruction
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    /// Require the instruction right before this one to be an Ed25519 program
+    /// check of exactly one `signer` signature over `message`; returns that signature
+    pub fn verify_preceding(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<[u8; 64]> {
+        let missing = || error!(ModelRegistryError::MissingEd25519Verification);
+        if load_current_index_checked(instructions)? == 0 {
+            return Err(missing());
+        }
+        let ix = get_instruction_relative(-1, instructions).map_err(|_| missing())?;
+        require_keys_eq!(ix.program_id, ed25519_program::id(), ModelRegistryError::MissingEd25519Verification);
+
+        // [count u8, padding u8, then per signature 7 x u16 offsets]
+        let data = &ix.data;
+        require!(data.len() >= 16 && data[0] == 1, ModelRegistryError::MissingEd25519Verification);
+        let field = |i: usize| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]);
+        let (sig_offset, sig_ix) = (field(0) as usize, field(1));
+        let (key_offset, key_ix) = (field(2) as usize, field(3));
+        let (msg_offset, msg_len, msg_ix) = (field(4) as usize, field(5) as usize, field(6));
+        require!(
+            sig_ix == THIS_INSTRUCTION && key_ix == THIS_INSTRUCTION && msg_ix == THIS_INSTRUCTION,
+            ModelRegistryError::MissingEd25519Verification
+        );
+
+        let key = data.get(key_offset..key_offset + 32).ok_or_else(missing)?;
+        let signed = data.get(msg_offset..msg_offset + msg_len).ok_or_else(missing)?;
+        require!(
+            key == signer.as_ref() && signed == message,
+            ModelRegistryError::MissingEd25519Verification
+        );
+        let signature = data.get(sig_offset..sig_offset + 64).ok_or_else(missing)?;
+        Ok(signature.try_into().unwrap())
+    }
+}
+
+/// ECVRF-EDWARDS25519-SHA512-TAI (RFC 9381) verification through the
+/// curve25519 syscalls. Proofs are made with a node's Ed25519 key, so the
+/// public key is the node's address.
+pub mod ecvrf {
+    use super::*;
+    use sha2::Sha512;
+    use solana_zk_token_sdk::curve25519::{
+        edwards::{multiply_edwards, subtract_edwards, validate_edwards, PodEdwardsPoint},
+        scalar::PodScalar,
+    };
+
+    /// `Gamma || c || s`
+    pub const PROOF_LEN: usize = 32 + 16 + 32;
+
+    const SUITE: u8 = 0x03;
+    /// Compressed Ed25519 base point, y = 4/5
+    const BASE_POINT: PodEdwardsPoint = PodEdwardsPoint([
+        0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+        0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    ]);
+    /// Compressed neutral element, (0, 1)
+    const IDENTITY: PodEdwardsPoint = PodEdwardsPoint([
+        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ]);
+    /// Group order L, little-endian
+    const ORDER: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+    ];
+
+    /// The proof's 64-byte output if it is valid for `public` and `alpha`
+    pub fn verify(public: &Pubkey, alpha: &[u8], proof: &[u8; PROOF_LEN]) -> Option<[u8; 64]> {
+        let y = PodEdwardsPoint(public.to_bytes());
+        let gamma = PodEdwardsPoint(proof[..32].try_into().unwrap());
+        let c: [u8; 16] = proof[32..48].try_into().unwrap();
+        let s: [u8; 32] = proof[48..].try_into().unwrap();
+        if !validate_edwards(&y) || !validate_edwards(&gamma) || cofactor(&y)? == IDENTITY || !below_order(&s) {
+            return None;
+        }
+
+        let h = encode_to_curve(&y, alpha)?;
+        let mut c_scalar = [0u8; 32];
+        c_scalar[..16].copy_from_slice(&c);
+        let (c_scalar, s) = (PodScalar(c_scalar), PodScalar(s));
+        // U = s*B - c*Y, V = s*H - c*Gamma
+        let u = subtract_edwards(&multiply_edwards(&s, &BASE_POINT)?, &multiply_edwards(&c_scalar, &y)?)?;
+        let v = subtract_edwards(&multiply_edwards(&s, &h)?, &multiply_edwards(&c_scalar, &gamma)?)?;
+        if challenge(&[&y, &h, &gamma, &u, &v]) != c {
+            return None;
+        }
+        let mut output = [0u8; 64];
+        output.copy_from_slice(&Sha512::new().chain_update([SUITE, 0x03]).chain_update(cofactor(&gamma)?.0).chain_update([0x00]).finalize());
+        Some(output)
+    }
+
+    fn encode_to_curve(y: &PodEdwardsPoint, alpha: &[u8]) -> Option<PodEdwardsPoint> {
+        (0..=u8::MAX).find_map(|ctr| {
+            let hash = Sha512::new()
+                .chain_update([SUITE, 0x01])
+                .chain_update(y.0)
+                .chain_update(alpha)
+                .chain_update([ctr, 0x00])
+                .finalize();
+            let candidate = PodEdwardsPoint(hash[..32].try_into().unwrap());
+            validate_edwards(&candidate).then(|| cofactor(&candidate)).flatten()
+        })
+    }
+
+    fn challenge(points: &[&PodEdwardsPoint]) -> [u8; 16] {
+        let mut hasher = Sha512::new().chain_update([SUITE, 0x02]);
+        for point in points {
+            hasher.update(point.0);
+        }
+        hasher.update([0x00]);
+        hasher.finalize()[..16].try_into().unwrap()
+    }
+
+    fn cofactor(point: &PodEdwardsPoint) -> Option<PodEdwardsPoint> {
+        let mut eight = [0u8; 32];
+        eight[0] = 8;
+        multiply_edwards(&PodScalar(eight), point)
+    }
+
+    fn below_order(s: &[u8; 32]) -> bool {
+        s.iter().rev().cmp(ORDER.iter().rev()) == std::cmp::Ordering::Less
+    }
+}
+
 /// Streaming hash for large model files
 pub struct ModelHasher {
     blake3: Blake3,
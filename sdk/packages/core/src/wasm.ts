@@ -0,0 +1,154 @@
+// sdk/packages/core/src/wasm.ts
+
+import { PublicKey, TransactionInstruction } from '@solana/web3.js';
+import init, { ScoriaClient, ModelHasher, blake3, verifyGroth16 } from '../wasm/scoria_sdk.js';
+
+/** Shape returned by the `scoria-sdk` instruction builders */
+interface WasmInstruction {
+  programId: string;
+  keys: { pubkey: string; isSigner: boolean; isWritable: boolean }[];
+  data: Uint8Array;
+}
+
+let ready: Promise<unknown> | undefined;
+
+/** Load the WASM module once; pass a URL or bytes when not served next to the bundle */
+export function initScoriaSdk(module?: Parameters<typeof init>[0]): Promise<unknown> {
+  ready ??= init(module);
+  return ready;
+}
+
+export function toTransactionInstruction(ix: WasmInstruction): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: new PublicKey(ix.programId),
+    keys: ix.keys.map((k) => ({ ...k, pubkey: new PublicKey(k.pubkey) })),
+    data: Buffer.from(ix.data),
+  });
+}
+
+/** Light client over the WASM builders, returning web3.js instructions */
+export class ScoriaLightClient {
+  private readonly inner: ScoriaClient;
+
+  constructor(registryProgramId: PublicKey, governanceProgramId: PublicKey) {
+    this.inner = new ScoriaClient(registryProgramId.toBase58(), governanceProgramId.toBase58());
+  }
+
+  registerModel(
+    payer: PublicKey,
+    adminAuthority: PublicKey,
+    modelHash: Uint8Array,
+    zkCircuitHash: Uint8Array,
+    storageFee: bigint,
+    inferenceFee: bigint,
+    feeMint?: PublicKey,
+  ): TransactionInstruction {
+    return toTransactionInstruction(
+      this.inner.registerModel(
+        payer.toBase58(),
+        adminAuthority.toBase58(),
+        modelHash,
+        zkCircuitHash,
+        storageFee,
+        inferenceFee,
+        feeMint?.toBase58(),
+      ),
+    );
+  }
+
+  requestInference(
+    requester: PublicKey,
+    model: PublicKey,
+    inputHash: Uint8Array,
+    inputUri: string,
+    zkProof: Uint8Array,
+    feeMint?: PublicKey,
+    useAccessToken = false,
+  ): TransactionInstruction {
+    return toTransactionInstruction(
+      this.inner.requestInference(
+        requester.toBase58(),
+        model.toBase58(),
+        inputHash,
+        inputUri,
+        zkProof,
+        feeMint?.toBase58(),
+        useAccessToken,
+      ),
+    );
+  }
+
+  /**
+   * `contribution` is a fresh keypair that must co-sign the transaction;
+   * `recordsRoot` is the Merkle root over the `recordCount` records
+   */
+  contributeData(
+    contributor: PublicKey,
+    model: PublicKey,
+    contribution: PublicKey,
+    encryptedData: Uint8Array,
+    dataHash: Uint8Array,
+    recordsRoot: Uint8Array,
+    recordCount: bigint,
+  ): TransactionInstruction {
+    return toTransactionInstruction(
+      this.inner.contributeData(
+        contributor.toBase58(),
+        model.toBase58(),
+        contribution.toBase58(),
+        encryptedData,
+        dataHash,
+        recordsRoot,
+        recordCount,
+      ),
+    );
+  }
+
+  castVote(
+    voter: PublicKey,
+    proposal: PublicKey,
+    governanceMint: PublicKey,
+    choice: number,
+    weight: bigint,
+    proof: Uint8Array,
+    onBehalfOf?: PublicKey,
+  ): TransactionInstruction {
+    return toTransactionInstruction(
+      this.inner.castVote(
+        voter.toBase58(),
+        proposal.toBase58(),
+        governanceMint.toBase58(),
+        choice,
+        weight,
+        proof,
+        onBehalfOf?.toBase58(),
+      ),
+    );
+  }
+
+  modelAddress(modelHash: Uint8Array): PublicKey {
+    return new PublicKey(this.inner.modelAddress(modelHash));
+  }
+
+  accessTokenAddress(model: PublicKey, holder: PublicKey): PublicKey {
+    return new PublicKey(this.inner.accessTokenAddress(model.toBase58(), holder.toBase58()));
+  }
+}
+
+/** BLAKE3 of a model streamed chunk by chunk */
+export async function hashModel(stream: ReadableStream<Uint8Array>): Promise<Uint8Array> {
+  const hasher = new ModelHasher();
+  const reader = stream.getReader();
+  try {
+    for (;;) {
+      const { done, value } = await reader.read();
+      if (done) break;
+      hasher.update(value);
+    }
+    return hasher.finalize();
+  } finally {
+    hasher.free();
+  }
+}
+
+export { blake3, verifyGroth16 };
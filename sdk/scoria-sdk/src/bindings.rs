@@ -0,0 +1,233 @@
+// sdk/scoria-sdk/src/bindings.rs
+
+//! JS surface. Instructions come back as plain
+//! `{ programId, keys: [{ pubkey, isSigner, isWritable }], data }` objects,
+//! which `@scoria/ai-core`'s `wasm.ts` turns into web3.js `TransactionInstruction`s.
+
+use crate::{hash, instructions, verify};
+use serde::Serialize;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsAccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsInstruction {
+    program_id: String,
+    keys: Vec<JsAccountMeta>,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+fn to_js(ix: Instruction) -> Result<JsValue, JsError> {
+    let ix = JsInstruction {
+        program_id: ix.program_id.to_string(),
+        keys: ix
+            .accounts
+            .into_iter()
+            .map(|a| JsAccountMeta { pubkey: a.pubkey.to_string(), is_signer: a.is_signer, is_writable: a.is_writable })
+            .collect(),
+        data: ix.data,
+    };
+    Ok(serde_wasm_bindgen::to_value(&ix)?)
+}
+
+fn pubkey(value: &str, what: &str) -> Result<Pubkey, JsError> {
+    Pubkey::from_str(value).map_err(|e| JsError::new(&format!("{what}: {e}")))
+}
+
+fn hash32(value: &[u8], what: &str) -> Result<[u8; 32], JsError> {
+    value.try_into().map_err(|_| JsError::new(&format!("{what} must be 32 bytes, got {}", value.len())))
+}
+
+fn token_fee(mint: Option<String>) -> Result<Option<instructions::TokenFee>, JsError> {
+    mint.map(|m| Ok(instructions::TokenFee { mint: pubkey(&m, "feeMint")? })).transpose()
+}
+
+/// Instruction builders bound to deployed program ids
+#[wasm_bindgen]
+pub struct ScoriaClient {
+    registry: Pubkey,
+    governance: Pubkey,
+}
+
+#[wasm_bindgen]
+impl ScoriaClient {
+    #[wasm_bindgen(constructor)]
+    pub fn new(registry_program_id: &str, governance_program_id: &str) -> Result<ScoriaClient, JsError> {
+        Ok(Self {
+            registry: pubkey(registry_program_id, "registryProgramId")?,
+            governance: pubkey(governance_program_id, "governanceProgramId")?,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = registerModel)]
+    pub fn register_model(
+        &self,
+        payer: &str,
+        admin_authority: &str,
+        model_hash: &[u8],
+        zk_circuit_hash: &[u8],
+        storage_fee: u64,
+        inference_fee: u64,
+        fee_mint: Option<String>,
+    ) -> Result<JsValue, JsError> {
+        to_js(instructions::register_model(
+            &self.registry,
+            &pubkey(payer, "payer")?,
+            &pubkey(admin_authority, "adminAuthority")?,
+            hash32(model_hash, "modelHash")?,
+            hash32(zk_circuit_hash, "zkCircuitHash")?,
+            storage_fee,
+            inference_fee,
+            token_fee(fee_mint)?,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = requestInference)]
+    pub fn request_inference(
+        &self,
+        requester: &str,
+        model: &str,
+        input_hash: &[u8],
+        input_uri: String,
+        zk_proof: Vec<u8>,
+        fee_mint: Option<String>,
+        use_access_token: Option<bool>,
+    ) -> Result<JsValue, JsError> {
+        to_js(instructions::request_inference(
+            &self.registry,
+            &pubkey(requester, "requester")?,
+            &pubkey(model, "model")?,
+            hash32(input_hash, "inputHash")?,
+            input_uri,
+            zk_proof,
+            token_fee(fee_mint)?,
+            use_access_token.unwrap_or(false),
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = contributeData)]
+    pub fn contribute_data(
+        &self,
+        contributor: &str,
+        model: &str,
+        contribution: &str,
+        encrypted_data: Vec<u8>,
+        data_hash: &[u8],
+        records_root: &[u8],
+        record_count: u64,
+    ) -> Result<JsValue, JsError> {
+        to_js(instructions::contribute_data(
+            &self.registry,
+            &pubkey(contributor, "contributor")?,
+            &pubkey(model, "model")?,
+            &pubkey(contribution, "contribution")?,
+            encrypted_data,
+            hash32(data_hash, "dataHash")?,
+            hash32(records_root, "recordsRoot")?,
+            record_count,
+        ))
+    }
+
+    /// `onBehalfOf` defaults to the voter; `governanceMint` locates the
+    /// delegator's token account for delegated votes
+    #[wasm_bindgen(js_name = castVote)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_vote(
+        &self,
+        voter: &str,
+        proposal: &str,
+        governance_mint: &str,
+        choice: u8,
+        weight: u64,
+        proof: &[u8],
+        on_behalf_of: Option<String>,
+    ) -> Result<JsValue, JsError> {
+        let voter = pubkey(voter, "voter")?;
+        let owner = on_behalf_of.map(|o| pubkey(&o, "onBehalfOf")).transpose()?.unwrap_or(voter);
+        let proof = proof
+            .try_into()
+            .map_err(|_| JsError::new(&format!("proof must be a 64-byte signature, got {}", proof.len())))?;
+        to_js(instructions::cast_vote(
+            &self.governance,
+            &voter,
+            &pubkey(proposal, "proposal")?,
+            &owner,
+            &pubkey(governance_mint, "governanceMint")?,
+            choice,
+            weight,
+            proof,
+        ))
+    }
+
+    /// Model account address for a model hash
+    #[wasm_bindgen(js_name = modelAddress)]
+    pub fn model_address(&self, model_hash: &[u8]) -> Result<String, JsError> {
+        Ok(hash::model_pda(&self.registry, &hash32(model_hash, "modelHash")?).to_string())
+    }
+
+    /// Capability token address of `holder` on `model`
+    #[wasm_bindgen(js_name = accessTokenAddress)]
+    pub fn access_token_address(&self, model: &str, holder: &str) -> Result<String, JsError> {
+        Ok(hash::access_token_pda(&self.registry, &pubkey(model, "model")?, &pubkey(holder, "holder")?).to_string())
+    }
+}
+
+/// Streaming BLAKE3 for model files too large to buffer
+#[wasm_bindgen]
+pub struct ModelHasher(hash::ModelHasher);
+
+#[wasm_bindgen]
+impl ModelHasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ModelHasher {
+        Self(hash::ModelHasher::new())
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.0.update(chunk);
+    }
+
+    pub fn finalize(&self) -> Vec<u8> {
+        self.0.finalize().to_vec()
+    }
+}
+
+impl Default for ModelHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+pub fn blake3(bytes: &[u8]) -> Vec<u8> {
+    hash::blake3(bytes).to_vec()
+}
+
+/// Throws with the failure reason; the error's `name` is the failure class
+#[wasm_bindgen(js_name = verifyGroth16)]
+pub fn verify_groth16(proof: &[u8], vk: &[u8], public_inputs: &[u8]) -> Result<(), JsValue> {
+    verify::verify_groth16(proof, vk, public_inputs).map_err(|e| {
+        let class = match e {
+            verify::VerifyError::Invalid => "InvalidProof",
+            verify::VerifyError::PublicInputCount { .. } => "PublicInputCount",
+            verify::VerifyError::UnsupportedProtocol(_) => "UnsupportedProtocol",
+            verify::VerifyError::Malformed { .. } => "Malformed",
+        };
+        let error = js_sys::Error::new(&e.to_string());
+        error.set_name(class);
+        error.into()
+    })
+}
@@ -0,0 +1,71 @@
+// sdk/scoria-sdk/src/hash.rs
+
+use solana_program::pubkey::Pubkey;
+
+/// Incremental BLAKE3, matching the client's model and input hashes.
+/// Large models can be fed chunk by chunk from a `ReadableStream`.
+#[derive(Default)]
+pub struct ModelHasher(blake3::Hasher);
+
+impl ModelHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) -> &mut Self {
+        self.0.update(chunk);
+        self
+    }
+
+    pub fn finalize(&self) -> [u8; 32] {
+        *self.0.finalize().as_bytes()
+    }
+}
+
+/// BLAKE3 of a whole buffer; the `model_hash`/`input_hash` instruction argument
+pub fn blake3(bytes: &[u8]) -> [u8; 32] {
+    *blake3::hash(bytes).as_bytes()
+}
+
+/// `[b"model", model_hash]`
+pub fn model_pda(program_id: &Pubkey, model_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"model", model_hash], program_id).0
+}
+
+/// `[b"inference", model, input_hash]`
+pub fn inference_request_pda(program_id: &Pubkey, model: &Pubkey, input_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"inference", model.as_ref(), input_hash], program_id).0
+}
+
+/// `[b"access_token", model, holder]`
+pub fn access_token_pda(program_id: &Pubkey, model: &Pubkey, holder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"access_token", model.as_ref(), holder.as_ref()], program_id).0
+}
+
+/// `[b"vote", proposal, on_behalf_of]`
+pub fn vote_record_pda(program_id: &Pubkey, proposal: &Pubkey, on_behalf_of: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vote", proposal.as_ref(), on_behalf_of.as_ref()], program_id).0
+}
+
+/// Parse a 32-byte hash from hex
+pub fn parse_hash(hex_hash: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(hex_hash.trim_start_matches("0x")).map_err(|e| format!("invalid hash: {e}"))?;
+    bytes.try_into().map_err(|b: Vec<u8>| format!("hash must be 32 bytes, got {}", b.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streamed_hash_matches_one_shot() {
+        let data = vec![7u8; 3 * 1024 * 1024 + 17];
+        let mut hasher = ModelHasher::new();
+        for chunk in data.chunks(64 * 1024) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), blake3(&data));
+        assert_eq!(parse_hash(&hex::encode(blake3(&data))).unwrap(), blake3(&data));
+        assert!(parse_hash("abcd").is_err());
+    }
+}
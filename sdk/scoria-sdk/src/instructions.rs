@@ -0,0 +1,277 @@
+// sdk/scoria-sdk/src/instructions.rs
+
+use crate::hash::{access_token_pda, inference_request_pda, model_pda, vote_record_pda};
+use borsh::BorshSerialize;
+use solana_program::{
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Pay the fee in a whitelisted SPL token instead of lamports
+#[derive(Debug, Clone, Copy)]
+pub struct TokenFee {
+    pub mint: Pubkey,
+}
+
+impl TokenFee {
+    /// `payment_config`, the payer's ATA, `[seed, mint]` destination and token program
+    fn accounts(&self, program_id: &Pubkey, payer: &Pubkey, destination_seed: &[u8]) -> [AccountMeta; 4] {
+        let payment_config = Pubkey::find_program_address(&[b"payment_config"], program_id).0;
+        let destination = Pubkey::find_program_address(&[destination_seed, self.mint.as_ref()], program_id).0;
+        [
+            AccountMeta::new_readonly(payment_config, false),
+            AccountMeta::new(associated_token_address(payer, &self.mint), false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ]
+    }
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Anchor's `sha256("global:<name>")[..8]`
+fn discriminator(name: &str) -> [u8; 8] {
+    let mut out = [0; 8];
+    out.copy_from_slice(&hashv(&[b"global:", name.as_bytes()]).to_bytes()[..8]);
+    out
+}
+
+fn data(name: &str, args: impl BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator(name).to_vec();
+    args.serialize(&mut data).expect("serializing to a Vec cannot fail");
+    data
+}
+
+/// Anchor encodes an omitted `Option<Account>` as the program id
+fn absent(program_id: &Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(*program_id, false)
+}
+
+fn optional_fee(fee: Option<[AccountMeta; 4]>, program_id: &Pubkey) -> Vec<AccountMeta> {
+    fee.map(Vec::from).unwrap_or_else(|| vec![absent(program_id); 4])
+}
+
+fn not_paused(program_id: &Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(Pubkey::find_program_address(&[b"program_pause"], program_id).0, false)
+}
+
+#[derive(BorshSerialize)]
+struct RegisterModelArgs {
+    model_hash: [u8; 32],
+    zk_circuit_hash: [u8; 32],
+    storage_fee: u64,
+    inference_fee: u64,
+}
+
+/// `register_model`; signed by `payer` and the registry's admin authority
+#[allow(clippy::too_many_arguments)]
+pub fn register_model(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    admin_authority: &Pubkey,
+    model_hash: [u8; 32],
+    zk_circuit_hash: [u8; 32],
+    storage_fee: u64,
+    inference_fee: u64,
+    fee: Option<TokenFee>,
+) -> Instruction {
+    let admin = Pubkey::find_program_address(&[b"admin"], program_id).0;
+    let mut accounts = vec![
+        AccountMeta::new(admin, false),
+        AccountMeta::new(model_pda(program_id, &model_hash), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*admin_authority, true),
+    ];
+    accounts.extend(optional_fee(fee.map(|f| f.accounts(program_id, payer, b"treasury")), program_id));
+    accounts.extend([
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        not_paused(program_id),
+    ]);
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: data(
+            "register_model",
+            RegisterModelArgs { model_hash, zk_circuit_hash, storage_fee, inference_fee },
+        ),
+    }
+}
+
+#[derive(BorshSerialize)]
+struct RequestInferenceArgs {
+    input_hash: [u8; 32],
+    input_uri: String,
+    zk_proof: Vec<u8>,
+}
+
+/// `request_inference`; the fee is escrowed until the request is fulfilled.
+/// Providers fetch the input from `input_uri` and check it against `input_hash`.
+/// With `access_token` the requester spends their capability token for `model`
+/// instead of needing an ACL entry.
+#[allow(clippy::too_many_arguments)]
+pub fn request_inference(
+    program_id: &Pubkey,
+    requester: &Pubkey,
+    model: &Pubkey,
+    input_hash: [u8; 32],
+    input_uri: String,
+    zk_proof: Vec<u8>,
+    fee: Option<TokenFee>,
+    access_token: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*model, false),
+        AccountMeta::new(inference_request_pda(program_id, model, &input_hash), false),
+        AccountMeta::new(*requester, true),
+        match access_token {
+            true => AccountMeta::new(access_token_pda(program_id, model, requester), false),
+            false => absent(program_id),
+        },
+    ];
+    accounts.extend(optional_fee(fee.map(|f| f.accounts(program_id, requester, b"escrow")), program_id));
+    accounts.extend([AccountMeta::new_readonly(system_program::ID, false), not_paused(program_id)]);
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: data("request_inference", RequestInferenceArgs { input_hash, input_uri, zk_proof }),
+    }
+}
+
+#[derive(BorshSerialize)]
+struct ContributeDataArgs {
+    encrypted_data: Vec<u8>,
+    data_hash: [u8; 32],
+    records_root: [u8; 32],
+    record_count: u64,
+}
+
+/// `contribute_data`; `contribution` is a fresh keypair that must also sign.
+/// `records_root` commits to the `record_count` contributed records.
+#[allow(clippy::too_many_arguments)]
+pub fn contribute_data(
+    program_id: &Pubkey,
+    contributor: &Pubkey,
+    model: &Pubkey,
+    contribution: &Pubkey,
+    encrypted_data: Vec<u8>,
+    data_hash: [u8; 32],
+    records_root: [u8; 32],
+    record_count: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*model, false),
+            AccountMeta::new(*contribution, true),
+            AccountMeta::new(*contributor, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: data(
+            "contribute_data",
+            ContributeDataArgs { encrypted_data, data_hash, records_root, record_count },
+        ),
+    }
+}
+
+#[derive(BorshSerialize)]
+struct CastVoteArgs {
+    vote_choice: u8,
+    weight: u64,
+    on_behalf_of: [u8; 32],
+    proof: [u8; 64],
+}
+
+/// `cast_vote` on the DAO program. `proof` is the voter's Schnorr signature
+/// over the proposal key; a different `on_behalf_of` votes delegated weight.
+/// Either way the weight is capped by what `on_behalf_of`'s `governance_mint`
+/// ATA holds.
+#[allow(clippy::too_many_arguments)]
+pub fn cast_vote(
+    program_id: &Pubkey,
+    voter: &Pubkey,
+    proposal: &Pubkey,
+    on_behalf_of: &Pubkey,
+    governance_mint: &Pubkey,
+    vote_choice: u8,
+    weight: u64,
+    proof: [u8; 64],
+) -> Instruction {
+    let delegation = if on_behalf_of == voter {
+        absent(program_id)
+    } else {
+        AccountMeta::new_readonly(Pubkey::find_program_address(&[b"delegation", on_behalf_of.as_ref()], program_id).0, false)
+    };
+    let config = Pubkey::find_program_address(&[b"config"], program_id).0;
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*proposal, false),
+            AccountMeta::new(vote_record_pda(program_id, proposal, on_behalf_of), false),
+            AccountMeta::new(*voter, true),
+            delegation,
+            AccountMeta::new_readonly(associated_token_address(on_behalf_of, governance_mint), false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: data(
+            "cast_vote",
+            CastVoteArgs { vote_choice, weight, on_behalf_of: on_behalf_of.to_bytes(), proof },
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_encodes_anchor_args_and_optional_accounts() {
+        let (program_id, payer, admin) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = register_model(&program_id, &payer, &admin, [1; 32], [2; 32], 10, 20, None);
+
+        assert_eq!(ix.data[..8], discriminator("register_model"));
+        assert_eq!(ix.data.len(), 8 + 32 + 32 + 8 + 8);
+        assert_eq!(ix.data[72..80], 10u64.to_le_bytes());
+        assert_eq!(ix.accounts.len(), 11);
+        assert!(ix.accounts[4..8].iter().all(|a| a.pubkey == program_id && !a.is_writable));
+
+        let mint = Pubkey::new_unique();
+        let paid = register_model(&program_id, &payer, &admin, [1; 32], [2; 32], 10, 20, Some(TokenFee { mint }));
+        assert_eq!(paid.accounts[5].pubkey, associated_token_address(&payer, &mint));
+        assert_eq!(paid.accounts[7].pubkey, TOKEN_PROGRAM_ID);
+    }
+
+    #[test]
+    fn delegated_vote_passes_the_delegation_record() {
+        let (program_id, voter, owner, proposal) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let mint = Pubkey::new_unique();
+
+        let own = cast_vote(&program_id, &voter, &proposal, &voter, &mint, 1, 5, [0; 64]);
+        assert_eq!(own.accounts[3].pubkey, program_id);
+        assert_eq!(own.accounts[4].pubkey, associated_token_address(&voter, &mint));
+
+        let delegated = cast_vote(&program_id, &voter, &proposal, &owner, &mint, 1, 5, [0; 64]);
+        assert_eq!(delegated.accounts[1].pubkey, vote_record_pda(&program_id, &proposal, &owner));
+        assert_ne!(delegated.accounts[3].pubkey, program_id);
+        assert_eq!(delegated.accounts[4].pubkey, associated_token_address(&owner, &mint));
+        assert_eq!(delegated.data[17..49], owner.to_bytes());
+    }
+}
@@ -0,0 +1,17 @@
+// sdk/scoria-sdk/src/lib.rs
+
+//! Light client for dApps: builds registry and governance instructions,
+//! verifies inference proofs and computes the hashes the programs key on.
+//! Nothing here signs, sends or proves; pair it with a wallet adapter.
+//!
+//! Compiles for `wasm32-unknown-unknown`; `bindings` is the JS surface
+//! (`wasm-pack build sdk/scoria-sdk --target web`).
+
+pub mod bindings;
+pub mod hash;
+pub mod instructions;
+pub mod verify;
+
+pub use hash::{access_token_pda, model_pda, ModelHasher};
+pub use instructions::{contribute_data, register_model, request_inference, cast_vote, TokenFee};
+pub use verify::{verify_groth16, VerifyError};
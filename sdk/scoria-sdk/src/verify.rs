@@ -0,0 +1,197 @@
+// sdk/scoria-sdk/src/verify.rs
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use serde_json::Value;
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+/// Same classes as the CLI's `proof verify`, so dApps can report them alike
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("Malformed {what}: {reason}")]
+    Malformed { what: &'static str, reason: String },
+    #[error("Only Groth16 proofs can be verified in the light client, got {0}")]
+    UnsupportedProtocol(String),
+    #[error("Verifying key expects {expected} public inputs, got {actual}")]
+    PublicInputCount { expected: usize, actual: usize },
+    #[error("Proof is invalid for these public inputs")]
+    Invalid,
+}
+
+/// Verify a Groth16 proof over BN254.
+///
+/// `proof` and `vk` are snarkjs JSON or arkworks compressed bytes;
+/// `public_inputs` is snarkjs `public.json`. Proving stays in the CLI.
+pub fn verify_groth16(proof: &[u8], vk: &[u8], public_inputs: &[u8]) -> Result<(), VerifyError> {
+    let proof = parse_proof(proof)?;
+    let vk = parse_verifying_key(vk)?;
+    let public = parse_public_inputs(public_inputs)?;
+
+    if public.len() + 1 != vk.gamma_abc_g1.len() {
+        return Err(VerifyError::PublicInputCount {
+            expected: vk.gamma_abc_g1.len().saturating_sub(1),
+            actual: public.len(),
+        });
+    }
+    match Groth16::<Bn254>::verify_proof(&prepare_verifying_key(&vk), &proof, &public) {
+        Ok(true) => Ok(()),
+        _ => Err(VerifyError::Invalid),
+    }
+}
+
+pub fn parse_proof(bytes: &[u8]) -> Result<Proof<Bn254>, VerifyError> {
+    let Some(json) = parse_json(bytes, "proof")? else {
+        return Proof::deserialize_compressed(bytes).map_err(|e| malformed("proof", e));
+    };
+    check_protocol(&json, "proof")?;
+    Ok(Proof {
+        a: g1_json(&json["pi_a"], "proof")?,
+        b: g2_json(&json["pi_b"], "proof")?,
+        c: g1_json(&json["pi_c"], "proof")?,
+    })
+}
+
+pub fn parse_verifying_key(bytes: &[u8]) -> Result<VerifyingKey<Bn254>, VerifyError> {
+    let Some(json) = parse_json(bytes, "verifying key")? else {
+        return VerifyingKey::deserialize_compressed(bytes).map_err(|e| malformed("verifying key", e));
+    };
+    check_protocol(&json, "verifying key")?;
+    let ic = json["IC"].as_array().ok_or_else(|| malformed("verifying key", "`IC` is not an array"))?;
+    Ok(VerifyingKey {
+        alpha_g1: g1_json(&json["vk_alpha_1"], "verifying key")?,
+        beta_g2: g2_json(&json["vk_beta_2"], "verifying key")?,
+        gamma_g2: g2_json(&json["vk_gamma_2"], "verifying key")?,
+        delta_g2: g2_json(&json["vk_delta_2"], "verifying key")?,
+        gamma_abc_g1: ic.iter().map(|p| g1_json(p, "verifying key")).collect::<Result<_, _>>()?,
+    })
+}
+
+/// snarkjs `public.json`: an array of decimal strings
+pub fn parse_public_inputs(bytes: &[u8]) -> Result<Vec<Fr>, VerifyError> {
+    let json: Value = serde_json::from_slice(bytes).map_err(|e| malformed("public inputs", e))?;
+    json.as_array()
+        .ok_or_else(|| malformed("public inputs", "expected a JSON array"))?
+        .iter()
+        .map(|v| decimal(v, "public inputs"))
+        .collect()
+}
+
+fn malformed(what: &'static str, reason: impl ToString) -> VerifyError {
+    VerifyError::Malformed { what, reason: reason.to_string() }
+}
+
+/// `None` for binary (arkworks) encodings
+fn parse_json(bytes: &[u8], what: &'static str) -> Result<Option<Value>, VerifyError> {
+    match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'{') => serde_json::from_slice(bytes).map(Some).map_err(|e| malformed(what, e)),
+        _ => Ok(None),
+    }
+}
+
+fn check_protocol(json: &Value, what: &'static str) -> Result<(), VerifyError> {
+    if let Some(curve) = json["curve"].as_str() {
+        if curve != "bn128" && curve != "bn254" {
+            return Err(malformed(what, format!("unsupported curve `{curve}`")));
+        }
+    }
+    match json["protocol"].as_str() {
+        Some("groth16") => Ok(()),
+        Some(other) => Err(VerifyError::UnsupportedProtocol(other.to_string())),
+        None => Err(malformed(what, "missing `protocol`")),
+    }
+}
+
+fn decimal<F: FromStr + Display>(value: &Value, what: &'static str) -> Result<F, VerifyError> {
+    // `F::from_str` reduces out-of-range values; only canonical encodings round-trip
+    value
+        .as_str()
+        .and_then(|s| F::from_str(s).ok().filter(|f| f.to_string() == s.trim_start_matches('0')))
+        .ok_or_else(|| malformed(what, format!("`{value}` is not a decimal field element")))
+}
+
+/// Projective `[x, y, z]` with `z` either 1 or 0 (infinity)
+fn g1_json(value: &Value, what: &'static str) -> Result<G1Affine, VerifyError> {
+    let xyz = value.as_array().filter(|a| a.len() == 3).ok_or_else(|| malformed(what, "G1 point is not [x, y, z]"))?;
+    let z: Fq = decimal(&xyz[2], what)?;
+    if z == Fq::from(0u64) {
+        return Ok(G1Affine::zero());
+    }
+    if z != Fq::from(1u64) {
+        return Err(malformed(what, "G1 point is not normalized"));
+    }
+    let point = G1Affine::new_unchecked(decimal(&xyz[0], what)?, decimal(&xyz[1], what)?);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+        .then_some(point)
+        .ok_or_else(|| malformed(what, "G1 point is not on the curve"))
+}
+
+/// `[[x.c0, x.c1], [y.c0, y.c1], [z.c0, z.c1]]`
+fn g2_json(value: &Value, what: &'static str) -> Result<G2Affine, VerifyError> {
+    let fq2 = |v: &Value| -> Result<Fq2, VerifyError> {
+        let c = v.as_array().filter(|a| a.len() == 2).ok_or_else(|| malformed(what, "G2 coordinate is not [c0, c1]"))?;
+        Ok(Fq2::new(decimal(&c[0], what)?, decimal(&c[1], what)?))
+    };
+    let xyz = value.as_array().filter(|a| a.len() == 3).ok_or_else(|| malformed(what, "G2 point is not [x, y, z]"))?;
+    let z = fq2(&xyz[2])?;
+    if z == Fq2::from(0u64) {
+        return Ok(G2Affine::zero());
+    }
+    if z != Fq2::from(1u64) {
+        return Err(malformed(what, "G2 point is not normalized"));
+    }
+    let point = G2Affine::new_unchecked(fq2(&xyz[0])?, fq2(&xyz[1])?);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+        .then_some(point)
+        .ok_or_else(|| malformed(what, "G2 point is not on the curve"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError};
+    use ark_serialize::CanonicalSerialize;
+    use ark_snark::SNARK;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    /// `x * y == z` with `z` public
+    #[derive(Clone)]
+    struct Product(u64, u64);
+
+    impl ConstraintSynthesizer<Fr> for Product {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let x = cs.new_witness_variable(|| Ok(Fr::from(self.0)))?;
+            let y = cs.new_witness_variable(|| Ok(Fr::from(self.1)))?;
+            let z = cs.new_input_variable(|| Ok(Fr::from(self.0 * self.1)))?;
+            cs.enforce_constraint(
+                LinearCombination::from(x),
+                LinearCombination::from(y),
+                LinearCombination::from(z),
+            )
+        }
+    }
+
+    fn compressed(value: &impl CanonicalSerialize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        value.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn verifies_compressed_groth16_and_rejects_wrong_inputs() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(Product(3, 7), &mut rng).unwrap();
+        let proof = Groth16::<Bn254>::prove(&pk, Product(3, 7), &mut rng).unwrap();
+        let (proof, vk) = (compressed(&proof), compressed(&vk));
+
+        assert!(verify_groth16(&proof, &vk, br#"["21"]"#).is_ok());
+        assert!(matches!(verify_groth16(&proof, &vk, br#"["22"]"#), Err(VerifyError::Invalid)));
+        assert!(matches!(verify_groth16(&proof, &vk, br#"[]"#), Err(VerifyError::PublicInputCount { .. })));
+        assert!(matches!(
+            verify_groth16(br#"{"protocol": "plonk"}"#, &vk, br#"["21"]"#),
+            Err(VerifyError::UnsupportedProtocol(_))
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+// tests/e2e/src/cli.rs
+
+use crate::{path_from_env, repo_root, Validator};
+use anyhow::Context;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{write_keypair_file, Keypair, Signer},
+};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+use tokio::process::Command;
+
+fn binary() -> PathBuf {
+    path_from_env("SCORIA_CLI", "client/target/debug/scoria-client")
+}
+
+/// `scoria-cli` with a fresh wallet and a config pointing at the stack
+pub struct Cli {
+    config: PathBuf,
+    wallet: Keypair,
+}
+
+impl Cli {
+    pub fn new(workdir: &Path, validator: &Validator, ipfs_gateway: &str) -> anyhow::Result<Self> {
+        let wallet = Keypair::new();
+        let wallet_path = workdir.join("wallet.json");
+        write_keypair_file(&wallet, &wallet_path).map_err(|e| anyhow::anyhow!("writing wallet: {e}"))?;
+
+        let circuits = std::env::var("SCORIA_CIRCUIT_INDEX")
+            .unwrap_or_else(|_| format!("file://{}", repo_root().join("circuits/index.json").display()));
+        let config = workdir.join("client.toml");
+        std::fs::write(
+            &config,
+            format!(
+                r#"[network]
+rpc_url = "{rpc}"
+
+[wallet]
+path = "{wallet}"
+
+[security]
+encryption_key = "scoria-e2e"
+
+[paths]
+model_cache = "{work}/models"
+audit_logs = "{work}/audit"
+
+[zkp.registry]
+index_uri = "{circuits}"
+ipfs_gateway = "{ipfs_gateway}"
+cache_dir = "{work}/circuits"
+"#,
+                rpc = validator.rpc_url,
+                wallet = wallet_path.display(),
+                work = workdir.display(),
+            ),
+        )?;
+        Ok(Self { config, wallet })
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.wallet.pubkey()
+    }
+
+    /// Run a subcommand, failing with its stderr unless it exits successfully
+    pub async fn run<I, S>(&self, args: I) -> anyhow::Result<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let output = Command::new(binary())
+            .arg("--config")
+            .arg(&self.config)
+            .args(args)
+            .output()
+            .await
+            .with_context(|| format!("running {}; build the client or set SCORIA_CLI", binary().display()))?;
+        anyhow::ensure!(
+            output.status.success(),
+            "scoria-cli failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
@@ -0,0 +1,121 @@
+// tests/e2e/src/indexer.rs
+
+use crate::{path_from_env, program_id, repo_root, wait_for, Validator, INDEX_TIMEOUT};
+use anyhow::Context;
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+use tokio::process::{Child, Command};
+use tokio_postgres::{types::ToSql, NoTls, Row};
+
+fn binary() -> PathBuf {
+    path_from_env("SCORIA_INDEXER", "indexer/target/debug/scoria-indexer")
+}
+
+/// The indexer process, migrated and listening to the validator
+pub struct Indexer {
+    pub api_url: String,
+    _process: Child,
+}
+
+impl Indexer {
+    pub async fn start(
+        workdir: &Path,
+        validator: &Validator,
+        database_url: &str,
+        kafka_brokers: &str,
+    ) -> anyhow::Result<Self> {
+        let health_port = portpicker::pick_unused_port().context("no free port for the health server")?;
+        let idl = path_from_env("SCORIA_IDL_DIR", "target/idl");
+        let config = workdir.join("indexer.toml");
+        std::fs::write(
+            &config,
+            format!(
+                r#"[database]
+url = "{database_url}"
+
+[solana]
+rpc_endpoint = "{rpc}"
+
+[listener]
+ws_endpoint = "{ws}"
+program_id = "{registry}"
+governance_program_id = "{dao}"
+kafka_topic = "scoria-e2e-events"
+registry_idl = "{registry_idl}"
+governance_idl = "{dao_idl}"
+
+[kafka]
+brokers = "{kafka_brokers}"
+
+[sink]
+type = "kafka"
+
+[outbox]
+poll_interval_ms = 100
+transactional_id = "scoria-e2e"
+
+[monitoring]
+health_check_port = {health_port}
+"#,
+                rpc = validator.rpc_url,
+                ws = validator.ws_url,
+                registry = program_id("model_registry")?,
+                dao = program_id("dao")?,
+                registry_idl = idl.join("model_registry.json").display(),
+                dao_idl = idl.join("dao.json").display(),
+            ),
+        )?;
+
+        let migrations = repo_root().join("indexer/migrations");
+        let status = Command::new(binary())
+            .arg("--config")
+            .arg(&config)
+            .args(["migrate", "up", "--dir"])
+            .arg(&migrations)
+            .status()
+            .await
+            .with_context(|| format!("running {}; build the indexer or set SCORIA_INDEXER", binary().display()))?;
+        anyhow::ensure!(status.success(), "indexer migrations failed: {status}");
+
+        let process = Command::new(binary())
+            .arg("--config")
+            .arg(&config)
+            .arg("run")
+            .env("RUST_LOG", std::env::var("RUST_LOG").unwrap_or("info".into()))
+            .stdout(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let api_url = format!("http://127.0.0.1:{health_port}");
+        wait_for("indexer health server", Duration::from_secs(30), || async {
+            Ok(tokio::net::TcpStream::connect(("127.0.0.1", health_port)).await.ok())
+        })
+        .await?;
+        Ok(Self { api_url, _process: process })
+    }
+}
+
+/// Read side of the indexer database
+pub struct Db {
+    client: tokio_postgres::Client,
+}
+
+impl Db {
+    pub async fn connect(database_url: &str) -> anyhow::Result<Self> {
+        let (client, connection) = tokio_postgres::connect(database_url, NoTls).await?;
+        tokio::spawn(connection);
+        Ok(Self { client })
+    }
+
+    pub async fn query(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> anyhow::Result<Vec<Row>> {
+        Ok(self.client.query(sql, params).await?)
+    }
+
+    /// First row of `sql` once the indexer has written it
+    pub async fn wait_for_row(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> anyhow::Result<Row> {
+        wait_for(sql, INDEX_TIMEOUT, || async { Ok(self.query(sql, params).await?.into_iter().next()) }).await
+    }
+}
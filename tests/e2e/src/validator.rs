@@ -0,0 +1,88 @@
+// tests/e2e/src/validator.rs
+
+use crate::{path_from_env, wait_for};
+use anyhow::Context;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+use std::{path::PathBuf, process::Stdio, time::Duration};
+use tempfile::TempDir;
+use tokio::process::{Child, Command};
+
+/// Anchor programs loaded at genesis, by `anchor build` artifact name
+pub const PROGRAMS: [&str; 3] = ["model_registry", "federation", "dao"];
+
+fn deploy_dir() -> PathBuf {
+    path_from_env("SCORIA_PROGRAMS_DIR", "target/deploy")
+}
+
+/// Address a program is deployed at, from the keypair `anchor build` writes next to it
+pub fn program_id(program: &str) -> anyhow::Result<Pubkey> {
+    let keypair = deploy_dir().join(format!("{program}-keypair.json"));
+    read_keypair_file(&keypair)
+        .map(|k| k.pubkey())
+        .map_err(|e| anyhow::anyhow!("{}: {e}; run `anchor build` first", keypair.display()))
+}
+
+pub struct Validator {
+    pub rpc_url: String,
+    pub ws_url: String,
+    _process: Child,
+    _ledger: TempDir,
+}
+
+impl Validator {
+    pub async fn start() -> anyhow::Result<Self> {
+        let ledger = TempDir::new()?;
+        let rpc_port = portpicker::pick_unused_port().context("no free port for RPC")?;
+        let faucet_port = portpicker::pick_unused_port().context("no free port for the faucet")?;
+
+        let mut command = Command::new(std::env::var("SOLANA_TEST_VALIDATOR").unwrap_or("solana-test-validator".into()));
+        command
+            .arg("--ledger")
+            .arg(ledger.path())
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &faucet_port.to_string()])
+            .args(["--reset", "--quiet"]);
+        for program in PROGRAMS {
+            let so = deploy_dir().join(format!("{program}.so"));
+            anyhow::ensure!(so.exists(), "{} is missing; run `anchor build` first", so.display());
+            command.arg("--bpf-program").arg(program_id(program)?.to_string()).arg(so);
+        }
+        let process = command
+            .stdout(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .context("solana-test-validator must be on PATH or set in SOLANA_TEST_VALIDATOR")?;
+
+        // The websocket listens on the port after RPC
+        let validator = Self {
+            rpc_url: format!("http://127.0.0.1:{rpc_port}"),
+            ws_url: format!("ws://127.0.0.1:{}", rpc_port + 1),
+            _process: process,
+            _ledger: ledger,
+        };
+        let rpc = validator.rpc();
+        wait_for("validator health", Duration::from_secs(60), || async {
+            Ok(rpc.get_health().await.ok())
+        })
+        .await?;
+        Ok(validator)
+    }
+
+    pub fn rpc(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.rpc_url.clone(), CommitmentConfig::confirmed())
+    }
+
+    pub async fn fund(&self, pubkey: &Pubkey, lamports: u64) -> anyhow::Result<()> {
+        let rpc = self.rpc();
+        rpc.request_airdrop(pubkey, lamports).await?;
+        wait_for("airdrop", Duration::from_secs(30), || async {
+            Ok((rpc.get_balance(pubkey).await? >= lamports).then_some(()))
+        })
+        .await
+    }
+}
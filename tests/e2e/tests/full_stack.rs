@@ -0,0 +1,74 @@
+// tests/e2e/tests/full_stack.rs
+
+use scoria_e2e::{fixture, program_id, Stack};
+use solana_sdk::pubkey::Pubkey;
+use testcontainers::clients;
+
+fn arg(path: std::path::PathBuf) -> String {
+    path.display().to_string()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "needs Docker, solana-test-validator and `anchor build` output"]
+async fn test_deploy_infer_contribute_vote() -> anyhow::Result<()> {
+    let docker = clients::Cli::default();
+    let stack = Stack::start(&docker).await?;
+    let owner = stack.cli.pubkey().to_string();
+    let scratch = tempfile::tempdir()?;
+
+    // Deploy; the model account is keyed by the plaintext hash
+    let model_path = fixture("relu.onnx");
+    let deployed: serde_json::Value =
+        serde_json::from_str(&stack.cli.run(["--output", "json", "deploy", &arg(model_path.clone()), "onnx"]).await?)?;
+    let model_hash = blake3::hash(&std::fs::read(&model_path)?);
+    let model = Pubkey::find_program_address(&[b"model", model_hash.as_bytes()], &program_id("model_registry")?)
+        .0
+        .to_string();
+    assert_eq!(deployed["model"], model.as_str());
+    assert_eq!(deployed["model_hash"], model_hash.to_hex().as_str());
+    let row = stack.db.wait_for_row("SELECT owner FROM models WHERE id = $1", &[&model]).await?;
+    assert_eq!(row.get::<_, String>("owner"), owner);
+
+    // Infer against the deployed model; the CLI checks the proof before writing
+    let output = scratch.path().join("output.json");
+    stack
+        .cli
+        .run(["infer", &model, &arg(fixture("input.json")), &arg(output.clone())])
+        .await?;
+    assert!(!std::fs::read(&output)?.is_empty());
+
+    // Contribute; the leaderboard is projected from the Kafka topic
+    stack
+        .cli
+        .run(["contribute", &arg(fixture("contribution.csv")), &model, "--dp-epsilon", "3.0"])
+        .await?;
+    let row = stack
+        .db
+        .wait_for_row(
+            "SELECT contributions FROM contributor_leaderboard WHERE model_id = $1 AND contributor = $2",
+            &[&model, &owner],
+        )
+        .await?;
+    assert_eq!(row.get::<_, i64>("contributions"), 1);
+
+    // Governance; the new proposal's address is read back from the indexer
+    stack
+        .cli
+        .run(["governance", "create-proposal", &arg(fixture("proposal.json")), "1"])
+        .await?;
+    let proposal: String = stack
+        .db
+        .wait_for_row("SELECT proposal_id FROM governance_proposals WHERE author = $1", &[&owner])
+        .await?
+        .get("proposal_id");
+    stack.cli.run(["governance", "vote", &proposal, "0", "1"]).await?;
+    let row = stack
+        .db
+        .wait_for_row("SELECT votes FROM governance_proposals WHERE proposal_id = $1 AND votes > 0", &[&proposal])
+        .await?;
+    assert_eq!(row.get::<_, i64>("votes"), 1);
+    let row = stack.db.wait_for_row("SELECT votes FROM governance_voters WHERE voter = $1", &[&owner]).await?;
+    assert_eq!(row.get::<_, i64>("votes"), 1);
+
+    Ok(())
+}